@@ -0,0 +1,182 @@
+//! Folder watch triggers
+//!
+//! Watched folders are listed on every scheduler tick rather than through OS file
+//! events. Each automation keeps the names of the files it has seen as its trigger
+//! state, and every name that shows up later starts a run. The first listing after an
+//! automation is created or edited only records what is already there. Hidden files,
+//! unfinished downloads and editor lock files are ignored.
+
+use super::enqueue_run;
+use crate::commands::AppState;
+use crate::models::{AutomationEvent, AutomationTrigger};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Files modified more recently may still be being written
+const SETTLE_TIME: Duration = Duration::from_secs(10);
+/// Runs started per folder and tick; the rest wait for the next tick
+const MAX_NEW_FILES_PER_CHECK: usize = 20;
+/// Extensions of downloads in progress; the finished file appears under its own name
+const PARTIAL_EXTENSIONS: &[&str] = &["crdownload", "part", "partial", "download", "tmp"];
+
+pub(super) async fn check_folders(state: &AppState) -> anyhow::Result<()> {
+    let automations = state
+        .db
+        .list_enabled_automations_by_trigger("folder_watch")
+        .await?;
+    for automation in automations {
+        let AutomationTrigger::FolderWatch { path } = &automation.trigger else {
+            continue;
+        };
+        let folder = Path::new(path);
+        let listing = match list_files(folder).await {
+            Ok(listing) => listing,
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️ [automations] Can't list {} for \"{}\": {}",
+                    path,
+                    automation.name,
+                    e
+                );
+                continue;
+            }
+        };
+        let seen: Option<Vec<String>> = automation
+            .trigger_state
+            .as_deref()
+            .and_then(|state| serde_json::from_str(state).ok());
+        let (added, now_seen) = diff_listing(seen.as_deref(), &listing, SystemTime::now());
+
+        if seen.as_ref() != Some(&now_seen) {
+            state
+                .db
+                .set_automation_trigger_state(&automation.id, &serde_json::to_string(&now_seen)?)
+                .await?;
+        }
+        for name in added {
+            let file = folder.join(&name);
+            tracing::info!(
+                "📂 [automations] New file {} for \"{}\"",
+                file.display(),
+                automation.name
+            );
+            let event = AutomationEvent::FileAdded {
+                path: file.to_string_lossy().to_string(),
+            };
+            enqueue_run(state, &automation.id, event).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Files directly in a folder, with their modification times
+async fn list_files(folder: &Path) -> std::io::Result<Vec<(String, SystemTime)>> {
+    let mut entries = tokio::fs::read_dir(folder).await?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_ignored(&name) {
+            continue;
+        }
+        let metadata = entry.metadata().await?;
+        if metadata.is_file() {
+            files.push((name, metadata.modified()?));
+        }
+    }
+    Ok(files)
+}
+
+fn is_ignored(name: &str) -> bool {
+    // "~$report.docx" is Office's lock file for report.docx
+    if name.starts_with('.') || name.starts_with("~$") {
+        return true;
+    }
+    Path::new(name).extension().is_some_and(|extension| {
+        PARTIAL_EXTENSIONS.contains(&extension.to_string_lossy().to_lowercase().as_str())
+    })
+}
+
+/// New files in a listing, and the names to remember as seen. Files still settling
+/// are only reported once they have settled; without a previous listing nothing is new.
+fn diff_listing(
+    seen: Option<&[String]>,
+    listing: &[(String, SystemTime)],
+    now: SystemTime,
+) -> (Vec<String>, Vec<String>) {
+    // A modification time in the future counts as settled
+    let settled = |modified: &SystemTime| {
+        now.duration_since(*modified)
+            .map_or(true, |age| age >= SETTLE_TIME)
+    };
+    let Some(seen) = seen else {
+        let mut names: Vec<String> = listing
+            .iter()
+            .filter(|(_, modified)| settled(modified))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        return (Vec::new(), names);
+    };
+
+    let seen: HashSet<&str> = seen.iter().map(String::as_str).collect();
+    let mut added: Vec<String> = listing
+        .iter()
+        .filter(|(name, modified)| !seen.contains(name.as_str()) && settled(modified))
+        .map(|(name, _)| name.clone())
+        .collect();
+    added.sort();
+    added.truncate(MAX_NEW_FILES_PER_CHECK);
+
+    // Deleted files are forgotten, so a file added again later counts as new
+    let mut now_seen: Vec<String> = listing
+        .iter()
+        .map(|(name, _)| name)
+        .filter(|name| seen.contains(name.as_str()) || added.contains(name))
+        .cloned()
+        .collect();
+    now_seen.sort();
+    (added, now_seen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_listing() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let old = now - Duration::from_secs(60);
+        let fresh = now - Duration::from_secs(2);
+        let listing = vec![
+            ("report.pdf".to_string(), old),
+            ("notes.txt".to_string(), old),
+            ("copying.zip".to_string(), fresh),
+        ];
+
+        // The first listing is the baseline
+        let (added, seen) = diff_listing(None, &listing, now);
+        assert!(added.is_empty());
+        assert_eq!(seen, vec!["notes.txt", "report.pdf"]);
+
+        // gone.txt was deleted and notes.txt is new; copying.zip is still being written
+        let previous = vec!["gone.txt".to_string(), "report.pdf".to_string()];
+        let (added, seen) = diff_listing(Some(&previous), &listing, now);
+        assert_eq!(added, vec!["notes.txt"]);
+        assert_eq!(seen, vec!["notes.txt", "report.pdf"]);
+
+        let later = now + SETTLE_TIME;
+        let (added, _) = diff_listing(Some(&seen), &listing, later);
+        assert_eq!(added, vec!["copying.zip"]);
+    }
+
+    #[test]
+    fn test_is_ignored() {
+        assert!(is_ignored(".DS_Store"));
+        assert!(is_ignored("~$minutes.docx"));
+        assert!(is_ignored("report.pdf.crdownload"));
+        assert!(is_ignored("video.MP4.part"));
+        assert!(!is_ignored("report.pdf"));
+        assert!(!is_ignored("README"));
+    }
+}
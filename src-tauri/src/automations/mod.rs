@@ -0,0 +1,185 @@
+//! Automations
+//!
+//! An automation is a rule: a trigger (a daily time, a file appearing in a folder, a
+//! webhook request or a conversation being archived) and the action it runs (post a
+//! prompt into a conversation, export a conversation or call an MCP tool). Triggers
+//! enqueue an `automation_run` job carrying the [`AutomationEvent`]; the job runs the
+//! action and records it in the automation's run history (see
+//! `commands::chat::automation`).
+//!
+//! The scheduler wakes every [`TICK_INTERVAL`] to start due schedules and list watched
+//! folders ([`folder`]); [`webhook`] serves hook requests while a webhook automation is
+//! enabled. A schedule missed while the app was closed runs once at launch.
+
+mod folder;
+mod webhook;
+
+pub use webhook::webhook_url;
+pub(crate) use webhook::{TOKEN_LEN as WEBHOOK_TOKEN_LEN, sync_server as sync_webhook_server};
+
+use crate::commands::AppState;
+use crate::jobs::AutomationRunPayload;
+use crate::models::{
+    AutomationEvent, AutomationTrigger, JOB_PRIORITY_NORMAL, Job, JobKind, parse_run_time,
+};
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
+use std::time::Duration;
+use tauri::Manager;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Next time after `after` that the local clock shows `run_at` ("HH:MM")
+pub fn next_run_at(run_at: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let time = parse_run_time(run_at)?;
+    next_occurrence(time, &after.with_timezone(&Local)).map(|next| next.with_timezone(&Utc))
+}
+
+fn next_occurrence<Tz: TimeZone>(time: NaiveTime, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let timezone = after.timezone();
+    let mut date = after.date_naive();
+    // Today, tomorrow, and the day after in case tomorrow's time falls into a DST gap
+    for _ in 0..3 {
+        let local = date.and_time(time);
+        // A time skipped by a DST change runs an hour later
+        let candidate = timezone.from_local_datetime(&local).earliest().or_else(|| {
+            timezone
+                .from_local_datetime(&(local + chrono::Duration::hours(1)))
+                .earliest()
+        });
+        if let Some(candidate) = candidate
+            && candidate > *after
+        {
+            return Some(candidate);
+        }
+        date = date.succ_opt()?;
+    }
+    None
+}
+
+/// Next scheduled run for a trigger, or none unless it is an enabled schedule
+pub fn scheduled_run(trigger: &AutomationTrigger, is_enabled: bool) -> Option<DateTime<Utc>> {
+    match trigger {
+        AutomationTrigger::Schedule { run_at } if is_enabled => next_run_at(run_at, Utc::now()),
+        _ => None,
+    }
+}
+
+/// Enqueue a run of an automation
+pub(crate) async fn enqueue_run(
+    state: &AppState,
+    automation_id: &str,
+    event: AutomationEvent,
+) -> anyhow::Result<Job> {
+    state
+        .job_queue
+        .enqueue(
+            JobKind::AutomationRun,
+            &AutomationRunPayload {
+                automation_id: automation_id.to_string(),
+                event,
+            },
+            JOB_PRIORITY_NORMAL,
+        )
+        .await
+}
+
+/// Run the automations triggered by archiving a conversation. Failures are logged;
+/// archiving itself has already succeeded.
+pub(crate) async fn on_conversation_archived(state: &AppState, conversation_id: &str) {
+    let automations = match state
+        .db
+        .list_enabled_automations_by_trigger("conversation_archived")
+        .await
+    {
+        Ok(automations) => automations,
+        Err(e) => {
+            tracing::error!("❌ [automations] Failed to list archive automations: {}", e);
+            return;
+        }
+    };
+    for automation in automations {
+        let event = AutomationEvent::ConversationArchived {
+            conversation_id: conversation_id.to_string(),
+        };
+        if let Err(e) = enqueue_run(state, &automation.id, event).await {
+            tracing::error!(
+                "❌ [automations] Failed to enqueue \"{}\": {}",
+                automation.name,
+                e
+            );
+        }
+    }
+}
+
+/// Check schedules and watched folders until the app exits, serving webhooks while
+/// they are needed
+pub async fn start(app: tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    loop {
+        // Also retries a port that was taken
+        webhook::sync_server(&state).await;
+        if let Err(e) = enqueue_due(&state).await {
+            tracing::error!(
+                "❌ [automations] Failed to check for due automations: {}",
+                e
+            );
+        }
+        if let Err(e) = folder::check_folders(&state).await {
+            tracing::error!("❌ [automations] Failed to check watched folders: {}", e);
+        }
+        tokio::time::sleep(TICK_INTERVAL).await;
+    }
+}
+
+async fn enqueue_due(state: &AppState) -> anyhow::Result<()> {
+    let now = Utc::now();
+    for automation in state.db.list_due_automations(now).await? {
+        // Scheduled before enqueueing, so a failed enqueue skips a day instead of
+        // retrying every tick
+        let next = match &automation.trigger {
+            AutomationTrigger::Schedule { run_at } => next_run_at(run_at, now),
+            _ => None,
+        };
+        state
+            .db
+            .set_automation_next_run(&automation.id, next)
+            .await?;
+        tracing::info!(
+            "⏰ [automations] Running \"{}\", next run at {}",
+            automation.name,
+            next.map(|t| t.to_rfc3339())
+                .unwrap_or_else(|| "never".to_string())
+        );
+        enqueue_run(state, &automation.id, AutomationEvent::Schedule).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_next_occurrence() {
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let after = offset.with_ymd_and_hms(2025, 6, 10, 8, 0, 0).unwrap();
+
+        let later_today = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        assert_eq!(
+            next_occurrence(later_today, &after).unwrap().to_rfc3339(),
+            "2025-06-10T09:00:00+02:00"
+        );
+        let earlier = NaiveTime::from_hms_opt(7, 30, 0).unwrap();
+        assert_eq!(
+            next_occurrence(earlier, &after).unwrap().to_rfc3339(),
+            "2025-06-11T07:30:00+02:00"
+        );
+        // Exactly now has already run
+        let now = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        assert_eq!(
+            next_occurrence(now, &after).unwrap().to_rfc3339(),
+            "2025-06-11T08:00:00+02:00"
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Webhook triggers
+//!
+//! A small HTTP server on 127.0.0.1 accepts `POST /hooks/<token>`. The token picks the
+//! automation and the request body is passed to its action, so other programs on this
+//! machine can start automations. The server only runs while an enabled webhook
+//! automation exists, and tokens are 128 random bits generated by the app. The port
+//! stays the same across launches so hook URLs keep working: [`DEFAULT_PORT`] unless
+//! the `automation_webhook_port` setting says otherwise.
+
+use super::enqueue_run;
+use crate::commands::AppState;
+use crate::models::{AutomationEvent, AutomationTrigger};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+const WEBHOOK_PORT_KEY: &str = "automation_webhook_port";
+const DEFAULT_PORT: u16 = 17_860;
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 256 * 1024;
+/// Slow clients are dropped rather than holding a task forever
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Length of a URL-safe base64 token of 128 bits; shorter tokens never match
+pub(crate) const TOKEN_LEN: usize = 22;
+
+struct Server {
+    port: u16,
+    task: JoinHandle<()>,
+}
+
+/// The running server, if any
+static SERVER: Mutex<Option<Server>> = Mutex::const_new(None);
+
+/// URL that triggers the webhook with `token`, while the server is running
+pub async fn webhook_url(token: &str) -> Option<String> {
+    SERVER
+        .lock()
+        .await
+        .as_ref()
+        .map(|server| format!("http://127.0.0.1:{}/hooks/{}", server.port, token))
+}
+
+/// Start the server while an enabled webhook automation exists and stop it otherwise
+pub(crate) async fn sync_server(state: &AppState) {
+    let needed = match state
+        .db
+        .list_enabled_automations_by_trigger("webhook")
+        .await
+    {
+        Ok(automations) => !automations.is_empty(),
+        Err(e) => {
+            tracing::error!("❌ [automations] Failed to list webhook automations: {}", e);
+            return;
+        }
+    };
+    let mut server = SERVER.lock().await;
+    if needed && server.is_none() {
+        *server = start_server(state).await;
+    } else if !needed && let Some(running) = server.take() {
+        running.task.abort();
+        tracing::info!("🪝 [automations] Webhook server stopped");
+    }
+}
+
+async fn start_server(state: &AppState) -> Option<Server> {
+    let port = state
+        .db
+        .get_setting(WEBHOOK_PORT_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|port| port.trim().parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!(
+                "⚠️ [automations] Webhook server can't listen on port {}: {}",
+                port,
+                e
+            );
+            return None;
+        }
+    };
+    tracing::info!(
+        "🪝 [automations] Webhook server listening on 127.0.0.1:{}",
+        port
+    );
+
+    let state = state.clone();
+    let task = tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::debug!("[automations] Webhook accept failed: {}", e);
+                    continue;
+                }
+            };
+            let state = state.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(&state, stream).await {
+                    tracing::debug!("[automations] Webhook request failed: {}", e);
+                }
+            });
+        }
+    });
+    Some(Server { port, task })
+}
+
+struct Response {
+    status: u16,
+    reason: &'static str,
+    body: &'static str,
+}
+
+const ACCEPTED: Response = Response {
+    status: 202,
+    reason: "Accepted",
+    body: "Automation queued",
+};
+const NOT_FOUND: Response = Response {
+    status: 404,
+    reason: "Not Found",
+    body: "No enabled automation for this hook",
+};
+const METHOD_NOT_ALLOWED: Response = Response {
+    status: 405,
+    reason: "Method Not Allowed",
+    body: "Use POST",
+};
+const BAD_REQUEST: Response = Response {
+    status: 400,
+    reason: "Bad Request",
+    body: "Malformed request",
+};
+const TOO_LARGE: Response = Response {
+    status: 413,
+    reason: "Payload Too Large",
+    body: "Request body too large",
+};
+const SERVER_ERROR: Response = Response {
+    status: 500,
+    reason: "Internal Server Error",
+    body: "Automation could not be queued",
+};
+
+async fn handle_connection(state: &AppState, mut stream: TcpStream) -> anyhow::Result<()> {
+    let response = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => trigger(state, request).await.unwrap_or_else(|e| {
+            tracing::error!("❌ [automations] Failed to handle webhook: {}", e);
+            SERVER_ERROR
+        }),
+        Ok(Err(response)) => response,
+        Err(_) => return Ok(()),
+    };
+    let reply = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason,
+        response.body.len(),
+        response.body
+    );
+    stream.write_all(reply.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let head_end = loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(BAD_REQUEST);
+        }
+        let n = stream.read(&mut chunk).await.map_err(|_| BAD_REQUEST)?;
+        if n == 0 {
+            return Err(BAD_REQUEST);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]);
+    let (method, path, content_length) = parse_head(&head).ok_or(BAD_REQUEST)?;
+    if content_length > MAX_BODY_BYTES {
+        return Err(TOO_LARGE);
+    }
+    let mut body = buf.split_off(head_end + 4);
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await.map_err(|_| BAD_REQUEST)?;
+        if n == 0 {
+            return Err(BAD_REQUEST);
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+    Ok(Request { method, path, body })
+}
+
+/// Method, path without the query, and Content-Length (0 when absent)
+fn parse_head(head: &str) -> Option<(String, String, usize)> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut content_length = 0;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().ok()?;
+        }
+    }
+    Some((method, path, content_length))
+}
+
+async fn trigger(state: &AppState, request: Request) -> anyhow::Result<Response> {
+    let Some(token) = request
+        .path
+        .strip_prefix("/hooks/")
+        .filter(|token| !token.is_empty())
+    else {
+        return Ok(NOT_FOUND);
+    };
+    if request.method != "POST" {
+        return Ok(METHOD_NOT_ALLOWED);
+    }
+
+    let automations = state
+        .db
+        .list_enabled_automations_by_trigger("webhook")
+        .await?;
+    let Some(automation) = automations.iter().find(|automation| {
+        matches!(&automation.trigger, AutomationTrigger::Webhook { token: t } if tokens_match(t, token))
+    }) else {
+        return Ok(NOT_FOUND);
+    };
+
+    tracing::info!(
+        "🪝 [automations] Webhook for \"{}\" ({} byte(s))",
+        automation.name,
+        request.body.len()
+    );
+    let event = AutomationEvent::Webhook {
+        body: String::from_utf8_lossy(&request.body).to_string(),
+    };
+    enqueue_run(state, &automation.id, event).await?;
+    Ok(ACCEPTED)
+}
+
+/// Compare in constant time, so response timing doesn't reveal how much of a token
+/// matched. Tokens shorter than [`TOKEN_LEN`] (chosen by hand before tokens were
+/// always generated) never match.
+fn tokens_match(expected: &str, given: &str) -> bool {
+    if expected.len() < TOKEN_LEN || expected.len() != given.len() {
+        return false;
+    }
+    expected
+        .bytes()
+        .zip(given.bytes())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_head() {
+        let head = "POST /hooks/abc123?source=ci HTTP/1.1\r\nHost: 127.0.0.1\r\ncontent-length: 17";
+        assert_eq!(
+            parse_head(head),
+            Some(("POST".to_string(), "/hooks/abc123".to_string(), 17))
+        );
+        assert_eq!(
+            parse_head("GET /hooks/abc HTTP/1.1"),
+            Some(("GET".to_string(), "/hooks/abc".to_string(), 0))
+        );
+        assert_eq!(
+            parse_head("POST /hooks/abc HTTP/1.1\r\nContent-Length: lots"),
+            None
+        );
+        assert_eq!(parse_head(""), None);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        let token = "q2Xv8nKp0LmZ4tR7yB1cWg";
+        assert!(tokens_match(token, token));
+        assert!(!tokens_match(token, "q2Xv8nKp0LmZ4tR7yB1cWh"));
+        assert!(!tokens_match(token, "q2Xv8nKp0LmZ4tR7yB1cW"));
+        assert!(!tokens_match("short", "short"));
+    }
+}
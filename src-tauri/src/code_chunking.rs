@@ -0,0 +1,407 @@
+//! Syntax-aware condensing of large code attachments
+//!
+//! Source files that don't fit the context budget are split on top-level
+//! definitions (and on members of large classes/impls) with tree-sitter. The model
+//! gets a structural outline of the whole file plus the chunks most relevant to the
+//! user's message, with omitted line ranges marked, instead of a blunt truncation.
+
+use tree_sitter::{Node, Parser};
+
+/// Containers larger than this are split into their members
+const SPLIT_CONTAINER_CHARS: usize = 4000;
+/// Share of the budget the outline may use
+const OUTLINE_BUDGET_DIVISOR: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+}
+
+impl CodeLanguage {
+    /// Detect the language from a file name's extension
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let ext = name.rsplit_once('.')?.1.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" => Some(CodeLanguage::Rust),
+            "py" | "pyi" => Some(CodeLanguage::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(CodeLanguage::JavaScript),
+            "ts" | "mts" | "cts" => Some(CodeLanguage::TypeScript),
+            "tsx" => Some(CodeLanguage::Tsx),
+            "go" => Some(CodeLanguage::Go),
+            _ => None,
+        }
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        match self {
+            CodeLanguage::Rust => tree_sitter_rust::LANGUAGE.into(),
+            CodeLanguage::Python => tree_sitter_python::LANGUAGE.into(),
+            CodeLanguage::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            CodeLanguage::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            CodeLanguage::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            CodeLanguage::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+}
+
+/// A contiguous range of lines (0-based, inclusive) forming one syntactic unit
+#[derive(Debug, Clone)]
+struct CodeChunk {
+    kind: String,
+    name: Option<String>,
+    start_line: usize,
+    end_line: usize,
+    /// Index of the container chunk (header) this member belongs to
+    parent: Option<usize>,
+    /// Container headers/trailers: included whenever one of their members is
+    structural: bool,
+}
+
+/// Condense `source` to roughly `budget_chars`. Returns `None` when the file already
+/// fits, the language isn't supported, or parsing fails (callers keep the original).
+pub fn condense_code_file(
+    file_name: &str,
+    source: &str,
+    budget_chars: usize,
+    query: &str,
+) -> Option<String> {
+    if source.len() <= budget_chars {
+        return None;
+    }
+    let language = CodeLanguage::from_file_name(file_name)?;
+    let lines: Vec<&str> = source.lines().collect();
+    let chunks = chunk_code(source, language)?;
+    if chunks.is_empty() {
+        return None;
+    }
+
+    let outline = build_outline(&chunks, budget_chars / OUTLINE_BUDGET_DIVISOR);
+    let selected = select_chunks(
+        &chunks,
+        &lines,
+        query,
+        budget_chars.saturating_sub(outline.len()),
+    );
+
+    let mut body = String::new();
+    let mut next_line = 0;
+    let mut ranges: Vec<(usize, usize)> = selected
+        .iter()
+        .map(|&i| (chunks[i].start_line, chunks[i].end_line))
+        .collect();
+    ranges.sort();
+    for (start, end) in ranges {
+        let start = start.max(next_line);
+        let end = end.min(lines.len() - 1);
+        if start > end {
+            continue;
+        }
+        if start > next_line {
+            body.push_str(&omitted_marker(next_line, start - 1));
+        }
+        for line in &lines[start..=end] {
+            body.push_str(line);
+            body.push('\n');
+        }
+        next_line = end + 1;
+    }
+    if next_line < lines.len() {
+        body.push_str(&omitted_marker(next_line, lines.len() - 1));
+    }
+
+    Some(format!(
+        "[Large file condensed: {} lines. Showing {} of {} sections most relevant to the request; omitted lines are marked.]\n\nOutline:\n{}\n{}",
+        lines.len(),
+        selected.len(),
+        chunks.len(),
+        outline,
+        body
+    ))
+}
+
+fn omitted_marker(start: usize, end: usize) -> String {
+    format!("… lines {}-{} omitted …\n", start + 1, end + 1)
+}
+
+/// Split a file into chunks on top-level definitions
+fn chunk_code(source: &str, language: CodeLanguage) -> Option<Vec<CodeChunk>> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    let tree = parser.parse(source, None)?;
+    let root = tree.root_node();
+
+    let mut chunks = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        collect_chunks(node, source, &mut chunks);
+    }
+    Some(chunks)
+}
+
+fn collect_chunks(node: Node, source: &str, chunks: &mut Vec<CodeChunk>) {
+    let name = node_name(node, source);
+    let start_line = node.start_position().row;
+    let end_line = node.end_position().row;
+
+    let members: Vec<Node> = if node.byte_range().len() > SPLIT_CONTAINER_CHARS {
+        container_body(node)
+            .map(|body| {
+                let mut cursor = body.walk();
+                body.named_children(&mut cursor).collect()
+            })
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let (Some(first), Some(last)) = (members.first(), members.last()) else {
+        chunks.push(CodeChunk {
+            kind: node.kind().to_string(),
+            name,
+            start_line,
+            end_line,
+            parent: None,
+            structural: false,
+        });
+        return;
+    };
+
+    // Header (signature up to the first member), members, then the closing lines
+    let header = chunks.len();
+    let first_member_line = first.start_position().row;
+    chunks.push(CodeChunk {
+        kind: node.kind().to_string(),
+        name: name.clone(),
+        start_line,
+        end_line: first_member_line.saturating_sub(1).max(start_line),
+        parent: None,
+        structural: true,
+    });
+    for member in &members {
+        chunks.push(CodeChunk {
+            kind: member.kind().to_string(),
+            name: node_name(*member, source),
+            start_line: member.start_position().row,
+            end_line: member.end_position().row,
+            parent: Some(header),
+            structural: false,
+        });
+    }
+    let last_member_line = last.end_position().row;
+    if end_line > last_member_line {
+        chunks.push(CodeChunk {
+            kind: format!("end of {}", node.kind()),
+            name,
+            start_line: last_member_line + 1,
+            end_line,
+            parent: Some(header),
+            structural: true,
+        });
+    }
+}
+
+/// The member list of a class/impl/trait/module, looking through export and
+/// decorator wrappers
+fn container_body(node: Node) -> Option<Node> {
+    let inner = node
+        .child_by_field_name("declaration")
+        .or_else(|| node.child_by_field_name("definition"))
+        .unwrap_or(node);
+    inner.child_by_field_name("body")
+}
+
+fn node_name(node: Node, source: &str) -> Option<String> {
+    let inner = node
+        .child_by_field_name("declaration")
+        .or_else(|| node.child_by_field_name("definition"))
+        .unwrap_or(node);
+    let name_node = inner
+        .child_by_field_name("name")
+        // Rust `impl Type` / `impl Trait for Type`
+        .or_else(|| inner.child_by_field_name("type"))
+        // Go `type Foo struct {}` and JS `const foo = ...`
+        .or_else(|| {
+            let mut cursor = inner.walk();
+            inner
+                .named_children(&mut cursor)
+                .find(|c| c.kind().ends_with("_spec") || c.kind() == "variable_declarator")
+                .and_then(|c| c.child_by_field_name("name"))
+        })?;
+    name_node
+        .utf8_text(source.as_bytes())
+        .ok()
+        .map(|s| s.to_string())
+}
+
+/// One outline line per chunk, truncated to `max_chars`
+fn build_outline(chunks: &[CodeChunk], max_chars: usize) -> String {
+    let mut outline = String::new();
+    for chunk in chunks {
+        if chunk.structural && chunk.parent.is_some() {
+            continue;
+        }
+        let indent = if chunk.parent.is_some() { "  " } else { "" };
+        let line = format!(
+            "{}- L{}-{} {}{}\n",
+            indent,
+            chunk.start_line + 1,
+            chunk.end_line + 1,
+            chunk.kind,
+            chunk
+                .name
+                .as_ref()
+                .map(|n| format!(" {}", n))
+                .unwrap_or_default()
+        );
+        if outline.len() + line.len() > max_chars {
+            outline.push_str("- …\n");
+            break;
+        }
+        outline.push_str(&line);
+    }
+    outline
+}
+
+/// Pick the chunks most relevant to the query that fit the budget.
+/// Returns chunk indices.
+fn select_chunks(chunks: &[CodeChunk], lines: &[&str], query: &str, budget: usize) -> Vec<usize> {
+    let terms = query_terms(query);
+    let chunk_text = |c: &CodeChunk| {
+        let end = c.end_line.min(lines.len() - 1);
+        lines[c.start_line.min(end)..=end].join("\n")
+    };
+    let chunk_len = |c: &CodeChunk| chunk_text(c).len() + 1;
+
+    let mut scored: Vec<(usize, usize)> = chunks
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.structural)
+        .map(|(i, c)| {
+            let text = chunk_text(c).to_lowercase();
+            let name = c.name.as_deref().unwrap_or_default().to_lowercase();
+            let score: usize = terms
+                .iter()
+                .map(|t| {
+                    let in_name = if name.contains(t.as_str()) { 10 } else { 0 };
+                    in_name + text.matches(t.as_str()).count().min(5)
+                })
+                .sum();
+            (i, score)
+        })
+        .collect();
+    // Highest score first; earlier chunks win ties (imports and top-of-file context)
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut selected = Vec::new();
+    let mut used = 0;
+    for (i, _) in scored {
+        let mut cost = chunk_len(&chunks[i]);
+        // Members pull in their container's header and trailer
+        let structural: Vec<usize> = match chunks[i].parent {
+            Some(parent) => std::iter::once(parent)
+                .chain(
+                    chunks
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, c)| c.structural && c.parent == Some(parent))
+                        .map(|(j, _)| j),
+                )
+                .filter(|j| !selected.contains(j))
+                .collect(),
+            None => Vec::new(),
+        };
+        cost += structural
+            .iter()
+            .map(|&j| chunk_len(&chunks[j]))
+            .sum::<usize>();
+        if used + cost > budget {
+            continue;
+        }
+        used += cost;
+        selected.push(i);
+        selected.extend(structural);
+    }
+    selected
+}
+
+/// Lowercased identifier-like words from the query (3+ chars)
+fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| w.len() >= 3)
+        .map(|w| w.to_lowercase())
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn large_rust_source() -> String {
+        let mut source = String::from("use std::fmt;\n\n");
+        for i in 0..40 {
+            source.push_str(&format!(
+                "fn helper_{i}() -> u32 {{\n    let value = {i};\n    value * 2\n}}\n\n"
+            ));
+        }
+        source.push_str("fn parse_config(input: &str) -> usize {\n    input.len()\n}\n");
+        source
+    }
+
+    #[test]
+    fn test_language_detection() {
+        assert_eq!(
+            CodeLanguage::from_file_name("src/main.rs"),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::from_file_name("App.tsx"),
+            Some(CodeLanguage::Tsx)
+        );
+        assert_eq!(CodeLanguage::from_file_name("notes.txt"), None);
+    }
+
+    #[test]
+    fn test_small_files_are_left_alone() {
+        assert!(condense_code_file("main.rs", "fn main() {}", 1000, "main").is_none());
+    }
+
+    #[test]
+    fn test_condense_keeps_relevant_chunks() {
+        let source = large_rust_source();
+        let condensed =
+            condense_code_file("lib.rs", &source, 600, "Why does parse_config fail?").unwrap();
+
+        assert!(condensed.contains("Outline:"));
+        assert!(condensed.contains("fn parse_config(input: &str) -> usize {"));
+        assert!(condensed.contains("omitted"));
+        assert!(condensed.len() < source.len());
+    }
+
+    #[test]
+    fn test_large_containers_are_split_into_members() {
+        let mut source = String::from("class Store:\n");
+        for i in 0..120 {
+            source.push_str(&format!(
+                "    def method_{i}(self):\n        return {i}\n\n"
+            ));
+        }
+        let chunks = chunk_code(&source, CodeLanguage::Python).unwrap();
+
+        assert!(chunks[0].structural);
+        assert_eq!(chunks[0].name.as_deref(), Some("Store"));
+        assert!(
+            chunks
+                .iter()
+                .any(|c| c.name.as_deref() == Some("method_42") && c.parent == Some(0))
+        );
+    }
+}
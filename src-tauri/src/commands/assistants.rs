@@ -1,4 +1,5 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::models::{Assistant, CreateAssistantRequest};
 use tauri::State;
 
@@ -6,25 +7,21 @@ use tauri::State;
 pub async fn create_assistant(
     state: State<'_, AppState>,
     req: CreateAssistantRequest,
-) -> Result<Assistant, String> {
-    state
-        .db
-        .create_assistant(req)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<Assistant, AppError> {
+    state.db.create_assistant(req).await.map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_assistant(
     state: State<'_, AppState>,
     id: String,
-) -> Result<Option<Assistant>, String> {
-    state.db.get_assistant(&id).await.map_err(|e| e.to_string())
+) -> Result<Option<Assistant>, AppError> {
+    state.db.get_assistant(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn list_assistants(state: State<'_, AppState>) -> Result<Vec<Assistant>, String> {
-    state.db.list_assistants().await.map_err(|e| e.to_string())
+pub async fn list_assistants(state: State<'_, AppState>) -> Result<Vec<Assistant>, AppError> {
+    state.db.list_assistants().await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -32,19 +29,15 @@ pub async fn update_assistant(
     state: State<'_, AppState>,
     id: String,
     req: CreateAssistantRequest,
-) -> Result<Assistant, String> {
+) -> Result<Assistant, AppError> {
     state
         .db
         .update_assistant(&id, req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn delete_assistant(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    state
-        .db
-        .delete_assistant(&id)
-        .await
-        .map_err(|e| e.to_string())
+pub async fn delete_assistant(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_assistant(&id).await.map_err(AppError::from)
 }
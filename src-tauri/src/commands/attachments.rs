@@ -1,5 +1,9 @@
 use super::AppState;
+use crate::error::AppError;
+use crate::llm::tools::path_policy;
 use crate::models::{FileAttachment, UserAttachment};
+use crate::repo_context::{self, RepoContext};
+use std::path::Path;
 use tauri::State;
 
 // ==========================================================================
@@ -10,22 +14,192 @@ use tauri::State;
 pub async fn get_message_attachments(
     state: State<'_, AppState>,
     message_id: String,
-) -> Result<Vec<UserAttachment>, String> {
+) -> Result<Vec<UserAttachment>, AppError> {
     state
         .db
         .get_message_attachments(&message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_file_attachment(
     state: State<'_, AppState>,
     id: String,
-) -> Result<FileAttachment, String> {
+) -> Result<FileAttachment, AppError> {
     state
         .db
         .get_file_attachment(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Get a small JPEG preview of an image attachment as a data URL.
+/// Returns `None` for non-image attachments. Thumbnails missing for attachments
+/// stored before thumbnails existed are generated on first request.
+#[tauri::command]
+pub async fn get_attachment_thumbnail(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<Option<String>, AppError> {
+    use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+    let attachment = state.db.get_file_attachment(&id).await?;
+    if !crate::thumbnails::supports_thumbnail(&attachment.mime_type) {
+        return Ok(None);
+    }
+
+    let thumbnail = tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<u8>> {
+        let thumbnail_path = crate::thumbnails::thumbnail_storage_path(&attachment.content_hash);
+        if let Ok(bytes) = crate::storage::read_binary(&app, &thumbnail_path) {
+            return Ok(bytes);
+        }
+        let original = crate::storage::read_binary(&app, &attachment.storage_path)?;
+        let thumbnail_path =
+            crate::thumbnails::ensure_thumbnail(&app, &attachment.content_hash, &original)?;
+        crate::storage::read_binary(&app, &thumbnail_path)
+    })
+    .await??;
+
+    Ok(Some(format!(
+        "data:image/jpeg;base64,{}",
+        STANDARD.encode(&thumbnail)
+    )))
+}
+
+/// Copy a stored attachment out of app storage to `destination_path`.
+/// The original file extension is appended when the destination has none, and the
+/// file's modification time is set to when the attachment was added. Returns the
+/// path actually written.
+#[tauri::command]
+pub async fn export_attachment(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    id: String,
+    destination_path: String,
+) -> Result<String, AppError> {
+    let attachment = state.db.get_file_attachment(&id).await?;
+
+    let source = crate::storage::get_full_path(&app, &attachment.storage_path)?;
+    let destination = export_destination(
+        std::path::Path::new(&destination_path),
+        &attachment.file_name,
+        &attachment.storage_path,
+    );
+
+    std::fs::copy(&source, &destination)
+        .map_err(|e| format!("Failed to export {}: {}", attachment.file_name, e))?;
+
+    if let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(&attachment.created_at)
+        && let Ok(file) = std::fs::File::options().write(true).open(&destination)
+    {
+        let _ = file.set_modified(std::time::SystemTime::from(created_at));
+    }
+
+    tracing::info!(
+        "📤 [export_attachment] Exported {} to {:?}",
+        attachment.file_name,
+        destination
+    );
+    Ok(destination.to_string_lossy().to_string())
+}
+
+/// Collect branch, recent commits, uncommitted changes and tracked files of the git
+/// repository containing `path`. The frontend attaches the returned Markdown
+/// (`name`, `content`) to the next message as a text file. Without a path (or with a
+/// relative one), the conversation's working directory is used.
+#[tauri::command]
+pub async fn attach_repo_context(
+    state: State<'_, AppState>,
+    path: Option<String>,
+    conversation_id: Option<String>,
+) -> Result<RepoContext, AppError> {
+    let working_directory = match &conversation_id {
+        Some(id) => {
+            state
+                .db
+                .get_conversation_settings(id)
+                .await?
+                .working_directory
+        }
+        None => None,
+    };
+    let working_directory = working_directory.as_deref().map(Path::new);
+    let path = match path.as_deref().filter(|path| !path.trim().is_empty()) {
+        Some(path) => path_policy::resolve(path, working_directory),
+        None => working_directory.map(Path::to_path_buf).ok_or_else(|| {
+            AppError::validation(
+                "No repository path given and the conversation has no working directory",
+            )
+        })?,
+    };
+    let path = path.as_path();
+    if !path.is_absolute() {
+        return Err(AppError::validation("The repository path must be absolute"));
+    }
+    path_policy::check_read(path, working_directory).map_err(AppError::validation)?;
+    if !path.is_dir() {
+        return Err(AppError::validation(format!(
+            "Not a directory: {}",
+            path.display()
+        )));
+    }
+
+    let context = repo_context::collect(path)
+        .await
+        .map_err(|e| AppError::validation(e.to_string()))?;
+    tracing::info!(
+        "📦 [attachments] Collected repository context for {} ({} files, {} changed)",
+        context.root,
+        context.file_count,
+        context.status.len()
+    );
+    Ok(context)
+}
+
+/// Add the attachment's extension (from its file name, else its storage path) when
+/// the chosen destination has none
+fn export_destination(
+    destination: &std::path::Path,
+    file_name: &str,
+    storage_path: &str,
+) -> std::path::PathBuf {
+    if destination.extension().is_some() {
+        return destination.to_path_buf();
+    }
+
+    let ext = std::path::Path::new(file_name)
+        .extension()
+        .or_else(|| std::path::Path::new(storage_path).extension());
+    match ext {
+        Some(ext) => destination.with_extension(ext),
+        None => destination.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_export_destination_keeps_explicit_extension() {
+        assert_eq!(
+            export_destination(Path::new("/tmp/photo.jpeg"), "photo.png", "files/abc.png"),
+            PathBuf::from("/tmp/photo.jpeg")
+        );
+    }
+
+    #[test]
+    fn test_export_destination_adds_missing_extension() {
+        assert_eq!(
+            export_destination(Path::new("/tmp/report"), "report.pdf", "files/abc.pdf"),
+            PathBuf::from("/tmp/report.pdf")
+        );
+        assert_eq!(
+            export_destination(Path::new("/tmp/image"), "pasted image", "files/abc.png"),
+            PathBuf::from("/tmp/image.png")
+        );
+    }
 }
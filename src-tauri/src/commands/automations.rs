@@ -0,0 +1,236 @@
+use super::AppState;
+use crate::automations::{WEBHOOK_TOKEN_LEN, scheduled_run, sync_webhook_server};
+use crate::error::AppError;
+use crate::models::{
+    Automation, AutomationAction, AutomationEvent, AutomationInput, AutomationRun,
+    AutomationTrigger,
+};
+use crate::prompts::DROP_FOLDER_PROMPT;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use std::path::Path;
+use tauri::State;
+
+/// Runs returned by `list_automation_runs` unless a limit is given
+const DEFAULT_RUN_LIMIT: i64 = 20;
+
+#[tauri::command]
+pub async fn list_automations(state: State<'_, AppState>) -> Result<Vec<Automation>, AppError> {
+    state.db.list_automations().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn create_automation(
+    state: State<'_, AppState>,
+    input: AutomationInput,
+) -> Result<Automation, AppError> {
+    let input = prepare_input(&state, input, None).await?;
+    let next_run_at = scheduled_run(&input.trigger, input.is_enabled);
+    let automation = state.db.create_automation(input, next_run_at).await?;
+    sync_webhook_server(&state).await;
+    Ok(automation)
+}
+
+/// Replace an automation's trigger and action. A schedule is rescheduled from its new
+/// time; a webhook keeps its token.
+#[tauri::command]
+pub async fn update_automation(
+    state: State<'_, AppState>,
+    id: String,
+    input: AutomationInput,
+) -> Result<Automation, AppError> {
+    let existing = state
+        .db
+        .get_automation(&id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Automation not found: {}", id)))?;
+    let input = prepare_input(&state, input, Some(&existing.trigger)).await?;
+    let next_run_at = scheduled_run(&input.trigger, input.is_enabled);
+    let automation = state.db.update_automation(&id, input, next_run_at).await?;
+    sync_webhook_server(&state).await;
+    Ok(automation)
+}
+
+/// Delete an automation and its run history; its conversation is kept
+#[tauri::command]
+pub async fn delete_automation(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_automation(&id).await?;
+    sync_webhook_server(&state).await;
+    Ok(())
+}
+
+/// Watch `folder` and post every document that appears in it, with `prompt` (by
+/// default: summarize and extract action items), into `conversation_id`. Without a
+/// conversation, one named after the automation is created on the first run.
+#[tauri::command]
+pub async fn create_drop_folder(
+    state: State<'_, AppState>,
+    folder: String,
+    assistant_id: Option<String>,
+    conversation_id: Option<String>,
+    prompt: Option<String>,
+) -> Result<Automation, AppError> {
+    let folder_name = Path::new(folder.trim())
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| folder.clone());
+    let prompt = prompt
+        .filter(|prompt| !prompt.trim().is_empty())
+        .unwrap_or_else(|| DROP_FOLDER_PROMPT.to_string());
+    let input = AutomationInput {
+        name: format!("Drop folder: {}", folder_name),
+        trigger: AutomationTrigger::FolderWatch { path: folder },
+        action: AutomationAction::RunPrompt {
+            prompt,
+            sources: Vec::new(),
+            assistant_id,
+            conversation_id,
+        },
+        is_enabled: true,
+    };
+    create_automation(state, input).await
+}
+
+/// Run an automation's action now, whatever its trigger and even while disabled
+#[tauri::command]
+pub async fn run_automation_now(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    if state.db.get_automation(&id).await?.is_none() {
+        return Err(AppError::not_found(format!("Automation not found: {}", id)));
+    }
+    crate::automations::enqueue_run(&state, &id, AutomationEvent::Manual).await?;
+    Ok(())
+}
+
+/// An automation's most recent runs, newest first
+#[tauri::command]
+pub async fn list_automation_runs(
+    state: State<'_, AppState>,
+    automation_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<AutomationRun>, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_RUN_LIMIT).clamp(1, 100);
+    state
+        .db
+        .list_automation_runs(&automation_id, limit)
+        .await
+        .map_err(AppError::from)
+}
+
+/// URL to POST to for a webhook automation, or `None` while the webhook server isn't
+/// running (e.g. its port is taken)
+#[tauri::command]
+pub async fn get_automation_webhook_url(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<String>, AppError> {
+    let automation = state
+        .db
+        .get_automation(&id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Automation not found: {}", id)))?;
+    match &automation.trigger {
+        AutomationTrigger::Webhook { token } => Ok(crate::automations::webhook_url(token).await),
+        _ => Err(AppError::validation(
+            "Automation isn't triggered by a webhook",
+        )),
+    }
+}
+
+/// Validate an input and fill in what the user leaves out: blank ids become `None`
+/// and a webhook keeps `existing`'s token or gets a new one. Tokens are never taken
+/// from the input, so every hook URL is unguessable.
+async fn prepare_input(
+    state: &AppState,
+    mut input: AutomationInput,
+    existing: Option<&AutomationTrigger>,
+) -> Result<AutomationInput, AppError> {
+    input.validate().map_err(AppError::validation)?;
+
+    match &mut input.trigger {
+        AutomationTrigger::FolderWatch { path } => {
+            *path = path.trim().to_string();
+            if !tokio::fs::metadata(path.as_str())
+                .await
+                .is_ok_and(|metadata| metadata.is_dir())
+            {
+                return Err(AppError::not_found(format!("Folder not found: {}", path)));
+            }
+        }
+        AutomationTrigger::Webhook { token } => {
+            *token = match existing {
+                Some(AutomationTrigger::Webhook { token }) if token.len() >= WEBHOOK_TOKEN_LEN => {
+                    token.clone()
+                }
+                _ => new_webhook_token(),
+            };
+        }
+        _ => {}
+    }
+
+    match &mut input.action {
+        AutomationAction::RunPrompt {
+            assistant_id,
+            conversation_id,
+            ..
+        } => {
+            blank_to_none(assistant_id);
+            blank_to_none(conversation_id);
+            if let Some(id) = assistant_id
+                && state.db.get_assistant(id).await?.is_none()
+            {
+                return Err(AppError::not_found(format!("Assistant not found: {}", id)));
+            }
+            ensure_conversation_exists(state, conversation_id.as_deref()).await?;
+        }
+        AutomationAction::ExportConversation {
+            conversation_id,
+            directory,
+            ..
+        } => {
+            blank_to_none(conversation_id);
+            *directory = directory.trim().to_string();
+            ensure_conversation_exists(state, conversation_id.as_deref()).await?;
+        }
+        AutomationAction::CallMcpTool {
+            server_id,
+            tool_name,
+            ..
+        } => {
+            *tool_name = tool_name.trim().to_string();
+            let server = state.db.get_tool(server_id).await.ok();
+            if !server.is_some_and(|server| server.r#type == "mcp") {
+                return Err(AppError::not_found(format!(
+                    "MCP server not found: {}",
+                    server_id
+                )));
+            }
+        }
+    }
+    Ok(input)
+}
+
+/// 128 random bits, URL-safe
+fn new_webhook_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn blank_to_none(value: &mut Option<String>) {
+    if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        *value = None;
+    }
+}
+
+async fn ensure_conversation_exists(state: &AppState, id: Option<&str>) -> Result<(), AppError> {
+    if let Some(id) = id
+        && state.db.get_conversation(id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Conversation not found: {}",
+            id
+        )));
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::llm::capabilities::{MODELS_DEV_URL, ModelCapabilities};
 use tauri::State;
 
@@ -7,7 +8,7 @@ pub async fn get_model_capabilities(
     state: State<'_, AppState>,
     provider_type: String,
     model_id: String,
-) -> Result<ModelCapabilities, String> {
+) -> Result<ModelCapabilities, AppError> {
     Ok(state
         .capabilities_cache
         .resolve(&provider_type, &model_id)
@@ -15,7 +16,7 @@ pub async fn get_model_capabilities(
 }
 
 #[tauri::command]
-pub async fn refresh_capabilities_cache(state: State<'_, AppState>) -> Result<usize, String> {
+pub async fn refresh_capabilities_cache(state: State<'_, AppState>) -> Result<usize, AppError> {
     let count = state
         .capabilities_cache
         .refresh_from_url(MODELS_DEV_URL)
@@ -0,0 +1,322 @@
+//! Zip archive attachment processing
+//!
+//! A zip attached as a file (base64 data URL content) is unpacked in memory: its text
+//! files become individual `FileData` entries named by their path inside the archive,
+//! and the archive itself is stored and linked to the user message.
+
+use super::super::AppState;
+use super::types::FileAttachmentInput;
+use crate::events::{self, AttachmentUpdate};
+use crate::llm::FileData;
+use crate::models::CreateFileAttachmentRequest;
+use std::io::Read;
+
+/// Largest archive accepted, compressed
+const MAX_ARCHIVE_BYTES: usize = 20 * 1024 * 1024;
+/// Archives with more entries than this are rejected outright
+const MAX_ARCHIVE_ENTRIES: usize = 2000;
+/// Number of text files ingested from one archive
+const MAX_INGESTED_FILES: usize = 200;
+/// Single files larger than this (uncompressed) are skipped
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+/// Total uncompressed text ingested from one archive
+const MAX_TOTAL_BYTES: usize = 4 * 1024 * 1024;
+
+/// Directories that never contain anything useful for the model
+const SKIPPED_DIRS: &[&str] = &[
+    "__MACOSX",
+    ".git",
+    "node_modules",
+    "target",
+    ".venv",
+    "__pycache__",
+];
+
+/// Decoded zip attachment
+pub(crate) struct ParsedArchive {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Whether a file attachment is a zip archive
+fn is_zip_attachment(file: &FileAttachmentInput) -> bool {
+    matches!(
+        file.mime_type.as_str(),
+        "application/zip" | "application/x-zip-compressed"
+    ) || file.name.to_lowercase().ends_with(".zip")
+}
+
+/// Split zip archives out of the file attachments. Zips must be sent as base64
+/// data URLs; anything else is left in the regular file list.
+pub(crate) fn split_archive_attachments(
+    files: Option<Vec<FileAttachmentInput>>,
+) -> (Option<Vec<FileAttachmentInput>>, Vec<ParsedArchive>) {
+    let Some(files) = files else {
+        return (None, Vec::new());
+    };
+
+    let mut remaining = Vec::with_capacity(files.len());
+    let mut archives = Vec::new();
+
+    for file in files {
+        let data = file
+            .content
+            .strip_prefix("data:")
+            .and_then(|rest| rest.split_once(";base64,"))
+            .map(|(_, data)| data);
+
+        match data {
+            Some(data) if is_zip_attachment(&file) => {
+                match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, data) {
+                    Ok(bytes) => {
+                        tracing::info!(
+                            "🗜️ [archive] Parsed archive: {} ({} bytes)",
+                            file.name,
+                            bytes.len()
+                        );
+                        archives.push(ParsedArchive {
+                            name: file.name,
+                            bytes,
+                        });
+                    }
+                    Err(e) => tracing::error!("Failed to decode archive {}: {}", file.name, e),
+                }
+            }
+            _ => remaining.push(file),
+        }
+    }
+
+    (Some(remaining), archives)
+}
+
+/// Extract the text files from a zip archive, enforcing size and entry limits.
+/// Binary files, oversized files and common build/VCS directories are skipped.
+pub(crate) fn extract_text_files(archive: &ParsedArchive) -> Result<Vec<FileData>, String> {
+    if archive.bytes.len() > MAX_ARCHIVE_BYTES {
+        return Err(format!(
+            "Archive is too large ({} bytes, limit {})",
+            archive.bytes.len(),
+            MAX_ARCHIVE_BYTES
+        ));
+    }
+
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&archive.bytes))
+        .map_err(|e| format!("Invalid zip archive: {}", e))?;
+    if zip.len() > MAX_ARCHIVE_ENTRIES {
+        return Err(format!(
+            "Archive has too many entries ({}, limit {})",
+            zip.len(),
+            MAX_ARCHIVE_ENTRIES
+        ));
+    }
+
+    let mut files = Vec::new();
+    let mut total_bytes = 0usize;
+    let mut skipped = 0usize;
+
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        // `enclosed_name` rejects absolute paths and `..` traversal
+        let Some(path) = entry.enclosed_name() else {
+            skipped += 1;
+            continue;
+        };
+        let relative_path = path.to_string_lossy().replace('\\', "/");
+        if relative_path
+            .split('/')
+            .any(|part| SKIPPED_DIRS.contains(&part))
+            || entry.size() > MAX_FILE_BYTES
+        {
+            skipped += 1;
+            continue;
+        }
+
+        if files.len() >= MAX_INGESTED_FILES || total_bytes >= MAX_TOTAL_BYTES {
+            skipped += 1;
+            continue;
+        }
+
+        // Never trust the declared size: cap the actual read as well
+        let mut bytes = Vec::new();
+        (&mut entry)
+            .take(MAX_FILE_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read {}: {}", relative_path, e))?;
+        if bytes.len() as u64 > MAX_FILE_BYTES || total_bytes + bytes.len() > MAX_TOTAL_BYTES {
+            skipped += 1;
+            continue;
+        }
+
+        let Some(content) = decode_text(bytes) else {
+            skipped += 1;
+            continue;
+        };
+
+        total_bytes += content.len();
+        files.push(FileData {
+            media_type: text_media_type(&relative_path).to_string(),
+            name: relative_path,
+            content,
+        });
+    }
+
+    tracing::info!(
+        "🗜️ [archive] Extracted {} text file(s) from {} ({} skipped, {} bytes)",
+        files.len(),
+        archive.name,
+        skipped,
+        total_bytes
+    );
+
+    Ok(files)
+}
+
+/// Decode bytes as UTF-8 text, treating NUL bytes or invalid UTF-8 as binary
+fn decode_text(bytes: Vec<u8>) -> Option<String> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn text_media_type(path: &str) -> &'static str {
+    match path.rsplit('.').next().unwrap_or_default() {
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        _ => "text/plain",
+    }
+}
+
+/// Store the archive itself (deduplicated by hash) and link it to the user message
+pub(crate) async fn store_archive(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    archive: &ParsedArchive,
+    user_message_id: &str,
+    conversation_id: &str,
+) {
+    let content_hash = crate::storage::hash_bytes(&archive.bytes);
+
+    let storage_path = match state.db.find_file_by_hash(&content_hash).await {
+        Ok(Some(existing)) => existing.storage_path,
+        _ => {
+            let path = crate::storage::generate_file_storage_path(&content_hash, "zip");
+            if let Err(e) = crate::storage::write_binary(app, &path, &archive.bytes) {
+                tracing::error!("Failed to save archive {}: {}", archive.name, e);
+                return;
+            }
+            path
+        }
+    };
+
+    let file_attachment = match state
+        .db
+        .create_file_attachment(CreateFileAttachmentRequest {
+            file_name: archive.name.clone(),
+            file_size: archive.bytes.len() as i64,
+            mime_type: "application/zip".to_string(),
+            storage_path,
+            content_hash,
+        })
+        .await
+    {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::error!("Failed to create file record for {}: {}", archive.name, e);
+            return;
+        }
+    };
+
+    if let Err(e) = state
+        .db
+        .link_message_attachment(user_message_id, &file_attachment.id, None)
+        .await
+    {
+        tracing::error!("Failed to link archive to message: {}", e);
+        return;
+    }
+
+    tracing::info!(
+        "🗜️ [attachment] Saved archive attachment: {} -> {}",
+        archive.name,
+        file_attachment.id
+    );
+    events::emit(
+        app,
+        AttachmentUpdate::new(user_message_id, conversation_id, &file_attachment.id),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn build_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_text_files() {
+        let bytes = build_zip(&[
+            ("project/README.md", b"# Hello"),
+            ("project/src/main.rs", b"fn main() {}"),
+            ("project/logo.png", b"\x89PNG\0\0"),
+            ("project/.git/HEAD", b"ref: refs/heads/main"),
+            ("__MACOSX/project/._README.md", b"junk"),
+        ]);
+        let archive = ParsedArchive {
+            name: "project.zip".to_string(),
+            bytes,
+        };
+
+        let files = extract_text_files(&archive).unwrap();
+        let names: Vec<_> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["project/README.md", "project/src/main.rs"]);
+        assert_eq!(files[0].media_type, "text/markdown");
+        assert_eq!(files[1].content, "fn main() {}");
+    }
+
+    #[test]
+    fn test_extract_text_files_rejects_invalid_zip() {
+        let archive = ParsedArchive {
+            name: "broken.zip".to_string(),
+            bytes: b"not a zip".to_vec(),
+        };
+        assert!(extract_text_files(&archive).is_err());
+    }
+
+    #[test]
+    fn test_split_archive_attachments() {
+        let files = vec![
+            FileAttachmentInput {
+                name: "notes.txt".to_string(),
+                content: "hello".to_string(),
+                mime_type: "text/plain".to_string(),
+            },
+            FileAttachmentInput {
+                name: "project.zip".to_string(),
+                content: "data:application/zip;base64,UEsFBg==".to_string(),
+                mime_type: "application/zip".to_string(),
+            },
+        ];
+
+        let (remaining, archives) = split_archive_attachments(Some(files));
+        assert_eq!(remaining.unwrap().len(), 1);
+        assert_eq!(archives.len(), 1);
+        assert_eq!(archives[0].bytes, b"PK\x05\x06");
+    }
+}
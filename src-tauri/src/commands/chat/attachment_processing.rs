@@ -1,12 +1,32 @@
 //! File and image attachment processing
 
 use super::super::AppState;
+use crate::events::{self, AttachmentUpdate};
 use crate::llm::{FileData, ImageData};
 use crate::models::CreateFileAttachmentRequest;
-use tauri::Emitter;
 
 use super::types::{FileAttachmentInput, ImageAttachmentInput};
 
+/// Settings key: longest image side (px) sent to providers; 0 disables downscaling
+pub const IMAGE_MAX_DIMENSION_KEY: &str = "image_max_dimension";
+
+/// Settings key: JPEG quality (1-100) used when recompressing images for providers
+pub const IMAGE_JPEG_QUALITY_KEY: &str = "image_jpeg_quality";
+
+/// Settings key: "false" keeps EXIF/GPS and other metadata in uploaded images
+pub const STRIP_IMAGE_METADATA_KEY: &str = "strip_image_metadata";
+
+/// Context assumed when the model's limit is unknown (tokens)
+pub(crate) const DEFAULT_CONTEXT_TOKENS: usize = 32_000;
+/// Rough characters per token for budgeting attachments
+const CHARS_PER_TOKEN: usize = 4;
+
+const DEFAULT_IMAGE_MAX_DIMENSION: u32 = 2048;
+const DEFAULT_IMAGE_JPEG_QUALITY: u8 = 85;
+
+/// Images at or below this size are only recompressed if they exceed the max dimension
+const IMAGE_RECOMPRESS_THRESHOLD_BYTES: usize = 1024 * 1024;
+
 /// Parsed image data with filename
 pub(crate) struct ParsedImage {
     pub name: String,
@@ -126,13 +146,13 @@ pub(crate) async fn store_file_attachments(
                             file_attachment.id
                         );
 
-                        let _ = app.emit(
-                            "attachment-update",
-                            serde_json::json!({
-                                "message_id": user_message_id,
-                                "conversation_id": conversation_id,
-                                "attachment_id": file_attachment.id,
-                            }),
+                        events::emit(
+                            app,
+                            AttachmentUpdate::new(
+                                user_message_id,
+                                conversation_id,
+                                &file_attachment.id,
+                            ),
                         );
                     }
                 }
@@ -186,13 +206,13 @@ pub(crate) async fn store_file_attachments(
                     );
 
                     // Emit attachment-update so UI refreshes and shows the file
-                    let _ = app.emit(
-                        "attachment-update",
-                        serde_json::json!({
-                            "message_id": user_message_id,
-                            "conversation_id": conversation_id,
-                            "attachment_id": file_attachment.id,
-                        }),
+                    events::emit(
+                        app,
+                        AttachmentUpdate::new(
+                            user_message_id,
+                            conversation_id,
+                            &file_attachment.id,
+                        ),
                     );
                 }
             }
@@ -229,6 +249,11 @@ pub(crate) async fn store_image_attachments(
         // Hash image bytes for deduplication
         let content_hash = crate::storage::hash_bytes(&bytes);
 
+        // Thumbnails are keyed by content hash, so deduplicated images share one
+        if crate::thumbnails::supports_thumbnail(&img.media_type) {
+            crate::thumbnails::spawn_thumbnail(app, content_hash.clone(), bytes.clone());
+        }
+
         // Check if we already have this image (deduplication)
         if let Ok(Some(existing)) = state.db.find_file_by_hash(&content_hash).await {
             tracing::info!(
@@ -264,13 +289,13 @@ pub(crate) async fn store_image_attachments(
                             file_attachment.id
                         );
 
-                        let _ = app.emit(
-                            "attachment-update",
-                            serde_json::json!({
-                                "message_id": user_message_id,
-                                "conversation_id": conversation_id,
-                                "attachment_id": file_attachment.id,
-                            }),
+                        events::emit(
+                            app,
+                            AttachmentUpdate::new(
+                                user_message_id,
+                                conversation_id,
+                                &file_attachment.id,
+                            ),
                         );
                     }
                 }
@@ -325,13 +350,13 @@ pub(crate) async fn store_image_attachments(
                     );
 
                     // Emit attachment-update so UI refreshes and shows the image
-                    let _ = app.emit(
-                        "attachment-update",
-                        serde_json::json!({
-                            "message_id": user_message_id,
-                            "conversation_id": conversation_id,
-                            "attachment_id": file_attachment.id,
-                        }),
+                    events::emit(
+                        app,
+                        AttachmentUpdate::new(
+                            user_message_id,
+                            conversation_id,
+                            &file_attachment.id,
+                        ),
                     );
                 }
             }
@@ -346,3 +371,326 @@ pub(crate) async fn store_image_attachments(
         }
     }
 }
+
+/// Store a generated image (deduplicated by hash) and link it to an assistant message
+/// at position `index`. Returns the new file attachment id.
+pub(crate) async fn store_generated_image(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    message_id: &str,
+    index: usize,
+    mime_type: &str,
+    bytes: &[u8],
+) -> Option<String> {
+    let content_hash = crate::storage::hash_bytes(bytes);
+    if crate::thumbnails::supports_thumbnail(mime_type) {
+        crate::thumbnails::spawn_thumbnail(app, content_hash.clone(), bytes.to_vec());
+    }
+    let ext = crate::storage::get_extension_for_content_type(mime_type);
+    let file_name = format!("generated-image-{}.{}", index + 1, ext);
+
+    // Check for deduplication
+    let storage_path = if let Ok(Some(existing)) = state.db.find_file_by_hash(&content_hash).await {
+        existing.storage_path.clone()
+    } else {
+        let path = crate::storage::generate_file_storage_path(&content_hash, ext);
+        if let Err(e) = crate::storage::write_binary(app, &path, bytes) {
+            tracing::error!("Failed to save generated image {}: {}", index + 1, e);
+            return None;
+        }
+        path
+    };
+
+    match state
+        .db
+        .create_file_attachment(CreateFileAttachmentRequest {
+            file_name: file_name.clone(),
+            file_size: bytes.len() as i64,
+            mime_type: mime_type.to_string(),
+            storage_path,
+            content_hash,
+        })
+        .await
+    {
+        Ok(file_attachment) => {
+            if let Err(e) = state
+                .db
+                .link_message_attachment(message_id, &file_attachment.id, Some(index as i32))
+                .await
+            {
+                tracing::error!("Failed to link generated image to message: {}", e);
+                None
+            } else {
+                tracing::info!(
+                    "🖼️ [attachment] Saved generated image attachment: {} -> {}",
+                    file_name,
+                    file_attachment.id
+                );
+                Some(file_attachment.id)
+            }
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to create file record for generated image {}: {}",
+                index + 1,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Condense source files that would overflow the context: attachments share half of
+/// the model's context window, and code files over their share are replaced with an
+/// outline plus the chunks most relevant to `query`.
+pub(crate) fn condense_large_code_files(
+    chat_messages: &mut [crate::llm::ChatMessage],
+    max_context_tokens: Option<i64>,
+    query: &str,
+) {
+    let file_count: usize = chat_messages.iter().map(|m| m.files.len()).sum();
+    if file_count == 0 {
+        return;
+    }
+
+    let context_tokens = max_context_tokens
+        .filter(|t| *t > 0)
+        .map(|t| t as usize)
+        .unwrap_or(DEFAULT_CONTEXT_TOKENS);
+    let budget_per_file = context_tokens * CHARS_PER_TOKEN / 2 / file_count;
+
+    for file in chat_messages.iter_mut().flat_map(|m| m.files.iter_mut()) {
+        if let Some(condensed) = crate::code_chunking::condense_code_file(
+            &file.name,
+            &file.content,
+            budget_per_file,
+            query,
+        ) {
+            tracing::info!(
+                "✂️ [attachment] Condensed {} for context: {} -> {} chars",
+                file.name,
+                file.content.len(),
+                condensed.len()
+            );
+            file.content = condensed;
+        }
+    }
+}
+
+/// Remove EXIF/GPS and other metadata from uploaded images (unless disabled in
+/// settings). Call before `store_image_attachments` so neither the stored copy nor
+/// the provider request contains it.
+pub(crate) async fn scrub_image_metadata(
+    state: &AppState,
+    images: Vec<ParsedImage>,
+) -> Vec<ParsedImage> {
+    let enabled = !matches!(
+        state
+            .db
+            .get_setting(STRIP_IMAGE_METADATA_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("false")
+    );
+    if !enabled || images.is_empty() {
+        return images;
+    }
+
+    tokio::task::spawn_blocking(move || images.into_iter().map(scrub_parsed_image).collect())
+        .await
+        .unwrap_or_default()
+}
+
+fn scrub_parsed_image(image: ParsedImage) -> ParsedImage {
+    let Ok(bytes) = base64::Engine::decode(
+        &base64::engine::general_purpose::STANDARD,
+        &image.data.base64,
+    ) else {
+        return image;
+    };
+
+    match crate::image_metadata::strip_metadata(&bytes, &image.data.media_type) {
+        Some(stripped) => {
+            tracing::info!(
+                "🧹 [attachment] Stripped metadata from {}: {} -> {} bytes",
+                image.name,
+                bytes.len(),
+                stripped.len()
+            );
+            ParsedImage {
+                name: image.name,
+                data: ImageData {
+                    base64: base64::Engine::encode(
+                        &base64::engine::general_purpose::STANDARD,
+                        &stripped,
+                    ),
+                    media_type: image.data.media_type,
+                },
+            }
+        }
+        None => image,
+    }
+}
+
+/// Downscale and recompress images before they are base64-encoded for the LLM.
+/// Call after `store_image_attachments` so the stored originals stay untouched.
+pub(crate) async fn prepare_images_for_llm(
+    state: &AppState,
+    images: Vec<ParsedImage>,
+) -> Vec<ParsedImage> {
+    if images.is_empty() {
+        return images;
+    }
+
+    let max_dimension = state
+        .db
+        .get_setting(IMAGE_MAX_DIMENSION_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_IMAGE_MAX_DIMENSION);
+    if max_dimension == 0 {
+        return images;
+    }
+
+    let quality = state
+        .db
+        .get_setting(IMAGE_JPEG_QUALITY_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<u8>().ok())
+        .unwrap_or(DEFAULT_IMAGE_JPEG_QUALITY)
+        .clamp(1, 100);
+
+    // Decoding and encoding is CPU-bound; keep it off the async workers
+    tokio::task::spawn_blocking(move || {
+        images
+            .into_iter()
+            .map(|image| optimize_parsed_image(image, max_dimension, quality))
+            .collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
+fn optimize_parsed_image(image: ParsedImage, max_dimension: u32, quality: u8) -> ParsedImage {
+    let bytes = match base64::Engine::decode(
+        &base64::engine::general_purpose::STANDARD,
+        &image.data.base64,
+    ) {
+        Ok(b) => b,
+        Err(_) => return image,
+    };
+
+    match downscale_image(&bytes, &image.data.media_type, max_dimension, quality) {
+        Some((optimized, media_type)) => {
+            tracing::info!(
+                "🗜️ [attachment] Optimized image {} for LLM: {} -> {} bytes ({})",
+                image.name,
+                bytes.len(),
+                optimized.len(),
+                media_type
+            );
+            ParsedImage {
+                name: image.name,
+                data: ImageData {
+                    base64: base64::Engine::encode(
+                        &base64::engine::general_purpose::STANDARD,
+                        &optimized,
+                    ),
+                    media_type,
+                },
+            }
+        }
+        None => image,
+    }
+}
+
+/// Resize `bytes` so the longest side is at most `max_dimension`, re-encoding as JPEG
+/// (or PNG when the image has transparency). Returns `None` when the original should be
+/// sent as-is: already small enough, animated GIF, undecodable, or no size win.
+fn downscale_image(
+    bytes: &[u8],
+    media_type: &str,
+    max_dimension: u32,
+    quality: u8,
+) -> Option<(Vec<u8>, String)> {
+    // Re-encoding would drop GIF animation frames
+    if media_type == "image/gif" {
+        return None;
+    }
+
+    let img = image::load_from_memory(bytes).ok()?;
+    let oversized = img.width() > max_dimension || img.height() > max_dimension;
+    if !oversized && bytes.len() <= IMAGE_RECOMPRESS_THRESHOLD_BYTES {
+        return None;
+    }
+
+    let img = if oversized {
+        img.resize(
+            max_dimension,
+            max_dimension,
+            image::imageops::FilterType::Lanczos3,
+        )
+    } else {
+        img
+    };
+
+    let mut output = std::io::Cursor::new(Vec::new());
+    let media_type = if img.color().has_alpha() {
+        img.write_to(&mut output, image::ImageFormat::Png).ok()?;
+        "image/png"
+    } else {
+        let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut output, quality);
+        img.to_rgb8().write_with_encoder(encoder).ok()?;
+        "image/jpeg"
+    };
+    let output = output.into_inner();
+
+    // Only a size win is worth swapping out the original (resizing always is)
+    if !oversized && output.len() >= bytes.len() {
+        return None;
+    }
+
+    Some((output, media_type.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_png(width: u32, height: u32) -> Vec<u8> {
+        let img = image::RgbImage::from_pixel(width, height, image::Rgb([120, 40, 200]));
+        let mut out = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(&mut out, image::ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_downscale_image_resizes_oversized() {
+        let png = encode_png(400, 100);
+        let (bytes, media_type) = downscale_image(&png, "image/png", 200, 80).unwrap();
+
+        let resized = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((resized.width(), resized.height()), (200, 50));
+        assert_eq!(media_type, "image/jpeg");
+    }
+
+    #[test]
+    fn test_downscale_image_keeps_small_images() {
+        let png = encode_png(64, 64);
+        assert!(downscale_image(&png, "image/png", 200, 80).is_none());
+    }
+
+    #[test]
+    fn test_downscale_image_skips_gif() {
+        let png = encode_png(400, 100);
+        assert!(downscale_image(&png, "image/gif", 200, 80).is_none());
+    }
+}
@@ -0,0 +1,377 @@
+//! Audio attachment processing
+//!
+//! Audio attachments (voice memos) are stored like other files, transcribed with the
+//! configured engine, saved as a transcription step on the user message, and the
+//! transcript is injected into the prompt.
+
+use super::super::AppState;
+use super::types::AudioAttachmentInput;
+use crate::events::{self, AttachmentUpdate, TranscriptionComplete, TranscriptionStarted};
+use crate::models::{CreateFileAttachmentRequest, CreateTranscriptionRequest};
+use crate::transcription::{self, TranscriptionEngine};
+
+/// Decoded audio attachment
+pub(crate) struct ParsedAudio {
+    pub name: String,
+    pub media_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// A finished transcript, ready to be injected into the prompt
+pub(crate) struct AudioTranscript {
+    pub name: String,
+    pub content: String,
+}
+
+/// Parse audio attachments (data URLs) from frontend input
+pub(crate) fn parse_audio_attachments(
+    audio: Option<Vec<AudioAttachmentInput>>,
+) -> Vec<ParsedAudio> {
+    let mut parsed = Vec::new();
+
+    for item in audio.unwrap_or_default() {
+        let Some((media_type, base64_data)) = item
+            .base64
+            .strip_prefix("data:")
+            .and_then(|rest| rest.split_once(";base64,"))
+        else {
+            tracing::warn!("⚠️ [audio] Skipping {}: not a base64 data URL", item.name);
+            continue;
+        };
+
+        // Some recorders produce generic data URLs; fall back to the declared type
+        let media_type = if media_type.is_empty() || media_type == "application/octet-stream" {
+            item.mime_type.as_str()
+        } else {
+            media_type
+        };
+
+        if !transcription::is_audio_mime_type(media_type) {
+            tracing::warn!(
+                "⚠️ [audio] Skipping {}: unsupported type {}",
+                item.name,
+                media_type
+            );
+            continue;
+        }
+
+        match base64::Engine::decode(&base64::engine::general_purpose::STANDARD, base64_data) {
+            Ok(bytes) => {
+                tracing::info!(
+                    "🎙️ [audio] Parsed audio: {} - {} ({} bytes)",
+                    item.name,
+                    media_type,
+                    bytes.len()
+                );
+                parsed.push(ParsedAudio {
+                    name: item.name,
+                    media_type: media_type.to_string(),
+                    bytes,
+                });
+            }
+            Err(e) => tracing::error!("Failed to decode audio {}: {}", item.name, e),
+        }
+    }
+
+    parsed
+}
+
+/// Store, transcribe and record each audio attachment. Failed transcriptions are
+/// recorded as error steps and left out of the returned transcripts.
+pub(crate) async fn process_audio_attachments(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    audio: &[ParsedAudio],
+    user_message_id: &str,
+    conversation_id: &str,
+) -> Vec<AudioTranscript> {
+    let mut transcripts = Vec::new();
+
+    for (index, item) in audio.iter().enumerate() {
+        let Some((file_id, storage_path)) =
+            store_audio(state, app, item, user_message_id, conversation_id).await
+        else {
+            continue;
+        };
+
+        events::emit(
+            app,
+            TranscriptionStarted {
+                message_id: user_message_id.to_string(),
+                conversation_id: conversation_id.to_string(),
+                file_id: file_id.clone(),
+            },
+        );
+
+        let started = std::time::Instant::now();
+        let (engine, result) = transcribe(state, app, conversation_id, item, &storage_path).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        let (content, status, error) = match result {
+            Ok(text) if !text.is_empty() => (text, "success", None),
+            Ok(_) => (
+                String::new(),
+                "error",
+                Some("Transcription was empty".to_string()),
+            ),
+            Err(e) => {
+                tracing::error!("❌ [audio] Transcription of {} failed: {}", item.name, e);
+                (String::new(), "error", Some(e))
+            }
+        };
+
+        match state
+            .db
+            .create_transcription(CreateTranscriptionRequest {
+                message_id: user_message_id.to_string(),
+                file_id: Some(file_id.clone()),
+                content: content.clone(),
+                engine: engine.id().to_string(),
+                status: Some(status.to_string()),
+                error: error.clone(),
+                duration_ms: Some(duration_ms),
+                display_order: Some(index as i32),
+            })
+            .await
+        {
+            Ok(step) => {
+                events::emit(
+                    app,
+                    TranscriptionComplete {
+                        message_id: user_message_id.to_string(),
+                        conversation_id: conversation_id.to_string(),
+                        transcription_id: step.id,
+                        file_id: file_id.clone(),
+                        status: status.to_string(),
+                        error: error.clone(),
+                    },
+                );
+            }
+            Err(e) => tracing::error!("Failed to save transcription step: {}", e),
+        }
+
+        if status == "success" {
+            tracing::info!(
+                "✅ [audio] Transcribed {} with {} in {}ms ({} chars)",
+                item.name,
+                engine.id(),
+                duration_ms,
+                content.len()
+            );
+            transcripts.push(AudioTranscript {
+                name: item.name.clone(),
+                content,
+            });
+        }
+    }
+
+    transcripts
+}
+
+/// Append transcripts to the user's text so the model sees what was said
+pub(crate) fn append_transcripts(content: &str, transcripts: &[AudioTranscript]) -> String {
+    if transcripts.is_empty() {
+        return content.to_string();
+    }
+
+    let mut result = content.trim_end().to_string();
+    for t in transcripts {
+        if !result.is_empty() {
+            result.push_str("\n\n");
+        }
+        result.push_str(&format!(
+            "[Voice memo transcript: {}]\n{}",
+            t.name, t.content
+        ));
+    }
+    result
+}
+
+/// Run the configured transcription engine on a stored audio file
+async fn transcribe(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+    audio: &ParsedAudio,
+    storage_path: &str,
+) -> (TranscriptionEngine, Result<String, String>) {
+    let engine = setting(state, transcription::TRANSCRIPTION_ENGINE_KEY)
+        .await
+        .and_then(|id| TranscriptionEngine::from_id(&id))
+        .unwrap_or_default();
+    let language = setting(state, transcription::TRANSCRIPTION_LANGUAGE_KEY).await;
+
+    let result = match engine {
+        TranscriptionEngine::WhisperCpp => {
+            let binary = setting(state, transcription::WHISPER_CPP_BINARY_KEY)
+                .await
+                .unwrap_or_else(|| transcription::DEFAULT_WHISPER_CPP_BINARY.to_string());
+            match setting(state, transcription::WHISPER_CPP_MODEL_KEY).await {
+                Some(model_path) => match crate::storage::get_full_path(app, storage_path) {
+                    Ok(audio_path) => transcription::transcribe_with_whisper_cpp(
+                        &binary,
+                        &model_path,
+                        &audio_path,
+                        language.as_deref(),
+                    )
+                    .await
+                    .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                },
+                None => Err("No whisper.cpp model configured".to_string()),
+            }
+        }
+        TranscriptionEngine::Provider => {
+            let model = setting(state, transcription::TRANSCRIPTION_MODEL_KEY)
+                .await
+                .unwrap_or_else(|| transcription::DEFAULT_TRANSCRIPTION_MODEL.to_string());
+            match resolve_stt_endpoint(
+                state,
+                conversation_id,
+                setting(state, transcription::TRANSCRIPTION_PROVIDER_ID_KEY).await,
+            )
+            .await
+            {
+                Ok((base_url, api_key)) => transcription::transcribe_with_provider(
+                    &base_url,
+                    api_key.as_deref(),
+                    &model,
+                    &audio.name,
+                    &audio.media_type,
+                    &audio.bytes,
+                    language.as_deref(),
+                )
+                .await
+                .map_err(|e| e.to_string()),
+                Err(e) => Err(e),
+            }
+        }
+    };
+
+    (engine, result)
+}
+
+/// Read a non-empty setting value
+async fn setting(state: &AppState, key: &str) -> Option<String> {
+    state
+        .db
+        .get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Resolve (base_url, api_key) for the provider used for speech-to-text. Fails when
+/// the conversation's provider policy doesn't allow it, so the audio is never uploaded.
+async fn resolve_stt_endpoint(
+    state: &AppState,
+    conversation_id: &str,
+    provider_id: Option<String>,
+) -> Result<(String, Option<String>), String> {
+    let provider_id =
+        provider_id.ok_or_else(|| "No transcription provider configured".to_string())?;
+    let provider = state
+        .db
+        .get_provider(&provider_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Provider not found: {}", provider_id))?;
+    crate::commands::enforce_provider_policy_for_provider(state, conversation_id, &provider)
+        .await?;
+
+    let base_url = match provider.base_url.filter(|u| !u.trim().is_empty()) {
+        Some(url) => url,
+        None => match provider.provider_type.as_str() {
+            "openai" => crate::llm::openai::DEFAULT_BASE_URL.to_string(),
+            "groq" => crate::llm::groq::DEFAULT_BASE_URL.to_string(),
+            other => {
+                return Err(format!(
+                    "Provider type {} has no default transcription endpoint; set a base URL",
+                    other
+                ));
+            }
+        },
+    };
+
+    Ok((base_url, provider.api_key))
+}
+
+/// Store the audio file (deduplicated by hash) and link it to the user message.
+/// Returns (file_id, storage_path).
+async fn store_audio(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    audio: &ParsedAudio,
+    user_message_id: &str,
+    conversation_id: &str,
+) -> Option<(String, String)> {
+    let content_hash = crate::storage::hash_bytes(&audio.bytes);
+
+    let storage_path = match state.db.find_file_by_hash(&content_hash).await {
+        Ok(Some(existing)) => {
+            tracing::info!(
+                "♻️ [dedup] Reusing existing audio content for {} (hash: {}...)",
+                audio.name,
+                &content_hash[..16]
+            );
+            existing.storage_path
+        }
+        _ => {
+            let ext = std::path::Path::new(&audio.name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or_else(|| {
+                    crate::storage::get_extension_for_content_type(&audio.media_type)
+                });
+            let storage_path = crate::storage::generate_file_storage_path(&content_hash, ext);
+            if let Err(e) = crate::storage::write_binary(app, &storage_path, &audio.bytes) {
+                tracing::error!("Failed to save audio {}: {}", audio.name, e);
+                return None;
+            }
+            storage_path
+        }
+    };
+
+    let file_attachment = match state
+        .db
+        .create_file_attachment(CreateFileAttachmentRequest {
+            file_name: audio.name.clone(),
+            file_size: audio.bytes.len() as i64,
+            mime_type: audio.media_type.clone(),
+            storage_path: storage_path.clone(),
+            content_hash,
+        })
+        .await
+    {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::error!(
+                "Failed to create file record for audio {}: {}",
+                audio.name,
+                e
+            );
+            return None;
+        }
+    };
+
+    if let Err(e) = state
+        .db
+        .link_message_attachment(user_message_id, &file_attachment.id, None)
+        .await
+    {
+        tracing::error!("Failed to link audio to message: {}", e);
+    } else {
+        tracing::info!(
+            "🎙️ [attachment] Saved audio attachment: {} -> {}",
+            audio.name,
+            file_attachment.id
+        );
+        events::emit(
+            app,
+            AttachmentUpdate::new(user_message_id, conversation_id, &file_attachment.id),
+        );
+    }
+
+    Some((file_attachment.id, storage_path))
+}
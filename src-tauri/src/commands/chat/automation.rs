@@ -0,0 +1,549 @@
+//! Automation runs
+//!
+//! Run by the job queue when an automation is triggered (see `crate::automations`).
+//! Each run is recorded in the automation's history with what its action produced.
+//!
+//! - A prompt is sent into the automation's conversation like a user message: page
+//!   sources go along as URLs to fetch, a new file in a watched folder as an attachment
+//!   (see [`dropped_files`](super::dropped_files)), feed entries since the previous run
+//!   are listed below the prompt, and so is a webhook body or an archived conversation.
+//!   The reply streams in the background and triggers the usual completion
+//!   notification.
+//! - An export writes the conversation to the automation's folder, replacing the file
+//!   of an earlier run.
+//! - An MCP tool is called with the stored arguments.
+
+use super::super::AppState;
+use super::binding;
+use super::dropped_files::{self, DroppedAttachment};
+use crate::exporters::{self, ExportOptions, fenced};
+use crate::models::{
+    Automation, AutomationAction, AutomationEvent, AutomationSourceKind, CreateConversationRequest,
+};
+use crate::web_fetch::{self, Feed};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use tauri::Manager;
+
+/// Newest entries listed per feed
+const MAX_FEED_ENTRIES: usize = 15;
+/// Longer tool results are cut in the run history
+const MAX_OUTPUT_CHARS: usize = 4000;
+const MAX_FILE_NAME_CHARS: usize = 80;
+
+/// Run an automation's action and record the outcome. An error lets the job retry;
+/// nothing has been posted or written at that point.
+pub(crate) async fn run_automation(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    automation_id: &str,
+    event: &AutomationEvent,
+) -> Result<(), String> {
+    // Deleted since the job was queued
+    let Some(automation) = state
+        .db
+        .get_automation(automation_id)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+    // Disabled since; manual runs go ahead regardless
+    if !automation.is_enabled && *event != AutomationEvent::Manual {
+        return Ok(());
+    }
+
+    let run_id = state
+        .db
+        .start_automation_run(automation_id, event)
+        .await
+        .map_err(|e| e.to_string())?;
+    let result = match &automation.action {
+        AutomationAction::RunPrompt { .. } => post_prompt(state, app, &automation, event).await,
+        AutomationAction::ExportConversation { .. } => {
+            export_conversation(state, &automation, event).await
+        }
+        AutomationAction::CallMcpTool { .. } => call_tool(state, &automation, event).await,
+    };
+    if let Err(e) = &result {
+        tracing::warn!("⚠️ [automation] \"{}\" failed: {}", automation.name, e);
+    }
+
+    if let Err(e) = state
+        .db
+        .finish_automation_run(
+            &run_id,
+            result
+                .as_ref()
+                .map(|output| Some(output.as_str()))
+                .map_err(String::as_str),
+        )
+        .await
+    {
+        tracing::error!("❌ [automation] Failed to finish run {}: {}", run_id, e);
+    }
+    if let Err(e) = state
+        .db
+        .record_automation_run(automation_id, result.as_ref().err().map(String::as_str))
+        .await
+    {
+        tracing::error!(
+            "❌ [automation] Failed to record run of {}: {}",
+            automation_id,
+            e
+        );
+    }
+    result.map(|_| ())
+}
+
+/// Returns the conversation posted into
+async fn post_prompt(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    automation: &Automation,
+    event: &AutomationEvent,
+) -> Result<String, String> {
+    let AutomationAction::RunPrompt {
+        prompt,
+        sources,
+        assistant_id,
+        conversation_id,
+    } = &automation.action
+    else {
+        return Err("Not a prompt automation".to_string());
+    };
+    // Read first, so a file that can't be ingested posts nothing
+    let (mut files, mut images, mut audio) = (Vec::new(), Vec::new(), Vec::new());
+    if let AutomationEvent::FileAdded { path } = event {
+        match dropped_files::ingest(Path::new(path)).await? {
+            DroppedAttachment::File(file) => files.push(file),
+            DroppedAttachment::Image(image) => images.push(image),
+            DroppedAttachment::Audio(clip) => audio.push(clip),
+        }
+    }
+    let conversation_id =
+        ensure_conversation(state, automation, conversation_id.as_deref()).await?;
+
+    let mut urls = Vec::new();
+    let mut feeds = Vec::new();
+    for source in sources {
+        match source.kind {
+            AutomationSourceKind::Url => urls.push(source.url.clone()),
+            AutomationSourceKind::Rss => {
+                feeds.push((source.url.clone(), web_fetch::fetch_feed(&source.url).await))
+            }
+        }
+    }
+    let since = automation
+        .last_run_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc));
+    let mut content = build_run_content(prompt, &feeds, since);
+    if let Some(context) = event_context(state, event).await {
+        content.push_str("\n\n");
+        content.push_str(&context);
+    }
+
+    // Without an assistant, send_message falls back to the conversation's binding
+    let assistant = match assistant_id {
+        Some(id) => Some(binding::resolve_assistant_binding(state, id).await?),
+        None => None,
+    };
+    let (provider, model, api_key, base_url, api_style, system_prompt, user_prompt) =
+        match assistant {
+            Some(b) => (
+                Some(b.provider),
+                Some(b.model),
+                b.api_key,
+                b.base_url,
+                b.api_style,
+                b.system_prompt,
+                b.user_prompt,
+            ),
+            None => (None, None, None, None, None, None, None),
+        };
+
+    tracing::info!(
+        "⏰ [automation] Posting \"{}\" into conversation {} ({} page(s), {} feed(s))",
+        automation.name,
+        conversation_id,
+        urls.len(),
+        feeds.len()
+    );
+    super::send_message(
+        app.state::<AppState>(),
+        app.clone(),
+        conversation_id.clone(),
+        content,
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+        None,
+        system_prompt,
+        user_prompt,
+        None,
+        assistant_id.clone(),
+        (!urls.is_empty()).then_some(urls),
+        (!images.is_empty()).then_some(images),
+        (!files.is_empty()).then_some(files),
+        (!audio.is_empty()).then_some(audio),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .map_err(String::from)?;
+    Ok(conversation_id)
+}
+
+/// The automation's conversation, creating it if unset or deleted
+async fn ensure_conversation(
+    state: &AppState,
+    automation: &Automation,
+    conversation_id: Option<&str>,
+) -> Result<String, String> {
+    if let Some(id) = conversation_id
+        && state
+            .db
+            .get_conversation(id)
+            .await
+            .map_err(|e| e.to_string())?
+            .is_some()
+    {
+        return Ok(id.to_string());
+    }
+
+    let conversation = state
+        .db
+        .create_conversation(CreateConversationRequest {
+            title: automation.name.clone(),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    state
+        .db
+        .set_automation_conversation(&automation.id, &conversation.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!(
+        "⏰ [automation] Created conversation {} for \"{}\"",
+        conversation.id,
+        automation.name
+    );
+    Ok(conversation.id)
+}
+
+/// What triggered the run, for the prompt. Scheduled and manual runs add nothing.
+async fn event_context(state: &AppState, event: &AutomationEvent) -> Option<String> {
+    match event {
+        AutomationEvent::Schedule | AutomationEvent::Manual => None,
+        // The file itself is attached
+        AutomationEvent::FileAdded { path } => Some(format!("## New file\n\n{}", path)),
+        AutomationEvent::Webhook { body } if body.trim().is_empty() => {
+            Some("## Webhook received".to_string())
+        }
+        AutomationEvent::Webhook { body } => {
+            Some(format!("## Webhook payload\n\n{}", fenced(body)))
+        }
+        AutomationEvent::ConversationArchived { conversation_id } => {
+            // The transcript without process steps
+            let options = ExportOptions {
+                include_thinking: false,
+                include_tool_calls: false,
+                include_search_decisions: false,
+                include_sources: true,
+            };
+            let transcript = exporters::collect(&state.db, conversation_id, options)
+                .await
+                .and_then(|conversation| {
+                    exporters::render(&conversation, exporters::ExportFormat::Markdown)
+                });
+            Some(match transcript {
+                Ok(transcript) => format!("## Archived conversation\n\n{}", transcript.trim()),
+                Err(e) => format!(
+                    "## Archived conversation\n\nThe conversation could not be loaded: {}",
+                    e
+                ),
+            })
+        }
+    }
+}
+
+/// Returns the file written
+async fn export_conversation(
+    state: &AppState,
+    automation: &Automation,
+    event: &AutomationEvent,
+) -> Result<String, String> {
+    let AutomationAction::ExportConversation {
+        conversation_id,
+        format,
+        directory,
+    } = &automation.action
+    else {
+        return Err("Not an export automation".to_string());
+    };
+    let conversation_id = match (conversation_id, event) {
+        (Some(id), _) => id.as_str(),
+        (None, AutomationEvent::ConversationArchived { conversation_id }) => {
+            conversation_id.as_str()
+        }
+        (None, _) => return Err("No conversation to export".to_string()),
+    };
+
+    let conversation = exporters::collect(&state.db, conversation_id, ExportOptions::default())
+        .await
+        .map_err(|e| e.to_string())?;
+    let rendered = exporters::render(&conversation, *format).map_err(|e| e.to_string())?;
+    tokio::fs::create_dir_all(directory)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", directory, e))?;
+    let destination = Path::new(directory).join(export_file_name(
+        &conversation.title,
+        &conversation.id,
+        format.extension(),
+    ));
+    tokio::fs::write(&destination, rendered)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", destination.display(), e))?;
+
+    tracing::info!(
+        "📤 [automation] \"{}\" exported conversation {} to {}",
+        automation.name,
+        conversation.id,
+        destination.display()
+    );
+    Ok(destination.to_string_lossy().to_string())
+}
+
+/// "<title> (<id prefix>).<ext>", so each conversation keeps one file and titles that
+/// clean up to the same name don't collide
+fn export_file_name(title: &str, id: &str, extension: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.');
+    let stem = if cleaned.is_empty() {
+        "Conversation"
+    } else {
+        cleaned
+    };
+    // v7 ids share their leading timestamp digits, so use the random tail
+    let suffix = id.get(id.len().saturating_sub(8)..).unwrap_or(id);
+    format!("{} ({}).{}", stem, suffix, extension)
+}
+
+/// Returns the tool's result, shortened for the run history
+async fn call_tool(
+    state: &AppState,
+    automation: &Automation,
+    event: &AutomationEvent,
+) -> Result<String, String> {
+    let AutomationAction::CallMcpTool {
+        server_id,
+        tool_name,
+        arguments,
+    } = &automation.action
+    else {
+        return Err("Not an MCP tool automation".to_string());
+    };
+    let server = state
+        .db
+        .get_tool(server_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !server.is_enabled {
+        return Err(format!("MCP server \"{}\" is disabled", server.name));
+    }
+    let connection = state
+        .mcp_manager
+        .get_or_connect(&server)
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", server.name, e))?;
+    if !connection
+        .mcp_tools
+        .iter()
+        .any(|tool| tool.name == tool_name.as_str())
+    {
+        return Err(format!("{} has no tool named {}", server.name, tool_name));
+    }
+
+    tracing::info!(
+        "🔌 [automation] \"{}\" calling '{}' on server '{}'",
+        automation.name,
+        tool_name,
+        server.name
+    );
+    let arguments = fill_arguments(arguments, event.input());
+    let output = crate::llm::tools::call_mcp_tool(
+        &connection.client,
+        tool_name,
+        &arguments,
+        server.get_transport_type(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(if output.chars().count() > MAX_OUTPUT_CHARS {
+        let short: String = output.chars().take(MAX_OUTPUT_CHARS - 1).collect();
+        format!("{}…", short)
+    } else {
+        output
+    })
+}
+
+/// Replace `{{input}}` in every string of the arguments
+fn fill_arguments(arguments: &serde_json::Value, input: &str) -> serde_json::Value {
+    use serde_json::Value;
+    match arguments {
+        Value::String(s) => Value::String(s.replace("{{input}}", input)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| fill_arguments(item, input))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), fill_arguments(value, input)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// The prompt followed by each feed's entries published after `since` (entries
+/// without a date are always listed)
+fn build_run_content(
+    prompt: &str,
+    feeds: &[(String, Result<Feed, String>)],
+    since: Option<DateTime<Utc>>,
+) -> String {
+    let mut content = prompt.trim().to_string();
+    for (url, feed) in feeds {
+        let feed = match feed {
+            Ok(feed) => feed,
+            Err(e) => {
+                content.push_str(&format!(
+                    "\n\n## {}\n\nThe feed could not be loaded: {}",
+                    url, e
+                ));
+                continue;
+            }
+        };
+        content.push_str(&format!("\n\n## {}", feed.title.as_deref().unwrap_or(url)));
+
+        let entries: Vec<_> = feed
+            .items
+            .iter()
+            .filter(|item| match (item.published, since) {
+                (Some(published), Some(since)) => published > since,
+                _ => true,
+            })
+            .take(MAX_FEED_ENTRIES)
+            .collect();
+        if entries.is_empty() {
+            content.push_str("\n\nNo new entries since the last run.");
+            continue;
+        }
+        content.push('\n');
+        for item in entries {
+            let title = if item.title.is_empty() {
+                "Untitled"
+            } else {
+                item.title.as_str()
+            };
+            content.push_str(&match &item.link {
+                Some(link) => format!("\n- [{}]({})", title, link),
+                None => format!("\n- {}", title),
+            });
+            if let Some(published) = item.published {
+                content.push_str(&format!(" ({})", published.format("%Y-%m-%d")));
+            }
+            if let Some(summary) = &item.summary {
+                content.push_str(&format!(": {}", summary));
+            }
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web_fetch::FeedItem;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_export_file_name() {
+        let id = "0197a1b2-c3d4-7e5f-8a9b-0c1d2e3f4a5b";
+        assert_eq!(
+            export_file_name("Trip: Lisbon / Porto?", id, "md"),
+            "Trip_ Lisbon _ Porto_ (2e3f4a5b).md"
+        );
+        assert_eq!(
+            export_file_name("...", id, "html"),
+            "Conversation (2e3f4a5b).html"
+        );
+    }
+
+    #[test]
+    fn test_fill_arguments() {
+        let arguments = serde_json::json!({
+            "path": "{{input}}",
+            "tags": ["inbox", "from {{input}}"],
+            "limit": 3
+        });
+        assert_eq!(
+            fill_arguments(&arguments, "/tmp/a.txt"),
+            serde_json::json!({
+                "path": "/tmp/a.txt",
+                "tags": ["inbox", "from /tmp/a.txt"],
+                "limit": 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_run_content() {
+        let item = |title: &str, day: u32| FeedItem {
+            title: title.to_string(),
+            link: Some(format!("https://example.com/{}", day)),
+            summary: None,
+            published: Some(Utc.with_ymd_and_hms(2025, 6, day, 8, 0, 0).unwrap()),
+        };
+        let feeds = vec![
+            (
+                "https://example.com/feed".to_string(),
+                Ok(Feed {
+                    title: Some("Example".to_string()),
+                    items: vec![item("New", 10), item("Old", 8)],
+                }),
+            ),
+            (
+                "https://down.example/rss".to_string(),
+                Err("Feed returned status: 503".to_string()),
+            ),
+        ];
+        let since = Utc.with_ymd_and_hms(2025, 6, 9, 7, 0, 0).unwrap();
+        let content = build_run_content("Summarize the news.", &feeds, Some(since));
+        assert_eq!(
+            content,
+            "Summarize the news.\n\n## Example\n\n- [New](https://example.com/10) (2025-06-10)\
+             \n\n## https://down.example/rss\n\nThe feed could not be loaded: Feed returned status: 503"
+        );
+    }
+}
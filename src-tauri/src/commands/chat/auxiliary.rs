@@ -0,0 +1,142 @@
+//! Cancellation registry for auxiliary LLM calls
+//!
+//! Title generation, summaries and search decisions are short non-streaming calls made
+//! around the main generation. Each conversation gets a parent token here; every call
+//! runs on a child token, so stopping or deleting the conversation (or quitting the app)
+//! aborts them instead of leaving requests hanging on the runtime. The entry only lives
+//! while calls are in flight: the [`AuxiliaryCall`] guard of the last one removes it.
+//! Ollama model pulls register here too, under `ollama-pull:<model>`.
+//!
+//! [`auxiliary_text`] turns the response of such a call into its answer text.
+
+use super::super::{AppState, AuxiliaryTasks};
+use crate::error::{AppError, ErrorKind};
+use crate::llm::ChatResponse;
+use crate::thinking_parser;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio_util::sync::CancellationToken;
+
+/// Parent token of a conversation's auxiliary calls and how many are in flight
+pub(crate) struct AuxiliaryEntry {
+    token: CancellationToken,
+    /// Tells a re-registered entry apart from one cancelled while its calls still ran
+    id: u64,
+    calls: usize,
+}
+
+static NEXT_ENTRY_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An in-flight auxiliary call. Keep it alive until the call finishes; dropping the
+/// last one for a conversation removes the conversation's entry.
+pub(crate) struct AuxiliaryCall {
+    tasks: AuxiliaryTasks,
+    conversation_id: String,
+    entry_id: u64,
+    token: CancellationToken,
+}
+
+impl AuxiliaryCall {
+    /// Token to pass to the provider call
+    pub(crate) fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for AuxiliaryCall {
+    fn drop(&mut self) {
+        let conversation_id = std::mem::take(&mut self.conversation_id);
+        let entry_id = self.entry_id;
+        match self.tasks.try_write() {
+            Ok(mut tasks) => release(&mut tasks, &conversation_id, entry_id),
+            Err(_) => {
+                let tasks = self.tasks.clone();
+                tauri::async_runtime::spawn(async move {
+                    release(&mut *tasks.write().await, &conversation_id, entry_id);
+                });
+            }
+        }
+    }
+}
+
+fn release(
+    tasks: &mut std::collections::HashMap<String, AuxiliaryEntry>,
+    conversation_id: &str,
+    entry_id: u64,
+) {
+    if let Some(entry) = tasks.get_mut(conversation_id)
+        && entry.id == entry_id
+    {
+        entry.calls -= 1;
+        if entry.calls == 0 {
+            tasks.remove(conversation_id);
+        }
+    }
+}
+
+/// Register an auxiliary call in `conversation_id`
+pub(crate) async fn auxiliary_call(state: &AppState, conversation_id: &str) -> AuxiliaryCall {
+    let mut tasks = state.auxiliary_tasks.write().await;
+    let entry = tasks
+        .entry(conversation_id.to_string())
+        .or_insert_with(|| AuxiliaryEntry {
+            token: CancellationToken::new(),
+            id: NEXT_ENTRY_ID.fetch_add(1, Ordering::Relaxed),
+            calls: 0,
+        });
+    entry.calls += 1;
+    AuxiliaryCall {
+        tasks: state.auxiliary_tasks.clone(),
+        conversation_id: conversation_id.to_string(),
+        entry_id: entry.id,
+        token: entry.token.child_token(),
+    }
+}
+
+/// Cancel all auxiliary calls for a conversation. Returns true if any were in flight.
+pub(crate) async fn cancel_auxiliary(state: &AppState, conversation_id: &str) -> bool {
+    match state.auxiliary_tasks.write().await.remove(conversation_id) {
+        Some(entry) => {
+            entry.token.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Cancel every generation and auxiliary call (used on app exit, outside the async runtime)
+pub(crate) fn cancel_all_sync(state: &AppState) {
+    if let Ok(tasks) = state.generation_tasks.try_read() {
+        for token in tasks.values() {
+            token.cancel();
+        }
+    }
+    if let Ok(tasks) = state.auxiliary_tasks.try_read() {
+        for entry in tasks.values() {
+            entry.token.cancel();
+        }
+    }
+}
+
+/// The answer of an auxiliary call, without the think tags reasoning models may wrap
+/// their deliberation in. An empty answer is a provider error naming `what` was asked
+/// for ("translation", "summary", ...).
+pub(crate) fn auxiliary_text(
+    response: &ChatResponse,
+    model: &str,
+    provider: &str,
+    what: &str,
+) -> Result<String, AppError> {
+    let parsed = thinking_parser::parse_thinking_content_with(
+        &response.content,
+        thinking_parser::formats_for_model(model),
+    );
+    let text = parsed.content.trim();
+    if text.is_empty() {
+        return Err(AppError::new(
+            ErrorKind::Provider,
+            format!("Model returned an empty {}", what),
+        )
+        .with_provider_type(provider));
+    }
+    Ok(text.to_string())
+}
@@ -0,0 +1,238 @@
+//! Conversation model binding
+//!
+//! Resolves which model/assistant a message should be sent with. The frontend normally
+//! passes provider/model explicitly; when it doesn't, we fall back to the binding stored
+//! in conversation settings so a conversation keeps its model across devices and reinstalls,
+//! and finally to the default model roles.
+
+use super::super::AppState;
+use crate::models::ModelRole;
+
+/// Fully resolved provider/model configuration for a generation request
+pub(crate) struct ResolvedBinding {
+    pub provider: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub base_url: Option<String>,
+    pub api_style: Option<String>,
+    pub model_db_id: Option<String>,
+    pub assistant_db_id: Option<String>,
+    /// Assistant system prompt (only set when resolved from a stored assistant binding)
+    pub system_prompt: Option<String>,
+    /// Assistant user prompt (only set when resolved from a stored assistant binding)
+    pub user_prompt: Option<String>,
+}
+
+/// Resolve provider connection info for a model DB id.
+/// Returns (provider_type, model_id, api_key, base_url, api_style).
+pub(crate) async fn resolve_model_connection(
+    state: &AppState,
+    model_db_id: &str,
+) -> Result<
+    (
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ),
+    String,
+> {
+    let model = state
+        .db
+        .get_model(model_db_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Model not found: {}", model_db_id))?;
+
+    let provider = state
+        .db
+        .get_provider(&model.provider_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Provider not found: {}", model.provider_id))?;
+
+    Ok((
+        provider.provider_type,
+        model.model_id,
+        provider.api_key,
+        provider.base_url,
+        provider.api_style,
+    ))
+}
+
+/// Resolve a binding for an assistant: its model's connection plus its prompts
+pub(crate) async fn resolve_assistant_binding(
+    state: &AppState,
+    assistant_id: &str,
+) -> Result<ResolvedBinding, String> {
+    let assistant = state
+        .db
+        .get_assistant(assistant_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Assistant not found: {}", assistant_id))?;
+
+    let (provider, model, api_key, base_url, api_style) =
+        resolve_model_connection(state, &assistant.model_id).await?;
+
+    Ok(ResolvedBinding {
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+        model_db_id: None,
+        assistant_db_id: Some(assistant.id),
+        system_prompt: Some(assistant.system_prompt),
+        user_prompt: assistant.user_prompt,
+    })
+}
+
+/// Resolve a binding for a bare model
+pub(crate) async fn resolve_model_binding(
+    state: &AppState,
+    model_db_id: &str,
+) -> Result<ResolvedBinding, String> {
+    let (provider, model, api_key, base_url, api_style) =
+        resolve_model_connection(state, model_db_id).await?;
+
+    Ok(ResolvedBinding {
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+        model_db_id: Some(model_db_id.to_string()),
+        assistant_db_id: None,
+        system_prompt: None,
+        user_prompt: None,
+    })
+}
+
+/// Resolve the model assigned to a role, if one is set and still exists
+pub(crate) async fn resolve_role_binding(
+    state: &AppState,
+    role: ModelRole,
+) -> Option<ResolvedBinding> {
+    let model_db_id = state
+        .db
+        .resolve_model_reference(role.id())
+        .await
+        .ok()
+        .flatten()?;
+
+    match resolve_model_binding(state, &model_db_id).await {
+        Ok(binding) => Some(binding),
+        Err(e) => {
+            tracing::warn!(
+                "⚠️ [binding] Failed to resolve '{}' role model: {}",
+                role.id(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Resolve a role model for an auxiliary call (title, summary, search decision, ...)
+/// made for a conversation. A role model the conversation's provider policy doesn't
+/// allow is passed over, so callers fall back to the conversation's own model or skip
+/// the call instead of sending conversation content to a disallowed provider.
+pub(crate) async fn resolve_allowed_role_binding(
+    state: &AppState,
+    role: ModelRole,
+    conversation_id: &str,
+) -> Option<ResolvedBinding> {
+    let binding = resolve_role_binding(state, role).await?;
+    match crate::commands::enforce_provider_policy(
+        state,
+        conversation_id,
+        &binding.provider,
+        binding.base_url.as_deref(),
+        binding.model_db_id.as_deref(),
+        None,
+    )
+    .await
+    {
+        Ok(()) => Some(binding),
+        Err(e) => {
+            tracing::info!(
+                "🛡️ [binding] Not using the '{}' role model in conversation {}: {}",
+                role.id(),
+                conversation_id,
+                e.message
+            );
+            None
+        }
+    }
+}
+
+/// Resolve the binding stored in conversation settings.
+///
+/// An assistant binding takes precedence over a bare model binding. Without a stored
+/// binding, the default chat model (or vision model, when `wants_vision`) is used.
+pub(crate) async fn resolve_stored_binding(
+    state: &AppState,
+    conversation_id: &str,
+    wants_vision: bool,
+) -> Result<ResolvedBinding, String> {
+    let settings = state
+        .db
+        .get_conversation_settings(conversation_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let resolved = if let Some(assistant_id) = settings.selected_assistant_id {
+        resolve_assistant_binding(state, &assistant_id).await?
+    } else if let Some(model_db_id) = settings.selected_model_id {
+        resolve_model_binding(state, &model_db_id).await?
+    } else {
+        let vision = if wants_vision {
+            resolve_role_binding(state, ModelRole::Vision).await
+        } else {
+            None
+        };
+        let fallback = match vision {
+            Some(binding) => Some(binding),
+            None => resolve_role_binding(state, ModelRole::Chat).await,
+        };
+        fallback.ok_or_else(|| "No model selected for this conversation".to_string())?
+    };
+
+    tracing::info!(
+        "🔗 [binding] Using stored binding {}/{} for conversation {}",
+        resolved.provider,
+        resolved.model,
+        conversation_id
+    );
+
+    Ok(resolved)
+}
+
+/// Persist the model/assistant a message was sent with as the conversation's binding
+pub(crate) async fn remember_binding(
+    state: &AppState,
+    conversation_id: &str,
+    model_db_id: &Option<String>,
+    assistant_db_id: &Option<String>,
+) {
+    if model_db_id.is_none() && assistant_db_id.is_none() {
+        return;
+    }
+
+    // An assistant binding supersedes the model it runs on
+    let model_id = if assistant_db_id.is_some() {
+        None
+    } else {
+        model_db_id.as_deref()
+    };
+
+    if let Err(e) = state
+        .db
+        .set_conversation_binding(conversation_id, model_id, assistant_db_id.as_deref())
+        .await
+    {
+        tracing::warn!("⚠️ [binding] Failed to persist conversation binding: {}", e);
+    }
+}
@@ -0,0 +1,203 @@
+//! Coalescing of streamed text and reasoning chunks
+//!
+//! Fast providers (Groq, local models) produce hundreds of tiny chunks per second,
+//! and sending each one costs an IPC message, a JSON serialize and a re-render.
+//! Consecutive chunks of the same kind are buffered and sent together every
+//! `FLUSH_INTERVAL` or once `MAX_BUFFERED_BYTES` accumulate. Other stream events
+//! (tool calls, images) flush the buffer first, so ordering is preserved.
+
+use crate::events::{ChatStream, ChatStreamReasoning, StreamEvent, StreamSink};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+const FLUSH_INTERVAL: Duration = Duration::from_millis(30);
+const MAX_BUFFERED_BYTES: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkKind {
+    Text,
+    Reasoning,
+}
+
+/// Pending chunks of a single kind
+#[derive(Debug)]
+struct ChunkBuffer {
+    kind: ChunkKind,
+    buffer: String,
+    last_flush: Instant,
+}
+
+impl ChunkBuffer {
+    fn new(now: Instant) -> Self {
+        Self {
+            kind: ChunkKind::Text,
+            buffer: String::new(),
+            last_flush: now,
+        }
+    }
+
+    /// Buffer a chunk and return whatever is due to be sent, oldest first
+    fn push(&mut self, kind: ChunkKind, chunk: &str, now: Instant) -> Vec<(ChunkKind, String)> {
+        let mut due = Vec::new();
+        if kind != self.kind && !self.buffer.is_empty() {
+            due.push((self.kind, std::mem::take(&mut self.buffer)));
+        }
+        self.kind = kind;
+        self.buffer.push_str(chunk);
+
+        if self.buffer.len() >= MAX_BUFFERED_BYTES
+            || now.duration_since(self.last_flush) >= FLUSH_INTERVAL
+        {
+            due.extend(self.flush(now));
+        }
+        due
+    }
+
+    fn flush(&mut self, now: Instant) -> Option<(ChunkKind, String)> {
+        self.last_flush = now;
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some((self.kind, std::mem::take(&mut self.buffer)))
+    }
+
+    /// Flush only if the buffer has waited at least one interval
+    fn flush_if_stale(&mut self, now: Instant) -> Option<(ChunkKind, String)> {
+        if now.duration_since(self.last_flush) >= FLUSH_INTERVAL {
+            self.flush(now)
+        } else {
+            None
+        }
+    }
+}
+
+/// Buffers a generation's text/reasoning chunks in front of its [`StreamSink`]
+#[derive(Clone)]
+pub(crate) struct ChunkCoalescer {
+    buffer: Arc<Mutex<ChunkBuffer>>,
+    sink: StreamSink,
+    conversation_id: String,
+}
+
+impl ChunkCoalescer {
+    pub(crate) fn new(sink: StreamSink, conversation_id: String) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(ChunkBuffer::new(Instant::now()))),
+            sink,
+            conversation_id,
+        }
+    }
+
+    pub(crate) fn push(&self, kind: ChunkKind, chunk: &str) {
+        // Send while holding the lock so the timer can't reorder chunks
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        for (kind, content) in buffer.push(kind, chunk, Instant::now()) {
+            self.send(kind, content);
+        }
+    }
+
+    /// Send buffered chunks now, e.g. when the stream ends
+    pub(crate) fn flush(&self) {
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        if let Some((kind, content)) = buffer.flush(Instant::now()) {
+            self.send(kind, content);
+        }
+    }
+
+    /// Send other stream events through the coalescer to keep them in order
+    pub(crate) fn send_event(&self, event: StreamEvent) {
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        if let Some((kind, content)) = buffer.flush(Instant::now()) {
+            self.send(kind, content);
+        }
+        self.sink.send(event);
+    }
+
+    /// Flush chunks left waiting when the provider pauses, until `stop` is cancelled
+    pub(crate) fn spawn_timer(&self, stop: CancellationToken) {
+        let coalescer = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = stop.cancelled() => break,
+                    _ = interval.tick() => {
+                        let Ok(mut buffer) = coalescer.buffer.lock() else {
+                            break;
+                        };
+                        if let Some((kind, content)) = buffer.flush_if_stale(Instant::now()) {
+                            coalescer.send(kind, content);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn send(&self, kind: ChunkKind, content: String) {
+        let conversation_id = self.conversation_id.clone();
+        self.sink.send(match kind {
+            ChunkKind::Text => StreamEvent::ChatStream(ChatStream {
+                conversation_id,
+                content,
+            }),
+            ChunkKind::Reasoning => StreamEvent::ChatStreamReasoning(ChatStreamReasoning {
+                conversation_id,
+                content,
+            }),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffers_chunks_within_interval() {
+        let start = Instant::now();
+        let mut buffer = ChunkBuffer::new(start);
+
+        assert!(buffer.push(ChunkKind::Text, "Hel", start).is_empty());
+        assert!(
+            buffer
+                .push(ChunkKind::Text, "lo", start + Duration::from_millis(10))
+                .is_empty()
+        );
+        let due = buffer.push(ChunkKind::Text, "!", start + FLUSH_INTERVAL);
+        assert_eq!(due, vec![(ChunkKind::Text, "Hello!".to_string())]);
+    }
+
+    #[test]
+    fn test_kind_change_flushes_previous_kind_first() {
+        let start = Instant::now();
+        let mut buffer = ChunkBuffer::new(start);
+
+        buffer.push(ChunkKind::Reasoning, "thinking", start);
+        let due = buffer.push(ChunkKind::Text, "answer", start);
+        assert_eq!(due, vec![(ChunkKind::Reasoning, "thinking".to_string())]);
+        assert_eq!(
+            buffer.flush(start),
+            Some((ChunkKind::Text, "answer".to_string()))
+        );
+        assert_eq!(buffer.flush(start), None);
+    }
+
+    #[test]
+    fn test_large_buffer_flushes_immediately() {
+        let start = Instant::now();
+        let mut buffer = ChunkBuffer::new(start);
+        let chunk = "x".repeat(MAX_BUFFERED_BYTES);
+
+        let due = buffer.push(ChunkKind::Text, &chunk, start);
+        assert_eq!(due.len(), 1);
+        assert!(buffer.flush_if_stale(start).is_none());
+    }
+}
@@ -0,0 +1,109 @@
+//! Inline citations of fetched pages
+//!
+//! Fetched pages are numbered in the prompt (see
+//! [`crate::web_fetch::build_llm_content_with_attachments`]) and the model is asked to
+//! cite them as `[1]`, `[2]`. Once the reply is saved, the markers it actually used are
+//! mapped to the pages' fetch results and stored with the assistant message, so the
+//! frontend can turn them into links.
+
+use super::super::AppState;
+use crate::web_fetch::FetchedWebResource;
+
+/// The fetch result behind each source number in the prompt: `[n]` cites entry `n - 1`,
+/// which is `None` when the page couldn't be stored
+pub(crate) fn numbered_sources(
+    resources: &[FetchedWebResource],
+    fetch_result_ids: &[Option<String>],
+) -> Vec<Option<String>> {
+    resources
+        .iter()
+        .zip(fetch_result_ids)
+        .filter(|(resource, _)| resource.extraction_error.is_none())
+        .map(|(_, id)| id.clone())
+        .collect()
+}
+
+/// Map the markers cited in a saved reply to `sources` and store them with the message
+pub(crate) async fn store_citations(
+    state: &AppState,
+    message_id: &str,
+    content: &str,
+    sources: &[Option<String>],
+) {
+    if sources.is_empty() {
+        return;
+    }
+    let citations: Vec<(i32, String)> = cited_numbers(content)
+        .into_iter()
+        .filter_map(|number| {
+            let fetch_result_id = sources.get(number.checked_sub(1)?)?.clone()?;
+            Some((number as i32, fetch_result_id))
+        })
+        .collect();
+    if citations.is_empty() {
+        return;
+    }
+
+    match state.db.set_message_citations(message_id, &citations).await {
+        Ok(()) => tracing::info!(
+            "🔗 [citations] Stored {} citation(s) for message {}",
+            citations.len(),
+            message_id
+        ),
+        Err(e) => tracing::error!("Failed to store citations: {}", e),
+    }
+}
+
+/// Numbers cited as `[n]` or `[n, m]`, in order of first use. Markdown links (`[1](...)`)
+/// and code are not citations.
+fn cited_numbers(content: &str) -> Vec<usize> {
+    let mut numbers = Vec::new();
+    let mut in_fence = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        // Odd segments are inside inline code spans
+        for segment in line.split('`').step_by(2) {
+            let mut rest = segment;
+            while let Some(start) = rest.find('[') {
+                rest = &rest[start + 1..];
+                let Some(end) = rest.find(']') else {
+                    break;
+                };
+                if rest[end + 1..].starts_with('(') {
+                    continue;
+                }
+                let cited: Option<Vec<usize>> = rest[..end]
+                    .split(',')
+                    .map(|n| n.trim().parse().ok())
+                    .collect();
+                for number in cited.unwrap_or_default() {
+                    if number > 0 && !numbers.contains(&number) {
+                        numbers.push(number);
+                    }
+                }
+            }
+        }
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cited_numbers() {
+        let content = "Rust 1.80 shipped in July [2]. It stabilized LazyLock [1][3, 2].\n\
+            See [the notes](https://example.com) and [4](https://example.com/4).\n\
+            - [ ] not a citation, nor is `arr[5]`\n\
+            ```\nlet x = v[6];\n```\n\
+            Back to prose [7].";
+        assert_eq!(cited_numbers(content), vec![2, 1, 3, 7]);
+    }
+}
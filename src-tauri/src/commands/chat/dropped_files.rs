@@ -0,0 +1,165 @@
+//! Files dropped into a watched folder
+//!
+//! When a folder-watch automation posts a prompt, the new file goes along as the
+//! attachment a user would have added: text files and PDFs (as their extracted text) as
+//! documents, zips as archives, images and audio as media. Other binary files fail the
+//! run.
+
+use super::types::{AudioAttachmentInput, FileAttachmentInput, ImageAttachmentInput};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use std::path::Path;
+
+/// Larger files are not ingested
+const MAX_DROPPED_FILE_BYTES: u64 = 20 * 1024 * 1024;
+/// Text documents are sent whole, so they get a tighter limit
+const MAX_TEXT_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+pub(crate) enum DroppedAttachment {
+    File(FileAttachmentInput),
+    Image(ImageAttachmentInput),
+    Audio(AudioAttachmentInput),
+}
+
+#[derive(Debug, PartialEq)]
+enum FileKind {
+    Text(&'static str),
+    Pdf,
+    Zip,
+    Image(&'static str),
+    Audio(&'static str),
+}
+
+/// Kind and MIME type by extension; `None` for extensions read as text if they decode
+fn classify(extension: &str) -> Option<FileKind> {
+    let kind = match extension.to_lowercase().as_str() {
+        "pdf" => FileKind::Pdf,
+        "zip" => FileKind::Zip,
+        "png" => FileKind::Image("image/png"),
+        "jpg" | "jpeg" => FileKind::Image("image/jpeg"),
+        "gif" => FileKind::Image("image/gif"),
+        "webp" => FileKind::Image("image/webp"),
+        "bmp" => FileKind::Image("image/bmp"),
+        "mp3" => FileKind::Audio("audio/mpeg"),
+        "m4a" => FileKind::Audio("audio/mp4"),
+        "wav" => FileKind::Audio("audio/wav"),
+        "ogg" => FileKind::Audio("audio/ogg"),
+        "flac" => FileKind::Audio("audio/flac"),
+        "md" | "markdown" => FileKind::Text("text/markdown"),
+        "json" => FileKind::Text("application/json"),
+        "html" | "htm" => FileKind::Text("text/html"),
+        "xml" => FileKind::Text("text/xml"),
+        "csv" => FileKind::Text("text/csv"),
+        "yaml" | "yml" => FileKind::Text("text/yaml"),
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Read a new file into an attachment
+pub(crate) async fn ingest(path: &Path) -> Result<DroppedAttachment, String> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Not a file: {}", path.display()))?;
+    let size = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?
+        .len();
+    if size > MAX_DROPPED_FILE_BYTES {
+        return Err(format!("{} is too large ({} bytes)", name, size));
+    }
+    let kind = path
+        .extension()
+        .and_then(|extension| classify(&extension.to_string_lossy()))
+        .unwrap_or(FileKind::Text("text/plain"));
+    tracing::info!(
+        "📥 [automation] Ingesting {} ({} bytes, {:?})",
+        name,
+        size,
+        kind
+    );
+
+    let data_url = |mime_type: &str, bytes: &[u8]| {
+        format!("data:{};base64,{}", mime_type, STANDARD.encode(bytes))
+    };
+    match kind {
+        FileKind::Text(mime_type) => {
+            if size > MAX_TEXT_FILE_BYTES {
+                return Err(format!("{} is too large to read as text", name));
+            }
+            let bytes = read(path, &name).await?;
+            let content = String::from_utf8(bytes)
+                .ok()
+                .filter(|text| !text.contains('\0'))
+                .ok_or_else(|| format!("{} isn't a supported document type", name))?;
+            Ok(DroppedAttachment::File(FileAttachmentInput {
+                name,
+                content,
+                mime_type: mime_type.to_string(),
+            }))
+        }
+        FileKind::Pdf => {
+            let pdf = path.to_path_buf();
+            let text = tokio::task::spawn_blocking(move || pdf_extract::extract_text(&pdf))
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| format!("Failed to extract text from {}: {}", name, e))?;
+            if text.trim().is_empty() {
+                return Err(format!(
+                    "{} has no extractable text (it may contain only scanned images)",
+                    name
+                ));
+            }
+            Ok(DroppedAttachment::File(FileAttachmentInput {
+                name,
+                content: text,
+                mime_type: "text/plain".to_string(),
+            }))
+        }
+        FileKind::Zip => {
+            let bytes = read(path, &name).await?;
+            Ok(DroppedAttachment::File(FileAttachmentInput {
+                content: data_url("application/zip", &bytes),
+                name,
+                mime_type: "application/zip".to_string(),
+            }))
+        }
+        FileKind::Image(mime_type) => {
+            let bytes = read(path, &name).await?;
+            Ok(DroppedAttachment::Image(ImageAttachmentInput {
+                base64: data_url(mime_type, &bytes),
+                name,
+                mime_type: mime_type.to_string(),
+            }))
+        }
+        FileKind::Audio(mime_type) => {
+            let bytes = read(path, &name).await?;
+            Ok(DroppedAttachment::Audio(AudioAttachmentInput {
+                base64: data_url(mime_type, &bytes),
+                name,
+                mime_type: mime_type.to_string(),
+            }))
+        }
+    }
+}
+
+async fn read(path: &Path, name: &str) -> Result<Vec<u8>, String> {
+    tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("PDF"), Some(FileKind::Pdf));
+        assert_eq!(classify("jpeg"), Some(FileKind::Image("image/jpeg")));
+        assert_eq!(classify("m4a"), Some(FileKind::Audio("audio/mp4")));
+        assert_eq!(classify("md"), Some(FileKind::Text("text/markdown")));
+        // Read as text if it decodes
+        assert_eq!(classify("rs"), None);
+    }
+}
@@ -0,0 +1,241 @@
+//! Suggested follow-up questions
+//!
+//! Opt-in post-generation step: after a response is saved, the "fast" role model
+//! (falling back to the model that answered, also when the conversation's provider
+//! policy rules the fast model out) suggests a few follow-up prompts that the
+//! frontend renders as quick-reply chips. This runs after `chat-complete`, so the
+//! generation (and its provider slot) is already released; the suggestions arrive in a
+//! `follow-up-suggestions` event.
+
+use super::super::AppState;
+use super::binding;
+use crate::events::{self, FollowUpSuggestions};
+use crate::llm::{self, ChatMessage};
+use crate::models::ModelRole;
+use crate::prompts;
+use std::time::Duration;
+
+/// Settings key: "true" enables follow-up suggestions after each response
+pub const FOLLOW_UP_SUGGESTIONS_ENABLED_KEY: &str = "follow_up_suggestions_enabled";
+
+/// Number of suggestions kept from the model output
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Responses are truncated before being sent to keep the request small
+const MAX_RESPONSE_CHARS: usize = 4000;
+
+/// Suggestions that take longer are dropped
+const SUGGESTIONS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether follow-up suggestions are enabled in settings
+pub(crate) async fn follow_ups_enabled(state: &AppState) -> bool {
+    matches!(
+        state
+            .db
+            .get_setting(FOLLOW_UP_SUGGESTIONS_ENABLED_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    )
+}
+
+/// Generate suggestions for a saved reply in the background and emit them
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_follow_up_suggestions(
+    state: AppState,
+    app: tauri::AppHandle,
+    conversation_id: String,
+    message_id: String,
+    user_message: String,
+    response: String,
+    provider: String,
+    model: String,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    api_style: Option<String>,
+) {
+    tokio::spawn(async move {
+        let generated = tokio::time::timeout(
+            SUGGESTIONS_TIMEOUT,
+            generate_follow_up_suggestions(
+                &state,
+                &conversation_id,
+                &message_id,
+                &user_message,
+                &response,
+                &provider,
+                &model,
+                api_key,
+                base_url,
+                api_style,
+            ),
+        )
+        .await;
+        match generated {
+            Ok(Some(suggestions)) => events::emit(
+                &app,
+                FollowUpSuggestions {
+                    conversation_id,
+                    message_id,
+                    suggestions,
+                },
+            ),
+            Ok(None) => {}
+            Err(_) => tracing::warn!(
+                "⚠️ [follow_ups] Gave up on suggestions for message {} after {}s",
+                message_id,
+                SUGGESTIONS_TIMEOUT.as_secs()
+            ),
+        }
+    });
+}
+
+/// Generate follow-up suggestions and attach them to the assistant message.
+/// Failures are logged and yield `None` so they never affect the response itself.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn generate_follow_up_suggestions(
+    state: &AppState,
+    conversation_id: &str,
+    message_id: &str,
+    user_message: &str,
+    response: &str,
+    provider: &str,
+    model: &str,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    api_style: Option<String>,
+) -> Option<Vec<String>> {
+    let (provider, model, api_key, base_url, api_style) =
+        match binding::resolve_allowed_role_binding(state, ModelRole::Fast, conversation_id).await {
+            Some(fast) => (
+                fast.provider,
+                fast.model,
+                fast.api_key,
+                fast.base_url,
+                fast.api_style,
+            ),
+            None => (
+                provider.to_string(),
+                model.to_string(),
+                api_key,
+                base_url,
+                api_style,
+            ),
+        };
+
+    let response: String = response.chars().take(MAX_RESPONSE_CHARS).collect();
+    let auxiliary = super::auxiliary::auxiliary_call(state, conversation_id).await;
+    let result = llm::call_provider(
+        &provider,
+        model,
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::FOLLOW_UP_SUGGESTIONS_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_follow_up_suggestions_user_prompt(user_message, &response),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary.token(),
+    )
+    .await;
+
+    let suggestions = match result {
+        Ok(r) => parse_suggestions(&r.content),
+        Err(e) => {
+            tracing::warn!("⚠️ [follow_ups] Failed to generate suggestions: {}", e);
+            return None;
+        }
+    };
+
+    if suggestions.is_empty() {
+        tracing::warn!("⚠️ [follow_ups] Model returned no usable suggestions");
+        return None;
+    }
+
+    if let Err(e) = state
+        .db
+        .set_message_follow_up_suggestions(message_id, &suggestions)
+        .await
+    {
+        tracing::error!("❌ [follow_ups] Failed to store suggestions: {}", e);
+    }
+
+    tracing::info!(
+        "💡 [follow_ups] Generated {} suggestion(s) for message {}",
+        suggestions.len(),
+        message_id
+    );
+    Some(suggestions)
+}
+
+/// Parse the model output: a JSON array (possibly inside a code block), or one
+/// suggestion per line as a fallback for models that ignore the format
+fn parse_suggestions(raw: &str) -> Vec<String> {
+    let trimmed = raw.trim();
+    let json_part = match (trimmed.find('['), trimmed.rfind(']')) {
+        (Some(start), Some(end)) if start < end => Some(&trimmed[start..=end]),
+        _ => None,
+    };
+
+    let candidates: Vec<String> = match json_part.and_then(|j| serde_json::from_str(j).ok()) {
+        Some(list) => list,
+        None => trimmed
+            .lines()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches(|c: char| {
+                        c.is_ascii_digit() || c == '-' || c == '*' || c == '.' || c == ')'
+                    })
+                    .to_string()
+            })
+            .collect(),
+    };
+
+    candidates
+        .into_iter()
+        .map(|s| s.trim().trim_matches('"').trim().to_string())
+        .filter(|s| !s.is_empty() && !s.starts_with("```"))
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_suggestions_json_array() {
+        let result = parse_suggestions(r#"["One?", "Two?", "Three?", "Four?"]"#);
+        assert_eq!(result, vec!["One?", "Two?", "Three?"]);
+    }
+
+    #[test]
+    fn test_parse_suggestions_code_block() {
+        let result = parse_suggestions("```json\n[\"A\", \"B\"]\n```");
+        assert_eq!(result, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_parse_suggestions_line_fallback() {
+        let result = parse_suggestions("1. First idea\n2. Second idea\n\n- Third idea");
+        assert_eq!(result, vec!["First idea", "Second idea", "Third idea"]);
+    }
+}
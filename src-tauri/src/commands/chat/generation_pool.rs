@@ -0,0 +1,205 @@
+//! Concurrency cap for generations
+//!
+//! Each send runs in its own task, and without a cap a burst of messages (or a
+//! roundtable next to a few open chats) hits the provider all at once, which a local
+//! Ollama can't keep up with. Generations wait for a slot in [`GenerationPool`]
+//! instead: at most [`MAX_CONCURRENT_GENERATIONS_KEY`] run at once, and provider types
+//! listed in [`PROVIDER_GENERATION_LIMITS_KEY`] have their own, lower cap. Limits are
+//! read from settings whenever a generation looks for a slot.
+
+use crate::db::Database;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+/// Settings key: generations running at once across all providers
+pub const MAX_CONCURRENT_GENERATIONS_KEY: &str = "max_concurrent_generations";
+
+/// Settings key: JSON object of per-provider caps, e.g. `{"ollama": 2}`; 0 lifts the
+/// provider's own cap
+pub const PROVIDER_GENERATION_LIMITS_KEY: &str = "provider_generation_limits";
+
+const DEFAULT_MAX_CONCURRENT_GENERATIONS: usize = 4;
+
+/// Local models share one machine's GPU, so they run one at a time by default
+const DEFAULT_PROVIDER_LIMITS: &[(&str, usize)] = &[("ollama", 1)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GenerationLimits {
+    pub max_total: usize,
+    pub per_provider: HashMap<String, usize>,
+}
+
+impl Default for GenerationLimits {
+    fn default() -> Self {
+        Self {
+            max_total: DEFAULT_MAX_CONCURRENT_GENERATIONS,
+            per_provider: DEFAULT_PROVIDER_LIMITS
+                .iter()
+                .map(|(provider, limit)| (provider.to_string(), *limit))
+                .collect(),
+        }
+    }
+}
+
+impl GenerationLimits {
+    pub async fn load(db: &Database) -> Self {
+        let mut limits = Self::default();
+        if let Some(max) = db
+            .get_setting(MAX_CONCURRENT_GENERATIONS_KEY)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            && max > 0
+        {
+            limits.max_total = max;
+        }
+        if let Some(json) = db
+            .get_setting(PROVIDER_GENERATION_LIMITS_KEY)
+            .await
+            .ok()
+            .flatten()
+        {
+            match serde_json::from_str::<HashMap<String, usize>>(&json) {
+                Ok(caps) => limits.per_provider.extend(caps),
+                Err(e) => tracing::warn!(
+                    "⚠️ [generation_pool] Ignoring invalid provider limits: {}",
+                    e
+                ),
+            }
+        }
+        limits
+    }
+
+    fn provider_limit(&self, provider: &str) -> Option<usize> {
+        self.per_provider
+            .get(provider)
+            .copied()
+            .filter(|limit| *limit > 0)
+    }
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    running: usize,
+    running_by_provider: HashMap<String, usize>,
+}
+
+impl PoolState {
+    fn has_slot(&self, provider: &str, limits: &GenerationLimits) -> bool {
+        if self.running >= limits.max_total {
+            return false;
+        }
+        match limits.provider_limit(provider) {
+            Some(limit) => self.running_by_provider.get(provider).copied().unwrap_or(0) < limit,
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GenerationPool {
+    state: Mutex<PoolState>,
+    released: Notify,
+}
+
+impl GenerationPool {
+    /// Wait for a slot for a generation against `provider`. `on_queued` runs once if
+    /// no slot is free right away. Returns `None` when cancelled while waiting.
+    pub(crate) async fn acquire(
+        self: &Arc<Self>,
+        provider: &str,
+        db: &Database,
+        cancel_token: &CancellationToken,
+        on_queued: impl FnOnce(),
+    ) -> Option<GenerationPermit> {
+        let mut on_queued = Some(on_queued);
+        loop {
+            // Registered before checking, so a release in between still wakes us
+            let released = self.released.notified();
+            let limits = GenerationLimits::load(db).await;
+            if let Some(permit) = self.try_acquire(provider, &limits) {
+                return Some(permit);
+            }
+            if let Some(on_queued) = on_queued.take() {
+                on_queued();
+            }
+            tokio::select! {
+                _ = released => {}
+                _ = cancel_token.cancelled() => return None,
+            }
+        }
+    }
+
+    fn try_acquire(
+        self: &Arc<Self>,
+        provider: &str,
+        limits: &GenerationLimits,
+    ) -> Option<GenerationPermit> {
+        let mut state = self.state.lock().ok()?;
+        if !state.has_slot(provider, limits) {
+            return None;
+        }
+        state.running += 1;
+        *state
+            .running_by_provider
+            .entry(provider.to_string())
+            .or_insert(0) += 1;
+        Some(GenerationPermit {
+            pool: self.clone(),
+            provider: provider.to_string(),
+        })
+    }
+
+    fn release(&self, provider: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.running = state.running.saturating_sub(1);
+            if let Some(count) = state.running_by_provider.get_mut(provider) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    state.running_by_provider.remove(provider);
+                }
+            }
+        }
+        self.released.notify_waiters();
+    }
+}
+
+/// A running generation's slot, given back on drop
+pub(crate) struct GenerationPermit {
+    pool: Arc<GenerationPool>,
+    provider: String,
+}
+
+impl Drop for GenerationPermit {
+    fn drop(&mut self) {
+        self.pool.release(&self.provider);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_caps_and_release() {
+        let pool = Arc::new(GenerationPool::default());
+        let limits = GenerationLimits {
+            max_total: 2,
+            per_provider: HashMap::from([("ollama".to_string(), 1), ("openai".to_string(), 0)]),
+        };
+
+        let ollama = pool.try_acquire("ollama", &limits).unwrap();
+        assert!(pool.try_acquire("ollama", &limits).is_none());
+        let openai = pool.try_acquire("openai", &limits).unwrap();
+        // The global cap is reached even though openai has no cap of its own
+        assert!(pool.try_acquire("openai", &limits).is_none());
+
+        drop(ollama);
+        assert!(pool.try_acquire("ollama", &limits).is_some());
+        drop(openai);
+        assert_eq!(pool.state.lock().unwrap().running, 0);
+    }
+}
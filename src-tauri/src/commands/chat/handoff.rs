@@ -0,0 +1,162 @@
+//! Switching assistants mid-conversation
+//!
+//! The conversation is bound to the new assistant and the switch is recorded as an
+//! `assistant_switch` process step on the latest message. With a handoff summary, the
+//! "fast" role model (falling back to the conversation's model) briefs the new
+//! assistant on the conversation so far; the briefing is added to its system prompt for
+//! as long as it stays bound (see `message_builder`).
+
+use super::super::AppState;
+use super::auxiliary::auxiliary_text;
+use super::binding;
+use super::summary::build_transcript;
+use super::title::get_conversation_provider_info;
+use crate::error::AppError;
+use crate::llm::{self, ChatMessage};
+use crate::models::{AssistantSwitch, CreateAssistantSwitchRequest, Message, ModelRole};
+use crate::prompts;
+use tauri::State;
+
+/// Hand the conversation to another assistant. Returns the recorded switch, or `None`
+/// when nothing was recorded: the assistant was already bound, or the conversation has
+/// no messages yet to attach the step to.
+#[tauri::command]
+pub async fn switch_assistant(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    assistant_id: String,
+    with_handoff_summary: bool,
+) -> Result<Option<AssistantSwitch>, AppError> {
+    if state.private_conversations.contains(&conversation_id) {
+        return Err(AppError::validation(
+            "Assistants can't be switched in a private conversation",
+        ));
+    }
+    if state.db.get_conversation(&conversation_id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Conversation not found: {}",
+            conversation_id
+        )));
+    }
+    if state.db.get_assistant(&assistant_id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Assistant not found: {}",
+            assistant_id
+        )));
+    }
+
+    let settings = state.db.get_conversation_settings(&conversation_id).await?;
+    let from_assistant_id = settings.selected_assistant_id;
+    if from_assistant_id.as_deref() == Some(assistant_id.as_str()) {
+        return Ok(None);
+    }
+
+    let messages = state
+        .db
+        .list_messages_by_conversation(&conversation_id)
+        .await?;
+    // Generated first, so a failed summary leaves the conversation as it was
+    let summary = if with_handoff_summary && !messages.is_empty() {
+        Some(generate_handoff_summary(&state, &conversation_id, &messages).await?)
+    } else {
+        None
+    };
+
+    state
+        .db
+        .set_conversation_binding(&conversation_id, None, Some(&assistant_id))
+        .await?;
+    tracing::info!(
+        "🔀 [handoff] Conversation {} switched to assistant {}{}",
+        conversation_id,
+        assistant_id,
+        if summary.is_some() {
+            " with a handoff summary"
+        } else {
+            ""
+        }
+    );
+
+    let Some(last_message) = messages.last() else {
+        return Ok(None);
+    };
+    let display_order = state
+        .db
+        .get_message_steps(&last_message.id)
+        .await?
+        .iter()
+        .map(|step| step.display_order() + 1)
+        .max()
+        .unwrap_or(0);
+    let switch = state
+        .db
+        .create_assistant_switch(CreateAssistantSwitchRequest {
+            message_id: last_message.id.clone(),
+            from_assistant_id,
+            to_assistant_id: assistant_id,
+            summary,
+            display_order: Some(display_order),
+        })
+        .await?;
+    Ok(Some(switch))
+}
+
+async fn generate_handoff_summary(
+    state: &AppState,
+    conversation_id: &str,
+    messages: &[Message],
+) -> Result<String, AppError> {
+    let (provider, model, api_key, base_url, api_style) =
+        match binding::resolve_role_binding(state, ModelRole::Fast).await {
+            Some(fast) => (
+                fast.provider,
+                fast.model,
+                fast.api_key,
+                fast.base_url,
+                fast.api_style,
+            ),
+            None => get_conversation_provider_info(state, conversation_id).await?,
+        };
+    crate::commands::enforce_provider_policy(
+        state,
+        conversation_id,
+        &provider,
+        base_url.as_deref(),
+        None,
+        None,
+    )
+    .await?;
+
+    let auxiliary = super::auxiliary::auxiliary_call(state, conversation_id).await;
+    let response = llm::call_provider(
+        &provider,
+        model.clone(),
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::HANDOFF_SUMMARY_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_handoff_summary_user_prompt(&build_transcript(messages)),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary.token(),
+    )
+    .await?;
+
+    auxiliary_text(&response, &model, &provider, "handoff summary")
+}
@@ -0,0 +1,165 @@
+//! Image generation command
+//!
+//! Generates images from a prompt with an image model (DALL-E / gpt-image on
+//! OpenAI-compatible providers, image models on OpenRouter). The prompt is saved as a
+//! user message and the images as attachments of an assistant message.
+
+use super::super::AppState;
+use super::attachment_processing::store_generated_image;
+use super::save_user_message;
+use crate::error::AppError;
+use crate::events::{self, AttachmentUpdate, ChatComplete, ChatError};
+use crate::llm::image_generation::{self, ImageGenerationOptions};
+use crate::models::{CreateMessageRequest, Message};
+use tauri::State;
+
+/// Generate images for a prompt and attach them to a new assistant message
+#[tauri::command]
+pub async fn generate_image(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    conversation_id: String,
+    prompt: String,
+    provider_id: String,
+    model: String,
+    options: Option<ImageGenerationOptions>,
+) -> Result<Message, AppError> {
+    let prompt = prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err(AppError::validation("Prompt cannot be empty"));
+    }
+    let options = options.unwrap_or_default();
+
+    let provider = state
+        .db
+        .get_provider(&provider_id)
+        .await?
+        .ok_or_else(|| format!("Provider not found: {}", provider_id))?;
+    // Checked before the prompt is saved or sent anywhere
+    crate::commands::enforce_provider_policy_for_provider(&state, &conversation_id, &provider)
+        .await?;
+
+    tracing::info!(
+        "🎨 [generate_image] Generating with {}/{} for conversation {}",
+        provider.provider_type,
+        model,
+        conversation_id
+    );
+
+    let user_message = save_user_message(&state, &conversation_id, &prompt).await?;
+
+    let base_url = provider.base_url.clone().filter(|u| !u.trim().is_empty());
+    let result = match provider.provider_type.as_str() {
+        "openrouter" => {
+            image_generation::generate_openrouter_images(
+                base_url
+                    .as_deref()
+                    .unwrap_or(crate::llm::openrouter::DEFAULT_BASE_URL),
+                provider.api_key.as_deref(),
+                &model,
+                &prompt,
+                &options,
+            )
+            .await
+        }
+        "openai" | "custom_openai" => {
+            image_generation::generate_openai_images(
+                base_url
+                    .as_deref()
+                    .unwrap_or(crate::llm::openai::DEFAULT_BASE_URL),
+                provider.api_key.as_deref(),
+                &model,
+                &prompt,
+                &options,
+            )
+            .await
+        }
+        other => Err(anyhow::anyhow!(
+            "Image generation is not supported for provider type {}",
+            other
+        )),
+    };
+
+    let images = match result {
+        Ok(images) => images,
+        Err(e) => {
+            tracing::error!("❌ [generate_image] Generation failed: {}", e);
+            let error = AppError::from(e).with_provider_type(&provider.provider_type);
+            events::emit(&app, ChatError::new(&conversation_id, &error));
+            return Err(error);
+        }
+    };
+
+    // Link the reply to the model record when this provider/model pair is configured
+    let sender_id = state
+        .db
+        .list_all_models()
+        .await
+        .ok()
+        .and_then(|models| {
+            models
+                .into_iter()
+                .find(|m| m.provider_id == provider_id && m.model_id == model)
+        })
+        .map(|m| m.id);
+
+    let content = images
+        .iter()
+        .find_map(|i| i.revised_prompt.clone())
+        .unwrap_or_else(|| " ".to_string());
+    let mut assistant_message = state
+        .db
+        .create_message(CreateMessageRequest {
+            conversation_id: Some(conversation_id.clone()),
+            sender_type: "model".to_string(),
+            sender_id,
+            content,
+            tokens: None,
+        })
+        .await?;
+    state
+        .db
+        .set_message_generation_info(&assistant_message.id, &provider.provider_type, &model, None)
+        .await?;
+    assistant_message.provider_type = Some(provider.provider_type.clone());
+    assistant_message.model_id = Some(model.clone());
+
+    let mut stored = 0;
+    for (index, image) in images.iter().enumerate() {
+        if let Some(attachment_id) = store_generated_image(
+            &state,
+            &app,
+            &assistant_message.id,
+            index,
+            &image.media_type,
+            &image.bytes,
+        )
+        .await
+        {
+            stored += 1;
+            events::emit(
+                &app,
+                AttachmentUpdate::new(&assistant_message.id, &conversation_id, &attachment_id),
+            );
+        }
+    }
+
+    tracing::info!(
+        "✅ [generate_image] Stored {}/{} image(s) on message {}",
+        stored,
+        images.len(),
+        assistant_message.id
+    );
+
+    events::emit(
+        &app,
+        ChatComplete {
+            conversation_id: conversation_id.clone(),
+            message: Some(assistant_message.clone()),
+            user_message: Some(user_message),
+            cancelled: false,
+        },
+    );
+
+    Ok(assistant_message)
+}
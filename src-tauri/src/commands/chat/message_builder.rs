@@ -5,19 +5,32 @@
 //! assistant(tool_calls) -> tool(result) -> ... -> assistant(final text).
 
 use super::AppState;
-use super::attachment_processing;
+use super::attachment_processing::{self, DEFAULT_CONTEXT_TOKENS};
+use crate::events::{self, ContextBuilt};
 use crate::llm::{self, ChatMessage, ToolCallData};
+use crate::models::{DEFAULT_CONTEXT_BUDGET_PERCENT, HistoryMode, Message};
 use crate::prompts;
+use crate::tokenizer::count_tokens;
+
+/// Flat estimate for an attached image; providers bill roughly this for a
+/// downscaled image
+const IMAGE_TOKENS: usize = 800;
+/// Role markers and separators the chat template adds around each message
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
 
 /// Build chat messages for LLM request
 ///
-/// # Arguments
-/// * `context_message_count` - Optional limit on number of history messages to include.
-///   - `None` or negative value: include all history
-///   - `Some(n)` where n > 0: include only the last n messages
+/// History follows the conversation's `history_mode` setting; `include_history = false`
+/// (e.g. an assistant configured without history) skips it regardless of the mode.
+/// In `token_budget` mode the oldest messages are dropped until the request fits the
+/// budget, counted against the context window of `model_id`. What was included is
+/// reported in a `context-built` event. Replies a regeneration is replacing are left out.
 #[allow(clippy::too_many_arguments)]
 pub async fn build_chat_messages(
     state: &AppState,
+    app: &tauri::AppHandle,
+    provider_type: &str,
+    model_id: &str,
     conversation_id: &str,
     user_message_id: &str,
     processed_content: &str,
@@ -26,15 +39,83 @@ pub async fn build_chat_messages(
     include_history: bool,
     user_images: &[attachment_processing::ParsedImage],
     user_files: &[llm::FileData],
-    context_message_count: Option<i64>,
+    replaced_message_ids: &[String],
 ) -> Vec<ChatMessage> {
-    let base_prompt = system_prompt
-        .clone()
-        .unwrap_or_else(|| prompts::DEFAULT_ASSISTANT_SYSTEM_PROMPT.to_string());
+    let base_prompt = match system_prompt {
+        Some(prompt) => prompt.clone(),
+        None => crate::commands::default_system_prompt(state).await,
+    };
 
-    let system_prompt_content = base_prompt;
+    let settings = state
+        .db
+        .get_conversation_settings(conversation_id)
+        .await
+        .ok();
+    let history_mode = settings
+        .as_ref()
+        .map(|s| s.history_mode)
+        .unwrap_or_default();
+    let budget_percent = settings
+        .as_ref()
+        .and_then(|s| s.context_budget_percent)
+        .unwrap_or(DEFAULT_CONTEXT_BUDGET_PERCENT)
+        .clamp(1, 100) as usize;
+
+    let messages = if include_history && history_mode != HistoryMode::None {
+        match state.private_conversations.messages(conversation_id) {
+            Some(messages) => messages,
+            None => state
+                .db
+                .list_messages_by_conversation(conversation_id)
+                .await
+                .unwrap_or_default(),
+        }
+    } else {
+        Vec::new()
+    };
+    let history_messages: Vec<&Message> = messages
+        .iter()
+        .filter(|msg| msg.id != user_message_id && !replaced_message_ids.contains(&msg.id))
+        .collect();
 
-    let mut chat_messages = vec![ChatMessage {
+    let conversation = if history_mode == HistoryMode::Summarized {
+        state
+            .db
+            .get_conversation(conversation_id)
+            .await
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+    let summary = conversation
+        .as_ref()
+        .and_then(|c| Some((c.summary.as_deref()?, c.summary_message_count)));
+
+    let (messages_to_include, summary) = select_history(&history_messages, history_mode, summary);
+
+    let mut system_prompt_content = match summary {
+        Some(summary) => base_prompt + &prompts::build_history_summary_section(summary),
+        None => base_prompt,
+    };
+
+    // A handoff briefing is for the assistant the conversation was switched to
+    if let Some(assistant_id) = settings
+        .as_ref()
+        .and_then(|s| s.selected_assistant_id.as_deref())
+        && let Some(handoff) = state
+            .db
+            .get_latest_assistant_switch(conversation_id)
+            .await
+            .ok()
+            .flatten()
+            .filter(|switch| switch.to_assistant_id == assistant_id)
+            .and_then(|switch| switch.summary)
+    {
+        system_prompt_content.push_str(&prompts::build_handoff_section(&handoff));
+    }
+
+    let system_message = ChatMessage {
         role: "system".to_string(),
         content: system_prompt_content,
         images: vec![],
@@ -42,164 +123,162 @@ pub async fn build_chat_messages(
         tool_calls: vec![],
         tool_call_id: None,
         reasoning_content: None,
-    }];
+    };
 
-    if include_history
-        && let Ok(messages) = state
-            .db
-            .list_messages_by_conversation(conversation_id)
-            .await
-    {
-        let history_messages: Vec<_> = messages
-            .iter()
-            .filter(|msg| msg.id != user_message_id)
-            .collect();
-
-        let messages_to_include = match context_message_count {
-            Some(count) if count > 0 => {
-                let count = count as usize;
-                if history_messages.len() > count {
-                    tracing::info!(
-                        "📊 [message_builder] Limiting context to {} messages (had {})",
-                        count,
-                        history_messages.len()
-                    );
-                    &history_messages[history_messages.len() - count..]
+    // Each history message becomes one or more chat messages (tool call chains)
+    let mut history_groups: Vec<(&str, Vec<ChatMessage>)> = Vec::new();
+    for msg in messages_to_include.iter() {
+        let mut chat_messages = Vec::new();
+        match msg.sender_type.as_str() {
+            "user" => {
+                // Voice memo transcripts are part of what the user said
+                let transcripts: Vec<_> = state
+                    .db
+                    .get_transcriptions_by_message(&msg.id)
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|t| t.status == "success")
+                    .map(|t| t.content)
+                    .collect();
+                let content = if transcripts.is_empty() {
+                    msg.content.clone()
                 } else {
-                    &history_messages[..]
-                }
+                    let mut content = msg.content.trim_end().to_string();
+                    for transcript in transcripts {
+                        if !content.is_empty() {
+                            content.push_str("\n\n");
+                        }
+                        content.push_str("[Voice memo transcript]\n");
+                        content.push_str(&transcript);
+                    }
+                    content
+                };
+                chat_messages.push(ChatMessage {
+                    role: "user".to_string(),
+                    content,
+                    images: vec![],
+                    files: vec![],
+                    tool_calls: vec![],
+                    tool_call_id: None,
+                    reasoning_content: None,
+                });
             }
-            _ => &history_messages[..],
-        };
+            "model" | "assistant" => {
+                let db_tool_calls = state
+                    .db
+                    .get_tool_calls_by_message(&msg.id)
+                    .await
+                    .unwrap_or_default();
 
-        for msg in messages_to_include.iter() {
-            match msg.sender_type.as_str() {
-                "user" => {
+                let thinking_steps = state
+                    .db
+                    .get_thinking_steps_by_message(&msg.id)
+                    .await
+                    .unwrap_or_default();
+                let reasoning = if thinking_steps.is_empty() {
+                    None
+                } else {
+                    let joined: String = thinking_steps
+                        .iter()
+                        .map(|s| s.content.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    if joined.trim().is_empty() {
+                        None
+                    } else {
+                        Some(joined)
+                    }
+                };
+
+                if db_tool_calls.is_empty() {
                     chat_messages.push(ChatMessage {
-                        role: "user".to_string(),
+                        role: "assistant".to_string(),
                         content: msg.content.clone(),
                         images: vec![],
                         files: vec![],
                         tool_calls: vec![],
                         tool_call_id: None,
-                        reasoning_content: None,
+                        reasoning_content: reasoning,
                     });
-                }
-                "model" | "assistant" => {
-                    let db_tool_calls = state
-                        .db
-                        .get_tool_calls_by_message(&msg.id)
-                        .await
-                        .unwrap_or_default();
+                } else {
+                    let tc_data: Vec<ToolCallData> = db_tool_calls
+                        .iter()
+                        .map(|tc| ToolCallData {
+                            id: tc.id.clone(),
+                            tool_name: tc.tool_name.clone(),
+                            tool_input: tc.tool_input.clone().unwrap_or_default(),
+                            tool_output: tc.tool_output.clone(),
+                        })
+                        .collect();
 
-                    let thinking_steps = state
+                    // 1) Assistant message carrying tool_calls (content may be
+                    //    empty when the assistant only invoked tools)
+                    let content_blocks = state
                         .db
-                        .get_thinking_steps_by_message(&msg.id)
+                        .get_content_blocks_by_message(&msg.id)
                         .await
                         .unwrap_or_default();
-                    let reasoning = if thinking_steps.is_empty() {
-                        None
-                    } else {
-                        let joined: String = thinking_steps
+
+                    let pre_tool_text = if !content_blocks.is_empty() {
+                        let min_tc_order = db_tool_calls
+                            .iter()
+                            .map(|tc| tc.display_order)
+                            .min()
+                            .unwrap_or(0);
+                        content_blocks
                             .iter()
-                            .map(|s| s.content.as_str())
+                            .filter(|cb| cb.display_order < min_tc_order)
+                            .map(|cb| cb.content.as_str())
                             .collect::<Vec<_>>()
-                            .join("\n");
-                        if joined.trim().is_empty() {
-                            None
-                        } else {
-                            Some(joined)
-                        }
-                    };
-
-                    if db_tool_calls.is_empty() {
-                        chat_messages.push(ChatMessage {
-                            role: "assistant".to_string(),
-                            content: msg.content.clone(),
-                            images: vec![],
-                            files: vec![],
-                            tool_calls: vec![],
-                            tool_call_id: None,
-                            reasoning_content: reasoning,
-                        });
+                            .join("")
                     } else {
-                        let tc_data: Vec<ToolCallData> = db_tool_calls
-                            .iter()
-                            .map(|tc| ToolCallData {
-                                id: tc.id.clone(),
-                                tool_name: tc.tool_name.clone(),
-                                tool_input: tc.tool_input.clone().unwrap_or_default(),
-                                tool_output: tc.tool_output.clone(),
-                            })
-                            .collect();
-
-                        // 1) Assistant message carrying tool_calls (content may be
-                        //    empty when the assistant only invoked tools)
-                        let content_blocks = state
-                            .db
-                            .get_content_blocks_by_message(&msg.id)
-                            .await
-                            .unwrap_or_default();
-
-                        let pre_tool_text = if !content_blocks.is_empty() {
-                            let min_tc_order = db_tool_calls
-                                .iter()
-                                .map(|tc| tc.display_order)
-                                .min()
-                                .unwrap_or(0);
-                            content_blocks
-                                .iter()
-                                .filter(|cb| cb.display_order < min_tc_order)
-                                .map(|cb| cb.content.as_str())
-                                .collect::<Vec<_>>()
-                                .join("")
-                        } else {
-                            String::new()
-                        };
-
-                        chat_messages.push(ChatMessage {
-                            role: "assistant".to_string(),
-                            content: pre_tool_text,
-                            images: vec![],
-                            files: vec![],
-                            tool_calls: tc_data.clone(),
-                            tool_call_id: None,
-                            reasoning_content: reasoning,
-                        });
+                        String::new()
+                    };
 
-                        // 2) Tool result messages
-                        for tc in &tc_data {
-                            if let Some(ref output) = tc.tool_output {
-                                chat_messages.push(ChatMessage {
-                                    role: "tool".to_string(),
-                                    content: output.clone(),
-                                    images: vec![],
-                                    files: vec![],
-                                    tool_calls: vec![],
-                                    tool_call_id: Some(tc.id.clone()),
-                                    reasoning_content: None,
-                                });
-                            }
-                        }
+                    chat_messages.push(ChatMessage {
+                        role: "assistant".to_string(),
+                        content: pre_tool_text,
+                        images: vec![],
+                        files: vec![],
+                        tool_calls: tc_data.clone(),
+                        tool_call_id: None,
+                        reasoning_content: reasoning,
+                    });
 
-                        // 3) Final assistant text after tool calls (the stored
-                        //    message content), if non-empty
-                        if !msg.content.trim().is_empty() {
+                    // 2) Tool result messages
+                    for tc in &tc_data {
+                        if let Some(ref output) = tc.tool_output {
                             chat_messages.push(ChatMessage {
-                                role: "assistant".to_string(),
-                                content: msg.content.clone(),
+                                role: "tool".to_string(),
+                                content: output.clone(),
                                 images: vec![],
                                 files: vec![],
                                 tool_calls: vec![],
-                                tool_call_id: None,
+                                tool_call_id: Some(tc.id.clone()),
                                 reasoning_content: None,
                             });
                         }
                     }
+
+                    // 3) Final assistant text after tool calls (the stored
+                    //    message content), if non-empty
+                    if !msg.content.trim().is_empty() {
+                        chat_messages.push(ChatMessage {
+                            role: "assistant".to_string(),
+                            content: msg.content.clone(),
+                            images: vec![],
+                            files: vec![],
+                            tool_calls: vec![],
+                            tool_call_id: None,
+                            reasoning_content: None,
+                        });
+                    }
                 }
-                _ => continue,
             }
+            _ => continue,
         }
+        history_groups.push((msg.id.as_str(), chat_messages));
     }
 
     let final_user_content = if let Some(prompt) = user_prompt {
@@ -210,7 +289,7 @@ pub async fn build_chat_messages(
 
     let llm_images: Vec<llm::ImageData> = user_images.iter().map(|img| img.data.clone()).collect();
 
-    chat_messages.push(ChatMessage {
+    let current_message = ChatMessage {
         role: "user".to_string(),
         content: final_user_content,
         images: llm_images,
@@ -218,7 +297,184 @@ pub async fn build_chat_messages(
         tool_calls: vec![],
         tool_call_id: None,
         reasoning_content: None,
-    });
+    };
+
+    let fixed_tokens = message_tokens(&system_message) + message_tokens(&current_message);
+    let group_tokens: Vec<usize> = history_groups
+        .iter()
+        .map(|(_, group)| group.iter().map(message_tokens).sum())
+        .collect();
+
+    let budget_tokens = if history_mode == HistoryMode::TokenBudget {
+        let context_window = state
+            .capabilities_cache
+            .resolve(provider_type, model_id)
+            .await
+            .max_context_length
+            .filter(|t| *t > 0)
+            .map(|t| t as usize)
+            .unwrap_or(DEFAULT_CONTEXT_TOKENS);
+        Some(context_window * budget_percent / 100)
+    } else {
+        None
+    };
+    let start = match budget_tokens {
+        Some(budget) => fit_to_budget(&group_tokens, budget.saturating_sub(fixed_tokens)),
+        None => 0,
+    };
+
+    let included = &history_groups[start..];
+    let included_message_ids: Vec<String> = included.iter().map(|(id, _)| id.to_string()).collect();
+    let total_tokens = fixed_tokens + group_tokens[start..].iter().sum::<usize>();
+    let omitted_message_count = history_messages.len() - included.len();
+    if omitted_message_count > 0 {
+        tracing::info!(
+            "📊 [message_builder] History mode {:?}: sending {} of {} messages (~{} tokens)",
+            history_mode,
+            included.len(),
+            history_messages.len(),
+            total_tokens
+        );
+    }
+    events::emit(
+        app,
+        ContextBuilt {
+            conversation_id: conversation_id.to_string(),
+            history_mode,
+            budget_tokens,
+            total_tokens,
+            included_message_ids,
+            omitted_message_count,
+        },
+    );
 
+    let mut chat_messages = vec![system_message];
+    chat_messages.extend(history_groups.drain(start..).flat_map(|(_, group)| group));
+    chat_messages.push(current_message);
     chat_messages
 }
+
+/// Approximate tokens a chat message takes up in the request
+fn message_tokens(message: &ChatMessage) -> usize {
+    let text_tokens = count_tokens(&message.content)
+        + message.reasoning_content.as_deref().map_or(0, count_tokens);
+    let tool_call_tokens: usize = message
+        .tool_calls
+        .iter()
+        .map(|tc| count_tokens(&tc.tool_name) + count_tokens(&tc.tool_input))
+        .sum();
+    let file_tokens: usize = message
+        .files
+        .iter()
+        .map(|f| count_tokens(&f.name) + count_tokens(&f.content))
+        .sum();
+    MESSAGE_OVERHEAD_TOKENS
+        + text_tokens
+        + tool_call_tokens
+        + file_tokens
+        + message.images.len() * IMAGE_TOKENS
+}
+
+/// Index of the oldest history entry to keep so that the newest entries fit in
+/// `available` tokens. Entries are kept or dropped whole, newest first.
+fn fit_to_budget(group_tokens: &[usize], available: usize) -> usize {
+    let mut used = 0;
+    for (i, tokens) in group_tokens.iter().enumerate().rev() {
+        used += tokens;
+        if used > available {
+            return i + 1;
+        }
+    }
+    0
+}
+
+/// Pick the history messages to send under `mode`. In summarized mode the messages the
+/// summary was generated from are dropped and the summary is returned to replace them;
+/// without a summary every message is sent. Token budgets are applied afterwards, once
+/// the messages are built.
+fn select_history<'a>(
+    history: &'a [&'a Message],
+    mode: HistoryMode,
+    summary: Option<(&'a str, i64)>,
+) -> (&'a [&'a Message], Option<&'a str>) {
+    match mode {
+        HistoryMode::All | HistoryMode::TokenBudget => (history, None),
+        HistoryMode::None => (&[], None),
+        HistoryMode::Summarized => match summary {
+            Some((summary, covered)) if !summary.trim().is_empty() && covered > 0 => {
+                let start = (covered as usize).min(history.len());
+                (&history[start..], Some(summary))
+            }
+            _ => (history, None),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            conversation_id: Some("conv".to_string()),
+            sender_type: "user".to_string(),
+            sender_id: None,
+            content: id.to_string(),
+            tokens: None,
+            input_tokens: None,
+            output_tokens: None,
+            cost: None,
+            follow_up_suggestions: None,
+            provider_type: None,
+            model_id: None,
+            model_params: None,
+            created_at: String::new(),
+        }
+    }
+
+    fn ids(messages: &[&Message]) -> Vec<String> {
+        messages.iter().map(|m| m.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_select_history_modes() {
+        let owned: Vec<Message> = ["a", "b", "c", "d"].into_iter().map(message).collect();
+        let history: Vec<&Message> = owned.iter().collect();
+
+        let (all, _) = select_history(&history, HistoryMode::All, None);
+        assert_eq!(ids(all), ["a", "b", "c", "d"]);
+
+        // Trimmed later by fit_to_budget
+        let (budgeted, _) = select_history(&history, HistoryMode::TokenBudget, None);
+        assert_eq!(budgeted.len(), 4);
+
+        let (none, _) = select_history(&history, HistoryMode::None, None);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_select_history_summarized() {
+        let owned: Vec<Message> = ["a", "b", "c", "d"].into_iter().map(message).collect();
+        let history: Vec<&Message> = owned.iter().collect();
+
+        let (rest, summary) = select_history(&history, HistoryMode::Summarized, Some(("recap", 3)));
+        assert_eq!(ids(rest), ["d"]);
+        assert_eq!(summary, Some("recap"));
+
+        // No summary yet: send everything
+        let (all, summary) = select_history(&history, HistoryMode::Summarized, None);
+        assert_eq!(all.len(), 4);
+        assert!(summary.is_none());
+    }
+
+    #[test]
+    fn test_fit_to_budget_keeps_newest() {
+        let tokens = [100, 40, 30, 20];
+        assert_eq!(fit_to_budget(&tokens, 1000), 0);
+        assert_eq!(fit_to_budget(&tokens, 90), 1);
+        assert_eq!(fit_to_budget(&tokens, 89), 2);
+        assert_eq!(fit_to_budget(&tokens, 10), 4);
+        assert_eq!(fit_to_budget(&[], 0), 0);
+    }
+}
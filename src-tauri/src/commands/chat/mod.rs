@@ -2,37 +2,87 @@
 //!
 //! This module handles sending messages, streaming LLM responses, and related functionality.
 
+mod archive_processing;
 mod attachment_processing;
+mod audio_processing;
+pub mod automation;
+pub(crate) mod auxiliary;
+mod binding;
+mod chunk_coalescer;
+mod citations;
+mod dropped_files;
+mod follow_ups;
+pub mod generation_pool;
+pub mod handoff;
+pub mod image_generation;
 mod message_builder;
+mod ocr;
+pub mod outbox;
 mod participants;
+pub mod private;
+pub mod request_debug;
+mod roundtable;
 mod search_processing;
+pub mod step_explanation;
+mod stream_accumulator;
 mod streaming;
+pub mod summary;
 pub mod title;
+pub mod translation;
 mod types;
 mod url_processing;
+pub mod url_summary;
+pub mod verification;
 pub mod web_search;
 
 use super::AppState;
+use crate::error::AppError;
+use crate::events::{
+    self, ChatComplete, GenerationQueued, GenerationStarted, GenerationStopped, StreamChannel,
+    StreamSink,
+};
 use crate::models::{CreateMessageRequest, Message};
 use crate::web_fetch;
-use tauri::{Emitter, State};
+use tauri::State;
 use tokio_util::sync::CancellationToken;
 
 // Re-export types
-pub use types::{FileAttachmentInput, ImageAttachmentInput, ParameterOverrides};
+pub use types::{
+    AudioAttachmentInput, FileAttachmentInput, ImageAttachmentInput, ParameterOverrides,
+    RoundtableOptions, RoundtableParticipant,
+};
 
 /// Send a message and start LLM generation
 ///
 /// This command returns immediately after saving the user message.
 /// LLM processing happens in a background task.
+///
+/// When `provider`/`model` are omitted, the model or assistant bound to the
+/// conversation (stored in conversation settings) is used instead, then the
+/// default chat/vision model role.
+///
+/// When `roundtable` is set, each listed participant replies in turn instead of
+/// a single model response.
+///
+/// Streamed chunks, reasoning and tool calls are sent over `stream_channel` when
+/// given, and emitted as global events otherwise.
+///
+/// While offline, generations for remote providers wait in the outbox and start
+/// when the network returns.
+///
+/// Fails with a `budget_exceeded` error when the reply is expected to go over the
+/// conversation's token or cost budget, and with `provider_not_allowed` when the
+/// conversation's provider policy rejects the provider.
+///
+/// Messages in private conversations are kept in memory only (see [`private`]).
 #[tauri::command]
 pub async fn send_message(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     conversation_id: String,
     content: String,
-    provider: String,
-    model: String,
+    provider: Option<String>,
+    model: Option<String>,
     api_key: Option<String>,
     base_url: Option<String>,
     api_style: Option<String>,
@@ -44,48 +94,127 @@ pub async fn send_message(
     urls_to_fetch: Option<Vec<String>>,
     images: Option<Vec<ImageAttachmentInput>>,
     files: Option<Vec<FileAttachmentInput>>,
+    audio: Option<Vec<AudioAttachmentInput>>,
     search_enabled: Option<bool>,
     parameter_overrides: Option<types::ParameterOverrides>,
-    context_message_count: Option<i64>,
     use_provider_defaults: Option<bool>,
-) -> Result<Message, String> {
-    log_send_message_params(
-        &conversation_id,
-        &content,
-        &provider,
-        &model,
-        &base_url,
-        &system_prompt,
-        &user_prompt,
-        &model_db_id,
-        &assistant_db_id,
-        &urls_to_fetch,
-        &images,
-        &files,
-        &search_enabled,
-        &parameter_overrides,
-        &context_message_count,
-        &use_provider_defaults,
-    );
+    roundtable: Option<types::RoundtableOptions>,
+    stream_channel: Option<StreamChannel>,
+) -> Result<Message, AppError> {
+    let is_private = state.private_conversations.contains(&conversation_id);
+    if is_private {
+        private::check_send_options(
+            &urls_to_fetch,
+            &images,
+            &files,
+            &audio,
+            search_enabled,
+            &roundtable,
+        )?;
+    }
 
-    // Save user message to database
-    let user_message = save_user_message(&state, &conversation_id, &content).await?;
+    // Resolve provider/model, falling back to the conversation's stored binding
+    let resolved = match (provider, model) {
+        (Some(provider), Some(model)) => {
+            if !is_private {
+                binding::remember_binding(&state, &conversation_id, &model_db_id, &assistant_db_id)
+                    .await;
+            }
+            binding::ResolvedBinding {
+                provider,
+                model,
+                api_key,
+                base_url,
+                api_style,
+                model_db_id,
+                assistant_db_id,
+                system_prompt,
+                user_prompt,
+            }
+        }
+        _ => {
+            let wants_vision = images.as_ref().is_some_and(|v| !v.is_empty());
+            let stored =
+                binding::resolve_stored_binding(&state, &conversation_id, wants_vision).await?;
+            binding::ResolvedBinding {
+                api_key: api_key.or(stored.api_key),
+                base_url: base_url.or(stored.base_url),
+                api_style: api_style.or(stored.api_style),
+                model_db_id: model_db_id.or(stored.model_db_id),
+                assistant_db_id: assistant_db_id.or(stored.assistant_db_id),
+                system_prompt: system_prompt.or(stored.system_prompt),
+                user_prompt: user_prompt.or(stored.user_prompt),
+                ..stored
+            }
+        }
+    };
+    let binding::ResolvedBinding {
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+        model_db_id,
+        assistant_db_id,
+        system_prompt,
+        user_prompt,
+    } = resolved;
 
-    // Auto-add participants
-    participants::ensure_participants(&state, &conversation_id, &model_db_id, &assistant_db_id)
-        .await;
+    let user_message = if is_private {
+        // Content stays out of the logs as well
+        tracing::info!(
+            "🕶️ [send_message] Private conversation {} ({} / {})",
+            conversation_id,
+            provider,
+            model
+        );
+        let message = private::new_message(&conversation_id, "user", None, content.clone());
+        state.private_conversations.push_message(message.clone());
+        message
+    } else {
+        log_send_message_params(
+            &conversation_id,
+            &content,
+            &provider,
+            &model,
+            &base_url,
+            &system_prompt,
+            &user_prompt,
+            &model_db_id,
+            &assistant_db_id,
+            &urls_to_fetch,
+            &images,
+            &files,
+            &search_enabled,
+            &parameter_overrides,
+            &use_provider_defaults,
+        );
 
-    // Create and register cancellation token
-    let cancel_token = CancellationToken::new();
-    {
-        let mut tasks = state.generation_tasks.write().await;
-        tasks.insert(conversation_id.clone(), cancel_token.clone());
-    }
+        super::enforce_conversation_budget(&state, &app, &conversation_id).await?;
+        // Roundtable seats are checked one by one when they are resolved
+        if roundtable.is_none() {
+            super::enforce_provider_policy(
+                &state,
+                &conversation_id,
+                &provider,
+                base_url.as_deref(),
+                model_db_id.as_deref(),
+                assistant_db_id.as_deref(),
+            )
+            .await?;
+        }
 
-    // Spawn background task
-    spawn_background_task(
-        state.inner().clone(),
-        app,
+        // Save user message to database
+        let user_message = save_user_message(&state, &conversation_id, &content).await?;
+
+        // Auto-add participants
+        participants::ensure_participants(&state, &conversation_id, &model_db_id, &assistant_db_id)
+            .await;
+        user_message
+    };
+
+    let pending = outbox::PendingGeneration {
+        stream: StreamSink::new(app.clone(), stream_channel),
         conversation_id,
         content,
         provider,
@@ -101,13 +230,27 @@ pub async fn send_message(
         urls_to_fetch,
         images,
         files,
-        search_enabled.unwrap_or(false),
-        user_message.id.clone(),
-        cancel_token,
+        audio,
+        search_enabled: search_enabled.unwrap_or(false),
+        user_message_id: user_message.id.clone(),
         parameter_overrides,
-        context_message_count,
-        use_provider_defaults.unwrap_or(false),
-    );
+        use_provider_defaults: use_provider_defaults.unwrap_or(false),
+        roundtable,
+        replaced_message_ids: Vec::new(),
+    };
+
+    // Offline: keep the message and send it once the network is back
+    if !crate::network::is_online() && pending.needs_network() {
+        let entry = state.outbox.push(pending)?;
+        tracing::info!(
+            "📤 [send_message] Offline, queued message {} in outbox",
+            entry.message_id
+        );
+        outbox::emit_updated(&app, &state.outbox);
+        return Ok(user_message);
+    }
+
+    start_generation(state.inner().clone(), app, pending).await;
 
     Ok(user_message)
 }
@@ -118,12 +261,27 @@ pub async fn stop_generation(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
     conversation_id: String,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     tracing::info!(
         "🛑 [stop_generation] Stopping generation for conversation: {}",
         conversation_id
     );
 
+    // Titles/summaries in flight for this conversation are stopped too
+    let auxiliary_cancelled = auxiliary::cancel_auxiliary(&state, &conversation_id).await;
+
+    if state.outbox.remove_conversation(&conversation_id) {
+        tracing::info!("✅ [stop_generation] Removed queued message from outbox");
+        outbox::emit_updated(&app, &state.outbox);
+        events::emit(
+            &app,
+            GenerationStopped {
+                conversation_id: conversation_id.clone(),
+            },
+        );
+        return Ok(true);
+    }
+
     let tasks = state.generation_tasks.read().await;
 
     if let Some(cancel_token) = tasks.get(&conversation_id) {
@@ -132,13 +290,16 @@ pub async fn stop_generation(
 
         state.bash_session_manager.abort_running(&conversation_id);
 
-        let _ = app.emit(
-            "generation-stopped",
-            serde_json::json!({
-                "conversation_id": conversation_id,
-            }),
+        events::emit(
+            &app,
+            GenerationStopped {
+                conversation_id: conversation_id.clone(),
+            },
         );
 
+        Ok(true)
+    } else if auxiliary_cancelled {
+        tracing::info!("✅ [stop_generation] Cancelled auxiliary calls");
         Ok(true)
     } else {
         tracing::warn!("⚠️ [stop_generation] No active task found for conversation");
@@ -165,7 +326,6 @@ fn log_send_message_params(
     files: &Option<Vec<FileAttachmentInput>>,
     search_enabled: &Option<bool>,
     parameter_overrides: &Option<types::ParameterOverrides>,
-    context_message_count: &Option<i64>,
     use_provider_defaults: &Option<bool>,
 ) {
     tracing::info!("🚀 [send_message] Command received!");
@@ -183,7 +343,6 @@ fn log_send_message_params(
     tracing::info!("   files count: {:?}", files.as_ref().map(|v| v.len()));
     tracing::info!("   search_enabled: {:?}", search_enabled);
     tracing::info!("   parameter_overrides: {:?}", parameter_overrides);
-    tracing::info!("   context_message_count: {:?}", context_message_count);
     tracing::info!("   use_provider_defaults: {:?}", use_provider_defaults);
 }
 
@@ -215,10 +374,79 @@ async fn save_user_message(
     Ok(user_message)
 }
 
+/// Register the cancellation token and start the background task
+async fn start_generation(
+    state: AppState,
+    app: tauri::AppHandle,
+    pending: outbox::PendingGeneration,
+) {
+    let outbox::PendingGeneration {
+        stream,
+        conversation_id,
+        content,
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+        include_history,
+        system_prompt,
+        user_prompt,
+        model_db_id,
+        assistant_db_id,
+        urls_to_fetch,
+        images,
+        files,
+        audio,
+        search_enabled,
+        user_message_id,
+        parameter_overrides,
+        use_provider_defaults,
+        roundtable,
+        replaced_message_ids,
+    } = pending;
+
+    let cancel_token = CancellationToken::new();
+    {
+        let mut tasks = state.generation_tasks.write().await;
+        tasks.insert(conversation_id.clone(), cancel_token.clone());
+    }
+
+    spawn_background_task(
+        state,
+        app,
+        stream,
+        conversation_id,
+        content,
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+        include_history,
+        system_prompt,
+        user_prompt,
+        model_db_id,
+        assistant_db_id,
+        urls_to_fetch,
+        images,
+        files,
+        audio,
+        search_enabled,
+        user_message_id,
+        cancel_token,
+        parameter_overrides,
+        use_provider_defaults,
+        roundtable,
+        replaced_message_ids,
+    );
+}
+
 #[allow(clippy::too_many_arguments)]
 fn spawn_background_task(
     state: AppState,
     app: tauri::AppHandle,
+    stream: StreamSink,
     conversation_id: String,
     content: String,
     provider: String,
@@ -234,19 +462,66 @@ fn spawn_background_task(
     urls_to_fetch: Option<Vec<String>>,
     images: Option<Vec<ImageAttachmentInput>>,
     files: Option<Vec<FileAttachmentInput>>,
+    audio: Option<Vec<AudioAttachmentInput>>,
     search_enabled: bool,
     user_message_id: String,
     cancel_token: CancellationToken,
     parameter_overrides: Option<types::ParameterOverrides>,
-    context_message_count: Option<i64>,
     use_provider_defaults: bool,
+    roundtable: Option<types::RoundtableOptions>,
+    replaced_message_ids: Vec<String>,
 ) {
     tracing::info!("🔄 [send_message] Spawning background task...");
 
     tokio::spawn(async move {
+        let app_for_queue = app.clone();
+        let queued_conversation_id = conversation_id.clone();
+        let queued_provider = provider.clone();
+        let permit = state
+            .generation_pool
+            .acquire(&provider, &state.db, &cancel_token, move || {
+                tracing::info!(
+                    "⏳ [send_message] No free {} generation slot, queued",
+                    queued_provider
+                );
+                events::emit(
+                    &app_for_queue,
+                    GenerationQueued {
+                        conversation_id: queued_conversation_id,
+                        provider: queued_provider,
+                    },
+                );
+            })
+            .await;
+        let Some(_permit) = permit else {
+            // Stopped while waiting for a slot
+            state
+                .generation_tasks
+                .write()
+                .await
+                .remove(&conversation_id);
+            events::emit(
+                &app,
+                ChatComplete {
+                    conversation_id,
+                    message: None,
+                    user_message: None,
+                    cancelled: true,
+                },
+            );
+            return;
+        };
+        events::emit(
+            &app,
+            GenerationStarted {
+                conversation_id: conversation_id.clone(),
+            },
+        );
+
         process_llm_request(
             state,
             app,
+            stream,
             conversation_id,
             content,
             provider,
@@ -262,12 +537,14 @@ fn spawn_background_task(
             urls_to_fetch,
             images,
             files,
+            audio,
             search_enabled,
             user_message_id,
             cancel_token,
             parameter_overrides,
-            context_message_count,
             use_provider_defaults,
+            roundtable,
+            replaced_message_ids,
         )
         .await;
     });
@@ -277,6 +554,7 @@ fn spawn_background_task(
 async fn process_llm_request(
     state: AppState,
     app: tauri::AppHandle,
+    stream: StreamSink,
     conversation_id: String,
     content: String,
     provider: String,
@@ -292,12 +570,14 @@ async fn process_llm_request(
     urls_to_fetch: Option<Vec<String>>,
     images: Option<Vec<ImageAttachmentInput>>,
     files: Option<Vec<FileAttachmentInput>>,
+    audio: Option<Vec<AudioAttachmentInput>>,
     search_enabled: bool,
     user_message_id: String,
     cancel_token: CancellationToken,
     parameter_overrides: Option<types::ParameterOverrides>,
-    context_message_count: Option<i64>,
     use_provider_defaults: bool,
+    roundtable: Option<types::RoundtableOptions>,
+    replaced_message_ids: Vec<String>,
 ) {
     tracing::info!("🎯 [background_task] Started processing LLM request");
 
@@ -315,12 +595,14 @@ async fn process_llm_request(
             &user_message_id,
             &conversation_id,
             urls_to_fetch.unwrap_or_default(),
+            cancel_token.clone(),
         )
         .await
     } else {
         search_processing::SearchProcessingResult {
             urls: urls_to_fetch.unwrap_or_default(),
             search_result_id: None,
+            previously_retrieved: Vec::new(),
         }
     };
 
@@ -338,10 +620,34 @@ async fn process_llm_request(
     // Step 3: Build LLM content with fetched resources
     let processed_content =
         web_fetch::build_llm_content_with_attachments(&content, &url_result.fetched_resources);
+    let processed_content = search_processing::append_previously_retrieved(
+        &processed_content,
+        &search_result.previously_retrieved,
+    );
+    let citation_sources =
+        citations::numbered_sources(&url_result.fetched_resources, &url_result.fetch_result_ids);
 
     // Step 4: Parse attachments
     let user_images = attachment_processing::parse_image_attachments(images);
-    let user_files = attachment_processing::parse_file_attachments(files);
+    let user_images = attachment_processing::scrub_image_metadata(&state, user_images).await;
+    let (files, user_archives) = archive_processing::split_archive_attachments(files);
+    let mut user_files = attachment_processing::parse_file_attachments(files);
+    let user_audio = audio_processing::parse_audio_attachments(audio);
+
+    // Transcribe audio (voice memos) and inject the transcripts into the prompt
+    let processed_content = if user_audio.is_empty() {
+        processed_content
+    } else {
+        let transcripts = audio_processing::process_audio_attachments(
+            &state,
+            &app,
+            &user_audio,
+            &user_message_id,
+            &conversation_id,
+        )
+        .await;
+        audio_processing::append_transcripts(&processed_content, &transcripts)
+    };
 
     // Step 5: Store attachments
     attachment_processing::store_file_attachments(
@@ -353,6 +659,22 @@ async fn process_llm_request(
     )
     .await;
 
+    // Archives are stored as-is; their text files are only sent to the model
+    for archive in &user_archives {
+        archive_processing::store_archive(
+            &state,
+            &app,
+            archive,
+            &user_message_id,
+            &conversation_id,
+        )
+        .await;
+        match archive_processing::extract_text_files(archive) {
+            Ok(extracted) => user_files.extend(extracted),
+            Err(e) => tracing::warn!("⚠️ [archive] Skipping {}: {}", archive.name, e),
+        }
+    }
+
     attachment_processing::store_image_attachments(
         &state,
         &app,
@@ -362,9 +684,38 @@ async fn process_llm_request(
     )
     .await;
 
-    // Step 6: Build chat messages with context limit
+    // Originals are stored; send resized/recompressed copies to the provider
+    let user_images = attachment_processing::prepare_images_for_llm(&state, user_images).await;
+
+    // Roundtable mode: participants take turns instead of a single response
+    if let Some(options) = roundtable.filter(|o| !o.participants.is_empty()) {
+        roundtable::run_roundtable(
+            state,
+            app,
+            stream,
+            conversation_id,
+            content,
+            processed_content,
+            user_message_id,
+            user_images,
+            user_files,
+            citation_sources,
+            include_history.unwrap_or(true),
+            parameter_overrides,
+            use_provider_defaults,
+            options,
+            cancel_token,
+        )
+        .await;
+        return;
+    }
+
+    // Step 6: Build chat messages (history per the conversation's history mode)
     let chat_messages = message_builder::build_chat_messages(
         &state,
+        &app,
+        &provider,
+        &model,
         &conversation_id,
         &user_message_id,
         &processed_content,
@@ -373,67 +724,27 @@ async fn process_llm_request(
         include_history.unwrap_or(true),
         &user_images,
         &user_files,
-        context_message_count,
+        &replaced_message_ids,
     )
     .await;
 
     // Step 7: Get assistant config and build model params
     let assistant_config = get_assistant_config(&state, &assistant_db_id).await;
+    let conversation_settings = state
+        .db
+        .get_conversation_settings(&conversation_id)
+        .await
+        .ok();
 
-    // Determine model params based on settings:
-    // - use_provider_defaults: true -> use empty params (provider defaults)
-    // - parameter_overrides: set -> use custom overrides
-    // - otherwise -> use assistant preset (if available)
-    let model_params = if use_provider_defaults {
-        tracing::info!("📋 [background_task] Using provider defaults (no parameters sent)");
-        crate::models::ModelParameters::default()
-    } else if let Some(overrides) = parameter_overrides {
-        // Custom parameter overrides
-        let mut params = crate::models::ModelParameters::default();
-        if overrides.temperature.is_some() {
-            params.temperature = overrides.temperature;
-        }
-        if overrides.max_tokens.is_some() {
-            params.max_tokens = overrides.max_tokens;
-        }
-        if overrides.top_p.is_some() {
-            params.top_p = overrides.top_p;
-        }
-        if overrides.frequency_penalty.is_some() {
-            params.frequency_penalty = overrides.frequency_penalty;
-        }
-        if overrides.presence_penalty.is_some() {
-            params.presence_penalty = overrides.presence_penalty;
-        }
-        tracing::info!(
-            "📋 [background_task] Applied custom parameter overrides: temp={:?}, max_tokens={:?}, top_p={:?}",
-            params.temperature,
-            params.max_tokens,
-            params.top_p
-        );
-        params
-    } else {
-        // Use assistant preset params (if any)
-        assistant_config
-            .as_ref()
-            .and_then(|a| a.preset.as_ref())
-            .map(|preset| {
-                tracing::info!(
-                    "📋 [background_task] Using assistant preset: temp={:?}, max_tokens={:?}",
-                    preset.temperature,
-                    preset.max_tokens
-                );
-                crate::models::ModelParameters {
-                    temperature: preset.temperature,
-                    max_tokens: preset.max_tokens,
-                    top_p: preset.top_p,
-                    frequency_penalty: preset.frequency_penalty,
-                    presence_penalty: preset.presence_penalty,
-                    additional_params: preset.additional_params.clone(),
-                }
-            })
-            .unwrap_or_default()
-    };
+    let model_preset = get_model_default_preset(&state, &model_db_id).await;
+
+    let model_params = build_model_params(
+        assistant_config.as_ref(),
+        model_preset.as_ref(),
+        conversation_settings.as_ref(),
+        parameter_overrides,
+        use_provider_defaults,
+    );
 
     let system_prompt_for_agent = chat_messages
         .first()
@@ -459,14 +770,97 @@ async fn process_llm_request(
         cancel_token,
         state,
         app,
+        stream,
         conversation_id,
         content,
         model_db_id,
         assistant_db_id,
+        citation_sources,
+        replaced_message_ids,
     )
     .await;
 }
 
+/// Determine model params based on settings:
+/// - use_provider_defaults: true -> use empty params (provider defaults)
+/// - otherwise each parameter comes from the first layer that sets it: per-request
+///   overrides, then the conversation's custom parameters, then the assistant preset
+///   (or the model's default preset when there is none).
+///   Parameters no layer sets are left to the provider.
+fn build_model_params(
+    assistant_config: Option<&crate::models::Assistant>,
+    model_preset: Option<&crate::models::ModelParameterPreset>,
+    conversation_settings: Option<&crate::models::ConversationSettings>,
+    parameter_overrides: Option<types::ParameterOverrides>,
+    use_provider_defaults: bool,
+) -> crate::models::ModelParameters {
+    if use_provider_defaults {
+        tracing::info!("📋 [background_task] Using provider defaults (no parameters sent)");
+        return crate::models::ModelParameters::default();
+    }
+
+    let request = parameter_overrides.unwrap_or_default();
+    let conversation = conversation_settings
+        .filter(|s| s.use_custom_parameters)
+        .map(|s| s.parameter_overrides.clone())
+        .unwrap_or_default();
+    let preset = assistant_config
+        .and_then(|a| a.preset.as_ref())
+        .or(model_preset);
+
+    let params = crate::models::ModelParameters {
+        temperature: request
+            .temperature
+            .or(conversation.temperature)
+            .or(preset.and_then(|p| p.temperature)),
+        max_tokens: request
+            .max_tokens
+            .or(conversation.max_tokens)
+            .or(preset.and_then(|p| p.max_tokens)),
+        top_p: request
+            .top_p
+            .or(conversation.top_p)
+            .or(preset.and_then(|p| p.top_p)),
+        frequency_penalty: request
+            .frequency_penalty
+            .or(conversation.frequency_penalty)
+            .or(preset.and_then(|p| p.frequency_penalty)),
+        presence_penalty: request
+            .presence_penalty
+            .or(conversation.presence_penalty)
+            .or(preset.and_then(|p| p.presence_penalty)),
+        additional_params: preset.and_then(|p| p.additional_params.clone()),
+    };
+    tracing::info!(
+        "📋 [background_task] Resolved model params: temp={:?}, max_tokens={:?}, top_p={:?}",
+        params.temperature,
+        params.max_tokens,
+        params.top_p
+    );
+    params
+}
+
+/// The default parameter preset assigned to a model, if any
+async fn get_model_default_preset(
+    state: &AppState,
+    model_db_id: &Option<String>,
+) -> Option<crate::models::ModelParameterPreset> {
+    let model = state
+        .db
+        .get_model(model_db_id.as_deref()?)
+        .await
+        .ok()
+        .flatten()?;
+    let preset_id = model.default_preset_id?;
+    match state.db.get_model_parameter_preset(&preset_id).await {
+        Ok(preset) => preset,
+        Err(e) => {
+            tracing::warn!("⚠️  [background_task] Error fetching model preset: {}", e);
+            None
+        }
+    }
+}
+
 async fn get_assistant_config(
     state: &AppState,
     assistant_db_id: &Option<String>,
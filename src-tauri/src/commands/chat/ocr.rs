@@ -0,0 +1,188 @@
+//! OCR fallback for models without vision
+//!
+//! When the responding model can't take images, the text in attached images is
+//! extracted and injected into the prompt instead of silently dropping the images.
+//! Extraction uses the "vision" role model when one is set and the conversation's
+//! provider policy allows it, otherwise the local tesseract CLI.
+
+use super::super::AppState;
+use super::binding;
+use crate::llm::{self, ChatMessage, ImageData};
+use crate::models::ModelRole;
+use crate::prompts;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
+
+/// Settings key: "auto" (vision model, then tesseract), "vision_model", "tesseract" or "off"
+pub const IMAGE_OCR_ENGINE_KEY: &str = "image_ocr_engine";
+/// Settings key: path to the tesseract binary (defaults to `tesseract` on PATH)
+pub const TESSERACT_BINARY_KEY: &str = "tesseract_binary";
+/// Settings key: tesseract language codes, e.g. "eng+deu" (defaults to "eng")
+pub const TESSERACT_LANGUAGES_KEY: &str = "tesseract_languages";
+
+const TESSERACT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Extract text from each image. Entries are `None` when nothing could be extracted.
+pub(crate) async fn extract_image_text(
+    state: &AppState,
+    conversation_id: &str,
+    images: &[ImageData],
+    cancel_token: &CancellationToken,
+) -> Vec<Option<String>> {
+    let engine = setting(state, IMAGE_OCR_ENGINE_KEY)
+        .await
+        .unwrap_or_else(|| "auto".to_string());
+    if engine == "off" {
+        return vec![None; images.len()];
+    }
+
+    let vision = if engine == "auto" || engine == "vision_model" {
+        binding::resolve_allowed_role_binding(state, ModelRole::Vision, conversation_id).await
+    } else {
+        None
+    };
+    // Images stay on this machine when the policy rules out the configured vision model
+    let vision_denied = engine == "vision_model"
+        && vision.is_none()
+        && binding::resolve_role_binding(state, ModelRole::Vision)
+            .await
+            .is_some();
+    let use_tesseract =
+        engine == "tesseract" || (engine == "auto" && vision.is_none()) || vision_denied;
+    let tesseract_binary = setting(state, TESSERACT_BINARY_KEY)
+        .await
+        .unwrap_or_else(|| "tesseract".to_string());
+    let tesseract_languages = setting(state, TESSERACT_LANGUAGES_KEY)
+        .await
+        .unwrap_or_else(|| "eng".to_string());
+
+    let mut results = Vec::with_capacity(images.len());
+    for image in images {
+        if cancel_token.is_cancelled() {
+            results.push(None);
+            continue;
+        }
+
+        let text = if let Some(ref vision) = vision {
+            ocr_with_vision_model(vision, image, cancel_token).await
+        } else if use_tesseract {
+            ocr_with_tesseract(&tesseract_binary, &tesseract_languages, image).await
+        } else {
+            Err("No OCR engine available".to_string())
+        };
+
+        match text {
+            Ok(t) if !t.trim().is_empty() => results.push(Some(t.trim().to_string())),
+            Ok(_) => results.push(None),
+            Err(e) => {
+                tracing::warn!("⚠️ [ocr] Failed to extract image text: {}", e);
+                results.push(None);
+            }
+        }
+    }
+
+    results
+}
+
+/// Append extracted image text to a message so non-vision models can use it
+pub(crate) fn append_image_text(content: &str, texts: &[Option<String>]) -> String {
+    let mut result = content.to_string();
+    for (i, text) in texts.iter().enumerate() {
+        if let Some(text) = text {
+            if !result.is_empty() {
+                result.push_str("\n\n");
+            }
+            result.push_str(&format!("[Text extracted from image {}]\n{}", i + 1, text));
+        }
+    }
+    result
+}
+
+async fn ocr_with_vision_model(
+    vision: &binding::ResolvedBinding,
+    image: &ImageData,
+    cancel_token: &CancellationToken,
+) -> Result<String, String> {
+    let response = llm::call_provider(
+        &vision.provider,
+        vision.model.clone(),
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::IMAGE_OCR_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: "Extract the text from this image.".to_string(),
+                images: vec![image.clone()],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        vision.api_key.clone(),
+        vision.base_url.clone(),
+        vision.api_style.clone(),
+        cancel_token.child_token(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(response.content)
+}
+
+async fn ocr_with_tesseract(
+    binary: &str,
+    languages: &str,
+    image: &ImageData,
+) -> Result<String, String> {
+    let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &image.base64)
+        .map_err(|e| e.to_string())?;
+
+    // `tesseract stdin stdout` reads the image from stdin and prints the text
+    let mut child = tokio::process::Command::new(binary)
+        .args(["stdin", "stdout", "-l", languages])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", binary, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(&bytes).await.map_err(|e| e.to_string())?;
+    }
+
+    let output = tokio::time::timeout(TESSERACT_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| "tesseract timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "tesseract exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+async fn setting(state: &AppState, key: &str) -> Option<String> {
+    state
+        .db
+        .get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .filter(|v| !v.trim().is_empty())
+}
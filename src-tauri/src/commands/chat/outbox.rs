@@ -0,0 +1,168 @@
+//! Outbox for messages sent while offline
+//!
+//! When the network is down, `send_message` still saves the user message but parks the
+//! generation here instead of failing against an unreachable provider. The network
+//! monitor calls [`flush`] when connectivity returns. Chats with local providers
+//! (Ollama, localhost endpoints) never go through the outbox.
+//!
+//! The queue lives in memory: after a restart the user message is still there and can
+//! be regenerated, but it isn't sent automatically.
+
+use super::types::{
+    AudioAttachmentInput, FileAttachmentInput, ImageAttachmentInput, ParameterOverrides,
+    RoundtableOptions,
+};
+use super::{AppState, start_generation};
+use crate::error::AppError;
+use crate::events::{self, OutboxUpdated, StreamSink};
+use crate::network;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{Manager, State};
+
+/// Everything needed to start a generation for a saved user message
+pub(crate) struct PendingGeneration {
+    pub stream: StreamSink,
+    pub conversation_id: String,
+    pub content: String,
+    pub provider: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub base_url: Option<String>,
+    pub api_style: Option<String>,
+    pub include_history: Option<bool>,
+    pub system_prompt: Option<String>,
+    pub user_prompt: Option<String>,
+    pub model_db_id: Option<String>,
+    pub assistant_db_id: Option<String>,
+    pub urls_to_fetch: Option<Vec<String>>,
+    pub images: Option<Vec<ImageAttachmentInput>>,
+    pub files: Option<Vec<FileAttachmentInput>>,
+    pub audio: Option<Vec<AudioAttachmentInput>>,
+    pub search_enabled: bool,
+    pub user_message_id: String,
+    pub parameter_overrides: Option<ParameterOverrides>,
+    pub use_provider_defaults: bool,
+    pub roundtable: Option<RoundtableOptions>,
+    /// Replies a regeneration replaces, oldest first. They stay out of the history and
+    /// are removed once the new reply is saved.
+    pub replaced_message_ids: Vec<String>,
+}
+
+impl PendingGeneration {
+    /// Whether this has to wait for the network
+    pub fn needs_network(&self) -> bool {
+        !network::is_local_endpoint(&self.provider, self.base_url.as_deref())
+    }
+}
+
+/// A queued message as shown to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxEntry {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub content: String,
+    pub provider: String,
+    pub model: String,
+    pub queued_at: String,
+}
+
+struct QueuedGeneration {
+    entry: OutboxEntry,
+    pending: PendingGeneration,
+}
+
+#[derive(Default)]
+pub struct Outbox {
+    queue: Mutex<Vec<QueuedGeneration>>,
+}
+
+impl Outbox {
+    pub fn entries(&self) -> Vec<OutboxEntry> {
+        self.queue
+            .lock()
+            .map(|queue| queue.iter().map(|q| q.entry.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Queue a generation; one per conversation, since replies are generated in order
+    pub(crate) fn push(&self, pending: PendingGeneration) -> Result<OutboxEntry, String> {
+        let mut queue = self.queue.lock().map_err(|e| e.to_string())?;
+        if queue
+            .iter()
+            .any(|q| q.entry.conversation_id == pending.conversation_id)
+        {
+            return Err("A message is already waiting to be sent in this conversation".into());
+        }
+        let entry = OutboxEntry {
+            message_id: pending.user_message_id.clone(),
+            conversation_id: pending.conversation_id.clone(),
+            content: pending.content.clone(),
+            provider: pending.provider.clone(),
+            model: pending.model.clone(),
+            queued_at: chrono::Utc::now().to_rfc3339(),
+        };
+        queue.push(QueuedGeneration {
+            entry: entry.clone(),
+            pending,
+        });
+        Ok(entry)
+    }
+
+    /// Drop the queued message for a conversation, returning whether there was one
+    pub(crate) fn remove_conversation(&self, conversation_id: &str) -> bool {
+        let Ok(mut queue) = self.queue.lock() else {
+            return false;
+        };
+        let before = queue.len();
+        queue.retain(|q| q.entry.conversation_id != conversation_id);
+        queue.len() != before
+    }
+
+    fn take_all(&self) -> Vec<QueuedGeneration> {
+        self.queue
+            .lock()
+            .map(|mut queue| std::mem::take(&mut *queue))
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn emit_updated(app: &tauri::AppHandle, outbox: &Outbox) {
+    events::emit(
+        app,
+        OutboxUpdated {
+            entries: outbox.entries(),
+        },
+    );
+}
+
+/// Start every queued generation, oldest first
+pub(crate) async fn flush(app: &tauri::AppHandle) {
+    let state: State<'_, AppState> = app.state();
+    let queued = state.outbox.take_all();
+    if queued.is_empty() {
+        return;
+    }
+
+    tracing::info!("📤 [outbox] Dispatching {} queued message(s)", queued.len());
+    emit_updated(app, &state.outbox);
+    for queued in queued {
+        tracing::info!(
+            "📤 [outbox] Sending message {} in conversation {}",
+            queued.entry.message_id,
+            queued.entry.conversation_id
+        );
+        start_generation(state.inner().clone(), app.clone(), queued.pending).await;
+    }
+}
+
+/// Messages waiting for the network to come back
+#[tauri::command]
+pub async fn list_outbox(state: State<'_, AppState>) -> Result<Vec<OutboxEntry>, AppError> {
+    Ok(state.outbox.entries())
+}
+
+#[tauri::command]
+pub async fn get_network_status() -> Result<bool, AppError> {
+    Ok(network::is_online())
+}
@@ -3,7 +3,7 @@
 //! Handles auto-adding participants (users, models, assistants) to conversations.
 
 use super::AppState;
-use crate::models::CreateConversationParticipantRequest;
+use crate::models::{ConversationParticipant, CreateConversationParticipantRequest};
 
 /// Ensure required participants are added to a conversation
 pub async fn ensure_participants(
@@ -28,18 +28,6 @@ pub async fn ensure_participants(
         .iter()
         .any(|p| p.participant_type == "user");
 
-    let current_model_exists = model_db_id.as_ref().is_some_and(|model_id| {
-        existing_participants
-            .iter()
-            .any(|p| p.participant_type == "model" && p.participant_id.as_ref() == Some(model_id))
-    });
-
-    let current_assistant_exists = assistant_db_id.as_ref().is_some_and(|assistant_id| {
-        existing_participants.iter().any(|p| {
-            p.participant_type == "assistant" && p.participant_id.as_ref() == Some(assistant_id)
-        })
-    });
-
     tracing::info!(
         "📋 [send_message] Current participants: {} total",
         existing_participants.len()
@@ -50,15 +38,51 @@ pub async fn ensure_participants(
         add_self_user_participant(state, conversation_id).await;
     }
 
-    // Add assistant or model participant
+    // Add assistant or model participant. Ones that left the conversation
+    // stay out until they're re-added explicitly.
     if let Some(assistant_id) = assistant_db_id {
-        if !current_assistant_exists {
-            add_assistant_participant(state, conversation_id, assistant_id).await;
+        match presence(&existing_participants, "assistant", assistant_id) {
+            Presence::Missing => {
+                add_assistant_participant(state, conversation_id, assistant_id).await
+            }
+            Presence::Left => tracing::info!(
+                "👋 [send_message] Assistant {} left this conversation; not re-adding",
+                assistant_id
+            ),
+            Presence::Active => {}
+        }
+    } else if let Some(model_id) = model_db_id {
+        match presence(&existing_participants, "model", model_id) {
+            Presence::Missing => add_model_participant(state, conversation_id, model_id).await,
+            Presence::Left => tracing::info!(
+                "👋 [send_message] Model {} left this conversation; not re-adding",
+                model_id
+            ),
+            Presence::Active => {}
         }
-    } else if let Some(model_id) = model_db_id
-        && !current_model_exists
-    {
-        add_model_participant(state, conversation_id, model_id).await;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Presence {
+    Missing,
+    Active,
+    /// Was deactivated (status `left`)
+    Left,
+}
+
+fn presence(
+    participants: &[ConversationParticipant],
+    participant_type: &str,
+    participant_id: &str,
+) -> Presence {
+    match participants.iter().find(|p| {
+        p.participant_type == participant_type
+            && p.participant_id.as_deref() == Some(participant_id)
+    }) {
+        None => Presence::Missing,
+        Some(p) if p.status == "left" => Presence::Left,
+        Some(_) => Presence::Active,
     }
 }
 
@@ -142,3 +166,39 @@ async fn add_model_participant(state: &AppState, conversation_id: &str, model_id
         Err(e) => tracing::warn!("⚠️  [send_message] Error getting model: {}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(participant_type: &str, id: &str, status: &str) -> ConversationParticipant {
+        ConversationParticipant {
+            id: format!("p-{}", id),
+            conversation_id: "c".to_string(),
+            participant_type: participant_type.to_string(),
+            participant_id: Some(id.to_string()),
+            display_name: None,
+            role: "member".to_string(),
+            status: status.to_string(),
+            joined_at: String::new(),
+            left_at: None,
+            last_read_at: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_presence_respects_left_participants() {
+        let participants = vec![
+            participant("model", "m1", "active"),
+            participant("model", "m2", "left"),
+        ];
+        assert_eq!(presence(&participants, "model", "m1"), Presence::Active);
+        assert_eq!(presence(&participants, "model", "m2"), Presence::Left);
+        assert_eq!(presence(&participants, "model", "m3"), Presence::Missing);
+        assert_eq!(
+            presence(&participants, "assistant", "m1"),
+            Presence::Missing
+        );
+    }
+}
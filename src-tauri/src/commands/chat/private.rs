@@ -0,0 +1,261 @@
+//! Private (incognito) conversations
+//!
+//! A private conversation and its messages live only in this registry: nothing is
+//! written to the database or to storage, and everything is gone once the
+//! conversation is closed or the app quits. Replies stream like any other, but the
+//! generation pipeline skips persistence for them: no title generation, summaries,
+//! usage records, follow-up suggestions, webhooks or notifications.
+//!
+//! Attachments, URL fetching, web search and roundtables all store their results, so
+//! private conversations don't support them.
+
+use super::AppState;
+use super::types::{
+    AudioAttachmentInput, FileAttachmentInput, ImageAttachmentInput, RoundtableOptions,
+};
+use crate::error::AppError;
+use crate::models::{Conversation, Message};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::State;
+use uuid::Uuid;
+
+const DEFAULT_TITLE: &str = "Private chat";
+
+struct PrivateConversation {
+    conversation: Conversation,
+    messages: Vec<Message>,
+}
+
+/// In-memory store for private conversations
+#[derive(Default)]
+pub struct PrivateConversations {
+    conversations: Mutex<HashMap<String, PrivateConversation>>,
+}
+
+impl PrivateConversations {
+    pub fn create(&self, title: Option<String>) -> Conversation {
+        let now = chrono::Utc::now().to_rfc3339();
+        let conversation = Conversation {
+            id: Uuid::now_v7().to_string(),
+            title: title
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_TITLE.to_string()),
+            summary: None,
+            summary_message_count: 0,
+            created_at: now.clone(),
+            updated_at: now,
+            last_message: None,
+            is_private: true,
+            metadata: None,
+            workspace_id: None,
+            archived_at: None,
+        };
+        if let Ok(mut conversations) = self.conversations.lock() {
+            conversations.insert(
+                conversation.id.clone(),
+                PrivateConversation {
+                    conversation: conversation.clone(),
+                    messages: Vec::new(),
+                },
+            );
+        }
+        conversation
+    }
+
+    pub fn contains(&self, conversation_id: &str) -> bool {
+        self.conversations
+            .lock()
+            .is_ok_and(|c| c.contains_key(conversation_id))
+    }
+
+    pub fn get(&self, conversation_id: &str) -> Option<Conversation> {
+        let conversations = self.conversations.lock().ok()?;
+        conversations
+            .get(conversation_id)
+            .map(|c| c.conversation.clone())
+    }
+
+    /// Most recently active first
+    pub fn list(&self) -> Vec<Conversation> {
+        let Ok(conversations) = self.conversations.lock() else {
+            return Vec::new();
+        };
+        let mut list: Vec<Conversation> = conversations
+            .values()
+            .map(|c| c.conversation.clone())
+            .collect();
+        list.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        list
+    }
+
+    /// Messages of a private conversation, or `None` if it isn't one
+    pub fn messages(&self, conversation_id: &str) -> Option<Vec<Message>> {
+        let conversations = self.conversations.lock().ok()?;
+        conversations
+            .get(conversation_id)
+            .map(|c| c.messages.clone())
+    }
+
+    /// Append a message to its conversation. Returns false if the conversation was
+    /// closed in the meantime.
+    pub fn push_message(&self, message: Message) -> bool {
+        let Ok(mut conversations) = self.conversations.lock() else {
+            return false;
+        };
+        let Some(conversation) = message
+            .conversation_id
+            .as_deref()
+            .and_then(|id| conversations.get_mut(id))
+        else {
+            return false;
+        };
+        conversation.conversation.updated_at = message.created_at.clone();
+        conversation.conversation.last_message = Some(message.content.clone());
+        conversation.messages.push(message);
+        true
+    }
+
+    pub fn remove(&self, conversation_id: &str) -> bool {
+        self.conversations
+            .lock()
+            .is_ok_and(|mut c| c.remove(conversation_id).is_some())
+    }
+}
+
+/// An unsaved message for a private conversation
+pub(crate) fn new_message(
+    conversation_id: &str,
+    sender_type: &str,
+    sender_id: Option<String>,
+    content: String,
+) -> Message {
+    Message {
+        id: Uuid::now_v7().to_string(),
+        conversation_id: Some(conversation_id.to_string()),
+        sender_type: sender_type.to_string(),
+        sender_id,
+        content,
+        tokens: None,
+        input_tokens: None,
+        output_tokens: None,
+        cost: None,
+        follow_up_suggestions: None,
+        provider_type: None,
+        model_id: None,
+        model_params: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Reject send options that would store data
+pub(crate) fn check_send_options(
+    urls_to_fetch: &Option<Vec<String>>,
+    images: &Option<Vec<ImageAttachmentInput>>,
+    files: &Option<Vec<FileAttachmentInput>>,
+    audio: &Option<Vec<AudioAttachmentInput>>,
+    search_enabled: Option<bool>,
+    roundtable: &Option<RoundtableOptions>,
+) -> Result<(), AppError> {
+    let has_attachments = images.as_ref().is_some_and(|v| !v.is_empty())
+        || files.as_ref().is_some_and(|v| !v.is_empty())
+        || audio.as_ref().is_some_and(|v| !v.is_empty());
+    let unsupported = if has_attachments {
+        Some("Attachments")
+    } else if urls_to_fetch.as_ref().is_some_and(|v| !v.is_empty()) {
+        Some("Fetching URLs")
+    } else if search_enabled == Some(true) {
+        Some("Web search")
+    } else if roundtable
+        .as_ref()
+        .is_some_and(|o| !o.participants.is_empty())
+    {
+        Some("Roundtables")
+    } else {
+        None
+    };
+    match unsupported {
+        Some(feature) => Err(AppError::validation(format!(
+            "{} can't be used in private conversations",
+            feature
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Start a conversation that is kept in memory only
+#[tauri::command]
+pub async fn create_private_conversation(
+    state: State<'_, AppState>,
+    title: Option<String>,
+) -> Result<Conversation, AppError> {
+    let conversation = state.private_conversations.create(title);
+    tracing::info!(
+        "🕶️ [private] Created private conversation {}",
+        conversation.id
+    );
+    Ok(conversation)
+}
+
+#[tauri::command]
+pub async fn list_private_conversations(
+    state: State<'_, AppState>,
+) -> Result<Vec<Conversation>, AppError> {
+    Ok(state.private_conversations.list())
+}
+
+/// Stop any reply in progress and discard the conversation and its messages
+#[tauri::command]
+pub async fn close_private_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> Result<bool, AppError> {
+    if let Some(cancel_token) = state
+        .generation_tasks
+        .write()
+        .await
+        .remove(&conversation_id)
+    {
+        cancel_token.cancel();
+    }
+    state.outbox.remove_conversation(&conversation_id);
+    state.bash_session_manager.remove(&conversation_id);
+    state.request_log.remove(&conversation_id);
+    Ok(state.private_conversations.remove(&conversation_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_private_conversation_lifecycle() {
+        let store = PrivateConversations::default();
+        let conversation = store.create(None);
+        assert!(conversation.is_private);
+        assert_eq!(conversation.title, DEFAULT_TITLE);
+        assert!(store.contains(&conversation.id));
+
+        let message = new_message(&conversation.id, "user", None, "hello".to_string());
+        assert!(store.push_message(message));
+        let messages = store.messages(&conversation.id).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            store.get(&conversation.id).unwrap().last_message.as_deref(),
+            Some("hello")
+        );
+
+        assert!(store.remove(&conversation.id));
+        assert!(store.messages(&conversation.id).is_none());
+        let late = new_message(&conversation.id, "model", None, "reply".to_string());
+        assert!(!store.push_message(late));
+    }
+
+    #[test]
+    fn test_check_send_options() {
+        assert!(check_send_options(&None, &None, &None, &None, Some(false), &None).is_ok());
+        let urls = Some(vec!["https://example.com".to_string()]);
+        assert!(check_send_options(&urls, &None, &None, &None, None, &None).is_err());
+        assert!(check_send_options(&None, &None, &None, &None, Some(true), &None).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Raw request snapshots for debugging
+//!
+//! Keeps the payload of the most recent generation per conversation (effective system
+//! prompt, parameters, tools and converted messages) so users can check exactly what
+//! the provider received. API keys are never stored; tokens found in the base URL,
+//! parameters or message text are masked, and inline image data is elided.
+//!
+//! Snapshots live in memory only and are gone after a restart.
+
+use super::AppState;
+use crate::error::AppError;
+use crate::logger::redact_secrets;
+use crate::models::ModelParameters;
+use rig::completion::Message as RigMessage;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Strings longer than this that look like base64 are treated as inline file data
+const INLINE_DATA_MIN_LEN: usize = 512;
+
+/// The request sent to the provider for a generation
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestDebug {
+    pub conversation_id: String,
+    pub provider_type: String,
+    pub model_id: String,
+    pub base_url: Option<String>,
+    pub api_style: Option<String>,
+    /// System prompt after tool, skill and environment instructions were appended
+    pub system_prompt: Option<String>,
+    pub model_params: ModelParameters,
+    pub tools: Vec<String>,
+    /// Messages in the provider-neutral format, prompt last
+    pub messages: Vec<Value>,
+    pub created_at: String,
+}
+
+impl RequestDebug {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        conversation_id: &str,
+        provider_type: &str,
+        model_id: &str,
+        base_url: Option<&str>,
+        api_style: Option<&str>,
+        system_prompt: Option<&str>,
+        model_params: &ModelParameters,
+        tools: Vec<String>,
+        history: &[RigMessage],
+        prompt: &RigMessage,
+    ) -> Self {
+        let mut model_params = model_params.clone();
+        if let Some(params) = model_params.additional_params.as_mut() {
+            redact_value(params);
+        }
+        let messages = history
+            .iter()
+            .chain(std::iter::once(prompt))
+            .map(|message| {
+                let mut value = serde_json::to_value(message).unwrap_or(Value::Null);
+                redact_value(&mut value);
+                value
+            })
+            .collect();
+
+        Self {
+            conversation_id: conversation_id.to_string(),
+            provider_type: provider_type.to_string(),
+            model_id: model_id.to_string(),
+            base_url: base_url.map(redact_secrets),
+            api_style: api_style.map(str::to_string),
+            system_prompt: system_prompt.map(redact_secrets),
+            model_params,
+            tools,
+            messages,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Mask secrets in every string of a JSON value and elide inline file data
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::String(s) => {
+            *s = if is_inline_data(s) {
+                format!("[{} bytes of base64 data]", s.len())
+            } else {
+                redact_secrets(s)
+            };
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) && value.is_string() {
+                    *value = Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parameter names that hold credentials, e.g. `api_key` or `Authorization` headers
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    ["key", "token", "secret", "password", "authorization"]
+        .iter()
+        .any(|word| key.contains(word))
+}
+
+fn is_inline_data(s: &str) -> bool {
+    let data = s
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(',').map(|(_, data)| data))
+        .unwrap_or(s);
+    data.len() >= INLINE_DATA_MIN_LEN
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+}
+
+/// Most recent request per conversation
+#[derive(Default)]
+pub struct RequestLog {
+    requests: Mutex<HashMap<String, RequestDebug>>,
+}
+
+impl RequestLog {
+    pub(crate) fn record(&self, request: RequestDebug) {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.insert(request.conversation_id.clone(), request);
+        }
+    }
+
+    pub fn get(&self, conversation_id: &str) -> Option<RequestDebug> {
+        self.requests
+            .lock()
+            .ok()
+            .and_then(|requests| requests.get(conversation_id).cloned())
+    }
+
+    pub(crate) fn remove(&self, conversation_id: &str) {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.remove(conversation_id);
+        }
+    }
+}
+
+/// The request sent for the most recent generation in a conversation, if any was made
+/// since the app started
+#[tauri::command]
+pub async fn get_last_request_debug(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> Result<Option<RequestDebug>, AppError> {
+    Ok(state.request_log.get(&conversation_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_value_masks_secrets_and_inline_data() {
+        let image = "A".repeat(INLINE_DATA_MIN_LEN);
+        let mut value = serde_json::json!({
+            "content": [
+                { "type": "text", "text": "my key is sk-proj-abcdefghijklmnopqrstuvwx" },
+                { "type": "image", "data": image },
+            ]
+        });
+        redact_value(&mut value);
+
+        let text = value["content"][0]["text"].as_str().unwrap();
+        assert_eq!(text, "my key is [REDACTED]");
+        let data = value["content"][1]["data"].as_str().unwrap();
+        assert_eq!(data, "[512 bytes of base64 data]");
+
+        let mut params = serde_json::json!({ "headers": { "Authorization": "abc" }, "top_k": 40 });
+        redact_value(&mut params);
+        assert_eq!(params["headers"]["Authorization"], "[REDACTED]");
+        assert_eq!(params["top_k"], 40);
+    }
+
+    #[test]
+    fn test_is_inline_data() {
+        assert!(!is_inline_data(
+            "Why did the model ignore my system prompt?"
+        ));
+        assert!(is_inline_data(&format!(
+            "data:image/png;base64,{}",
+            "iVBORw0KGgo".repeat(60)
+        )));
+        // Long prose is not mistaken for data
+        assert!(!is_inline_data(&"word ".repeat(200)));
+    }
+}
@@ -0,0 +1,305 @@
+//! Roundtable (multi-participant) mode
+//!
+//! Several participants (models or assistants) answer the same user message in turn.
+//! Each turn rebuilds the chat history from the database, so every participant sees
+//! the replies saved by the participants before it.
+
+use super::binding::{self, ResolvedBinding};
+use super::types::{ParameterOverrides, RoundtableOptions, RoundtableParticipant};
+use super::{AppState, attachment_processing, message_builder, participants, streaming};
+use crate::error::AppError;
+use crate::events::{
+    self, ChatError, RoundtableComplete, RoundtableParticipantSkipped, RoundtableTurnStarted,
+    StreamSink,
+};
+use crate::llm;
+use crate::prompts;
+use tokio_util::sync::CancellationToken;
+
+/// Upper bound on rounds so a single message can't trigger unbounded generation
+pub const MAX_ROUNDTABLE_ROUNDS: u32 = 5;
+
+/// Upper bound on participants per roundtable
+pub const MAX_ROUNDTABLE_PARTICIPANTS: usize = 8;
+
+/// A participant with its resolved connection and display name
+struct Seat {
+    participant: RoundtableParticipant,
+    display_name: String,
+    binding: ResolvedBinding,
+}
+
+/// Run all roundtable turns for a user message
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_roundtable(
+    state: AppState,
+    app: tauri::AppHandle,
+    stream: StreamSink,
+    conversation_id: String,
+    content: String,
+    processed_content: String,
+    user_message_id: String,
+    user_images: Vec<attachment_processing::ParsedImage>,
+    user_files: Vec<llm::FileData>,
+    citation_sources: Vec<Option<String>>,
+    include_history: bool,
+    parameter_overrides: Option<ParameterOverrides>,
+    use_provider_defaults: bool,
+    options: RoundtableOptions,
+    cancel_token: CancellationToken,
+) {
+    let rounds = options.rounds.unwrap_or(1).clamp(1, MAX_ROUNDTABLE_ROUNDS);
+    let seats = resolve_seats(&state, &app, &conversation_id, options.participants).await;
+
+    if seats.is_empty() {
+        let error = AppError::validation("No roundtable participants could be resolved");
+        events::emit(&app, ChatError::new(&conversation_id, &error));
+        let mut tasks = state.generation_tasks.write().await;
+        tasks.remove(&conversation_id);
+        return;
+    }
+
+    let roster: Vec<String> = seats.iter().map(|s| s.display_name.clone()).collect();
+    tracing::info!(
+        "🎙️ [roundtable] Starting {} round(s) with participants: {:?}",
+        rounds,
+        roster
+    );
+
+    let conversation_settings = state
+        .db
+        .get_conversation_settings(&conversation_id)
+        .await
+        .ok();
+    let mut turns_completed = 0u32;
+
+    'rounds: for round in 0..rounds {
+        for (turn, seat) in seats.iter().enumerate() {
+            if cancel_token.is_cancelled() {
+                break 'rounds;
+            }
+
+            // Each turn's streaming removes the task when done; re-register so
+            // stop_generation keeps working for the remaining turns
+            {
+                let mut tasks = state.generation_tasks.write().await;
+                tasks.insert(conversation_id.clone(), cancel_token.clone());
+            }
+
+            events::emit(
+                &app,
+                RoundtableTurnStarted {
+                    conversation_id: conversation_id.clone(),
+                    round,
+                    turn,
+                    participant_type: seat.participant.participant_type.clone(),
+                    participant_id: seat.participant.participant_id.clone(),
+                    display_name: seat.display_name.clone(),
+                },
+            );
+
+            let is_opening_turn = round == 0 && turn == 0;
+            let chat_messages = if is_opening_turn {
+                message_builder::build_chat_messages(
+                    &state,
+                    &app,
+                    &seat.binding.provider,
+                    &seat.binding.model,
+                    &conversation_id,
+                    &user_message_id,
+                    &processed_content,
+                    &seat.binding.user_prompt,
+                    &seat.binding.system_prompt,
+                    include_history,
+                    &user_images,
+                    &user_files,
+                    &[],
+                )
+                .await
+            } else {
+                // Later turns see the original user message and all prior replies
+                // as history, then get a turn prompt addressed to them
+                let turn_prompt =
+                    prompts::build_roundtable_turn_prompt(&seat.display_name, &roster);
+                message_builder::build_chat_messages(
+                    &state,
+                    &app,
+                    &seat.binding.provider,
+                    &seat.binding.model,
+                    &conversation_id,
+                    "",
+                    &turn_prompt,
+                    &None,
+                    &seat.binding.system_prompt,
+                    true,
+                    &[],
+                    &[],
+                    &[],
+                )
+                .await
+            };
+
+            let assistant_config =
+                super::get_assistant_config(&state, &seat.binding.assistant_db_id).await;
+            let model_preset =
+                super::get_model_default_preset(&state, &seat.binding.model_db_id).await;
+            let model_params = super::build_model_params(
+                assistant_config.as_ref(),
+                model_preset.as_ref(),
+                conversation_settings.as_ref(),
+                parameter_overrides.clone(),
+                use_provider_defaults,
+            );
+
+            let system_prompt_for_agent = chat_messages
+                .first()
+                .filter(|m| m.role == "system")
+                .map(|m| m.content.clone());
+
+            streaming::handle_agent_streaming(
+                seat.binding.provider.clone(),
+                seat.binding.model.clone(),
+                chat_messages,
+                seat.binding.api_key.clone(),
+                seat.binding.base_url.clone(),
+                seat.binding.api_style.clone(),
+                system_prompt_for_agent,
+                model_params,
+                cancel_token.clone(),
+                state.clone(),
+                app.clone(),
+                stream.clone(),
+                conversation_id.clone(),
+                content.clone(),
+                seat.binding.model_db_id.clone(),
+                seat.binding.assistant_db_id.clone(),
+                // Only the opening turn sees the numbered pages
+                if is_opening_turn {
+                    citation_sources.clone()
+                } else {
+                    Vec::new()
+                },
+                Vec::new(),
+            )
+            .await;
+
+            turns_completed += 1;
+        }
+    }
+
+    let cancelled = cancel_token.is_cancelled();
+    tracing::info!(
+        "🎙️ [roundtable] Finished after {} turn(s) (cancelled: {})",
+        turns_completed,
+        cancelled
+    );
+
+    events::emit(
+        &app,
+        RoundtableComplete {
+            conversation_id: conversation_id.clone(),
+            turns_completed,
+            cancelled,
+        },
+    );
+
+    let mut tasks = state.generation_tasks.write().await;
+    tasks.remove(&conversation_id);
+}
+
+/// Resolve participant bindings, skipping (and reporting) any that can't be resolved
+async fn resolve_seats(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+    requested: Vec<RoundtableParticipant>,
+) -> Vec<Seat> {
+    let mut seats = Vec::new();
+
+    for participant in requested.into_iter().take(MAX_ROUNDTABLE_PARTICIPANTS) {
+        let resolved = match participant.participant_type.as_str() {
+            "assistant" => resolve_assistant_seat(state, &participant.participant_id).await,
+            "model" => resolve_model_seat(state, &participant.participant_id).await,
+            other => Err(format!("Unsupported participant type: {}", other)),
+        };
+        let resolved = match resolved {
+            Ok((display_name, binding)) => crate::commands::enforce_provider_policy(
+                state,
+                conversation_id,
+                &binding.provider,
+                binding.base_url.as_deref(),
+                binding.model_db_id.as_deref(),
+                binding.assistant_db_id.as_deref(),
+            )
+            .await
+            .map(|_| (display_name, binding))
+            .map_err(|e| e.message),
+            Err(e) => Err(e),
+        };
+
+        match resolved {
+            Ok((display_name, binding)) => {
+                participants::ensure_participants(
+                    state,
+                    conversation_id,
+                    &binding.model_db_id,
+                    &binding.assistant_db_id,
+                )
+                .await;
+                seats.push(Seat {
+                    participant,
+                    display_name,
+                    binding,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️ [roundtable] Skipping participant {}: {}",
+                    participant.participant_id,
+                    e
+                );
+                events::emit(
+                    app,
+                    RoundtableParticipantSkipped {
+                        conversation_id: conversation_id.to_string(),
+                        participant_type: participant.participant_type,
+                        participant_id: participant.participant_id,
+                        error: e,
+                    },
+                );
+            }
+        }
+    }
+
+    seats
+}
+
+async fn resolve_assistant_seat(
+    state: &AppState,
+    assistant_id: &str,
+) -> Result<(String, ResolvedBinding), String> {
+    let binding = binding::resolve_assistant_binding(state, assistant_id).await?;
+    let name = state
+        .db
+        .get_assistant(assistant_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|a| a.name)
+        .unwrap_or_else(|| binding.model.clone());
+    Ok((name, binding))
+}
+
+async fn resolve_model_seat(
+    state: &AppState,
+    model_db_id: &str,
+) -> Result<(String, ResolvedBinding), String> {
+    let binding = binding::resolve_model_binding(state, model_db_id).await?;
+    let name = state
+        .db
+        .get_model(model_db_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|m| m.name)
+        .unwrap_or_else(|| binding.model.clone());
+    Ok((name, binding))
+}
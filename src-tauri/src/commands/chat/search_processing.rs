@@ -1,25 +1,78 @@
 //! Search decision and execution logic
 
 use super::super::AppState;
-use crate::models::{CreateSearchDecisionRequest, CreateSearchResultRequest};
+use crate::events::{
+    self, AttachmentUpdate, ChatWarning, SearchCompleted, SearchDecisionComplete,
+    SearchDecisionStarted, SearchResultAttachment,
+};
+use crate::models::{
+    ContextType, CreateSearchDecisionRequest, CreateSearchResultItemRequest,
+    CreateSearchResultRequest, FetchResult, ModelRole,
+};
 use crate::web_search::SearchProvider;
-use tauri::Emitter;
+use chrono::{DateTime, Utc};
+use tokio_util::sync::CancellationToken;
+
+/// Results requested from the engine; all are stored as the search's result list
+const SEARCH_RESULT_LIMIT: usize = 10;
+/// Top results whose pages are fetched into the context
+const FETCHED_RESULT_LIMIT: usize = 5;
+/// Results already fetched in the conversation within this many hours are skipped;
+/// older ones are fetched again in case the page changed
+const REVISIT_AFTER_HOURS: i64 = 24;
 
 /// Result of search processing
 pub(crate) struct SearchProcessingResult {
     pub urls: Vec<String>,
     pub search_result_id: Option<String>,
+    /// Search results skipped because an earlier message in the conversation fetched them
+    pub previously_retrieved: Vec<FetchResult>,
+}
+
+/// Get the search provider for a conversation: its workspace's choice, else the global
+/// setting
+async fn get_search_provider(state: &AppState, conversation_id: &str) -> SearchProvider {
+    let workspace_provider = match state.db.get_conversation_workspace(conversation_id).await {
+        Ok(Some(workspace)) => workspace.settings.search_provider,
+        _ => None,
+    };
+    let provider_id = match workspace_provider {
+        Some(id) => Some(id),
+        None => state.db.get_setting("search_provider").await.ok().flatten(),
+    };
+    provider_id
+        .and_then(|id| SearchProvider::from_id(&id))
+        .unwrap_or_default()
 }
 
-/// Get the configured search provider from settings
-async fn get_search_provider(state: &AppState) -> SearchProvider {
-    match state.db.get_setting("search_provider").await {
-        Ok(Some(provider_id)) => SearchProvider::from_id(&provider_id).unwrap_or_default(),
-        _ => SearchProvider::default(),
+/// Detach a message's previous search results and the pages fetched for them, so a new
+/// search replaces them as the message's sources
+pub(crate) async fn supersede_search_context(
+    state: &AppState,
+    user_message_id: &str,
+) -> anyhow::Result<()> {
+    for search_result in state
+        .db
+        .get_search_results_by_message(user_message_id)
+        .await?
+    {
+        for fetch in state
+            .db
+            .get_fetch_results_by_source("search", &search_result.id)
+            .await?
+        {
+            state
+                .db
+                .unlink_message_context(user_message_id, ContextType::FetchResult, &fetch.id)
+                .await?;
+        }
+        state.db.delete_search_result(&search_result.id).await?;
     }
+    Ok(())
 }
 
 /// Process search decision and execute search if needed
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn process_search_decision(
     state: &AppState,
     app: &tauri::AppHandle,
@@ -32,68 +85,36 @@ pub(crate) async fn process_search_decision(
     user_message_id: &str,
     conversation_id: &str,
     fallback_urls: Vec<String>,
+    cancel_token: CancellationToken,
 ) -> SearchProcessingResult {
     tracing::info!("🔍 [search] Web search enabled, checking if search is needed...");
 
-    // Emit event to show "deciding" state immediately
-    let _ = app.emit(
-        "search-decision-started",
-        serde_json::json!({
-            "message_id": user_message_id,
-            "conversation_id": conversation_id,
-        }),
-    );
-
-    // Use AI to decide if search is truly needed
-    let decision = match crate::web_search::decide_search_needed(
-        content, provider, model, api_key, base_url, api_style,
-    )
-    .await
-    {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::warn!("⚠️ [search] Search decision failed, skipping search: {}", e);
-            crate::web_search::SearchDecisionResult {
-                reasoning: format!("Decision failed: {}", e),
-                search_needed: false,
-                search_query: None,
-            }
-        }
-    };
-
-    // Store the search decision in database (as a process step)
-    match state
-        .db
-        .create_search_decision(CreateSearchDecisionRequest {
-            message_id: user_message_id.to_string(),
-            reasoning: decision.reasoning.clone(),
-            search_needed: decision.search_needed,
-            search_query: decision.search_query.clone(),
-            search_result_id: None,
-            display_order: Some(0),
-        })
-        .await
-    {
-        Ok(search_decision) => {
+    // A decision stored earlier for this message (set by a forced re-run) is not asked again
+    let decision = match stored_decision(state, user_message_id).await {
+        Some(decision) => {
             tracing::info!(
-                "📝 [search] Created search decision: {}",
-                search_decision.id
-            );
-            // SearchDecision is now directly linked via message_id FK
-
-            // Emit search decision complete for UI
-            let _ = app.emit(
-                "search-decision-complete",
-                serde_json::json!({
-                    "message_id": user_message_id,
-                    "conversation_id": conversation_id,
-                }),
+                "♻️ [search] Using stored search decision, query: {:?}",
+                decision.search_query
             );
+            decision
         }
-        Err(e) => {
-            tracing::error!("❌ [search] Failed to create search decision: {}", e);
+        None => {
+            decide_and_store(
+                state,
+                app,
+                content,
+                provider,
+                model,
+                api_key,
+                base_url,
+                api_style,
+                user_message_id,
+                conversation_id,
+                cancel_token,
+            )
+            .await
         }
-    }
+    };
 
     if !decision.search_needed {
         tracing::info!(
@@ -103,6 +124,7 @@ pub(crate) async fn process_search_decision(
         return SearchProcessingResult {
             urls: fallback_urls,
             search_result_id: None,
+            previously_retrieved: Vec::new(),
         };
     }
 
@@ -116,7 +138,7 @@ pub(crate) async fn process_search_decision(
     );
 
     // Get the configured search provider
-    let provider = get_search_provider(state).await;
+    let provider = get_search_provider(state, conversation_id).await;
     let engine_id = provider.id().to_string();
     tracing::info!(
         "🔍 [search] Using search provider: {}",
@@ -145,20 +167,19 @@ pub(crate) async fn process_search_decision(
             // SearchResult is now directly linked via message_id FK
 
             // Emit attachment update so UI shows SearchPreview immediately
-            let _ = app.emit(
-                "attachment-update",
-                serde_json::json!({
-                    "message_id": user_message_id,
-                    "conversation_id": conversation_id,
-                    "attachment": {
-                        "type": "search_result",
-                        "id": search_result.id,
-                        "query": keywords,
-                        "engine": engine_id,
-                        "total_results": null,
-                        "searched_at": searched_at,
-                    }
-                }),
+            events::emit(
+                app,
+                AttachmentUpdate {
+                    attachment: Some(SearchResultAttachment {
+                        attachment_type: "search_result",
+                        id: search_result.id.clone(),
+                        query: keywords.clone(),
+                        engine: engine_id.clone(),
+                        total_results: None,
+                        searched_at: Some(searched_at.clone()),
+                    }),
+                    ..AttachmentUpdate::new(user_message_id, conversation_id, &search_result.id)
+                },
             );
 
             Some(search_result.id)
@@ -170,7 +191,7 @@ pub(crate) async fn process_search_decision(
     };
 
     // Now perform the actual search using the configured provider
-    match crate::web_search::search(provider, &keywords, 5).await {
+    match crate::web_search::search_with_fallback(provider, &keywords, SEARCH_RESULT_LIMIT).await {
         Ok(search_response) => {
             tracing::info!(
                 "✅ [search] Search completed, found {} results",
@@ -186,52 +207,352 @@ pub(crate) async fn process_search_decision(
                 {
                     tracing::error!("Failed to update search result total: {}", e);
                 }
+                let items = search_response
+                    .results
+                    .iter()
+                    .map(|r| CreateSearchResultItemRequest {
+                        title: r.title.clone(),
+                        url: r.url.clone(),
+                        snippet: r.snippet.clone(),
+                    })
+                    .collect();
+                if let Err(e) = state.db.set_search_result_items(sr_id, items).await {
+                    tracing::error!("Failed to store search result items: {}", e);
+                }
 
                 // Emit attachment-update so frontend shows result count immediately
-                let _ = app.emit(
-                    "attachment-update",
-                    serde_json::json!({
-                        "message_id": user_message_id,
-                        "conversation_id": conversation_id,
-                        "attachment": {
-                            "type": "search_result",
-                            "id": sr_id,
-                            "query": search_response.query,
-                            "engine": search_response.provider.id(),
-                            "total_results": search_response.total_results,
-                        }
-                    }),
+                events::emit(
+                    app,
+                    AttachmentUpdate {
+                        attachment: Some(SearchResultAttachment {
+                            attachment_type: "search_result",
+                            id: sr_id.clone(),
+                            query: search_response.query.clone(),
+                            engine: search_response.provider.id().to_string(),
+                            total_results: Some(search_response.total_results),
+                            searched_at: None,
+                        }),
+                        ..AttachmentUpdate::new(user_message_id, conversation_id, sr_id)
+                    },
+                );
+            }
+
+            // Pages an earlier message already fetched make room for lower-ranked results
+            let visited = state
+                .db
+                .get_fetch_results_by_conversation(conversation_id, user_message_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("⚠️ [search] Failed to load visited pages: {}", e);
+                    Vec::new()
+                });
+            let (search_urls, previously_retrieved) = skip_visited(
+                search_response.results.iter().map(|r| r.url.clone()),
+                &visited,
+                Utc::now(),
+            );
+            if !previously_retrieved.is_empty() {
+                tracing::info!(
+                    "♻️ [search] Skipping {} result(s) already fetched in this conversation",
+                    previously_retrieved.len()
                 );
             }
 
             // Emit search completed event
-            let search_urls: Vec<String> = search_response
-                .results
-                .iter()
-                .map(|r| r.url.clone())
-                .collect();
-            let _ = app.emit(
-                "search-completed",
-                serde_json::json!({
-                    "message_id": user_message_id,
-                    "conversation_id": conversation_id,
-                    "search_result_id": search_result_id,
-                    "query": search_response.query,
-                    "results_count": search_response.results.len(),
-                }),
+            events::emit(
+                app,
+                SearchCompleted {
+                    message_id: user_message_id.to_string(),
+                    conversation_id: conversation_id.to_string(),
+                    search_result_id: search_result_id.clone(),
+                    query: search_response.query.clone(),
+                    results_count: search_response.results.len(),
+                },
             );
 
             SearchProcessingResult {
                 urls: search_urls,
                 search_result_id,
+                previously_retrieved,
             }
         }
         Err(e) => {
             tracing::error!("❌ [search] Search failed: {}", e);
+            // The reply goes ahead without results, so say why instead of failing silently
+            if let Some(unavailable) = e.downcast_ref::<crate::web_fetch::ChromeUnavailable>() {
+                events::emit(
+                    app,
+                    ChatWarning {
+                        conversation_id: conversation_id.to_string(),
+                        warning: unavailable.to_string(),
+                        ocr_applied: false,
+                    },
+                );
+            }
             SearchProcessingResult {
                 urls: fallback_urls,
                 search_result_id,
+                previously_retrieved: Vec::new(),
+            }
+        }
+    }
+}
+
+/// The decision already stored for this message, when it calls for a search
+async fn stored_decision(
+    state: &AppState,
+    user_message_id: &str,
+) -> Option<crate::web_search::SearchDecisionResult> {
+    let decisions = state
+        .db
+        .get_search_decisions_by_message(user_message_id)
+        .await
+        .ok()?;
+    decisions
+        .into_iter()
+        .find(|d| d.search_needed && d.search_query.is_some())
+        .map(|d| crate::web_search::SearchDecisionResult {
+            reasoning: d.reasoning,
+            search_needed: d.search_needed,
+            search_query: d.search_query,
+            alternative_queries: d.alternative_queries,
+            confidence: d.confidence,
+        })
+}
+
+/// Ask the model whether to search and store its decision as a process step
+#[allow(clippy::too_many_arguments)]
+async fn decide_and_store(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    content: &str,
+    provider: &str,
+    model: &str,
+    api_key: Option<&str>,
+    base_url: Option<&str>,
+    api_style: Option<&str>,
+    user_message_id: &str,
+    conversation_id: &str,
+    cancel_token: CancellationToken,
+) -> crate::web_search::SearchDecisionResult {
+    // Emit event to show "deciding" state immediately
+    events::emit(
+        app,
+        SearchDecisionStarted {
+            message_id: user_message_id.to_string(),
+            conversation_id: conversation_id.to_string(),
+        },
+    );
+
+    // Use AI to decide if search is truly needed (on the "fast" role model when set and
+    // allowed by the conversation's provider policy)
+    let fast =
+        super::binding::resolve_allowed_role_binding(state, ModelRole::Fast, conversation_id).await;
+    let decision_result = match fast {
+        Some(ref fast) => {
+            crate::web_search::decide_search_needed(
+                content,
+                &fast.provider,
+                &fast.model,
+                fast.api_key.as_deref(),
+                fast.base_url.as_deref(),
+                fast.api_style.as_deref(),
+                cancel_token,
+            )
+            .await
+        }
+        None => {
+            crate::web_search::decide_search_needed(
+                content,
+                provider,
+                model,
+                api_key,
+                base_url,
+                api_style,
+                cancel_token,
+            )
+            .await
+        }
+    };
+    let decision = match decision_result {
+        Ok(d) => d,
+        Err(e) => {
+            tracing::warn!("⚠️ [search] Search decision failed, skipping search: {}", e);
+            crate::web_search::SearchDecisionResult {
+                reasoning: format!("Decision failed: {}", e),
+                search_needed: false,
+                search_query: None,
+                alternative_queries: Vec::new(),
+                confidence: None,
+            }
+        }
+    };
+
+    // Store the search decision in database (as a process step)
+    match state
+        .db
+        .create_search_decision(CreateSearchDecisionRequest {
+            message_id: user_message_id.to_string(),
+            reasoning: decision.reasoning.clone(),
+            search_needed: decision.search_needed,
+            search_query: decision.search_query.clone(),
+            alternative_queries: decision.alternative_queries.clone(),
+            confidence: decision.confidence,
+            search_result_id: None,
+            display_order: Some(0),
+        })
+        .await
+    {
+        Ok(search_decision) => {
+            tracing::info!(
+                "📝 [search] Created search decision: {}",
+                search_decision.id
+            );
+            // SearchDecision is now directly linked via message_id FK
+
+            // Emit search decision complete for UI
+            events::emit(
+                app,
+                SearchDecisionComplete {
+                    message_id: user_message_id.to_string(),
+                    conversation_id: conversation_id.to_string(),
+                },
+            );
+        }
+        Err(e) => {
+            tracing::error!("❌ [search] Failed to create search decision: {}", e);
+        }
+    }
+
+    decision
+}
+
+/// Pick up to [`FETCHED_RESULT_LIMIT`] result URLs to fetch, in rank order, passing over
+/// pages in `visited` (newest first) fetched less than [`REVISIT_AFTER_HOURS`] ago.
+/// Returns the URLs and the visited pages passed over.
+fn skip_visited(
+    urls: impl IntoIterator<Item = String>,
+    visited: &[FetchResult],
+    now: DateTime<Utc>,
+) -> (Vec<String>, Vec<FetchResult>) {
+    let mut to_fetch = Vec::new();
+    let mut skipped: Vec<FetchResult> = Vec::new();
+    for url in urls {
+        if to_fetch.len() == FETCHED_RESULT_LIMIT {
+            break;
+        }
+        let recent = visited
+            .iter()
+            .find(|fetch| same_page(&fetch.url, &url))
+            .filter(|fetch| {
+                DateTime::parse_from_rfc3339(&fetch.created_at).is_ok_and(|fetched_at| {
+                    now.signed_duration_since(fetched_at)
+                        < chrono::Duration::hours(REVISIT_AFTER_HOURS)
+                })
+            });
+        match recent {
+            Some(fetch) => {
+                if !skipped.iter().any(|s| s.id == fetch.id) {
+                    skipped.push(fetch.clone());
+                }
             }
+            None => to_fetch.push(url),
         }
     }
+    (to_fetch, skipped)
+}
+
+/// URLs that differ only in a fragment or trailing slash point at the same page
+fn same_page(a: &str, b: &str) -> bool {
+    fn normalize(url: &str) -> &str {
+        url.split('#').next().unwrap_or(url).trim_end_matches('/')
+    }
+    normalize(a).eq_ignore_ascii_case(normalize(b))
+}
+
+/// Tell the model which search results were skipped because earlier messages in the
+/// conversation already retrieved them
+pub(crate) fn append_previously_retrieved(content: &str, pages: &[FetchResult]) -> String {
+    if pages.is_empty() {
+        return content.to_string();
+    }
+    let mut content = content.to_string();
+    content.push_str(
+        "\n\n---\n**Note:** These search results were retrieved for earlier messages in this conversation, so their content is not repeated here:",
+    );
+    for page in pages {
+        match page.title.as_deref().filter(|t| !t.trim().is_empty()) {
+            Some(title) => content.push_str(&format!("\n- {} ({})", title.trim(), page.url)),
+            None => content.push_str(&format!("\n- {}", page.url)),
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fetch(id: &str, url: &str, created_at: &str) -> FetchResult {
+        FetchResult {
+            id: id.to_string(),
+            source_type: "search".to_string(),
+            source_id: None,
+            url: url.to_string(),
+            title: None,
+            description: None,
+            storage_path: String::new(),
+            content_type: "text/markdown".to_string(),
+            original_mime: None,
+            status: "success".to_string(),
+            error: None,
+            keywords: None,
+            headings: None,
+            original_size: None,
+            processed_size: None,
+            favicon_url: None,
+            favicon_path: None,
+            content_hash: None,
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_skip_visited() {
+        let now = DateTime::parse_from_rfc3339("2025-06-02T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let visited = vec![
+            fetch("recent", "https://a.example/page/", "2025-06-02T08:00:00Z"),
+            fetch("stale", "https://b.example/", "2025-05-30T08:00:00Z"),
+        ];
+        let urls: Vec<String> = [
+            "https://a.example/page#intro",
+            "https://b.example",
+            "https://c.example/1",
+            "https://c.example/2",
+            "https://c.example/3",
+            "https://c.example/4",
+            "https://c.example/5",
+        ]
+        .iter()
+        .map(|u| u.to_string())
+        .collect();
+
+        let (to_fetch, skipped) = skip_visited(urls, &visited, now);
+        // The skipped page makes room for one more result; the stale one is refreshed
+        assert_eq!(
+            to_fetch,
+            vec![
+                "https://b.example",
+                "https://c.example/1",
+                "https://c.example/2",
+                "https://c.example/3",
+                "https://c.example/4",
+            ]
+        );
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].id, "recent");
+    }
 }
@@ -0,0 +1,256 @@
+//! Process step explanations
+//!
+//! Explains a thinking step or tool call in plain language with the "fast" role model
+//! (falling back to the conversation's model), for people who don't read reasoning
+//! traces or tool arguments. The explanation is cached per step, so opening it again
+//! costs nothing.
+
+use super::super::AppState;
+use super::auxiliary::auxiliary_text;
+use super::binding;
+use super::title::get_conversation_provider_info;
+use crate::error::AppError;
+use crate::llm::{self, ChatMessage};
+use crate::models::{
+    CreateStepExplanationRequest, Message, ModelRole, StepExplanation, StepType, ToolCall,
+};
+use crate::prompts;
+use tauri::State;
+
+/// Longer steps are cut before being sent
+const MAX_STEP_CHARS: usize = 6000;
+/// Tool arguments and results each get a share of the step
+const MAX_TOOL_FIELD_CHARS: usize = 2500;
+const MAX_QUESTION_CHARS: usize = 1000;
+
+/// Explain a `thinking` or `tool_call` step. A cached explanation is returned unless
+/// `regenerate` is set.
+#[tauri::command]
+pub async fn explain_step(
+    state: State<'_, AppState>,
+    step_id: String,
+    step_type: StepType,
+    regenerate: Option<bool>,
+) -> Result<StepExplanation, AppError> {
+    if !regenerate.unwrap_or(false)
+        && let Some(explanation) = state.db.get_step_explanation(&step_id).await?
+    {
+        return Ok(explanation);
+    }
+
+    let (message_id, step) = match step_type {
+        StepType::Thinking => {
+            let step = state.db.get_thinking_step(&step_id).await.map_err(|_| {
+                AppError::not_found(format!("Thinking step not found: {}", step_id))
+            })?;
+            (step.message_id, clip(&step.content, MAX_STEP_CHARS))
+        }
+        StepType::ToolCall => {
+            let call =
+                state.db.get_tool_call(&step_id).await.map_err(|_| {
+                    AppError::not_found(format!("Tool call not found: {}", step_id))
+                })?;
+            (call.message_id.clone(), describe_tool_call(&call))
+        }
+        _ => {
+            return Err(AppError::validation(
+                "Only thinking steps and tool calls can be explained",
+            ));
+        }
+    };
+    if step.trim().is_empty() {
+        return Err(AppError::validation("The step has nothing to explain"));
+    }
+
+    let message = state
+        .db
+        .get_message(&message_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Message not found: {}", message_id)))?;
+    let conversation_id = message
+        .conversation_id
+        .clone()
+        .ok_or_else(|| AppError::validation("The message doesn't belong to a conversation"))?;
+    let messages = state
+        .db
+        .list_messages_by_conversation(&conversation_id)
+        .await?;
+    let question = asking_message(&messages, &message_id)
+        .map(|question| clip(&question.content, MAX_QUESTION_CHARS));
+
+    let (provider, model, api_key, base_url, api_style) =
+        match binding::resolve_role_binding(&state, ModelRole::Fast).await {
+            Some(fast) => (
+                fast.provider,
+                fast.model,
+                fast.api_key,
+                fast.base_url,
+                fast.api_style,
+            ),
+            None => get_conversation_provider_info(&state, &conversation_id).await?,
+        };
+    crate::commands::enforce_provider_policy(
+        &state,
+        &conversation_id,
+        &provider,
+        base_url.as_deref(),
+        None,
+        None,
+    )
+    .await?;
+
+    tracing::info!(
+        "💡 [step_explanation] Explaining {} {} with {}/{}",
+        step_type,
+        step_id,
+        provider,
+        model
+    );
+
+    let auxiliary = super::auxiliary::auxiliary_call(&state, &conversation_id).await;
+    let response = llm::call_provider(
+        &provider,
+        model.clone(),
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::STEP_EXPLANATION_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_step_explanation_user_prompt(question.as_deref(), &step),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary.token(),
+    )
+    .await?;
+
+    let explanation = auxiliary_text(&response, &model, &provider, "explanation")?;
+
+    state
+        .db
+        .save_step_explanation(CreateStepExplanationRequest {
+            step_id,
+            step_type,
+            message_id,
+            content: explanation,
+            model: Some(format!("{}/{}", provider, model)),
+        })
+        .await
+        .map_err(AppError::from)
+}
+
+/// The user message the step's reply answers
+fn asking_message<'a>(messages: &'a [Message], message_id: &str) -> Option<&'a Message> {
+    let position = messages.iter().position(|m| m.id == message_id)?;
+    messages[..position]
+        .iter()
+        .rev()
+        .find(|m| m.sender_type == "user" && !m.content.trim().is_empty())
+}
+
+fn describe_tool_call(call: &ToolCall) -> String {
+    let mut text = format!("Tool: {}\nStatus: {}", call.tool_name, call.status);
+    if let Some(input) = call.tool_input.as_deref().filter(|s| !s.trim().is_empty()) {
+        text.push_str("\nInput: ");
+        text.push_str(&clip(input, MAX_TOOL_FIELD_CHARS));
+    }
+    if let Some(output) = call.tool_output.as_deref().filter(|s| !s.trim().is_empty()) {
+        text.push_str("\nResult: ");
+        text.push_str(&clip(output, MAX_TOOL_FIELD_CHARS));
+    }
+    if let Some(error) = call.error.as_deref().filter(|s| !s.trim().is_empty()) {
+        text.push_str("\nError: ");
+        text.push_str(&clip(error, MAX_TOOL_FIELD_CHARS));
+    }
+    text
+}
+
+fn clip(text: &str, max_chars: usize) -> String {
+    if text.chars().count() > max_chars {
+        let short: String = text.chars().take(max_chars - 1).collect();
+        format!("{}…", short)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, sender_type: &str, content: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            conversation_id: Some("c".to_string()),
+            sender_type: sender_type.to_string(),
+            sender_id: None,
+            content: content.to_string(),
+            tokens: None,
+            input_tokens: None,
+            output_tokens: None,
+            cost: None,
+            follow_up_suggestions: None,
+            provider_type: None,
+            model_id: None,
+            model_params: None,
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_asking_message() {
+        let messages = vec![
+            message("1", "user", "First question"),
+            message("2", "assistant", "First answer"),
+            message("3", "user", "Second question"),
+            message("4", "assistant", "Second answer"),
+        ];
+        assert_eq!(
+            asking_message(&messages, "4").map(|m| m.id.as_str()),
+            Some("3")
+        );
+        assert_eq!(
+            asking_message(&messages, "2").map(|m| m.id.as_str()),
+            Some("1")
+        );
+        assert!(asking_message(&messages, "1").is_none());
+        assert!(asking_message(&messages, "missing").is_none());
+    }
+
+    #[test]
+    fn test_describe_tool_call() {
+        let call = ToolCall {
+            id: "t".to_string(),
+            message_id: "m".to_string(),
+            tool_name: "web_search".to_string(),
+            tool_input: Some(r#"{"query":"rust 2024 edition"}"#.to_string()),
+            tool_output: Some("x".repeat(MAX_TOOL_FIELD_CHARS + 10)),
+            status: "success".to_string(),
+            error: None,
+            duration_ms: Some(800),
+            display_order: 0,
+            created_at: String::new(),
+            completed_at: None,
+        };
+        let text = describe_tool_call(&call);
+        assert!(text.starts_with(
+            "Tool: web_search\nStatus: success\nInput: {\"query\":\"rust 2024 edition\"}\nResult: "
+        ));
+        assert!(text.ends_with('…'));
+        assert!(!text.contains("Error:"));
+    }
+}
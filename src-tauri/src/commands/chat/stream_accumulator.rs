@@ -0,0 +1,166 @@
+//! Accumulation of a streamed response
+//!
+//! The stream callback runs inside the provider stream and must not wait, so it only
+//! forwards chunks over a channel. A single task owns a [`StreamAccumulator`] and
+//! applies them in arrival order, so every chunk is kept and the display order of
+//! text, reasoning and tool calls matches the stream.
+
+use std::collections::HashMap;
+
+/// Tool call seen in the stream: (display_order, tool_name, tool_input, tool_output)
+pub(crate) type TrackedToolCall = (i32, String, String, Option<String>);
+
+#[derive(Debug, Default)]
+pub(crate) struct StreamAccumulator {
+    /// All text, for the final message
+    pub content: String,
+    /// All reasoning received through the provider's reasoning channel
+    pub reasoning: String,
+    /// Generated images as data URLs
+    pub images: Vec<String>,
+    /// Text blocks with display order; text after the last tool call is added by
+    /// [`finish`](Self::finish)
+    pub content_blocks: Vec<(i32, String)>,
+    /// Reasoning blocks with display order
+    pub reasoning_blocks: Vec<(i32, String)>,
+    pub tool_calls: HashMap<String, TrackedToolCall>,
+    /// Incremented on every transition between text, reasoning and tool calls
+    display_order: i32,
+    reasoning_started: bool,
+    current_content: String,
+    current_reasoning: String,
+    current_reasoning_order: Option<i32>,
+}
+
+impl StreamAccumulator {
+    pub fn push_text(&mut self, chunk: &str) {
+        self.content.push_str(chunk);
+        self.current_content.push_str(chunk);
+    }
+
+    /// Returns true for the first reasoning chunk of a round
+    pub fn push_reasoning(&mut self, chunk: &str) -> bool {
+        let started = !self.reasoning_started;
+        if started {
+            self.reasoning_started = true;
+            self.flush_content_block();
+            self.current_reasoning_order = Some(self.next_order());
+        }
+        self.reasoning.push_str(chunk);
+        self.current_reasoning.push_str(chunk);
+        started
+    }
+
+    /// Track a tool call, closing the text and reasoning blocks before it
+    pub fn start_tool_call(&mut self, id: String, tool_name: String, tool_input: String) {
+        self.flush_reasoning_block();
+        // Reasoning after the tool result starts a new block
+        self.reasoning_started = false;
+        self.flush_content_block();
+
+        let order = self.next_order();
+        self.tool_calls
+            .insert(id, (order, tool_name, tool_input, None));
+    }
+
+    /// Record a tool result. Returns the tracked call, or `None` for unknown ids.
+    pub fn complete_tool_call(&mut self, id: &str, output: String) -> Option<&TrackedToolCall> {
+        let call = self.tool_calls.get_mut(id)?;
+        call.3 = Some(output);
+        Some(call)
+    }
+
+    /// Keep a generated image. Returns false for duplicates: the API may re-send the
+    /// same image with slightly different encoding (e.g. OpenRouter Gemini streams
+    /// the image once, then echoes a re-encoded copy with the finish chunk). Exact
+    /// matches catch identical re-sends; a size difference under 2% catches
+    /// re-encoded copies.
+    pub fn push_image(&mut self, data_url: &str) -> bool {
+        let new_len = data_url.len();
+        let is_duplicate = self.images.iter().any(|existing| {
+            if existing == data_url {
+                return true;
+            }
+            let existing_len = existing.len();
+            new_len.abs_diff(existing_len) * 100 < existing_len.max(1) * 2
+        });
+        if is_duplicate {
+            tracing::info!(
+                "🖼️ [streaming] Skipping duplicate image ({} bytes, similar to existing)",
+                new_len
+            );
+            return false;
+        }
+        self.images.push(data_url.to_string());
+        true
+    }
+
+    /// Close the blocks still open when the stream ended
+    pub fn finish(&mut self) {
+        self.flush_reasoning_block();
+        self.flush_content_block();
+    }
+
+    fn next_order(&mut self) -> i32 {
+        let order = self.display_order;
+        self.display_order += 1;
+        order
+    }
+
+    fn flush_content_block(&mut self) {
+        if !self.current_content.trim().is_empty() {
+            let order = self.next_order();
+            let block = std::mem::take(&mut self.current_content);
+            self.content_blocks.push((order, block));
+        }
+    }
+
+    fn flush_reasoning_block(&mut self) {
+        if !self.current_reasoning.trim().is_empty() {
+            let block = std::mem::take(&mut self.current_reasoning);
+            if let Some(order) = self.current_reasoning_order {
+                self.reasoning_blocks.push((order, block));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_keep_stream_order() {
+        let mut acc = StreamAccumulator::default();
+        assert!(acc.push_reasoning("Need the weather."));
+        assert!(!acc.push_reasoning(" Use the tool."));
+        acc.start_tool_call("call-1".into(), "web_search".into(), "{}".into());
+        assert!(acc.complete_tool_call("call-1", "Sunny".into()).is_some());
+        assert!(acc.complete_tool_call("unknown", "?".into()).is_none());
+        acc.push_text("It is ");
+        acc.push_text("sunny.");
+        acc.finish();
+
+        assert_eq!(
+            acc.reasoning_blocks,
+            vec![(0, "Need the weather. Use the tool.".to_string())]
+        );
+        assert_eq!(acc.tool_calls["call-1"].0, 1);
+        assert_eq!(acc.tool_calls["call-1"].3.as_deref(), Some("Sunny"));
+        assert_eq!(acc.content_blocks, vec![(2, "It is sunny.".to_string())]);
+        assert_eq!(acc.content, "It is sunny.");
+    }
+
+    #[test]
+    fn test_push_image_skips_reencoded_copies() {
+        let mut acc = StreamAccumulator::default();
+        let image = format!("data:image/png;base64,{}", "A".repeat(1000));
+        let reencoded = format!("data:image/png;base64,{}", "B".repeat(1005));
+        let other = format!("data:image/png;base64,{}", "C".repeat(2000));
+        assert!(acc.push_image(&image));
+        assert!(!acc.push_image(&image));
+        assert!(!acc.push_image(&reencoded));
+        assert!(acc.push_image(&other));
+        assert_eq!(acc.images.len(), 2);
+    }
+}
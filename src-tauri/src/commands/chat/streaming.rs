@@ -1,6 +1,12 @@
 //! Agent-based streaming for LLM responses
 
 use super::super::AppState;
+use crate::error::{AppError, ErrorKind};
+use crate::events::{
+    self, ChatComplete, ChatError, ChatStreamImage, ChatWarning, McpAuthRequired, ReasoningStarted,
+    StreamEvent, StreamSink, ToolCallCompleted, ToolCallStarted,
+};
+use crate::jobs::{AnswerVerificationPayload, SummaryRefreshPayload};
 use crate::llm::agent_builder::{
     AgentConfig, build_assistant_message, build_assistant_message_with_tool_calls,
     build_tool_result_message, build_user_message, create_provider_agent, stream_chat_with_agent,
@@ -9,11 +15,12 @@ use crate::llm::tools::bash::{BashTool, TempFileList};
 use crate::llm::tools::{
     McpSchemaTool, McpServerCatalog, McpToolUseTool, SkillCatalogEntry, SkillTool,
 };
-use crate::llm::{ChatMessage, ChatResponse, StreamChunkType};
-use crate::mcp::sync_tool_definitions;
+use crate::llm::{ChatMessage, ChatResponse, StreamChunkType, ollama};
+use crate::mcp::{McpConnectionManager, sync_tool_definitions};
 use crate::models::{
-    CreateContentBlockRequest, CreateFileAttachmentRequest, CreateMessageRequest,
-    CreateThinkingStepRequest, CreateToolCallRequest, McpTransportType, ModelParameters,
+    CreateContentBlockRequest, CreateMessageRequest, CreateThinkingStepRequest,
+    CreateToolCallRequest, JOB_PRIORITY_LOW, JobKind, McpTransportType, Message, ModelParameters,
+    NewUsageEntry,
 };
 use crate::prompts;
 use rig::completion::Message as RigMessage;
@@ -23,11 +30,16 @@ use rmcp::service::Peer;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::Emitter;
 use tauri::Manager;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc};
 use tokio_util::sync::CancellationToken;
 
+use super::attachment_processing::{condense_large_code_files, store_generated_image};
+use super::chunk_coalescer::{ChunkCoalescer, ChunkKind};
+use super::follow_ups::{follow_ups_enabled, spawn_follow_up_suggestions};
+use super::ocr::{append_image_text, extract_image_text};
+use super::request_debug::RequestDebug;
+use super::stream_accumulator::StreamAccumulator;
 use super::title::auto_generate_title_if_needed;
 use crate::db::tools::{
     BUILTIN_BASH_ID, BUILTIN_EDIT_ID, BUILTIN_GLOB_ID, BUILTIN_GREP_ID, BUILTIN_KILL_SHELL_ID,
@@ -60,10 +72,13 @@ pub(crate) async fn handle_agent_streaming(
     cancel_token: CancellationToken,
     state_clone: AppState,
     app: tauri::AppHandle,
+    stream: StreamSink,
     conversation_id_clone: String,
     content: String,
     model_db_id: Option<String>,
     assistant_db_id: Option<String>,
+    citation_sources: Vec<Option<String>>,
+    replaced_message_ids: Vec<String>,
 ) {
     tracing::info!(
         "✅ [agent_streaming] Using {} provider with agent API",
@@ -71,7 +86,17 @@ pub(crate) async fn handle_agent_streaming(
     );
 
     // Build agent config from system prompt and model parameters
-    let mut config = AgentConfig::new().with_model_params(model_params);
+    let mut config = AgentConfig::new().with_model_params(model_params.clone());
+    if provider_type == "ollama"
+        && let Some(keep_alive) = crate::commands::configured_keep_alive(&state_clone).await
+    {
+        crate::llm::ollama::apply_keep_alive(&mut config.model_params, &keep_alive);
+    }
+    if provider_type == "openrouter"
+        && let Some(routing) = openrouter_routing(&state_clone, model_db_id.as_deref()).await
+    {
+        config = config.with_openrouter_routing(routing);
+    }
 
     // Start with the base system prompt
     let mut effective_system_prompt = system_prompt.clone().unwrap_or_default();
@@ -94,19 +119,17 @@ pub(crate) async fn handle_agent_streaming(
 
     // Load assistant's configured tools and skills
     if let Some(ref assistant_id) = assistant_db_id {
-        match state_clone.db.get_assistant_tool_ids(assistant_id).await {
-            Ok(ids) => {
-                if !ids.is_empty() {
-                    tracing::info!(
-                        "🛠️ [agent_streaming] Assistant has {} configured tool(s)",
-                        ids.len()
-                    );
-                    all_enabled_tool_ids.extend(ids);
+        let ids = load_assistant_tool_ids(&state_clone, assistant_id).await;
+        if !ids.is_empty() {
+            tracing::info!(
+                "🛠️ [agent_streaming] Assistant has {} configured tool(s)",
+                ids.len()
+            );
+            for id in ids {
+                if !all_enabled_tool_ids.contains(&id) {
+                    all_enabled_tool_ids.push(id);
                 }
             }
-            Err(e) => {
-                tracing::warn!("⚠️ [agent_streaming] Failed to load assistant tools: {}", e);
-            }
         }
 
         // Load assistant skills: collect catalog entries + auto-enable required tools
@@ -226,16 +249,14 @@ pub(crate) async fn handle_agent_streaming(
 
     // Append environment info block to system prompt
     {
-        use chrono::Local;
-
-        let today = Local::now().format("%a %b %d %Y").to_string();
+        let time_lines = crate::time_context::environment_lines(&state_clone.db).await;
         let platform = match std::env::consts::OS {
             "macos" => "darwin",
             other => other,
         };
 
         let mut env_block = format!(
-            "\n\nYou are running on model {provider_type}/{model_id}\n\n## Environment\n\n- Platform: {platform}\n- Today's date: {today}",
+            "\n\nYou are running on model {provider_type}/{model_id}\n\n## Environment\n\n- Platform: {platform}\n{time_lines}",
         );
 
         // Only include working directory info when explicitly set in conversation settings
@@ -253,12 +274,45 @@ pub(crate) async fn handle_agent_streaming(
         effective_system_prompt.push_str(&env_block);
     }
 
+    // Pin the reply language so injected context in another language doesn't switch it
+    if let Some(language) = conv_settings
+        .as_ref()
+        .and_then(|s| s.reply_language.as_deref())
+        .and_then(|setting| crate::language::resolve_reply_language(setting, &content))
+    {
+        tracing::info!("🌐 [agent_streaming] Replying in {}", language);
+        effective_system_prompt.push_str(&prompts::build_reply_language_section(&language));
+    }
+
     // Check model capabilities and strip unsupported features
     let capabilities = state_clone
         .capabilities_cache
         .resolve(&provider_type, &model_id)
         .await;
 
+    // Local Ollama models aren't in the capabilities catalog; ask the server instead
+    if provider_type == "ollama"
+        && capabilities.supports_vision.is_none()
+        && chat_messages.iter().any(|m| !m.images.is_empty())
+    {
+        let supports_vision = ollama::model_supports_vision(base_url.as_deref(), &model_id).await;
+        if supports_vision == Some(false) {
+            tracing::error!(
+                "🚫 [agent_streaming] Ollama model '{}' does not accept images",
+                model_id
+            );
+            let error = AppError::validation(format!(
+                "The Ollama model '{}' does not support images. Use a vision model such as llava or qwen2.5vl, or remove the image attachments.",
+                model_id
+            ))
+            .with_provider_type(&provider_type);
+            events::emit(&app, ChatError::new(&conversation_id_clone, &error));
+            let mut tasks = state_clone.generation_tasks.write().await;
+            tasks.remove(&conversation_id_clone);
+            return;
+        }
+    }
+
     if capabilities.supports_tool_use == Some(false) {
         if !all_enabled_tool_ids.is_empty() || !skill_entries.is_empty() {
             tracing::info!(
@@ -418,11 +472,13 @@ pub(crate) async fn handle_agent_streaming(
                     "🔐 [agent_streaming] MCP server {} failed auth, emitting mcp-auth-required",
                     server_id
                 );
-                let payload = serde_json::json!({
-                    "conversation_id": conversation_id_clone,
-                    "server_id": server_id,
-                });
-                let _ = app.emit("mcp-auth-required", payload);
+                events::emit(
+                    &app,
+                    McpAuthRequired {
+                        conversation_id: conversation_id_clone.clone(),
+                        server_id: server_id.clone(),
+                    },
+                );
             }
 
             if loaded.server_tools.iter().any(|(t, _)| !t.is_empty()) {
@@ -527,45 +583,64 @@ pub(crate) async fn handle_agent_streaming(
         Ok(a) => a,
         Err(e) => {
             tracing::error!("❌ [agent_streaming] Failed to create agent: {}", e);
-            let error_payload = serde_json::json!({
-                "conversation_id": conversation_id_clone,
-                "error": format!("Failed to create agent: {}", e),
-            });
-            let _ = app.emit("chat-error", error_payload);
+            let error = AppError::classify(format!("Failed to create agent: {}", e))
+                .with_provider_type(&provider_type);
+            events::emit(&app, ChatError::new(&conversation_id_clone, &error));
             let mut tasks = state_clone.generation_tasks.write().await;
             tasks.remove(&conversation_id_clone);
             return;
         }
     };
 
-    // Strip images if model does not support vision
+    // Replace images with their extracted text if model does not support vision
     let chat_messages = if capabilities.supports_vision == Some(false) {
         let image_count: usize = chat_messages.iter().map(|m| m.images.len()).sum();
+        let mut ocr_applied = false;
+        let mut stripped = Vec::with_capacity(chat_messages.len());
+        for mut m in chat_messages {
+            if !m.images.is_empty() {
+                let texts = extract_image_text(
+                    &state_clone,
+                    &conversation_id_clone,
+                    &m.images,
+                    &cancel_token,
+                )
+                .await;
+                ocr_applied |= texts.iter().any(|t| t.is_some());
+                m.content = append_image_text(&m.content, &texts);
+                m.images.clear();
+            }
+            stripped.push(m);
+        }
         if image_count > 0 {
             tracing::warn!(
-                "🚫 [agent_streaming] Model '{}' does not support vision; dropping {} image(s) from messages",
+                "🚫 [agent_streaming] Model '{}' does not support vision; replaced {} image(s) with extracted text (ocr: {})",
                 model_id,
-                image_count
+                image_count,
+                ocr_applied
             );
-            let _ = app.emit(
-                "chat-warning",
-                serde_json::json!({
-                    "conversation_id": conversation_id_clone,
-                    "warning": "model_no_vision",
-                }),
+            events::emit(
+                &app,
+                ChatWarning {
+                    conversation_id: conversation_id_clone.clone(),
+                    warning: "model_no_vision".to_string(),
+                    ocr_applied,
+                },
             );
         }
-        chat_messages
-            .into_iter()
-            .map(|mut m| {
-                m.images.clear();
-                m
-            })
-            .collect::<Vec<_>>()
+        stripped
     } else {
         chat_messages
     };
 
+    // Large source files get an outline plus relevant chunks instead of overflowing
+    let mut chat_messages = chat_messages;
+    condense_large_code_files(
+        &mut chat_messages,
+        capabilities.max_context_length,
+        &content,
+    );
+
     // Convert ChatMessages to rig's Message format for history
     let mut chat_history: Vec<RigMessage> = Vec::new();
     let mut current_prompt: Option<RigMessage> = None;
@@ -608,55 +683,49 @@ pub(crate) async fn handle_agent_streaming(
     // Use the last user message as prompt, or create one from content
     let prompt = current_prompt.unwrap_or_else(|| build_user_message(&content, &[], &[]));
 
-    // Track accumulated content for events
-    let accumulated_content = Arc::new(RwLock::new(String::new()));
-    let accumulated_reasoning = Arc::new(RwLock::new(String::new()));
-    let accumulated_images: Arc<RwLock<Vec<String>>> = Arc::new(RwLock::new(Vec::new()));
-    let reasoning_started = Arc::new(std::sync::atomic::AtomicBool::new(false));
-
-    // Track display order for proper interleaving of thinking, tool calls, and content
-    // display_order_counter increments whenever we transition between content/thinking/tool calls
-    let display_order_counter = Arc::new(std::sync::atomic::AtomicI32::new(0));
-
-    // Track current content block being accumulated (will be flushed before tool calls)
-    let current_content_block = Arc::new(RwLock::new(String::new()));
-
-    // Track content blocks with their display order: Vec<(display_order, content)>
-    let content_blocks: Arc<RwLock<Vec<(i32, String)>>> = Arc::new(RwLock::new(Vec::new()));
-
-    // Track reasoning/thinking blocks with display order: Vec<(display_order, content)>
-    let reasoning_blocks: Arc<RwLock<Vec<(i32, String)>>> = Arc::new(RwLock::new(Vec::new()));
-
-    // Track current reasoning block being accumulated
-    let current_reasoning_block = Arc::new(RwLock::new(String::new()));
-    let current_reasoning_order = Arc::new(std::sync::atomic::AtomicI32::new(-1));
-
-    // Track tool calls: HashMap<tool_call_id, (display_order, tool_name, tool_input, tool_output)>
-    let tool_calls_map: Arc<
-        RwLock<std::collections::HashMap<String, (i32, String, String, Option<String>)>>,
-    > = Arc::new(RwLock::new(std::collections::HashMap::new()));
-
-    let accumulated_content_for_callback = accumulated_content.clone();
-    let accumulated_reasoning_for_callback = accumulated_reasoning.clone();
-    let accumulated_images_for_callback = accumulated_images.clone();
-    let reasoning_started_for_callback = reasoning_started.clone();
-    let display_order_for_callback = display_order_counter.clone();
-    let current_content_for_callback = current_content_block.clone();
-    let content_blocks_for_callback = content_blocks.clone();
-    let reasoning_blocks_for_callback = reasoning_blocks.clone();
-    let current_reasoning_for_callback = current_reasoning_block.clone();
-    let current_reasoning_order_for_callback = current_reasoning_order.clone();
-    let tool_calls_for_callback = tool_calls_map.clone();
-    let conversation_id_for_stream = conversation_id_clone.clone();
-    let app_for_stream = app.clone();
+    // Keep a redacted copy for "view raw request"
+    let mut request_tools = all_enabled_tool_ids.clone();
+    request_tools.extend(mcp_tool_name_to_server_id.keys().cloned());
+    state_clone.request_log.record(RequestDebug::new(
+        &conversation_id_clone,
+        &provider_type,
+        &model_id,
+        base_url.as_deref(),
+        api_style.as_deref(),
+        config.system_prompt.as_deref(),
+        &model_params,
+        request_tools,
+        &chat_history,
+        &prompt,
+    ));
+
+    let coalescer = ChunkCoalescer::new(stream, conversation_id_clone.clone());
+    let stop_coalescer_timer = CancellationToken::new();
+    coalescer.spawn_timer(stop_coalescer_timer.clone());
+
+    // A single task applies chunks in order; the stream callback only forwards them
+    let (chunk_tx, chunk_rx) = mpsc::unbounded_channel();
+    let accumulator_task = tokio::spawn(accumulate_stream(
+        chunk_rx,
+        StreamEventContext {
+            app: app.clone(),
+            conversation_id: conversation_id_clone.clone(),
+            coalescer: coalescer.clone(),
+            mcp_tool_map: mcp_tool_name_to_server_id.clone(),
+            mcp_server_name_map: mcp_tool_name_to_server_name.clone(),
+            mcp_manager: state_clone.mcp_manager.clone(),
+        },
+    ));
     let cancel_token_for_callback = cancel_token.clone();
-    let mcp_tool_map_for_callback = mcp_tool_name_to_server_id.clone();
-    let mcp_server_name_map_for_callback = mcp_tool_name_to_server_name.clone();
-    let mcp_manager_for_callback = state_clone.mcp_manager.clone();
+
+    // Private conversations are kept out of the database entirely
+    let is_private = state_clone
+        .private_conversations
+        .contains(&conversation_id_clone);
 
     // Auto-generate title for new conversations early (only needs user message).
     // Fire-and-forget: runs concurrently with the LLM streaming below.
-    {
+    if !is_private {
         let state_for_title = state_clone.clone();
         let app_for_title = app.clone();
         let conversation_id_for_title = conversation_id_clone.clone();
@@ -683,6 +752,9 @@ pub(crate) async fn handle_agent_streaming(
         });
     }
 
+    // Reasoning delimiters this model family writes inline
+    let thinking_formats = crate::thinking_parser::formats_for_model(&model_id);
+
     // Stream using the agent
     let response = stream_chat_with_agent(
         agent,
@@ -695,275 +767,39 @@ pub(crate) async fn handle_agent_streaming(
                 tracing::info!("🛑 [agent_streaming] Generation cancelled, stopping stream");
                 return false;
             }
-
-            match chunk_type {
-                StreamChunkType::Text => {
-                    // Accumulate text content (for final message)
-                    if let Ok(mut content) = accumulated_content_for_callback.try_write() {
-                        content.push_str(&chunk);
-                    }
-
-                    // Also accumulate into current content block for proper ordering
-                    if let Ok(mut current_block) = current_content_for_callback.try_write() {
-                        current_block.push_str(&chunk);
-                    }
-
-                    let payload = serde_json::json!({
-                        "conversation_id": conversation_id_for_stream,
-                        "content": chunk,
-                    });
-                    let _ = app_for_stream.emit("chat-stream", payload);
-                }
-                StreamChunkType::Reasoning => {
-                    // Emit reasoning-started event on first reasoning chunk
-                    if !reasoning_started_for_callback
-                        .swap(true, std::sync::atomic::Ordering::SeqCst)
-                    {
-                        // First reasoning chunk - flush any pending content block
-                        if let Ok(mut current_block) = current_content_for_callback.try_write()
-                            && !current_block.trim().is_empty()
-                        {
-                            let order = display_order_for_callback
-                                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                            if let Ok(mut blocks) = content_blocks_for_callback.try_write() {
-                                blocks.push((order, current_block.clone()));
-                            }
-                            current_block.clear();
-                        }
-
-                        // Set current reasoning order
-                        let order = display_order_for_callback
-                            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                        current_reasoning_order_for_callback
-                            .store(order, std::sync::atomic::Ordering::SeqCst);
-
-                        let started_payload = serde_json::json!({
-                            "conversation_id": conversation_id_for_stream,
-                        });
-                        let _ = app_for_stream.emit("reasoning-started", started_payload);
-                    }
-
-                    // Accumulate reasoning content
-                    if let Ok(mut reasoning) = accumulated_reasoning_for_callback.try_write() {
-                        reasoning.push_str(&chunk);
-                    }
-
-                    // Also accumulate into current reasoning block
-                    if let Ok(mut current_reasoning) = current_reasoning_for_callback.try_write() {
-                        current_reasoning.push_str(&chunk);
-                    }
-
-                    let payload = serde_json::json!({
-                        "conversation_id": conversation_id_for_stream,
-                        "content": chunk,
-                    });
-                    let _ = app_for_stream.emit("chat-stream-reasoning", payload);
-                }
-                StreamChunkType::ToolCall(tool_info) => {
-                    // Flush any pending reasoning block before tool call
-                    if let Ok(mut current_reasoning) = current_reasoning_for_callback.try_write()
-                        && !current_reasoning.trim().is_empty()
-                    {
-                        let order = current_reasoning_order_for_callback
-                            .load(std::sync::atomic::Ordering::SeqCst);
-                        if order >= 0
-                            && let Ok(mut blocks) = reasoning_blocks_for_callback.try_write()
-                        {
-                            blocks.push((order, current_reasoning.clone()));
-                        }
-                        current_reasoning.clear();
-                    }
-                    // Reset reasoning started for next round
-                    reasoning_started_for_callback
-                        .store(false, std::sync::atomic::Ordering::SeqCst);
-
-                    // Flush any pending content block before tool call
-                    if let Ok(mut current_block) = current_content_for_callback.try_write()
-                        && !current_block.trim().is_empty()
-                    {
-                        let order = display_order_for_callback
-                            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                        if let Ok(mut blocks) = content_blocks_for_callback.try_write() {
-                            blocks.push((order, current_block.clone()));
-                        }
-                        current_block.clear();
-                    }
-
-                    // Get display order for this tool call
-                    let tool_order = display_order_for_callback
-                        .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-
-                    // For mcp meta-tool, extract server, real MCP tool name, and inner arguments
-                    let (actual_tool_name, display_name, display_input) =
-                        if tool_info.tool_name == "mcp_tool_use" {
-                            if let Ok(parsed) =
-                                serde_json::from_str::<serde_json::Value>(&tool_info.tool_input)
-                            {
-                                let server_name = parsed["server"]
-                                    .as_str()
-                                    .unwrap_or("unknown")
-                                    .to_string();
-                                let real_name = parsed["tool"]
-                                    .as_str()
-                                    .unwrap_or("mcp_tool_use")
-                                    .to_string();
-                                let inner_args = parsed
-                                    .get("arguments")
-                                    .map(|a| a.to_string())
-                                    .unwrap_or_else(|| "{}".to_string());
-                                // Store composite key for auth lookup; build display name directly
-                                let composite_key = format!("{}/{}", server_name, real_name);
-                                let display = format!(
-                                    "mcp__{}__{}",
-                                    sanitize_server_name(&server_name),
-                                    real_name
-                                );
-                                (composite_key, display, inner_args)
-                            } else {
-                                let fallback_name = tool_info.tool_name.clone();
-                                let display = mcp_display_name(
-                                    &fallback_name,
-                                    &mcp_server_name_map_for_callback,
-                                );
-                                (
-                                    fallback_name,
-                                    display,
-                                    tool_info.tool_input.clone(),
-                                )
-                            }
-                        } else {
-                            let name = tool_info.tool_name.clone();
-                            let display = mcp_display_name(
-                                &name,
-                                &mcp_server_name_map_for_callback,
-                            );
-                            (name, display, tool_info.tool_input.clone())
-                        };
-
-                    // Store tool call in tracking map (actual MCP tool name for auth lookup)
-                    if let Ok(mut tool_calls) = tool_calls_for_callback.try_write() {
-                        tool_calls.insert(
-                            tool_info.id.clone(),
-                            (
-                                tool_order,
-                                actual_tool_name,
-                                display_input.clone(),
-                                None,
-                            ),
-                        );
-                    }
-
-                    // Emit tool call event to frontend with display name
-                    let payload = serde_json::json!({
-                        "conversation_id": conversation_id_for_stream,
-                        "tool_call_id": tool_info.id,
-                        "tool_name": display_name,
-                        "tool_input": display_input,
-                    });
-                    let _ = app_for_stream.emit("tool-call-started", payload);
-                }
-                StreamChunkType::ToolResult(result_info) => {
-                    // Update tool call with result
-                    if let Ok(mut tool_calls) = tool_calls_for_callback.try_write()
-                        && let Some((_, name, input, output)) = tool_calls.get_mut(&result_info.id)
-                    {
-                        *output = Some(result_info.tool_output.clone());
-
-                        // Detect 401 auth errors from MCP tool calls (uses original name)
-                        if is_auth_error(&result_info.tool_output)
-                            && let Some(server_id) = mcp_tool_map_for_callback.get(name.as_str()) {
-                                tracing::warn!(
-                                    "🔐 [agent_streaming] MCP tool '{}' returned auth error, server: {}",
-                                    name, server_id
-                                );
-                                let server_id = server_id.clone();
-                                let app_handle = app_for_stream.clone();
-                                let conv_id = conversation_id_for_stream.clone();
-                                let manager = mcp_manager_for_callback.clone();
-                                tokio::spawn(async move {
-                                    manager.disconnect(&server_id).await;
-                                    let payload = serde_json::json!({
-                                        "conversation_id": conv_id,
-                                        "server_id": server_id,
-                                    });
-                                    let _ = app_handle.emit("mcp-auth-required", payload);
-                                });
-                            }
-
-                        // Build display name for frontend (name may be composite "server/tool" in lazy-load)
-                        let display_name =
-                            mcp_display_name_from_stored(name, &mcp_server_name_map_for_callback);
-
-                        // Emit tool result event to frontend
-                        let payload = serde_json::json!({
-                            "conversation_id": conversation_id_for_stream,
-                            "tool_call_id": result_info.id,
-                            "tool_name": display_name,
-                            "tool_input": input.clone(),
-                            "tool_output": result_info.tool_output,
-                        });
-                        let _ = app_for_stream.emit("tool-call-completed", payload);
-                    }
-                }
-                StreamChunkType::Image(data_url) => {
-                    let is_duplicate = if let Ok(mut images) =
-                        accumulated_images_for_callback.try_write()
-                    {
-                        let new_len = data_url.len();
-                        // The API may re-send the same image with slightly
-                        // different encoding (e.g. OpenRouter Gemini streams
-                        // the image once, then echoes a re-encoded copy with
-                        // the finish chunk).  Exact string match catches
-                        // identical re-sends; size-based comparison catches
-                        // re-encoded duplicates (typically <1% size diff).
-                        if images.iter().any(|existing: &String| {
-                            if *existing == data_url {
-                                return true;
-                            }
-                            let existing_len = existing.len();
-                            let diff = new_len.abs_diff(existing_len);
-                            diff * 100 < existing_len.max(1) * 2
-                        }) {
-                            tracing::info!(
-                                "🖼️ [streaming] Skipping duplicate image ({} bytes, similar to existing)",
-                                new_len
-                            );
-                            true
-                        } else {
-                            images.push(data_url.clone());
-                            false
-                        }
-                    } else {
-                        false
-                    };
-
-                    if !is_duplicate {
-                        let payload = serde_json::json!({
-                            "conversation_id": conversation_id_for_stream,
-                            "image_url": data_url,
-                        });
-                        let _ = app_for_stream.emit("chat-stream-image", payload);
-                    }
-                }
-            }
-
-            true // Continue streaming
+            chunk_tx.send((chunk, chunk_type)).is_ok()
         },
         &provider_type,
+        thinking_formats,
     )
     .await;
 
+    // The callback and its sender are dropped by now, so the task ends once drained
+    let mut accumulated = match accumulator_task.await {
+        Ok(accumulated) => accumulated,
+        Err(e) => {
+            tracing::error!("❌ [agent_streaming] Stream accumulator failed: {}", e);
+            StreamAccumulator::default()
+        }
+    };
+    accumulated.finish();
+
+    // Send chunks still buffered before completion/error events go out
+    stop_coalescer_timer.cancel();
+    coalescer.flush();
+
     // Handle the response: on cancellation build synthetic response so we can save accumulated data
     let (response, was_stream_error) = match response {
         Ok(r) => (r, false),
         Err(e) => {
             if cancel_token.is_cancelled() {
                 tracing::info!("🛑 [agent_streaming] Generation cancelled (stream returned error)");
-                let accumulated = accumulated_content.read().await.clone();
-                let accumulated_reason = accumulated_reasoning.read().await.clone();
-                let parsed = crate::thinking_parser::parse_thinking_content(&accumulated);
-                let thinking = if !accumulated_reason.is_empty() {
-                    Some(accumulated_reason)
+                let parsed = crate::thinking_parser::parse_thinking_content_with(
+                    &accumulated.content,
+                    thinking_formats,
+                );
+                let thinking = if !accumulated.reasoning.is_empty() {
+                    Some(accumulated.reasoning.clone())
                 } else {
                     parsed.thinking_content
                 };
@@ -972,16 +808,15 @@ pub(crate) async fn handle_agent_streaming(
                         content: parsed.content,
                         thinking_content: thinking,
                         tokens: None,
+                        input_tokens: None,
+                        output_tokens: None,
                     },
                     true,
                 )
             } else {
                 tracing::error!("❌ [agent_streaming] Stream error: {}", e);
-                let error_payload = serde_json::json!({
-                    "conversation_id": conversation_id_clone,
-                    "error": e.to_string(),
-                });
-                let _ = app.emit("chat-error", error_payload);
+                let error = AppError::from(e).with_provider_type(&provider_type);
+                events::emit(&app, ChatError::new(&conversation_id_clone, &error));
                 let mut tasks = state_clone.generation_tasks.write().await;
                 tasks.remove(&conversation_id_clone);
                 return;
@@ -1006,16 +841,14 @@ pub(crate) async fn handle_agent_streaming(
     }
 
     // Check if we have any data worth saving
-    let has_tool_calls = !tool_calls_map.read().await.is_empty();
-    let has_reasoning_blocks = !reasoning_blocks.read().await.is_empty()
-        || !current_reasoning_block.read().await.trim().is_empty();
-    let has_content_blocks = !content_blocks.read().await.is_empty()
-        || !current_content_block.read().await.trim().is_empty();
+    let has_tool_calls = !accumulated.tool_calls.is_empty();
+    let has_reasoning_blocks = !accumulated.reasoning_blocks.is_empty();
+    let has_content_blocks = !accumulated.content_blocks.is_empty();
     let has_thinking = response
         .thinking_content
         .as_ref()
         .is_some_and(|t| !t.is_empty());
-    let has_images = !accumulated_images.read().await.is_empty();
+    let has_images = !accumulated.images.is_empty();
     let has_any_data = !final_content.trim().is_empty()
         || has_tool_calls
         || has_reasoning_blocks
@@ -1026,26 +859,27 @@ pub(crate) async fn handle_agent_streaming(
     if !has_any_data {
         if was_cancelled {
             tracing::info!("⚠️ [agent_streaming] Cancelled with no data to save");
-            let payload = serde_json::json!({
-                "conversation_id": conversation_id_clone,
-                "message": null,
-                "cancelled": true,
-            });
-            let _ = app.emit("chat-complete", payload);
+            events::emit(
+                &app,
+                ChatComplete {
+                    conversation_id: conversation_id_clone.clone(),
+                    message: None,
+                    user_message: None,
+                    cancelled: true,
+                },
+            );
         } else {
             tracing::info!("⚠️ [agent_streaming] Skipping save of empty response");
-            let error_payload = serde_json::json!({
-                "conversation_id": conversation_id_clone,
-                "error": "Model returned empty response",
-            });
-            let _ = app.emit("chat-error", error_payload);
+            let error = AppError::new(ErrorKind::Provider, "Model returned empty response")
+                .with_provider_type(&provider_type);
+            events::emit(&app, ChatError::new(&conversation_id_clone, &error));
         }
         let mut tasks = state_clone.generation_tasks.write().await;
         tasks.remove(&conversation_id_clone);
         return;
     }
 
-    let images_snapshot = accumulated_images.read().await.clone();
+    let images_snapshot = accumulated.images.clone();
     let save_content = if final_content.trim().is_empty() && images_snapshot.is_empty() {
         " ".to_string()
     } else {
@@ -1061,8 +895,38 @@ pub(crate) async fn handle_agent_streaming(
         ("assistant".to_string(), None)
     };
 
+    if is_private {
+        let mut message = super::private::new_message(
+            &conversation_id_clone,
+            &sender_type,
+            sender_id,
+            save_content,
+        );
+        message.tokens = response.tokens;
+        message.input_tokens = response.input_tokens;
+        message.output_tokens = response.output_tokens;
+        message.provider_type = Some(provider_type.clone());
+        message.model_id = Some(model_id.clone());
+        // Dropped if the conversation was closed while the reply streamed
+        let kept = state_clone
+            .private_conversations
+            .push_message(message.clone());
+        events::emit(
+            &app,
+            ChatComplete {
+                conversation_id: conversation_id_clone.clone(),
+                message: kept.then_some(message),
+                user_message: None,
+                cancelled: was_cancelled,
+            },
+        );
+        let mut tasks = state_clone.generation_tasks.write().await;
+        tasks.remove(&conversation_id_clone);
+        return;
+    }
+
     // Save assistant message
-    let assistant_message = match state_clone
+    let mut assistant_message = match state_clone
         .db
         .create_message(CreateMessageRequest {
             conversation_id: Some(conversation_id_clone.clone()),
@@ -1076,16 +940,56 @@ pub(crate) async fn handle_agent_streaming(
         Ok(msg) => msg,
         Err(e) => {
             tracing::error!("Failed to save assistant message: {}", e);
-            let error_payload = serde_json::json!({
-                "conversation_id": conversation_id_clone,
-                "error": format!("Failed to save message: {}", e),
-            });
-            let _ = app.emit("chat-error", error_payload);
+            let error = AppError::new(
+                ErrorKind::Internal,
+                format!("Failed to save message: {}", e),
+            );
+            events::emit(&app, ChatError::new(&conversation_id_clone, &error));
             let mut tasks = state_clone.generation_tasks.write().await;
             tasks.remove(&conversation_id_clone);
             return;
         }
     };
+    // The replaced replies were kept until now so a failed or stopped regeneration
+    // leaves them in place
+    if !replaced_message_ids.is_empty()
+        && let Err(e) = state_clone
+            .db
+            .supersede_messages(&assistant_message.id, &replaced_message_ids)
+            .await
+    {
+        tracing::warn!(
+            "⚠️ [agent_streaming] Failed to replace the previous replies: {}",
+            e
+        );
+    }
+
+    record_message_usage(
+        &app,
+        &state_clone,
+        &mut assistant_message,
+        &model_db_id,
+        &assistant_db_id,
+        &provider_type,
+        &model_id,
+        &response,
+    )
+    .await;
+    record_generation_info(
+        &state_clone,
+        &mut assistant_message,
+        &provider_type,
+        &model_id,
+        &model_params,
+    )
+    .await;
+    super::citations::store_citations(
+        &state_clone,
+        &assistant_message.id,
+        &final_content,
+        &citation_sources,
+    )
+    .await;
 
     // Save generated images as file attachments linked to the assistant message
     if !images_snapshot.is_empty() {
@@ -1119,88 +1023,20 @@ pub(crate) async fn handle_agent_streaming(
                 }
             };
 
-            let content_hash = crate::storage::hash_bytes(&bytes);
-            let ext = crate::storage::get_extension_for_content_type(&mime_type);
-            let file_name = format!("generated-image-{}.{}", i + 1, ext);
-
-            // Check for deduplication
-            let storage_path =
-                if let Ok(Some(existing)) = state_clone.db.find_file_by_hash(&content_hash).await {
-                    existing.storage_path.clone()
-                } else {
-                    let path = crate::storage::generate_file_storage_path(&content_hash, ext);
-                    if let Err(e) = crate::storage::write_binary(&app, &path, &bytes) {
-                        tracing::error!("Failed to save generated image {}: {}", i + 1, e);
-                        continue;
-                    }
-                    path
-                };
-
-            match state_clone
-                .db
-                .create_file_attachment(CreateFileAttachmentRequest {
-                    file_name: file_name.clone(),
-                    file_size: bytes.len() as i64,
-                    mime_type: mime_type.clone(),
-                    storage_path: storage_path.clone(),
-                    content_hash,
-                })
-                .await
-            {
-                Ok(file_attachment) => {
-                    if let Err(e) = state_clone
-                        .db
-                        .link_message_attachment(
-                            &assistant_message.id,
-                            &file_attachment.id,
-                            Some(i as i32),
-                        )
-                        .await
-                    {
-                        tracing::error!("Failed to link generated image to message: {}", e);
-                    } else {
-                        tracing::info!(
-                            "🖼️ [streaming] Saved generated image attachment: {} -> {}",
-                            file_name,
-                            file_attachment.id
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "Failed to create file record for generated image {}: {}",
-                        i + 1,
-                        e
-                    );
-                }
-            }
-        }
-    }
-
-    // Flush any remaining reasoning block
-    {
-        let current_reasoning = current_reasoning_block.read().await;
-        if !current_reasoning.trim().is_empty() {
-            let order = current_reasoning_order.load(std::sync::atomic::Ordering::SeqCst);
-            if order >= 0 {
-                let mut blocks = reasoning_blocks.write().await;
-                blocks.push((order, current_reasoning.clone()));
-            }
-        }
-    }
-
-    // Flush any remaining content block
-    {
-        let current_block = current_content_block.read().await;
-        if !current_block.trim().is_empty() {
-            let order = display_order_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-            let mut blocks = content_blocks.write().await;
-            blocks.push((order, current_block.clone()));
+            store_generated_image(
+                &state_clone,
+                &app,
+                &assistant_message.id,
+                i,
+                &mime_type,
+                &bytes,
+            )
+            .await;
         }
     }
 
     // Save reasoning/thinking blocks with proper display order
-    let reasoning_data = reasoning_blocks.read().await;
+    let reasoning_data = &accumulated.reasoning_blocks;
     if !reasoning_data.is_empty() {
         tracing::info!(
             "💾 [agent_streaming] Saving {} reasoning block(s) to database",
@@ -1233,10 +1069,9 @@ pub(crate) async fn handle_agent_streaming(
             }
         }
     }
-    drop(reasoning_data);
 
     // Save tool calls to database with proper display order
-    let tool_calls_data = tool_calls_map.read().await;
+    let tool_calls_data = &accumulated.tool_calls;
     if !tool_calls_data.is_empty() {
         tracing::info!(
             "💾 [agent_streaming] Saving {} tool call(s) to database",
@@ -1295,13 +1130,11 @@ pub(crate) async fn handle_agent_streaming(
             }
         }
     }
-    drop(tool_calls_data);
 
     // Save content blocks to database with proper display order
     // Also extract <think> tag thinking from content blocks and save as separate thinking_steps
     // Only save if we have tool calls (otherwise content is just the message content)
-    let content_data = content_blocks.read().await;
-    let has_tool_calls = !tool_calls_map.read().await.is_empty();
+    let content_data = &accumulated.content_blocks;
     let mut xml_thinking_saved = false;
     if has_tool_calls && !content_data.is_empty() {
         tracing::info!(
@@ -1315,7 +1148,8 @@ pub(crate) async fn handle_agent_streaming(
             }
 
             // Parse content block for <think> tags
-            let parsed = crate::thinking_parser::parse_thinking_content(content);
+            let parsed =
+                crate::thinking_parser::parse_thinking_content_with(content, thinking_formats);
 
             // Save extracted thinking as a separate thinking_step
             if let Some(ref thinking) = parsed.thinking_content
@@ -1372,11 +1206,10 @@ pub(crate) async fn handle_agent_streaming(
             }
         }
     }
-    drop(content_data);
 
     // Fallback: if no API reasoning blocks and no XML thinking was extracted
     // from content blocks, save the combined thinking content (no-tool-call case)
-    if reasoning_blocks.read().await.is_empty()
+    if accumulated.reasoning_blocks.is_empty()
         && !xml_thinking_saved
         && let Some(thinking_content) = response.thinking_content
         && !thinking_content.is_empty()
@@ -1406,17 +1239,395 @@ pub(crate) async fn handle_agent_streaming(
     );
 
     // Notify frontend that streaming is complete
-    let completion_payload = serde_json::json!({
-        "conversation_id": conversation_id_clone,
-        "message": assistant_message,
-    });
-    let _ = app.emit("chat-complete", completion_payload);
+    let assistant_message_id = assistant_message.id.clone();
+    events::emit(
+        &app,
+        ChatComplete {
+            conversation_id: conversation_id_clone.clone(),
+            message: Some(assistant_message),
+            user_message: None,
+            cancelled: was_cancelled,
+        },
+    );
 
     // Remove task from tracking
     {
         let mut tasks = state_clone.generation_tasks.write().await;
         tasks.remove(&conversation_id_clone);
     }
+
+    // Optional quick-reply suggestions (skipped for cancelled or empty responses)
+    if !was_cancelled && !final_content.trim().is_empty() && follow_ups_enabled(&state_clone).await
+    {
+        spawn_follow_up_suggestions(
+            state_clone.clone(),
+            app.clone(),
+            conversation_id_clone.clone(),
+            assistant_message_id.clone(),
+            content.clone(),
+            final_content.clone(),
+            provider_type.clone(),
+            model_id.clone(),
+            api_key.clone(),
+            base_url.clone(),
+            api_style.clone(),
+        );
+    }
+
+    // Refresh the conversation summary in the background when enough messages accumulated
+    if let Err(e) = state_clone
+        .job_queue
+        .enqueue(
+            JobKind::SummaryRefresh,
+            &SummaryRefreshPayload {
+                conversation_id: conversation_id_clone.clone(),
+            },
+            JOB_PRIORITY_LOW,
+        )
+        .await
+    {
+        tracing::warn!(
+            "⚠️ [agent_streaming] Failed to queue summary refresh: {}",
+            e
+        );
+    }
+
+    // Optional check of the reply against the pages it was written from
+    let fetch_result_ids: Vec<String> = citation_sources.into_iter().flatten().collect();
+    if !was_cancelled
+        && !fetch_result_ids.is_empty()
+        && super::verification::verification_enabled(&state_clone).await
+        && let Err(e) = state_clone
+            .job_queue
+            .enqueue(
+                JobKind::AnswerVerification,
+                &AnswerVerificationPayload {
+                    conversation_id: conversation_id_clone.clone(),
+                    message_id: assistant_message_id,
+                    fetch_result_ids,
+                },
+                JOB_PRIORITY_LOW,
+            )
+            .await
+    {
+        tracing::warn!(
+            "⚠️ [agent_streaming] Failed to queue answer verification: {}",
+            e
+        );
+    }
+}
+
+/// What the accumulator task needs to forward stream events to the frontend
+struct StreamEventContext {
+    app: tauri::AppHandle,
+    conversation_id: String,
+    coalescer: ChunkCoalescer,
+    mcp_tool_map: Arc<HashMap<String, String>>,
+    mcp_server_name_map: Arc<HashMap<String, String>>,
+    mcp_manager: Arc<McpConnectionManager>,
+}
+
+/// Apply streamed chunks in arrival order and forward them to the frontend. Returns
+/// the accumulated response once the stream callback is dropped.
+async fn accumulate_stream(
+    mut chunks: mpsc::UnboundedReceiver<(String, StreamChunkType)>,
+    ctx: StreamEventContext,
+) -> StreamAccumulator {
+    let mut acc = StreamAccumulator::default();
+
+    while let Some((chunk, chunk_type)) = chunks.recv().await {
+        match chunk_type {
+            StreamChunkType::Text => {
+                acc.push_text(&chunk);
+                ctx.coalescer.push(ChunkKind::Text, &chunk);
+            }
+            StreamChunkType::Reasoning => {
+                if acc.push_reasoning(&chunk) {
+                    ctx.coalescer
+                        .send_event(StreamEvent::ReasoningStarted(ReasoningStarted {
+                            conversation_id: ctx.conversation_id.clone(),
+                        }));
+                }
+                ctx.coalescer.push(ChunkKind::Reasoning, &chunk);
+            }
+            StreamChunkType::ToolCall(tool_info) => {
+                // For mcp meta-tool, extract server, real MCP tool name, and inner arguments
+                let (actual_tool_name, display_name, display_input) = if tool_info.tool_name
+                    == "mcp_tool_use"
+                {
+                    if let Ok(parsed) =
+                        serde_json::from_str::<serde_json::Value>(&tool_info.tool_input)
+                    {
+                        let server_name =
+                            parsed["server"].as_str().unwrap_or("unknown").to_string();
+                        let real_name = parsed["tool"]
+                            .as_str()
+                            .unwrap_or("mcp_tool_use")
+                            .to_string();
+                        let inner_args = parsed
+                            .get("arguments")
+                            .map(|a| a.to_string())
+                            .unwrap_or_else(|| "{}".to_string());
+                        // Store composite key for auth lookup; build display name directly
+                        let composite_key = format!("{}/{}", server_name, real_name);
+                        let display =
+                            format!("mcp__{}__{}", sanitize_server_name(&server_name), real_name);
+                        (composite_key, display, inner_args)
+                    } else {
+                        let fallback_name = tool_info.tool_name.clone();
+                        let display = mcp_display_name(&fallback_name, &ctx.mcp_server_name_map);
+                        (fallback_name, display, tool_info.tool_input.clone())
+                    }
+                } else {
+                    let name = tool_info.tool_name.clone();
+                    let display = mcp_display_name(&name, &ctx.mcp_server_name_map);
+                    (name, display, tool_info.tool_input.clone())
+                };
+
+                // Track the actual MCP tool name for auth lookup
+                acc.start_tool_call(
+                    tool_info.id.clone(),
+                    actual_tool_name,
+                    display_input.clone(),
+                );
+
+                // Emit tool call event to frontend with display name
+                ctx.coalescer
+                    .send_event(StreamEvent::ToolCallStarted(ToolCallStarted {
+                        conversation_id: ctx.conversation_id.clone(),
+                        tool_call_id: tool_info.id,
+                        tool_name: display_name,
+                        tool_input: display_input,
+                    }));
+            }
+            StreamChunkType::ToolResult(result_info) => {
+                let Some((_, name, input, _)) =
+                    acc.complete_tool_call(&result_info.id, result_info.tool_output.clone())
+                else {
+                    continue;
+                };
+
+                // Detect 401 auth errors from MCP tool calls (uses original name)
+                if is_auth_error(&result_info.tool_output)
+                    && let Some(server_id) = ctx.mcp_tool_map.get(name.as_str())
+                {
+                    tracing::warn!(
+                        "🔐 [agent_streaming] MCP tool '{}' returned auth error, server: {}",
+                        name,
+                        server_id
+                    );
+                    let server_id = server_id.clone();
+                    let app_handle = ctx.app.clone();
+                    let conv_id = ctx.conversation_id.clone();
+                    let manager = ctx.mcp_manager.clone();
+                    tokio::spawn(async move {
+                        manager.disconnect(&server_id).await;
+                        events::emit(
+                            &app_handle,
+                            McpAuthRequired {
+                                conversation_id: conv_id,
+                                server_id,
+                            },
+                        );
+                    });
+                }
+
+                // Build display name for frontend (name may be composite "server/tool" in lazy-load)
+                let display_name = mcp_display_name_from_stored(name, &ctx.mcp_server_name_map);
+
+                // Emit tool result event to frontend
+                ctx.coalescer
+                    .send_event(StreamEvent::ToolCallCompleted(ToolCallCompleted {
+                        conversation_id: ctx.conversation_id.clone(),
+                        tool_call_id: result_info.id,
+                        tool_name: display_name,
+                        tool_input: input.clone(),
+                        tool_output: result_info.tool_output,
+                    }));
+            }
+            StreamChunkType::Image(data_url) => {
+                if acc.push_image(&data_url) {
+                    ctx.coalescer
+                        .send_event(StreamEvent::ChatStreamImage(ChatStreamImage {
+                            conversation_id: ctx.conversation_id.clone(),
+                            image_url: data_url,
+                        }));
+                }
+            }
+        }
+    }
+
+    acc
+}
+
+/// Store token usage and the resulting cost on a saved assistant message, and add
+/// it to the usage ledger. Cost is only computed when the responding model has
+/// pricing configured.
+#[allow(clippy::too_many_arguments)]
+async fn record_message_usage(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    message: &mut Message,
+    model_db_id: &Option<String>,
+    assistant_db_id: &Option<String>,
+    provider_type: &str,
+    model_id: &str,
+    response: &ChatResponse,
+) {
+    if response.input_tokens.is_none() && response.output_tokens.is_none() {
+        return;
+    }
+
+    let pricing_model_id = match (model_db_id, assistant_db_id) {
+        (Some(model_id), _) => Some(model_id.clone()),
+        (None, Some(assistant_id)) => state
+            .db
+            .get_assistant(assistant_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|a| a.model_id),
+        (None, None) => None,
+    };
+
+    let model = match pricing_model_id {
+        Some(id) => state.db.get_model(&id).await.ok().flatten(),
+        None => None,
+    };
+    let cost = model
+        .as_ref()
+        .and_then(|m| m.cost_for_usage(response.input_tokens, response.output_tokens));
+
+    if let Err(e) = state
+        .db
+        .set_message_usage(
+            &message.id,
+            response.input_tokens,
+            response.output_tokens,
+            cost,
+        )
+        .await
+    {
+        tracing::warn!("⚠️ [agent_streaming] Failed to save message usage: {}", e);
+        return;
+    }
+
+    message.input_tokens = response.input_tokens;
+    message.output_tokens = response.output_tokens;
+    message.cost = cost;
+
+    let provider = match &model {
+        Some(m) => state.db.get_provider(&m.provider_id).await.ok().flatten(),
+        None => None,
+    };
+    let entry = NewUsageEntry {
+        message_id: Some(message.id.clone()),
+        conversation_id: message.conversation_id.clone(),
+        provider_id: provider.as_ref().map(|p| p.id.clone()),
+        provider_name: provider.map(|p| p.name),
+        provider_type: Some(provider_type.to_string()),
+        model_db_id: model.as_ref().map(|m| m.id.clone()),
+        model_id: Some(model_id.to_string()),
+        model_name: model.map(|m| m.name),
+        input_tokens: response.input_tokens.unwrap_or(0),
+        output_tokens: response.output_tokens.unwrap_or(0),
+        cost,
+    };
+    crate::commands::record_spend(app, state, entry).await;
+}
+
+/// Store which provider, model and parameters produced a saved assistant message.
+/// Parameters are left empty when the request used the provider defaults.
+async fn record_generation_info(
+    state: &AppState,
+    message: &mut Message,
+    provider_type: &str,
+    model_id: &str,
+    model_params: &ModelParameters,
+) {
+    let model_params = Some(model_params).filter(|p| p.has_custom_params());
+    if let Err(e) = state
+        .db
+        .set_message_generation_info(&message.id, provider_type, model_id, model_params)
+        .await
+    {
+        tracing::warn!(
+            "⚠️ [agent_streaming] Failed to save message generation info: {}",
+            e
+        );
+        return;
+    }
+
+    message.provider_type = Some(provider_type.to_string());
+    message.model_id = Some(model_id.to_string());
+    message.model_params = model_params.cloned();
+}
+
+/// OpenRouter routing for a model: the provider's defaults with the model's overrides
+async fn openrouter_routing(
+    state: &AppState,
+    model_db_id: Option<&str>,
+) -> Option<crate::models::OpenRouterRouting> {
+    let model = state.db.get_model(model_db_id?).await.ok().flatten()?;
+    let provider = state
+        .db
+        .get_provider(&model.provider_id)
+        .await
+        .ok()
+        .flatten()?;
+    let routing = match (provider.openrouter_routing, model.openrouter_routing) {
+        (Some(defaults), Some(overrides)) => defaults.merged_with(&overrides),
+        (defaults, overrides) => overrides.or(defaults)?,
+    };
+    Some(routing)
+}
+
+/// Load the tools/MCP servers linked to an assistant, dropping links to tools that
+/// were deleted or globally disabled since the assistant was configured.
+async fn load_assistant_tool_ids(state: &AppState, assistant_id: &str) -> Vec<String> {
+    let linked_ids = match state.db.get_assistant_tool_ids(assistant_id).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!("⚠️ [agent_streaming] Failed to load assistant tools: {}", e);
+            return Vec::new();
+        }
+    };
+
+    if linked_ids.is_empty() {
+        return linked_ids;
+    }
+
+    let tools = match state.db.get_tools_by_ids(&linked_ids).await {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::warn!(
+                "⚠️ [agent_streaming] Failed to resolve assistant tools, using links as-is: {}",
+                e
+            );
+            return linked_ids;
+        }
+    };
+
+    linked_ids
+        .into_iter()
+        .filter(|id| match tools.iter().find(|t| &t.id == id) {
+            Some(tool) if tool.is_enabled => true,
+            Some(tool) => {
+                tracing::info!(
+                    "🚫 [agent_streaming] Skipping disabled assistant tool '{}'",
+                    tool.name
+                );
+                false
+            }
+            None => {
+                tracing::warn!(
+                    "⚠️ [agent_streaming] Assistant links to missing tool '{}'",
+                    id
+                );
+                false
+            }
+        })
+        .collect()
 }
 
 /// Result of loading MCP tools: server tools for the agent + mappings for tool name resolution.
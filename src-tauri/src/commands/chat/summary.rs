@@ -0,0 +1,193 @@
+//! Conversation summary generation
+//!
+//! Produces a short summary stored on the conversation (shown in the sidebar), either on
+//! demand or automatically every N new messages when `conversation_summary_refresh_interval`
+//! is set.
+
+use super::super::AppState;
+use super::binding;
+use super::title::get_conversation_provider_info;
+use crate::error::AppError;
+use crate::events::{self, ConversationUpdated};
+use crate::llm::{self, ChatMessage};
+use crate::models::{Message, ModelRole};
+use crate::prompts;
+use tauri::State;
+
+/// Settings key: regenerate the summary after this many new messages (0 or unset = off)
+pub const SUMMARY_REFRESH_INTERVAL_KEY: &str = "conversation_summary_refresh_interval";
+
+/// Only the most recent messages are sent to keep the request small
+const MAX_TRANSCRIPT_MESSAGES: usize = 40;
+
+/// Per-message character cap inside the transcript
+const MAX_TRANSCRIPT_MESSAGE_CHARS: usize = 2000;
+
+#[tauri::command]
+pub async fn generate_conversation_summary(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    conversation_id: String,
+) -> Result<String, AppError> {
+    Ok(generate_and_store_summary(&state, &app, &conversation_id).await?)
+}
+
+/// Regenerate the summary if auto-refresh is enabled and enough new messages arrived.
+/// Runs as a background job; not being due yet is not an error.
+pub(crate) async fn refresh_summary_if_due(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+) -> Result<(), String> {
+    let interval = state
+        .db
+        .get_setting(SUMMARY_REFRESH_INTERVAL_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(0);
+    if interval <= 0 {
+        return Ok(());
+    }
+
+    // Deleted since the job was queued
+    let Some(conversation) = state
+        .db
+        .get_conversation(conversation_id)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+    let messages = state
+        .db
+        .list_messages_by_conversation(conversation_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if (messages.len() as i64) - conversation.summary_message_count < interval {
+        return Ok(());
+    }
+
+    tracing::info!(
+        "📝 [summary] Auto-refreshing summary for conversation {} ({} messages)",
+        conversation_id,
+        messages.len()
+    );
+    generate_and_store_summary(state, app, conversation_id).await?;
+    Ok(())
+}
+
+/// Generate a summary with the "fast" role model (falling back to the conversation's
+/// model, also when its provider policy rules the fast model out), store it on the
+/// conversation and notify the frontend.
+pub(crate) async fn generate_and_store_summary(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+) -> Result<String, String> {
+    let messages = state
+        .db
+        .list_messages_by_conversation(conversation_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if messages.is_empty() {
+        return Err("No messages in conversation to summarize".to_string());
+    }
+
+    let (provider, model, api_key, base_url, api_style) =
+        match binding::resolve_allowed_role_binding(state, ModelRole::Fast, conversation_id).await {
+            Some(fast) => (
+                fast.provider,
+                fast.model,
+                fast.api_key,
+                fast.base_url,
+                fast.api_style,
+            ),
+            None => get_conversation_provider_info(state, conversation_id).await?,
+        };
+
+    let transcript = build_transcript(&messages);
+    let auxiliary = super::auxiliary::auxiliary_call(state, conversation_id).await;
+    let response = llm::call_provider(
+        &provider,
+        model,
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::CONVERSATION_SUMMARY_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_conversation_summary_user_prompt(&transcript),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary.token(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let summary = response.content.trim().to_string();
+    if summary.is_empty() {
+        return Err("Model returned an empty summary".to_string());
+    }
+
+    state
+        .db
+        .set_conversation_summary(conversation_id, &summary, messages.len() as i64)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    events::emit(
+        app,
+        ConversationUpdated {
+            conversation_id: conversation_id.to_string(),
+            summary: Some(summary.clone()),
+            ..Default::default()
+        },
+    );
+
+    tracing::info!(
+        "📝 [summary] Stored summary for conversation {}",
+        conversation_id
+    );
+    Ok(summary)
+}
+
+/// Render recent messages as a plain "Role: text" transcript
+pub(super) fn build_transcript(messages: &[Message]) -> String {
+    let start = messages.len().saturating_sub(MAX_TRANSCRIPT_MESSAGES);
+    messages[start..]
+        .iter()
+        .filter(|m| !m.content.trim().is_empty())
+        .map(|m| {
+            let role = if m.sender_type == "user" {
+                "User"
+            } else {
+                "Assistant"
+            };
+            let content: String = m
+                .content
+                .chars()
+                .take(MAX_TRANSCRIPT_MESSAGE_CHARS)
+                .collect();
+            format!("{}: {}", role, content.trim())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -1,10 +1,52 @@
 //! Conversation title generation
 
 use super::super::AppState;
+use super::binding::ResolvedBinding;
+use crate::error::AppError;
+use crate::events::{self, ConversationUpdated};
 use crate::llm::{self, ChatMessage};
+use crate::models::ModelRole;
+use crate::network::is_local_endpoint;
 use crate::prompts;
 use anyhow::Result;
-use tauri::{Emitter, State};
+use tauri::State;
+use tokio_util::sync::CancellationToken;
+
+/// Settings key for when titles are generated automatically
+pub const TITLE_GENERATION_MODE_KEY: &str = "title_generation_mode";
+
+/// When new conversations get an automatic title. Manual generation always works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleGenerationMode {
+    /// After the first message, with the fast model or the conversation's model
+    Auto,
+    /// Only when the title model runs on a local endpoint, so nothing leaves the machine
+    LocalOnly,
+    /// Never automatically
+    Manual,
+}
+
+impl From<&str> for TitleGenerationMode {
+    fn from(s: &str) -> Self {
+        match s {
+            "local_only" => TitleGenerationMode::LocalOnly,
+            "manual" => TitleGenerationMode::Manual,
+            _ => TitleGenerationMode::Auto,
+        }
+    }
+}
+
+async fn title_generation_mode(state: &AppState) -> TitleGenerationMode {
+    state
+        .db
+        .get_setting(TITLE_GENERATION_MODE_KEY)
+        .await
+        .ok()
+        .flatten()
+        .as_deref()
+        .map(TitleGenerationMode::from)
+        .unwrap_or(TitleGenerationMode::Auto)
+}
 
 /// Helper to get provider info from conversation participants.
 /// Returns (provider_type, model_id, api_key, base_url, api_style).
@@ -95,7 +137,7 @@ pub(crate) async fn get_conversation_provider_info(
 pub async fn generate_conversation_title_manually(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     tracing::info!(
         "🏷️ [manual_title] Generating title for conversation: {}",
         conversation_id
@@ -105,11 +147,12 @@ pub async fn generate_conversation_title_manually(
     let messages = state
         .db
         .list_messages_by_conversation(&conversation_id)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     if messages.is_empty() {
-        return Err("No messages in conversation to generate title from".to_string());
+        return Err(AppError::validation(
+            "No messages in conversation to generate title from",
+        ));
     }
 
     // Find first user message
@@ -120,7 +163,9 @@ pub async fn generate_conversation_title_manually(
         .unwrap_or_default();
 
     if user_message.is_empty() {
-        return Err("No user message found to generate title from".to_string());
+        return Err(AppError::validation(
+            "No user message found to generate title from",
+        ));
     }
 
     // Get provider info from conversation participants
@@ -128,106 +173,94 @@ pub async fn generate_conversation_title_manually(
         get_conversation_provider_info(&state, &conversation_id).await?;
 
     // Generate the title
+    let auxiliary = super::auxiliary::auxiliary_call(&state, &conversation_id).await;
     let title = generate_conversation_title(
         &state,
+        &conversation_id,
         &user_message,
         &provider,
         &model,
         api_key,
         base_url,
         api_style,
+        auxiliary.token(),
     )
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     tracing::info!("🏷️ [manual_title] Generated title: {}", title);
     Ok(title)
 }
 
+/// Model used for titles: the "fast" role model (falls back to the legacy summary
+/// model setting) when the conversation's provider policy allows it, otherwise the
+/// conversation's own model.
+async fn resolve_title_model(
+    state: &AppState,
+    conversation_id: &str,
+    provider: &str,
+    model: &str,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    api_style: Option<String>,
+) -> ResolvedBinding {
+    match super::binding::resolve_allowed_role_binding(state, ModelRole::Fast, conversation_id)
+        .await
+    {
+        Some(fast) => {
+            tracing::info!(
+                "🏷️ [generate_title] Using fast model: {} from provider: {}",
+                fast.model,
+                fast.provider
+            );
+            fast
+        }
+        None => {
+            tracing::info!("🏷️ [generate_title] No fast model set, using current model");
+            ResolvedBinding {
+                provider: provider.to_string(),
+                model: model.to_string(),
+                api_key,
+                base_url,
+                api_style,
+                model_db_id: None,
+                assistant_db_id: None,
+                system_prompt: None,
+                user_prompt: None,
+            }
+        }
+    }
+}
+
 /// Helper function to generate conversation title
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn generate_conversation_title(
     state: &AppState,
+    conversation_id: &str,
     user_message: &str,
     provider: &str,
     model: &str,
     api_key: Option<String>,
     base_url: Option<String>,
     api_style: Option<String>,
+    cancel_token: CancellationToken,
 ) -> Result<String> {
     tracing::info!("🏷️ [generate_title] Starting title generation...");
 
-    // Check if there's a custom summary model setting
-    let summary_model_id = state
-        .db
-        .get_setting("conversation_summary_model_id")
-        .await
-        .ok()
-        .flatten();
-
-    let (summary_provider, summary_model, summary_api_key, summary_base_url, summary_api_style) =
-        if let Some(model_id) = summary_model_id {
-            // Get the custom model settings
-            match state.db.get_model(&model_id).await {
-                Ok(Some(m)) => {
-                    // Get provider info
-                    match state.db.get_provider(&m.provider_id).await {
-                        Ok(Some(p)) => {
-                            tracing::info!(
-                                "🏷️ [generate_title] Using custom summary model: {} from provider: {}",
-                                m.model_id,
-                                p.provider_type
-                            );
-                            (
-                                p.provider_type.clone(),
-                                m.model_id.clone(),
-                                p.api_key.clone(),
-                                p.base_url.clone(),
-                                p.api_style.clone(),
-                            )
-                        }
-                        _ => {
-                            tracing::info!(
-                                "🏷️ [generate_title] Custom model provider not found, using current model"
-                            );
-                            (
-                                provider.to_string(),
-                                model.to_string(),
-                                api_key.clone(),
-                                base_url.clone(),
-                                api_style.clone(),
-                            )
-                        }
-                    }
-                }
-                _ => {
-                    tracing::info!(
-                        "🏷️ [generate_title] Custom model not found, using current model"
-                    );
-                    (
-                        provider.to_string(),
-                        model.to_string(),
-                        api_key.clone(),
-                        base_url.clone(),
-                        api_style.clone(),
-                    )
-                }
-            }
-        } else {
-            // Use the current conversation model by default
-            tracing::info!("🏷️ [generate_title] No custom summary model set, using current model");
-            (
-                provider.to_string(),
-                model.to_string(),
-                api_key.clone(),
-                base_url.clone(),
-                api_style.clone(),
-            )
-        };
+    let title_model = resolve_title_model(
+        state,
+        conversation_id,
+        provider,
+        model,
+        api_key,
+        base_url,
+        api_style,
+    )
+    .await;
 
     // Generate title using unified provider handler
     let response = llm::call_provider(
-        &summary_provider,
-        summary_model,
+        &title_model.provider,
+        title_model.model,
         vec![
             ChatMessage {
                 role: "system".to_string(),
@@ -248,9 +281,10 @@ pub(crate) async fn generate_conversation_title(
                 reasoning_content: None,
             },
         ],
-        summary_api_key,
-        summary_base_url,
-        summary_api_style,
+        title_model.api_key,
+        title_model.base_url,
+        title_model.api_style,
+        cancel_token,
     )
     .await?;
 
@@ -278,18 +312,46 @@ pub(crate) async fn auto_generate_title_if_needed(
     base_url: Option<String>,
     api_style: Option<String>,
 ) {
+    let mode = title_generation_mode(state).await;
+    if mode == TitleGenerationMode::Manual {
+        return;
+    }
+
     if let Ok(Some(conversation)) = state.db.get_conversation(conversation_id).await
         && conversation.title.is_empty()
     {
+        if mode == TitleGenerationMode::LocalOnly {
+            let title_model = resolve_title_model(
+                state,
+                conversation_id,
+                provider,
+                model,
+                None,
+                base_url.clone(),
+                None,
+            )
+            .await;
+            if !is_local_endpoint(&title_model.provider, title_model.base_url.as_deref()) {
+                tracing::info!(
+                    "🏷️ [auto_title] Skipping: title model ({}) is not local",
+                    title_model.provider
+                );
+                return;
+            }
+        }
+
         tracing::info!("🏷️ [auto_title] Generating title for new conversation...");
+        let auxiliary = super::auxiliary::auxiliary_call(state, conversation_id).await;
         match generate_conversation_title(
             state,
+            conversation_id,
             user_content,
             provider,
             model,
             api_key,
             base_url,
             api_style,
+            auxiliary.token(),
         )
         .await
         {
@@ -298,12 +360,13 @@ pub(crate) async fn auto_generate_title_if_needed(
                     Ok(_) => {
                         tracing::info!("✅ [auto_title] Conversation title updated to: {}", title);
                         // Notify frontend of title update
-                        let _ = app.emit(
-                            "conversation-updated",
-                            serde_json::json!({
-                                "conversation_id": conversation_id,
-                                "title": title,
-                            }),
+                        events::emit(
+                            app,
+                            ConversationUpdated {
+                                conversation_id: conversation_id.to_string(),
+                                title: Some(title),
+                                ..Default::default()
+                            },
                         );
                     }
                     Err(e) => tracing::error!(
@@ -316,3 +379,22 @@ pub(crate) async fn auto_generate_title_if_needed(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_generation_mode_from_str() {
+        assert_eq!(
+            TitleGenerationMode::from("local_only"),
+            TitleGenerationMode::LocalOnly
+        );
+        assert_eq!(
+            TitleGenerationMode::from("manual"),
+            TitleGenerationMode::Manual
+        );
+        assert_eq!(TitleGenerationMode::from("auto"), TitleGenerationMode::Auto);
+        assert_eq!(TitleGenerationMode::from(""), TitleGenerationMode::Auto);
+    }
+}
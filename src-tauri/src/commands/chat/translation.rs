@@ -0,0 +1,142 @@
+//! Message translation
+//!
+//! Translates a message with the "fast" role model (falling back to the conversation's
+//! model) and stores the result as a `translation` process step on the message, so the
+//! UI can switch between original and translation without another request.
+
+use super::super::AppState;
+use super::auxiliary::auxiliary_text;
+use super::binding;
+use super::title::get_conversation_provider_info;
+use crate::error::AppError;
+use crate::llm::{self, ChatMessage};
+use crate::models::{CreateTranslationRequest, ModelRole, Translation};
+use crate::prompts;
+use tauri::State;
+
+/// Translate a message into `target_lang` (a language name such as "German").
+/// A stored translation into the same language is returned as is.
+#[tauri::command]
+pub async fn translate_message(
+    state: State<'_, AppState>,
+    message_id: String,
+    target_lang: String,
+) -> Result<Translation, AppError> {
+    let target_lang = target_lang.trim().to_string();
+    if target_lang.is_empty() {
+        return Err(AppError::validation("A target language is required"));
+    }
+
+    let message = state
+        .db
+        .get_message(&message_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Message not found: {}", message_id)))?;
+    if message.content.trim().is_empty() {
+        return Err(AppError::validation("The message has no text to translate"));
+    }
+    let conversation_id = message
+        .conversation_id
+        .clone()
+        .ok_or_else(|| AppError::validation("The message doesn't belong to a conversation"))?;
+
+    let existing = state.db.get_translations_by_message(&message_id).await?;
+    if let Some(translation) = existing
+        .iter()
+        .find(|t| t.target_language.eq_ignore_ascii_case(&target_lang))
+    {
+        return Ok(translation.clone());
+    }
+
+    let (provider, model, api_key, base_url, api_style) =
+        match binding::resolve_role_binding(&state, ModelRole::Fast).await {
+            Some(fast) => (
+                fast.provider,
+                fast.model,
+                fast.api_key,
+                fast.base_url,
+                fast.api_style,
+            ),
+            None => get_conversation_provider_info(&state, &conversation_id).await?,
+        };
+    crate::commands::enforce_provider_policy(
+        &state,
+        &conversation_id,
+        &provider,
+        base_url.as_deref(),
+        None,
+        None,
+    )
+    .await?;
+
+    tracing::info!(
+        "🌐 [translation] Translating message {} into {} with {}/{}",
+        message_id,
+        target_lang,
+        provider,
+        model
+    );
+
+    let auxiliary = super::auxiliary::auxiliary_call(&state, &conversation_id).await;
+    let response = llm::call_provider(
+        &provider,
+        model.clone(),
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::TRANSLATION_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_translation_user_prompt(&target_lang, &message.content),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary.token(),
+    )
+    .await?;
+
+    let translation = auxiliary_text(&response, &model, &provider, "translation")?;
+
+    let display_order = state
+        .db
+        .get_message_steps(&message_id)
+        .await?
+        .iter()
+        .map(|step| step.display_order() + 1)
+        .max()
+        .unwrap_or(0);
+    state
+        .db
+        .create_translation(CreateTranslationRequest {
+            message_id,
+            target_language: target_lang,
+            content: translation,
+            model: Some(format!("{}/{}", provider, model)),
+            display_order: Some(display_order),
+        })
+        .await
+        .map_err(AppError::from)
+}
+
+/// Remove a stored translation so the message can be translated again
+#[tauri::command]
+pub async fn delete_translation(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state
+        .db
+        .delete_translation(&id)
+        .await
+        .map_err(AppError::from)
+}
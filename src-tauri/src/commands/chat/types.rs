@@ -18,8 +18,18 @@ pub struct ImageAttachmentInput {
     pub mime_type: String,
 }
 
-/// Parameter overrides from conversation settings
+/// Audio attachment data from frontend (transcribed before sending)
 #[derive(Debug, Clone, Deserialize)]
+pub struct AudioAttachmentInput {
+    pub name: String,
+    /// Data URL (`data:audio/...;base64,...`)
+    pub base64: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+}
+
+/// Per-request parameter overrides; these win over the conversation's custom parameters
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ParameterOverrides {
     pub temperature: Option<f64>,
     pub max_tokens: Option<i64>,
@@ -27,3 +37,19 @@ pub struct ParameterOverrides {
     pub frequency_penalty: Option<f64>,
     pub presence_penalty: Option<f64>,
 }
+
+/// A participant taking turns in a roundtable
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoundtableParticipant {
+    /// "model" or "assistant"
+    pub participant_type: String,
+    pub participant_id: String,
+}
+
+/// Roundtable mode: several participants answer in turn, each seeing prior replies
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoundtableOptions {
+    pub participants: Vec<RoundtableParticipant>,
+    /// Number of full rounds through all participants (defaults to 1)
+    pub rounds: Option<u32>,
+}
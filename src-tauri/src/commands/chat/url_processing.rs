@@ -1,12 +1,18 @@
 //! URL fetching and storage logic
 
 use super::super::AppState;
+use crate::events::{
+    self, AttachmentProcessingComplete, AttachmentProcessingStarted, AttachmentUpdate,
+};
 use crate::models::{ContextType, CreateFetchResultRequest};
-use crate::web_fetch::{self, FetchConfig, FetchMode, FetchedWebResource, LocalMethod};
-use tauri::Emitter;
+use crate::web_fetch::{
+    self, ByteBudget, DEFAULT_FETCH_BUDGET_MB, FETCH_BUDGET_MB_KEY, FetchConfig, FetchMode,
+    FetchedWebResource, LocalMethod,
+};
+use std::sync::Arc;
 
-/// Load fetch configuration from settings
-async fn load_fetch_config(state: &AppState) -> FetchConfig {
+/// Load fetch configuration from settings. Each call starts a fresh download budget.
+pub(super) async fn load_fetch_config(state: &AppState) -> FetchConfig {
     let mode = match state.db.get_setting("web_fetch_mode").await {
         Ok(Some(m)) if m == "api" => FetchMode::Api,
         _ => FetchMode::Local,
@@ -29,16 +35,55 @@ async fn load_fetch_config(state: &AppState) -> FetchConfig {
         .flatten()
         .filter(|k| !k.is_empty());
 
+    let budget_mb = state
+        .db
+        .get_setting(FETCH_BUDGET_MB_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|mb| mb.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_FETCH_BUDGET_MB);
+
     FetchConfig {
         mode,
         local_method,
         jina_api_key,
+        byte_budget: (budget_mb > 0).then(|| Arc::new(ByteBudget::from_megabytes(budget_mb))),
+    }
+}
+
+/// Store a page's favicon so the UI doesn't load it from the site on every render.
+/// Returns the storage path; pages of one site share the file.
+async fn cache_favicon(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    favicon_url: &str,
+) -> Option<String> {
+    if let Ok(Some(path)) = state.db.find_cached_favicon(favicon_url).await
+        && crate::storage::file_exists(app, &path).unwrap_or(false)
+    {
+        return Some(path);
     }
+
+    let favicon = web_fetch::download_favicon(favicon_url).await?;
+    let path = crate::storage::generate_favicon_storage_path(
+        &crate::storage::hash_bytes(&favicon.bytes),
+        &favicon.mime_type,
+    );
+    if !crate::storage::file_exists(app, &path).unwrap_or(false)
+        && let Err(e) = crate::storage::write_binary(app, &path, &favicon.bytes)
+    {
+        tracing::warn!("Failed to cache favicon {}: {}", favicon_url, e);
+        return None;
+    }
+    Some(path)
 }
 
 /// Result of URL processing
 pub(crate) struct UrlProcessingResult {
     pub fetched_resources: Vec<FetchedWebResource>,
+    /// The stored fetch result of each fetched resource, `None` where storing failed
+    pub fetch_result_ids: Vec<Option<String>>,
     pub attachment_ids: Vec<String>,
 }
 
@@ -54,18 +99,19 @@ pub(crate) async fn fetch_and_store_urls(
     if urls.is_empty() {
         return UrlProcessingResult {
             fetched_resources: Vec::new(),
+            fetch_result_ids: Vec::new(),
             attachment_ids: Vec::new(),
         };
     }
 
     tracing::info!("🔍 [url_processing] Processing {} URLs", urls.len());
-    let _ = app.emit(
-        "attachment-processing-started",
-        serde_json::json!({
-            "message_id": user_message_id,
-            "conversation_id": conversation_id,
-            "urls": urls,
-        }),
+    events::emit(
+        app,
+        AttachmentProcessingStarted {
+            message_id: user_message_id.to_string(),
+            conversation_id: conversation_id.to_string(),
+            urls: urls.to_vec(),
+        },
     );
 
     // Load fetch config from settings
@@ -80,6 +126,7 @@ pub(crate) async fn fetch_and_store_urls(
     let (mut rx, fetch_handle) = web_fetch::fetch_urls_with_config(urls, None, fetch_config).await;
 
     let mut fetched_resources: Vec<FetchedWebResource> = Vec::new();
+    let mut fetch_result_ids: Vec<Option<String>> = Vec::new();
     let mut attachment_ids: Vec<String> = Vec::new();
 
     // Process each result as it arrives from the channel
@@ -109,16 +156,15 @@ pub(crate) async fn fetch_and_store_urls(
             }
 
             // Emit attachment-update immediately so UI shows this result
-            let _ = app.emit(
-                "attachment-update",
-                serde_json::json!({
-                    "message_id": user_message_id,
-                    "conversation_id": conversation_id,
-                    "attachment_id": existing.id,
-                    "completed_url": resource.url,
-                }),
+            events::emit(
+                app,
+                AttachmentUpdate {
+                    completed_url: Some(resource.url.clone()),
+                    ..AttachmentUpdate::new(user_message_id, conversation_id, &existing.id)
+                },
             );
 
+            fetch_result_ids.push(Some(existing.id.clone()));
             attachment_ids.push(existing.id);
             fetched_resources.push(resource);
             continue;
@@ -135,6 +181,7 @@ pub(crate) async fn fetch_and_store_urls(
                 resource.url,
                 e
             );
+            fetch_result_ids.push(None);
             fetched_resources.push(resource);
             continue;
         }
@@ -146,6 +193,10 @@ pub(crate) async fn fetch_and_store_urls(
         };
         let headings_json = serde_json::to_string(&resource.metadata.headings).ok();
         let content_size = resource.content.len() as i64;
+        let favicon_path = match &resource.metadata.favicon_url {
+            Some(favicon_url) => cache_favicon(state, app, favicon_url).await,
+            None => None,
+        };
 
         // Determine source type
         let (source_type, source_id) = if search_result_id.is_some() {
@@ -157,7 +208,7 @@ pub(crate) async fn fetch_and_store_urls(
             ("user_link".to_string(), None)
         };
 
-        match state
+        let fetch_result_id = match state
             .db
             .create_fetch_result(CreateFetchResultRequest {
                 source_type: Some(source_type),
@@ -175,6 +226,7 @@ pub(crate) async fn fetch_and_store_urls(
                 original_size: resource.metadata.original_length.map(|l| l as i64),
                 processed_size: Some(content_size),
                 favicon_url: resource.metadata.favicon_url.clone(),
+                favicon_path,
                 content_hash: Some(content_hash.clone()),
             })
             .await
@@ -195,25 +247,26 @@ pub(crate) async fn fetch_and_store_urls(
                 }
 
                 // Emit attachment-update immediately so UI shows this result
-                let _ = app.emit(
-                    "attachment-update",
-                    serde_json::json!({
-                        "message_id": user_message_id,
-                        "conversation_id": conversation_id,
-                        "attachment_id": fetch_result.id,
-                        "completed_url": resource.url,
-                    }),
+                events::emit(
+                    app,
+                    AttachmentUpdate {
+                        completed_url: Some(resource.url.clone()),
+                        ..AttachmentUpdate::new(user_message_id, conversation_id, &fetch_result.id)
+                    },
                 );
 
-                attachment_ids.push(fetch_result.id);
+                attachment_ids.push(fetch_result.id.clone());
+                Some(fetch_result.id)
             }
             Err(e) => {
                 tracing::error!("Failed to create fetch_result for {}: {}", resource.url, e);
                 // Clean up saved file on failure
                 let _ = crate::storage::delete_file(app, &storage_path);
+                None
             }
-        }
+        };
 
+        fetch_result_ids.push(fetch_result_id);
         fetched_resources.push(resource);
     }
 
@@ -226,17 +279,18 @@ pub(crate) async fn fetch_and_store_urls(
     );
 
     // Emit attachment processing complete event with attachment IDs
-    let _ = app.emit(
-        "attachment-processing-complete",
-        serde_json::json!({
-            "message_id": user_message_id,
-            "conversation_id": conversation_id,
-            "attachment_ids": attachment_ids,
-        }),
+    events::emit(
+        app,
+        AttachmentProcessingComplete {
+            message_id: user_message_id.to_string(),
+            conversation_id: conversation_id.to_string(),
+            attachment_ids: attachment_ids.clone(),
+        },
     );
 
     UrlProcessingResult {
         fetched_resources,
+        fetch_result_ids,
         attachment_ids,
     }
 }
@@ -0,0 +1,281 @@
+//! URL quick-summarize
+//!
+//! Fetches a single page and summarizes it with the "fast" role model, outside of any
+//! chat turn. Meant for share-sheet and hotkey workflows: the caller either just shows
+//! the summary, or has it posted into a conversation (by default a dedicated
+//! "Read Later" conversation) as the URL followed by the summary.
+
+use super::super::AppState;
+use super::auxiliary::auxiliary_text;
+use super::binding;
+use super::title::get_conversation_provider_info;
+use crate::error::AppError;
+use crate::llm::{self, ChatMessage};
+use crate::models::{CreateConversationRequest, CreateMessageRequest, ModelRole};
+use crate::prompts;
+use crate::web_fetch;
+use serde::Serialize;
+use tauri::State;
+
+/// Settings key: id of the dedicated Read Later conversation
+pub const READ_LATER_CONVERSATION_ID_KEY: &str = "read_later_conversation_id";
+
+const READ_LATER_TITLE: &str = "Read Later";
+
+/// Pages are truncated to keep the request within small models' context
+const MAX_PAGE_CHARS: usize = 12000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlSummary {
+    pub url: String,
+    pub title: Option<String>,
+    pub summary: String,
+    /// Conversation the summary was posted into, when saved
+    pub conversation_id: Option<String>,
+    /// The posted summary message, when saved
+    pub message_id: Option<String>,
+}
+
+/// Fetch `url` and summarize it. With `save`, the URL and summary are posted into
+/// `conversation_id`, or into the Read Later conversation when none is given.
+#[tauri::command]
+pub async fn summarize_url(
+    state: State<'_, AppState>,
+    url: String,
+    conversation_id: Option<String>,
+    save: Option<bool>,
+) -> Result<UrlSummary, AppError> {
+    let url = url.trim().to_string();
+    match url::Url::parse(&url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => return Err(AppError::validation(format!("Not a web URL: {}", url))),
+    }
+    if !crate::network::is_online() {
+        return Err(AppError::validation(
+            "You're offline; try again when the network is back",
+        ));
+    }
+    let conversation_id = conversation_id.filter(|id| !id.is_empty());
+    if let Some(id) = &conversation_id
+        && state.db.get_conversation(id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Conversation not found: {}",
+            id
+        )));
+    }
+
+    let target_conversation = match (save.unwrap_or(false), &conversation_id) {
+        (false, _) => None,
+        (true, Some(id)) => Some(id.clone()),
+        (true, None) => Some(ensure_read_later_conversation(&state).await?),
+    };
+
+    let (provider, model, api_key, base_url, api_style) =
+        resolve_summary_model(&state, conversation_id.as_deref()).await?;
+    if let Some(id) = &target_conversation {
+        crate::commands::enforce_provider_policy(
+            &state,
+            id,
+            &provider,
+            base_url.as_deref(),
+            None,
+            None,
+        )
+        .await?;
+    }
+
+    let fetch_config = super::url_processing::load_fetch_config(&state).await;
+    let page =
+        web_fetch::fetch_web_resource_with_config(&url, Some(MAX_PAGE_CHARS), &fetch_config).await;
+    if let Some(error) = page.extraction_error {
+        return Err(AppError::validation(format!(
+            "Failed to fetch {}: {}",
+            url, error
+        )));
+    }
+    if page.content.trim().is_empty() {
+        return Err(AppError::validation(format!(
+            "No readable content at {}",
+            url
+        )));
+    }
+
+    tracing::info!(
+        "🔗 [url_summary] Summarizing {} with {}/{}",
+        url,
+        provider,
+        model
+    );
+
+    // Posted summaries can be stopped with the conversation's other auxiliary calls
+    let auxiliary = match &target_conversation {
+        Some(id) => Some(super::auxiliary::auxiliary_call(&state, id).await),
+        None => None,
+    };
+    let response = llm::call_provider(
+        &provider,
+        model.clone(),
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::URL_SUMMARY_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_url_summary_user_prompt(
+                    &url,
+                    page.title.as_deref(),
+                    &page.content,
+                ),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary
+            .as_ref()
+            .map(|call| call.token())
+            .unwrap_or_default(),
+    )
+    .await?;
+
+    let summary = auxiliary_text(&response, &model, &provider, "summary")?;
+
+    let mut result = UrlSummary {
+        url,
+        title: page.title,
+        summary,
+        conversation_id: None,
+        message_id: None,
+    };
+    if let Some(id) = target_conversation {
+        let message_id = post_summary(&state, &id, &result, &provider, &model).await?;
+        tracing::info!(
+            "🔗 [url_summary] Posted summary of {} to conversation {}",
+            result.url,
+            id
+        );
+        result.conversation_id = Some(id);
+        result.message_id = Some(message_id);
+    }
+    Ok(result)
+}
+
+/// The "fast" role model, else the conversation's model, else the default chat model
+async fn resolve_summary_model(
+    state: &AppState,
+    conversation_id: Option<&str>,
+) -> Result<
+    (
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ),
+    AppError,
+> {
+    let role = match binding::resolve_role_binding(state, ModelRole::Fast).await {
+        Some(fast) => Some(fast),
+        None if conversation_id.is_none() => {
+            binding::resolve_role_binding(state, ModelRole::Chat).await
+        }
+        None => None,
+    };
+    if let Some(binding) = role {
+        return Ok((
+            binding.provider,
+            binding.model,
+            binding.api_key,
+            binding.base_url,
+            binding.api_style,
+        ));
+    }
+    match conversation_id {
+        Some(id) => Ok(get_conversation_provider_info(state, id).await?),
+        None => Err(AppError::validation(
+            "No model available; set a fast or default chat model in settings",
+        )),
+    }
+}
+
+/// Post the URL as a user message and the summary as a reply to it
+async fn post_summary(
+    state: &AppState,
+    conversation_id: &str,
+    summary: &UrlSummary,
+    provider: &str,
+    model: &str,
+) -> Result<String, AppError> {
+    let link = match &summary.title {
+        Some(title) if !title.trim().is_empty() => {
+            format!("[{}]({})", title.trim(), summary.url)
+        }
+        _ => summary.url.clone(),
+    };
+    state
+        .db
+        .create_message(CreateMessageRequest {
+            conversation_id: Some(conversation_id.to_string()),
+            sender_type: "user".to_string(),
+            sender_id: None,
+            content: link,
+            tokens: None,
+        })
+        .await?;
+    let message = state
+        .db
+        .create_message(CreateMessageRequest {
+            conversation_id: Some(conversation_id.to_string()),
+            sender_type: "model".to_string(),
+            sender_id: None,
+            content: summary.summary.clone(),
+            tokens: None,
+        })
+        .await?;
+    state
+        .db
+        .set_message_generation_info(&message.id, provider, model, None)
+        .await?;
+    Ok(message.id)
+}
+
+/// Id of the Read Later conversation, creating it if missing or deleted
+async fn ensure_read_later_conversation(state: &AppState) -> Result<String, AppError> {
+    if let Some(id) = state
+        .db
+        .get_setting(READ_LATER_CONVERSATION_ID_KEY)
+        .await?
+        .filter(|id| !id.is_empty())
+        && state.db.get_conversation(&id).await?.is_some()
+    {
+        return Ok(id);
+    }
+
+    let conversation = state
+        .db
+        .create_conversation(CreateConversationRequest {
+            title: READ_LATER_TITLE.to_string(),
+        })
+        .await?;
+    state
+        .db
+        .set_setting(READ_LATER_CONVERSATION_ID_KEY, &conversation.id)
+        .await?;
+    tracing::info!(
+        "🔗 [url_summary] Created Read Later conversation {}",
+        conversation.id
+    );
+    Ok(conversation.id)
+}
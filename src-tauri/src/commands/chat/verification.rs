@@ -0,0 +1,270 @@
+//! Answer verification against fetched sources
+//!
+//! Opt-in post-generation step for research workflows: when a reply was written from
+//! fetched pages, a background job has the "fast" role model (falling back to the
+//! conversation's model) compare the reply with those pages. The verdict and the
+//! statements the pages don't back are stored as a `verification` process step on the
+//! reply. Replies are left unverified when the conversation's provider policy allows
+//! neither model.
+
+use super::super::AppState;
+use super::auxiliary::auxiliary_text;
+use super::binding;
+use super::title::get_conversation_provider_info;
+use crate::events::{self, AnswerVerified};
+use crate::llm::{self, ChatMessage};
+use crate::models::{CreateVerificationRequest, ModelRole, UnsupportedClaim};
+use crate::prompts;
+use serde::Deserialize;
+
+/// Settings key: "true" verifies replies written from fetched pages
+pub const ANSWER_VERIFICATION_ENABLED_KEY: &str = "answer_verification_enabled";
+
+/// Each page is truncated to keep the request within small models' context
+const MAX_SOURCE_CHARS: usize = 6000;
+
+const MAX_ANSWER_CHARS: usize = 8000;
+
+const VERDICTS: [&str; 3] = ["supported", "partially_supported", "unsupported"];
+
+/// Whether answer verification is enabled in settings
+pub(crate) async fn verification_enabled(state: &AppState) -> bool {
+    matches!(
+        state
+            .db
+            .get_setting(ANSWER_VERIFICATION_ENABLED_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    )
+}
+
+/// Check an assistant message against the fetch results it was given and store the
+/// outcome. Run by the job queue; an error lets the job retry.
+pub(crate) async fn verify_answer(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+    message_id: &str,
+    fetch_result_ids: &[String],
+) -> Result<(), String> {
+    // Deleted (e.g. regenerated) before the job ran
+    let Some(message) = state
+        .db
+        .get_message(message_id)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+    if !state
+        .db
+        .get_verifications_by_message(message_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .is_empty()
+    {
+        return Ok(());
+    }
+
+    let mut sources = Vec::new();
+    for id in fetch_result_ids {
+        let Ok(fetch) = state.db.get_fetch_result(id).await else {
+            continue;
+        };
+        match crate::storage::read_content(app, &fetch.storage_path) {
+            Ok(content) => sources.push((
+                fetch.url,
+                content.chars().take(MAX_SOURCE_CHARS).collect::<String>(),
+            )),
+            Err(e) => tracing::warn!("⚠️ [verification] Skipping source {}: {}", fetch.url, e),
+        }
+    }
+    if sources.is_empty() {
+        tracing::info!(
+            "ℹ️ [verification] No sources left to check message {} against",
+            message_id
+        );
+        return Ok(());
+    }
+
+    let (provider, model, api_key, base_url, api_style) =
+        match binding::resolve_allowed_role_binding(state, ModelRole::Fast, conversation_id).await {
+            Some(fast) => (
+                fast.provider,
+                fast.model,
+                fast.api_key,
+                fast.base_url,
+                fast.api_style,
+            ),
+            None => {
+                let info = get_conversation_provider_info(state, conversation_id).await?;
+                // A denial won't go away on retry, so the job finishes without a verdict
+                if let Err(e) = crate::commands::enforce_provider_policy(
+                    state,
+                    conversation_id,
+                    &info.0,
+                    info.3.as_deref(),
+                    None,
+                    None,
+                )
+                .await
+                {
+                    tracing::info!(
+                        "🛡️ [verification] Skipping message {}: {}",
+                        message_id,
+                        e.message
+                    );
+                    return Ok(());
+                }
+                info
+            }
+        };
+
+    let answer: String = message.content.chars().take(MAX_ANSWER_CHARS).collect();
+    let auxiliary = super::auxiliary::auxiliary_call(state, conversation_id).await;
+    let response = llm::call_provider(
+        &provider,
+        model.clone(),
+        vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: prompts::ANSWER_VERIFICATION_SYSTEM_PROMPT.to_string(),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: prompts::build_answer_verification_user_prompt(&sources, &answer),
+                images: vec![],
+                files: vec![],
+                tool_calls: vec![],
+                tool_call_id: None,
+                reasoning_content: None,
+            },
+        ],
+        api_key,
+        base_url,
+        api_style,
+        auxiliary.token(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let text =
+        auxiliary_text(&response, &model, &provider, "verification").map_err(String::from)?;
+    let outcome = parse_verification(&text)
+        .ok_or_else(|| "Model returned no usable verification".to_string())?;
+
+    let display_order = state
+        .db
+        .get_message_steps(message_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|step| step.display_order() + 1)
+        .max()
+        .unwrap_or(0);
+    let verification = state
+        .db
+        .create_verification(CreateVerificationRequest {
+            message_id: message_id.to_string(),
+            verdict: outcome.verdict,
+            summary: outcome.summary,
+            unsupported_claims: outcome.unsupported_claims,
+            model: Some(format!("{}/{}", provider, model)),
+            display_order: Some(display_order),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "🔎 [verification] Message {}: {} ({} unsupported claim(s))",
+        message_id,
+        verification.verdict,
+        verification.unsupported_claims.len()
+    );
+    events::emit(
+        app,
+        AnswerVerified {
+            conversation_id: conversation_id.to_string(),
+            message_id: message_id.to_string(),
+            verification_id: verification.id,
+            verdict: verification.verdict,
+        },
+    );
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct VerificationOutcome {
+    #[serde(default)]
+    verdict: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    unsupported_claims: Vec<UnsupportedClaim>,
+}
+
+/// Parse the model output: a JSON object, possibly inside a code block. An unknown
+/// verdict is derived from whether any claims were flagged.
+fn parse_verification(raw: &str) -> Option<VerificationOutcome> {
+    let trimmed = raw.trim();
+    let json = match (trimmed.find('{'), trimmed.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &trimmed[start..=end],
+        _ => return None,
+    };
+    let mut outcome: VerificationOutcome = serde_json::from_str(json).ok()?;
+
+    outcome
+        .unsupported_claims
+        .retain(|c| !c.claim.trim().is_empty());
+    let verdict = outcome
+        .verdict
+        .trim()
+        .to_lowercase()
+        .replace([' ', '-'], "_");
+    outcome.verdict = if VERDICTS.contains(&verdict.as_str()) {
+        verdict
+    } else if outcome.unsupported_claims.is_empty() {
+        "supported".to_string()
+    } else {
+        "partially_supported".to_string()
+    };
+    outcome.summary = outcome.summary.trim().to_string();
+    Some(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verification() {
+        let raw = "```json\n{\"verdict\": \"Partially Supported\", \"summary\": \"Mostly backed.\", \
+            \"unsupported_claims\": [{\"claim\": \"Released in 2019\", \"reason\": \"Sources say 2021\"}, \
+            {\"claim\": \" \", \"reason\": \"\"}]}\n```";
+        let outcome = parse_verification(raw).unwrap();
+        assert_eq!(outcome.verdict, "partially_supported");
+        assert_eq!(outcome.summary, "Mostly backed.");
+        assert_eq!(
+            outcome.unsupported_claims,
+            vec![UnsupportedClaim {
+                claim: "Released in 2019".to_string(),
+                reason: "Sources say 2021".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_verification_unknown_verdict() {
+        let outcome = parse_verification(r#"{"verdict": "mostly fine"}"#).unwrap();
+        assert_eq!(outcome.verdict, "supported");
+        assert!(parse_verification("The answer looks fine.").is_none());
+    }
+}
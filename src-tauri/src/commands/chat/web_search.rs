@@ -1,6 +1,11 @@
 //! Web search commands
 
-use crate::web_search::{SearchProvider, WebSearchResponse};
+use super::{AppState, outbox, search_processing, start_generation, url_processing};
+use crate::error::AppError;
+use crate::events::{StreamChannel, StreamSink};
+use crate::models::{CreateSearchDecisionRequest, Message, SearchDecision, SearchResultItem};
+use crate::web_search::{SearchProvider, SearchProviderHealth, WebSearchResponse};
+use tauri::State;
 
 /// Perform a web search using the specified provider
 #[tauri::command]
@@ -8,7 +13,7 @@ pub async fn perform_web_search(
     query: String,
     max_results: Option<usize>,
     provider: Option<String>,
-) -> Result<WebSearchResponse, String> {
+) -> Result<WebSearchResponse, AppError> {
     let max = max_results.unwrap_or(5);
     let search_provider = provider
         .as_deref()
@@ -22,20 +27,20 @@ pub async fn perform_web_search(
         search_provider.display_name()
     );
 
-    crate::web_search::search(search_provider, &query, max)
+    crate::web_search::search_with_fallback(search_provider, &query, max)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(crate::web_fetch::browser_app_error)
 }
 
 /// Extract search keywords from user input
 #[tauri::command]
-pub async fn extract_search_keywords(user_input: String) -> Result<String, String> {
+pub async fn extract_search_keywords(user_input: String) -> Result<String, AppError> {
     Ok(crate::web_search::extract_search_keywords(&user_input))
 }
 
 /// Get the list of available search providers
 #[tauri::command]
-pub async fn get_search_providers() -> Result<Vec<SearchProviderInfo>, String> {
+pub async fn get_search_providers() -> Result<Vec<SearchProviderInfo>, AppError> {
     Ok(SearchProvider::all()
         .into_iter()
         .map(|p| SearchProviderInfo {
@@ -45,6 +50,291 @@ pub async fn get_search_providers() -> Result<Vec<SearchProviderInfo>, String> {
         .collect())
 }
 
+/// Recent success rate of each search provider, in the order searches try them
+#[tauri::command]
+pub async fn get_search_provider_health(
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchProviderHealth>, AppError> {
+    let preferred = state
+        .db
+        .get_setting("search_provider")
+        .await?
+        .and_then(|id| SearchProvider::from_id(&id))
+        .unwrap_or_default();
+    Ok(crate::web_search::provider_health(preferred))
+}
+
+/// Re-run the reply to a user message with web search forced on, searching for `query`
+/// instead of what the search decision chose. See [`rerun_search`].
+#[tauri::command]
+pub async fn force_search_query(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    decision_id: String,
+    query: String,
+    stream_channel: Option<StreamChannel>,
+) -> Result<SearchDecision, AppError> {
+    let decision = state.db.get_search_decision(&decision_id).await?;
+    rerun_with_query(&state, app, &decision.message_id, query, stream_channel).await
+}
+
+/// Search again for a user message with a corrected query and regenerate the reply.
+///
+/// The new search and its fetched pages are attached to the same message; the previous
+/// search results and pages are detached, the replies after the message are replaced
+/// once the new reply is saved (the old reply's content stays available as a revision
+/// of it), and the message's search decision is updated (or created) to record the query.
+#[tauri::command]
+pub async fn rerun_search(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    message_id: String,
+    query: String,
+    stream_channel: Option<StreamChannel>,
+) -> Result<SearchDecision, AppError> {
+    rerun_with_query(&state, app, &message_id, query, stream_channel).await
+}
+
+async fn rerun_with_query(
+    state: &State<'_, AppState>,
+    app: tauri::AppHandle,
+    message_id: &str,
+    query: String,
+    stream_channel: Option<StreamChannel>,
+) -> Result<SearchDecision, AppError> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Err(AppError::validation("Search query is required"));
+    }
+
+    let message = state
+        .db
+        .get_message(message_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Message not found: {}", message_id)))?;
+    if message.sender_type != "user" {
+        return Err(AppError::validation(
+            "Only user messages can be searched again",
+        ));
+    }
+    let conversation_id = message
+        .conversation_id
+        .clone()
+        .ok_or_else(|| AppError::validation("Message does not belong to a conversation"))?;
+
+    if state
+        .generation_tasks
+        .read()
+        .await
+        .contains_key(&conversation_id)
+    {
+        return Err(AppError::validation(
+            "Wait for the current reply to finish before searching again",
+        ));
+    }
+
+    let mut pending =
+        checked_regeneration(state, &app, &message, stream_channel, true, None).await?;
+
+    tracing::info!(
+        "🔁 [rerun_search] Re-running message {} with search query: {}",
+        message.id,
+        query
+    );
+    replace_replies(state, &message, &mut pending).await?;
+    search_processing::supersede_search_context(state, &message.id).await?;
+    let decision = match state
+        .db
+        .get_search_decisions_by_message(&message.id)
+        .await?
+        .into_iter()
+        .next()
+    {
+        Some(decision) => state.db.force_search_decision(&decision.id, &query).await?,
+        None => {
+            state
+                .db
+                .create_search_decision(CreateSearchDecisionRequest {
+                    message_id: message.id.clone(),
+                    reasoning: "Search query provided by the user".to_string(),
+                    search_needed: true,
+                    search_query: Some(query.clone()),
+                    alternative_queries: Vec::new(),
+                    confidence: None,
+                    search_result_id: None,
+                    display_order: Some(0),
+                })
+                .await?
+        }
+    };
+
+    start_generation(state.inner().clone(), app, pending).await;
+
+    Ok(decision)
+}
+
+/// Fetch a page from a search's stored result list that wasn't fetched with the search,
+/// and attach it to the searched message.
+///
+/// With `regenerate`, the reply is generated again from all pages attached to the
+/// message, without searching anew, and replaces the replies after the message once it
+/// is saved. The old reply stays available as a revision of the new one.
+#[tauri::command]
+pub async fn fetch_search_result_item(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    item_id: String,
+    regenerate: Option<bool>,
+    stream_channel: Option<StreamChannel>,
+) -> Result<SearchResultItem, AppError> {
+    let item = state.db.get_search_result_item(&item_id).await?;
+    let search_result = state.db.get_search_result(&item.search_result_id).await?;
+    let message = state
+        .db
+        .get_message(&search_result.message_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(format!("Message not found: {}", search_result.message_id))
+        })?;
+    let conversation_id = message
+        .conversation_id
+        .clone()
+        .ok_or_else(|| AppError::validation("Message does not belong to a conversation"))?;
+
+    if state
+        .generation_tasks
+        .read()
+        .await
+        .contains_key(&conversation_id)
+    {
+        return Err(AppError::validation(
+            "Wait for the current reply to finish before fetching more results",
+        ));
+    }
+
+    tracing::info!(
+        "📥 [fetch_search_result_item] Fetching result #{} for message {}: {}",
+        item.rank,
+        message.id,
+        item.url
+    );
+    let result = url_processing::fetch_and_store_urls(
+        &state,
+        &app,
+        std::slice::from_ref(&item.url),
+        &message.id,
+        &conversation_id,
+        Some(&search_result.id),
+    )
+    .await;
+    if result.attachment_ids.is_empty() {
+        return Err(AppError::validation(format!("Couldn't fetch {}", item.url)));
+    }
+
+    if regenerate.unwrap_or(false) {
+        // Pages attached to the message only reach the model when fetched for the
+        // generation; unchanged pages are matched by content hash and not stored twice
+        let urls: Vec<String> = state
+            .db
+            .get_fetch_results_by_message(&message.id)
+            .await?
+            .into_iter()
+            .filter(|fetch| fetch.status == "success")
+            .map(|fetch| fetch.url)
+            .collect();
+        let mut pending =
+            checked_regeneration(&state, &app, &message, stream_channel, false, Some(urls)).await?;
+        replace_replies(&state, &message, &mut pending).await?;
+        start_generation(state.inner().clone(), app, pending).await;
+    }
+
+    Ok(state.db.get_search_result_item(&item_id).await?)
+}
+
+/// Build the generation that replaces the replies to a user message, checking that it may
+/// run now (network, budget, provider policy)
+async fn checked_regeneration(
+    state: &State<'_, AppState>,
+    app: &tauri::AppHandle,
+    message: &Message,
+    stream_channel: Option<StreamChannel>,
+    search_enabled: bool,
+    urls_to_fetch: Option<Vec<String>>,
+) -> Result<outbox::PendingGeneration, AppError> {
+    let conversation_id = message
+        .conversation_id
+        .clone()
+        .ok_or_else(|| AppError::validation("Message does not belong to a conversation"))?;
+    let binding = super::binding::resolve_stored_binding(state, &conversation_id, false).await?;
+    let pending = outbox::PendingGeneration {
+        stream: StreamSink::new(app.clone(), stream_channel),
+        conversation_id: conversation_id.clone(),
+        content: message.content.clone(),
+        provider: binding.provider,
+        model: binding.model,
+        api_key: binding.api_key,
+        base_url: binding.base_url,
+        api_style: binding.api_style,
+        include_history: None,
+        system_prompt: binding.system_prompt,
+        user_prompt: binding.user_prompt,
+        model_db_id: binding.model_db_id,
+        assistant_db_id: binding.assistant_db_id,
+        urls_to_fetch,
+        images: None,
+        files: None,
+        audio: None,
+        search_enabled,
+        user_message_id: message.id.clone(),
+        parameter_overrides: None,
+        use_provider_defaults: state
+            .db
+            .get_conversation_settings(&conversation_id)
+            .await
+            .map(|s| s.use_provider_defaults)
+            .unwrap_or(false),
+        roundtable: None,
+        replaced_message_ids: Vec::new(),
+    };
+    if !crate::network::is_online() && pending.needs_network() {
+        return Err(AppError::validation(
+            "You're offline; try again when the network is back",
+        ));
+    }
+    crate::commands::enforce_conversation_budget(state, app, &conversation_id).await?;
+    crate::commands::enforce_provider_policy(
+        state,
+        &conversation_id,
+        &pending.provider,
+        pending.base_url.as_deref(),
+        pending.model_db_id.as_deref(),
+        pending.assistant_db_id.as_deref(),
+    )
+    .await?;
+
+    Ok(pending)
+}
+
+/// Mark the replies after a user message as replaced by the regeneration. They are only
+/// removed once the new reply is saved, and the previous reply's content stays available
+/// as a revision of the new one.
+async fn replace_replies(
+    state: &State<'_, AppState>,
+    message: &Message,
+    pending: &mut outbox::PendingGeneration,
+) -> Result<(), AppError> {
+    pending.replaced_message_ids = state
+        .db
+        .list_messages_by_conversation(&pending.conversation_id)
+        .await?
+        .into_iter()
+        .skip_while(|m| m.id != message.id)
+        .skip(1)
+        .map(|m| m.id)
+        .collect();
+    Ok(())
+}
+
 /// Search provider information for frontend
 #[derive(serde::Serialize)]
 pub struct SearchProviderInfo {
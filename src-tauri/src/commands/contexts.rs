@@ -1,5 +1,8 @@
 use super::AppState;
-use crate::models::{ContextEnrichment, FetchResult, SearchResult};
+use crate::error::AppError;
+use crate::models::{
+    ContextEnrichment, FetchResult, MessageCitation, SearchResult, SearchResultItem,
+};
 use tauri::State;
 
 // ==========================================================================
@@ -10,36 +13,45 @@ use tauri::State;
 pub async fn get_message_contexts(
     state: State<'_, AppState>,
     message_id: String,
-) -> Result<Vec<ContextEnrichment>, String> {
+) -> Result<Vec<ContextEnrichment>, AppError> {
     state
         .db
         .get_message_contexts(&message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_search_result(
     state: State<'_, AppState>,
     id: String,
-) -> Result<SearchResult, String> {
+) -> Result<SearchResult, AppError> {
     state
         .db
         .get_search_result(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
+/// The full result list of a search, with the results that weren't fetched
 #[tauri::command]
-pub async fn get_fetch_result(
+pub async fn get_search_result_items(
     state: State<'_, AppState>,
-    id: String,
-) -> Result<FetchResult, String> {
+    search_result_id: String,
+) -> Result<Vec<SearchResultItem>, AppError> {
     state
         .db
-        .get_fetch_result(&id)
+        .get_search_result_items(&search_result_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn get_fetch_result(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<FetchResult, AppError> {
+    state.db.get_fetch_result(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -47,22 +59,35 @@ pub async fn get_fetch_results_by_source(
     state: State<'_, AppState>,
     source_type: String,
     source_id: String,
-) -> Result<Vec<FetchResult>, String> {
+) -> Result<Vec<FetchResult>, AppError> {
     state
         .db
         .get_fetch_results_by_source(&source_type, &source_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_fetch_results_by_message(
     state: State<'_, AppState>,
     message_id: String,
-) -> Result<Vec<FetchResult>, String> {
+) -> Result<Vec<FetchResult>, AppError> {
     state
         .db
         .get_fetch_results_by_message(&message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// The fetched pages an assistant message cites, by the `[number]` used in its text
+#[tauri::command]
+pub async fn get_message_citations(
+    state: State<'_, AppState>,
+    message_id: String,
+) -> Result<Vec<MessageCitation>, AppError> {
+    state
+        .db
+        .get_message_citations(&message_id)
+        .await
+        .map_err(AppError::from)
 }
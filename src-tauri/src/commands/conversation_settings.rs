@@ -1,5 +1,8 @@
 use super::AppState;
-use crate::models::{ConversationSettings, UpdateConversationSettingsRequest};
+use crate::error::AppError;
+use crate::models::{
+    ConversationSettings, ModelParameterOverrides, UpdateConversationSettingsRequest,
+};
 use tauri::State;
 use tracing::info;
 
@@ -7,7 +10,7 @@ use tracing::info;
 pub async fn get_conversation_settings(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<ConversationSettings, String> {
+) -> Result<ConversationSettings, AppError> {
     info!(
         "[conversation_settings] get_conversation_settings called: {}",
         conversation_id
@@ -16,7 +19,7 @@ pub async fn get_conversation_settings(
         .db
         .get_conversation_settings(&conversation_id)
         .await
-        .map_err(|e| e.to_string());
+        .map_err(AppError::from);
     info!(
         "[conversation_settings] get_conversation_settings result: {:?}",
         result
@@ -29,16 +32,26 @@ pub async fn update_conversation_settings(
     state: State<'_, AppState>,
     conversation_id: String,
     req: UpdateConversationSettingsRequest,
-) -> Result<ConversationSettings, String> {
+) -> Result<ConversationSettings, AppError> {
     info!(
         "[conversation_settings] update_conversation_settings called: {}, req: {:?}",
         conversation_id, req
     );
+    // Tools and project attachments run inside the working directory, so it has to exist
+    if let Some(Some(dir)) = &req.working_directory {
+        let path = std::path::Path::new(dir);
+        if !path.is_absolute() || !path.is_dir() {
+            return Err(AppError::validation(format!(
+                "Working directory must be an existing absolute directory: {}",
+                dir
+            )));
+        }
+    }
     let result = state
         .db
         .update_conversation_settings(&conversation_id, req)
         .await
-        .map_err(|e| e.to_string());
+        .map_err(AppError::from);
     info!(
         "[conversation_settings] update_conversation_settings result: {:?}",
         result
@@ -46,11 +59,60 @@ pub async fn update_conversation_settings(
     result
 }
 
+/// Use custom parameters for this conversation. They apply to every request unless
+/// the request brings its own overrides, and take precedence over the assistant preset.
+#[tauri::command]
+pub async fn set_conversation_parameter_overrides(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    overrides: ModelParameterOverrides,
+) -> Result<ConversationSettings, AppError> {
+    overrides.validate().map_err(AppError::validation)?;
+    info!(
+        "[conversation_settings] set_conversation_parameter_overrides: {}, {:?}",
+        conversation_id, overrides
+    );
+    let req = UpdateConversationSettingsRequest {
+        use_provider_defaults: Some(false),
+        use_custom_parameters: Some(true),
+        parameter_overrides: Some(overrides),
+        selected_preset_id: Some(None),
+        ..Default::default()
+    };
+    state
+        .db
+        .update_conversation_settings(&conversation_id, req)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Drop the conversation's custom parameters, falling back to the assistant preset
+#[tauri::command]
+pub async fn clear_conversation_parameter_overrides(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> Result<ConversationSettings, AppError> {
+    info!(
+        "[conversation_settings] clear_conversation_parameter_overrides: {}",
+        conversation_id
+    );
+    let req = UpdateConversationSettingsRequest {
+        use_custom_parameters: Some(false),
+        parameter_overrides: Some(ModelParameterOverrides::default()),
+        ..Default::default()
+    };
+    state
+        .db
+        .update_conversation_settings(&conversation_id, req)
+        .await
+        .map_err(AppError::from)
+}
+
 #[tauri::command]
 pub async fn reset_conversation_tools_to_global(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<ConversationSettings, String> {
+) -> Result<ConversationSettings, AppError> {
     info!(
         "[conversation_settings] reset_conversation_tools_to_global called: {}",
         conversation_id
@@ -59,17 +121,17 @@ pub async fn reset_conversation_tools_to_global(
         .db
         .reset_tools_and_skills_to_global(&conversation_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn delete_conversation_settings(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state
         .db
         .delete_conversation_settings(&conversation_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
@@ -0,0 +1,182 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::{
+    Conversation, ConversationTemplate, ConversationTemplateInput, CreateConversationRequest,
+    render_title,
+};
+use chrono::Local;
+use tauri::{Manager, State};
+
+#[tauri::command]
+pub async fn list_conversation_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<ConversationTemplate>, AppError> {
+    state
+        .db
+        .list_conversation_templates()
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn create_conversation_template(
+    state: State<'_, AppState>,
+    input: ConversationTemplateInput,
+) -> Result<ConversationTemplate, AppError> {
+    let input = prepare_input(&state, input).await?;
+    state
+        .db
+        .create_conversation_template(input)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn update_conversation_template(
+    state: State<'_, AppState>,
+    id: String,
+    input: ConversationTemplateInput,
+) -> Result<ConversationTemplate, AppError> {
+    if state.db.get_conversation_template(&id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Conversation template not found: {}",
+            id
+        )));
+    }
+    let input = prepare_input(&state, input).await?;
+    state
+        .db
+        .update_conversation_template(&id, input)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Delete a template. Conversations started from it are kept.
+#[tauri::command]
+pub async fn delete_conversation_template(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), AppError> {
+    state
+        .db
+        .delete_conversation_template(&id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Start a conversation from a template: the title pattern is filled in, the
+/// template's assistant and settings are applied, and the opening message is sent
+/// when the template says so (otherwise the frontend offers it as a draft).
+#[tauri::command]
+pub async fn create_conversation_from_template(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    template_id: String,
+) -> Result<Conversation, AppError> {
+    let template = state
+        .db
+        .get_conversation_template(&template_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(format!("Conversation template not found: {}", template_id))
+        })?;
+    if let Some(id) = &template.assistant_id
+        && state.db.get_assistant(id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!("Assistant not found: {}", id)));
+    }
+
+    let title = render_title(&template.title_pattern, Local::now());
+    let conversation = state
+        .db
+        .create_conversation(CreateConversationRequest {
+            title: if title.is_empty() {
+                template.name.clone()
+            } else {
+                title
+            },
+        })
+        .await?;
+
+    let mut settings = template.settings.clone();
+    if let Some(id) = &template.assistant_id {
+        settings.selected_assistant_id = Some(Some(id.clone()));
+        settings.selected_model_id = Some(None);
+    }
+    state
+        .db
+        .update_conversation_settings(&conversation.id, settings)
+        .await?;
+    tracing::info!(
+        "📋 [templates] Started conversation {} from \"{}\"",
+        conversation.id,
+        template.name
+    );
+
+    if template.send_opening_message
+        && let Some(message) = template
+            .opening_message
+            .filter(|message| !message.trim().is_empty())
+    {
+        // Provider and model come from the binding just stored in the settings
+        super::chat::send_message(
+            app.state::<AppState>(),
+            app.clone(),
+            conversation.id.clone(),
+            message,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    }
+
+    state
+        .db
+        .get_conversation(&conversation.id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Conversation not found: {}", conversation.id)))
+}
+
+/// Validate an input and drop blank optional values
+async fn prepare_input(
+    state: &AppState,
+    mut input: ConversationTemplateInput,
+) -> Result<ConversationTemplateInput, AppError> {
+    input.validate().map_err(AppError::validation)?;
+    input.assistant_id = input.assistant_id.filter(|id| !id.trim().is_empty());
+    input.opening_message = input
+        .opening_message
+        .filter(|message| !message.trim().is_empty());
+
+    if let Some(id) = &input.assistant_id
+        && state.db.get_assistant(id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!("Assistant not found: {}", id)));
+    }
+    if let Some(Some(model_id)) = &input.settings.selected_model_id
+        && state.db.get_model(model_id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Model not found: {}",
+            model_id
+        )));
+    }
+    Ok(input)
+}
@@ -1,7 +1,8 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::models::{
     Conversation, ConversationParticipant, CreateConversationParticipantRequest,
-    CreateConversationRequest, ParticipantSummary,
+    CreateConversationRequest, ParticipantSummary, UpdateConversationParticipantRequest,
 };
 use tauri::State;
 
@@ -9,33 +10,28 @@ use tauri::State;
 pub async fn create_conversation(
     state: State<'_, AppState>,
     req: CreateConversationRequest,
-) -> Result<Conversation, String> {
+) -> Result<Conversation, AppError> {
     state
         .db
         .create_conversation(req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_conversation(
     state: State<'_, AppState>,
     id: String,
-) -> Result<Option<Conversation>, String> {
-    state
-        .db
-        .get_conversation(&id)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<Option<Conversation>, AppError> {
+    if let Some(conversation) = state.private_conversations.get(&id) {
+        return Ok(Some(conversation));
+    }
+    state.db.get_conversation(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn list_conversations(state: State<'_, AppState>) -> Result<Vec<Conversation>, String> {
-    state
-        .db
-        .list_conversations()
-        .await
-        .map_err(|e| e.to_string())
+pub async fn list_conversations(state: State<'_, AppState>) -> Result<Vec<Conversation>, AppError> {
+    state.db.list_conversations().await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -43,29 +39,63 @@ pub async fn update_conversation(
     state: State<'_, AppState>,
     id: String,
     title: String,
-) -> Result<Conversation, String> {
+) -> Result<Conversation, AppError> {
     state
         .db
         .update_conversation(&id, &title)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Archive a conversation. Automations triggered by archiving run once per archive.
+#[tauri::command]
+pub async fn archive_conversation(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Conversation, AppError> {
+    if state.db.set_conversation_archived(&id, true).await? {
+        crate::automations::on_conversation_archived(&state, &id).await;
+    }
+    get_stored_conversation(&state, &id).await
 }
 
 #[tauri::command]
-pub async fn delete_conversation(state: State<'_, AppState>, id: String) -> Result<(), String> {
+pub async fn unarchive_conversation(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Conversation, AppError> {
+    state.db.set_conversation_archived(&id, false).await?;
+    get_stored_conversation(&state, &id).await
+}
+
+async fn get_stored_conversation(state: &AppState, id: &str) -> Result<Conversation, AppError> {
+    state
+        .db
+        .get_conversation(id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Conversation not found: {}", id)))
+}
+
+#[tauri::command]
+pub async fn delete_conversation(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
     // Cancel any active generation for this conversation
     if let Some(cancel_token) = state.generation_tasks.write().await.remove(&id) {
         cancel_token.cancel();
     }
+    super::chat::auxiliary::cancel_auxiliary(&state, &id).await;
 
     // Kill any persistent bash session for this conversation
     state.bash_session_manager.remove(&id);
 
+    if state.private_conversations.remove(&id) {
+        return Ok(());
+    }
+
     state
         .db
         .delete_conversation(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 // Conversation Participant commands
@@ -74,24 +104,24 @@ pub async fn delete_conversation(state: State<'_, AppState>, id: String) -> Resu
 pub async fn add_conversation_participant(
     state: State<'_, AppState>,
     req: CreateConversationParticipantRequest,
-) -> Result<ConversationParticipant, String> {
+) -> Result<ConversationParticipant, AppError> {
     state
         .db
         .add_conversation_participant(req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn list_conversation_participants(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<Vec<ConversationParticipant>, String> {
+) -> Result<Vec<ConversationParticipant>, AppError> {
     state
         .db
         .list_conversation_participants(&conversation_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -99,24 +129,58 @@ pub async fn get_conversation_participant_summary(
     state: State<'_, AppState>,
     conversation_id: String,
     current_user_id: String,
-) -> Result<Vec<ParticipantSummary>, String> {
+) -> Result<Vec<ParticipantSummary>, AppError> {
     state
         .db
         .get_conversation_participant_summary(&conversation_id, &current_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Update a participant's display name, role, or color and nickname
+#[tauri::command]
+pub async fn update_conversation_participant(
+    state: State<'_, AppState>,
+    id: String,
+    req: UpdateConversationParticipantRequest,
+) -> Result<ConversationParticipant, AppError> {
+    if let Some(metadata) = &req.metadata
+        && !metadata.has_valid_color()
+    {
+        return Err(AppError::validation(
+            "Participant color must be a hex color like #3b82f6",
+        ));
+    }
+    state
+        .db
+        .update_conversation_participant(&id, req)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Remove a participant from the conversation without deleting it
+#[tauri::command]
+pub async fn deactivate_participant(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ConversationParticipant, AppError> {
+    state
+        .db
+        .deactivate_conversation_participant(&id)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn remove_conversation_participant(
     state: State<'_, AppState>,
     id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state
         .db
         .remove_conversation_participant(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -124,10 +188,32 @@ pub async fn fork_conversation(
     state: State<'_, AppState>,
     conversation_id: String,
     message_id: String,
-) -> Result<Conversation, String> {
+) -> Result<Conversation, AppError> {
     state
         .db
         .fork_conversation(&conversation_id, &message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Start a new conversation with a copy of a message as its opening user message.
+/// The pages fetched for the message come along unless `include_contexts` is false.
+/// The new conversation's `metadata` links back to the original message.
+#[tauri::command]
+pub async fn send_to_new_conversation(
+    state: State<'_, AppState>,
+    message_id: String,
+    include_contexts: Option<bool>,
+) -> Result<Conversation, AppError> {
+    if state.db.get_message(&message_id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Message not found: {}",
+            message_id
+        )));
+    }
+    state
+        .db
+        .start_conversation_from_message(&message_id, include_contexts.unwrap_or(true))
+        .await
+        .map_err(AppError::from)
 }
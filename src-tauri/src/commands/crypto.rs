@@ -1,18 +1,19 @@
 use crate::crypto;
+use crate::error::AppError;
 
 #[tauri::command]
-pub async fn generate_keypair() -> Result<crypto::GeneratedKeyPair, String> {
-    crypto::generate_keypair().map_err(|e| e.to_string())
+pub async fn generate_keypair() -> Result<crypto::GeneratedKeyPair, AppError> {
+    crypto::generate_keypair().map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn export_keypair(public_key: String, private_key: String) -> Result<String, String> {
-    crypto::export_keypair(&public_key, &private_key).map_err(|e| e.to_string())
+pub async fn export_keypair(public_key: String, private_key: String) -> Result<String, AppError> {
+    crypto::export_keypair(&public_key, &private_key).map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn import_keypair(json: String) -> Result<crypto::GeneratedKeyPair, String> {
-    crypto::import_keypair(&json).map_err(|e| e.to_string())
+pub async fn import_keypair(json: String) -> Result<crypto::GeneratedKeyPair, AppError> {
+    crypto::import_keypair(&json).map_err(AppError::from)
 }
 
 /// Check if the OS keychain is available for secure storage.
@@ -0,0 +1,304 @@
+//! Health checks users can attach to bug reports
+//!
+//! `run_diagnostics` checks the database, attachment storage, keychain, headless Chrome,
+//! Ollama and every enabled provider, and returns one entry per check. Failures are
+//! reported in the entries rather than as a command error, so one broken dependency
+//! doesn't hide the others.
+
+use super::AppState;
+use super::model_fetch::{PING_TIMEOUT_SECS, classify_probe_error, probe_model};
+use crate::crash_report::{self, CrashReport};
+use crate::error::AppError;
+use crate::llm::common::create_http_client;
+use crate::llm::ollama;
+use crate::models::Provider;
+use crate::web_fetch::{self, ChromeStatus};
+use crate::{crypto, storage};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+const OLLAMA_TIMEOUT: Duration = Duration::from_secs(5);
+const STORAGE_PROBE_FILE: &str = ".diagnostics-probe";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but degraded or optional and unavailable
+    Warning,
+    Error,
+    /// Nothing to check (e.g. a provider without models)
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub latency_ms: Option<u64>,
+}
+
+impl DiagnosticCheck {
+    fn new(name: impl Into<String>, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            message: message.into(),
+            latency_ms: None,
+        }
+    }
+
+    fn with_latency(mut self, started: Instant) -> Self {
+        self.latency_ms = Some(started.elapsed().as_millis() as u64);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsReport {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub generated_at: String,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+#[tauri::command]
+pub async fn run_diagnostics(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<DiagnosticsReport, AppError> {
+    tracing::info!("🩺 [diagnostics] Running diagnostics");
+
+    let providers = state.db.list_providers().await.unwrap_or_default();
+    let enabled: Vec<Provider> = providers.into_iter().filter(|p| p.is_enabled).collect();
+
+    let mut checks = vec![
+        check_database(&state).await,
+        check_storage(&app),
+        check_keychain(),
+        check_headless_chrome().await,
+        check_ollama(&enabled).await,
+    ];
+    checks
+        .extend(futures::future::join_all(enabled.iter().map(|p| check_provider(&state, p))).await);
+
+    Ok(DiagnosticsReport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        checks,
+    })
+}
+
+async fn check_database(state: &AppState) -> DiagnosticCheck {
+    let started = Instant::now();
+    let check = match state.db.check_health().await {
+        Ok(version) => DiagnosticCheck::new(
+            "database",
+            CheckStatus::Ok,
+            format!("Integrity check passed (schema v{})", version),
+        ),
+        Err(e) => DiagnosticCheck::new("database", CheckStatus::Error, e.to_string()),
+    };
+    check.with_latency(started)
+}
+
+/// Write, read back and remove a file in the attachments directory
+fn check_storage(app: &tauri::AppHandle) -> DiagnosticCheck {
+    let result = (|| -> anyhow::Result<std::path::PathBuf> {
+        let dir = storage::get_attachments_dir(app)?;
+        std::fs::create_dir_all(&dir)?;
+        let probe = dir.join(STORAGE_PROBE_FILE);
+        std::fs::write(&probe, b"ok")?;
+        let read_back = std::fs::read(&probe);
+        std::fs::remove_file(&probe)?;
+        if read_back? != b"ok" {
+            anyhow::bail!("Probe file content mismatch");
+        }
+        Ok(dir)
+    })();
+
+    match result {
+        Ok(dir) => DiagnosticCheck::new(
+            "storage",
+            CheckStatus::Ok,
+            format!("{} is writable", dir.display()),
+        ),
+        Err(e) => DiagnosticCheck::new("storage", CheckStatus::Error, e.to_string()),
+    }
+}
+
+fn check_keychain() -> DiagnosticCheck {
+    if crypto::is_keychain_available() {
+        DiagnosticCheck::new(
+            "keychain",
+            CheckStatus::Ok,
+            "Encryption key stored in the OS keychain",
+        )
+    } else {
+        DiagnosticCheck::new(
+            "keychain",
+            CheckStatus::Warning,
+            "OS keychain unavailable; using a temporary key, so API keys must be re-entered after restart",
+        )
+    }
+}
+
+/// Whether web search and the fetch fallback can launch a headless Chrome
+#[tauri::command]
+pub async fn get_chrome_status() -> Result<ChromeStatus, AppError> {
+    tokio::task::spawn_blocking(web_fetch::chrome_status)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Download the pinned Chromium revision for the headless modules, for systems
+/// without Chrome. Returns the status afterwards.
+#[tauri::command]
+pub async fn download_chromium() -> Result<ChromeStatus, AppError> {
+    tokio::task::spawn_blocking(|| {
+        web_fetch::download_chromium()?;
+        Ok::<_, anyhow::Error>(web_fetch::chrome_status())
+    })
+    .await
+    .map_err(AppError::from)?
+    .map_err(AppError::from)
+}
+
+/// Headless Chrome is only needed for web search and the fetch fallback
+async fn check_headless_chrome() -> DiagnosticCheck {
+    match tokio::task::spawn_blocking(web_fetch::chrome_status).await {
+        Ok(ChromeStatus {
+            path: Some(path),
+            downloaded,
+            ..
+        }) => DiagnosticCheck::new(
+            "headless_chrome",
+            CheckStatus::Ok,
+            if downloaded {
+                format!("Using downloaded Chromium at {}", path)
+            } else {
+                format!("Found at {}", path)
+            },
+        ),
+        Ok(status) => DiagnosticCheck::new(
+            "headless_chrome",
+            CheckStatus::Warning,
+            status.error.unwrap_or_default(),
+        ),
+        Err(e) => DiagnosticCheck::new("headless_chrome", CheckStatus::Error, e.to_string()),
+    }
+}
+
+/// Reach the configured Ollama server, or the default local one.
+/// Unreachable is only an error when an Ollama provider is enabled.
+async fn check_ollama(enabled: &[Provider]) -> DiagnosticCheck {
+    let provider = enabled.iter().find(|p| p.provider_type == "ollama");
+    let url = ollama::api_url(provider.and_then(|p| p.base_url.as_deref()), "version");
+
+    let started = Instant::now();
+    let response = create_http_client()
+        .get(&url)
+        .timeout(OLLAMA_TIMEOUT)
+        .send()
+        .await;
+    let unreachable_status = if provider.is_some() {
+        CheckStatus::Error
+    } else {
+        CheckStatus::Warning
+    };
+
+    let check = match response {
+        Ok(response) if response.status().is_success() => {
+            let version = response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|v| v["version"].as_str().map(str::to_string))
+                .unwrap_or_else(|| "unknown".to_string());
+            DiagnosticCheck::new(
+                "ollama",
+                CheckStatus::Ok,
+                format!("Ollama {} reachable at {}", version, url),
+            )
+        }
+        Ok(response) => DiagnosticCheck::new(
+            "ollama",
+            unreachable_status,
+            format!("[HTTP {}] from {}", response.status().as_u16(), url),
+        ),
+        Err(e) => DiagnosticCheck::new(
+            "ollama",
+            unreachable_status,
+            format!("Not reachable at {}: {}", url, e),
+        ),
+    };
+    check.with_latency(started)
+}
+
+/// Ping the provider's first visible model with a 1-token request
+async fn check_provider(state: &AppState, provider: &Provider) -> DiagnosticCheck {
+    let name = format!("provider:{}", provider.name);
+    let model = match state.db.list_models(true).await {
+        Ok(models) => models.into_iter().find(|m| m.provider_id == provider.id),
+        Err(e) => return DiagnosticCheck::new(name, CheckStatus::Error, e.to_string()),
+    };
+    let Some(model) = model else {
+        return DiagnosticCheck::new(name, CheckStatus::Skipped, "No models configured");
+    };
+
+    let result = probe_model(
+        &provider.provider_type,
+        &model.model_id,
+        provider.api_key.as_deref(),
+        provider.base_url.as_deref(),
+        provider.api_style.as_deref(),
+        PING_TIMEOUT_SECS,
+    )
+    .await;
+
+    match result {
+        Ok(result) => {
+            let check = if result.success {
+                DiagnosticCheck::new(
+                    name,
+                    CheckStatus::Ok,
+                    format!("{} responded", model.model_id),
+                )
+            } else {
+                let error = result.error.unwrap_or_default();
+                DiagnosticCheck::new(
+                    name,
+                    CheckStatus::Error,
+                    format!(
+                        "{} failed ({}): {}",
+                        model.model_id,
+                        classify_probe_error(&error),
+                        error
+                    ),
+                )
+            };
+            DiagnosticCheck {
+                latency_ms: Some(result.latency_ms),
+                ..check
+            }
+        }
+        Err(e) => DiagnosticCheck::new(name, CheckStatus::Error, e),
+    }
+}
+
+/// The latest crash report not yet dismissed, offered to the user after a crash
+#[tauri::command]
+pub async fn get_last_crash_report() -> Result<Option<CrashReport>, AppError> {
+    crash_report::last_unseen_report().map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn dismiss_crash_report(id: String) -> Result<(), AppError> {
+    tracing::info!("💥 [crash] Dismissing crash report {}", id);
+    crash_report::dismiss(&id).map_err(AppError::from)
+}
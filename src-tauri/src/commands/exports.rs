@@ -0,0 +1,101 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::exporters::{self, ExportFormat, ExportOptions, ExportedConversation};
+use std::path::PathBuf;
+use tauri::State;
+
+/// Export a conversation to `destination_path` as Markdown, HTML or JSON. Thinking, tool
+/// calls, search decisions and sources are included unless turned off in `options`.
+/// The format's extension is added when missing. Returns the path written.
+#[tauri::command]
+pub async fn export_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    format: ExportFormat,
+    destination_path: String,
+    options: Option<ExportOptions>,
+) -> Result<String, AppError> {
+    let options = options.unwrap_or_default();
+    let conversation = exporters::collect(&state.db, &conversation_id, options).await?;
+    write_export(&conversation, format, destination_path).await
+}
+
+/// Export only the given messages of a conversation, e.g. a final answer and its
+/// sources. Messages are written in conversation order whatever the order of
+/// `message_ids`; they must all belong to the same conversation.
+#[tauri::command]
+pub async fn export_messages(
+    state: State<'_, AppState>,
+    message_ids: Vec<String>,
+    format: ExportFormat,
+    destination_path: String,
+    options: Option<ExportOptions>,
+) -> Result<String, AppError> {
+    if message_ids.is_empty() {
+        return Err(AppError::validation(
+            "Select at least one message to export",
+        ));
+    }
+    let options = options.unwrap_or_default();
+    let conversation = exporters::collect_selected(&state.db, &message_ids, options).await?;
+    write_export(&conversation, format, destination_path).await
+}
+
+/// Publish a conversation as a static site in `destination_dir` (created when missing):
+/// `index.html` plus assets, ready for GitHub Pages. Files of an earlier publish in the
+/// same directory are overwritten. Returns the directory written.
+#[tauri::command]
+pub async fn publish_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    destination_dir: String,
+    options: Option<ExportOptions>,
+) -> Result<String, AppError> {
+    let destination = PathBuf::from(destination_dir);
+    if destination.exists() && !destination.is_dir() {
+        return Err(AppError::validation(format!(
+            "Not a directory: {}",
+            destination.display()
+        )));
+    }
+    let options = options.unwrap_or_default();
+    let conversation = exporters::collect(&state.db, &conversation_id, options).await?;
+
+    for file in exporters::site::render(&conversation) {
+        let path = destination.join(file.path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, file.content).await?;
+    }
+
+    tracing::info!(
+        "📤 [export] Published conversation {} ({} message(s)) to {}",
+        conversation.id,
+        conversation.messages.len(),
+        destination.display()
+    );
+    Ok(destination.to_string_lossy().to_string())
+}
+
+async fn write_export(
+    conversation: &ExportedConversation,
+    format: ExportFormat,
+    destination_path: String,
+) -> Result<String, AppError> {
+    let rendered = exporters::render(conversation, format)?;
+
+    let mut destination = PathBuf::from(destination_path);
+    if destination.extension().is_none() {
+        destination.set_extension(format.extension());
+    }
+    tokio::fs::write(&destination, rendered).await?;
+
+    tracing::info!(
+        "📤 [export] Exported conversation {} ({} message(s)) to {}",
+        conversation.id,
+        conversation.messages.len(),
+        destination.display()
+    );
+    Ok(destination.to_string_lossy().to_string())
+}
@@ -0,0 +1,219 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::importers::{self, ImportBundle, ImportSource, ImportedRole};
+use crate::models::{
+    CreateAssistantRequest, CreateConversationRequest, CreateModelRequest, CreateProviderRequest,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub source: Option<ImportSource>,
+    pub providers: usize,
+    pub models: usize,
+    pub assistants: usize,
+    pub conversations: usize,
+    pub messages: usize,
+    /// Assistants skipped because no model was available to attach them to
+    pub skipped_assistants: usize,
+}
+
+/// Detect which app an export file or data folder came from
+#[tauri::command]
+pub async fn detect_import_source(path: String) -> Result<ImportSource, AppError> {
+    importers::detect_source(&PathBuf::from(path)).map_err(AppError::from)
+}
+
+/// Import providers, assistants and conversations from another chat app.
+/// `source` is detected from the path when omitted.
+#[tauri::command]
+pub async fn import_chat_data(
+    state: State<'_, AppState>,
+    path: String,
+    source: Option<String>,
+) -> Result<ImportSummary, AppError> {
+    let path = PathBuf::from(path);
+    let source = match source {
+        Some(s) => {
+            ImportSource::parse(&s).ok_or_else(|| format!("Unknown import source: {}", s))?
+        }
+        None => importers::detect_source(&path)?,
+    };
+
+    let bundle = tokio::task::spawn_blocking(move || importers::load(&path, source)).await??;
+
+    let mut summary = write_bundle(&state, bundle).await?;
+    summary.source = Some(source);
+
+    tracing::info!(
+        "📥 [import] Imported {} provider(s), {} model(s), {} assistant(s), {} conversation(s), {} message(s)",
+        summary.providers,
+        summary.models,
+        summary.assistants,
+        summary.conversations,
+        summary.messages
+    );
+    Ok(summary)
+}
+
+async fn write_bundle(state: &AppState, bundle: ImportBundle) -> anyhow::Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+
+    // Providers are matched on name + type so importing twice doesn't duplicate them
+    let existing_providers = state.db.list_providers().await?;
+    let mut provider_ids: HashMap<String, String> = HashMap::new();
+    for provider in &bundle.providers {
+        let id = match existing_providers
+            .iter()
+            .find(|p| p.name == provider.name && p.provider_type == provider.provider_type)
+        {
+            Some(existing) => existing.id.clone(),
+            None => {
+                let created = state
+                    .db
+                    .create_provider(CreateProviderRequest {
+                        name: provider.name.clone(),
+                        provider_type: provider.provider_type.clone(),
+                        api_key: provider.api_key.clone(),
+                        base_url: provider.base_url.clone(),
+                        api_style: None,
+                        description: Some("Imported".to_string()),
+                        is_enabled: Some(true),
+                    })
+                    .await?;
+                summary.providers += 1;
+                created.id
+            }
+        };
+        provider_ids.insert(provider.name.clone(), id);
+    }
+
+    let mut models = state.db.list_all_models().await?;
+    for provider in &bundle.providers {
+        let provider_id = &provider_ids[&provider.name];
+        for model_id in &provider.models {
+            if models
+                .iter()
+                .any(|m| &m.provider_id == provider_id && &m.model_id == model_id)
+            {
+                continue;
+            }
+            let created = state
+                .db
+                .create_model(CreateModelRequest {
+                    name: model_id.clone(),
+                    provider_id: provider_id.clone(),
+                    model_id: model_id.clone(),
+                    description: None,
+                    is_starred: None,
+                    input_price: None,
+                    output_price: None,
+                })
+                .await?;
+            models.push(created);
+            summary.models += 1;
+        }
+    }
+
+    // Model row for a model name, preferring the given provider
+    let find_model = |model: &str, provider: Option<&str>| {
+        let provider_id = provider.and_then(|p| provider_ids.get(p));
+        models
+            .iter()
+            .filter(|m| !m.is_deleted && m.model_id == model)
+            .max_by_key(|m| Some(&m.provider_id) == provider_id)
+            .map(|m| m.id.clone())
+    };
+
+    let existing_assistants = state.db.list_assistants().await?;
+    let fallback_model = models.iter().find(|m| !m.is_deleted).map(|m| m.id.clone());
+    for assistant in &bundle.assistants {
+        if existing_assistants.iter().any(|a| a.name == assistant.name) {
+            continue;
+        }
+        let model_id = assistant
+            .model
+            .as_deref()
+            .and_then(|m| find_model(m, assistant.provider.as_deref()))
+            .or_else(|| fallback_model.clone());
+        let Some(model_id) = model_id else {
+            summary.skipped_assistants += 1;
+            continue;
+        };
+        state
+            .db
+            .create_assistant(CreateAssistantRequest {
+                name: assistant.name.clone(),
+                role: None,
+                description: assistant.description.clone(),
+                system_prompt: assistant.system_prompt.clone(),
+                user_prompt: None,
+                model_id,
+                model_parameter_preset_id: None,
+                tool_ids: None,
+                skill_ids: None,
+                avatar_type: None,
+                avatar_bg: None,
+                avatar_text: None,
+                avatar_image_path: None,
+                avatar_image_url: None,
+                group_name: Some("Imported".to_string()),
+                is_starred: None,
+            })
+            .await?;
+        summary.assistants += 1;
+    }
+
+    for conversation in bundle.conversations {
+        let created = state
+            .db
+            .create_conversation(CreateConversationRequest {
+                title: conversation.title,
+            })
+            .await?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let created_at = conversation.created_at.unwrap_or_else(|| now.clone());
+
+        // Messages without a timestamp inherit the previous one so the order holds
+        let mut last_timestamp = created_at.clone();
+        for message in &conversation.messages {
+            let timestamp = message
+                .created_at
+                .clone()
+                .filter(|t| *t >= last_timestamp)
+                .unwrap_or_else(|| last_timestamp.clone());
+            let (sender_type, sender_id) = match message.role {
+                ImportedRole::User => ("user", None),
+                ImportedRole::Assistant => {
+                    match message.model.as_deref().and_then(|m| find_model(m, None)) {
+                        Some(model_id) => ("model", Some(model_id)),
+                        None => ("assistant", None),
+                    }
+                }
+            };
+            state
+                .db
+                .insert_imported_message(
+                    &created.id,
+                    sender_type,
+                    sender_id.as_deref(),
+                    &message.content,
+                    &timestamp,
+                )
+                .await?;
+            last_timestamp = timestamp;
+            summary.messages += 1;
+        }
+
+        state
+            .db
+            .set_conversation_timestamps(&created.id, &created_at, &last_timestamp)
+            .await?;
+        summary.conversations += 1;
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,20 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::{Job, JobStatus};
+use tauri::State;
+
+const DEFAULT_JOB_LIMIT: i64 = 100;
+
+/// Recent background jobs, newest first
+#[tauri::command]
+pub async fn list_jobs(
+    state: State<'_, AppState>,
+    status: Option<JobStatus>,
+    limit: Option<i64>,
+) -> Result<Vec<Job>, AppError> {
+    state
+        .db
+        .list_jobs(status, limit.unwrap_or(DEFAULT_JOB_LIMIT))
+        .await
+        .map_err(AppError::from)
+}
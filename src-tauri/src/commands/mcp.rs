@@ -1,6 +1,7 @@
 //! MCP server management commands
 
 use super::AppState;
+use crate::error::AppError;
 use crate::mcp::oauth;
 use crate::models::{
     CreateToolRequest, McpAuthType, McpConfig, McpTransportType, OAuthMetadata, Tool,
@@ -118,14 +119,14 @@ pub async fn create_mcp_server(
     endpoint: Option<String>,
     description: Option<String>,
     config: Option<McpServerConfig>,
-) -> Result<Tool, String> {
+) -> Result<Tool, AppError> {
     // Determine transport type and validate
     let (final_endpoint, final_config) = if let Some(cfg) = config {
         let transport = cfg.transport.as_str();
         match transport {
             "stdio" => {
                 if cfg.command.is_none() {
-                    return Err("STDIO transport requires a command".to_string());
+                    return Err(AppError::validation("STDIO transport requires a command"));
                 }
                 let mcp_config: McpConfig = cfg.into();
                 tracing::info!(
@@ -139,7 +140,7 @@ pub async fn create_mcp_server(
                         .unwrap_or_else(|| "none".to_string()),
                     mcp_config.args,
                 );
-                let json = mcp_config.to_json().map_err(|e| e.to_string())?;
+                let json = mcp_config.to_json()?;
                 tracing::debug!("  Stored config JSON: {}", json);
                 (None, Some(json))
             }
@@ -148,10 +149,7 @@ pub async fn create_mcp_server(
                 tracing::info!("🔌 Creating HTTP MCP server: {} at {}", name, ep);
                 let mut mcp_config = McpConfig::http();
                 mcp_config.headers = cfg.headers;
-                (
-                    Some(ep),
-                    Some(mcp_config.to_json().map_err(|e| e.to_string())?),
-                )
+                (Some(ep), Some(mcp_config.to_json()?))
             }
         }
     } else {
@@ -159,10 +157,7 @@ pub async fn create_mcp_server(
         let ep = endpoint.ok_or("HTTP transport requires an endpoint URL")?;
         tracing::info!("🔌 Creating HTTP MCP server: {} at {}", name, ep);
         let mcp_config = McpConfig::http();
-        (
-            Some(ep),
-            Some(mcp_config.to_json().map_err(|e| e.to_string())?),
-        )
+        (Some(ep), Some(mcp_config.to_json()?))
     };
 
     let req = CreateToolRequest {
@@ -174,22 +169,22 @@ pub async fn create_mcp_server(
         is_enabled: Some(true),
     };
 
-    state.db.create_tool(req).await.map_err(|e| e.to_string())
+    state.db.create_tool(req).await.map_err(AppError::from)
 }
 
 /// List all MCP servers and builtin tools
 /// Returns both MCP servers (type='mcp') and builtin tools (type='builtin')
 /// for use in the tools selection dialog
 #[tauri::command]
-pub async fn list_mcp_servers(state: State<'_, AppState>) -> Result<Vec<Tool>, String> {
+pub async fn list_mcp_servers(state: State<'_, AppState>) -> Result<Vec<Tool>, AppError> {
     // Return all tools (MCP + builtin) so the UI can display both
-    state.db.list_tools().await.map_err(|e| e.to_string())
+    state.db.list_tools().await.map_err(AppError::from)
 }
 
 /// Get a specific MCP server
 #[tauri::command]
-pub async fn get_mcp_server(state: State<'_, AppState>, id: String) -> Result<Tool, String> {
-    state.db.get_tool(&id).await.map_err(|e| e.to_string())
+pub async fn get_mcp_server(state: State<'_, AppState>, id: String) -> Result<Tool, AppError> {
+    state.db.get_tool(&id).await.map_err(AppError::from)
 }
 
 /// Update an MCP server configuration
@@ -202,12 +197,12 @@ pub async fn update_mcp_server(
     description: Option<String>,
     config: Option<McpServerConfig>,
     is_enabled: Option<bool>,
-) -> Result<Tool, String> {
+) -> Result<Tool, AppError> {
     // Disconnect existing connection
     state.mcp_manager.disconnect(&id).await;
 
     // Preserve existing OAuth metadata if not changing auth
-    let existing_tool = state.db.get_tool(&id).await.map_err(|e| e.to_string())?;
+    let existing_tool = state.db.get_tool(&id).await?;
     let existing_config = existing_tool.parse_mcp_config();
 
     // Determine transport type and validate
@@ -216,7 +211,7 @@ pub async fn update_mcp_server(
         match transport {
             "stdio" => {
                 if cfg.command.is_none() {
-                    return Err("STDIO transport requires a command".to_string());
+                    return Err(AppError::validation("STDIO transport requires a command"));
                 }
                 let mcp_config: McpConfig = cfg.into();
                 tracing::info!(
@@ -230,7 +225,7 @@ pub async fn update_mcp_server(
                         .unwrap_or_else(|| "none".to_string()),
                     mcp_config.args,
                 );
-                let json = mcp_config.to_json().map_err(|e| e.to_string())?;
+                let json = mcp_config.to_json()?;
                 tracing::debug!("  Stored config JSON: {}", json);
                 (None, Some(json))
             }
@@ -244,10 +239,7 @@ pub async fn update_mcp_server(
                     mcp_config.auth_type = ec.auth_type;
                     mcp_config.oauth_metadata = ec.oauth_metadata.clone();
                 }
-                (
-                    Some(ep),
-                    Some(mcp_config.to_json().map_err(|e| e.to_string())?),
-                )
+                (Some(ep), Some(mcp_config.to_json()?))
             }
         }
     } else {
@@ -255,10 +247,7 @@ pub async fn update_mcp_server(
         let ep = endpoint.ok_or("HTTP transport requires an endpoint URL")?;
         tracing::info!("📝 Updating HTTP MCP server: {} at {}", name, ep);
         let mcp_config = McpConfig::http();
-        (
-            Some(ep),
-            Some(mcp_config.to_json().map_err(|e| e.to_string())?),
-        )
+        (Some(ep), Some(mcp_config.to_json()?))
     };
 
     let req = CreateToolRequest {
@@ -270,32 +259,28 @@ pub async fn update_mcp_server(
         is_enabled,
     };
 
-    state
-        .db
-        .update_tool(&id, req)
-        .await
-        .map_err(|e| e.to_string())
+    state.db.update_tool(&id, req).await.map_err(AppError::from)
 }
 
 /// Delete an MCP server configuration
 #[tauri::command]
-pub async fn delete_mcp_server(state: State<'_, AppState>, id: String) -> Result<(), String> {
+pub async fn delete_mcp_server(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
     tracing::info!("🗑️ Deleting MCP server: {}", id);
 
     // Disconnect if connected
     state.mcp_manager.disconnect(&id).await;
 
-    state.db.delete_tool(&id).await.map_err(|e| e.to_string())
+    state.db.delete_tool(&id).await.map_err(AppError::from)
 }
 
 /// Toggle MCP server enabled status
 #[tauri::command]
-pub async fn toggle_mcp_server(state: State<'_, AppState>, id: String) -> Result<Tool, String> {
+pub async fn toggle_mcp_server(state: State<'_, AppState>, id: String) -> Result<Tool, AppError> {
     state
         .db
         .toggle_tool_enabled(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Set all tools of a given type to enabled or disabled
@@ -304,12 +289,12 @@ pub async fn set_all_tools_enabled(
     state: State<'_, AppState>,
     tool_type: String,
     enabled: bool,
-) -> Result<Vec<Tool>, String> {
+) -> Result<Vec<Tool>, AppError> {
     state
         .db
         .set_all_tools_enabled(&tool_type, enabled)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Test connection to an MCP server via HTTP endpoint
@@ -317,7 +302,7 @@ pub async fn set_all_tools_enabled(
 pub async fn test_mcp_connection(
     state: State<'_, AppState>,
     endpoint: String,
-) -> Result<Vec<McpToolInfo>, String> {
+) -> Result<Vec<McpToolInfo>, AppError> {
     tracing::info!("🧪 Testing HTTP MCP connection to: {}", endpoint);
 
     let tools = state
@@ -340,7 +325,7 @@ pub async fn test_mcp_connection(
 pub async fn test_mcp_stdio_connection(
     state: State<'_, AppState>,
     config: McpServerConfig,
-) -> Result<Vec<McpToolInfo>, String> {
+) -> Result<Vec<McpToolInfo>, AppError> {
     tracing::info!(
         "🧪 Testing STDIO MCP connection: {:?} (env_vars: {})",
         config.command,
@@ -352,7 +337,7 @@ pub async fn test_mcp_stdio_connection(
     );
 
     if config.command.is_none() {
-        return Err("STDIO transport requires a command".to_string());
+        return Err(AppError::validation("STDIO transport requires a command"));
     }
 
     let mcp_config: McpConfig = config.into();
@@ -374,7 +359,7 @@ pub async fn test_mcp_stdio_connection(
 
 /// Disconnect from an MCP server, clearing its cached connection
 #[tauri::command]
-pub async fn disconnect_mcp_server(state: State<'_, AppState>, id: String) -> Result<(), String> {
+pub async fn disconnect_mcp_server(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
     state.mcp_manager.disconnect(&id).await;
     Ok(())
 }
@@ -384,8 +369,8 @@ pub async fn disconnect_mcp_server(state: State<'_, AppState>, id: String) -> Re
 pub async fn list_mcp_server_tools(
     state: State<'_, AppState>,
     id: String,
-) -> Result<Vec<McpToolInfo>, String> {
-    let tool = state.db.get_tool(&id).await.map_err(|e| e.to_string())?;
+) -> Result<Vec<McpToolInfo>, AppError> {
+    let tool = state.db.get_tool(&id).await?;
 
     let connection = state
         .mcp_manager
@@ -408,12 +393,8 @@ pub async fn list_mcp_server_tools(
 pub async fn get_conversation_mcp_servers(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<Vec<Tool>, String> {
-    let settings = state
-        .db
-        .get_conversation_settings(&conversation_id)
-        .await
-        .map_err(|e| e.to_string())?;
+) -> Result<Vec<Tool>, AppError> {
+    let settings = state.db.get_conversation_settings(&conversation_id).await?;
 
     if settings.enabled_mcp_server_ids.is_empty() {
         return Ok(Vec::new());
@@ -423,7 +404,7 @@ pub async fn get_conversation_mcp_servers(
         .db
         .get_tools_by_ids(&settings.enabled_mcp_server_ids)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 // --- OAuth commands ---
@@ -439,15 +420,13 @@ pub struct StartOAuthResult {
 pub async fn start_mcp_oauth(
     state: State<'_, AppState>,
     server_id: String,
-) -> Result<StartOAuthResult, String> {
-    let tool = state
-        .db
-        .get_tool(&server_id)
-        .await
-        .map_err(|e| e.to_string())?;
+) -> Result<StartOAuthResult, AppError> {
+    let tool = state.db.get_tool(&server_id).await?;
     let config = tool.parse_mcp_config().ok_or("Invalid MCP config")?;
     if config.transport != McpTransportType::Http {
-        return Err("OAuth is only supported for HTTP transport".to_string());
+        return Err(AppError::validation(
+            "OAuth is only supported for HTTP transport",
+        ));
     }
     let endpoint = tool
         .endpoint
@@ -455,20 +434,14 @@ pub async fn start_mcp_oauth(
         .ok_or("HTTP MCP server has no endpoint")?;
 
     let http_client = reqwest::Client::new();
-    let discovery = oauth::discover(&http_client, endpoint)
-        .await
-        .map_err(|e| e.to_string())?;
+    let discovery = oauth::discover(&http_client, endpoint).await?;
 
-    let (port, rx) = oauth::run_callback_server()
-        .await
-        .map_err(|e| e.to_string())?;
+    let (port, rx) = oauth::run_callback_server().await?;
 
     let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
 
     let (client_id, client_secret) = if let Some(ref reg_url) = discovery.registration_endpoint {
-        oauth::register_client(&http_client, reg_url, &redirect_uri)
-            .await
-            .map_err(|e| e.to_string())?
+        oauth::register_client(&http_client, reg_url, &redirect_uri).await?
     } else {
         (MCP_OAUTH_CLIENT_ID.to_string(), None)
     };
@@ -478,8 +451,7 @@ pub async fn start_mcp_oauth(
         &redirect_uri,
         &client_id,
         client_secret.as_deref(),
-    )
-    .map_err(|e| e.to_string())?;
+    )?;
 
     let auth_url = auth_state.auth_url.clone();
     let pending = PendingOAuthState {
@@ -505,7 +477,7 @@ pub async fn start_mcp_oauth(
 pub async fn complete_mcp_oauth(
     state: State<'_, AppState>,
     server_id: String,
-) -> Result<Tool, String> {
+) -> Result<Tool, AppError> {
     let pending = {
         let mut map = state.pending_oauth.write().await;
         map.remove(&server_id)
@@ -526,30 +498,24 @@ pub async fn complete_mcp_oauth(
         &state_param,
         pending.auth_state.pkce_verifier,
     )
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     // Store OAuth tokens encrypted in SQLite (not the OS keychain)
     let oauth_json = serde_json::json!({
         "access_token": tokens.access_token,
         "refresh_token": tokens.refresh_token,
     });
-    let encrypted = crate::crypto::encrypt(&oauth_json.to_string()).map_err(|e| e.to_string())?;
+    let encrypted = crate::crypto::encrypt(&oauth_json.to_string())?;
     state
         .db
         .set_tool_auth_token(&server_id, Some(&encrypted))
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     let expires_at = tokens
         .expires_in_secs
         .map(|s| chrono::Utc::now().timestamp() + s as i64);
 
-    let mut tool = state
-        .db
-        .get_tool(&server_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut tool = state.db.get_tool(&server_id).await?;
     let mut config: McpConfig = tool.parse_mcp_config().unwrap_or_else(McpConfig::http);
     config.auth_type = Some(McpAuthType::Oauth);
     config.oauth_metadata = Some(OAuthMetadata {
@@ -559,7 +525,7 @@ pub async fn complete_mcp_oauth(
         token_expires_at: expires_at,
         is_authorized: true,
     });
-    tool.config = Some(config.to_json().map_err(|e| e.to_string())?);
+    tool.config = Some(config.to_json()?);
     state
         .db
         .update_tool(
@@ -573,16 +539,11 @@ pub async fn complete_mcp_oauth(
                 is_enabled: Some(tool.is_enabled),
             },
         )
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     state.mcp_manager.disconnect(&server_id).await;
 
-    state
-        .db
-        .get_tool(&server_id)
-        .await
-        .map_err(|e| e.to_string())
+    state.db.get_tool(&server_id).await.map_err(AppError::from)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -596,12 +557,8 @@ pub struct OAuthStatusResult {
 pub async fn check_mcp_oauth_status(
     state: State<'_, AppState>,
     server_id: String,
-) -> Result<OAuthStatusResult, String> {
-    let tool = state
-        .db
-        .get_tool(&server_id)
-        .await
-        .map_err(|e| e.to_string())?;
+) -> Result<OAuthStatusResult, AppError> {
+    let tool = state.db.get_tool(&server_id).await?;
     let config = tool.parse_mcp_config().unwrap_or_else(McpConfig::http);
     let meta = match &config.oauth_metadata {
         Some(m) => m,
@@ -621,19 +578,14 @@ pub async fn check_mcp_oauth_status(
 
 /// Revoke OAuth tokens for an MCP server
 #[tauri::command]
-pub async fn revoke_mcp_oauth(state: State<'_, AppState>, server_id: String) -> Result<(), String> {
-    state
-        .db
-        .set_tool_auth_token(&server_id, None)
-        .await
-        .map_err(|e| e.to_string())?;
+pub async fn revoke_mcp_oauth(
+    state: State<'_, AppState>,
+    server_id: String,
+) -> Result<(), AppError> {
+    state.db.set_tool_auth_token(&server_id, None).await?;
     state.mcp_manager.disconnect(&server_id).await;
 
-    let mut tool = state
-        .db
-        .get_tool(&server_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut tool = state.db.get_tool(&server_id).await?;
     if let Some(mut config) = tool.parse_mcp_config() {
         if let Some(meta) = config.oauth_metadata.as_ref() {
             config.oauth_metadata = Some(OAuthMetadata {
@@ -644,7 +596,7 @@ pub async fn revoke_mcp_oauth(state: State<'_, AppState>, server_id: String) ->
                 is_authorized: false,
             });
         }
-        tool.config = Some(config.to_json().map_err(|e| e.to_string())?);
+        tool.config = Some(config.to_json()?);
         state
             .db
             .update_tool(
@@ -658,8 +610,7 @@ pub async fn revoke_mcp_oauth(state: State<'_, AppState>, server_id: String) ->
                     is_enabled: Some(tool.is_enabled),
                 },
             )
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
     }
     Ok(())
 }
@@ -671,13 +622,12 @@ pub async fn set_mcp_bearer_token(
     state: State<'_, AppState>,
     server_id: String,
     token: String,
-) -> Result<(), String> {
-    let encrypted = crate::crypto::encrypt(&token).map_err(|e| e.to_string())?;
+) -> Result<(), AppError> {
+    let encrypted = crate::crypto::encrypt(&token)?;
     state
         .db
         .set_tool_auth_token(&server_id, Some(&encrypted))
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
     state.mcp_manager.disconnect(&server_id).await;
     Ok(())
 }
@@ -700,12 +650,8 @@ pub struct ProbeResult {
 pub async fn probe_mcp_endpoint(
     state: State<'_, AppState>,
     server_id: String,
-) -> Result<ProbeResult, String> {
-    let tool = state
-        .db
-        .get_tool(&server_id)
-        .await
-        .map_err(|e| e.to_string())?;
+) -> Result<ProbeResult, AppError> {
+    let tool = state.db.get_tool(&server_id).await?;
 
     let config = tool.parse_mcp_config().unwrap_or_else(McpConfig::http);
     if config.transport != McpTransportType::Http {
@@ -739,7 +685,7 @@ pub async fn probe_mcp_endpoint(
             }
             builder = builder.default_headers(header_map);
         }
-        builder.build().map_err(|e| e.to_string())?
+        builder.build()?
     };
 
     // 1. Probe endpoint
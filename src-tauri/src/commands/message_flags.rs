@@ -0,0 +1,75 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::{BookmarkedMessage, MessageFlag, MessageFlagKind};
+use tauri::State;
+
+/// Bookmark, rate or react to a message. `emoji` is required for emoji reactions.
+/// Returns the message's flags after the change.
+#[tauri::command]
+pub async fn set_message_flag(
+    state: State<'_, AppState>,
+    message_id: String,
+    kind: MessageFlagKind,
+    emoji: Option<String>,
+) -> Result<Vec<MessageFlag>, AppError> {
+    let value = kind
+        .value_for(emoji.as_deref())
+        .map_err(AppError::validation)?;
+    if state.db.get_message(&message_id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Message not found: {}",
+            message_id
+        )));
+    }
+    state.db.set_message_flag(&message_id, kind, &value).await?;
+    state
+        .db
+        .list_message_flags(&message_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Remove a flag from a message. Returns the message's remaining flags.
+#[tauri::command]
+pub async fn clear_message_flag(
+    state: State<'_, AppState>,
+    message_id: String,
+    kind: MessageFlagKind,
+    emoji: Option<String>,
+) -> Result<Vec<MessageFlag>, AppError> {
+    let value = kind
+        .value_for(emoji.as_deref())
+        .map_err(AppError::validation)?;
+    state
+        .db
+        .clear_message_flag(&message_id, kind, &value)
+        .await?;
+    state
+        .db
+        .list_message_flags(&message_id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn get_message_flags(
+    state: State<'_, AppState>,
+    message_id: String,
+) -> Result<Vec<MessageFlag>, AppError> {
+    state
+        .db
+        .list_message_flags(&message_id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn list_bookmarked_messages(
+    state: State<'_, AppState>,
+) -> Result<Vec<BookmarkedMessage>, AppError> {
+    state
+        .db
+        .list_bookmarked_messages()
+        .await
+        .map_err(AppError::from)
+}
@@ -1,41 +1,41 @@
 use super::AppState;
-use crate::models::{CreateMessageRequest, Message};
+use crate::error::AppError;
+use crate::models::{CreateMessageRequest, Message, MessageRevision};
 use tauri::State;
 
 #[tauri::command]
 pub async fn create_message(
     state: State<'_, AppState>,
     req: CreateMessageRequest,
-) -> Result<Message, String> {
-    state
-        .db
-        .create_message(req)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<Message, AppError> {
+    state.db.create_message(req).await.map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn list_messages_by_conversation(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<Vec<Message>, String> {
+) -> Result<Vec<Message>, AppError> {
+    if let Some(messages) = state.private_conversations.messages(&conversation_id) {
+        return Ok(messages);
+    }
     state
         .db
         .list_messages_by_conversation(&conversation_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn clear_messages_by_conversation(
     state: State<'_, AppState>,
     conversation_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state
         .db
         .delete_messages_in_conversation(&conversation_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -43,10 +43,66 @@ pub async fn delete_messages_from(
     state: State<'_, AppState>,
     conversation_id: String,
     message_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state
         .db
         .delete_messages_from(&conversation_id, &message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Change a message's text. The previous text is kept and can be brought back with
+/// `restore_revision`.
+#[tauri::command]
+pub async fn edit_message(
+    state: State<'_, AppState>,
+    message_id: String,
+    content: String,
+) -> Result<Message, AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::validation("Message content can't be empty"));
+    }
+    if state.db.get_message(&message_id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Message not found: {}",
+            message_id
+        )));
+    }
+    state
+        .db
+        .update_message_content(&message_id, &content)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Earlier versions of a message, newest first
+#[tauri::command]
+pub async fn list_message_revisions(
+    state: State<'_, AppState>,
+    message_id: String,
+) -> Result<Vec<MessageRevision>, AppError> {
+    state
+        .db
+        .list_message_revisions(&message_id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Bring back an earlier version of a message. The content it replaces becomes a
+/// revision itself, so restoring can be undone.
+#[tauri::command]
+pub async fn restore_revision(
+    state: State<'_, AppState>,
+    revision_id: String,
+) -> Result<Message, AppError> {
+    let revision = state
+        .db
+        .get_message_revision(&revision_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Revision not found: {}", revision_id)))?;
+    state
+        .db
+        .update_message_content(&revision.message_id, &revision.content)
+        .await
+        .map_err(AppError::from)
 }
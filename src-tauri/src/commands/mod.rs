@@ -1,26 +1,44 @@
 mod assistants;
 mod attachments;
+mod automations;
 pub(crate) mod capabilities;
 pub mod chat;
 mod contexts;
 mod conversation_settings;
+mod conversation_templates;
 mod conversations;
 mod crypto;
+mod diagnostics;
+mod exports;
+mod imports;
+mod jobs;
 pub mod mcp;
+mod message_flags;
 mod messages;
 mod model_fetch;
 mod model_parameter_presets;
+mod model_roles;
 mod models;
+mod notifications;
+mod ollama;
+mod patches;
 mod prompts;
+mod provider_policies;
 mod providers;
+mod quick_ask;
 mod resources;
 mod search;
 mod settings;
 mod skills;
 mod steps;
+mod updater;
+mod usage;
 mod users;
+mod webhooks;
+mod workspaces;
 
 use crate::db::Database;
+use crate::jobs::JobQueue;
 use crate::llm::capabilities::CapabilitiesCache;
 use crate::llm::tools::BashSessionManager;
 use crate::mcp::McpConnectionManager;
@@ -35,6 +53,9 @@ pub use crate::llm::models::ModelInfo;
 // Global state to track active generation tasks with cancellation tokens
 pub(crate) type GenerationTasks = Arc<RwLock<HashMap<String, CancellationToken>>>;
 
+// Per-conversation parent tokens for auxiliary LLM calls (titles, summaries, search decisions)
+pub(crate) type AuxiliaryTasks = Arc<RwLock<HashMap<String, chat::auxiliary::AuxiliaryEntry>>>;
+
 /// Pending OAuth flow state (keyed by server_id in mcp commands)
 pub type PendingOAuthMap = Arc<RwLock<HashMap<String, mcp::PendingOAuthState>>>;
 
@@ -42,31 +63,54 @@ pub type PendingOAuthMap = Arc<RwLock<HashMap<String, mcp::PendingOAuthState>>>;
 pub struct AppState {
     pub db: Database,
     pub generation_tasks: GenerationTasks,
+    pub auxiliary_tasks: AuxiliaryTasks,
     pub mcp_manager: Arc<McpConnectionManager>,
     pub pending_oauth: PendingOAuthMap,
     pub bash_session_manager: Arc<BashSessionManager>,
     pub capabilities_cache: Arc<CapabilitiesCache>,
+    pub job_queue: Arc<JobQueue>,
+    pub outbox: Arc<chat::outbox::Outbox>,
+    pub request_log: Arc<chat::request_debug::RequestLog>,
+    pub generation_pool: Arc<chat::generation_pool::GenerationPool>,
+    pub private_conversations: Arc<chat::private::PrivateConversations>,
 }
 
 // Re-export all commands
 pub use assistants::*;
 pub use attachments::*;
+pub use automations::*;
 pub use capabilities::*;
 pub use chat::*;
 pub use contexts::*;
 pub use conversation_settings::*;
+pub use conversation_templates::*;
 pub use conversations::*;
 pub use crypto::*;
+pub use diagnostics::*;
+pub use exports::*;
+pub use imports::*;
+pub use jobs::*;
 pub use mcp::*;
+pub use message_flags::*;
 pub use messages::*;
 pub use model_fetch::*;
 pub use model_parameter_presets::*;
+pub use model_roles::*;
 pub use models::*;
+pub use notifications::*;
+pub use ollama::*;
+pub use patches::*;
 pub use prompts::*;
+pub use provider_policies::*;
 pub use providers::*;
+pub use quick_ask::*;
 pub use resources::*;
 pub use search::*;
 pub use settings::*;
 pub use skills::*;
 pub use steps::*;
+pub use updater::*;
+pub use usage::*;
 pub use users::*;
+pub use webhooks::*;
+pub use workspaces::*;
@@ -1,3 +1,4 @@
+use crate::error::{AppError, ErrorKind};
 use crate::llm;
 use crate::llm::StreamChunkType;
 use crate::llm::agent_builder::{
@@ -5,33 +6,36 @@ use crate::llm::agent_builder::{
 };
 pub use crate::llm::models::ModelInfo;
 use serde::Serialize;
+use tauri::State;
 use tokio_util::sync::CancellationToken;
 
+use super::AppState;
+
 #[tauri::command]
 pub async fn fetch_openai_models(
     api_key: String,
     base_url: Option<String>,
-) -> Result<Vec<ModelInfo>, String> {
+) -> Result<Vec<ModelInfo>, AppError> {
     llm::models::fetch_openai_models(api_key, base_url)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn fetch_openrouter_models(
     api_key: String,
     base_url: Option<String>,
-) -> Result<Vec<ModelInfo>, String> {
+) -> Result<Vec<ModelInfo>, AppError> {
     llm::models::fetch_openrouter_models(api_key, base_url)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn fetch_ollama_models(base_url: String) -> Result<Vec<ModelInfo>, String> {
+pub async fn fetch_ollama_models(base_url: String) -> Result<Vec<ModelInfo>, AppError> {
     llm::models::fetch_ollama_models(base_url)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Generic model fetch for providers with OpenAI-compatible /models endpoint.
@@ -42,10 +46,10 @@ pub async fn fetch_provider_models(
     provider_type: String,
     api_key: String,
     base_url: String,
-) -> Result<Vec<ModelInfo>, String> {
+) -> Result<Vec<ModelInfo>, AppError> {
     llm::models::fetch_openai_compatible_models(api_key, base_url, &provider_type)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[derive(Debug, Serialize)]
@@ -64,17 +68,106 @@ pub async fn check_provider_api(
     api_key: Option<String>,
     base_url: Option<String>,
     api_style: Option<String>,
+) -> Result<CheckApiResult, AppError> {
+    Ok(probe_model(
+        &provider_type,
+        &model_id,
+        api_key.as_deref(),
+        base_url.as_deref(),
+        api_style.as_deref(),
+        PROBE_TIMEOUT_SECS,
+    )
+    .await?)
+}
+
+/// Timeout for connectivity probes
+const PROBE_TIMEOUT_SECS: u64 = 30;
+
+/// Timeout for model pings (the picker pings many models, so fail faster)
+pub(super) const PING_TIMEOUT_SECS: u64 = 15;
+
+#[derive(Debug, Serialize)]
+pub struct PingModelResult {
+    pub model_db_id: String,
+    /// "ok", "auth_error", "unreachable", "timeout", or "error"
+    pub status: String,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Ping a saved model with a 1-token request and report round-trip latency and health,
+/// so the model picker can badge unreachable local models or misconfigured keys.
+#[tauri::command]
+pub async fn ping_model(
+    state: State<'_, AppState>,
+    model_db_id: String,
+) -> Result<PingModelResult, AppError> {
+    let model = state
+        .db
+        .get_model(&model_db_id)
+        .await?
+        .ok_or_else(|| format!("Model not found: {}", model_db_id))?;
+    let provider = state
+        .db
+        .get_provider(&model.provider_id)
+        .await?
+        .ok_or_else(|| format!("Provider not found: {}", model.provider_id))?;
+
+    let result = probe_model(
+        &provider.provider_type,
+        &model.model_id,
+        provider.api_key.as_deref(),
+        provider.base_url.as_deref(),
+        provider.api_style.as_deref(),
+        PING_TIMEOUT_SECS,
+    )
+    .await?;
+
+    let status = if result.success {
+        "ok"
+    } else {
+        classify_probe_error(result.error.as_deref().unwrap_or_default())
+    };
+
+    Ok(PingModelResult {
+        model_db_id,
+        status: status.to_string(),
+        latency_ms: result.latency_ms,
+        error: result.error,
+    })
+}
+
+/// Map a probe error message to a coarse health status
+pub(super) fn classify_probe_error(error: &str) -> &'static str {
+    match AppError::classify(error).kind {
+        ErrorKind::Timeout => "timeout",
+        ErrorKind::Auth => "auth_error",
+        ErrorKind::Network => "unreachable",
+        _ => "error",
+    }
+}
+
+/// Send a minimal request and stop at the first streamed chunk.
+pub(super) async fn probe_model(
+    provider_type: &str,
+    model_id: &str,
+    api_key: Option<&str>,
+    base_url: Option<&str>,
+    api_style: Option<&str>,
+    timeout_secs: u64,
 ) -> Result<CheckApiResult, String> {
     let start = std::time::Instant::now();
 
+    // No max_tokens cap: some reasoning models reject tiny limits. Aborting on the
+    // first chunk keeps usage to a token or two anyway.
     let config = AgentConfig::new().with_system_prompt("You are a helpful assistant.".to_string());
 
     let agent = create_provider_agent(
-        &provider_type,
-        &model_id,
-        api_key.as_deref(),
-        base_url.as_deref(),
-        api_style.as_deref(),
+        provider_type,
+        model_id,
+        api_key,
+        base_url,
+        api_style,
         &config,
     )
     .map_err(|e| e.to_string())?;
@@ -84,7 +177,7 @@ pub async fn check_provider_api(
     let cancel_clone = cancel_token.clone();
 
     let result = tokio::time::timeout(
-        std::time::Duration::from_secs(30),
+        std::time::Duration::from_secs(timeout_secs),
         stream_chat_with_agent(
             agent,
             prompt,
@@ -100,6 +193,7 @@ pub async fn check_provider_api(
                 true
             },
             "check-api",
+            crate::thinking_parser::formats_for_model(model_id),
         ),
     )
     .await;
@@ -131,7 +225,29 @@ pub async fn check_provider_api(
         Err(_) => Ok(CheckApiResult {
             success: false,
             latency_ms,
-            error: Some("Connection timed out (30s)".to_string()),
+            error: Some(format!("Connection timed out ({}s)", timeout_secs)),
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_probe_error() {
+        assert_eq!(
+            classify_probe_error("Connection timed out (15s)"),
+            "timeout"
+        );
+        assert_eq!(
+            classify_probe_error("[HTTP 401] Unauthorized: invalid key"),
+            "auth_error"
+        );
+        assert_eq!(
+            classify_probe_error("error sending request for url (http://localhost:11434)"),
+            "unreachable"
+        );
+        assert_eq!(classify_probe_error("model not found"), "error");
+    }
+}
@@ -1,53 +1,56 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::models::{
-    CreateModelParameterPresetRequest, ModelParameterPreset, UpdateModelParameterPresetRequest,
+    CreateModelParameterPresetRequest, ExportedPreset, ModelParameterPreset, PresetExport,
+    UpdateModelParameterPresetRequest, unique_preset_name,
 };
+use std::path::PathBuf;
 use tauri::State;
 
 #[tauri::command]
 pub async fn list_model_parameter_presets(
     state: State<'_, AppState>,
-) -> Result<Vec<ModelParameterPreset>, String> {
+) -> Result<Vec<ModelParameterPreset>, AppError> {
     state
         .db
         .list_model_parameter_presets()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_model_parameter_preset(
     state: State<'_, AppState>,
     id: String,
-) -> Result<Option<ModelParameterPreset>, String> {
+) -> Result<Option<ModelParameterPreset>, AppError> {
     state
         .db
         .get_model_parameter_preset(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_default_model_parameter_preset(
     state: State<'_, AppState>,
-) -> Result<Option<ModelParameterPreset>, String> {
+) -> Result<Option<ModelParameterPreset>, AppError> {
     state
         .db
         .get_default_model_parameter_preset()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn create_model_parameter_preset(
     state: State<'_, AppState>,
     req: CreateModelParameterPresetRequest,
-) -> Result<ModelParameterPreset, String> {
+) -> Result<ModelParameterPreset, AppError> {
     state
         .db
         .create_model_parameter_preset(req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -55,22 +58,115 @@ pub async fn update_model_parameter_preset(
     state: State<'_, AppState>,
     id: String,
     req: UpdateModelParameterPresetRequest,
-) -> Result<ModelParameterPreset, String> {
+) -> Result<ModelParameterPreset, AppError> {
     state
         .db
         .update_model_parameter_preset(&id, req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn delete_model_parameter_preset(
     state: State<'_, AppState>,
     id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state
         .db
         .delete_model_parameter_preset(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Copy a preset (system presets included) as a new user preset named "<name> (copy)"
+#[tauri::command]
+pub async fn duplicate_preset(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ModelParameterPreset, AppError> {
+    let preset = state
+        .db
+        .get_model_parameter_preset(&id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Preset not found: {}", id)))?;
+    let taken = preset_names(&state).await?;
+
+    let mut req = CreateModelParameterPresetRequest::from(ExportedPreset::from(&preset));
+    req.name = unique_preset_name(&format!("{} (copy)", preset.name), &taken);
+    state
+        .db
+        .create_model_parameter_preset(req)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Write presets to `destination_path` as JSON. Exports the given `ids`, or every
+/// user-created preset when omitted. Returns the path written.
+#[tauri::command]
+pub async fn export_model_parameter_presets(
+    state: State<'_, AppState>,
+    ids: Option<Vec<String>>,
+    destination_path: String,
+) -> Result<String, AppError> {
+    let presets: Vec<ModelParameterPreset> = state
+        .db
+        .list_model_parameter_presets()
+        .await?
+        .into_iter()
+        .filter(|p| match &ids {
+            Some(ids) => ids.contains(&p.id),
+            None => !p.is_system,
+        })
+        .collect();
+    if presets.is_empty() {
+        return Err(AppError::validation("No presets to export"));
+    }
+
+    let json = serde_json::to_string_pretty(&PresetExport::new(&presets))?;
+    let mut destination = PathBuf::from(destination_path);
+    if destination.extension().is_none() {
+        destination.set_extension("json");
+    }
+    tokio::fs::write(&destination, json).await?;
+
+    tracing::info!(
+        "📤 [presets] Exported {} preset(s) to {}",
+        presets.len(),
+        destination.display()
+    );
+    Ok(destination.to_string_lossy().to_string())
+}
+
+/// Import presets from a JSON file written by `export_model_parameter_presets`.
+/// Imported presets are never system or default presets; names that are already
+/// taken get a numeric suffix. Returns the created presets.
+#[tauri::command]
+pub async fn import_model_parameter_presets(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<Vec<ModelParameterPreset>, AppError> {
+    let json = tokio::fs::read_to_string(&path).await?;
+    let export = PresetExport::parse(&json).map_err(AppError::validation)?;
+
+    let mut taken = preset_names(&state).await?;
+    let mut created = Vec::with_capacity(export.presets.len());
+    for preset in export.presets {
+        let mut req = CreateModelParameterPresetRequest::from(preset);
+        req.name = unique_preset_name(&req.name, &taken);
+        taken.push(req.name.clone());
+        created.push(state.db.create_model_parameter_preset(req).await?);
+    }
+
+    tracing::info!("📥 [presets] Imported {} preset(s)", created.len());
+    Ok(created)
+}
+
+async fn preset_names(state: &AppState) -> Result<Vec<String>, AppError> {
+    Ok(state
+        .db
+        .list_model_parameter_presets()
+        .await?
+        .into_iter()
+        .map(|p| p.name)
+        .collect())
 }
@@ -0,0 +1,62 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::{ModelRole, ModelRoleAssignments};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_model_roles(state: State<'_, AppState>) -> Result<ModelRoleAssignments, AppError> {
+    state
+        .db
+        .get_model_role_assignments()
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn set_model_role(
+    state: State<'_, AppState>,
+    role: ModelRole,
+    model_id: Option<String>,
+) -> Result<(), AppError> {
+    state
+        .db
+        .set_model_role(role, model_id.as_deref())
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn set_model_alias(
+    state: State<'_, AppState>,
+    alias: String,
+    model_id: Option<String>,
+) -> Result<(), AppError> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(AppError::validation("Alias cannot be empty"));
+    }
+    if ModelRole::from_id(alias).is_some() {
+        return Err(AppError::validation(format!(
+            "'{}' is a reserved role name",
+            alias
+        )));
+    }
+    state
+        .db
+        .set_model_alias(alias, model_id.as_deref())
+        .await
+        .map_err(AppError::from)
+}
+
+/// Resolve a role id, alias, or model id to a model DB id
+#[tauri::command]
+pub async fn resolve_model_reference(
+    state: State<'_, AppState>,
+    reference: String,
+) -> Result<Option<String>, AppError> {
+    state
+        .db
+        .resolve_model_reference(&reference)
+        .await
+        .map_err(AppError::from)
+}
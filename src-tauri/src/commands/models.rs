@@ -1,28 +1,38 @@
 use super::AppState;
-use crate::models::{CreateModelRequest, Model};
+use crate::error::AppError;
+use crate::models::{CreateModelRequest, Model, OpenRouterRouting};
 use tauri::State;
 
 #[tauri::command]
 pub async fn create_model(
     state: State<'_, AppState>,
-    req: CreateModelRequest,
-) -> Result<Model, String> {
-    state.db.create_model(req).await.map_err(|e| e.to_string())
+    mut req: CreateModelRequest,
+) -> Result<Model, AppError> {
+    fill_missing_pricing(&state, &mut req).await;
+    state.db.create_model(req).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn get_model(state: State<'_, AppState>, id: String) -> Result<Option<Model>, String> {
-    state.db.get_model(&id).await.map_err(|e| e.to_string())
+pub async fn get_model(state: State<'_, AppState>, id: String) -> Result<Option<Model>, AppError> {
+    state.db.get_model(&id).await.map_err(AppError::from)
 }
 
+/// List non-deleted models in display order; `visible_only` also drops hidden ones
 #[tauri::command]
-pub async fn list_models(state: State<'_, AppState>) -> Result<Vec<Model>, String> {
-    state.db.list_models().await.map_err(|e| e.to_string())
+pub async fn list_models(
+    state: State<'_, AppState>,
+    visible_only: Option<bool>,
+) -> Result<Vec<Model>, AppError> {
+    state
+        .db
+        .list_models(visible_only.unwrap_or(false))
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn list_all_models(state: State<'_, AppState>) -> Result<Vec<Model>, String> {
-    state.db.list_all_models().await.map_err(|e| e.to_string())
+pub async fn list_all_models(state: State<'_, AppState>) -> Result<Vec<Model>, AppError> {
+    state.db.list_all_models().await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -30,24 +40,155 @@ pub async fn update_model(
     state: State<'_, AppState>,
     id: String,
     req: CreateModelRequest,
-) -> Result<Model, String> {
+) -> Result<Model, AppError> {
     state
         .db
         .update_model(&id, req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn delete_model(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    state.db.delete_model(&id).await.map_err(|e| e.to_string())
+pub async fn delete_model(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_model(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn soft_delete_model(state: State<'_, AppState>, id: String) -> Result<(), String> {
+pub async fn soft_delete_model(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
     state
         .db
         .soft_delete_model(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn list_deleted_models(state: State<'_, AppState>) -> Result<Vec<Model>, AppError> {
+    state.db.list_deleted_models().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn restore_model(state: State<'_, AppState>, id: String) -> Result<Model, AppError> {
+    state.db.restore_model(&id).await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn set_model_hidden(
+    state: State<'_, AppState>,
+    id: String,
+    hidden: bool,
+) -> Result<Model, AppError> {
+    state
+        .db
+        .set_model_hidden(&id, hidden)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Set or clear a model's OpenRouter routing overrides; unset fields fall back to the
+/// provider's defaults
+#[tauri::command]
+pub async fn set_model_openrouter_routing(
+    state: State<'_, AppState>,
+    id: String,
+    routing: Option<OpenRouterRouting>,
+) -> Result<Model, AppError> {
+    let model = state
+        .db
+        .get_model(&id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Model not found: {}", id)))?;
+    let is_openrouter = state
+        .db
+        .get_provider(&model.provider_id)
+        .await?
+        .is_some_and(|p| p.provider_type == "openrouter");
+    if !is_openrouter {
+        return Err(AppError::validation(
+            "Routing preferences only apply to OpenRouter models",
+        ));
+    }
+    if let Some(routing) = &routing {
+        routing.validate().map_err(AppError::validation)?;
+    }
+    state
+        .db
+        .set_model_openrouter_routing(&id, routing.as_ref())
+        .await
+        .map_err(AppError::from)
+}
+
+/// Set or clear the parameter preset a model uses when no assistant preset applies
+#[tauri::command]
+pub async fn set_model_default_preset(
+    state: State<'_, AppState>,
+    id: String,
+    preset_id: Option<String>,
+) -> Result<Model, AppError> {
+    if let Some(preset_id) = &preset_id
+        && state
+            .db
+            .get_model_parameter_preset(preset_id)
+            .await?
+            .is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Parameter preset not found: {}",
+            preset_id
+        )));
+    }
+    state
+        .db
+        .set_model_default_preset(&id, preset_id.as_deref())
+        .await
+        .map_err(AppError::from)
+}
+
+/// Persist a manual model ordering (ids in display order)
+#[tauri::command]
+pub async fn reorder_models(
+    state: State<'_, AppState>,
+    ordered_ids: Vec<String>,
+) -> Result<(), AppError> {
+    state
+        .db
+        .reorder_models(&ordered_ids)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Permanently remove soft-deleted models that no message or assistant references
+#[tauri::command]
+pub async fn purge_deleted_models(state: State<'_, AppState>) -> Result<u64, AppError> {
+    let purged = state.db.purge_deleted_models().await?;
+    tracing::info!(
+        "🧹 [models] Purged {} unreferenced deleted model(s)",
+        purged
+    );
+    Ok(purged)
+}
+
+/// Fill in input/output prices from the model metadata cache (models.dev, which also
+/// covers OpenRouter) when the caller didn't provide them. Manually entered prices win.
+async fn fill_missing_pricing(state: &AppState, req: &mut CreateModelRequest) {
+    if req.input_price.is_some() && req.output_price.is_some() {
+        return;
+    }
+
+    let provider_type = match state.db.get_provider(&req.provider_id).await {
+        Ok(Some(provider)) => provider.provider_type,
+        _ => return,
+    };
+
+    let caps = state
+        .capabilities_cache
+        .resolve(&provider_type, &req.model_id)
+        .await;
+
+    if req.input_price.is_none() {
+        req.input_price = caps.input_price;
+    }
+    if req.output_price.is_none() {
+        req.output_price = caps.output_price;
+    }
 }
@@ -0,0 +1,14 @@
+use crate::error::AppError;
+use crate::notifications::NotificationState;
+use tauri::State;
+
+/// Tell the backend which conversation the UI is showing, so completions there
+/// don't trigger a notification while the window is focused
+#[tauri::command]
+pub fn set_active_conversation(
+    state: State<'_, NotificationState>,
+    conversation_id: Option<String>,
+) -> Result<(), AppError> {
+    state.set_active_conversation(conversation_id);
+    Ok(())
+}
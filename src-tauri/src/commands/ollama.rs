@@ -0,0 +1,204 @@
+//! Local Ollama model management (pull/delete/show) and warm-up
+
+use super::AppState;
+use super::chat::auxiliary::{auxiliary_call, cancel_auxiliary};
+use crate::error::AppError;
+use crate::events::{self, OllamaPullProgress};
+use crate::llm::ollama::{self, OllamaModelDetails};
+use crate::models::ModelRole;
+use tauri::{Manager, State};
+
+/// Key a pull's cancellation token is registered under in the auxiliary task registry
+fn pull_task_key(model: &str) -> String {
+    format!("ollama-pull:{}", model)
+}
+
+/// Pull (download) a model into the local Ollama server.
+///
+/// Emits `ollama-pull-progress` while downloading and resolves when the pull finishes.
+/// Progress events are throttled to status changes and whole-percent steps.
+/// `ollama_cancel_pull` aborts the pull.
+#[tauri::command]
+pub async fn ollama_pull_model(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    base_url: Option<String>,
+    model: String,
+) -> Result<(), AppError> {
+    tracing::info!("📥 [ollama] Pulling model: {}", model);
+
+    let pull = auxiliary_call(&state, &pull_task_key(&model)).await;
+    let cancel_token = pull.token();
+    let mut last_status = String::new();
+    let mut last_percent: Option<u64> = None;
+
+    let result = ollama::pull_model(base_url.as_deref(), &model, |progress| {
+        let percent = match (progress.completed, progress.total) {
+            (Some(done), Some(total)) if total > 0 => Some(done * 100 / total),
+            _ => None,
+        };
+
+        if progress.status != last_status || percent != last_percent {
+            last_status = progress.status.clone();
+            last_percent = percent;
+            events::emit(
+                &app,
+                OllamaPullProgress {
+                    model: model.clone(),
+                    status: progress.status.clone(),
+                    digest: progress.digest.clone(),
+                    total: progress.total,
+                    completed: progress.completed,
+                    percent,
+                },
+            );
+        }
+        !cancel_token.is_cancelled()
+    })
+    .await;
+
+    match result {
+        Ok(()) => {
+            tracing::info!("✅ [ollama] Pulled model: {}", model);
+            Ok(())
+        }
+        Err(e) => {
+            tracing::error!("❌ [ollama] Failed to pull model {}: {}", model, e);
+            Err(AppError::from(e).with_provider_type("ollama"))
+        }
+    }
+}
+
+/// Cancel a running pull of `model`. Returns true if one was running.
+#[tauri::command]
+pub async fn ollama_cancel_pull(
+    state: State<'_, AppState>,
+    model: String,
+) -> Result<bool, AppError> {
+    let cancelled = cancel_auxiliary(&state, &pull_task_key(&model)).await;
+    if cancelled {
+        tracing::info!("🛑 [ollama] Cancelling pull of model: {}", model);
+    }
+    Ok(cancelled)
+}
+
+/// Delete a model from the local Ollama server
+#[tauri::command]
+pub async fn ollama_delete_model(base_url: Option<String>, model: String) -> Result<(), AppError> {
+    tracing::info!("🗑️ [ollama] Deleting model: {}", model);
+    ollama::delete_model(base_url.as_deref(), &model)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Show details (modelfile, parameters, template, capabilities) for a local model
+#[tauri::command]
+pub async fn ollama_show_model(
+    base_url: Option<String>,
+    model: String,
+) -> Result<OllamaModelDetails, AppError> {
+    ollama::show_model(base_url.as_deref(), &model)
+        .await
+        .map_err(AppError::from)
+}
+
+/// The `keep_alive` duration configured in settings, if valid
+pub(crate) async fn configured_keep_alive(state: &AppState) -> Option<String> {
+    let value = state
+        .db
+        .get_setting(ollama::KEEP_ALIVE_SETTING_KEY)
+        .await
+        .ok()
+        .flatten()?;
+    let keep_alive = ollama::normalize_keep_alive(&value);
+    if keep_alive.is_none() && !value.trim().is_empty() {
+        tracing::warn!("⚠️ [ollama] Ignoring invalid keep_alive setting: {}", value);
+    }
+    keep_alive
+}
+
+/// Load the Ollama models assigned to the chat and fast roles (the fast model writes
+/// titles and summaries) in parallel. Returns the models that were loaded.
+async fn warm_up_role_models(state: &AppState) -> Vec<String> {
+    let keep_alive = configured_keep_alive(state).await;
+
+    let mut targets: Vec<(Option<String>, String)> = Vec::new();
+    for role in [ModelRole::Chat, ModelRole::Fast] {
+        let Some(model_db_id) = state
+            .db
+            .resolve_model_reference(role.id())
+            .await
+            .ok()
+            .flatten()
+        else {
+            continue;
+        };
+        let Ok(Some(model)) = state.db.get_model(&model_db_id).await else {
+            continue;
+        };
+        let Ok(Some(provider)) = state.db.get_provider(&model.provider_id).await else {
+            continue;
+        };
+        let target = (provider.base_url, model.model_id);
+        if provider.provider_type == "ollama" && !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    let loads = targets.iter().map(|(base_url, model)| async {
+        match ollama::load_model(base_url.as_deref(), model, keep_alive.as_deref()).await {
+            Ok(()) => {
+                tracing::info!("🔥 [ollama] Warmed up model: {}", model);
+                Some(model.clone())
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ [ollama] Failed to warm up {}: {}", model, e);
+                None
+            }
+        }
+    });
+    futures::future::join_all(loads)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Warm up at startup when enabled in settings
+pub async fn warm_up_on_startup(app: tauri::AppHandle) {
+    let state: State<'_, AppState> = app.state();
+    let enabled = matches!(
+        state
+            .db
+            .get_setting(ollama::WARM_UP_ON_STARTUP_SETTING_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    );
+    if enabled {
+        warm_up_role_models(&state).await;
+    }
+}
+
+/// Load the chat and fast role models if they are served by Ollama, e.g. when the user
+/// starts typing, so the first token doesn't wait for the model to load.
+/// Returns the models that were loaded.
+#[tauri::command]
+pub async fn ollama_warm_up_models(state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    Ok(warm_up_role_models(&state).await)
+}
+
+/// Load a single model, keeping it in memory for the configured `keep_alive` duration
+#[tauri::command]
+pub async fn ollama_load_model(
+    state: State<'_, AppState>,
+    base_url: Option<String>,
+    model: String,
+) -> Result<(), AppError> {
+    let keep_alive = configured_keep_alive(&state).await;
+    ollama::load_model(base_url.as_deref(), &model, keep_alive.as_deref())
+        .await
+        .map_err(|e| AppError::from(e).with_provider_type("ollama"))
+}
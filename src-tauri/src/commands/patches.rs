@@ -0,0 +1,182 @@
+//! Applying diffs the model suggested
+//!
+//! The frontend offers "apply" on a ```diff block in an assistant message once the
+//! user has picked (or confirmed) the file it targets. A dry run only reports whether
+//! and how the diff applies. A real run writes the file and, when the diff came from a
+//! message, records an `apply_patch` tool call on it so the change shows up among the
+//! message's process steps. Writes go through the same path policy as the edit tool,
+//! bounded by the conversation's working directory when one is set.
+
+use super::AppState;
+use crate::error::AppError;
+use crate::llm::tools::path_policy;
+use crate::models::CreateToolCallRequest;
+use crate::unified_diff;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Instant;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PatchResult {
+    pub path: String,
+    pub dry_run: bool,
+    pub hunks: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    /// The diff created the file
+    pub created: bool,
+    /// The recorded `apply_patch` tool call, for real runs on a message
+    pub tool_call_id: Option<String>,
+}
+
+/// Validate and apply a single-file unified diff to `path`. Relative paths resolve
+/// against the working directory of the message's conversation.
+#[tauri::command]
+pub async fn apply_patch(
+    state: State<'_, AppState>,
+    message_id: Option<String>,
+    path: String,
+    diff: String,
+    dry_run: Option<bool>,
+) -> Result<PatchResult, AppError> {
+    let dry_run = dry_run.unwrap_or(false);
+    let working_directory = match &message_id {
+        Some(id) => {
+            let message = state
+                .db
+                .get_message(id)
+                .await?
+                .ok_or_else(|| AppError::not_found(format!("Message not found: {}", id)))?;
+            match message.conversation_id {
+                Some(conversation_id) => {
+                    state
+                        .db
+                        .get_conversation_settings(&conversation_id)
+                        .await?
+                        .working_directory
+                }
+                None => None,
+            }
+        }
+        None => None,
+    };
+
+    let project_root = working_directory.as_ref().map(PathBuf::from);
+    let target = path_policy::resolve(&path, project_root.as_deref());
+    if target.is_relative() {
+        return Err(AppError::validation(
+            "The file path must be absolute when the conversation has no working directory",
+        ));
+    }
+    path_policy::check_write(&target, project_root.as_deref()).map_err(AppError::validation)?;
+    let path = target.to_string_lossy().to_string();
+
+    let started = Instant::now();
+    let outcome = apply_to_file(&target, &diff, dry_run).await;
+    tracing::info!(
+        "🩹 [patches] {} {}: {}",
+        if dry_run { "Dry run on" } else { "Applied to" },
+        path,
+        match &outcome {
+            Ok(applied) => format!(
+                "{} hunk(s), +{} -{}",
+                applied.hunks, applied.additions, applied.deletions
+            ),
+            Err(e) => e.clone(),
+        }
+    );
+
+    let tool_call_id = match (&message_id, dry_run) {
+        (Some(message_id), false) => {
+            let display_order = state
+                .db
+                .get_message_steps(message_id)
+                .await?
+                .iter()
+                .map(|step| step.display_order() + 1)
+                .max()
+                .unwrap_or(0);
+            let call = state
+                .db
+                .create_tool_call(CreateToolCallRequest {
+                    id: None,
+                    message_id: message_id.clone(),
+                    tool_name: "apply_patch".to_string(),
+                    tool_input: Some(serde_json::json!({ "path": path, "diff": diff }).to_string()),
+                    tool_output: outcome.as_ref().ok().map(|applied| {
+                        format!(
+                            "Applied {} hunk(s) to {} (+{} -{})",
+                            applied.hunks, path, applied.additions, applied.deletions
+                        )
+                    }),
+                    status: Some(if outcome.is_ok() { "success" } else { "error" }.to_string()),
+                    error: outcome.as_ref().err().cloned(),
+                    duration_ms: Some(started.elapsed().as_millis() as i64),
+                    display_order: Some(display_order),
+                    completed_at: Some(chrono::Utc::now().to_rfc3339()),
+                })
+                .await?;
+            Some(call.id)
+        }
+        _ => None,
+    };
+
+    let applied = outcome.map_err(AppError::validation)?;
+    Ok(PatchResult {
+        path,
+        dry_run,
+        hunks: applied.hunks,
+        additions: applied.additions,
+        deletions: applied.deletions,
+        created: applied.created,
+        tool_call_id,
+    })
+}
+
+struct FileChange {
+    hunks: usize,
+    additions: usize,
+    deletions: usize,
+    created: bool,
+}
+
+async fn apply_to_file(
+    target: &std::path::Path,
+    diff: &str,
+    dry_run: bool,
+) -> Result<FileChange, String> {
+    let patch = unified_diff::parse(diff)?;
+    let original = if tokio::fs::try_exists(target).await.unwrap_or(false) {
+        if !target.is_file() {
+            return Err(format!("Not a file: {}", target.display()));
+        }
+        Some(
+            tokio::fs::read_to_string(target)
+                .await
+                .map_err(|e| format!("Failed to read file: {}", e))?,
+        )
+    } else {
+        None
+    };
+    let applied = patch.apply(original.as_deref())?;
+
+    if !dry_run {
+        if let Some(parent) = target.parent()
+            && patch.creates_file()
+        {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        tokio::fs::write(target, &applied.content)
+            .await
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    Ok(FileChange {
+        hunks: applied.hunks,
+        additions: applied.additions,
+        deletions: applied.deletions,
+        created: patch.creates_file(),
+    })
+}
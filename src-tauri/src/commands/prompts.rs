@@ -1,3 +1,4 @@
+use crate::error::AppError;
 use tauri::State;
 
 use crate::commands::AppState;
@@ -7,30 +8,33 @@ use crate::models::{CreatePromptRequest, Prompt};
 pub async fn create_prompt(
     state: State<'_, AppState>,
     req: CreatePromptRequest,
-) -> Result<Prompt, String> {
-    state.db.create_prompt(req).await.map_err(|e| e.to_string())
+) -> Result<Prompt, AppError> {
+    state.db.create_prompt(req).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn get_prompt(state: State<'_, AppState>, id: String) -> Result<Option<Prompt>, String> {
-    state.db.get_prompt(&id).await.map_err(|e| e.to_string())
+pub async fn get_prompt(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<Prompt>, AppError> {
+    state.db.get_prompt(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn list_prompts(state: State<'_, AppState>) -> Result<Vec<Prompt>, String> {
-    state.db.list_prompts().await.map_err(|e| e.to_string())
+pub async fn list_prompts(state: State<'_, AppState>) -> Result<Vec<Prompt>, AppError> {
+    state.db.list_prompts().await.map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn list_prompts_by_category(
     state: State<'_, AppState>,
     category: String,
-) -> Result<Vec<Prompt>, String> {
+) -> Result<Vec<Prompt>, AppError> {
     state
         .db
         .list_prompts_by_category(&category)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -38,24 +42,27 @@ pub async fn update_prompt(
     state: State<'_, AppState>,
     id: String,
     req: CreatePromptRequest,
-) -> Result<Prompt, String> {
+) -> Result<Prompt, AppError> {
     state
         .db
         .update_prompt(&id, req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn delete_prompt(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    state.db.delete_prompt(&id).await.map_err(|e| e.to_string())
+pub async fn delete_prompt(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_prompt(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn toggle_prompt_star(state: State<'_, AppState>, id: String) -> Result<Prompt, String> {
+pub async fn toggle_prompt_star(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Prompt, AppError> {
     state
         .db
         .toggle_prompt_star(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
@@ -0,0 +1,220 @@
+use super::AppState;
+use crate::error::{AppError, ErrorKind};
+use crate::models::{
+    ConversationSettings, Provider, ProviderPolicy, ProviderPolicyInput,
+    UpdateConversationSettingsRequest,
+};
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_provider_policies(
+    state: State<'_, AppState>,
+) -> Result<Vec<ProviderPolicy>, AppError> {
+    state
+        .db
+        .list_provider_policies()
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn create_provider_policy(
+    state: State<'_, AppState>,
+    input: ProviderPolicyInput,
+) -> Result<ProviderPolicy, AppError> {
+    input.validate().map_err(AppError::validation)?;
+    state
+        .db
+        .create_provider_policy(input)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn update_provider_policy(
+    state: State<'_, AppState>,
+    id: String,
+    input: ProviderPolicyInput,
+) -> Result<ProviderPolicy, AppError> {
+    input.validate().map_err(AppError::validation)?;
+    ensure_editable(&state, &id).await?;
+    state
+        .db
+        .update_provider_policy(&id, input)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn delete_provider_policy(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), AppError> {
+    ensure_editable(&state, &id).await?;
+    state
+        .db
+        .delete_provider_policy(&id)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Restrict the providers a conversation may use, or lift the restriction (`None`)
+#[tauri::command]
+pub async fn set_conversation_provider_policy(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    policy_id: Option<String>,
+) -> Result<ConversationSettings, AppError> {
+    if let Some(id) = &policy_id
+        && state.db.get_provider_policy(id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Provider policy not found: {}",
+            id
+        )));
+    }
+
+    state
+        .db
+        .update_conversation_settings(
+            &conversation_id,
+            UpdateConversationSettingsRequest {
+                provider_policy_id: Some(policy_id),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(AppError::from)
+}
+
+async fn ensure_editable(state: &AppState, id: &str) -> Result<(), AppError> {
+    match state.db.get_provider_policy(id).await? {
+        Some(policy) if policy.is_builtin => Err(AppError::validation(format!(
+            "\"{}\" is a built-in policy and can't be changed",
+            policy.name
+        ))),
+        Some(_) => Ok(()),
+        None => Err(AppError::not_found(format!(
+            "Provider policy not found: {}",
+            id
+        ))),
+    }
+}
+
+/// Fail with a `provider_not_allowed` error when the conversation's provider policy
+/// doesn't allow the provider. `base_url` falls back to the provider record's, so
+/// remote endpoints configured on a provider are never mistaken for local ones.
+///
+/// A policy that no longer exists blocks the send rather than silently allowing
+/// cloud providers.
+pub(crate) async fn enforce_provider_policy(
+    state: &AppState,
+    conversation_id: &str,
+    provider_type: &str,
+    base_url: Option<&str>,
+    model_db_id: Option<&str>,
+    assistant_db_id: Option<&str>,
+) -> Result<(), AppError> {
+    let Some(policy) = conversation_policy(state, conversation_id, provider_type).await? else {
+        return Ok(());
+    };
+
+    let model_db_id = match (model_db_id, assistant_db_id) {
+        (Some(id), _) => Some(id.to_string()),
+        (None, Some(assistant_id)) => state
+            .db
+            .get_assistant(assistant_id)
+            .await?
+            .map(|a| a.model_id),
+        (None, None) => None,
+    };
+    let provider = match model_db_id {
+        Some(id) => match state.db.get_model(&id).await? {
+            Some(model) => state.db.get_provider(&model.provider_id).await?,
+            None => None,
+        },
+        None => None,
+    };
+
+    check_policy(&policy, conversation_id, provider_type, base_url, provider)
+}
+
+/// [`enforce_provider_policy`] for a provider used directly rather than through a
+/// model, such as the speech-to-text or image generation provider
+pub(crate) async fn enforce_provider_policy_for_provider(
+    state: &AppState,
+    conversation_id: &str,
+    provider: &Provider,
+) -> Result<(), AppError> {
+    let Some(policy) = conversation_policy(state, conversation_id, &provider.provider_type).await?
+    else {
+        return Ok(());
+    };
+    check_policy(
+        &policy,
+        conversation_id,
+        &provider.provider_type,
+        None,
+        Some(provider.clone()),
+    )
+}
+
+/// The conversation's policy, `None` when it has none
+async fn conversation_policy(
+    state: &AppState,
+    conversation_id: &str,
+    provider_type: &str,
+) -> Result<Option<ProviderPolicy>, AppError> {
+    let Some(policy_id) = state
+        .db
+        .get_conversation_settings(conversation_id)
+        .await?
+        .provider_policy_id
+    else {
+        return Ok(None);
+    };
+    match state.db.get_provider_policy(&policy_id).await? {
+        Some(policy) => Ok(Some(policy)),
+        None => Err(AppError::new(
+            ErrorKind::ProviderNotAllowed,
+            "This conversation's provider policy no longer exists; choose another policy",
+        )
+        .with_provider_type(provider_type)),
+    }
+}
+
+fn check_policy(
+    policy: &ProviderPolicy,
+    conversation_id: &str,
+    provider_type: &str,
+    base_url: Option<&str>,
+    provider: Option<Provider>,
+) -> Result<(), AppError> {
+    let base_url = base_url
+        .filter(|url| !url.trim().is_empty())
+        .or_else(|| provider.as_ref().and_then(|p| p.base_url.as_deref()));
+    let is_local = crate::network::is_local_endpoint(provider_type, base_url);
+    let provider_id = provider.as_ref().map(|p| p.id.as_str());
+
+    if policy.allows(provider_id, provider_type, is_local) {
+        return Ok(());
+    }
+
+    tracing::warn!(
+        "🛡️ [provider_policy] Blocked {} in conversation {} (policy: {})",
+        provider_type,
+        conversation_id,
+        policy.name
+    );
+    let provider_name = provider
+        .map(|p| p.name)
+        .unwrap_or_else(|| provider_type.to_string());
+    Err(AppError::new(
+        ErrorKind::ProviderNotAllowed,
+        format!(
+            "The \"{}\" policy of this conversation doesn't allow {}",
+            policy.name, provider_name
+        ),
+    )
+    .with_provider_type(provider_type))
+}
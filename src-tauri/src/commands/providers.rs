@@ -1,30 +1,27 @@
 use super::AppState;
-use crate::models::{CreateProviderRequest, Provider};
+use crate::error::AppError;
+use crate::models::{CreateProviderRequest, OpenRouterRouting, Provider};
 use tauri::State;
 
 #[tauri::command]
 pub async fn create_provider(
     state: State<'_, AppState>,
     req: CreateProviderRequest,
-) -> Result<Provider, String> {
-    state
-        .db
-        .create_provider(req)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<Provider, AppError> {
+    state.db.create_provider(req).await.map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_provider(
     state: State<'_, AppState>,
     id: String,
-) -> Result<Option<Provider>, String> {
-    state.db.get_provider(&id).await.map_err(|e| e.to_string())
+) -> Result<Option<Provider>, AppError> {
+    state.db.get_provider(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn list_providers(state: State<'_, AppState>) -> Result<Vec<Provider>, String> {
-    state.db.list_providers().await.map_err(|e| e.to_string())
+pub async fn list_providers(state: State<'_, AppState>) -> Result<Vec<Provider>, AppError> {
+    state.db.list_providers().await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -32,19 +29,43 @@ pub async fn update_provider(
     state: State<'_, AppState>,
     id: String,
     req: CreateProviderRequest,
-) -> Result<Provider, String> {
+) -> Result<Provider, AppError> {
     state
         .db
         .update_provider(&id, req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn delete_provider(state: State<'_, AppState>, id: String) -> Result<(), String> {
+pub async fn delete_provider(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_provider(&id).await.map_err(AppError::from)
+}
+
+/// Set or clear an OpenRouter provider's routing defaults (order, fallbacks, data
+/// collection, price caps). Models can override them with `set_model_openrouter_routing`.
+#[tauri::command]
+pub async fn set_provider_openrouter_routing(
+    state: State<'_, AppState>,
+    id: String,
+    routing: Option<OpenRouterRouting>,
+) -> Result<Provider, AppError> {
+    let provider = state
+        .db
+        .get_provider(&id)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Provider not found: {}", id)))?;
+    if provider.provider_type != "openrouter" {
+        return Err(AppError::validation(
+            "Routing preferences only apply to OpenRouter providers",
+        ));
+    }
+    if let Some(routing) = &routing {
+        routing.validate().map_err(AppError::validation)?;
+    }
     state
         .db
-        .delete_provider(&id)
+        .set_provider_openrouter_routing(&id, routing.as_ref())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
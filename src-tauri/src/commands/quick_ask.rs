@@ -0,0 +1,74 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::Message;
+use crate::quick_ask;
+use tauri::{Manager, State};
+
+#[tauri::command]
+pub async fn get_quick_ask_shortcut(state: State<'_, AppState>) -> Result<String, AppError> {
+    Ok(quick_ask::configured_shortcut(&state).await)
+}
+
+/// Change the Quick Ask hotkey (empty string disables it)
+#[tauri::command]
+pub async fn set_quick_ask_shortcut(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    shortcut: String,
+) -> Result<(), AppError> {
+    quick_ask::register_shortcut(&app, &shortcut)?;
+    state
+        .db
+        .set_setting(quick_ask::QUICK_ASK_SHORTCUT_KEY, shortcut.trim())
+        .await
+        .map_err(AppError::from)
+}
+
+/// Send a prompt from the Quick Ask window into the Quick Ask conversation,
+/// using the conversation's bound model or the default chat model
+#[tauri::command]
+pub async fn quick_ask(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    content: String,
+) -> Result<Message, AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::validation("Message cannot be empty"));
+    }
+    let conversation_id = quick_ask::ensure_conversation(&state).await?;
+
+    super::chat::send_message(
+        state,
+        app,
+        conversation_id,
+        content,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+}
+
+#[tauri::command]
+pub fn hide_quick_ask_window(app: tauri::AppHandle) -> Result<(), AppError> {
+    if let Some(window) = app.get_webview_window(quick_ask::QUICK_ASK_WINDOW_LABEL) {
+        window.hide()?;
+    }
+    Ok(())
+}
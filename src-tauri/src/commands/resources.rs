@@ -1,7 +1,16 @@
 use super::AppState;
-use crate::models::MessageResources;
+use crate::error::AppError;
+use crate::models::{ContextType, MessageResources};
+use crate::storage::ContentRange;
+use serde::Serialize;
 use tauri::State;
 
+/// Files larger than this must be read in pages with `read_fetch_content_range`
+const MAX_WHOLE_FETCH_CONTENT_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Largest page `read_fetch_content_range` returns
+const MAX_FETCH_CONTENT_RANGE_BYTES: u64 = 512 * 1024;
+
 // ==========================================================================
 // COMBINED: Get All Message Resources
 // ==========================================================================
@@ -10,39 +19,167 @@ use tauri::State;
 pub async fn get_message_resources(
     state: State<'_, AppState>,
     message_id: String,
-) -> Result<MessageResources, String> {
+) -> Result<MessageResources, AppError> {
     state
         .db
         .get_message_resources(&message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+/// Detach a search or fetch result from a message so it is left out of the context
+/// when the reply is regenerated. Detaching a search result also detaches the pages
+/// fetched for it. With `delete_file`, fetch results no longer attached to any message
+/// are deleted along with their stored content.
+#[tauri::command]
+pub async fn unlink_message_context(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    message_id: String,
+    context_type: ContextType,
+    context_id: String,
+    delete_file: bool,
+) -> Result<(), AppError> {
+    let fetch_ids = match context_type {
+        ContextType::FetchResult => vec![context_id],
+        ContextType::SearchResult => {
+            let search_result = state.db.get_search_result(&context_id).await?;
+            if search_result.message_id != message_id {
+                return Err(AppError::validation(format!(
+                    "Search result {} does not belong to message {}",
+                    context_id, message_id
+                )));
+            }
+            let fetches = state
+                .db
+                .get_fetch_results_by_source("search", &context_id)
+                .await?;
+            state.db.delete_search_result(&context_id).await?;
+            fetches.into_iter().map(|f| f.id).collect()
+        }
+    };
+
+    for fetch_id in fetch_ids {
+        state
+            .db
+            .unlink_message_context(&message_id, ContextType::FetchResult, &fetch_id)
+            .await?;
+        if delete_file {
+            delete_orphaned_fetch_result(&app, &state, &fetch_id).await?;
+        }
+    }
+
+    tracing::info!(
+        "🧹 [resources] Detached {} {} from message {}",
+        context_type,
+        context_id,
+        message_id
+    );
+    Ok(())
+}
+
+/// Delete a fetch result that no message uses anymore, and its files unless another
+/// fetch result stored the same content or favicon
+async fn delete_orphaned_fetch_result(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    fetch_id: &str,
+) -> Result<(), AppError> {
+    if state.db.count_fetch_result_links(fetch_id).await? > 0 {
+        return Ok(());
+    }
+    let Ok(fetch) = state.db.get_fetch_result(fetch_id).await else {
+        return Ok(());
+    };
+    state.db.delete_fetch_result(fetch_id).await?;
+    if state
+        .db
+        .count_fetch_results_by_storage_path(&fetch.storage_path)
+        .await?
+        == 0
+    {
+        crate::storage::delete_file(app, &fetch.storage_path)?;
+    }
+    if let Some(favicon_path) = &fetch.favicon_path
+        && state
+            .db
+            .count_fetch_results_by_favicon_path(favicon_path)
+            .await?
+            == 0
+    {
+        crate::storage::delete_file(app, favicon_path)?;
+    }
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn read_fetch_content(
     app: tauri::AppHandle,
     storage_path: String,
-) -> Result<String, String> {
-    crate::storage::read_content(&app, &storage_path).map_err(|e| e.to_string())
+) -> Result<String, AppError> {
+    let size = crate::storage::get_file_size(&app, &storage_path)?;
+    if size > MAX_WHOLE_FETCH_CONTENT_BYTES {
+        return Err(AppError::validation(format!(
+            "Content is too large to load at once ({} bytes); read it in pages",
+            size
+        )));
+    }
+    crate::storage::read_content(&app, &storage_path).map_err(AppError::from)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchContentMetadata {
+    pub total_size: u64,
+    /// Whether `read_fetch_content` will return the whole file
+    pub can_read_whole: bool,
+    /// Largest page `read_fetch_content_range` returns
+    pub max_range_length: u64,
+}
+
+#[tauri::command]
+pub async fn get_fetch_content_metadata(
+    app: tauri::AppHandle,
+    storage_path: String,
+) -> Result<FetchContentMetadata, AppError> {
+    let total_size = crate::storage::get_file_size(&app, &storage_path)?;
+    Ok(FetchContentMetadata {
+        total_size,
+        can_read_whole: total_size <= MAX_WHOLE_FETCH_CONTENT_BYTES,
+        max_range_length: MAX_FETCH_CONTENT_RANGE_BYTES,
+    })
+}
+
+/// Read a page of fetched content. Offsets are in bytes; pages end on character
+/// boundaries, so continue from the returned `next_offset`.
+#[tauri::command]
+pub async fn read_fetch_content_range(
+    app: tauri::AppHandle,
+    storage_path: String,
+    offset: u64,
+    length: u64,
+) -> Result<ContentRange, AppError> {
+    let length = length.min(MAX_FETCH_CONTENT_RANGE_BYTES);
+    crate::storage::read_content_range(&app, &storage_path, offset, length).map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn read_file_content(
     app: tauri::AppHandle,
     storage_path: String,
-) -> Result<String, String> {
-    crate::storage::read_content(&app, &storage_path).map_err(|e| e.to_string())
+) -> Result<String, AppError> {
+    crate::storage::read_content(&app, &storage_path).map_err(AppError::from)
 }
 
 // Read arbitrary text file from filesystem (for files selected via dialog)
 #[tauri::command]
-pub async fn read_text_file_from_path(path: String) -> Result<String, String> {
-    std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file {}: {}", path, e))
+pub async fn read_text_file_from_path(path: String) -> Result<String, AppError> {
+    std::fs::read_to_string(&path)
+        .map_err(|e| AppError::classify(format!("Failed to read file {}: {}", path, e)))
 }
 
 // Read arbitrary binary file as base64 (for files selected via dialog)
 #[tauri::command]
-pub async fn read_file_as_base64(path: String) -> Result<String, String> {
+pub async fn read_file_as_base64(path: String) -> Result<String, AppError> {
     use base64::{Engine as _, engine::general_purpose::STANDARD};
 
     let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read file {}: {}", path, e))?;
@@ -53,17 +190,16 @@ pub async fn read_file_as_base64(path: String) -> Result<String, String> {
 pub async fn read_image_base64(
     app: tauri::AppHandle,
     storage_path: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     use base64::{Engine as _, engine::general_purpose::STANDARD};
 
-    let bytes = crate::storage::read_binary(&app, &storage_path).map_err(|e| e.to_string())?;
+    let bytes = crate::storage::read_binary(&app, &storage_path)?;
     Ok(STANDARD.encode(&bytes))
 }
 
 #[tauri::command]
-pub fn get_attachment_url(app: tauri::AppHandle, storage_path: String) -> Result<String, String> {
-    let full_path =
-        crate::storage::get_full_path(&app, &storage_path).map_err(|e| e.to_string())?;
+pub fn get_attachment_url(app: tauri::AppHandle, storage_path: String) -> Result<String, AppError> {
+    let full_path = crate::storage::get_full_path(&app, &storage_path)?;
     Ok(full_path.to_string_lossy().to_string())
 }
 
@@ -72,19 +208,19 @@ pub fn copy_image_to_clipboard(
     app: tauri::AppHandle,
     storage_path: Option<String>,
     base64_data: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use base64::{Engine as _, engine::general_purpose::STANDARD};
     use std::borrow::Cow;
 
     let bytes = if let Some(path) = storage_path {
-        crate::storage::read_binary(&app, &path).map_err(|e| e.to_string())?
+        crate::storage::read_binary(&app, &path)?
     } else if let Some(data) = base64_data {
         let b64 = data.split_once(',').map(|(_, b)| b).unwrap_or(&data);
         STANDARD
             .decode(b64)
             .map_err(|e| format!("Failed to decode base64: {}", e))?
     } else {
-        return Err("No image data provided".to_string());
+        return Err(AppError::validation("No image data provided"));
     };
 
     let img =
@@ -1,5 +1,6 @@
 use super::AppState;
-use crate::models::SearchResults;
+use crate::error::AppError;
+use crate::models::{ConversationMessageMatch, SearchResults};
 use std::time::Instant;
 use tauri::State;
 
@@ -9,23 +10,15 @@ pub async fn search_chat_history(
     query: String,
     limit: Option<i64>,
     offset: Option<i64>,
-) -> Result<SearchResults, String> {
+) -> Result<SearchResults, AppError> {
     let limit = limit.unwrap_or(20);
     let offset = offset.unwrap_or(0);
 
     let start = Instant::now();
 
-    let messages = state
-        .db
-        .search_messages(&query, limit, offset)
-        .await
-        .map_err(|e| e.to_string())?;
+    let messages = state.db.search_messages(&query, limit, offset).await?;
 
-    let conversations = state
-        .db
-        .search_conversations(&query, 5)
-        .await
-        .map_err(|e| e.to_string())?;
+    let conversations = state.db.search_conversations(&query, 5).await?;
 
     let search_time_ms = start.elapsed().as_secs_f64() * 1000.0;
 
@@ -36,3 +29,17 @@ pub async fn search_chat_history(
         search_time_ms,
     })
 }
+
+/// Find-in-chat: matching messages of one conversation with snippets and ordinals
+#[tauri::command]
+pub async fn search_in_conversation(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    query: String,
+) -> Result<Vec<ConversationMessageMatch>, AppError> {
+    state
+        .db
+        .search_in_conversation(&conversation_id, &query)
+        .await
+        .map_err(AppError::from)
+}
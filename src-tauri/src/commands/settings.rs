@@ -1,13 +1,30 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::models::Setting;
+use crate::prompts;
+use serde::Serialize;
 use tauri::State;
 
+/// Settings key: system prompt used when neither an assistant nor the conversation
+/// provides one (unset = `prompts::DEFAULT_ASSISTANT_SYSTEM_PROMPT`)
+pub const DEFAULT_SYSTEM_PROMPT_KEY: &str = "default_system_prompt";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DefaultSystemPrompt {
+    /// The prompt in effect
+    pub prompt: String,
+    /// Whether `prompt` was set by the user rather than built in
+    pub is_custom: bool,
+    /// The built-in prompt that `reset_default_system_prompt` restores
+    pub builtin: String,
+}
+
 #[tauri::command]
 pub async fn get_setting(
     state: State<'_, AppState>,
     key: String,
-) -> Result<Option<String>, String> {
-    state.db.get_setting(&key).await.map_err(|e| e.to_string())
+) -> Result<Option<String>, AppError> {
+    state.db.get_setting(&key).await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -15,30 +32,98 @@ pub async fn set_setting(
     state: State<'_, AppState>,
     key: String,
     value: String,
-) -> Result<(), String> {
-    state
-        .db
-        .set_setting(&key, &value)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(), AppError> {
+    state.db.set_setting(&key, &value).await?;
+    if key == crate::web_fetch::FETCH_USER_AGENT_KEY
+        || key == crate::web_fetch::FETCH_ACCEPT_LANGUAGE_KEY
+    {
+        crate::web_fetch::reload_fetch_identity(&state.db).await;
+    }
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn get_all_settings(state: State<'_, AppState>) -> Result<Vec<Setting>, String> {
-    state.db.get_all_settings().await.map_err(|e| e.to_string())
+pub async fn get_all_settings(state: State<'_, AppState>) -> Result<Vec<Setting>, AppError> {
+    state.db.get_all_settings().await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn set_log_level(state: State<'_, AppState>, level: String) -> Result<(), String> {
+pub async fn set_log_level(state: State<'_, AppState>, level: String) -> Result<(), AppError> {
     // Set the log level in the logger
-    crate::logger::set_log_level(&level).map_err(|e| e.to_string())?;
+    crate::logger::set_log_level(&level)?;
 
     // Save the log level to database for persistence
+    state.db.set_setting("log_level_rust", &level).await?;
+
+    Ok(())
+}
+
+/// Zip recent backend and frontend logs, with API keys and tokens redacted, to
+/// `destination_path` so they can be attached to bug reports. Returns the path written.
+#[tauri::command]
+pub async fn export_logs(
+    destination_path: String,
+    days: Option<usize>,
+) -> Result<String, AppError> {
+    let path = tokio::task::spawn_blocking(move || {
+        crate::logger::export_logs(std::path::Path::new(&destination_path), days)
+    })
+    .await??;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn get_default_system_prompt(
+    state: State<'_, AppState>,
+) -> Result<DefaultSystemPrompt, AppError> {
+    let custom = custom_default_system_prompt(&state).await;
+    Ok(DefaultSystemPrompt {
+        is_custom: custom.is_some(),
+        prompt: custom.unwrap_or_else(|| prompts::DEFAULT_ASSISTANT_SYSTEM_PROMPT.to_string()),
+        builtin: prompts::DEFAULT_ASSISTANT_SYSTEM_PROMPT.to_string(),
+    })
+}
+
+#[tauri::command]
+pub async fn set_default_system_prompt(
+    state: State<'_, AppState>,
+    prompt: String,
+) -> Result<(), AppError> {
+    if prompt.trim().is_empty() {
+        return Err(AppError::validation(
+            "The default system prompt can't be empty; reset it to restore the built-in one",
+        ));
+    }
     state
         .db
-        .set_setting("log_level_rust", &level)
+        .set_setting(DEFAULT_SYSTEM_PROMPT_KEY, &prompt)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)
+}
 
-    Ok(())
+/// Go back to the built-in default system prompt
+#[tauri::command]
+pub async fn reset_default_system_prompt(state: State<'_, AppState>) -> Result<(), AppError> {
+    state
+        .db
+        .delete_setting(DEFAULT_SYSTEM_PROMPT_KEY)
+        .await
+        .map_err(AppError::from)
+}
+
+/// The system prompt for conversations without an assistant or custom prompt
+pub(crate) async fn default_system_prompt(state: &AppState) -> String {
+    custom_default_system_prompt(state)
+        .await
+        .unwrap_or_else(|| prompts::DEFAULT_ASSISTANT_SYSTEM_PROMPT.to_string())
+}
+
+async fn custom_default_system_prompt(state: &AppState) -> Option<String> {
+    state
+        .db
+        .get_setting(DEFAULT_SYSTEM_PROMPT_KEY)
+        .await
+        .ok()
+        .flatten()
+        .filter(|prompt| !prompt.trim().is_empty())
 }
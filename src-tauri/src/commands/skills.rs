@@ -1,25 +1,26 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::models::{CreateSkillRequest, Skill};
 use crate::skills::{ScanDirectory, SkillScanner};
 use serde::{Deserialize, Serialize};
 use tauri::{Manager, State};
 
 #[tauri::command]
-pub async fn list_skills(state: State<'_, AppState>) -> Result<Vec<Skill>, String> {
-    state.db.list_skills().await.map_err(|e| e.to_string())
+pub async fn list_skills(state: State<'_, AppState>) -> Result<Vec<Skill>, AppError> {
+    state.db.list_skills().await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn get_skill(state: State<'_, AppState>, id: String) -> Result<Option<Skill>, String> {
-    state.db.get_skill(&id).await.map_err(|e| e.to_string())
+pub async fn get_skill(state: State<'_, AppState>, id: String) -> Result<Option<Skill>, AppError> {
+    state.db.get_skill(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn create_skill(
     state: State<'_, AppState>,
     req: CreateSkillRequest,
-) -> Result<Skill, String> {
-    state.db.create_skill(req).await.map_err(|e| e.to_string())
+) -> Result<Skill, AppError> {
+    state.db.create_skill(req).await.map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -27,34 +28,34 @@ pub async fn update_skill(
     state: State<'_, AppState>,
     id: String,
     req: CreateSkillRequest,
-) -> Result<Skill, String> {
+) -> Result<Skill, AppError> {
     state
         .db
         .update_skill(&id, req)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn delete_skill(state: State<'_, AppState>, id: String) -> Result<(), String> {
-    state.db.delete_skill(&id).await.map_err(|e| e.to_string())
+pub async fn delete_skill(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_skill(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
-pub async fn toggle_skill(state: State<'_, AppState>, id: String) -> Result<Skill, String> {
-    state.db.toggle_skill(&id).await.map_err(|e| e.to_string())
+pub async fn toggle_skill(state: State<'_, AppState>, id: String) -> Result<Skill, AppError> {
+    state.db.toggle_skill(&id).await.map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn set_all_skills_enabled(
     state: State<'_, AppState>,
     enabled: bool,
-) -> Result<Vec<Skill>, String> {
+) -> Result<Vec<Skill>, AppError> {
     state
         .db
         .set_all_skills_enabled(enabled)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Well-known external skill directories (beyond ~/.chatshell/skills)
@@ -78,7 +79,7 @@ pub struct SkillSourceInfo {
 pub async fn get_skill_sources(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<Vec<SkillSourceInfo>, String> {
+) -> Result<Vec<SkillSourceInfo>, AppError> {
     let home = dirs::home_dir().unwrap_or_default();
     let app_data_dir = app
         .path()
@@ -131,7 +132,7 @@ pub async fn set_skill_source_enabled(
     state: State<'_, AppState>,
     source: String,
     enabled: bool,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let setting_key = EXTERNAL_SKILL_SOURCES
         .iter()
         .find(|(s, _, _)| *s == source)
@@ -142,7 +143,7 @@ pub async fn set_skill_source_enabled(
         .db
         .set_setting(setting_key, if enabled { "true" } else { "false" })
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Build the list of directories to scan based on current settings
@@ -205,11 +206,11 @@ async fn build_scan_directories(
 pub async fn scan_skills(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<Vec<Skill>, String> {
+) -> Result<Vec<Skill>, AppError> {
     let dirs = build_scan_directories(&state, &app).await?;
     let scanner = SkillScanner::new(dirs);
 
-    let discovered = scanner.scan_all().await.map_err(|e| e.to_string())?;
+    let discovered = scanner.scan_all().await?;
 
     let discovered_keys: std::collections::HashSet<(String, String)> = discovered
         .iter()
@@ -232,7 +233,7 @@ pub async fn scan_skills(
     }
 
     // Remove stale skills that no longer exist on the filesystem
-    let all_db_skills = state.db.list_skills().await.map_err(|e| e.to_string())?;
+    let all_db_skills = state.db.list_skills().await?;
     for skill in &all_db_skills {
         if !discovered_keys.contains(&(skill.name.clone(), skill.source.clone())) {
             tracing::info!(
@@ -250,12 +251,12 @@ pub async fn scan_skills(
         }
     }
 
-    state.db.list_skills().await.map_err(|e| e.to_string())
+    state.db.list_skills().await.map_err(AppError::from)
 }
 
 /// Open a skill source directory in the system file manager
 #[tauri::command]
-pub async fn open_skills_directory(app: tauri::AppHandle, source: String) -> Result<(), String> {
+pub async fn open_skills_directory(app: tauri::AppHandle, source: String) -> Result<(), AppError> {
     let home = dirs::home_dir().ok_or_else(|| "Cannot determine home directory".to_string())?;
 
     let dir = match source.as_str() {
@@ -271,7 +272,7 @@ pub async fn open_skills_directory(app: tauri::AppHandle, source: String) -> Res
         .map_err(|e| format!("Failed to create skills directory: {}", e))?;
 
     tauri_plugin_opener::open_path(dir, None::<&str>)
-        .map_err(|e| format!("Failed to open skills directory: {}", e))
+        .map_err(|e| AppError::classify(format!("Failed to open skills directory: {}", e)))
 }
 
 /// Read the content of a skill's SKILL.md file
@@ -279,9 +280,9 @@ pub async fn open_skills_directory(app: tauri::AppHandle, source: String) -> Res
 pub async fn read_skill_content(
     _state: State<'_, AppState>,
     path: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let skill_md = std::path::PathBuf::from(&path).join("SKILL.md");
     tokio::fs::read_to_string(&skill_md)
         .await
-        .map_err(|e| format!("Failed to read SKILL.md: {}", e))
+        .map_err(|e| AppError::classify(format!("Failed to read SKILL.md: {}", e)))
 }
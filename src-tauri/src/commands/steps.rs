@@ -1,4 +1,5 @@
 use super::AppState;
+use crate::error::AppError;
 use crate::models::{ProcessStep, SearchDecision, ThinkingStep};
 use tauri::State;
 
@@ -10,34 +11,34 @@ use tauri::State;
 pub async fn get_message_steps(
     state: State<'_, AppState>,
     message_id: String,
-) -> Result<Vec<ProcessStep>, String> {
+) -> Result<Vec<ProcessStep>, AppError> {
     state
         .db
         .get_message_steps(&message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_thinking_step(
     state: State<'_, AppState>,
     id: String,
-) -> Result<ThinkingStep, String> {
+) -> Result<ThinkingStep, AppError> {
     state
         .db
         .get_thinking_step(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub async fn get_search_decision(
     state: State<'_, AppState>,
     id: String,
-) -> Result<SearchDecision, String> {
+) -> Result<SearchDecision, AppError> {
     state
         .db
         .get_search_decision(&id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
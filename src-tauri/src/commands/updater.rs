@@ -0,0 +1,21 @@
+use crate::error::AppError;
+use crate::updater::{self, UpdateInfo};
+
+/// Check the configured channel for a newer version
+#[tauri::command]
+pub async fn check_for_update(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, AppError> {
+    updater::check(&app).await.map_err(AppError::from)
+}
+
+/// Download the update found by `check_for_update`, emitting `update-download-progress`
+#[tauri::command]
+pub async fn download_update(app: tauri::AppHandle) -> Result<(), AppError> {
+    updater::download(&app).await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn install_and_restart(app: tauri::AppHandle) -> Result<(), AppError> {
+    updater::install_and_restart(&app)
+        .await
+        .map_err(AppError::from)
+}
@@ -0,0 +1,199 @@
+use super::AppState;
+use crate::error::{AppError, ErrorKind};
+use crate::events::{self, ConversationBudgetWarning, SpendCapReached};
+use crate::models::{
+    BudgetStatus, ConversationSettings, MONTHLY_SPEND_CAP_KEY, NewUsageEntry, SpendReport,
+    UpdateConversationSettingsRequest, UsageStats, month_bounds,
+};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_usage_stats(
+    state: State<'_, AppState>,
+    conversation_id: Option<String>,
+    since: Option<String>,
+) -> Result<UsageStats, AppError> {
+    state
+        .db
+        .get_usage_stats(conversation_id.as_deref(), since.as_deref())
+        .await
+        .map_err(AppError::from)
+}
+
+/// Set or clear (`None`) the conversation's token and cost budgets. Cost is in USD.
+#[tauri::command]
+pub async fn set_conversation_budget(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    token_budget: Option<i64>,
+    cost_budget: Option<f64>,
+) -> Result<ConversationSettings, AppError> {
+    if token_budget.is_some_and(|b| b <= 0) {
+        return Err(AppError::validation("Token budget must be positive"));
+    }
+    if cost_budget.is_some_and(|b| !b.is_finite() || b <= 0.0) {
+        return Err(AppError::validation(
+            "Cost budget must be a positive amount",
+        ));
+    }
+
+    state
+        .db
+        .update_conversation_settings(
+            &conversation_id,
+            UpdateConversationSettingsRequest {
+                token_budget: Some(token_budget),
+                cost_budget: Some(cost_budget),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(AppError::from)
+}
+
+/// Usage against the conversation's budgets, or `None` when no budget is set
+#[tauri::command]
+pub async fn get_conversation_budget_status(
+    state: State<'_, AppState>,
+    conversation_id: String,
+) -> Result<Option<BudgetStatus>, AppError> {
+    conversation_budget_status(&state, &conversation_id).await
+}
+
+async fn conversation_budget_status(
+    state: &AppState,
+    conversation_id: &str,
+) -> Result<Option<BudgetStatus>, AppError> {
+    let settings = state.db.get_conversation_settings(conversation_id).await?;
+    if settings.token_budget.is_none() && settings.cost_budget.is_none() {
+        return Ok(None);
+    }
+    let stats = state
+        .db
+        .get_usage_stats(Some(conversation_id), None)
+        .await?;
+    Ok(BudgetStatus::evaluate(
+        settings.token_budget,
+        settings.cost_budget,
+        &stats,
+    ))
+}
+
+/// Refuse a send that is expected to go over the conversation's budget, and warn
+/// once usage gets close to it
+pub(crate) async fn enforce_conversation_budget(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+) -> Result<(), AppError> {
+    let Some(status) = conversation_budget_status(state, conversation_id).await? else {
+        return Ok(());
+    };
+
+    if status.exceeded {
+        tracing::warn!(
+            "💸 [budget] Blocked send in conversation {}: {} tokens, ${:.4} used",
+            conversation_id,
+            status.used_tokens,
+            status.used_cost
+        );
+        return Err(AppError::new(
+            ErrorKind::BudgetExceeded,
+            status.exceeded_message(),
+        ));
+    }
+    if status.warning {
+        events::emit(
+            app,
+            ConversationBudgetWarning {
+                conversation_id: conversation_id.to_string(),
+                status,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Estimated spend per provider and model for a month (`YYYY-MM`, UTC), the
+/// current month by default. Covers deleted conversations too.
+#[tauri::command]
+pub async fn get_spend_report(
+    state: State<'_, AppState>,
+    month: Option<String>,
+) -> Result<SpendReport, AppError> {
+    let month = month.unwrap_or_else(current_month);
+    let (start, end) = month_bounds(&month).map_err(AppError::validation)?;
+    let cap = monthly_spend_cap(&state).await;
+    state
+        .db
+        .get_spend_report(&month, &start, &end, cap)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Set or clear (`None`) the monthly spend soft cap in USD. Going over it only
+/// raises a `spend-cap-reached` event; sends are never blocked.
+#[tauri::command]
+pub async fn set_monthly_spend_cap(
+    state: State<'_, AppState>,
+    cap: Option<f64>,
+) -> Result<(), AppError> {
+    if cap.is_some_and(|c| !c.is_finite() || c <= 0.0) {
+        return Err(AppError::validation("Spend cap must be a positive amount"));
+    }
+    let value = cap.map(|c| c.to_string()).unwrap_or_default();
+    state
+        .db
+        .set_setting(MONTHLY_SPEND_CAP_KEY, &value)
+        .await
+        .map_err(AppError::from)
+}
+
+fn current_month() -> String {
+    chrono::Utc::now().format("%Y-%m").to_string()
+}
+
+async fn monthly_spend_cap(state: &AppState) -> Option<f64> {
+    state
+        .db
+        .get_setting(MONTHLY_SPEND_CAP_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|c| *c > 0.0)
+}
+
+/// Add a reply's usage to the ledger and raise `spend-cap-reached` when it takes
+/// this month's spend over the soft cap
+pub(crate) async fn record_spend(app: &tauri::AppHandle, state: &AppState, entry: NewUsageEntry) {
+    if let Err(e) = state.db.record_usage(&entry).await {
+        tracing::warn!("⚠️ [usage] Failed to record usage in ledger: {}", e);
+        return;
+    }
+
+    let (Some(cost), Some(cap)) = (entry.cost, monthly_spend_cap(state).await) else {
+        return;
+    };
+    let month = current_month();
+    let Ok((start, end)) = month_bounds(&month) else {
+        return;
+    };
+    let spent = match state.db.get_ledger_cost(&start, &end).await {
+        Ok(spent) => spent,
+        Err(e) => {
+            tracing::warn!("⚠️ [usage] Failed to total monthly spend: {}", e);
+            return;
+        }
+    };
+
+    // Only the reply that crosses the cap raises the event
+    if spent >= cap && spent - cost < cap {
+        tracing::warn!(
+            "💸 [usage] Monthly spend ${:.2} reached the ${:.2} cap",
+            spent,
+            cap
+        );
+        events::emit(app, SpendCapReached { month, spent, cap });
+    }
+}
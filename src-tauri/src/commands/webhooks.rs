@@ -0,0 +1,70 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::{Webhook, WebhookEvent, WebhookInput};
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_webhooks(state: State<'_, AppState>) -> Result<Vec<Webhook>, AppError> {
+    state.db.list_webhooks().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn create_webhook(
+    state: State<'_, AppState>,
+    input: WebhookInput,
+) -> Result<Webhook, AppError> {
+    validate_webhook_url(&input.url)?;
+    state.db.create_webhook(input).await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn update_webhook(
+    state: State<'_, AppState>,
+    id: String,
+    input: WebhookInput,
+) -> Result<Webhook, AppError> {
+    validate_webhook_url(&input.url)?;
+    state
+        .db
+        .update_webhook(&id, input)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn delete_webhook(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_webhook(&id).await.map_err(AppError::from)
+}
+
+/// Send a sample `chat_complete` payload to a webhook
+#[tauri::command]
+pub async fn test_webhook(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    let webhook = state
+        .db
+        .list_webhooks()
+        .await?
+        .into_iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| format!("Webhook not found: {}", id))?;
+
+    let body = serde_json::json!({
+        "event": WebhookEvent::ChatComplete.id(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "test": true,
+        "data": {
+            "conversation_id": "test",
+            "message": { "content": "This is a test delivery from ChatShell." },
+        },
+    })
+    .to_string();
+
+    Ok(crate::webhooks::deliver(&webhook, WebhookEvent::ChatComplete, &body).await?)
+}
+
+fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Webhook URL must use http or https".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use super::AppState;
+use crate::error::AppError;
+use crate::models::{CreateWorkspaceRequest, UpdateWorkspaceRequest, Workspace, WorkspaceSettings};
+use crate::web_search::SearchProvider;
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_workspaces(state: State<'_, AppState>) -> Result<Vec<Workspace>, AppError> {
+    state.db.list_workspaces().await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn create_workspace(
+    state: State<'_, AppState>,
+    req: CreateWorkspaceRequest,
+) -> Result<Workspace, AppError> {
+    if req.name.trim().is_empty() {
+        return Err(AppError::validation("Workspace name is required"));
+    }
+    validate_settings(&state, &req.settings).await?;
+    state.db.create_workspace(req).await.map_err(AppError::from)
+}
+
+#[tauri::command]
+pub async fn update_workspace(
+    state: State<'_, AppState>,
+    id: String,
+    req: UpdateWorkspaceRequest,
+) -> Result<Workspace, AppError> {
+    if req.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        return Err(AppError::validation("Workspace name is required"));
+    }
+    if let Some(settings) = &req.settings {
+        validate_settings(&state, settings).await?;
+    }
+    if state.db.get_workspace(&id).await?.is_none() {
+        return Err(AppError::not_found(format!("Workspace not found: {}", id)));
+    }
+    state
+        .db
+        .update_workspace(&id, req)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Delete a workspace. Its conversations are kept.
+#[tauri::command]
+pub async fn delete_workspace(state: State<'_, AppState>, id: String) -> Result<(), AppError> {
+    state.db.delete_workspace(&id).await.map_err(AppError::from)
+}
+
+/// Move a conversation into a workspace, or out of any (`None`). Settings the
+/// conversation hasn't set itself follow the new workspace from the next message on.
+#[tauri::command]
+pub async fn set_conversation_workspace(
+    state: State<'_, AppState>,
+    conversation_id: String,
+    workspace_id: Option<String>,
+) -> Result<(), AppError> {
+    if state.db.get_conversation(&conversation_id).await?.is_none() {
+        return Err(AppError::not_found(format!(
+            "Conversation not found: {}",
+            conversation_id
+        )));
+    }
+    if let Some(id) = &workspace_id
+        && state.db.get_workspace(id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!("Workspace not found: {}", id)));
+    }
+
+    state
+        .db
+        .set_conversation_workspace(&conversation_id, workspace_id.as_deref())
+        .await
+        .map_err(AppError::from)
+}
+
+async fn validate_settings(state: &AppState, settings: &WorkspaceSettings) -> Result<(), AppError> {
+    settings.validate().map_err(AppError::validation)?;
+    if let Some(provider) = &settings.search_provider
+        && SearchProvider::from_id(provider).is_none()
+    {
+        return Err(AppError::validation(format!(
+            "Unknown search provider: {}",
+            provider
+        )));
+    }
+    if let Some(model_id) = &settings.model_id
+        && state.db.get_model(model_id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Model not found: {}",
+            model_id
+        )));
+    }
+    if let Some(assistant_id) = &settings.assistant_id
+        && state.db.get_assistant(assistant_id).await?.is_none()
+    {
+        return Err(AppError::not_found(format!(
+            "Assistant not found: {}",
+            assistant_id
+        )));
+    }
+    Ok(())
+}
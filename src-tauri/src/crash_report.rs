@@ -0,0 +1,248 @@
+//! Crash capture
+//!
+//! A panic hook writes a crash report (message, location, backtrace, versions and the most
+//! recent log events) to `crashes/` in the app data directory before the process goes
+//! down. On the next launch `get_last_crash_report` returns it so the user can save it
+//! for a bug report, and `dismiss_crash_report` marks it as seen.
+
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+/// Log events kept in memory for the next crash report
+const MAX_BREADCRUMBS: usize = 50;
+
+/// Reports kept on disk; older ones are deleted at startup
+const MAX_REPORTS: usize = 10;
+
+const REPORT_PREFIX: &str = "crash-";
+const REPORT_SUFFIX: &str = ".json";
+const SEEN_SUFFIX: &str = ".seen.json";
+
+static CRASH_DIR: once_cell::sync::OnceCell<PathBuf> = once_cell::sync::OnceCell::new();
+
+lazy_static! {
+    static ref BREADCRUMBS: Mutex<VecDeque<String>> =
+        Mutex::new(VecDeque::with_capacity(MAX_BREADCRUMBS));
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: String,
+    pub app_version: String,
+    pub db_schema_version: i32,
+    pub os: String,
+    pub arch: String,
+    pub thread: String,
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Most recent info/warn/error log lines, secrets redacted
+    pub recent_events: Vec<String>,
+}
+
+/// Keeps the latest log events so a crash report can show what led up to it
+pub struct BreadcrumbLayer;
+
+impl<S: Subscriber> Layer<S> for BreadcrumbLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > Level::INFO {
+            return;
+        }
+
+        let mut visitor = EventVisitor(String::new());
+        event.record(&mut visitor);
+        let line = format!(
+            "{} {} {}: {}",
+            chrono::Utc::now().format("%H:%M:%S%.3f"),
+            metadata.level(),
+            metadata.target(),
+            visitor.0
+        );
+
+        // Never block: the panic hook may be reading while a panic unwinds through here
+        if let Ok(mut breadcrumbs) = BREADCRUMBS.try_lock() {
+            if breadcrumbs.len() == MAX_BREADCRUMBS {
+                breadcrumbs.pop_front();
+            }
+            breadcrumbs.push_back(line);
+        }
+    }
+}
+
+struct EventVisitor(String);
+
+impl Visit for EventVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Install the panic hook writing reports to `crash_dir`. The previous hook still runs.
+pub fn install(crash_dir: PathBuf) -> Result<()> {
+    std::fs::create_dir_all(&crash_dir)?;
+    prune_reports(&crash_dir);
+    let _ = CRASH_DIR.set(crash_dir);
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_report(info) {
+            Ok(path) => tracing::error!("💥 [crash] Panic captured, report written to {:?}", path),
+            Err(e) => tracing::error!("💥 [crash] Panic captured, failed to write report: {}", e),
+        }
+        previous(info);
+    }));
+    Ok(())
+}
+
+fn write_report(info: &PanicHookInfo<'_>) -> Result<PathBuf> {
+    let dir = CRASH_DIR
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("Crash reporter not installed"))?;
+
+    let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic payload".to_string()
+    };
+    let recent_events = match BREADCRUMBS.try_lock() {
+        Ok(breadcrumbs) => breadcrumbs
+            .iter()
+            .map(|line| crate::logger::redact_secrets(line))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let now = chrono::Utc::now();
+    let report = CrashReport {
+        id: now.format("%Y%m%dT%H%M%S%3fZ").to_string(),
+        timestamp: now.to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        db_schema_version: crate::db::CURRENT_SCHEMA_VERSION,
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .to_string(),
+        message: crate::logger::redact_secrets(&message),
+        location: info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+        recent_events,
+    };
+
+    let path = dir.join(format!("{}{}{}", REPORT_PREFIX, report.id, REPORT_SUFFIX));
+    std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    Ok(path)
+}
+
+/// Report files in `dir` (seen or not), oldest first
+fn report_files(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|name| name.starts_with(REPORT_PREFIX) && name.ends_with(REPORT_SUFFIX))
+                .collect()
+        })
+        .unwrap_or_default();
+    // Ids are timestamps, so names sort chronologically
+    names.sort();
+    names
+}
+
+fn prune_reports(dir: &Path) {
+    let names = report_files(dir);
+    for name in names.iter().take(names.len().saturating_sub(MAX_REPORTS)) {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+}
+
+/// The most recent report not yet dismissed
+pub fn last_unseen_report() -> Result<Option<CrashReport>> {
+    let Some(dir) = CRASH_DIR.get() else {
+        return Ok(None);
+    };
+    let Some(name) = report_files(dir)
+        .into_iter()
+        .rev()
+        .find(|name| !name.ends_with(SEEN_SUFFIX))
+    else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(dir.join(name))?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// Mark a report as seen so it isn't offered again
+pub fn dismiss(id: &str) -> Result<()> {
+    let dir = CRASH_DIR
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("Crash reporter not installed"))?;
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        anyhow::bail!("Invalid crash report id: {}", id);
+    }
+    let unseen = dir.join(format!("{}{}{}", REPORT_PREFIX, id, REPORT_SUFFIX));
+    let seen = dir.join(format!("{}{}{}", REPORT_PREFIX, id, SEEN_SUFFIX));
+    std::fs::rename(unseen, seen)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_keeps_newest_reports() {
+        let dir = std::env::temp_dir().join(format!(
+            "chatshell_crash_test_{:x}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        for i in 0..MAX_REPORTS + 3 {
+            let suffix = if i % 2 == 0 {
+                SEEN_SUFFIX
+            } else {
+                REPORT_SUFFIX
+            };
+            std::fs::write(
+                dir.join(format!("{}2026{:04}{}", REPORT_PREFIX, i, suffix)),
+                "{}",
+            )
+            .unwrap();
+        }
+        std::fs::write(dir.join("unrelated.txt"), "").unwrap();
+
+        prune_reports(&dir);
+        let remaining = report_files(&dir);
+        assert_eq!(remaining.len(), MAX_REPORTS);
+        assert!(remaining[0].starts_with("crash-20260003"));
+        assert!(dir.join("unrelated.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
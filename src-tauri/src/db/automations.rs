@@ -0,0 +1,311 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+
+use super::Database;
+use crate::models::{
+    Automation, AutomationEvent, AutomationInput, AutomationRun, AutomationRunStatus,
+};
+
+const AUTOMATION_COLUMNS: &str = "id, name, trigger, action, is_enabled, last_run_at, next_run_at, last_error, trigger_state, created_at, updated_at";
+
+const AUTOMATION_RUN_COLUMNS: &str =
+    "id, automation_id, event, status, output, error, started_at, finished_at";
+
+/// Older runs of an automation are deleted as new ones start
+const MAX_RUNS_PER_AUTOMATION: i64 = 100;
+
+fn automation_from_row(row: SqliteRow) -> Result<Automation> {
+    let trigger: String = row.get("trigger");
+    let action: String = row.get("action");
+    let is_enabled: i32 = row.get("is_enabled");
+    Ok(Automation {
+        id: row.get("id"),
+        name: row.get("name"),
+        trigger: serde_json::from_str(&trigger)?,
+        action: serde_json::from_str(&action)?,
+        is_enabled: is_enabled != 0,
+        last_run_at: row.get("last_run_at"),
+        next_run_at: row.get("next_run_at"),
+        last_error: row.get("last_error"),
+        trigger_state: row.get("trigger_state"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+fn automation_run_from_row(row: SqliteRow) -> Result<AutomationRun> {
+    let event: String = row.get("event");
+    let status: String = row.get("status");
+    Ok(AutomationRun {
+        id: row.get("id"),
+        automation_id: row.get("automation_id"),
+        event: serde_json::from_str(&event)?,
+        status: AutomationRunStatus::from_id(&status)
+            .ok_or_else(|| anyhow::anyhow!("Unknown automation run status: {}", status))?,
+        output: row.get("output"),
+        error: row.get("error"),
+        started_at: row.get("started_at"),
+        finished_at: row.get("finished_at"),
+    })
+}
+
+impl Database {
+    /// `next_run_at` is computed by the scheduler (`None` unless the trigger is an
+    /// enabled schedule)
+    pub async fn create_automation(
+        &self,
+        input: AutomationInput,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Automation> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO automations (id, name, trigger, action, is_enabled, next_run_at, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(input.name.trim())
+        .bind(serde_json::to_string(&input.trigger)?)
+        .bind(serde_json::to_string(&input.action)?)
+        .bind(if input.is_enabled { 1 } else { 0 })
+        .bind(next_run_at.map(|t| t.to_rfc3339()))
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_automation(&id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created automation"))
+    }
+
+    pub async fn get_automation(&self, id: &str) -> Result<Option<Automation>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM automations WHERE id = ?",
+            AUTOMATION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(automation_from_row).transpose()
+    }
+
+    pub async fn list_automations(&self) -> Result<Vec<Automation>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM automations ORDER BY name",
+            AUTOMATION_COLUMNS
+        ))
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(automation_from_row).collect()
+    }
+
+    /// Enabled automations whose trigger has the given `type`
+    pub async fn list_enabled_automations_by_trigger(
+        &self,
+        trigger_kind: &str,
+    ) -> Result<Vec<Automation>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM automations
+             WHERE is_enabled = 1 AND json_extract(trigger, '$.type') = ?
+             ORDER BY created_at",
+            AUTOMATION_COLUMNS
+        ))
+        .bind(trigger_kind)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(automation_from_row).collect()
+    }
+
+    /// Enabled automations whose next run is at or before `now`
+    pub async fn list_due_automations(&self, now: DateTime<Utc>) -> Result<Vec<Automation>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM automations
+             WHERE is_enabled = 1 AND next_run_at IS NOT NULL AND next_run_at <= ?
+             ORDER BY next_run_at",
+            AUTOMATION_COLUMNS
+        ))
+        .bind(now.to_rfc3339())
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(automation_from_row).collect()
+    }
+
+    /// Replace an automation's rule. Trigger bookkeeping starts over, so a folder
+    /// watch treats the folder's current files as seen.
+    pub async fn update_automation(
+        &self,
+        id: &str,
+        input: AutomationInput,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Automation> {
+        let now = Utc::now().to_rfc3339();
+        let updated = sqlx::query(
+            "UPDATE automations SET name = ?, trigger = ?, action = ?, is_enabled = ?, next_run_at = ?,
+             trigger_state = NULL, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(input.name.trim())
+        .bind(serde_json::to_string(&input.trigger)?)
+        .bind(serde_json::to_string(&input.action)?)
+        .bind(if input.is_enabled { 1 } else { 0 })
+        .bind(next_run_at.map(|t| t.to_rfc3339()))
+        .bind(&now)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(anyhow::anyhow!("Automation not found: {}", id));
+        }
+
+        self.get_automation(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Automation not found: {}", id))
+    }
+
+    /// Delete an automation with its run history
+    pub async fn delete_automation(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM automations WHERE id = ?")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_automation_next_run(
+        &self,
+        id: &str,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE automations SET next_run_at = ? WHERE id = ?")
+            .bind(next_run_at.map(|t| t.to_rfc3339()))
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_automation_trigger_state(&self, id: &str, trigger_state: &str) -> Result<()> {
+        sqlx::query("UPDATE automations SET trigger_state = ? WHERE id = ?")
+            .bind(trigger_state)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Remember the conversation created for a `run_prompt` action's replies
+    pub async fn set_automation_conversation(&self, id: &str, conversation_id: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE automations SET action = json_set(action, '$.conversation_id', ?) WHERE id = ?",
+        )
+        .bind(conversation_id)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of a run. Only successful runs move `last_run_at`, which
+    /// feed sources use to list just the entries published since.
+    pub async fn record_automation_run(&self, id: &str, error: Option<&str>) -> Result<()> {
+        match error {
+            Some(error) => {
+                sqlx::query("UPDATE automations SET last_error = ? WHERE id = ?")
+                    .bind(error)
+                    .bind(id)
+                    .execute(self.pool.as_ref())
+                    .await?;
+            }
+            None => {
+                sqlx::query(
+                    "UPDATE automations SET last_run_at = ?, last_error = NULL WHERE id = ?",
+                )
+                .bind(Utc::now().to_rfc3339())
+                .bind(id)
+                .execute(self.pool.as_ref())
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a running entry to an automation's history, dropping its oldest entries
+    /// beyond [`MAX_RUNS_PER_AUTOMATION`]
+    pub async fn start_automation_run(
+        &self,
+        automation_id: &str,
+        event: &AutomationEvent,
+    ) -> Result<String> {
+        let id = Uuid::now_v7().to_string();
+        sqlx::query(
+            "INSERT INTO automation_runs (id, automation_id, event, status, started_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(automation_id)
+        .bind(serde_json::to_string(event)?)
+        .bind(AutomationRunStatus::Running.id())
+        .bind(Utc::now().to_rfc3339())
+        .execute(self.pool.as_ref())
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM automation_runs WHERE automation_id = ? AND id NOT IN (
+                SELECT id FROM automation_runs WHERE automation_id = ?
+                ORDER BY started_at DESC LIMIT ?
+             )",
+        )
+        .bind(automation_id)
+        .bind(automation_id)
+        .bind(MAX_RUNS_PER_AUTOMATION)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(id)
+    }
+
+    pub async fn finish_automation_run(
+        &self,
+        id: &str,
+        result: Result<Option<&str>, &str>,
+    ) -> Result<()> {
+        let (status, output, error) = match result {
+            Ok(output) => (AutomationRunStatus::Succeeded, output, None),
+            Err(error) => (AutomationRunStatus::Failed, None, Some(error)),
+        };
+        sqlx::query(
+            "UPDATE automation_runs SET status = ?, output = ?, error = ?, finished_at = ? WHERE id = ?",
+        )
+        .bind(status.id())
+        .bind(output)
+        .bind(error)
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Newest first
+    pub async fn list_automation_runs(
+        &self,
+        automation_id: &str,
+        limit: i64,
+    ) -> Result<Vec<AutomationRun>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM automation_runs WHERE automation_id = ?
+             ORDER BY started_at DESC LIMIT ?",
+            AUTOMATION_RUN_COLUMNS
+        ))
+        .bind(automation_id)
+        .bind(limit)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(automation_run_from_row).collect()
+    }
+}
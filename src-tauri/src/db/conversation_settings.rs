@@ -3,29 +3,35 @@ use sqlx::Row;
 
 use super::Database;
 use crate::models::{
-    ConversationSettings, ModelParameterOverrides, PromptMode, UpdateConversationSettingsRequest,
+    ConversationSettings, HistoryMode, ModelParameterOverrides, PromptMode,
+    UpdateConversationSettingsRequest,
 };
 
 impl Database {
     /// Get settings for a conversation. Returns default settings if none exist.
+    /// Values the conversation leaves unset are resolved from its workspace.
     pub async fn get_conversation_settings(
         &self,
         conversation_id: &str,
     ) -> Result<ConversationSettings> {
         let row = sqlx::query(
             "SELECT conversation_id, use_provider_defaults, use_custom_parameters,
-             parameter_overrides, context_message_count, selected_preset_id,
-             system_prompt_mode, selected_system_prompt_id, custom_system_prompt,
+             parameter_overrides, history_mode, context_budget_percent, token_budget, cost_budget,
+             provider_policy_id, reply_language, selected_preset_id, system_prompt_mode, selected_system_prompt_id, custom_system_prompt,
              user_prompt_mode, selected_user_prompt_id, custom_user_prompt,
-             enabled_mcp_server_ids, enabled_skill_ids, working_directory
+             enabled_mcp_server_ids, enabled_skill_ids, working_directory,
+             selected_model_id, selected_assistant_id
              FROM conversation_settings WHERE conversation_id = ?",
         )
         .bind(conversation_id)
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        match row {
-            Some(row) => Ok(self.row_to_conversation_settings(&row)),
+        let history_mode_set = row
+            .as_ref()
+            .is_some_and(|row| row.get::<Option<String>, _>("history_mode").is_some());
+        let mut settings = match row {
+            Some(row) => self.row_to_conversation_settings(&row),
             None => {
                 // Return default settings if none exist, inheriting globally enabled tools
                 // This includes both MCP servers and builtin tools
@@ -47,12 +53,17 @@ impl Database {
                     .map(|s| s.id)
                     .collect();
 
-                Ok(ConversationSettings {
+                ConversationSettings {
                     conversation_id: conversation_id.to_string(),
                     use_provider_defaults: true,
                     use_custom_parameters: false,
                     parameter_overrides: ModelParameterOverrides::default(),
-                    context_message_count: None,
+                    history_mode: HistoryMode::All,
+                    context_budget_percent: None,
+                    token_budget: None,
+                    cost_budget: None,
+                    provider_policy_id: None,
+                    reply_language: None,
                     selected_preset_id: None,
                     system_prompt_mode: PromptMode::None,
                     selected_system_prompt_id: None,
@@ -63,9 +74,17 @@ impl Database {
                     enabled_mcp_server_ids: enabled_tool_ids,
                     enabled_skill_ids,
                     working_directory: None,
-                })
+                    selected_model_id: None,
+                    selected_assistant_id: None,
+                    inherited: Vec::new(),
+                }
             }
+        };
+
+        if let Some(workspace) = self.get_conversation_workspace(conversation_id).await? {
+            workspace.settings.apply_to(&mut settings, history_mode_set);
         }
+        Ok(settings)
     }
 
     /// Update settings for a conversation. Creates settings if they don't exist (upsert).
@@ -74,8 +93,15 @@ impl Database {
         conversation_id: &str,
         req: UpdateConversationSettingsRequest,
     ) -> Result<ConversationSettings> {
-        // Get existing settings or defaults
-        let existing = self.get_conversation_settings(conversation_id).await?;
+        // Get existing settings or defaults. Workspace values stay inherited unless
+        // this request sets them.
+        let mut existing = self.get_conversation_settings(conversation_id).await?;
+        existing.clear_inherited();
+        let inherited = if req.history_mode.is_some() {
+            Vec::new()
+        } else {
+            existing.inherited.clone()
+        };
 
         // Merge updates
         let use_provider_defaults = req
@@ -87,9 +113,16 @@ impl Database {
         let parameter_overrides = req
             .parameter_overrides
             .unwrap_or(existing.parameter_overrides);
-        let context_message_count = req
-            .context_message_count
-            .unwrap_or(existing.context_message_count);
+        let history_mode = req.history_mode.unwrap_or(existing.history_mode);
+        let context_budget_percent = req
+            .context_budget_percent
+            .unwrap_or(existing.context_budget_percent);
+        let token_budget = req.token_budget.unwrap_or(existing.token_budget);
+        let cost_budget = req.cost_budget.unwrap_or(existing.cost_budget);
+        let provider_policy_id = req
+            .provider_policy_id
+            .unwrap_or(existing.provider_policy_id);
+        let reply_language = req.reply_language.unwrap_or(existing.reply_language);
         let selected_preset_id = req
             .selected_preset_id
             .unwrap_or(existing.selected_preset_id);
@@ -114,28 +147,76 @@ impl Database {
             .unwrap_or(existing.enabled_mcp_server_ids);
         let enabled_skill_ids = req.enabled_skill_ids.unwrap_or(existing.enabled_skill_ids);
         let working_directory = req.working_directory.unwrap_or(existing.working_directory);
+        let selected_model_id = req.selected_model_id.unwrap_or(existing.selected_model_id);
+        let selected_assistant_id = req
+            .selected_assistant_id
+            .unwrap_or(existing.selected_assistant_id);
+
+        self.save_conversation_settings(&ConversationSettings {
+            conversation_id: conversation_id.to_string(),
+            use_provider_defaults,
+            use_custom_parameters,
+            parameter_overrides,
+            history_mode,
+            context_budget_percent,
+            token_budget,
+            cost_budget,
+            provider_policy_id,
+            reply_language,
+            selected_preset_id,
+            system_prompt_mode,
+            selected_system_prompt_id,
+            custom_system_prompt,
+            user_prompt_mode,
+            selected_user_prompt_id,
+            custom_user_prompt,
+            enabled_mcp_server_ids,
+            enabled_skill_ids,
+            working_directory,
+            selected_model_id,
+            selected_assistant_id,
+            inherited,
+        })
+        .await?;
 
+        self.get_conversation_settings(conversation_id).await
+    }
+
+    /// Write a full settings row for a conversation (upsert). An inherited history mode
+    /// is stored as NULL so it keeps following the workspace.
+    pub(crate) async fn save_conversation_settings(
+        &self,
+        settings: &ConversationSettings,
+    ) -> Result<()> {
         // Serialize parameter overrides to JSON
-        let parameter_overrides_json = serde_json::to_string(&parameter_overrides)?;
+        let parameter_overrides_json = serde_json::to_string(&settings.parameter_overrides)?;
         // Serialize enabled MCP server IDs to JSON
-        let enabled_mcp_server_ids_json = serde_json::to_string(&enabled_mcp_server_ids)?;
+        let enabled_mcp_server_ids_json = serde_json::to_string(&settings.enabled_mcp_server_ids)?;
         // Serialize enabled skill IDs to JSON
-        let enabled_skill_ids_json = serde_json::to_string(&enabled_skill_ids)?;
+        let enabled_skill_ids_json = serde_json::to_string(&settings.enabled_skill_ids)?;
+        let history_mode =
+            (!settings.is_inherited("history_mode")).then(|| String::from(settings.history_mode));
 
-        // Upsert
         sqlx::query(
             "INSERT INTO conversation_settings (
                 conversation_id, use_provider_defaults, use_custom_parameters,
-                parameter_overrides, context_message_count, selected_preset_id,
+                parameter_overrides, history_mode, context_budget_percent, token_budget,
+                cost_budget, provider_policy_id, reply_language, selected_preset_id,
                 system_prompt_mode, selected_system_prompt_id, custom_system_prompt,
                 user_prompt_mode, selected_user_prompt_id, custom_user_prompt,
-                enabled_mcp_server_ids, enabled_skill_ids, working_directory
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                enabled_mcp_server_ids, enabled_skill_ids, working_directory, selected_model_id,
+                selected_assistant_id
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(conversation_id) DO UPDATE SET
                 use_provider_defaults = excluded.use_provider_defaults,
                 use_custom_parameters = excluded.use_custom_parameters,
                 parameter_overrides = excluded.parameter_overrides,
-                context_message_count = excluded.context_message_count,
+                history_mode = excluded.history_mode,
+                context_budget_percent = excluded.context_budget_percent,
+                token_budget = excluded.token_budget,
+                cost_budget = excluded.cost_budget,
+                provider_policy_id = excluded.provider_policy_id,
+                reply_language = excluded.reply_language,
                 selected_preset_id = excluded.selected_preset_id,
                 system_prompt_mode = excluded.system_prompt_mode,
                 selected_system_prompt_id = excluded.selected_system_prompt_id,
@@ -145,27 +226,63 @@ impl Database {
                 custom_user_prompt = excluded.custom_user_prompt,
                 enabled_mcp_server_ids = excluded.enabled_mcp_server_ids,
                 enabled_skill_ids = excluded.enabled_skill_ids,
-                working_directory = excluded.working_directory",
+                working_directory = excluded.working_directory,
+                selected_model_id = excluded.selected_model_id,
+                selected_assistant_id = excluded.selected_assistant_id",
         )
-        .bind(conversation_id)
-        .bind(use_provider_defaults as i32)
-        .bind(use_custom_parameters as i32)
+        .bind(&settings.conversation_id)
+        .bind(settings.use_provider_defaults as i32)
+        .bind(settings.use_custom_parameters as i32)
         .bind(&parameter_overrides_json)
-        .bind(context_message_count)
-        .bind(&selected_preset_id)
-        .bind(String::from(system_prompt_mode.clone()))
-        .bind(&selected_system_prompt_id)
-        .bind(&custom_system_prompt)
-        .bind(String::from(user_prompt_mode.clone()))
-        .bind(&selected_user_prompt_id)
-        .bind(&custom_user_prompt)
+        .bind(history_mode)
+        .bind(settings.context_budget_percent)
+        .bind(settings.token_budget)
+        .bind(settings.cost_budget)
+        .bind(&settings.provider_policy_id)
+        .bind(&settings.reply_language)
+        .bind(&settings.selected_preset_id)
+        .bind(String::from(settings.system_prompt_mode.clone()))
+        .bind(&settings.selected_system_prompt_id)
+        .bind(&settings.custom_system_prompt)
+        .bind(String::from(settings.user_prompt_mode.clone()))
+        .bind(&settings.selected_user_prompt_id)
+        .bind(&settings.custom_user_prompt)
         .bind(&enabled_mcp_server_ids_json)
         .bind(&enabled_skill_ids_json)
-        .bind(&working_directory)
+        .bind(&settings.working_directory)
+        .bind(&settings.selected_model_id)
+        .bind(&settings.selected_assistant_id)
         .execute(self.pool.as_ref())
         .await?;
 
-        self.get_conversation_settings(conversation_id).await
+        Ok(())
+    }
+
+    /// Bind a conversation to the model/assistant it was last sent with.
+    /// Only touches the binding columns; no-op when the binding is unchanged.
+    pub async fn set_conversation_binding(
+        &self,
+        conversation_id: &str,
+        model_id: Option<&str>,
+        assistant_id: Option<&str>,
+    ) -> Result<()> {
+        let existing = self.get_conversation_settings(conversation_id).await?;
+        if existing.selected_model_id.as_deref() == model_id
+            && existing.selected_assistant_id.as_deref() == assistant_id
+        {
+            return Ok(());
+        }
+
+        self.update_conversation_settings(
+            conversation_id,
+            UpdateConversationSettingsRequest {
+                selected_model_id: Some(model_id.map(String::from)),
+                selected_assistant_id: Some(assistant_id.map(String::from)),
+                ..Default::default()
+            },
+        )
+        .await?;
+        Ok(())
     }
 
     /// Reset only the tools and skills in conversation settings to global defaults.
@@ -211,6 +328,7 @@ impl Database {
         let use_provider_defaults: i32 = row.get("use_provider_defaults");
         let use_custom_parameters: i32 = row.get("use_custom_parameters");
         let parameter_overrides_json: Option<String> = row.get("parameter_overrides");
+        let history_mode_str: Option<String> = row.get("history_mode");
         let system_prompt_mode_str: String = row.get("system_prompt_mode");
         let user_prompt_mode_str: String = row.get("user_prompt_mode");
         let enabled_mcp_server_ids_json: Option<String> = row.get("enabled_mcp_server_ids");
@@ -233,7 +351,15 @@ impl Database {
             use_provider_defaults: use_provider_defaults != 0,
             use_custom_parameters: use_custom_parameters != 0,
             parameter_overrides,
-            context_message_count: row.get("context_message_count"),
+            history_mode: history_mode_str
+                .as_deref()
+                .map(HistoryMode::from)
+                .unwrap_or_default(),
+            context_budget_percent: row.get("context_budget_percent"),
+            token_budget: row.get("token_budget"),
+            cost_budget: row.get("cost_budget"),
+            provider_policy_id: row.get("provider_policy_id"),
+            reply_language: row.get("reply_language"),
             selected_preset_id: row.get("selected_preset_id"),
             system_prompt_mode: PromptMode::from(system_prompt_mode_str.as_str()),
             selected_system_prompt_id: row.get("selected_system_prompt_id"),
@@ -244,6 +370,9 @@ impl Database {
             enabled_mcp_server_ids,
             enabled_skill_ids,
             working_directory: row.get("working_directory"),
+            selected_model_id: row.get("selected_model_id"),
+            selected_assistant_id: row.get("selected_assistant_id"),
+            inherited: Vec::new(),
         }
     }
 }
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+
+use super::Database;
+use crate::models::{ConversationTemplate, ConversationTemplateInput};
+
+const TEMPLATE_COLUMNS: &str = "id, name, title_pattern, assistant_id, settings, opening_message, send_opening_message, created_at, updated_at";
+
+fn template_from_row(row: SqliteRow) -> Result<ConversationTemplate> {
+    let settings: String = row.get("settings");
+    let send_opening_message: i32 = row.get("send_opening_message");
+    Ok(ConversationTemplate {
+        id: row.get("id"),
+        name: row.get("name"),
+        title_pattern: row.get("title_pattern"),
+        assistant_id: row.get("assistant_id"),
+        settings: serde_json::from_str(&settings)?,
+        opening_message: row.get("opening_message"),
+        send_opening_message: send_opening_message != 0,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+impl Database {
+    pub async fn create_conversation_template(
+        &self,
+        input: ConversationTemplateInput,
+    ) -> Result<ConversationTemplate> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO conversation_templates (id, name, title_pattern, assistant_id, settings, opening_message, send_opening_message, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(input.name.trim())
+        .bind(input.title_pattern.trim())
+        .bind(&input.assistant_id)
+        .bind(serde_json::to_string(&input.settings)?)
+        .bind(&input.opening_message)
+        .bind(if input.send_opening_message { 1 } else { 0 })
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_conversation_template(&id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created conversation template"))
+    }
+
+    pub async fn get_conversation_template(
+        &self,
+        id: &str,
+    ) -> Result<Option<ConversationTemplate>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM conversation_templates WHERE id = ?",
+            TEMPLATE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(template_from_row).transpose()
+    }
+
+    pub async fn list_conversation_templates(&self) -> Result<Vec<ConversationTemplate>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM conversation_templates ORDER BY name",
+            TEMPLATE_COLUMNS
+        ))
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(template_from_row).collect()
+    }
+
+    pub async fn update_conversation_template(
+        &self,
+        id: &str,
+        input: ConversationTemplateInput,
+    ) -> Result<ConversationTemplate> {
+        let updated = sqlx::query(
+            "UPDATE conversation_templates SET name = ?, title_pattern = ?, assistant_id = ?, settings = ?,
+             opening_message = ?, send_opening_message = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(input.name.trim())
+        .bind(input.title_pattern.trim())
+        .bind(&input.assistant_id)
+        .bind(serde_json::to_string(&input.settings)?)
+        .bind(&input.opening_message)
+        .bind(if input.send_opening_message { 1 } else { 0 })
+        .bind(Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(anyhow::anyhow!("Conversation template not found: {}", id));
+        }
+
+        self.get_conversation_template(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Conversation template not found: {}", id))
+    }
+
+    pub async fn delete_conversation_template(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM conversation_templates WHERE id = ?")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+}
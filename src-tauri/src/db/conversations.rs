@@ -5,8 +5,10 @@ use uuid::Uuid;
 
 use super::Database;
 use crate::models::{
-    Conversation, ConversationParticipant, CreateConversationParticipantRequest,
-    CreateConversationRequest, ParticipantSummary,
+    ContextEnrichment, ContextType, Conversation, ConversationMetadata, ConversationParticipant,
+    CreateConversationParticipantRequest, CreateConversationRequest, CreateMessageRequest,
+    ParticipantMetadata, ParticipantSummary, UpdateConversationParticipantRequest,
+    title_from_content,
 };
 
 impl Database {
@@ -39,6 +41,11 @@ impl Database {
             "SELECT 
                 c.id, 
                 c.title, 
+                c.summary,
+                c.summary_message_count,
+                c.metadata,
+                c.workspace_id,
+                c.archived_at,
                 c.created_at, 
                 c.updated_at,
                 (SELECT m.content 
@@ -57,9 +64,15 @@ impl Database {
             Some(row) => Ok(Some(Conversation {
                 id: row.get("id"),
                 title: row.get("title"),
+                summary: row.get("summary"),
+                summary_message_count: row.get("summary_message_count"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
                 last_message: row.get("last_message"),
+                is_private: false,
+                metadata: parse_conversation_metadata(row.get("metadata")),
+                workspace_id: row.get("workspace_id"),
+                archived_at: row.get("archived_at"),
             })),
             None => Ok(None),
         }
@@ -70,6 +83,11 @@ impl Database {
             "SELECT 
                 c.id, 
                 c.title, 
+                c.summary,
+                c.summary_message_count,
+                c.metadata,
+                c.workspace_id,
+                c.archived_at,
                 c.created_at, 
                 c.updated_at,
                 (SELECT m.content 
@@ -88,9 +106,15 @@ impl Database {
             .map(|row| Conversation {
                 id: row.get("id"),
                 title: row.get("title"),
+                summary: row.get("summary"),
+                summary_message_count: row.get("summary_message_count"),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
                 last_message: row.get("last_message"),
+                is_private: false,
+                metadata: parse_conversation_metadata(row.get("metadata")),
+                workspace_id: row.get("workspace_id"),
+                archived_at: row.get("archived_at"),
             })
             .collect();
 
@@ -112,6 +136,42 @@ impl Database {
             .ok_or_else(|| anyhow::anyhow!("Conversation not found"))
     }
 
+    /// Store a generated summary. Leaves updated_at alone so the sidebar order doesn't change.
+    pub async fn set_conversation_summary(
+        &self,
+        id: &str,
+        summary: &str,
+        message_count: i64,
+    ) -> Result<()> {
+        sqlx::query("UPDATE conversations SET summary = ?, summary_message_count = ? WHERE id = ?")
+            .bind(summary)
+            .bind(message_count)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Archive or restore a conversation. Returns false when it doesn't exist or was
+    /// already in that state.
+    pub async fn set_conversation_archived(&self, id: &str, archived: bool) -> Result<bool> {
+        let query = if archived {
+            "UPDATE conversations SET archived_at = ? WHERE id = ? AND archived_at IS NULL"
+        } else {
+            "UPDATE conversations SET archived_at = NULL WHERE id = ? AND archived_at IS NOT NULL"
+        };
+        let mut query = sqlx::query(query);
+        if archived {
+            query = query.bind(Utc::now().to_rfc3339());
+        }
+        let updated = query
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?
+            .rows_affected();
+        Ok(updated > 0)
+    }
+
     pub async fn delete_conversation(&self, id: &str) -> Result<()> {
         sqlx::query("DELETE FROM conversations WHERE id = ?")
             .bind(id)
@@ -128,10 +188,14 @@ impl Database {
         let id = Uuid::now_v7().to_string();
         let now = Utc::now().to_rfc3339();
 
-        sqlx::query(
+        // Adding a participant that left brings the existing row back
+        let id: String = sqlx::query_scalar(
             "INSERT INTO conversation_participants 
              (id, conversation_id, participant_type, participant_id, display_name, role, status, joined_at)
-             VALUES (?, ?, ?, ?, ?, 'member', 'active', ?)"
+             VALUES (?, ?, ?, ?, ?, 'member', 'active', ?)
+             ON CONFLICT(conversation_id, participant_type, participant_id)
+             DO UPDATE SET status = 'active', left_at = NULL
+             RETURNING id"
         )
         .bind(&id)
         .bind(&req.conversation_id)
@@ -139,7 +203,7 @@ impl Database {
         .bind(&req.participant_id)
         .bind(&req.display_name)
         .bind(&now)
-        .execute(self.pool.as_ref())
+        .fetch_one(self.pool.as_ref())
         .await?;
 
         self.get_conversation_participant(&id)
@@ -172,7 +236,7 @@ impl Database {
                 joined_at: row.get("joined_at"),
                 left_at: row.get("left_at"),
                 last_read_at: row.get("last_read_at"),
-                metadata: row.get("metadata"),
+                metadata: parse_participant_metadata(row.get("metadata")),
             })),
             None => Ok(None),
         }
@@ -204,7 +268,7 @@ impl Database {
                 joined_at: row.get("joined_at"),
                 left_at: row.get("left_at"),
                 last_read_at: row.get("last_read_at"),
-                metadata: row.get("metadata"),
+                metadata: parse_participant_metadata(row.get("metadata")),
             })
             .collect();
 
@@ -247,7 +311,9 @@ impl Database {
                     WHEN 'user' THEN u.avatar_image_url
                     WHEN 'assistant' THEN a.avatar_image_url
                     ELSE NULL
-                END as avatar_image_url
+                END as avatar_image_url,
+                json_extract(cp.metadata, '$.color') as color,
+                json_extract(cp.metadata, '$.nickname') as nickname
              FROM conversation_participants cp
              LEFT JOIN users u ON cp.participant_type = 'user' AND cp.participant_id = u.id
              LEFT JOIN assistants a ON cp.participant_type = 'assistant' AND cp.participant_id = a.id
@@ -273,12 +339,68 @@ impl Database {
                 avatar_text: row.get("avatar_text"),
                 avatar_image_path: row.get("avatar_image_path"),
                 avatar_image_url: row.get("avatar_image_url"),
+                color: row.get("color"),
+                nickname: row.get("nickname"),
             })
             .collect();
 
         Ok(summaries)
     }
 
+    pub async fn update_conversation_participant(
+        &self,
+        id: &str,
+        req: UpdateConversationParticipantRequest,
+    ) -> Result<ConversationParticipant> {
+        let existing = self
+            .get_conversation_participant(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Participant not found: {}", id))?;
+
+        let display_name = match req.display_name {
+            Some(name) if name.trim().is_empty() => None,
+            Some(name) => Some(name.trim().to_string()),
+            None => existing.display_name,
+        };
+        let role = req.role.map(String::from).unwrap_or(existing.role);
+        let metadata = req.metadata.or(existing.metadata);
+        let metadata = metadata.as_ref().map(serde_json::to_string).transpose()?;
+
+        sqlx::query(
+            "UPDATE conversation_participants SET display_name = ?, role = ?, metadata = ? WHERE id = ?",
+        )
+        .bind(&display_name)
+        .bind(&role)
+        .bind(&metadata)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_conversation_participant(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Participant not found: {}", id))
+    }
+
+    /// Mark a participant as having left, keeping its row (and color, nickname)
+    /// so it isn't re-added automatically on the next message
+    pub async fn deactivate_conversation_participant(
+        &self,
+        id: &str,
+    ) -> Result<ConversationParticipant> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "UPDATE conversation_participants SET status = 'left', left_at = ? WHERE id = ?",
+        )
+        .bind(&now)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_conversation_participant(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Participant not found: {}", id))
+    }
+
     pub async fn remove_conversation_participant(&self, id: &str) -> Result<()> {
         sqlx::query("DELETE FROM conversation_participants WHERE id = ?")
             .bind(id)
@@ -287,6 +409,76 @@ impl Database {
         Ok(())
     }
 
+    /// Start a new conversation whose opening user message is a copy of `message_id`.
+    /// With `include_contexts`, the pages fetched for the message are attached to the
+    /// copy too. The new conversation's metadata points back to the original message.
+    pub async fn start_conversation_from_message(
+        &self,
+        message_id: &str,
+        include_contexts: bool,
+    ) -> Result<Conversation> {
+        let source = self
+            .get_message(message_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Message not found: {}", message_id))?;
+
+        let title = match title_from_content(&source.content) {
+            Some(title) => title,
+            None => match source.conversation_id.as_deref() {
+                Some(id) => self.get_conversation(id).await?.map(|c| c.title),
+                None => None,
+            }
+            .unwrap_or_else(|| "New Conversation".to_string()),
+        };
+        let conversation = self
+            .create_conversation(CreateConversationRequest { title })
+            .await?;
+
+        let metadata = ConversationMetadata {
+            origin_conversation_id: source.conversation_id.clone(),
+            origin_message_id: Some(source.id.clone()),
+        };
+        sqlx::query("UPDATE conversations SET metadata = ? WHERE id = ?")
+            .bind(serde_json::to_string(&metadata)?)
+            .bind(&conversation.id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        let message = self
+            .create_message(CreateMessageRequest {
+                conversation_id: Some(conversation.id.clone()),
+                sender_type: "user".to_string(),
+                sender_id: None,
+                content: source.content.clone(),
+                tokens: source.tokens,
+            })
+            .await?;
+
+        if include_contexts {
+            let fetch_results = self
+                .get_message_contexts(&source.id)
+                .await?
+                .into_iter()
+                .filter_map(|context| match context {
+                    ContextEnrichment::FetchResult(f) => Some(f),
+                    ContextEnrichment::SearchResult(_) => None,
+                });
+            for (order, fetch_result) in fetch_results.enumerate() {
+                self.link_message_context(
+                    &message.id,
+                    ContextType::FetchResult,
+                    &fetch_result.id,
+                    Some(order as i32),
+                )
+                .await?;
+            }
+        }
+
+        self.get_conversation(&conversation.id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created conversation"))
+    }
+
     /// Fork a conversation: create a new conversation and copy all messages
     /// up to and including the specified message.
     pub async fn fork_conversation(
@@ -339,65 +531,49 @@ impl Database {
             .list_conversation_participants(source_conversation_id)
             .await?;
         for p in &participants {
-            self.add_conversation_participant(CreateConversationParticipantRequest {
-                conversation_id: new_conv.id.clone(),
-                participant_type: p.participant_type.clone(),
-                participant_id: p.participant_id.clone(),
-                display_name: p.display_name.clone(),
-            })
+            let copy = self
+                .add_conversation_participant(CreateConversationParticipantRequest {
+                    conversation_id: new_conv.id.clone(),
+                    participant_type: p.participant_type.clone(),
+                    participant_id: p.participant_id.clone(),
+                    display_name: p.display_name.clone(),
+                })
+                .await?;
+            // Keep role, status, colors and nicknames in the fork
+            sqlx::query(
+                "UPDATE conversation_participants
+                 SET (role, status, left_at, metadata) = (
+                     SELECT role, status, left_at, metadata FROM conversation_participants WHERE id = ?
+                 )
+                 WHERE id = ?",
+            )
+            .bind(&p.id)
+            .bind(&copy.id)
+            .execute(self.pool.as_ref())
             .await?;
         }
 
-        let settings = self
+        if let Some(workspace_id) = &source.workspace_id {
+            self.set_conversation_workspace(&new_conv.id, Some(workspace_id))
+                .await?;
+        }
+        let mut settings = self
             .get_conversation_settings(source_conversation_id)
             .await?;
-        let param_json = serde_json::to_string(&settings.parameter_overrides)?;
-        let mcp_json = serde_json::to_string(&settings.enabled_mcp_server_ids)?;
-        let skill_json = serde_json::to_string(&settings.enabled_skill_ids)?;
-        sqlx::query(
-            "INSERT INTO conversation_settings (
-                conversation_id, use_provider_defaults, use_custom_parameters,
-                parameter_overrides, context_message_count, selected_preset_id,
-                system_prompt_mode, selected_system_prompt_id, custom_system_prompt,
-                user_prompt_mode, selected_user_prompt_id, custom_user_prompt,
-                enabled_mcp_server_ids, enabled_skill_ids, working_directory
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            ON CONFLICT(conversation_id) DO UPDATE SET
-                use_provider_defaults = excluded.use_provider_defaults,
-                use_custom_parameters = excluded.use_custom_parameters,
-                parameter_overrides = excluded.parameter_overrides,
-                context_message_count = excluded.context_message_count,
-                selected_preset_id = excluded.selected_preset_id,
-                system_prompt_mode = excluded.system_prompt_mode,
-                selected_system_prompt_id = excluded.selected_system_prompt_id,
-                custom_system_prompt = excluded.custom_system_prompt,
-                user_prompt_mode = excluded.user_prompt_mode,
-                selected_user_prompt_id = excluded.selected_user_prompt_id,
-                custom_user_prompt = excluded.custom_user_prompt,
-                enabled_mcp_server_ids = excluded.enabled_mcp_server_ids,
-                enabled_skill_ids = excluded.enabled_skill_ids,
-                working_directory = excluded.working_directory",
-        )
-        .bind(&new_conv.id)
-        .bind(settings.use_provider_defaults as i32)
-        .bind(settings.use_custom_parameters as i32)
-        .bind(&param_json)
-        .bind(settings.context_message_count)
-        .bind(&settings.selected_preset_id)
-        .bind(String::from(settings.system_prompt_mode))
-        .bind(&settings.selected_system_prompt_id)
-        .bind(&settings.custom_system_prompt)
-        .bind(String::from(settings.user_prompt_mode))
-        .bind(&settings.selected_user_prompt_id)
-        .bind(&settings.custom_user_prompt)
-        .bind(&mcp_json)
-        .bind(&skill_json)
-        .bind(&settings.working_directory)
-        .execute(self.pool.as_ref())
-        .await?;
+        settings.conversation_id = new_conv.id.clone();
+        settings.clear_inherited();
+        self.save_conversation_settings(&settings).await?;
 
         self.get_conversation(&new_conv.id)
             .await?
             .ok_or_else(|| anyhow::anyhow!("Failed to retrieve forked conversation"))
     }
 }
+
+fn parse_conversation_metadata(raw: Option<String>) -> Option<ConversationMetadata> {
+    raw.and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn parse_participant_metadata(raw: Option<String>) -> Option<ParticipantMetadata> {
+    raw.and_then(|s| serde_json::from_str(&s).ok())
+}
@@ -4,7 +4,7 @@ use sqlx::{Row, sqlite::SqliteRow};
 use uuid::Uuid;
 
 use super::Database;
-use crate::models::{CreateFetchResultRequest, FetchResult};
+use crate::models::{CreateFetchResultRequest, FetchResult, MessageCitation};
 
 /// Maps a database row to a FetchResult struct.
 /// Used by all fetch result query methods to avoid code duplication.
@@ -28,13 +28,14 @@ fn map_fetch_result_row(row: &SqliteRow) -> FetchResult {
         original_size: row.get("original_size"),
         processed_size: row.get("processed_size"),
         favicon_url: row.get("favicon_url"),
+        favicon_path: row.get("favicon_path"),
         content_hash: row.get("content_hash"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }
 }
 
-const FETCH_RESULT_COLUMNS: &str = "id, source_type, source_id, url, title, description, storage_path, content_type, original_mime, status, error, keywords, headings, original_size, processed_size, favicon_url, content_hash, created_at, updated_at";
+const FETCH_RESULT_COLUMNS: &str = "id, source_type, source_id, url, title, description, storage_path, content_type, original_mime, status, error, keywords, headings, original_size, processed_size, favicon_url, favicon_path, content_hash, created_at, updated_at";
 
 impl Database {
     pub async fn create_fetch_result(&self, req: CreateFetchResultRequest) -> Result<FetchResult> {
@@ -46,8 +47,8 @@ impl Database {
         sqlx::query(
             "INSERT INTO fetch_results
              (id, source_type, source_id, url, title, description, storage_path, content_type, original_mime,
-              status, error, keywords, headings, original_size, processed_size, favicon_url, content_hash, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+              status, error, keywords, headings, original_size, processed_size, favicon_url, favicon_path, content_hash, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&id)
         .bind(&source_type)
@@ -65,6 +66,7 @@ impl Database {
         .bind(req.original_size)
         .bind(req.processed_size)
         .bind(&req.favicon_url)
+        .bind(&req.favicon_path)
         .bind(&req.content_hash)
         .bind(&now)
         .bind(&now)
@@ -103,6 +105,19 @@ impl Database {
         Ok(row.as_ref().map(map_fetch_result_row))
     }
 
+    /// Stored copy of a favicon some earlier fetch result already downloaded
+    pub async fn find_cached_favicon(&self, favicon_url: &str) -> Result<Option<String>> {
+        let path: Option<String> = sqlx::query_scalar(
+            "SELECT favicon_path FROM fetch_results
+             WHERE favicon_url = ? AND favicon_path IS NOT NULL
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(favicon_url)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        Ok(path)
+    }
+
     pub async fn get_fetch_results_by_source(
         &self,
         source_type: &str,
@@ -128,7 +143,7 @@ impl Database {
             "SELECT f.id, f.source_type, f.source_id, f.url, f.title, f.description,
                     f.storage_path, f.content_type, f.original_mime, f.status, f.error,
                     f.keywords, f.headings, f.original_size, f.processed_size,
-                    f.favicon_url, f.content_hash, f.created_at, f.updated_at
+                    f.favicon_url, f.favicon_path, f.content_hash, f.created_at, f.updated_at
              FROM fetch_results f
              INNER JOIN message_contexts mc ON mc.context_id = f.id AND mc.context_type = 'fetch_result'
              WHERE mc.message_id = ?
@@ -142,6 +157,85 @@ impl Database {
         Ok(rows.iter().map(map_fetch_result_row).collect())
     }
 
+    /// Pages fetched successfully for a conversation's messages other than `exclude_message_id`,
+    /// newest first
+    pub async fn get_fetch_results_by_conversation(
+        &self,
+        conversation_id: &str,
+        exclude_message_id: &str,
+    ) -> Result<Vec<FetchResult>> {
+        let query =
+            "SELECT DISTINCT f.id, f.source_type, f.source_id, f.url, f.title, f.description,
+                    f.storage_path, f.content_type, f.original_mime, f.status, f.error,
+                    f.keywords, f.headings, f.original_size, f.processed_size,
+                    f.favicon_url, f.favicon_path, f.content_hash, f.created_at, f.updated_at
+             FROM fetch_results f
+             INNER JOIN message_contexts mc ON mc.context_id = f.id AND mc.context_type = 'fetch_result'
+             INNER JOIN messages m ON m.id = mc.message_id
+             WHERE m.conversation_id = ? AND m.id != ? AND f.status = 'success'
+             ORDER BY f.created_at DESC";
+
+        let rows = sqlx::query(query)
+            .bind(conversation_id)
+            .bind(exclude_message_id)
+            .fetch_all(self.pool.as_ref())
+            .await?;
+
+        Ok(rows.iter().map(map_fetch_result_row).collect())
+    }
+
+    /// Store the fetch result each `[number]` marker of a message cites, replacing
+    /// citations stored for it before
+    pub async fn set_message_citations(
+        &self,
+        message_id: &str,
+        citations: &[(i32, String)],
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM message_citations WHERE message_id = ?")
+            .bind(message_id)
+            .execute(&mut *tx)
+            .await?;
+        for (number, fetch_result_id) in citations {
+            sqlx::query(
+                "INSERT INTO message_citations (message_id, number, fetch_result_id, created_at)
+                 VALUES (?, ?, ?, ?)",
+            )
+            .bind(message_id)
+            .bind(number)
+            .bind(fetch_result_id)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get_message_citations(&self, message_id: &str) -> Result<Vec<MessageCitation>> {
+        let rows = sqlx::query(
+            "SELECT c.message_id, c.number, c.fetch_result_id, f.url, f.title
+             FROM message_citations c
+             INNER JOIN fetch_results f ON f.id = c.fetch_result_id
+             WHERE c.message_id = ? ORDER BY c.number",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| MessageCitation {
+                message_id: row.get("message_id"),
+                number: row.get("number"),
+                fetch_result_id: row.get("fetch_result_id"),
+                url: row.get("url"),
+                title: row.get("title"),
+            })
+            .collect())
+    }
+
     pub async fn update_fetch_result_status(
         &self,
         id: &str,
@@ -159,6 +253,37 @@ impl Database {
         Ok(())
     }
 
+    /// Number of messages the fetch result is attached to
+    pub async fn count_fetch_result_links(&self, id: &str) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM message_contexts WHERE context_type = 'fetch_result' AND context_id = ?",
+        )
+        .bind(id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(count)
+    }
+
+    /// Number of fetch results sharing a stored file (files are content-addressed)
+    pub async fn count_fetch_results_by_storage_path(&self, storage_path: &str) -> Result<i64> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM fetch_results WHERE storage_path = ?")
+                .bind(storage_path)
+                .fetch_one(self.pool.as_ref())
+                .await?;
+        Ok(count)
+    }
+
+    /// Number of fetch results sharing a cached favicon
+    pub async fn count_fetch_results_by_favicon_path(&self, favicon_path: &str) -> Result<i64> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM fetch_results WHERE favicon_path = ?")
+                .bind(favicon_path)
+                .fetch_one(self.pool.as_ref())
+                .await?;
+        Ok(count)
+    }
+
     pub async fn delete_fetch_result(&self, id: &str) -> Result<()> {
         sqlx::query("DELETE FROM fetch_results WHERE id = ?")
             .bind(id)
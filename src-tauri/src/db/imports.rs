@@ -0,0 +1,60 @@
+use anyhow::Result;
+use uuid::Uuid;
+
+use super::Database;
+use crate::tokenizer;
+
+impl Database {
+    /// Insert a message from another app, keeping its original timestamp.
+    /// Unlike `create_message`, the conversation's `updated_at` is left alone.
+    pub async fn insert_imported_message(
+        &self,
+        conversation_id: &str,
+        sender_type: &str,
+        sender_id: Option<&str>,
+        content: &str,
+        created_at: &str,
+    ) -> Result<String> {
+        let id = Uuid::now_v7().to_string();
+
+        sqlx::query(
+            "INSERT INTO messages (id, conversation_id, sender_type, sender_id, content, tokens, created_at)
+             VALUES (?, ?, ?, ?, ?, NULL, ?)",
+        )
+        .bind(&id)
+        .bind(conversation_id)
+        .bind(sender_type)
+        .bind(sender_id)
+        .bind(content)
+        .bind(created_at)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO messages_fts(content, message_id, conversation_id) VALUES (?, ?, ?)",
+        )
+        .bind(tokenizer::tokenize_for_search(content))
+        .bind(&id)
+        .bind(conversation_id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Backdate an imported conversation to its original timestamps
+    pub async fn set_conversation_timestamps(
+        &self,
+        id: &str,
+        created_at: &str,
+        updated_at: &str,
+    ) -> Result<()> {
+        sqlx::query("UPDATE conversations SET created_at = ?, updated_at = ? WHERE id = ?")
+            .bind(created_at)
+            .bind(updated_at)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+
+use super::Database;
+use crate::models::{CreateJobRequest, Job, JobKind, JobStatus};
+
+const JOB_COLUMNS: &str = "id, kind, payload, status, priority, attempts, max_attempts, last_error, run_after, created_at, updated_at, completed_at";
+
+fn job_from_row(row: SqliteRow) -> Result<Job> {
+    let kind: String = row.get("kind");
+    let status: String = row.get("status");
+    let payload: String = row.get("payload");
+    Ok(Job {
+        id: row.get("id"),
+        kind: JobKind::from_id(&kind)
+            .ok_or_else(|| anyhow::anyhow!("Unknown job kind: {}", kind))?,
+        payload: serde_json::from_str(&payload)?,
+        status: JobStatus::from_id(&status)
+            .ok_or_else(|| anyhow::anyhow!("Unknown job status: {}", status))?,
+        priority: row.get("priority"),
+        attempts: row.get("attempts"),
+        max_attempts: row.get("max_attempts"),
+        last_error: row.get("last_error"),
+        run_after: row.get("run_after"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        completed_at: row.get("completed_at"),
+    })
+}
+
+impl Database {
+    pub async fn create_job(&self, req: CreateJobRequest) -> Result<Job> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO jobs (id, kind, payload, status, priority, attempts, max_attempts, run_after, created_at, updated_at)
+             VALUES (?, ?, ?, 'queued', ?, 0, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(req.kind.id())
+        .bind(serde_json::to_string(&req.payload)?)
+        .bind(req.priority)
+        .bind(req.max_attempts.max(1))
+        .bind(&now)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_job(&id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created job"))
+    }
+
+    pub async fn get_job(&self, id: &str) -> Result<Option<Job>> {
+        let row = sqlx::query(&format!("SELECT {} FROM jobs WHERE id = ?", JOB_COLUMNS))
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        row.map(job_from_row).transpose()
+    }
+
+    /// Most recent jobs first, optionally filtered by status
+    pub async fn list_jobs(&self, status: Option<JobStatus>, limit: i64) -> Result<Vec<Job>> {
+        let rows = match status {
+            Some(status) => {
+                sqlx::query(&format!(
+                    "SELECT {} FROM jobs WHERE status = ? ORDER BY created_at DESC LIMIT ?",
+                    JOB_COLUMNS
+                ))
+                .bind(status.id())
+                .bind(limit)
+                .fetch_all(self.pool.as_ref())
+                .await?
+            }
+            None => {
+                sqlx::query(&format!(
+                    "SELECT {} FROM jobs ORDER BY created_at DESC LIMIT ?",
+                    JOB_COLUMNS
+                ))
+                .bind(limit)
+                .fetch_all(self.pool.as_ref())
+                .await?
+            }
+        };
+        rows.into_iter().map(job_from_row).collect()
+    }
+
+    /// Mark the highest-priority due job as running and return it
+    pub async fn claim_next_job(&self) -> Result<Option<Job>> {
+        let now = Utc::now().to_rfc3339();
+        loop {
+            let row = sqlx::query(&format!(
+                "SELECT {} FROM jobs WHERE status = 'queued' AND run_after <= ?
+                 ORDER BY priority DESC, created_at ASC LIMIT 1",
+                JOB_COLUMNS
+            ))
+            .bind(&now)
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+            let Some(row) = row else {
+                return Ok(None);
+            };
+            let mut job = job_from_row(row)?;
+
+            // Another worker may have claimed it in between
+            let claimed = sqlx::query(
+                "UPDATE jobs SET status = 'running', attempts = attempts + 1, updated_at = ?
+                 WHERE id = ? AND status = 'queued'",
+            )
+            .bind(&now)
+            .bind(&job.id)
+            .execute(self.pool.as_ref())
+            .await?
+            .rows_affected();
+            if claimed == 1 {
+                job.status = JobStatus::Running;
+                job.attempts += 1;
+                job.updated_at = now;
+                return Ok(Some(job));
+            }
+        }
+    }
+
+    pub async fn complete_job(&self, id: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "UPDATE jobs SET status = 'completed', last_error = NULL, updated_at = ?, completed_at = ?
+             WHERE id = ?",
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Record a failed attempt: requeue at `retry_at`, or mark failed when `None`
+    pub async fn fail_job(
+        &self,
+        id: &str,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        match retry_at {
+            Some(retry_at) => {
+                sqlx::query(
+                    "UPDATE jobs SET status = 'queued', last_error = ?, run_after = ?, updated_at = ?
+                     WHERE id = ?",
+                )
+                .bind(error)
+                .bind(retry_at.to_rfc3339())
+                .bind(&now)
+                .bind(id)
+                .execute(self.pool.as_ref())
+                .await?;
+            }
+            None => {
+                sqlx::query(
+                    "UPDATE jobs SET status = 'failed', last_error = ?, updated_at = ?, completed_at = ?
+                     WHERE id = ?",
+                )
+                .bind(error)
+                .bind(&now)
+                .bind(&now)
+                .bind(id)
+                .execute(self.pool.as_ref())
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Requeue jobs left running by a previous session that exited mid-job
+    pub async fn requeue_interrupted_jobs(&self) -> Result<u64> {
+        let now = Utc::now().to_rfc3339();
+        let result = sqlx::query(
+            "UPDATE jobs SET status = 'queued', run_after = ?, updated_at = ? WHERE status = 'running'",
+        )
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete finished jobs older than `before`
+    pub async fn prune_finished_jobs(&self, before: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM jobs WHERE status IN ('completed', 'failed') AND completed_at < ?",
+        )
+        .bind(before.to_rfc3339())
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::Row;
+
+use super::Database;
+use crate::models::{BookmarkedMessage, MessageFlag, MessageFlagKind};
+
+impl Database {
+    /// Set a flag on a message. Thumbs up and down replace each other.
+    pub async fn set_message_flag(
+        &self,
+        message_id: &str,
+        kind: MessageFlagKind,
+        value: &str,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        if let Some(opposite) = kind.opposite() {
+            sqlx::query("DELETE FROM message_flags WHERE message_id = ? AND kind = ?")
+                .bind(message_id)
+                .bind(opposite.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query(
+            "INSERT OR IGNORE INTO message_flags (message_id, kind, value, created_at)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(message_id)
+        .bind(kind.as_str())
+        .bind(value)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn clear_message_flag(
+        &self,
+        message_id: &str,
+        kind: MessageFlagKind,
+        value: &str,
+    ) -> Result<()> {
+        sqlx::query("DELETE FROM message_flags WHERE message_id = ? AND kind = ? AND value = ?")
+            .bind(message_id)
+            .bind(kind.as_str())
+            .bind(value)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list_message_flags(&self, message_id: &str) -> Result<Vec<MessageFlag>> {
+        let rows = sqlx::query(
+            "SELECT message_id, kind, value, created_at FROM message_flags
+             WHERE message_id = ? ORDER BY created_at ASC",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let kind: String = row.get("kind");
+                let kind = kind.parse::<MessageFlagKind>().ok()?;
+                let value: String = row.get("value");
+                Some(MessageFlag {
+                    message_id: row.get("message_id"),
+                    kind,
+                    emoji: (kind == MessageFlagKind::Emoji).then_some(value),
+                    created_at: row.get("created_at"),
+                })
+            })
+            .collect())
+    }
+
+    /// Bookmarked messages across all conversations, most recently bookmarked first
+    pub async fn list_bookmarked_messages(&self) -> Result<Vec<BookmarkedMessage>> {
+        let rows = sqlx::query(
+            "SELECT f.message_id, f.created_at AS bookmarked_at, c.title AS conversation_title
+             FROM message_flags f
+             JOIN messages m ON m.id = f.message_id
+             LEFT JOIN conversations c ON c.id = m.conversation_id
+             WHERE f.kind = 'bookmark'
+             ORDER BY f.created_at DESC",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut bookmarks = Vec::with_capacity(rows.len());
+        for row in rows {
+            let message_id: String = row.get("message_id");
+            if let Some(message) = self.get_message(&message_id).await? {
+                bookmarks.push(BookmarkedMessage {
+                    message,
+                    conversation_title: row.get("conversation_title"),
+                    bookmarked_at: row.get("bookmarked_at"),
+                });
+            }
+        }
+        Ok(bookmarks)
+    }
+}
@@ -4,7 +4,10 @@ use sqlx::Row;
 use uuid::Uuid;
 
 use super::Database;
-use crate::models::{ConversationSearchResult, CreateMessageRequest, Message, MessageSearchResult};
+use crate::models::{
+    ConversationMessageMatch, ConversationSearchResult, CreateMessageRequest, Message,
+    MessageRevision, MessageSearchResult, ModelParameters,
+};
 use crate::search;
 use crate::tokenizer;
 
@@ -64,9 +67,69 @@ impl Database {
         result
     }
 
+    /// Record provider-reported token usage and computed cost for a message
+    pub async fn set_message_usage(
+        &self,
+        id: &str,
+        input_tokens: Option<i64>,
+        output_tokens: Option<i64>,
+        cost: Option<f64>,
+    ) -> Result<()> {
+        let total = match (input_tokens, output_tokens) {
+            (None, None) => None,
+            (i, o) => Some(i.unwrap_or(0) + o.unwrap_or(0)),
+        };
+        sqlx::query(
+            "UPDATE messages SET tokens = COALESCE(?, tokens), input_tokens = ?, output_tokens = ?, cost = ? WHERE id = ?",
+        )
+        .bind(total)
+        .bind(input_tokens)
+        .bind(output_tokens)
+        .bind(cost)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Attach suggested follow-up prompts to a message (stored as a JSON array)
+    pub async fn set_message_follow_up_suggestions(
+        &self,
+        id: &str,
+        suggestions: &[String],
+    ) -> Result<()> {
+        sqlx::query("UPDATE messages SET follow_up_suggestions = ? WHERE id = ?")
+            .bind(serde_json::to_string(suggestions)?)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Record the provider, model and effective parameters that generated a message
+    pub async fn set_message_generation_info(
+        &self,
+        id: &str,
+        provider_type: &str,
+        model_id: &str,
+        model_params: Option<&ModelParameters>,
+    ) -> Result<()> {
+        let model_params = model_params.map(serde_json::to_string).transpose()?;
+        sqlx::query(
+            "UPDATE messages SET provider_type = ?, model_id = ?, model_params = ? WHERE id = ?",
+        )
+        .bind(provider_type)
+        .bind(model_id)
+        .bind(model_params)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
     pub async fn get_message(&self, id: &str) -> Result<Option<Message>> {
         let row = sqlx::query(
-            "SELECT id, conversation_id, sender_type, sender_id, content, tokens, created_at
+            "SELECT id, conversation_id, sender_type, sender_id, content, tokens, input_tokens, output_tokens, cost, follow_up_suggestions, provider_type, model_id, model_params, created_at
              FROM messages WHERE id = ?",
         )
         .bind(id)
@@ -81,6 +144,15 @@ impl Database {
                 sender_id: row.get("sender_id"),
                 content: row.get("content"),
                 tokens: row.get("tokens"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                cost: row.get("cost"),
+                follow_up_suggestions: parse_follow_up_suggestions(
+                    row.get("follow_up_suggestions"),
+                ),
+                provider_type: row.get("provider_type"),
+                model_id: row.get("model_id"),
+                model_params: parse_model_params(row.get("model_params")),
                 created_at: row.get("created_at"),
             })),
             None => Ok(None),
@@ -92,7 +164,7 @@ impl Database {
         conversation_id: &str,
     ) -> Result<Vec<Message>> {
         let rows = sqlx::query(
-            "SELECT id, conversation_id, sender_type, sender_id, content, tokens, created_at
+            "SELECT id, conversation_id, sender_type, sender_id, content, tokens, input_tokens, output_tokens, cost, follow_up_suggestions, provider_type, model_id, model_params, created_at
              FROM messages WHERE conversation_id = ? ORDER BY created_at ASC",
         )
         .bind(conversation_id)
@@ -108,6 +180,15 @@ impl Database {
                 sender_id: row.get("sender_id"),
                 content: row.get("content"),
                 tokens: row.get("tokens"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                cost: row.get("cost"),
+                follow_up_suggestions: parse_follow_up_suggestions(
+                    row.get("follow_up_suggestions"),
+                ),
+                provider_type: row.get("provider_type"),
+                model_id: row.get("model_id"),
+                model_params: parse_model_params(row.get("model_params")),
                 created_at: row.get("created_at"),
             })
             .collect();
@@ -150,6 +231,141 @@ impl Database {
         Ok(())
     }
 
+    /// Delete every message created after `message_id` in the conversation
+    pub async fn delete_messages_after(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+    ) -> Result<()> {
+        let target = self.get_message(message_id).await?;
+        let target = target.ok_or_else(|| anyhow::anyhow!("Message not found: {}", message_id))?;
+
+        sqlx::query(
+            "DELETE FROM messages_fts WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ? AND created_at > ?)",
+        )
+        .bind(conversation_id)
+        .bind(&target.created_at)
+        .execute(self.pool.as_ref())
+        .await?;
+        sqlx::query("DELETE FROM messages WHERE conversation_id = ? AND created_at > ?")
+            .bind(conversation_id)
+            .bind(&target.created_at)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Replace a message's content. The current content is kept as a revision, and the
+    /// search index is updated.
+    pub async fn update_message_content(&self, id: &str, content: &str) -> Result<Message> {
+        let message = self
+            .get_message(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Message not found: {}", id))?;
+        if message.content == content {
+            return Ok(message);
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO message_revisions (id, message_id, content, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(id)
+        .bind(&message.content)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE messages SET content = ? WHERE id = ?")
+            .bind(content)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM messages_fts WHERE message_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "INSERT INTO messages_fts(content, message_id, conversation_id) VALUES (?, ?, ?)",
+        )
+        .bind(tokenizer::tokenize_for_search(content))
+        .bind(id)
+        .bind(message.conversation_id.as_deref().unwrap_or(""))
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        tracing::info!("✏️ [db] Updated content of message {}", id);
+        self.get_message(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Message not found: {}", id))
+    }
+
+    /// Earlier contents of a message, newest first
+    pub async fn list_message_revisions(&self, message_id: &str) -> Result<Vec<MessageRevision>> {
+        let revisions = sqlx::query_as::<_, MessageRevision>(
+            "SELECT id, message_id, content, created_at FROM message_revisions
+             WHERE message_id = ? ORDER BY created_at DESC, id DESC",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        Ok(revisions)
+    }
+
+    /// Remove the replies a regeneration replaced (oldest first) now that `reply_id` was
+    /// saved. The previous reply's content and revisions become revisions of the new one.
+    pub async fn supersede_messages(&self, reply_id: &str, replaced_ids: &[String]) -> Result<()> {
+        let previous = match replaced_ids.first() {
+            Some(id) => self
+                .get_message(id)
+                .await?
+                .filter(|message| message.sender_type != "user"),
+            None => None,
+        };
+
+        let mut tx = self.pool.begin().await?;
+        if let Some(previous) = previous {
+            sqlx::query("UPDATE message_revisions SET message_id = ? WHERE message_id = ?")
+                .bind(reply_id)
+                .bind(&previous.id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                "INSERT INTO message_revisions (id, message_id, content, created_at) VALUES (?, ?, ?, ?)",
+            )
+            .bind(Uuid::now_v7().to_string())
+            .bind(reply_id)
+            .bind(&previous.content)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+        }
+        for id in replaced_ids {
+            sqlx::query("DELETE FROM messages_fts WHERE message_id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM messages WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get_message_revision(&self, id: &str) -> Result<Option<MessageRevision>> {
+        let revision = sqlx::query_as::<_, MessageRevision>(
+            "SELECT id, message_id, content, created_at FROM message_revisions WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        Ok(revision)
+    }
+
     /// Backfill messages_fts with existing messages (idempotent; runs once per DB).
     pub async fn backfill_fts(&self) -> Result<()> {
         const FTS_BACKFILLED_KEY: &str = "fts_backfilled";
@@ -252,6 +468,67 @@ impl Database {
         Ok(results)
     }
 
+    /// Messages in one conversation matching `query`, in conversation order
+    pub async fn search_in_conversation(
+        &self,
+        conversation_id: &str,
+        query: &str,
+    ) -> Result<Vec<ConversationMessageMatch>> {
+        let tokenized_query = tokenizer::tokenize_query(query);
+        if tokenized_query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        #[derive(sqlx::FromRow)]
+        struct Row {
+            message_id: String,
+            ordinal: i64,
+            sender_type: String,
+            content: String,
+            created_at: String,
+        }
+
+        // Ordinals are numbered before the FTS filter so they match the full message list
+        let rows = sqlx::query_as::<_, Row>(
+            "SELECT o.id as message_id, o.ordinal, o.sender_type, o.content, o.created_at
+             FROM (
+                 SELECT id, sender_type, content, created_at,
+                        ROW_NUMBER() OVER (ORDER BY created_at ASC, rowid ASC) - 1 as ordinal
+                 FROM messages WHERE conversation_id = ?
+             ) o
+             JOIN messages_fts fts ON fts.message_id = o.id
+             WHERE messages_fts MATCH ?
+             ORDER BY o.ordinal",
+        )
+        .bind(conversation_id)
+        .bind(&tokenized_query)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let query_terms: Vec<String> = tokenized_query
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ConversationMessageMatch {
+                content_snippet: search::snippet::build_snippet(
+                    &r.content,
+                    &query_terms,
+                    6,
+                    1,
+                    120,
+                ),
+                match_count: search::snippet::count_matches(&r.content, &query_terms),
+                message_id: r.message_id,
+                ordinal: r.ordinal,
+                sender_type: r.sender_type,
+                created_at: r.created_at,
+            })
+            .collect())
+    }
+
     pub async fn search_conversations(
         &self,
         query: &str,
@@ -297,3 +574,65 @@ impl Database {
         Ok(results)
     }
 }
+
+fn parse_follow_up_suggestions(raw: Option<String>) -> Option<Vec<String>> {
+    raw.and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn parse_model_params(raw: Option<String>) -> Option<ModelParameters> {
+    raw.and_then(|s| serde_json::from_str(&s).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::test_db;
+    use crate::models::{CreateConversationRequest, CreateMessageRequest};
+
+    #[tokio::test]
+    async fn test_supersede_messages_keeps_previous_reply() {
+        let db = test_db().await;
+        let conversation = db
+            .create_conversation(CreateConversationRequest {
+                title: "Test".to_string(),
+            })
+            .await
+            .unwrap();
+        let message = |sender_type: &str, content: &str| CreateMessageRequest {
+            conversation_id: Some(conversation.id.clone()),
+            sender_type: sender_type.to_string(),
+            sender_id: None,
+            content: content.to_string(),
+            tokens: None,
+        };
+        db.create_message(message("user", "question"))
+            .await
+            .unwrap();
+        let old_reply = db.create_message(message("model", "first")).await.unwrap();
+        db.update_message_content(&old_reply.id, "edited")
+            .await
+            .unwrap();
+        let later = db.create_message(message("user", "more")).await.unwrap();
+        let new_reply = db.create_message(message("model", "second")).await.unwrap();
+
+        db.supersede_messages(&new_reply.id, &[old_reply.id.clone(), later.id.clone()])
+            .await
+            .unwrap();
+
+        let remaining: Vec<String> = db
+            .list_messages_by_conversation(&conversation.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(remaining, ["question", "second"]);
+        let revisions: Vec<String> = db
+            .list_message_revisions(&new_reply.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.content)
+            .collect();
+        assert_eq!(revisions, ["edited", "first"]);
+    }
+}
@@ -1,13 +1,20 @@
 mod assistants;
 mod attachments;
+mod automations;
 mod contexts;
 mod conversation_settings;
+mod conversation_templates;
 mod conversations;
 mod fetch_results;
+mod imports;
+mod jobs;
+mod message_flags;
 mod messages;
 mod model_parameter_presets;
+mod model_roles;
 mod models;
 mod prompts;
+mod provider_policies;
 mod providers;
 mod schema;
 mod search_results;
@@ -16,7 +23,12 @@ mod settings;
 pub mod skills;
 mod steps;
 pub mod tools;
+mod usage;
 mod users;
+mod webhooks;
+mod workspaces;
+
+pub use schema::CURRENT_SCHEMA_VERSION;
 
 use anyhow::Result;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
@@ -58,4 +70,25 @@ impl Database {
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// Run SQLite's quick integrity check and return the schema version
+    pub async fn check_health(&self) -> Result<i32> {
+        let (result,): (String,) = sqlx::query_as("PRAGMA quick_check")
+            .fetch_one(self.pool.as_ref())
+            .await?;
+        if result != "ok" {
+            anyhow::bail!("Integrity check failed: {}", result);
+        }
+        let (version,): (i32,) = sqlx::query_as("PRAGMA user_version")
+            .fetch_one(self.pool.as_ref())
+            .await?;
+        Ok(version)
+    }
+}
+
+/// A fresh database in a temporary file, for tests
+#[cfg(test)]
+pub(crate) async fn test_db() -> Database {
+    let path = std::env::temp_dir().join(format!("chatshell-test-{}.db", uuid::Uuid::now_v7()));
+    Database::new(&path.to_string_lossy()).await.unwrap()
 }
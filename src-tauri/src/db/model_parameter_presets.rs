@@ -283,6 +283,12 @@ impl Database {
             ));
         }
 
+        // Models fall back to no default preset
+        sqlx::query("UPDATE models SET default_preset_id = NULL WHERE default_preset_id = ?")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
         sqlx::query("DELETE FROM model_parameter_presets WHERE id = ?")
             .bind(id)
             .execute(self.pool.as_ref())
@@ -0,0 +1,84 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use super::Database;
+use crate::models::{LEGACY_SUMMARY_MODEL_KEY, MODEL_ALIASES_KEY, ModelRole, ModelRoleAssignments};
+
+impl Database {
+    /// Get the model DB id assigned to a role.
+    /// `Fast` falls back to the legacy summary model setting.
+    pub async fn get_model_role(&self, role: ModelRole) -> Result<Option<String>> {
+        let assigned = self
+            .get_setting(role.setting_key())
+            .await?
+            .filter(|v| !v.is_empty());
+
+        if assigned.is_none() && role == ModelRole::Fast {
+            return Ok(self
+                .get_setting(LEGACY_SUMMARY_MODEL_KEY)
+                .await?
+                .filter(|v| !v.is_empty()));
+        }
+
+        Ok(assigned)
+    }
+
+    /// Assign a model to a role, or clear it with `None`
+    pub async fn set_model_role(&self, role: ModelRole, model_id: Option<&str>) -> Result<()> {
+        self.set_setting(role.setting_key(), model_id.unwrap_or(""))
+            .await
+    }
+
+    pub async fn get_model_aliases(&self) -> Result<HashMap<String, String>> {
+        let raw = self.get_setting(MODEL_ALIASES_KEY).await?;
+        Ok(raw
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default())
+    }
+
+    /// Point an alias at a model, or remove it with `None`
+    pub async fn set_model_alias(&self, alias: &str, model_id: Option<&str>) -> Result<()> {
+        let mut aliases = self.get_model_aliases().await?;
+        match model_id {
+            Some(id) => {
+                aliases.insert(alias.to_string(), id.to_string());
+            }
+            None => {
+                aliases.remove(alias);
+            }
+        }
+        self.set_setting(MODEL_ALIASES_KEY, &serde_json::to_string(&aliases)?)
+            .await
+    }
+
+    pub async fn get_model_role_assignments(&self) -> Result<ModelRoleAssignments> {
+        Ok(ModelRoleAssignments {
+            chat: self.get_model_role(ModelRole::Chat).await?,
+            vision: self.get_model_role(ModelRole::Vision).await?,
+            fast: self.get_model_role(ModelRole::Fast).await?,
+            aliases: self.get_model_aliases().await?,
+        })
+    }
+
+    /// Resolve a model reference to a live (non-deleted) model DB id.
+    ///
+    /// A reference is a role id ("chat", "vision", "fast"), a user alias, or a model DB id.
+    pub async fn resolve_model_reference(&self, reference: &str) -> Result<Option<String>> {
+        let candidate = if let Some(role) = ModelRole::from_id(reference) {
+            self.get_model_role(role).await?
+        } else if let Some(id) = self.get_model_aliases().await?.remove(reference) {
+            Some(id)
+        } else {
+            Some(reference.to_string())
+        };
+
+        match candidate {
+            Some(id) => Ok(self
+                .get_model(&id)
+                .await?
+                .filter(|m| !m.is_deleted)
+                .map(|m| m.id)),
+            None => Ok(None),
+        }
+    }
+}
@@ -1,10 +1,37 @@
 use anyhow::Result;
 use chrono::Utc;
 use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
 use uuid::Uuid;
 
 use super::Database;
-use crate::models::{CreateModelRequest, Model};
+use crate::models::{CreateModelRequest, LEGACY_SUMMARY_MODEL_KEY, Model, ModelRole};
+
+const MODEL_COLUMNS: &str = "id, name, provider_id, model_id, description, is_starred, is_deleted, is_hidden, sort_order, input_price, output_price, openrouter_routing, default_preset_id, created_at, updated_at";
+
+fn row_to_model(row: &SqliteRow) -> Model {
+    let is_starred: i32 = row.get("is_starred");
+    let is_deleted: i32 = row.get("is_deleted");
+    let is_hidden: i32 = row.get("is_hidden");
+
+    Model {
+        id: row.get("id"),
+        name: row.get("name"),
+        provider_id: row.get("provider_id"),
+        model_id: row.get("model_id"),
+        description: row.get("description"),
+        is_starred: is_starred != 0,
+        is_deleted: is_deleted != 0,
+        is_hidden: is_hidden != 0,
+        sort_order: row.get("sort_order"),
+        input_price: row.get("input_price"),
+        output_price: row.get("output_price"),
+        openrouter_routing: super::providers::parse_routing(row.get("openrouter_routing")),
+        default_preset_id: row.get("default_preset_id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
 
 impl Database {
     pub async fn create_model(&self, req: CreateModelRequest) -> Result<Model> {
@@ -23,11 +50,15 @@ impl Database {
         if let Some(id) = existing_id {
             // Restore the soft-deleted model
             sqlx::query(
-                "UPDATE models SET is_deleted = 0, name = ?, description = ?, is_starred = ?, updated_at = ? WHERE id = ?"
+                "UPDATE models SET is_deleted = 0, name = ?, description = ?, is_starred = ?,
+                 input_price = COALESCE(?, input_price), output_price = COALESCE(?, output_price), updated_at = ?
+                 WHERE id = ?"
             )
             .bind(&req.name)
             .bind(&req.description)
             .bind(is_starred as i32)
+            .bind(req.input_price)
+            .bind(req.output_price)
             .bind(&now)
             .bind(&id)
             .execute(self.pool.as_ref())
@@ -42,8 +73,8 @@ impl Database {
         // Create new model
         let id = Uuid::now_v7().to_string();
         sqlx::query(
-            "INSERT INTO models (id, name, provider_id, model_id, description, is_starred, is_deleted, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, 0, ?, ?)"
+            "INSERT INTO models (id, name, provider_id, model_id, description, is_starred, is_deleted, input_price, output_price, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, 0, ?, ?, ?, ?)"
         )
         .bind(&id)
         .bind(&req.name)
@@ -51,6 +82,8 @@ impl Database {
         .bind(&req.model_id)
         .bind(&req.description)
         .bind(is_starred as i32)
+        .bind(req.input_price)
+        .bind(req.output_price)
         .bind(&now)
         .bind(&now)
         .execute(self.pool.as_ref())
@@ -62,109 +95,68 @@ impl Database {
     }
 
     pub async fn get_model(&self, id: &str) -> Result<Option<Model>> {
-        let row = sqlx::query(
-            "SELECT id, name, provider_id, model_id, description, is_starred, is_deleted, created_at, updated_at
-             FROM models WHERE id = ?"
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM models WHERE id = ?",
+            MODEL_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(self.pool.as_ref())
         .await?;
 
-        match row {
-            Some(row) => {
-                let is_starred: i32 = row.get("is_starred");
-                let is_deleted: i32 = row.get("is_deleted");
-
-                Ok(Some(Model {
-                    id: row.get("id"),
-                    name: row.get("name"),
-                    provider_id: row.get("provider_id"),
-                    model_id: row.get("model_id"),
-                    description: row.get("description"),
-                    is_starred: is_starred != 0,
-                    is_deleted: is_deleted != 0,
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                }))
-            }
-            None => Ok(None),
-        }
+        Ok(row.as_ref().map(row_to_model))
     }
 
-    pub async fn list_models(&self) -> Result<Vec<Model>> {
-        let rows = sqlx::query(
-            "SELECT id, name, provider_id, model_id, description, is_starred, is_deleted, created_at, updated_at
-             FROM models WHERE is_deleted = 0 ORDER BY created_at ASC"
-        )
+    /// List non-deleted models in display order.
+    /// With `visible_only`, models hidden from the picker are excluded.
+    pub async fn list_models(&self, visible_only: bool) -> Result<Vec<Model>> {
+        let hidden_filter = if visible_only {
+            " AND is_hidden = 0"
+        } else {
+            ""
+        };
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM models WHERE is_deleted = 0{} ORDER BY sort_order ASC, created_at ASC",
+            MODEL_COLUMNS, hidden_filter
+        ))
         .fetch_all(self.pool.as_ref())
         .await?;
 
-        let models = rows
-            .iter()
-            .map(|row| {
-                let is_starred: i32 = row.get("is_starred");
-                let is_deleted: i32 = row.get("is_deleted");
-
-                Model {
-                    id: row.get("id"),
-                    name: row.get("name"),
-                    provider_id: row.get("provider_id"),
-                    model_id: row.get("model_id"),
-                    description: row.get("description"),
-                    is_starred: is_starred != 0,
-                    is_deleted: is_deleted != 0,
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                }
-            })
-            .collect();
+        let models = rows.iter().map(row_to_model).collect();
 
         Ok(models)
     }
 
     pub async fn list_all_models(&self) -> Result<Vec<Model>> {
-        let rows = sqlx::query(
-            "SELECT id, name, provider_id, model_id, description, is_starred, is_deleted, created_at, updated_at
-             FROM models ORDER BY created_at ASC"
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM models ORDER BY sort_order ASC, created_at ASC",
+            MODEL_COLUMNS
+        ))
         .fetch_all(self.pool.as_ref())
         .await?;
 
-        let models = rows
-            .iter()
-            .map(|row| {
-                let is_starred: i32 = row.get("is_starred");
-                let is_deleted: i32 = row.get("is_deleted");
-
-                Model {
-                    id: row.get("id"),
-                    name: row.get("name"),
-                    provider_id: row.get("provider_id"),
-                    model_id: row.get("model_id"),
-                    description: row.get("description"),
-                    is_starred: is_starred != 0,
-                    is_deleted: is_deleted != 0,
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                }
-            })
-            .collect();
+        let models = rows.iter().map(row_to_model).collect();
 
         Ok(models)
     }
 
+    /// Update a model. Prices left out of the request keep their stored values, so
+    /// edits that don't touch pricing (starring, renaming) don't clear them.
     pub async fn update_model(&self, id: &str, req: CreateModelRequest) -> Result<Model> {
         let now = Utc::now().to_rfc3339();
         let is_starred = req.is_starred.unwrap_or(false);
 
         sqlx::query(
-            "UPDATE models SET name = ?, provider_id = ?, model_id = ?, description = ?, is_starred = ?, updated_at = ? WHERE id = ?"
+            "UPDATE models SET name = ?, provider_id = ?, model_id = ?, description = ?, is_starred = ?,
+             input_price = COALESCE(?, input_price), output_price = COALESCE(?, output_price), updated_at = ?
+             WHERE id = ?"
         )
         .bind(&req.name)
         .bind(&req.provider_id)
         .bind(&req.model_id)
         .bind(&req.description)
         .bind(is_starred as i32)
+        .bind(req.input_price)
+        .bind(req.output_price)
         .bind(&now)
         .bind(id)
         .execute(self.pool.as_ref())
@@ -183,6 +175,118 @@ impl Database {
         Ok(())
     }
 
+    pub async fn list_deleted_models(&self) -> Result<Vec<Model>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM models WHERE is_deleted = 1 ORDER BY updated_at DESC",
+            MODEL_COLUMNS
+        ))
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.iter().map(row_to_model).collect())
+    }
+
+    pub async fn restore_model(&self, id: &str) -> Result<Model> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("UPDATE models SET is_deleted = 0, updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        self.get_model(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Model not found"))
+    }
+
+    /// Hard-delete soft-deleted models that nothing references: no message, assistant,
+    /// conversation model selection, model role or alias.
+    /// Returns the number of models removed.
+    pub async fn purge_deleted_models(&self) -> Result<u64> {
+        // Roles and aliases live in settings values; collect the ids they point at
+        let mut referenced: Vec<String> = Vec::new();
+        for key in ModelRole::ALL
+            .iter()
+            .map(|role| role.setting_key())
+            .chain([LEGACY_SUMMARY_MODEL_KEY])
+        {
+            if let Some(id) = self.get_setting(key).await?.filter(|v| !v.is_empty()) {
+                referenced.push(id);
+            }
+        }
+        referenced.extend(self.get_model_aliases().await?.into_values());
+
+        let result = sqlx::query(
+            "DELETE FROM models
+             WHERE is_deleted = 1
+               AND NOT EXISTS (
+                   SELECT 1 FROM messages
+                   WHERE messages.sender_type = 'model' AND messages.sender_id = models.id
+               )
+               AND NOT EXISTS (
+                   SELECT 1 FROM assistants WHERE assistants.model_id = models.id
+               )
+               AND NOT EXISTS (
+                   SELECT 1 FROM conversation_settings
+                   WHERE conversation_settings.selected_model_id = models.id
+               )
+               AND models.id NOT IN (SELECT value FROM json_each(?))",
+        )
+        .bind(serde_json::to_string(&referenced)?)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn set_model_hidden(&self, id: &str, hidden: bool) -> Result<Model> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("UPDATE models SET is_hidden = ?, updated_at = ? WHERE id = ?")
+            .bind(hidden as i32)
+            .bind(&now)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        self.get_model(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Model not found"))
+    }
+
+    /// Set or clear (`None`) the parameter preset a model uses by default
+    pub async fn set_model_default_preset(
+        &self,
+        id: &str,
+        preset_id: Option<&str>,
+    ) -> Result<Model> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("UPDATE models SET default_preset_id = ?, updated_at = ? WHERE id = ?")
+            .bind(preset_id)
+            .bind(&now)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        self.get_model(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Model not found"))
+    }
+
+    /// Persist a manual ordering: each id gets its index as sort_order.
+    /// Models not listed keep their current position value.
+    pub async fn reorder_models(&self, ordered_ids: &[String]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for (index, id) in ordered_ids.iter().enumerate() {
+            sqlx::query("UPDATE models SET sort_order = ? WHERE id = ?")
+                .bind(index as i64)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
     pub async fn soft_delete_model(&self, id: &str) -> Result<()> {
         let now = Utc::now().to_rfc3339();
         sqlx::query("UPDATE models SET is_deleted = 1, updated_at = ? WHERE id = ?")
@@ -193,3 +297,98 @@ impl Database {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::db::test_db;
+    use crate::models::{CreateModelRequest, CreateProviderRequest, ModelRole};
+
+    #[tokio::test]
+    async fn test_update_model_keeps_prices() {
+        let db = test_db().await;
+        let provider = db
+            .create_provider(CreateProviderRequest {
+                name: "OpenAI".to_string(),
+                provider_type: "openai".to_string(),
+                api_key: None,
+                base_url: None,
+                api_style: None,
+                description: None,
+                is_enabled: Some(true),
+            })
+            .await
+            .unwrap();
+        let request = |is_starred, input_price, output_price| CreateModelRequest {
+            name: "GPT".to_string(),
+            provider_id: provider.id.clone(),
+            model_id: "gpt".to_string(),
+            description: None,
+            is_starred: Some(is_starred),
+            input_price,
+            output_price,
+        };
+        let model = db
+            .create_model(request(false, Some(2.5), Some(10.0)))
+            .await
+            .unwrap();
+
+        // Starring sends no prices
+        let starred = db
+            .update_model(&model.id, request(true, None, None))
+            .await
+            .unwrap();
+        assert!(starred.is_starred);
+        assert_eq!(starred.input_price, Some(2.5));
+        assert_eq!(starred.output_price, Some(10.0));
+
+        let repriced = db
+            .update_model(&model.id, request(true, Some(3.0), None))
+            .await
+            .unwrap();
+        assert_eq!(repriced.input_price, Some(3.0));
+        assert_eq!(repriced.output_price, Some(10.0));
+    }
+
+    #[tokio::test]
+    async fn test_purge_keeps_models_assigned_to_roles() {
+        let db = test_db().await;
+        let provider = db
+            .create_provider(CreateProviderRequest {
+                name: "Ollama".to_string(),
+                provider_type: "ollama".to_string(),
+                api_key: None,
+                base_url: None,
+                api_style: None,
+                description: None,
+                is_enabled: Some(true),
+            })
+            .await
+            .unwrap();
+        let model = db
+            .create_model(CreateModelRequest {
+                name: "Llama".to_string(),
+                provider_id: provider.id.clone(),
+                model_id: "llama3".to_string(),
+                description: None,
+                is_starred: None,
+                input_price: None,
+                output_price: None,
+            })
+            .await
+            .unwrap();
+        db.soft_delete_model(&model.id).await.unwrap();
+
+        db.set_model_role(ModelRole::Fast, Some(&model.id))
+            .await
+            .unwrap();
+        db.set_model_alias("local", Some(&model.id)).await.unwrap();
+        assert_eq!(db.purge_deleted_models().await.unwrap(), 0);
+
+        db.set_model_role(ModelRole::Fast, None).await.unwrap();
+        assert_eq!(db.purge_deleted_models().await.unwrap(), 0);
+
+        db.set_model_alias("local", None).await.unwrap();
+        assert_eq!(db.purge_deleted_models().await.unwrap(), 1);
+        assert!(db.get_model(&model.id).await.unwrap().is_none());
+    }
+}
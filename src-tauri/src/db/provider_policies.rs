@@ -0,0 +1,88 @@
+use anyhow::Result;
+
+use super::Database;
+use crate::models::{PROVIDER_POLICIES_KEY, ProviderPolicy, ProviderPolicyInput};
+
+impl Database {
+    async fn load_stored_provider_policies(&self) -> Result<Vec<ProviderPolicy>> {
+        let raw = self.get_setting(PROVIDER_POLICIES_KEY).await?;
+        Ok(raw
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default())
+    }
+
+    async fn save_stored_provider_policies(&self, policies: &[ProviderPolicy]) -> Result<()> {
+        self.set_setting(PROVIDER_POLICIES_KEY, &serde_json::to_string(policies)?)
+            .await
+    }
+
+    /// Built-in policies first, then the user's
+    pub async fn list_provider_policies(&self) -> Result<Vec<ProviderPolicy>> {
+        let mut policies = vec![ProviderPolicy::local_only()];
+        policies.extend(self.load_stored_provider_policies().await?);
+        Ok(policies)
+    }
+
+    pub async fn get_provider_policy(&self, id: &str) -> Result<Option<ProviderPolicy>> {
+        Ok(self
+            .list_provider_policies()
+            .await?
+            .into_iter()
+            .find(|p| p.id == id))
+    }
+
+    pub async fn create_provider_policy(
+        &self,
+        input: ProviderPolicyInput,
+    ) -> Result<ProviderPolicy> {
+        let policy = ProviderPolicy {
+            id: uuid::Uuid::now_v7().to_string(),
+            name: input.name.trim().to_string(),
+            allow_local: input.allow_local,
+            allowed_provider_ids: input.allowed_provider_ids,
+            allowed_provider_types: input.allowed_provider_types,
+            is_builtin: false,
+        };
+
+        let mut policies = self.load_stored_provider_policies().await?;
+        policies.push(policy.clone());
+        self.save_stored_provider_policies(&policies).await?;
+        Ok(policy)
+    }
+
+    pub async fn update_provider_policy(
+        &self,
+        id: &str,
+        input: ProviderPolicyInput,
+    ) -> Result<ProviderPolicy> {
+        let mut policies = self.load_stored_provider_policies().await?;
+        let policy = policies
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Provider policy not found: {}", id))?;
+
+        policy.name = input.name.trim().to_string();
+        policy.allow_local = input.allow_local;
+        policy.allowed_provider_ids = input.allowed_provider_ids;
+        policy.allowed_provider_types = input.allowed_provider_types;
+        let updated = policy.clone();
+
+        self.save_stored_provider_policies(&policies).await?;
+        Ok(updated)
+    }
+
+    /// Delete a policy; conversations that used it are no longer restricted
+    pub async fn delete_provider_policy(&self, id: &str) -> Result<()> {
+        let mut policies = self.load_stored_provider_policies().await?;
+        policies.retain(|p| p.id != id);
+        self.save_stored_provider_policies(&policies).await?;
+
+        sqlx::query(
+            "UPDATE conversation_settings SET provider_policy_id = NULL WHERE provider_policy_id = ?",
+        )
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+}
@@ -4,7 +4,20 @@ use sqlx::Row;
 use uuid::Uuid;
 
 use super::Database;
-use crate::models::{CreateProviderRequest, Provider};
+use crate::models::{CreateProviderRequest, OpenRouterRouting, Provider};
+
+/// Parse a stored routing JSON column; malformed values are treated as unset
+pub(super) fn parse_routing(raw: Option<String>) -> Option<OpenRouterRouting> {
+    raw.and_then(|json| serde_json::from_str(&json).ok())
+        .filter(|routing: &OpenRouterRouting| !routing.is_empty())
+}
+
+fn routing_json(routing: Option<&OpenRouterRouting>) -> Result<Option<String>> {
+    Ok(match routing.filter(|r| !r.is_empty()) {
+        Some(routing) => Some(serde_json::to_string(routing)?),
+        None => None,
+    })
+}
 
 impl Database {
     pub async fn create_provider(&self, req: CreateProviderRequest) -> Result<Provider> {
@@ -64,7 +77,7 @@ impl Database {
 
     pub async fn get_provider(&self, id: &str) -> Result<Option<Provider>> {
         let row = sqlx::query(
-            "SELECT id, name, provider_type, api_key, base_url, api_style, description, is_enabled, created_at, updated_at
+            "SELECT id, name, provider_type, api_key, base_url, api_style, description, is_enabled, openrouter_routing, created_at, updated_at
              FROM providers WHERE id = ?"
         )
         .bind(id)
@@ -102,6 +115,7 @@ impl Database {
                     api_style: row.get("api_style"),
                     description: row.get("description"),
                     is_enabled: is_enabled != 0,
+                    openrouter_routing: parse_routing(row.get("openrouter_routing")),
                     created_at: row.get("created_at"),
                     updated_at: row.get("updated_at"),
                 }))
@@ -112,7 +126,7 @@ impl Database {
 
     pub async fn list_providers(&self) -> Result<Vec<Provider>> {
         let rows = sqlx::query(
-            "SELECT id, name, provider_type, api_key, base_url, api_style, description, is_enabled, created_at, updated_at
+            "SELECT id, name, provider_type, api_key, base_url, api_style, description, is_enabled, openrouter_routing, created_at, updated_at
              FROM providers ORDER BY created_at ASC"
         )
         .fetch_all(self.pool.as_ref())
@@ -149,6 +163,7 @@ impl Database {
                 api_style: row.get("api_style"),
                 description: row.get("description"),
                 is_enabled: is_enabled != 0,
+                openrouter_routing: parse_routing(row.get("openrouter_routing")),
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
             });
@@ -252,4 +267,42 @@ impl Database {
             .await?;
         Ok(())
     }
+
+    /// Set or clear (`None`) a provider's OpenRouter routing defaults
+    pub async fn set_provider_openrouter_routing(
+        &self,
+        id: &str,
+        routing: Option<&OpenRouterRouting>,
+    ) -> Result<Provider> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("UPDATE providers SET openrouter_routing = ?, updated_at = ? WHERE id = ?")
+            .bind(routing_json(routing)?)
+            .bind(&now)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        self.get_provider(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Provider not found"))
+    }
+
+    /// Set or clear (`None`) a model's OpenRouter routing overrides
+    pub async fn set_model_openrouter_routing(
+        &self,
+        id: &str,
+        routing: Option<&OpenRouterRouting>,
+    ) -> Result<crate::models::Model> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("UPDATE models SET openrouter_routing = ?, updated_at = ? WHERE id = ?")
+            .bind(routing_json(routing)?)
+            .bind(&now)
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        self.get_model(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Model not found"))
+    }
 }
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+pub async fn create_automations_table(pool: &SqlitePool) -> Result<()> {
+    // Trigger -> action rules (see crate::automations); trigger and action are JSON
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS automations (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            trigger TEXT NOT NULL,
+            action TEXT NOT NULL,
+            is_enabled INTEGER NOT NULL DEFAULT 1,
+            last_run_at TEXT,
+            next_run_at TEXT,
+            last_error TEXT,
+            trigger_state TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_automations_due ON automations(is_enabled, next_run_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_automation_runs_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS automation_runs (
+            id TEXT PRIMARY KEY,
+            automation_id TEXT NOT NULL,
+            event TEXT NOT NULL,
+            status TEXT NOT NULL,
+            output TEXT,
+            error TEXT,
+            started_at TEXT NOT NULL,
+            finished_at TEXT,
+            FOREIGN KEY (automation_id) REFERENCES automations(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_automation_runs_automation ON automation_runs(automation_id, started_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Rebuild the v37 automations table, whose rows were daily prompts, as scheduled
+/// `run_prompt` rules
+pub async fn convert_scheduled_prompts(pool: &SqlitePool) -> Result<()> {
+    // The index would move to the renamed table and be dropped with it
+    sqlx::query("DROP INDEX IF EXISTS idx_automations_due")
+        .execute(pool)
+        .await?;
+    sqlx::query("ALTER TABLE automations RENAME TO automations_v37")
+        .execute(pool)
+        .await?;
+    create_automations_table(pool).await?;
+    sqlx::query(
+        "INSERT INTO automations (id, name, trigger, action, is_enabled, last_run_at, next_run_at, last_error, created_at, updated_at)
+         SELECT id, name,
+                json_object('type', 'schedule', 'run_at', run_at),
+                json_object('type', 'run_prompt', 'prompt', prompt, 'sources', json(sources),
+                            'assistant_id', assistant_id, 'conversation_id', conversation_id),
+                is_enabled, last_run_at, next_run_at, last_error, created_at, updated_at
+         FROM automations_v37",
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query("DROP TABLE automations_v37")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
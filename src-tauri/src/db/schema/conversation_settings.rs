@@ -8,7 +8,13 @@ pub async fn create_conversation_settings_table(pool: &SqlitePool) -> Result<()>
             use_provider_defaults INTEGER DEFAULT 1,
             use_custom_parameters INTEGER DEFAULT 0,
             parameter_overrides TEXT,
+            history_mode TEXT DEFAULT 'all',
             context_message_count INTEGER,
+            context_budget_percent INTEGER,
+            token_budget INTEGER,
+            cost_budget REAL,
+            provider_policy_id TEXT,
+            reply_language TEXT,
             selected_preset_id TEXT,
             system_prompt_mode TEXT DEFAULT 'none',
             selected_system_prompt_id TEXT,
@@ -19,6 +25,8 @@ pub async fn create_conversation_settings_table(pool: &SqlitePool) -> Result<()>
             enabled_mcp_server_ids TEXT,
             enabled_skill_ids TEXT,
             working_directory TEXT,
+            selected_model_id TEXT,
+            selected_assistant_id TEXT,
             FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE,
             FOREIGN KEY (selected_preset_id) REFERENCES model_parameter_presets(id) ON DELETE SET NULL,
             FOREIGN KEY (selected_system_prompt_id) REFERENCES prompts(id) ON DELETE SET NULL,
@@ -7,6 +7,10 @@ pub async fn create_conversations_table(pool: &SqlitePool) -> Result<()> {
         "CREATE TABLE IF NOT EXISTS conversations (
             id TEXT PRIMARY KEY,
             title TEXT NOT NULL,
+            summary TEXT,
+            summary_message_count INTEGER NOT NULL DEFAULT 0,
+            metadata TEXT,
+            workspace_id TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL
         )",
@@ -52,3 +56,41 @@ pub async fn create_conversations_table(pool: &SqlitePool) -> Result<()> {
 
     Ok(())
 }
+
+pub async fn create_workspaces_table(pool: &SqlitePool) -> Result<()> {
+    // Folders of conversations; `settings` holds the shared defaults as JSON
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS workspaces (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            settings TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_conversation_templates_table(pool: &SqlitePool) -> Result<()> {
+    // `settings` holds the conversation settings to apply as JSON
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS conversation_templates (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            title_pattern TEXT NOT NULL,
+            assistant_id TEXT,
+            settings TEXT NOT NULL DEFAULT '{}',
+            opening_message TEXT,
+            send_opening_message INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
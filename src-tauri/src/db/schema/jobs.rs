@@ -0,0 +1,32 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+pub async fn create_jobs_table(pool: &SqlitePool) -> Result<()> {
+    // Persistent background job queue (see crate::jobs)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS jobs (
+            id TEXT PRIMARY KEY,
+            kind TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'queued',
+            priority INTEGER NOT NULL DEFAULT 0,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            max_attempts INTEGER NOT NULL DEFAULT 3,
+            last_error TEXT,
+            run_after TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            completed_at TEXT
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_jobs_pending ON jobs(status, priority DESC, run_after)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
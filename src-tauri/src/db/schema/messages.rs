@@ -11,6 +11,10 @@ pub async fn create_messages_table(pool: &SqlitePool) -> Result<()> {
             sender_id TEXT,
             content TEXT NOT NULL,
             tokens INTEGER,
+            input_tokens INTEGER,
+            output_tokens INTEGER,
+            cost REAL,
+            follow_up_suggestions TEXT,
             created_at TEXT NOT NULL,
             FOREIGN KEY (conversation_id) REFERENCES conversations(id) ON DELETE CASCADE
         )",
@@ -115,6 +119,7 @@ pub async fn create_contexts_table(pool: &SqlitePool) -> Result<()> {
             original_size INTEGER,
             processed_size INTEGER,
             favicon_url TEXT,
+            favicon_path TEXT,
             content_hash TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL
@@ -158,3 +163,97 @@ pub async fn create_contexts_table(pool: &SqlitePool) -> Result<()> {
 
     Ok(())
 }
+
+pub async fn create_search_result_items_table(pool: &SqlitePool) -> Result<()> {
+    // The full result list of a search, including results that weren't fetched
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS search_result_items (
+            id TEXT PRIMARY KEY,
+            search_result_id TEXT NOT NULL,
+            rank INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            url TEXT NOT NULL,
+            snippet TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (search_result_id) REFERENCES search_results(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_search_result_items_search ON search_result_items(search_result_id, rank)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_message_citations_table(pool: &SqlitePool) -> Result<()> {
+    // Which fetched page each `[number]` marker in an assistant message cites
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS message_citations (
+            message_id TEXT NOT NULL,
+            number INTEGER NOT NULL,
+            fetch_result_id TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (message_id, number),
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE,
+            FOREIGN KEY (fetch_result_id) REFERENCES fetch_results(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_message_revisions_table(pool: &SqlitePool) -> Result<()> {
+    // Earlier contents of edited messages; listed newest first
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS message_revisions (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            content TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_message_revisions_message
+         ON message_revisions(message_id, created_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_message_flags_table(pool: &SqlitePool) -> Result<()> {
+    // Bookmarks, ratings and emoji reactions. `value` holds the emoji for reactions
+    // and is empty otherwise, so each flag is set at most once per message.
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS message_flags (
+            message_id TEXT NOT NULL,
+            kind TEXT NOT NULL CHECK(kind IN ('bookmark', 'thumbs_up', 'thumbs_down', 'emoji')),
+            value TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (message_id, kind, value),
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_message_flags_kind ON message_flags(kind, created_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -2,8 +2,10 @@ use anyhow::Result;
 use sqlx::SqlitePool;
 
 mod assistants;
+mod automations;
 mod conversation_settings;
 mod conversations;
+mod jobs;
 mod knowledge;
 mod messages;
 mod model_parameter_presets;
@@ -13,10 +15,11 @@ mod search;
 mod settings;
 mod skills;
 mod steps;
+mod usage;
 mod users;
 
 /// Current schema version. Increment this when adding new migrations.
-const CURRENT_SCHEMA_VERSION: i32 = 10;
+pub const CURRENT_SCHEMA_VERSION: i32 = 41;
 
 async fn get_user_version(pool: &SqlitePool) -> Result<i32> {
     let row: (i32,) = sqlx::query_as("PRAGMA user_version")
@@ -107,16 +110,249 @@ pub async fn init_schema(pool: &SqlitePool) -> Result<()> {
         tracing::info!("Migration to v10 completed");
     }
 
+    if current_version < 11 {
+        migrate_v10_to_v11(pool).await?;
+        set_user_version(pool, 11).await?;
+        tracing::info!("Migration to v11 completed");
+    }
+
+    if current_version < 12 {
+        migrate_v11_to_v12(pool).await?;
+        set_user_version(pool, 12).await?;
+        tracing::info!("Migration to v12 completed");
+    }
+
+    if current_version < 13 {
+        migrate_v12_to_v13(pool).await?;
+        set_user_version(pool, 13).await?;
+        tracing::info!("Migration to v13 completed");
+    }
+
+    if current_version < 14 {
+        migrate_v13_to_v14(pool).await?;
+        set_user_version(pool, 14).await?;
+        tracing::info!("Migration to v14 completed");
+    }
+
+    if current_version < 15 {
+        migrate_v14_to_v15(pool).await?;
+        set_user_version(pool, 15).await?;
+        tracing::info!("Migration to v15 completed");
+    }
+
+    if current_version < 16 {
+        migrate_v15_to_v16(pool).await?;
+        set_user_version(pool, 16).await?;
+        tracing::info!("Migration to v16 completed");
+    }
+
+    if current_version < 17 {
+        migrate_v16_to_v17(pool).await?;
+        set_user_version(pool, 17).await?;
+        tracing::info!("Migration to v17 completed");
+    }
+
+    if current_version < 18 {
+        migrate_v17_to_v18(pool).await?;
+        set_user_version(pool, 18).await?;
+        tracing::info!("Migration to v18 completed");
+    }
+
+    if current_version < 19 {
+        migrate_v18_to_v19(pool).await?;
+        set_user_version(pool, 19).await?;
+        tracing::info!("Migration to v19 completed");
+    }
+
+    if current_version < 20 {
+        migrate_v19_to_v20(pool).await?;
+        set_user_version(pool, 20).await?;
+        tracing::info!("Migration to v20 completed");
+    }
+
+    if current_version < 21 {
+        migrate_v20_to_v21(pool).await?;
+        set_user_version(pool, 21).await?;
+        tracing::info!("Migration to v21 completed");
+    }
+
+    if current_version < 22 {
+        migrate_v21_to_v22(pool).await?;
+        set_user_version(pool, 22).await?;
+        tracing::info!("Migration to v22 completed");
+    }
+
+    if current_version < 23 {
+        migrate_v22_to_v23(pool).await?;
+        set_user_version(pool, 23).await?;
+        tracing::info!("Migration to v23 completed");
+    }
+
+    if current_version < 24 {
+        migrate_v23_to_v24(pool).await?;
+        set_user_version(pool, 24).await?;
+        tracing::info!("Migration to v24 completed");
+    }
+
+    if current_version < 25 {
+        migrate_v24_to_v25(pool).await?;
+        set_user_version(pool, 25).await?;
+        tracing::info!("Migration to v25 completed");
+    }
+
+    if current_version < 26 {
+        migrate_v25_to_v26(pool).await?;
+        set_user_version(pool, 26).await?;
+        tracing::info!("Migration to v26 completed");
+    }
+
+    if current_version < 27 {
+        migrate_v26_to_v27(pool).await?;
+        set_user_version(pool, 27).await?;
+        tracing::info!("Migration to v27 completed");
+    }
+
+    if current_version < 28 {
+        migrate_v27_to_v28(pool).await?;
+        set_user_version(pool, 28).await?;
+        tracing::info!("Migration to v28 completed");
+    }
+
+    if current_version < 29 {
+        migrate_v28_to_v29(pool).await?;
+        set_user_version(pool, 29).await?;
+        tracing::info!("Migration to v29 completed");
+    }
+
+    if current_version < 30 {
+        migrate_v29_to_v30(pool).await?;
+        set_user_version(pool, 30).await?;
+        tracing::info!("Migration to v30 completed");
+    }
+
+    if current_version < 31 {
+        migrate_v30_to_v31(pool).await?;
+        set_user_version(pool, 31).await?;
+        tracing::info!("Migration to v31 completed");
+    }
+
+    if current_version < 32 {
+        migrate_v31_to_v32(pool).await?;
+        set_user_version(pool, 32).await?;
+        tracing::info!("Migration to v32 completed");
+    }
+
+    if current_version < 33 {
+        migrate_v32_to_v33(pool).await?;
+        set_user_version(pool, 33).await?;
+        tracing::info!("Migration to v33 completed");
+    }
+
+    if current_version < 34 {
+        migrate_v33_to_v34(pool).await?;
+        set_user_version(pool, 34).await?;
+        tracing::info!("Migration to v34 completed");
+    }
+
+    if current_version < 35 {
+        migrate_v34_to_v35(pool).await?;
+        set_user_version(pool, 35).await?;
+        tracing::info!("Migration to v35 completed");
+    }
+
+    if current_version < 36 {
+        migrate_v35_to_v36(pool).await?;
+        set_user_version(pool, 36).await?;
+        tracing::info!("Migration to v36 completed");
+    }
+
+    if current_version < 37 {
+        migrate_v36_to_v37(pool).await?;
+        set_user_version(pool, 37).await?;
+        tracing::info!("Migration to v37 completed");
+    }
+
+    if current_version < 38 {
+        migrate_v37_to_v38(pool).await?;
+        set_user_version(pool, 38).await?;
+        tracing::info!("Migration to v38 completed");
+    }
+
+    if current_version < 39 {
+        migrate_v38_to_v39(pool).await?;
+        set_user_version(pool, 39).await?;
+        tracing::info!("Migration to v39 completed");
+    }
+
+    if current_version < 40 {
+        migrate_v39_to_v40(pool).await?;
+        set_user_version(pool, 40).await?;
+        tracing::info!("Migration to v40 completed");
+    }
+
+    if current_version < 41 {
+        migrate_v40_to_v41(pool).await?;
+        set_user_version(pool, 41).await?;
+        tracing::info!("Migration to v41 completed");
+    }
+
     // Ensure columns exist (idempotent, fixes databases
     // that were bumped to a version before the columns were actually added)
     ensure_enabled_skill_ids_column(pool).await?;
     ensure_working_directory_column(pool).await?;
     ensure_api_style_column(pool).await?;
     ensure_auth_token_column(pool).await?;
+    ensure_conversation_binding_columns(pool).await?;
+    ensure_usage_pricing_columns(pool).await?;
+    ensure_model_display_columns(pool).await?;
+    ensure_conversation_summary_columns(pool).await?;
+    ensure_follow_up_suggestions_column(pool).await?;
+    ensure_message_generation_columns(pool).await?;
+    ensure_history_mode_column(pool).await?;
+    ensure_context_budget_column(pool).await?;
+    ensure_search_decision_detail_columns(pool).await?;
+    ensure_openrouter_routing_columns(pool).await?;
+    ensure_model_default_preset_column(pool).await?;
+    ensure_conversation_budget_columns(pool).await?;
+    ensure_provider_policy_column(pool).await?;
+    ensure_conversation_metadata_column(pool).await?;
+    ensure_reply_language_column(pool).await?;
+    ensure_conversation_workspace_column(pool).await?;
+    ensure_fetch_result_favicon_column(pool).await?;
+    ensure_conversation_archived_column(pool).await?;
 
     Ok(())
 }
 
+/// Check whether a column exists on a table
+async fn has_column(pool: &SqlitePool, table: &str, column: &str) -> Result<bool> {
+    let columns: Vec<(String,)> =
+        sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{}')", table))
+            .fetch_all(pool)
+            .await?;
+    Ok(columns.iter().any(|(name,)| name == column))
+}
+
+/// Add a column to a table if it doesn't exist yet (idempotent).
+/// SQLite doesn't support IF NOT EXISTS for ALTER TABLE, so we check manually.
+async fn add_column_if_missing(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<()> {
+    if !has_column(pool, table, column).await? {
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(pool)
+        .await?;
+        tracing::info!("Added {} column to {} table", column, table);
+    }
+    Ok(())
+}
+
 /// Initial schema (v1) - used for fresh installations
 async fn migrate_v0_to_v1(pool: &SqlitePool) -> Result<()> {
     providers::create_providers_table(pool).await?;
@@ -305,3 +541,330 @@ async fn migrate_v9_to_v10(pool: &SqlitePool) -> Result<()> {
     tracing::info!("Recreated skills and assistant_skills tables with UNIQUE(name, source)");
     Ok(())
 }
+
+/// Migration v10 -> v11: Persist the model/assistant bound to a conversation
+/// so `send_message` can resolve it without the frontend passing it along.
+async fn migrate_v10_to_v11(pool: &SqlitePool) -> Result<()> {
+    ensure_conversation_binding_columns(pool).await?;
+    Ok(())
+}
+
+/// Ensure selected_model_id and selected_assistant_id columns exist in conversation_settings (idempotent)
+async fn ensure_conversation_binding_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversation_settings", "selected_model_id", "TEXT").await?;
+    add_column_if_missing(
+        pool,
+        "conversation_settings",
+        "selected_assistant_id",
+        "TEXT",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Migration v11 -> v12: Per-model prices and per-message token usage/cost
+async fn migrate_v11_to_v12(pool: &SqlitePool) -> Result<()> {
+    ensure_usage_pricing_columns(pool).await?;
+    Ok(())
+}
+
+/// Ensure pricing columns on models and usage columns on messages exist (idempotent).
+/// Prices are USD per 1M tokens.
+async fn ensure_usage_pricing_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "models", "input_price", "REAL").await?;
+    add_column_if_missing(pool, "models", "output_price", "REAL").await?;
+    add_column_if_missing(pool, "messages", "input_tokens", "INTEGER").await?;
+    add_column_if_missing(pool, "messages", "output_tokens", "INTEGER").await?;
+    add_column_if_missing(pool, "messages", "cost", "REAL").await?;
+    Ok(())
+}
+
+/// Migration v12 -> v13: Model visibility and manual ordering
+async fn migrate_v12_to_v13(pool: &SqlitePool) -> Result<()> {
+    ensure_model_display_columns(pool).await?;
+    Ok(())
+}
+
+/// Ensure is_hidden and sort_order columns exist in models (idempotent)
+async fn ensure_model_display_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "models", "is_hidden", "INTEGER NOT NULL DEFAULT 0").await?;
+    add_column_if_missing(pool, "models", "sort_order", "INTEGER NOT NULL DEFAULT 0").await?;
+    Ok(())
+}
+
+/// Migration v13 -> v14: Conversation summaries
+async fn migrate_v13_to_v14(pool: &SqlitePool) -> Result<()> {
+    ensure_conversation_summary_columns(pool).await?;
+    Ok(())
+}
+
+/// Ensure summary and summary_message_count columns exist in conversations (idempotent)
+async fn ensure_conversation_summary_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversations", "summary", "TEXT").await?;
+    add_column_if_missing(
+        pool,
+        "conversations",
+        "summary_message_count",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Migration v14 -> v15: Suggested follow-up prompts on assistant messages
+async fn migrate_v14_to_v15(pool: &SqlitePool) -> Result<()> {
+    ensure_follow_up_suggestions_column(pool).await?;
+    Ok(())
+}
+
+/// Ensure follow_up_suggestions (JSON array) column exists in messages (idempotent)
+async fn ensure_follow_up_suggestions_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "messages", "follow_up_suggestions", "TEXT").await?;
+    Ok(())
+}
+
+/// Migration v15 -> v16: Transcriptions step table for audio attachments
+async fn migrate_v15_to_v16(pool: &SqlitePool) -> Result<()> {
+    // CREATE TABLE IF NOT EXISTS only adds the new transcriptions table
+    steps::create_steps_table(pool).await?;
+    Ok(())
+}
+
+/// Migration v16 -> v17: Persistent background job queue
+async fn migrate_v16_to_v17(pool: &SqlitePool) -> Result<()> {
+    jobs::create_jobs_table(pool).await?;
+    Ok(())
+}
+
+/// Migration v17 -> v18: Provider, model and parameters that generated each reply
+async fn migrate_v17_to_v18(pool: &SqlitePool) -> Result<()> {
+    ensure_message_generation_columns(pool).await?;
+    Ok(())
+}
+
+/// Ensure provider_type, model_id and model_params (JSON) columns exist in messages (idempotent)
+async fn ensure_message_generation_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "messages", "provider_type", "TEXT").await?;
+    add_column_if_missing(pool, "messages", "model_id", "TEXT").await?;
+    add_column_if_missing(pool, "messages", "model_params", "TEXT").await?;
+    Ok(())
+}
+
+/// Migration v18 -> v19: Per-conversation history strategy.
+/// Conversations that had a message limit keep it as `last_n`.
+async fn migrate_v18_to_v19(pool: &SqlitePool) -> Result<()> {
+    ensure_history_mode_column(pool).await?;
+    sqlx::query(
+        "UPDATE conversation_settings SET history_mode = 'last_n' WHERE context_message_count > 0",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Ensure history_mode column exists in conversation_settings (idempotent)
+async fn ensure_history_mode_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "conversation_settings",
+        "history_mode",
+        "TEXT DEFAULT 'all'",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Migration v19 -> v20: Token budget replaces the message-count history limit.
+/// `context_message_count` is left in place but no longer read.
+async fn migrate_v19_to_v20(pool: &SqlitePool) -> Result<()> {
+    ensure_context_budget_column(pool).await?;
+    sqlx::query(
+        "UPDATE conversation_settings SET history_mode = 'token_budget' WHERE history_mode = 'last_n'",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Ensure context_budget_percent column exists in conversation_settings (idempotent)
+async fn ensure_context_budget_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(
+        pool,
+        "conversation_settings",
+        "context_budget_percent",
+        "INTEGER",
+    )
+    .await?;
+    Ok(())
+}
+
+/// Migration v20 -> v21: Add alternative queries and confidence to search_decisions
+async fn migrate_v20_to_v21(pool: &SqlitePool) -> Result<()> {
+    ensure_search_decision_detail_columns(pool).await
+}
+
+/// Ensure alternative_queries and confidence columns exist in search_decisions (idempotent)
+async fn ensure_search_decision_detail_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "search_decisions", "alternative_queries", "TEXT").await?;
+    add_column_if_missing(pool, "search_decisions", "confidence", "REAL").await?;
+    Ok(())
+}
+
+async fn migrate_v21_to_v22(pool: &SqlitePool) -> Result<()> {
+    ensure_openrouter_routing_columns(pool).await
+}
+
+/// Ensure openrouter_routing (JSON) columns exist in providers and models (idempotent)
+async fn ensure_openrouter_routing_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "providers", "openrouter_routing", "TEXT").await?;
+    add_column_if_missing(pool, "models", "openrouter_routing", "TEXT").await?;
+    Ok(())
+}
+
+async fn migrate_v22_to_v23(pool: &SqlitePool) -> Result<()> {
+    ensure_model_default_preset_column(pool).await
+}
+
+/// Ensure default_preset_id column exists in models (idempotent)
+async fn ensure_model_default_preset_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "models", "default_preset_id", "TEXT").await
+}
+
+async fn migrate_v23_to_v24(pool: &SqlitePool) -> Result<()> {
+    ensure_conversation_budget_columns(pool).await
+}
+
+/// Ensure token_budget and cost_budget columns exist in conversation_settings (idempotent)
+async fn ensure_conversation_budget_columns(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversation_settings", "token_budget", "INTEGER").await?;
+    add_column_if_missing(pool, "conversation_settings", "cost_budget", "REAL").await?;
+    Ok(())
+}
+
+/// Migration v24 -> v25: Application-wide usage ledger, seeded from existing messages
+async fn migrate_v24_to_v25(pool: &SqlitePool) -> Result<()> {
+    usage::create_usage_ledger_table(pool).await?;
+    usage::backfill_usage_ledger(pool).await?;
+    Ok(())
+}
+
+async fn migrate_v25_to_v26(pool: &SqlitePool) -> Result<()> {
+    ensure_provider_policy_column(pool).await
+}
+
+/// Ensure provider_policy_id column exists in conversation_settings (idempotent)
+async fn ensure_provider_policy_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversation_settings", "provider_policy_id", "TEXT").await
+}
+
+/// Migration v26 -> v27: Keep earlier versions of edited messages
+async fn migrate_v26_to_v27(pool: &SqlitePool) -> Result<()> {
+    messages::create_message_revisions_table(pool).await
+}
+
+/// Migration v27 -> v28: Bookmarks and reactions on messages
+async fn migrate_v27_to_v28(pool: &SqlitePool) -> Result<()> {
+    messages::create_message_flags_table(pool).await
+}
+
+async fn migrate_v28_to_v29(pool: &SqlitePool) -> Result<()> {
+    ensure_conversation_metadata_column(pool).await
+}
+
+/// Ensure metadata (JSON) column exists in conversations (idempotent)
+async fn ensure_conversation_metadata_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversations", "metadata", "TEXT").await
+}
+
+async fn migrate_v29_to_v30(pool: &SqlitePool) -> Result<()> {
+    ensure_reply_language_column(pool).await
+}
+
+/// Ensure reply_language column exists in conversation_settings (idempotent)
+async fn ensure_reply_language_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversation_settings", "reply_language", "TEXT").await
+}
+
+/// Migration v30 -> v31: Translations step table
+async fn migrate_v30_to_v31(pool: &SqlitePool) -> Result<()> {
+    // CREATE TABLE IF NOT EXISTS only adds the new translations table
+    steps::create_steps_table(pool).await
+}
+
+/// Migration v31 -> v32: Workspaces sharing default settings between conversations
+async fn migrate_v31_to_v32(pool: &SqlitePool) -> Result<()> {
+    conversations::create_workspaces_table(pool).await?;
+    ensure_conversation_workspace_column(pool).await
+}
+
+/// Ensure workspace_id column and index exist in conversations (idempotent)
+async fn ensure_conversation_workspace_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversations", "workspace_id", "TEXT").await?;
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_conversations_workspace ON conversations(workspace_id)",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Migration v32 -> v33: Individual search result items
+async fn migrate_v32_to_v33(pool: &SqlitePool) -> Result<()> {
+    messages::create_search_result_items_table(pool).await
+}
+
+/// Migration v33 -> v34: Citations of fetched pages in assistant messages
+async fn migrate_v33_to_v34(pool: &SqlitePool) -> Result<()> {
+    messages::create_message_citations_table(pool).await
+}
+
+/// Migration v34 -> v35: Verifications step table
+async fn migrate_v34_to_v35(pool: &SqlitePool) -> Result<()> {
+    steps::create_steps_table(pool).await
+}
+
+/// Migration v35 -> v36: Favicons cached in storage
+async fn migrate_v35_to_v36(pool: &SqlitePool) -> Result<()> {
+    ensure_fetch_result_favicon_column(pool).await
+}
+
+/// Ensure favicon_path column exists in fetch_results (idempotent)
+async fn ensure_fetch_result_favicon_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "fetch_results", "favicon_path", "TEXT").await
+}
+
+/// Migration v36 -> v37: Scheduled automations
+async fn migrate_v36_to_v37(pool: &SqlitePool) -> Result<()> {
+    automations::create_automations_table(pool).await
+}
+
+/// Migration v37 -> v38: Automation triggers and actions, run history, archived
+/// conversations
+async fn migrate_v37_to_v38(pool: &SqlitePool) -> Result<()> {
+    // Fresh databases already created the new shape at v37
+    if has_column(pool, "automations", "prompt").await? {
+        automations::convert_scheduled_prompts(pool).await?;
+    }
+    automations::create_automation_runs_table(pool).await?;
+    ensure_conversation_archived_column(pool).await
+}
+
+/// Ensure archived_at column exists in conversations (idempotent)
+async fn ensure_conversation_archived_column(pool: &SqlitePool) -> Result<()> {
+    add_column_if_missing(pool, "conversations", "archived_at", "TEXT").await
+}
+
+/// Migration v38 -> v39: Conversation templates
+async fn migrate_v38_to_v39(pool: &SqlitePool) -> Result<()> {
+    conversations::create_conversation_templates_table(pool).await
+}
+
+/// Migration v39 -> v40: Assistant switch step table
+async fn migrate_v39_to_v40(pool: &SqlitePool) -> Result<()> {
+    steps::create_steps_table(pool).await
+}
+
+/// Migration v40 -> v41: Cached explanations of process steps
+async fn migrate_v40_to_v41(pool: &SqlitePool) -> Result<()> {
+    steps::create_steps_table(pool).await
+}
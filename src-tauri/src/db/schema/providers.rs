@@ -12,6 +12,7 @@ pub async fn create_providers_table(pool: &SqlitePool) -> Result<()> {
             api_style TEXT,
             description TEXT,
             is_enabled INTEGER DEFAULT 1,
+            openrouter_routing TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL
         )",
@@ -32,6 +33,12 @@ pub async fn create_models_table(pool: &SqlitePool) -> Result<()> {
             description TEXT,
             is_starred INTEGER DEFAULT 0,
             is_deleted INTEGER DEFAULT 0,
+            input_price REAL,
+            output_price REAL,
+            openrouter_routing TEXT,
+            default_preset_id TEXT,
+            is_hidden INTEGER NOT NULL DEFAULT 0,
+            sort_order INTEGER NOT NULL DEFAULT 0,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
             FOREIGN KEY (provider_id) REFERENCES providers(id) ON DELETE CASCADE
@@ -31,6 +31,8 @@ pub async fn create_steps_table(pool: &SqlitePool) -> Result<()> {
             reasoning TEXT NOT NULL,
             search_needed INTEGER NOT NULL,
             search_query TEXT,
+            alternative_queries TEXT,
+            confidence REAL,
             search_result_id TEXT,
             display_order INTEGER DEFAULT 0,
             created_at TEXT NOT NULL,
@@ -118,5 +120,119 @@ pub async fn create_steps_table(pool: &SqlitePool) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Transcriptions table - transcripts of audio attachments on user messages
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS transcriptions (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            file_id TEXT,
+            content TEXT NOT NULL,
+            engine TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'success',
+            error TEXT,
+            duration_ms INTEGER,
+            display_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE,
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE SET NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_transcriptions_message ON transcriptions(message_id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Translations table - on-demand translations of messages
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS translations (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            target_language TEXT NOT NULL,
+            content TEXT NOT NULL,
+            model TEXT,
+            display_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_translations_message ON translations(message_id)")
+        .execute(pool)
+        .await?;
+
+    // Verifications table - checks of assistant messages against their sources
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS verifications (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            verdict TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            unsupported_claims TEXT NOT NULL DEFAULT '[]',
+            model TEXT,
+            display_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_verifications_message ON verifications(message_id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Assistant switches table - handoffs to another assistant mid-conversation
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS assistant_switches (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            from_assistant_id TEXT,
+            to_assistant_id TEXT NOT NULL,
+            summary TEXT,
+            display_order INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_assistant_switches_message ON assistant_switches(message_id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Step explanations table - cached plain-language explanations of thinking steps
+    // and tool calls, one per step
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS step_explanations (
+            id TEXT PRIMARY KEY,
+            step_id TEXT NOT NULL UNIQUE,
+            step_type TEXT NOT NULL,
+            message_id TEXT NOT NULL,
+            content TEXT NOT NULL,
+            model TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id) ON DELETE CASCADE
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_step_explanations_message ON step_explanations(message_id)",
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+pub async fn create_usage_ledger_table(pool: &SqlitePool) -> Result<()> {
+    // Application-wide usage log. Provider and model names are copied in and there are
+    // no foreign keys, so spend history survives deleting conversations or providers.
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS usage_ledger (
+            id TEXT PRIMARY KEY,
+            message_id TEXT,
+            conversation_id TEXT,
+            provider_id TEXT,
+            provider_name TEXT,
+            provider_type TEXT,
+            model_db_id TEXT,
+            model_id TEXT,
+            model_name TEXT,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            cost REAL,
+            created_at TEXT NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_usage_ledger_created_at ON usage_ledger(created_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Copy usage already recorded on messages into the ledger (idempotent)
+pub async fn backfill_usage_ledger(pool: &SqlitePool) -> Result<()> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO usage_ledger (
+            id, message_id, conversation_id, provider_id, provider_name, provider_type,
+            model_db_id, model_id, model_name, input_tokens, output_tokens, cost, created_at
+        )
+        SELECT m.id, m.id, m.conversation_id, p.id, p.name,
+               COALESCE(m.provider_type, p.provider_type), mo.id,
+               COALESCE(m.model_id, mo.model_id), mo.name,
+               COALESCE(m.input_tokens, 0), COALESCE(m.output_tokens, 0), m.cost, m.created_at
+        FROM messages m
+        LEFT JOIN assistants a ON m.sender_type = 'assistant' AND a.id = m.sender_id
+        LEFT JOIN models mo ON mo.id =
+            CASE WHEN m.sender_type = 'model' THEN m.sender_id ELSE a.model_id END
+        LEFT JOIN providers p ON p.id = mo.provider_id
+        WHERE m.input_tokens IS NOT NULL OR m.output_tokens IS NOT NULL",
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
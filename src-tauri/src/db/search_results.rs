@@ -4,7 +4,9 @@ use sqlx::Row;
 use uuid::Uuid;
 
 use super::Database;
-use crate::models::{CreateSearchResultRequest, SearchResult};
+use crate::models::{
+    CreateSearchResultItemRequest, CreateSearchResultRequest, SearchResult, SearchResultItem,
+};
 
 impl Database {
     pub async fn create_search_result(
@@ -103,4 +105,83 @@ impl Database {
             .await?;
         Ok(())
     }
+
+    /// Store a search's result list, replacing items stored for it before
+    pub async fn set_search_result_items(
+        &self,
+        search_result_id: &str,
+        items: Vec<CreateSearchResultItemRequest>,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM search_result_items WHERE search_result_id = ?")
+            .bind(search_result_id)
+            .execute(&mut *tx)
+            .await?;
+        for (index, item) in items.into_iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO search_result_items (id, search_result_id, rank, title, url, snippet, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::now_v7().to_string())
+            .bind(search_result_id)
+            .bind(index as i32 + 1)
+            .bind(&item.title)
+            .bind(&item.url)
+            .bind(&item.snippet)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get_search_result_items(
+        &self,
+        search_result_id: &str,
+    ) -> Result<Vec<SearchResultItem>> {
+        let rows = sqlx::query(&format!(
+            "{} WHERE i.search_result_id = ? ORDER BY i.rank",
+            SELECT_SEARCH_RESULT_ITEMS
+        ))
+        .bind(search_result_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.iter().map(search_result_item_from_row).collect())
+    }
+
+    pub async fn get_search_result_item(&self, id: &str) -> Result<SearchResultItem> {
+        let row = sqlx::query(&format!("{} WHERE i.id = ?", SELECT_SEARCH_RESULT_ITEMS))
+            .bind(id)
+            .fetch_optional(self.pool.as_ref())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Search result item not found: {}", id))?;
+
+        Ok(search_result_item_from_row(&row))
+    }
+}
+
+/// `fetched` tells whether the page was fetched for the search the item belongs to
+const SELECT_SEARCH_RESULT_ITEMS: &str =
+    "SELECT i.id, i.search_result_id, i.rank, i.title, i.url, i.snippet, i.created_at,
+            EXISTS (
+                SELECT 1 FROM fetch_results f
+                WHERE f.source_type = 'search' AND f.source_id = i.search_result_id
+                  AND f.url = i.url
+            ) AS fetched
+     FROM search_result_items i";
+
+fn search_result_item_from_row(row: &sqlx::sqlite::SqliteRow) -> SearchResultItem {
+    SearchResultItem {
+        id: row.get("id"),
+        search_result_id: row.get("search_result_id"),
+        rank: row.get("rank"),
+        title: row.get("title"),
+        url: row.get("url"),
+        snippet: row.get("snippet"),
+        fetched: row.get("fetched"),
+        created_at: row.get("created_at"),
+    }
 }
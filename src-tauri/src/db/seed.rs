@@ -62,7 +62,7 @@ impl Database {
         };
 
         // Check if models already exist for this provider
-        let existing_models = self.list_models().await?;
+        let existing_models = self.list_models(false).await?;
         let provider_has_models = existing_models
             .iter()
             .any(|m| m.provider_id == ollama_provider.id);
@@ -111,6 +111,8 @@ impl Database {
                         model_id: ollama_model.id.clone(),
                         description: ollama_model.description.clone(),
                         is_starred: Some(false),
+                        input_price: None,
+                        output_price: None,
                     })
                     .await?;
                 tracing::info!("✅ [db] Created model: {}", model.name);
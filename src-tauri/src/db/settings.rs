@@ -28,6 +28,14 @@ impl Database {
         Ok(())
     }
 
+    pub async fn delete_setting(&self, key: &str) -> Result<()> {
+        sqlx::query("DELETE FROM settings WHERE key = ?")
+            .bind(key)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
     pub async fn get_all_settings(&self) -> Result<Vec<Setting>> {
         let rows = sqlx::query("SELECT key, value, updated_at FROM settings ORDER BY key")
             .fetch_all(self.pool.as_ref())
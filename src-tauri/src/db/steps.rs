@@ -1,15 +1,51 @@
 use anyhow::Result;
 use chrono::Utc;
-use sqlx::Row;
+use sqlx::{Row, sqlite::SqliteRow};
 use uuid::Uuid;
 
 use super::Database;
 use crate::models::{
-    CodeExecution, ContentBlock, CreateCodeExecutionRequest, CreateContentBlockRequest,
-    CreateSearchDecisionRequest, CreateThinkingStepRequest, CreateToolCallRequest, ProcessStep,
-    SearchDecision, ThinkingStep, ToolCall,
+    AssistantSwitch, CodeExecution, ContentBlock, CreateAssistantSwitchRequest,
+    CreateCodeExecutionRequest, CreateContentBlockRequest, CreateSearchDecisionRequest,
+    CreateStepExplanationRequest, CreateThinkingStepRequest, CreateToolCallRequest,
+    CreateTranscriptionRequest, CreateTranslationRequest, CreateVerificationRequest, ProcessStep,
+    SearchDecision, StepExplanation, ThinkingStep, ToolCall, Transcription, Translation,
+    Verification,
 };
 
+fn map_search_decision_row(row: &SqliteRow) -> SearchDecision {
+    let search_needed: i32 = row.get("search_needed");
+    let alternative_queries: Option<String> = row.get("alternative_queries");
+    SearchDecision {
+        id: row.get("id"),
+        message_id: row.get("message_id"),
+        reasoning: row.get("reasoning"),
+        search_needed: search_needed != 0,
+        search_query: row.get("search_query"),
+        alternative_queries: alternative_queries
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default(),
+        confidence: row.get("confidence"),
+        search_result_id: row.get("search_result_id"),
+        display_order: row.get("display_order"),
+        created_at: row.get("created_at"),
+    }
+}
+
+fn map_verification_row(row: &SqliteRow) -> Verification {
+    let unsupported_claims: String = row.get("unsupported_claims");
+    Verification {
+        id: row.get("id"),
+        message_id: row.get("message_id"),
+        verdict: row.get("verdict"),
+        summary: row.get("summary"),
+        unsupported_claims: serde_json::from_str(&unsupported_claims).unwrap_or_default(),
+        model: row.get("model"),
+        display_order: row.get("display_order"),
+        created_at: row.get("created_at"),
+    }
+}
+
 impl Database {
     // Thinking Step operations
     pub async fn create_thinking_step(
@@ -97,16 +133,19 @@ impl Database {
         let id = Uuid::now_v7().to_string();
         let now = Utc::now().to_rfc3339();
         let display_order = req.display_order.unwrap_or(0);
+        let alternative_queries = serde_json::to_string(&req.alternative_queries)?;
 
         sqlx::query(
-            "INSERT INTO search_decisions (id, message_id, reasoning, search_needed, search_query, search_result_id, display_order, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO search_decisions (id, message_id, reasoning, search_needed, search_query, alternative_queries, confidence, search_result_id, display_order, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&id)
         .bind(&req.message_id)
         .bind(&req.reasoning)
         .bind(req.search_needed as i32)
         .bind(&req.search_query)
+        .bind(&alternative_queries)
+        .bind(req.confidence)
         .bind(&req.search_result_id)
         .bind(display_order)
         .bind(&now)
@@ -118,7 +157,7 @@ impl Database {
 
     pub async fn get_search_decision(&self, id: &str) -> Result<SearchDecision> {
         let row = sqlx::query(
-            "SELECT id, message_id, reasoning, search_needed, search_query, search_result_id, display_order, created_at
+            "SELECT id, message_id, reasoning, search_needed, search_query, alternative_queries, confidence, search_result_id, display_order, created_at
              FROM search_decisions WHERE id = ?"
         )
         .bind(id)
@@ -126,18 +165,7 @@ impl Database {
         .await?
         .ok_or_else(|| anyhow::anyhow!("Search decision not found: {}", id))?;
 
-        let search_needed: i32 = row.get("search_needed");
-
-        Ok(SearchDecision {
-            id: row.get("id"),
-            message_id: row.get("message_id"),
-            reasoning: row.get("reasoning"),
-            search_needed: search_needed != 0,
-            search_query: row.get("search_query"),
-            search_result_id: row.get("search_result_id"),
-            display_order: row.get("display_order"),
-            created_at: row.get("created_at"),
-        })
+        Ok(map_search_decision_row(&row))
     }
 
     pub async fn get_search_decisions_by_message(
@@ -145,29 +173,27 @@ impl Database {
         message_id: &str,
     ) -> Result<Vec<SearchDecision>> {
         let rows = sqlx::query(
-            "SELECT id, message_id, reasoning, search_needed, search_query, search_result_id, display_order, created_at
+            "SELECT id, message_id, reasoning, search_needed, search_query, alternative_queries, confidence, search_result_id, display_order, created_at
              FROM search_decisions WHERE message_id = ? ORDER BY display_order, created_at"
         )
         .bind(message_id)
         .fetch_all(self.pool.as_ref())
         .await?;
 
-        Ok(rows
-            .iter()
-            .map(|row| {
-                let search_needed: i32 = row.get("search_needed");
-                SearchDecision {
-                    id: row.get("id"),
-                    message_id: row.get("message_id"),
-                    reasoning: row.get("reasoning"),
-                    search_needed: search_needed != 0,
-                    search_query: row.get("search_query"),
-                    search_result_id: row.get("search_result_id"),
-                    display_order: row.get("display_order"),
-                    created_at: row.get("created_at"),
-                }
-            })
-            .collect())
+        Ok(rows.iter().map(map_search_decision_row).collect())
+    }
+
+    /// Mark a decision as needing a search with `query`, e.g. after the user overrides it
+    pub async fn force_search_decision(&self, id: &str, query: &str) -> Result<SearchDecision> {
+        sqlx::query(
+            "UPDATE search_decisions SET search_needed = 1, search_query = ?, search_result_id = NULL WHERE id = ?",
+        )
+        .bind(query)
+        .bind(id)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_search_decision(id).await
     }
 
     pub async fn delete_search_decision(&self, id: &str) -> Result<()> {
@@ -459,6 +485,302 @@ impl Database {
         Ok(())
     }
 
+    // Transcription operations
+    pub async fn create_transcription(
+        &self,
+        req: CreateTranscriptionRequest,
+    ) -> Result<Transcription> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+        let status = req.status.unwrap_or_else(|| "success".to_string());
+        let display_order = req.display_order.unwrap_or(0);
+
+        sqlx::query(
+            "INSERT INTO transcriptions (id, message_id, file_id, content, engine, status, error, duration_ms, display_order, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&req.message_id)
+        .bind(&req.file_id)
+        .bind(&req.content)
+        .bind(&req.engine)
+        .bind(&status)
+        .bind(&req.error)
+        .bind(req.duration_ms)
+        .bind(display_order)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_transcription(&id).await
+    }
+
+    pub async fn get_transcription(&self, id: &str) -> Result<Transcription> {
+        let row = sqlx::query(
+            "SELECT id, message_id, file_id, content, engine, status, error, duration_ms, display_order, created_at
+             FROM transcriptions WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Transcription not found: {}", id))?;
+
+        Ok(Transcription {
+            id: row.get("id"),
+            message_id: row.get("message_id"),
+            file_id: row.get("file_id"),
+            content: row.get("content"),
+            engine: row.get("engine"),
+            status: row.get("status"),
+            error: row.get("error"),
+            duration_ms: row.get("duration_ms"),
+            display_order: row.get("display_order"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    pub async fn get_transcriptions_by_message(
+        &self,
+        message_id: &str,
+    ) -> Result<Vec<Transcription>> {
+        let rows = sqlx::query(
+            "SELECT id, message_id, file_id, content, engine, status, error, duration_ms, display_order, created_at
+             FROM transcriptions WHERE message_id = ? ORDER BY display_order, created_at",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| Transcription {
+                id: row.get("id"),
+                message_id: row.get("message_id"),
+                file_id: row.get("file_id"),
+                content: row.get("content"),
+                engine: row.get("engine"),
+                status: row.get("status"),
+                error: row.get("error"),
+                duration_ms: row.get("duration_ms"),
+                display_order: row.get("display_order"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    pub async fn delete_transcription(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM transcriptions WHERE id = ?")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    // Translation operations
+    pub async fn create_translation(&self, req: CreateTranslationRequest) -> Result<Translation> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+        let display_order = req.display_order.unwrap_or(0);
+
+        sqlx::query(
+            "INSERT INTO translations (id, message_id, target_language, content, model, display_order, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&req.message_id)
+        .bind(&req.target_language)
+        .bind(&req.content)
+        .bind(&req.model)
+        .bind(display_order)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        let translation = sqlx::query_as::<_, Translation>(
+            "SELECT id, message_id, target_language, content, model, display_order, created_at
+             FROM translations WHERE id = ?",
+        )
+        .bind(&id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(translation)
+    }
+
+    pub async fn get_translations_by_message(&self, message_id: &str) -> Result<Vec<Translation>> {
+        let translations = sqlx::query_as::<_, Translation>(
+            "SELECT id, message_id, target_language, content, model, display_order, created_at
+             FROM translations WHERE message_id = ? ORDER BY display_order, created_at",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        Ok(translations)
+    }
+
+    pub async fn delete_translation(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM translations WHERE id = ?")
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    // Verification operations
+    pub async fn create_verification(
+        &self,
+        req: CreateVerificationRequest,
+    ) -> Result<Verification> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+        let display_order = req.display_order.unwrap_or(0);
+        let unsupported_claims = serde_json::to_string(&req.unsupported_claims)?;
+
+        sqlx::query(
+            "INSERT INTO verifications (id, message_id, verdict, summary, unsupported_claims, model, display_order, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&req.message_id)
+        .bind(&req.verdict)
+        .bind(&req.summary)
+        .bind(&unsupported_claims)
+        .bind(&req.model)
+        .bind(display_order)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        let row = sqlx::query(
+            "SELECT id, message_id, verdict, summary, unsupported_claims, model, display_order, created_at
+             FROM verifications WHERE id = ?",
+        )
+        .bind(&id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(map_verification_row(&row))
+    }
+
+    pub async fn get_verifications_by_message(
+        &self,
+        message_id: &str,
+    ) -> Result<Vec<Verification>> {
+        let rows = sqlx::query(
+            "SELECT id, message_id, verdict, summary, unsupported_claims, model, display_order, created_at
+             FROM verifications WHERE message_id = ? ORDER BY display_order, created_at",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        Ok(rows.iter().map(map_verification_row).collect())
+    }
+
+    // Assistant switch operations
+    pub async fn create_assistant_switch(
+        &self,
+        req: CreateAssistantSwitchRequest,
+    ) -> Result<AssistantSwitch> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+        let display_order = req.display_order.unwrap_or(0);
+
+        sqlx::query(
+            "INSERT INTO assistant_switches (id, message_id, from_assistant_id, to_assistant_id, summary, display_order, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&req.message_id)
+        .bind(&req.from_assistant_id)
+        .bind(&req.to_assistant_id)
+        .bind(&req.summary)
+        .bind(display_order)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        let switch = sqlx::query_as::<_, AssistantSwitch>(
+            "SELECT id, message_id, from_assistant_id, to_assistant_id, summary, display_order, created_at
+             FROM assistant_switches WHERE id = ?",
+        )
+        .bind(&id)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(switch)
+    }
+
+    pub async fn get_assistant_switches_by_message(
+        &self,
+        message_id: &str,
+    ) -> Result<Vec<AssistantSwitch>> {
+        let switches = sqlx::query_as::<_, AssistantSwitch>(
+            "SELECT id, message_id, from_assistant_id, to_assistant_id, summary, display_order, created_at
+             FROM assistant_switches WHERE message_id = ? ORDER BY display_order, created_at",
+        )
+        .bind(message_id)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        Ok(switches)
+    }
+
+    /// The most recent assistant switch in a conversation
+    pub async fn get_latest_assistant_switch(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<AssistantSwitch>> {
+        let switch = sqlx::query_as::<_, AssistantSwitch>(
+            "SELECT s.id, s.message_id, s.from_assistant_id, s.to_assistant_id, s.summary, s.display_order, s.created_at
+             FROM assistant_switches s
+             JOIN messages m ON m.id = s.message_id
+             WHERE m.conversation_id = ?
+             ORDER BY s.created_at DESC
+             LIMIT 1",
+        )
+        .bind(conversation_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        Ok(switch)
+    }
+
+    // Step explanation operations
+    pub async fn get_step_explanation(&self, step_id: &str) -> Result<Option<StepExplanation>> {
+        let explanation = sqlx::query_as::<_, StepExplanation>(
+            "SELECT id, step_id, step_type, message_id, content, model, created_at
+             FROM step_explanations WHERE step_id = ?",
+        )
+        .bind(step_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        Ok(explanation)
+    }
+
+    /// Store the explanation of a step, replacing an earlier one
+    pub async fn save_step_explanation(
+        &self,
+        req: CreateStepExplanationRequest,
+    ) -> Result<StepExplanation> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO step_explanations (id, step_id, step_type, message_id, content, model, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(step_id) DO UPDATE SET content = excluded.content, model = excluded.model,
+                created_at = excluded.created_at",
+        )
+        .bind(&id)
+        .bind(&req.step_id)
+        .bind(req.step_type.to_string())
+        .bind(&req.message_id)
+        .bind(&req.content)
+        .bind(&req.model)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_step_explanation(&req.step_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve step explanation"))
+    }
+
     // Get all process steps for a message (combined from all step tables)
     pub async fn get_message_steps(&self, message_id: &str) -> Result<Vec<ProcessStep>> {
         let mut steps: Vec<(i32, String, ProcessStep)> = Vec::new();
@@ -508,6 +830,42 @@ impl Database {
             ));
         }
 
+        // Fetch transcriptions
+        for step in self.get_transcriptions_by_message(message_id).await? {
+            steps.push((
+                step.display_order,
+                step.created_at.clone(),
+                ProcessStep::Transcription(step),
+            ));
+        }
+
+        // Fetch translations
+        for step in self.get_translations_by_message(message_id).await? {
+            steps.push((
+                step.display_order,
+                step.created_at.clone(),
+                ProcessStep::Translation(step),
+            ));
+        }
+
+        // Fetch verifications
+        for step in self.get_verifications_by_message(message_id).await? {
+            steps.push((
+                step.display_order,
+                step.created_at.clone(),
+                ProcessStep::Verification(step),
+            ));
+        }
+
+        // Fetch assistant switches
+        for step in self.get_assistant_switches_by_message(message_id).await? {
+            steps.push((
+                step.display_order,
+                step.created_at.clone(),
+                ProcessStep::AssistantSwitch(step),
+            ));
+        }
+
         // Sort by display_order, then by created_at
         steps.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
 
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::Row;
+use uuid::Uuid;
+
+use super::Database;
+use crate::models::{ModelSpend, NewUsageEntry, SenderUsage, SpendReport, SpendRow, UsageStats};
+
+impl Database {
+    /// Aggregate token usage and cost over messages that reported usage.
+    ///
+    /// Both filters are optional: `conversation_id` scopes to one conversation,
+    /// `since` (RFC 3339) restricts to messages created at or after that time.
+    pub async fn get_usage_stats(
+        &self,
+        conversation_id: Option<&str>,
+        since: Option<&str>,
+    ) -> Result<UsageStats> {
+        let rows = sqlx::query(
+            "SELECT sender_type, sender_id,
+                    COUNT(*) AS message_count,
+                    COALESCE(SUM(input_tokens), 0) AS input_tokens,
+                    COALESCE(SUM(output_tokens), 0) AS output_tokens,
+                    COALESCE(SUM(cost), 0.0) AS cost,
+                    SUM(CASE WHEN cost IS NULL THEN 1 ELSE 0 END) AS unpriced_count
+             FROM messages
+             WHERE (input_tokens IS NOT NULL OR output_tokens IS NOT NULL)
+               AND (?1 IS NULL OR conversation_id = ?1)
+               AND (?2 IS NULL OR created_at >= ?2)
+             GROUP BY sender_type, sender_id
+             ORDER BY cost DESC",
+        )
+        .bind(conversation_id)
+        .bind(since)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut stats = UsageStats {
+            message_count: 0,
+            input_tokens: 0,
+            output_tokens: 0,
+            cost: 0.0,
+            unpriced_message_count: 0,
+            by_sender: Vec::with_capacity(rows.len()),
+        };
+
+        for row in rows {
+            let sender = SenderUsage {
+                sender_type: row.get("sender_type"),
+                sender_id: row.get("sender_id"),
+                message_count: row.get("message_count"),
+                input_tokens: row.get("input_tokens"),
+                output_tokens: row.get("output_tokens"),
+                cost: row.get("cost"),
+            };
+            let unpriced: i64 = row.get("unpriced_count");
+
+            stats.message_count += sender.message_count;
+            stats.input_tokens += sender.input_tokens;
+            stats.output_tokens += sender.output_tokens;
+            stats.cost += sender.cost;
+            stats.unpriced_message_count += unpriced;
+            stats.by_sender.push(sender);
+        }
+
+        Ok(stats)
+    }
+
+    /// Append a reply's usage to the application-wide ledger
+    pub async fn record_usage(&self, entry: &NewUsageEntry) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO usage_ledger (
+                id, message_id, conversation_id, provider_id, provider_name, provider_type,
+                model_db_id, model_id, model_name, input_tokens, output_tokens, cost, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(&entry.message_id)
+        .bind(&entry.conversation_id)
+        .bind(&entry.provider_id)
+        .bind(&entry.provider_name)
+        .bind(&entry.provider_type)
+        .bind(&entry.model_db_id)
+        .bind(&entry.model_id)
+        .bind(&entry.model_name)
+        .bind(entry.input_tokens)
+        .bind(entry.output_tokens)
+        .bind(entry.cost)
+        .bind(Utc::now().to_rfc3339())
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(())
+    }
+
+    /// Total estimated cost in the ledger between `start` (inclusive) and `end`
+    pub async fn get_ledger_cost(&self, start: &str, end: &str) -> Result<f64> {
+        let (cost,): (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(cost), 0.0) FROM usage_ledger
+             WHERE created_at >= ? AND created_at < ?",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        Ok(cost)
+    }
+
+    /// Ledger usage between `start` (inclusive) and `end`, grouped by provider and model
+    pub async fn get_spend_report(
+        &self,
+        month: &str,
+        start: &str,
+        end: &str,
+        cap: Option<f64>,
+    ) -> Result<SpendReport> {
+        let rows = sqlx::query(
+            "SELECT provider_id, MAX(provider_name) AS provider_name, provider_type,
+                    model_db_id, model_id, MAX(model_name) AS model_name,
+                    COUNT(*) AS message_count,
+                    SUM(input_tokens) AS input_tokens,
+                    SUM(output_tokens) AS output_tokens,
+                    COALESCE(SUM(cost), 0.0) AS cost,
+                    SUM(CASE WHEN cost IS NULL THEN 1 ELSE 0 END) AS unpriced_count
+             FROM usage_ledger
+             WHERE created_at >= ? AND created_at < ?
+             GROUP BY provider_id, provider_type, model_db_id, model_id",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let rows = rows
+            .into_iter()
+            .map(|row| SpendRow {
+                provider_id: row.get("provider_id"),
+                provider_name: row.get("provider_name"),
+                provider_type: row.get("provider_type"),
+                model: ModelSpend {
+                    model_db_id: row.get("model_db_id"),
+                    model_id: row.get("model_id"),
+                    model_name: row.get("model_name"),
+                    message_count: row.get("message_count"),
+                    input_tokens: row.get("input_tokens"),
+                    output_tokens: row.get("output_tokens"),
+                    cost: row.get("cost"),
+                    unpriced_message_count: row.get("unpriced_count"),
+                },
+            })
+            .collect();
+
+        Ok(SpendReport::new(month.to_string(), cap, rows))
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::Database;
+use crate::models::{WEBHOOKS_KEY, Webhook, WebhookEvent, WebhookInput};
+
+/// On-disk form of a webhook: the secret is kept encrypted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredWebhook {
+    id: String,
+    name: String,
+    url: String,
+    #[serde(default)]
+    encrypted_secret: Option<String>,
+    events: Vec<WebhookEvent>,
+    is_enabled: bool,
+    created_at: String,
+}
+
+impl StoredWebhook {
+    fn into_webhook(self) -> Webhook {
+        let secret =
+            self.encrypted_secret
+                .and_then(|encrypted| match crate::crypto::decrypt(&encrypted) {
+                    Ok(secret) => Some(secret),
+                    Err(e) => {
+                        tracing::error!(
+                            "⚠️  [db] Failed to decrypt secret for webhook {}: {}",
+                            self.id,
+                            e
+                        );
+                        None
+                    }
+                });
+        Webhook {
+            id: self.id,
+            name: self.name,
+            url: self.url,
+            has_secret: secret.is_some(),
+            secret,
+            events: self.events,
+            is_enabled: self.is_enabled,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl Database {
+    async fn load_stored_webhooks(&self) -> Result<Vec<StoredWebhook>> {
+        let raw = self.get_setting(WEBHOOKS_KEY).await?;
+        Ok(raw
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default())
+    }
+
+    async fn save_stored_webhooks(&self, webhooks: &[StoredWebhook]) -> Result<()> {
+        self.set_setting(WEBHOOKS_KEY, &serde_json::to_string(webhooks)?)
+            .await
+    }
+
+    /// All webhooks with decrypted secrets
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        Ok(self
+            .load_stored_webhooks()
+            .await?
+            .into_iter()
+            .map(StoredWebhook::into_webhook)
+            .collect())
+    }
+
+    pub async fn create_webhook(&self, input: WebhookInput) -> Result<Webhook> {
+        let stored = StoredWebhook {
+            id: uuid::Uuid::now_v7().to_string(),
+            name: input.name,
+            url: input.url,
+            encrypted_secret: encrypt_secret(input.secret.as_deref())?,
+            events: input.events,
+            is_enabled: input.is_enabled,
+            created_at: Utc::now().to_rfc3339(),
+        };
+
+        let mut webhooks = self.load_stored_webhooks().await?;
+        webhooks.push(stored.clone());
+        self.save_stored_webhooks(&webhooks).await?;
+        Ok(stored.into_webhook())
+    }
+
+    pub async fn update_webhook(&self, id: &str, input: WebhookInput) -> Result<Webhook> {
+        let mut webhooks = self.load_stored_webhooks().await?;
+        let stored = webhooks
+            .iter_mut()
+            .find(|w| w.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Webhook not found: {}", id))?;
+
+        stored.name = input.name;
+        stored.url = input.url;
+        stored.events = input.events;
+        stored.is_enabled = input.is_enabled;
+        if input.secret.is_some() {
+            stored.encrypted_secret = encrypt_secret(input.secret.as_deref())?;
+        }
+        let updated = stored.clone();
+
+        self.save_stored_webhooks(&webhooks).await?;
+        Ok(updated.into_webhook())
+    }
+
+    pub async fn delete_webhook(&self, id: &str) -> Result<()> {
+        let mut webhooks = self.load_stored_webhooks().await?;
+        webhooks.retain(|w| w.id != id);
+        self.save_stored_webhooks(&webhooks).await
+    }
+}
+
+fn encrypt_secret(secret: Option<&str>) -> Result<Option<String>> {
+    match secret.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(secret) => Ok(Some(crate::crypto::encrypt(secret)?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::Row;
+use uuid::Uuid;
+
+use super::Database;
+use crate::models::{CreateWorkspaceRequest, UpdateWorkspaceRequest, Workspace, WorkspaceSettings};
+
+impl Database {
+    pub async fn create_workspace(&self, req: CreateWorkspaceRequest) -> Result<Workspace> {
+        let id = Uuid::now_v7().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO workspaces (id, name, settings, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(req.name.trim())
+        .bind(serde_json::to_string(&req.settings)?)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool.as_ref())
+        .await?;
+
+        self.get_workspace(&id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created workspace"))
+    }
+
+    pub async fn get_workspace(&self, id: &str) -> Result<Option<Workspace>> {
+        let row = sqlx::query(
+            "SELECT id, name, settings, created_at, updated_at FROM workspaces WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.as_ref().map(row_to_workspace))
+    }
+
+    pub async fn list_workspaces(&self) -> Result<Vec<Workspace>> {
+        let rows = sqlx::query(
+            "SELECT id, name, settings, created_at, updated_at FROM workspaces ORDER BY name",
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        Ok(rows.iter().map(row_to_workspace).collect())
+    }
+
+    pub async fn update_workspace(
+        &self,
+        id: &str,
+        req: UpdateWorkspaceRequest,
+    ) -> Result<Workspace> {
+        let existing = self
+            .get_workspace(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Workspace not found: {}", id))?;
+        let name = req
+            .name
+            .map(|n| n.trim().to_string())
+            .unwrap_or(existing.name);
+        let settings = req.settings.unwrap_or(existing.settings);
+
+        sqlx::query("UPDATE workspaces SET name = ?, settings = ?, updated_at = ? WHERE id = ?")
+            .bind(&name)
+            .bind(serde_json::to_string(&settings)?)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(self.pool.as_ref())
+            .await?;
+
+        self.get_workspace(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Workspace not found: {}", id))
+    }
+
+    /// Delete a workspace; its conversations are kept and fall back to the global defaults
+    pub async fn delete_workspace(&self, id: &str) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE conversations SET workspace_id = NULL WHERE workspace_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM workspaces WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Move a conversation into a workspace, or out of any (`None`)
+    pub async fn set_conversation_workspace(
+        &self,
+        conversation_id: &str,
+        workspace_id: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE conversations SET workspace_id = ? WHERE id = ?")
+            .bind(workspace_id)
+            .bind(conversation_id)
+            .execute(self.pool.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// The workspace a conversation belongs to, if any
+    pub async fn get_conversation_workspace(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<Workspace>> {
+        let row = sqlx::query(
+            "SELECT w.id, w.name, w.settings, w.created_at, w.updated_at
+             FROM workspaces w
+             JOIN conversations c ON c.workspace_id = w.id
+             WHERE c.id = ?",
+        )
+        .bind(conversation_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+
+        Ok(row.as_ref().map(row_to_workspace))
+    }
+}
+
+fn row_to_workspace(row: &sqlx::sqlite::SqliteRow) -> Workspace {
+    let settings: Option<String> = row.get("settings");
+    Workspace {
+        id: row.get("id"),
+        name: row.get("name"),
+        settings: settings
+            .and_then(|json| serde_json::from_str::<WorkspaceSettings>(&json).ok())
+            .unwrap_or_default(),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
@@ -0,0 +1,187 @@
+//! `chatshell://` URL scheme handling
+//!
+//! Supported links:
+//! - `chatshell://conversation/<id>` opens a conversation
+//! - `chatshell://new?prompt=...&assistant=...` starts a new chat, optionally
+//!   prefilled and with an assistant selected by id or name
+//!
+//! Links are turned into the same navigation events the tray uses, so launchers
+//! (Raycast, Alfred) and browsers can drive the app.
+
+use crate::commands::AppState;
+use crate::events::{self, NavigateConversation, NavigateNewChat};
+use tauri::{Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+pub const SCHEME: &str = "chatshell";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Conversation {
+        id: String,
+    },
+    NewChat {
+        prompt: Option<String>,
+        assistant: Option<String>,
+    },
+}
+
+/// Parse a `chatshell://` URL; unknown routes yield `None`
+pub fn parse(url: &Url) -> Option<DeepLink> {
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    // `chatshell://conversation/<id>` puts the route in the host
+    let mut segments = url
+        .host_str()
+        .into_iter()
+        .chain(url.path_segments().into_iter().flatten())
+        .filter(|s| !s.is_empty());
+
+    match segments.next()? {
+        "conversation" => {
+            let id = segments.next()?;
+            Some(DeepLink::Conversation { id: id.to_string() })
+        }
+        "new" => {
+            let param = |key: &str| {
+                url.query_pairs()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.into_owned())
+                    .filter(|v| !v.trim().is_empty())
+            };
+            Some(DeepLink::NewChat {
+                prompt: param("prompt"),
+                assistant: param("assistant"),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Handle links the app was launched with and any opened while it runs
+pub fn init(app: &tauri::AppHandle) {
+    // Installed bundles register the scheme; dev builds on Linux/Windows need it at runtime
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        tracing::warn!("⚠️ [deep-link] Failed to register URL scheme: {}", e);
+    }
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        handle_urls(app, urls);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle_urls(&handle, event.urls());
+    });
+}
+
+fn handle_urls(app: &tauri::AppHandle, urls: Vec<Url>) {
+    for url in urls {
+        match parse(&url) {
+            Some(link) => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move { open(&app, link).await });
+            }
+            None => tracing::warn!("⚠️ [deep-link] Unsupported link: {}", url),
+        }
+    }
+}
+
+async fn open(app: &tauri::AppHandle, link: DeepLink) {
+    let state: tauri::State<'_, AppState> = app.state();
+    tracing::info!("🔗 [deep-link] Opening {:?}", link);
+
+    match link {
+        DeepLink::Conversation { id } => {
+            if !matches!(state.db.get_conversation(&id).await, Ok(Some(_))) {
+                tracing::warn!("⚠️ [deep-link] Conversation not found: {}", id);
+                return;
+            }
+            crate::tray::show_main_window(app);
+            events::emit(
+                app,
+                NavigateConversation {
+                    conversation_id: id,
+                },
+            );
+        }
+        DeepLink::NewChat { prompt, assistant } => {
+            let assistant_id = match assistant {
+                Some(assistant) => resolve_assistant(&state, &assistant).await,
+                None => None,
+            };
+            crate::tray::show_main_window(app);
+            events::emit(
+                app,
+                NavigateNewChat {
+                    prompt,
+                    assistant_id,
+                },
+            );
+        }
+    }
+}
+
+/// Match an assistant by id, then by name (case-insensitive)
+async fn resolve_assistant(state: &AppState, assistant: &str) -> Option<String> {
+    let assistants = state.db.list_assistants().await.ok()?;
+    let found = assistants
+        .iter()
+        .find(|a| a.id == assistant)
+        .or_else(|| {
+            assistants
+                .iter()
+                .find(|a| a.name.eq_ignore_ascii_case(assistant))
+        })
+        .map(|a| a.id.clone());
+    if found.is_none() {
+        tracing::warn!("⚠️ [deep-link] Assistant not found: {}", assistant);
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(url: &str) -> Option<DeepLink> {
+        parse(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_parse_conversation_link() {
+        assert_eq!(
+            parse_str("chatshell://conversation/0192abcd"),
+            Some(DeepLink::Conversation {
+                id: "0192abcd".to_string()
+            })
+        );
+        assert_eq!(parse_str("chatshell://conversation/"), None);
+    }
+
+    #[test]
+    fn test_parse_new_chat_link() {
+        assert_eq!(
+            parse_str("chatshell://new?prompt=Explain%20this&assistant=Coder"),
+            Some(DeepLink::NewChat {
+                prompt: Some("Explain this".to_string()),
+                assistant: Some("Coder".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_str("chatshell://new"),
+            Some(DeepLink::NewChat {
+                prompt: None,
+                assistant: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_links() {
+        assert_eq!(parse_str("chatshell://settings"), None);
+        assert_eq!(parse_str("https://conversation/abc"), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Structured errors returned by Tauri commands and carried by error events
+//!
+//! Serialized as `{ kind, message, retryable, provider? }` so the frontend can tell
+//! an invalid API key from a flaky network or a bad request. Any displayable error
+//! converts into an `AppError` (like `anyhow::Error`, it deliberately doesn't
+//! implement `Display` itself), with the kind inferred from the message: provider
+//! failures carry an `[HTTP <status>]` prefix, transport errors come from reqwest.
+//!
+//! `chat-error` events carry the same fields (see `events::ChatError`).
+
+use serde::Serialize;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Missing or rejected credentials
+    Auth,
+    /// The server couldn't be reached
+    Network,
+    Timeout,
+    RateLimited,
+    NotFound,
+    /// The request itself was invalid
+    Validation,
+    /// The provider returned an error response
+    Provider,
+    Cancelled,
+    /// The conversation's token or cost budget would be exceeded
+    BudgetExceeded,
+    /// The conversation's provider policy doesn't allow the provider
+    ProviderNotAllowed,
+    /// Web search or the fetch fallback needs Chrome, which isn't installed
+    BrowserUnavailable,
+    Internal,
+}
+
+/// Details of a failed provider request
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProviderErrorDetails {
+    /// HTTP status returned by the provider
+    pub status: Option<u16>,
+    pub provider_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Whether retrying the same request may succeed
+    pub retryable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<ProviderErrorDetails>,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retryable: matches!(
+                kind,
+                ErrorKind::Network | ErrorKind::Timeout | ErrorKind::RateLimited
+            ),
+            provider: None,
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Validation, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    /// Infer the kind from an error message
+    pub fn classify(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let status = http_status(&message);
+        let lower = message.to_lowercase();
+
+        let kind = match status {
+            Some(401 | 403) => ErrorKind::Auth,
+            Some(404) => ErrorKind::NotFound,
+            Some(408) => ErrorKind::Timeout,
+            Some(429) => ErrorKind::RateLimited,
+            Some(400 | 422) => ErrorKind::Validation,
+            Some(_) => ErrorKind::Provider,
+            None if lower.contains("timed out") || lower.contains("timeout") => ErrorKind::Timeout,
+            None if lower.contains("401")
+                || lower.contains("403")
+                || lower.contains("unauthorized")
+                || lower.contains("api key")
+                || lower.contains("authentication") =>
+            {
+                ErrorKind::Auth
+            }
+            None if lower.contains("rate limit") || lower.contains("too many requests") => {
+                ErrorKind::RateLimited
+            }
+            None if lower.contains("connection refused")
+                || lower.contains("error sending request")
+                || lower.contains("dns error")
+                || lower.contains("failed to connect") =>
+            {
+                ErrorKind::Network
+            }
+            None if lower.contains("cancelled") => ErrorKind::Cancelled,
+            None if lower.contains("not found") => ErrorKind::NotFound,
+            None => ErrorKind::Internal,
+        };
+
+        let mut error = Self::new(kind, message);
+        if let Some(status) = status {
+            // Server-side failures are usually transient
+            error.retryable |= status >= 500;
+            error.provider = Some(ProviderErrorDetails {
+                status: Some(status),
+                provider_type: None,
+            });
+        }
+        error
+    }
+
+    /// Attach the provider type to a provider error
+    pub fn with_provider_type(mut self, provider_type: impl Into<String>) -> Self {
+        self.provider
+            .get_or_insert_with(ProviderErrorDetails::default)
+            .provider_type = Some(provider_type.into());
+        self
+    }
+}
+
+impl<E: Display> From<E> for AppError {
+    fn from(error: E) -> Self {
+        Self::classify(error.to_string())
+    }
+}
+
+/// Lets code that still uses string errors call commands with `?`
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.message
+    }
+}
+
+/// Status code from an `[HTTP <status>] ...` message
+fn http_status(message: &str) -> Option<u16> {
+    let rest = message.split("[HTTP ").nth(1)?;
+    rest.split(']').next()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_http_errors() {
+        let error = AppError::classify("[HTTP 401] Unauthorized: invalid key");
+        assert_eq!(error.kind, ErrorKind::Auth);
+        assert!(!error.retryable);
+        assert_eq!(error.provider.unwrap().status, Some(401));
+
+        let error = AppError::classify("[HTTP 503] Service unavailable");
+        assert_eq!(error.kind, ErrorKind::Provider);
+        assert!(error.retryable);
+
+        assert_eq!(
+            AppError::classify("[HTTP 429] Slow down").kind,
+            ErrorKind::RateLimited
+        );
+    }
+
+    #[test]
+    fn test_classify_transport_errors() {
+        let error = AppError::classify("error sending request for url (http://localhost:11434)");
+        assert_eq!(error.kind, ErrorKind::Network);
+        assert!(error.retryable);
+        assert_eq!(
+            AppError::classify("Connection timed out (15s)").kind,
+            ErrorKind::Timeout
+        );
+        assert_eq!(
+            AppError::classify("OpenAI API key required").kind,
+            ErrorKind::Auth
+        );
+        assert_eq!(
+            AppError::classify("Something broke").kind,
+            ErrorKind::Internal
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let error = AppError::validation("Title is required");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "kind": "validation",
+                "message": "Title is required",
+                "retryable": false,
+            })
+        );
+    }
+}
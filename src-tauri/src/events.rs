@@ -0,0 +1,559 @@
+//! Typed payloads for events emitted to the frontend
+//!
+//! Each event is a struct implementing [`AppEvent`], which ties it to its event
+//! name. [`emit`] serializes the payload flat, with a `schema_version` field added,
+//! so listeners can detect payloads from a newer or older backend. Bump
+//! [`EVENT_SCHEMA_VERSION`] when an existing field changes meaning or is removed;
+//! adding an optional field doesn't need a bump.
+//!
+//! Per-chunk streaming output goes through a [`StreamSink`] instead: over the
+//! `tauri::ipc::Channel` passed to the command that started the generation, so only
+//! the requesting window receives it, or as global events for callers without one.
+//! Completed tool calls sent over a channel are still handed to the webhooks.
+
+use crate::commands::chat::outbox::OutboxEntry;
+use crate::error::{AppError, ErrorKind, ProviderErrorDetails};
+use crate::models::{BudgetStatus, HistoryMode, Message, WebhookEvent};
+use serde::Serialize;
+use tauri::Emitter;
+use tauri::ipc::Channel;
+
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// A payload emitted under a fixed event name
+pub trait AppEvent: Serialize + Clone {
+    const NAME: &'static str;
+}
+
+/// A payload with the schema version added
+#[derive(Clone, Serialize)]
+pub struct Envelope<E> {
+    schema_version: u32,
+    #[serde(flatten)]
+    payload: E,
+}
+
+impl<E> Envelope<E> {
+    fn new(payload: E) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            payload,
+        }
+    }
+}
+
+/// Emit an event to all windows
+pub fn emit<E: AppEvent>(app: &tauri::AppHandle, event: E) {
+    if let Err(e) = app.emit(E::NAME, Envelope::new(event)) {
+        tracing::warn!("⚠️ [events] Failed to emit {}: {}", E::NAME, e);
+    }
+}
+
+/// Streaming output of a generation, tagged with its event name in `event`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum StreamEvent {
+    ChatStream(ChatStream),
+    ChatStreamReasoning(ChatStreamReasoning),
+    ReasoningStarted(ReasoningStarted),
+    ChatStreamImage(ChatStreamImage),
+    ToolCallStarted(ToolCallStarted),
+    ToolCallCompleted(ToolCallCompleted),
+}
+
+/// Channel the frontend passes to `send_message` to receive streaming output
+pub type StreamChannel = Channel<Envelope<StreamEvent>>;
+
+/// Destination for a generation's streaming output
+#[derive(Clone)]
+pub struct StreamSink {
+    app: tauri::AppHandle,
+    channel: Option<StreamChannel>,
+}
+
+impl StreamSink {
+    pub fn new(app: tauri::AppHandle, channel: Option<StreamChannel>) -> Self {
+        Self { app, channel }
+    }
+
+    pub fn send(&self, event: StreamEvent) {
+        let Some(channel) = &self.channel else {
+            match event {
+                StreamEvent::ChatStream(e) => emit(&self.app, e),
+                StreamEvent::ChatStreamReasoning(e) => emit(&self.app, e),
+                StreamEvent::ReasoningStarted(e) => emit(&self.app, e),
+                StreamEvent::ChatStreamImage(e) => emit(&self.app, e),
+                StreamEvent::ToolCallStarted(e) => emit(&self.app, e),
+                StreamEvent::ToolCallCompleted(e) => emit(&self.app, e),
+            }
+            return;
+        };
+        // Webhooks listen to global events, which the channel bypasses
+        if let StreamEvent::ToolCallCompleted(e) = &event {
+            crate::webhooks::forward(
+                &self.app,
+                WebhookEvent::ToolCallCompleted,
+                &Envelope::new(e.clone()),
+            );
+        }
+        // The window may have closed mid-generation; the response is still saved
+        if let Err(e) = channel.send(Envelope::new(event)) {
+            tracing::debug!("[events] Stream channel closed: {}", e);
+        }
+    }
+}
+
+macro_rules! app_event {
+    ($ty:ty, $name:literal) => {
+        impl AppEvent for $ty {
+            const NAME: &'static str = $name;
+        }
+    };
+}
+
+// ---------------------------------------------------------------------------
+// Generation lifecycle
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationStarted {
+    pub conversation_id: String,
+}
+app_event!(GenerationStarted, "generation-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationStopped {
+    pub conversation_id: String,
+}
+app_event!(GenerationStopped, "generation-stopped");
+
+/// The generation waits for a free slot; `generation-started` follows once it runs
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationQueued {
+    pub conversation_id: String,
+    pub provider: String,
+}
+app_event!(GenerationQueued, "generation-queued");
+
+/// A chunk of response text
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatStream {
+    pub conversation_id: String,
+    pub content: String,
+}
+app_event!(ChatStream, "chat-stream");
+
+/// A chunk of reasoning/thinking text
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatStreamReasoning {
+    pub conversation_id: String,
+    pub content: String,
+}
+app_event!(ChatStreamReasoning, "chat-stream-reasoning");
+
+/// The first reasoning chunk of a reasoning block
+#[derive(Debug, Clone, Serialize)]
+pub struct ReasoningStarted {
+    pub conversation_id: String,
+}
+app_event!(ReasoningStarted, "reasoning-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatStreamImage {
+    pub conversation_id: String,
+    /// Data URL of the generated image
+    pub image_url: String,
+}
+app_event!(ChatStreamImage, "chat-stream-image");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCallStarted {
+    pub conversation_id: String,
+    pub tool_call_id: String,
+    pub tool_name: String,
+    pub tool_input: String,
+}
+app_event!(ToolCallStarted, "tool-call-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCallCompleted {
+    pub conversation_id: String,
+    pub tool_call_id: String,
+    pub tool_name: String,
+    pub tool_input: String,
+    pub tool_output: String,
+}
+app_event!(ToolCallCompleted, "tool-call-completed");
+
+/// A response finished. `message` is `None` when a cancelled response had nothing
+/// to save.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatComplete {
+    pub conversation_id: String,
+    pub message: Option<Message>,
+    /// Set when the request also created the user message (e.g. image generation)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_message: Option<Message>,
+    pub cancelled: bool,
+}
+app_event!(ChatComplete, "chat-complete");
+
+/// Suggested follow-up prompts for a saved reply, generated after `chat-complete`
+#[derive(Debug, Clone, Serialize)]
+pub struct FollowUpSuggestions {
+    pub conversation_id: String,
+    pub message_id: String,
+    pub suggestions: Vec<String>,
+}
+app_event!(FollowUpSuggestions, "follow-up-suggestions");
+
+/// A response failed; carries the fields of [`AppError`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatError {
+    pub conversation_id: String,
+    pub error: String,
+    pub kind: ErrorKind,
+    pub retryable: bool,
+    pub provider: Option<ProviderErrorDetails>,
+}
+app_event!(ChatError, "chat-error");
+
+impl ChatError {
+    pub fn new(conversation_id: impl Into<String>, error: &AppError) -> Self {
+        Self {
+            conversation_id: conversation_id.into(),
+            error: error.message.clone(),
+            kind: error.kind,
+            retryable: error.retryable,
+            provider: error.provider.clone(),
+        }
+    }
+}
+
+/// A non-fatal problem with the request, e.g. images sent to a text-only model
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatWarning {
+    pub conversation_id: String,
+    pub warning: String,
+    pub ocr_applied: bool,
+}
+app_event!(ChatWarning, "chat-warning");
+
+/// The history that went into a request, for debugging context selection
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextBuilt {
+    pub conversation_id: String,
+    pub history_mode: HistoryMode,
+    /// Token budget for the whole request in `token_budget` mode
+    pub budget_tokens: Option<usize>,
+    /// Tokens in the request: system prompt, history and the current message
+    pub total_tokens: usize,
+    /// History messages sent, oldest first
+    pub included_message_ids: Vec<String>,
+    /// History messages left out by the history mode or the budget
+    pub omitted_message_count: usize,
+}
+app_event!(ContextBuilt, "context-built");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct McpAuthRequired {
+    pub conversation_id: String,
+    pub server_id: String,
+}
+app_event!(McpAuthRequired, "mcp-auth-required");
+
+// ---------------------------------------------------------------------------
+// Attachments and processing steps
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentProcessingStarted {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub urls: Vec<String>,
+}
+app_event!(AttachmentProcessingStarted, "attachment-processing-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentProcessingComplete {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub attachment_ids: Vec<String>,
+}
+app_event!(
+    AttachmentProcessingComplete,
+    "attachment-processing-complete"
+);
+
+/// An attachment or context enrichment was added to (or updated on) a message
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentUpdate {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub attachment_id: String,
+    /// The URL whose fetch produced this attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_url: Option<String>,
+    /// Inline details so the UI can render a search before it is reloaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<SearchResultAttachment>,
+}
+app_event!(AttachmentUpdate, "attachment-update");
+
+impl AttachmentUpdate {
+    pub fn new(
+        message_id: impl Into<String>,
+        conversation_id: impl Into<String>,
+        attachment_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            message_id: message_id.into(),
+            conversation_id: conversation_id.into(),
+            attachment_id: attachment_id.into(),
+            completed_url: None,
+            attachment: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResultAttachment {
+    /// Always `"search_result"`
+    #[serde(rename = "type")]
+    pub attachment_type: &'static str,
+    pub id: String,
+    pub query: String,
+    pub engine: String,
+    pub total_results: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub searched_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchDecisionStarted {
+    pub message_id: String,
+    pub conversation_id: String,
+}
+app_event!(SearchDecisionStarted, "search-decision-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchDecisionComplete {
+    pub message_id: String,
+    pub conversation_id: String,
+}
+app_event!(SearchDecisionComplete, "search-decision-complete");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchCompleted {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub search_result_id: Option<String>,
+    pub query: String,
+    pub results_count: usize,
+}
+app_event!(SearchCompleted, "search-completed");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionStarted {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub file_id: String,
+}
+app_event!(TranscriptionStarted, "transcription-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionComplete {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub transcription_id: String,
+    pub file_id: String,
+    /// `"success"` or `"error"`
+    pub status: String,
+    pub error: Option<String>,
+}
+app_event!(TranscriptionComplete, "transcription-complete");
+
+/// A reply was checked against its sources; the result is a `verification` process step
+#[derive(Debug, Clone, Serialize)]
+pub struct AnswerVerified {
+    pub conversation_id: String,
+    pub message_id: String,
+    pub verification_id: String,
+    pub verdict: String,
+}
+app_event!(AnswerVerified, "answer-verified");
+
+// ---------------------------------------------------------------------------
+// Roundtable
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundtableTurnStarted {
+    pub conversation_id: String,
+    pub round: u32,
+    pub turn: usize,
+    pub participant_type: String,
+    pub participant_id: String,
+    pub display_name: String,
+}
+app_event!(RoundtableTurnStarted, "roundtable-turn-started");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundtableParticipantSkipped {
+    pub conversation_id: String,
+    pub participant_type: String,
+    pub participant_id: String,
+    pub error: String,
+}
+app_event!(
+    RoundtableParticipantSkipped,
+    "roundtable-participant-skipped"
+);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundtableComplete {
+    pub conversation_id: String,
+    pub turns_completed: u32,
+    pub cancelled: bool,
+}
+app_event!(RoundtableComplete, "roundtable-complete");
+
+// ---------------------------------------------------------------------------
+// Conversations and app-level navigation
+// ---------------------------------------------------------------------------
+
+/// Conversation fields changed in the background; only changed fields are set
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConversationUpdated {
+    pub conversation_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+app_event!(ConversationUpdated, "conversation-updated");
+
+/// A send brought the conversation close to its token or cost budget
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationBudgetWarning {
+    pub conversation_id: String,
+    pub status: BudgetStatus,
+}
+app_event!(ConversationBudgetWarning, "conversation-budget-warning");
+
+/// Estimated spend for the month went over the soft cap
+#[derive(Debug, Clone, Serialize)]
+pub struct SpendCapReached {
+    /// `YYYY-MM`
+    pub month: String,
+    pub spent: f64,
+    pub cap: f64,
+}
+app_event!(SpendCapReached, "spend-cap-reached");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NavigateConversation {
+    pub conversation_id: String,
+}
+app_event!(NavigateConversation, "navigate-conversation");
+
+/// Open a new chat, optionally prefilled
+#[derive(Debug, Clone, Serialize)]
+pub struct NavigateNewChat {
+    pub prompt: Option<String>,
+    pub assistant_id: Option<String>,
+}
+app_event!(NavigateNewChat, "navigate-new-chat");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrayNewChat {}
+app_event!(TrayNewChat, "tray-new-chat");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaPullProgress {
+    pub model: String,
+    pub status: String,
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
+    pub percent: Option<u64>,
+}
+app_event!(OllamaPullProgress, "ollama-pull-progress");
+
+// ---------------------------------------------------------------------------
+// Connectivity and outbox
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityChanged {
+    pub online: bool,
+}
+app_event!(ConnectivityChanged, "connectivity-changed");
+
+/// The outbox changed; carries every message still waiting for the network
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxUpdated {
+    pub entries: Vec<OutboxEntry>,
+}
+app_event!(OutboxUpdated, "outbox-updated");
+
+// ---------------------------------------------------------------------------
+// Updates
+// ---------------------------------------------------------------------------
+
+/// `percent` is missing when the server doesn't report the download size
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateDownloadProgress {
+    pub version: String,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    pub percent: Option<u64>,
+    pub finished: bool,
+}
+app_event!(UpdateDownloadProgress, "update-download-progress");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_is_flat_and_versioned() {
+        let envelope = Envelope::new(ChatStream {
+            conversation_id: "c1".to_string(),
+            content: "Hel".to_string(),
+        });
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            serde_json::json!({
+                "schema_version": 1,
+                "conversation_id": "c1",
+                "content": "Hel",
+            })
+        );
+    }
+
+    #[test]
+    fn test_stream_event_is_tagged_with_event_name() {
+        let event = StreamEvent::ToolCallStarted(ToolCallStarted {
+            conversation_id: "c1".to_string(),
+            tool_call_id: "t1".to_string(),
+            tool_name: "web_search".to_string(),
+            tool_input: "{}".to_string(),
+        });
+        let value = serde_json::to_value(Envelope::new(event)).unwrap();
+        assert_eq!(value["event"], "tool-call-started");
+        assert_eq!(value["schema_version"], 1);
+        assert_eq!(value["tool_call_id"], "t1");
+    }
+
+    #[test]
+    fn test_chat_error_carries_error_fields() {
+        let error = AppError::classify("[HTTP 401] Unauthorized").with_provider_type("openai");
+        let value = serde_json::to_value(ChatError::new("c1", &error)).unwrap();
+        assert_eq!(value["error"], "[HTTP 401] Unauthorized");
+        assert_eq!(value["kind"], "auth");
+        assert_eq!(value["retryable"], false);
+        assert_eq!(value["provider"]["status"], 401);
+        assert_eq!(value["provider"]["provider_type"], "openai");
+    }
+}
@@ -0,0 +1,181 @@
+//! Standalone HTML export
+//!
+//! Message text is rendered from Markdown. Raw HTML in messages is escaped rather than
+//! passed through, so an exported page can't run scripts from model output.
+
+use super::{ExportedConversation, ExportedMessage, ExportedPart, ExportedRole, ExportedSource};
+use pulldown_cmark::{Event, Options, Parser};
+use std::fmt::Write;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:820px;margin:2rem auto;\
+padding:0 1rem;line-height:1.6;color:#1f2328}\
+.message{border-top:1px solid #d0d7de;padding:1rem 0}\
+.sender{font-weight:600;margin-bottom:.5rem}\
+.user .sender{color:#0969da}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;border-radius:6px}\
+details,.step{color:#57606a;margin:.5rem 0}\
+.sources{font-size:.9em}";
+
+pub fn render(conversation: &ExportedConversation) -> String {
+    render_document(conversation, &format!("<style>{}</style>\n", STYLE))
+}
+
+/// The full page, with `head` (already escaped) added to the `<head>` element
+pub(super) fn render_document(conversation: &ExportedConversation, head: &str) -> String {
+    let mut out = String::new();
+    let title = escape(&conversation.title);
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         {}</head>\n<body>\n<h1>{}</h1>\n<p><small>Exported {}</small></p>\n",
+        title,
+        head,
+        title,
+        escape(&conversation.exported_at)
+    );
+    for message in &conversation.messages {
+        render_message(&mut out, message);
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_message(out: &mut String, message: &ExportedMessage) {
+    let (class, sender) = match (message.role, &message.sender_name) {
+        (ExportedRole::User, _) => ("user", "User"),
+        (ExportedRole::Assistant, Some(name)) => ("assistant", name.as_str()),
+        (ExportedRole::Assistant, None) => ("assistant", "Assistant"),
+    };
+    let _ = writeln!(
+        out,
+        "<section class=\"message {}\">\n<div class=\"sender\">{}</div>",
+        class,
+        escape(sender)
+    );
+
+    for part in &message.parts {
+        match part {
+            ExportedPart::Text { content } => out.push_str(&markdown_to_html(content)),
+            ExportedPart::Thinking { content } => {
+                let _ = writeln!(
+                    out,
+                    "<details><summary>Thinking</summary>{}</details>",
+                    markdown_to_html(content)
+                );
+            }
+            ExportedPart::ToolCall {
+                name,
+                input,
+                output,
+                status,
+                error,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "<details class=\"step\"><summary>Tool call: <code>{}</code> ({})</summary>",
+                    escape(name),
+                    escape(status)
+                );
+                if let Some(input) = input {
+                    let _ = writeln!(out, "<pre>{}</pre>", escape(input));
+                }
+                if let Some(output) = output {
+                    let _ = writeln!(out, "<p>Output:</p><pre>{}</pre>", escape(output));
+                }
+                if let Some(error) = error {
+                    let _ = writeln!(out, "<p>Error: {}</p>", escape(error));
+                }
+                out.push_str("</details>\n");
+            }
+            ExportedPart::SearchDecision {
+                reasoning,
+                search_needed,
+                query,
+            } => {
+                let decision = match (search_needed, query) {
+                    (true, Some(query)) => format!("Searched for \u{201c}{}\u{201d}", query),
+                    (true, None) => "Searched the web".to_string(),
+                    (false, _) => "No search needed".to_string(),
+                };
+                let _ = writeln!(
+                    out,
+                    "<p class=\"step\"><strong>{}.</strong> {}</p>",
+                    escape(&decision),
+                    escape(reasoning.trim())
+                );
+            }
+            ExportedPart::Transcript { content } => {
+                let _ = writeln!(
+                    out,
+                    "<blockquote><em>Transcript:</em> {}</blockquote>",
+                    escape(content.trim())
+                );
+            }
+        }
+    }
+
+    if !message.sources.is_empty() {
+        out.push_str("<div class=\"sources\"><strong>Sources</strong>\n<ul>\n");
+        for source in &message.sources {
+            match source {
+                ExportedSource::WebSearch { query, engine } => {
+                    let _ = writeln!(
+                        out,
+                        "<li>Web search ({}): {}</li>",
+                        escape(engine),
+                        escape(query)
+                    );
+                }
+                ExportedSource::Page { url, title } => {
+                    let _ = writeln!(
+                        out,
+                        "<li><a href=\"{}\">{}</a></li>",
+                        escape(url),
+                        escape(title.as_deref().unwrap_or(url))
+                    );
+                }
+            }
+        }
+        out.push_str("</ul></div>\n");
+    }
+    out.push_str("</section>\n");
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let parser = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        other => other,
+    });
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+pub(super) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html_escapes_raw_html() {
+        let html = markdown_to_html("**hi** <script>alert(1)</script>");
+        assert!(html.contains("<strong>hi</strong>"));
+        assert!(!html.contains("<script>"));
+    }
+}
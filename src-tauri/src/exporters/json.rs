@@ -0,0 +1,8 @@
+//! JSON export: the collected conversation as-is
+
+use super::ExportedConversation;
+use anyhow::Result;
+
+pub fn render(conversation: &ExportedConversation) -> Result<String> {
+    Ok(serde_json::to_string_pretty(conversation)?)
+}
@@ -0,0 +1,110 @@
+//! Markdown export
+//!
+//! Thinking goes into collapsed `<details>` blocks, which most Markdown viewers render.
+
+use super::{ExportedConversation, ExportedMessage, ExportedPart, ExportedRole, ExportedSource};
+use std::fmt::Write;
+
+pub fn render(conversation: &ExportedConversation) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", conversation.title);
+    let _ = writeln!(out, "_Exported {}_\n", conversation.exported_at);
+
+    for message in &conversation.messages {
+        render_message(&mut out, message);
+    }
+    out
+}
+
+fn render_message(out: &mut String, message: &ExportedMessage) {
+    let sender = match (message.role, &message.sender_name) {
+        (ExportedRole::User, _) => "User",
+        (ExportedRole::Assistant, Some(name)) => name.as_str(),
+        (ExportedRole::Assistant, None) => "Assistant",
+    };
+    let _ = writeln!(out, "---\n\n## {}\n", sender);
+
+    for part in &message.parts {
+        match part {
+            ExportedPart::Text { content } => {
+                let _ = writeln!(out, "{}\n", content.trim_end());
+            }
+            ExportedPart::Thinking { content } => {
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>Thinking</summary>\n\n{}\n\n</details>\n",
+                    content.trim_end()
+                );
+            }
+            ExportedPart::ToolCall {
+                name,
+                input,
+                output,
+                status,
+                error,
+            } => {
+                let _ = writeln!(out, "**Tool call: `{}`** ({})\n", name, status);
+                if let Some(input) = input {
+                    let _ = writeln!(out, "{}\n", fenced(input));
+                }
+                if let Some(output) = output {
+                    let _ = writeln!(out, "Output:\n\n{}\n", fenced(output));
+                }
+                if let Some(error) = error {
+                    let _ = writeln!(out, "Error: {}\n", error);
+                }
+            }
+            ExportedPart::SearchDecision {
+                reasoning,
+                search_needed,
+                query,
+            } => {
+                let decision = match (search_needed, query) {
+                    (true, Some(query)) => format!("Searched for \"{}\"", query),
+                    (true, None) => "Searched the web".to_string(),
+                    (false, _) => "No search needed".to_string(),
+                };
+                let _ = writeln!(out, "> **{}.** {}\n", decision, reasoning.trim());
+            }
+            ExportedPart::Transcript { content } => {
+                let _ = writeln!(out, "> _Transcript:_ {}\n", content.trim());
+            }
+        }
+    }
+
+    if !message.sources.is_empty() {
+        let _ = writeln!(out, "**Sources**\n");
+        for source in &message.sources {
+            match source {
+                ExportedSource::WebSearch { query, engine } => {
+                    let _ = writeln!(out, "- Web search ({}): {}", engine, query);
+                }
+                ExportedSource::Page { url, title } => {
+                    let _ = writeln!(out, "- [{}]({})", title.as_deref().unwrap_or(url), url);
+                }
+            }
+        }
+        out.push('\n');
+    }
+}
+
+/// Wrap in a code fence longer than any backtick run inside the text
+pub(crate) fn fenced(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}\n{}\n{}", fence, text.trim_end(), fence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fenced_outgrows_inner_backticks() {
+        assert_eq!(fenced("ls"), "```\nls\n```");
+        assert_eq!(
+            fenced("```rust\nfn main() {}\n```"),
+            "````\n```rust\nfn main() {}\n```\n````"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Conversation exporters
+//!
+//! [`collect`] loads a conversation into an [`ExportedConversation`] ([`collect_selected`]
+//! loads only some of its messages), keeping only the process steps and sources selected
+//! in [`ExportOptions`]. The format modules render
+//! that neutral shape, so every format honors the same toggles.
+//!
+//! Supported formats: Markdown, standalone HTML and JSON. [`site`] publishes the HTML
+//! rendering as a small static site instead of a single file.
+
+mod html;
+mod json;
+mod markdown;
+pub mod site;
+
+pub(crate) use markdown::fenced;
+
+use crate::db::Database;
+use crate::models::{ContextEnrichment, Message, ProcessStep};
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// What to include besides the message text. Everything is included by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub include_thinking: bool,
+    /// Tool calls and code executions
+    pub include_tool_calls: bool,
+    pub include_search_decisions: bool,
+    /// Fetched web pages and web search queries
+    pub include_sources: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            include_thinking: true,
+            include_tool_calls: true,
+            include_search_decisions: true,
+            include_sources: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportedRole {
+    User,
+    Assistant,
+}
+
+/// A piece of a message body, in display order
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportedPart {
+    Text {
+        content: String,
+    },
+    Thinking {
+        content: String,
+    },
+    ToolCall {
+        name: String,
+        input: Option<String>,
+        output: Option<String>,
+        status: String,
+        error: Option<String>,
+    },
+    SearchDecision {
+        reasoning: String,
+        search_needed: bool,
+        query: Option<String>,
+    },
+    /// Transcript of an audio attachment
+    Transcript {
+        content: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportedSource {
+    WebSearch { query: String, engine: String },
+    Page { url: String, title: Option<String> },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedMessage {
+    pub id: String,
+    pub role: ExportedRole,
+    /// Model or assistant name for replies
+    pub sender_name: Option<String>,
+    pub created_at: String,
+    pub parts: Vec<ExportedPart>,
+    pub sources: Vec<ExportedSource>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedConversation {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub exported_at: String,
+    pub messages: Vec<ExportedMessage>,
+}
+
+/// Render a collected conversation
+pub fn render(conversation: &ExportedConversation, format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Markdown => Ok(markdown::render(conversation)),
+        ExportFormat::Html => Ok(html::render(conversation)),
+        ExportFormat::Json => json::render(conversation),
+    }
+}
+
+/// Load a conversation with the resources selected in `options`
+pub async fn collect(
+    db: &Database,
+    conversation_id: &str,
+    options: ExportOptions,
+) -> Result<ExportedConversation> {
+    let messages = db.list_messages_by_conversation(conversation_id).await?;
+    collect_messages(db, conversation_id, messages, options).await
+}
+
+/// Load a subset of a conversation's messages, in conversation order. All messages
+/// must belong to the same conversation.
+pub async fn collect_selected(
+    db: &Database,
+    message_ids: &[String],
+    options: ExportOptions,
+) -> Result<ExportedConversation> {
+    let mut messages: Vec<Message> = Vec::with_capacity(message_ids.len());
+    for id in message_ids {
+        if messages.iter().any(|m| &m.id == id) {
+            continue;
+        }
+        let message = db
+            .get_message(id)
+            .await?
+            .ok_or_else(|| anyhow!("Message not found: {}", id))?;
+        messages.push(message);
+    }
+
+    let Some(conversation_id) = messages.first().and_then(|m| m.conversation_id.clone()) else {
+        bail!("No messages selected");
+    };
+    if messages
+        .iter()
+        .any(|m| m.conversation_id.as_deref() != Some(conversation_id.as_str()))
+    {
+        bail!("Selected messages must belong to the same conversation");
+    }
+    messages.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    collect_messages(db, &conversation_id, messages, options).await
+}
+
+async fn collect_messages(
+    db: &Database,
+    conversation_id: &str,
+    messages: Vec<Message>,
+    options: ExportOptions,
+) -> Result<ExportedConversation> {
+    let conversation = db
+        .get_conversation(conversation_id)
+        .await?
+        .ok_or_else(|| anyhow!("Conversation not found: {}", conversation_id))?;
+
+    let mut sender_names = SenderNames::default();
+    let mut exported = Vec::with_capacity(messages.len());
+    for message in messages {
+        exported.push(collect_message(db, message, options, &mut sender_names).await?);
+    }
+
+    Ok(ExportedConversation {
+        id: conversation.id,
+        title: conversation.title,
+        created_at: conversation.created_at,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        messages: exported,
+    })
+}
+
+async fn collect_message(
+    db: &Database,
+    message: Message,
+    options: ExportOptions,
+    sender_names: &mut SenderNames,
+) -> Result<ExportedMessage> {
+    let resources = db.get_message_resources(&message.id).await?;
+    let role = if message.sender_type == "user" {
+        ExportedRole::User
+    } else {
+        ExportedRole::Assistant
+    };
+    let sender_name = match role {
+        ExportedRole::User => None,
+        ExportedRole::Assistant => sender_names.get(db, &message).await,
+    };
+
+    let sources = if options.include_sources {
+        resources.contexts.iter().filter_map(source_of).collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(ExportedMessage {
+        parts: message_parts(&message.content, &resources.steps, options),
+        id: message.id,
+        role,
+        sender_name,
+        created_at: message.created_at,
+        sources,
+    })
+}
+
+/// Interleave steps and text. Messages with content blocks are rendered block by block
+/// in step order; otherwise selected steps come before the full message text.
+fn message_parts(
+    content: &str,
+    steps: &[ProcessStep],
+    options: ExportOptions,
+) -> Vec<ExportedPart> {
+    let has_content_blocks = steps
+        .iter()
+        .any(|s| matches!(s, ProcessStep::ContentBlock(_)));
+
+    let mut parts: Vec<ExportedPart> = steps
+        .iter()
+        .filter_map(|step| match step {
+            ProcessStep::ContentBlock(b) if has_content_blocks => Some(ExportedPart::Text {
+                content: b.content.clone(),
+            }),
+            ProcessStep::Thinking(t) if options.include_thinking => Some(ExportedPart::Thinking {
+                content: t.content.clone(),
+            }),
+            ProcessStep::ToolCall(t) if options.include_tool_calls => {
+                Some(ExportedPart::ToolCall {
+                    name: t.tool_name.clone(),
+                    input: t.tool_input.clone(),
+                    output: t.tool_output.clone(),
+                    status: t.status.clone(),
+                    error: t.error.clone(),
+                })
+            }
+            ProcessStep::CodeExecution(c) if options.include_tool_calls => {
+                Some(ExportedPart::ToolCall {
+                    name: format!("code_execution ({})", c.language),
+                    input: Some(c.code.clone()),
+                    output: c.output.clone(),
+                    status: c.status.clone(),
+                    error: c.error.clone(),
+                })
+            }
+            ProcessStep::SearchDecision(d) if options.include_search_decisions => {
+                Some(ExportedPart::SearchDecision {
+                    reasoning: d.reasoning.clone(),
+                    search_needed: d.search_needed,
+                    query: d.search_query.clone(),
+                })
+            }
+            ProcessStep::Transcription(t) if t.status == "success" => {
+                Some(ExportedPart::Transcript {
+                    content: t.content.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    if !has_content_blocks && !content.trim().is_empty() {
+        parts.push(ExportedPart::Text {
+            content: content.to_string(),
+        });
+    }
+    parts
+}
+
+fn source_of(context: &ContextEnrichment) -> Option<ExportedSource> {
+    match context {
+        ContextEnrichment::SearchResult(s) => Some(ExportedSource::WebSearch {
+            query: s.query.clone(),
+            engine: s.engine.clone(),
+        }),
+        ContextEnrichment::FetchResult(f) if f.status != "failed" => Some(ExportedSource::Page {
+            url: f.url.clone(),
+            title: f.title.clone(),
+        }),
+        ContextEnrichment::FetchResult(_) => None,
+    }
+}
+
+/// Model and assistant names, looked up once per sender
+#[derive(Default)]
+struct SenderNames {
+    cache: HashMap<String, Option<String>>,
+}
+
+impl SenderNames {
+    async fn get(&mut self, db: &Database, message: &Message) -> Option<String> {
+        let sender_id = message.sender_id.as_deref()?;
+        if let Some(name) = self.cache.get(sender_id) {
+            return name.clone();
+        }
+        let name = match message.sender_type.as_str() {
+            "model" => db.get_model(sender_id).await.ok().flatten().map(|m| m.name),
+            "assistant" => db
+                .get_assistant(sender_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|a| a.name),
+            _ => None,
+        };
+        self.cache.insert(sender_id.to_string(), name.clone());
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ContentBlock, ThinkingStep, ToolCall};
+
+    fn thinking(order: i32) -> ProcessStep {
+        ProcessStep::Thinking(ThinkingStep {
+            id: "t".to_string(),
+            message_id: "m".to_string(),
+            content: "pondering".to_string(),
+            source: "llm".to_string(),
+            display_order: order,
+            created_at: String::new(),
+        })
+    }
+
+    fn tool_call(order: i32) -> ProcessStep {
+        ProcessStep::ToolCall(ToolCall {
+            id: "c".to_string(),
+            message_id: "m".to_string(),
+            tool_name: "web_search".to_string(),
+            tool_input: None,
+            tool_output: None,
+            status: "success".to_string(),
+            error: None,
+            duration_ms: None,
+            display_order: order,
+            created_at: String::new(),
+            completed_at: None,
+        })
+    }
+
+    fn block(order: i32, content: &str) -> ProcessStep {
+        ProcessStep::ContentBlock(ContentBlock {
+            id: "b".to_string(),
+            message_id: "m".to_string(),
+            content: content.to_string(),
+            display_order: order,
+            created_at: String::new(),
+        })
+    }
+
+    #[test]
+    fn test_message_parts_honors_toggles() {
+        let steps = vec![thinking(0), tool_call(1)];
+        let options = ExportOptions {
+            include_thinking: false,
+            ..ExportOptions::default()
+        };
+        let parts = message_parts("Answer", &steps, options);
+        assert_eq!(parts.len(), 2);
+        assert!(matches!(parts[0], ExportedPart::ToolCall { .. }));
+        assert!(matches!(&parts[1], ExportedPart::Text { content } if content == "Answer"));
+    }
+
+    #[test]
+    fn test_message_parts_interleaves_content_blocks() {
+        let steps = vec![block(0, "First"), tool_call(1), block(2, "Second")];
+        let options = ExportOptions {
+            include_tool_calls: false,
+            ..ExportOptions::default()
+        };
+        let parts = message_parts("FirstSecond", &steps, options);
+        assert_eq!(parts.len(), 2);
+        assert!(matches!(&parts[0], ExportedPart::Text { content } if content == "First"));
+        assert!(matches!(&parts[1], ExportedPart::Text { content } if content == "Second"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Static site export ("publish")
+//!
+//! Renders a conversation as a directory that can be dropped into GitHub Pages or any
+//! static host: `index.html`, its stylesheet under `assets/`, and an empty `.nojekyll`
+//! so Pages serves the files as they are. The page body is the one of the HTML export;
+//! only the head differs (external stylesheet, viewport and link preview tags).
+
+use super::html::{escape, render_document};
+use super::{ExportedConversation, ExportedPart};
+
+const STYLESHEET: &str = ":root{color-scheme:light dark;--fg:#1f2328;--muted:#57606a;\
+--border:#d0d7de;--code:#f6f8fa;--accent:#0969da}\n\
+@media (prefers-color-scheme:dark){:root{--fg:#e6edf3;--muted:#8d96a0;--border:#30363d;\
+--code:#161b22;--accent:#4493f8}}\n\
+body{font-family:system-ui,sans-serif;max-width:820px;margin:2rem auto;padding:0 1rem;\
+line-height:1.6;color:var(--fg)}\n\
+a{color:var(--accent)}\n\
+.message{border-top:1px solid var(--border);padding:1rem 0}\n\
+.sender{font-weight:600;margin-bottom:.5rem}\n\
+.user .sender{color:var(--accent)}\n\
+pre{background:var(--code);padding:.75rem;overflow-x:auto;border-radius:6px}\n\
+code{font-size:.9em}\n\
+img{max-width:100%}\n\
+table{border-collapse:collapse}td,th{border:1px solid var(--border);padding:.25rem .5rem}\n\
+details,.step{color:var(--muted);margin:.5rem 0}\n\
+.sources{font-size:.9em}\n";
+
+/// Link previews show the start of the conversation
+const DESCRIPTION_CHARS: usize = 200;
+
+/// A file of the site, relative to its root directory
+#[derive(Debug)]
+pub struct SiteFile {
+    pub path: &'static str,
+    pub content: String,
+}
+
+pub fn render(conversation: &ExportedConversation) -> Vec<SiteFile> {
+    let title = escape(&conversation.title);
+    let mut head = format!(
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <meta property=\"og:type\" content=\"article\">\n\
+         <meta property=\"og:title\" content=\"{}\">\n",
+        title
+    );
+    if let Some(description) = description(conversation) {
+        let description = escape(&description);
+        head.push_str(&format!(
+            "<meta name=\"description\" content=\"{0}\">\n\
+             <meta property=\"og:description\" content=\"{0}\">\n",
+            description
+        ));
+    }
+    head.push_str("<link rel=\"stylesheet\" href=\"assets/style.css\">\n");
+
+    vec![
+        SiteFile {
+            path: "index.html",
+            content: render_document(conversation, &head),
+        },
+        SiteFile {
+            path: "assets/style.css",
+            content: STYLESHEET.to_string(),
+        },
+        SiteFile {
+            path: ".nojekyll",
+            content: String::new(),
+        },
+    ]
+}
+
+/// The first message text on one line, shortened for link previews
+fn description(conversation: &ExportedConversation) -> Option<String> {
+    let text = conversation
+        .messages
+        .iter()
+        .flat_map(|message| &message.parts)
+        .find_map(|part| match part {
+            ExportedPart::Text { content } if !content.trim().is_empty() => Some(content),
+            _ => None,
+        })?;
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > DESCRIPTION_CHARS {
+        let short: String = line.chars().take(DESCRIPTION_CHARS - 1).collect();
+        Some(format!("{}…", short.trim_end()))
+    } else {
+        Some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::{ExportedMessage, ExportedRole};
+
+    #[test]
+    fn test_render_site() {
+        let conversation = ExportedConversation {
+            id: "c".to_string(),
+            title: "Rust \"lifetimes\"".to_string(),
+            created_at: String::new(),
+            exported_at: "2026-10-16T12:00:00Z".to_string(),
+            messages: vec![ExportedMessage {
+                id: "m".to_string(),
+                role: ExportedRole::User,
+                sender_name: None,
+                created_at: String::new(),
+                parts: vec![ExportedPart::Text {
+                    content: "Why does\nthis <borrow> fail?".to_string(),
+                }],
+                sources: vec![],
+            }],
+        };
+        let files = render(&conversation);
+        let paths: Vec<&str> = files.iter().map(|file| file.path).collect();
+        assert_eq!(paths, ["index.html", "assets/style.css", ".nojekyll"]);
+
+        let index = &files[0].content;
+        assert!(index.contains("<link rel=\"stylesheet\" href=\"assets/style.css\">"));
+        assert!(index.contains("content=\"Rust &quot;lifetimes&quot;\""));
+        assert!(index.contains("content=\"Why does this &lt;borrow&gt; fail?\""));
+        assert!(!index.contains("<style>"));
+    }
+}
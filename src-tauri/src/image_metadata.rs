@@ -0,0 +1,246 @@
+//! Image metadata scrubbing
+//!
+//! Removes EXIF (including GPS), XMP, IPTC and text metadata from uploaded images by
+//! rewriting the container without the metadata blocks, so pixels are not re-encoded.
+//! The one exception is a JPEG whose EXIF orientation rotates the image: dropping the
+//! tag would show it sideways, so the rotation is applied and the image re-encoded.
+
+use image::metadata::Orientation;
+
+const JPEG_REENCODE_QUALITY: u8 = 92;
+
+/// Strip metadata from an image. Returns `None` when nothing was removed or the
+/// format isn't handled (the original bytes should be used as-is).
+pub fn strip_metadata(bytes: &[u8], media_type: &str) -> Option<Vec<u8>> {
+    match media_type {
+        "image/jpeg" | "image/jpg" => strip_jpeg(bytes),
+        "image/png" => strip_png(bytes),
+        "image/webp" => strip_webp(bytes),
+        _ => None,
+    }
+}
+
+/// JPEG: drop APP1 (EXIF/XMP), APP13 (IPTC/Photoshop) and COM segments
+fn strip_jpeg(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() < 4 || bytes[0..2] != [0xFF, 0xD8] {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&bytes[0..2]);
+    let mut pos = 2;
+    let mut removed = false;
+    let mut orientation = None;
+
+    while pos + 4 <= bytes.len() {
+        if bytes[pos] != 0xFF {
+            return None;
+        }
+        let marker = bytes[pos + 1];
+        // Start of scan: the rest is entropy-coded image data
+        if marker == 0xDA {
+            break;
+        }
+        let length = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > bytes.len() {
+            return None;
+        }
+
+        let payload = &bytes[pos + 4..end];
+        if marker == 0xE1 || marker == 0xED || marker == 0xFE {
+            if marker == 0xE1
+                && let Some(exif) = payload.strip_prefix(b"Exif\0\0")
+            {
+                orientation = Orientation::from_exif_chunk(exif);
+            }
+            removed = true;
+        } else {
+            output.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+    }
+
+    if !removed {
+        return None;
+    }
+
+    match orientation {
+        Some(o) if o != Orientation::NoTransforms => reencode_oriented_jpeg(bytes, o),
+        _ => {
+            output.extend_from_slice(&bytes[pos..]);
+            Some(output)
+        }
+    }
+}
+
+/// Apply the EXIF rotation to the pixels and re-encode without metadata
+fn reencode_oriented_jpeg(bytes: &[u8], orientation: Orientation) -> Option<Vec<u8>> {
+    let mut img = image::load_from_memory(bytes).ok()?;
+    img.apply_orientation(orientation);
+
+    let mut output = std::io::Cursor::new(Vec::new());
+    let encoder =
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut output, JPEG_REENCODE_QUALITY);
+    img.to_rgb8().write_with_encoder(encoder).ok()?;
+    Some(output.into_inner())
+}
+
+/// PNG: drop eXIf, text (tEXt/iTXt/zTXt) and tIME chunks
+fn strip_png(bytes: &[u8]) -> Option<Vec<u8>> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if !bytes.starts_with(SIGNATURE) {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(SIGNATURE);
+    let mut pos = SIGNATURE.len();
+    let mut removed = false;
+
+    while pos + 12 <= bytes.len() {
+        let length = u32::from_be_bytes(bytes[pos..pos + 4].try_into().ok()?) as usize;
+        let end = pos.checked_add(12 + length)?;
+        if end > bytes.len() {
+            return None;
+        }
+
+        let chunk_type = &bytes[pos + 4..pos + 8];
+        if matches!(chunk_type, b"eXIf" | b"tEXt" | b"iTXt" | b"zTXt" | b"tIME") {
+            removed = true;
+        } else {
+            output.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+    }
+
+    removed.then_some(output)
+}
+
+/// WebP: drop EXIF and XMP chunks and clear their flags in the VP8X header
+fn strip_webp(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&bytes[0..12]);
+    let mut pos = 12;
+    let mut removed = false;
+
+    while pos + 8 <= bytes.len() {
+        let size = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().ok()?) as usize;
+        // Chunks are padded to an even size
+        let end = pos.checked_add(8 + size + (size & 1))?.min(bytes.len());
+        if pos + 8 + size > bytes.len() {
+            return None;
+        }
+
+        match &bytes[pos..pos + 4] {
+            b"EXIF" | b"XMP " => removed = true,
+            b"VP8X" if size >= 1 => {
+                let start = output.len();
+                output.extend_from_slice(&bytes[pos..end]);
+                // Flags byte: bit 3 = EXIF, bit 2 = XMP
+                output[start + 8] &= !0b0000_1100;
+            }
+            _ => output.extend_from_slice(&bytes[pos..end]),
+        }
+        pos = end;
+    }
+
+    if !removed {
+        return None;
+    }
+
+    let riff_size = (output.len() - 8) as u32;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(format: image::ImageFormat, width: u32, height: u32) -> Vec<u8> {
+        let img = image::RgbImage::from_pixel(width, height, image::Rgb([200, 100, 50]));
+        let mut out = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(&mut out, format)
+            .unwrap();
+        out.into_inner()
+    }
+
+    /// Minimal big-endian TIFF block with a single orientation entry
+    fn exif_with_orientation(orientation: u16) -> Vec<u8> {
+        let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+        exif.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0, 0, 0, 1]);
+        exif.extend_from_slice(&orientation.to_be_bytes());
+        exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        exif
+    }
+
+    fn insert_jpeg_segment(jpeg: &[u8], marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut out = jpeg[0..2].to_vec();
+        out.extend_from_slice(&[0xFF, marker]);
+        out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        out.extend_from_slice(payload);
+        out.extend_from_slice(&jpeg[2..]);
+        out
+    }
+
+    #[test]
+    fn test_strip_jpeg_removes_exif_losslessly() {
+        let jpeg = encode(image::ImageFormat::Jpeg, 8, 4);
+        let tagged = insert_jpeg_segment(&jpeg, 0xE1, &exif_with_orientation(1));
+
+        let stripped = strip_metadata(&tagged, "image/jpeg").unwrap();
+        assert_eq!(stripped, jpeg);
+        assert!(strip_metadata(&jpeg, "image/jpeg").is_none());
+    }
+
+    #[test]
+    fn test_strip_jpeg_applies_rotation() {
+        let jpeg = encode(image::ImageFormat::Jpeg, 8, 4);
+        // 6 = rotate 90° clockwise
+        let tagged = insert_jpeg_segment(&jpeg, 0xE1, &exif_with_orientation(6));
+
+        let stripped = strip_metadata(&tagged, "image/jpeg").unwrap();
+        assert!(!stripped.windows(6).any(|w| w == b"Exif\0\0"));
+        let decoded = image::load_from_memory(&stripped).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (4, 8));
+    }
+
+    #[test]
+    fn test_strip_png_removes_metadata_chunks() {
+        let png = encode(image::ImageFormat::Png, 4, 4);
+        // Insert a tEXt chunk right after IHDR (8-byte signature + 25-byte IHDR)
+        let mut chunk = 7u32.to_be_bytes().to_vec();
+        chunk.extend_from_slice(b"tEXtGPS\0abc");
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        let mut tagged = png[..33].to_vec();
+        tagged.extend_from_slice(&chunk);
+        tagged.extend_from_slice(&png[33..]);
+
+        assert_eq!(strip_metadata(&tagged, "image/png").unwrap(), png);
+        assert!(strip_metadata(&png, "image/png").is_none());
+    }
+
+    #[test]
+    fn test_strip_webp_removes_exif_chunk() {
+        let mut webp = b"RIFF\0\0\0\0WEBP".to_vec();
+        webp.extend_from_slice(b"VP8X\x0a\0\0\0\x08\0\0\0\0\0\0\0\0\0");
+        webp.extend_from_slice(b"VP8L\x02\0\0\0ab");
+        webp.extend_from_slice(b"EXIF\x03\0\0\0xyz\0");
+        let size = (webp.len() - 8) as u32;
+        webp[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let stripped = strip_metadata(&webp, "image/webp").unwrap();
+        assert!(!stripped.windows(4).any(|w| w == b"EXIF"));
+        assert_eq!(stripped[20], 0);
+        assert_eq!(
+            u32::from_le_bytes(stripped[4..8].try_into().unwrap()) as usize,
+            stripped.len() - 8
+        );
+    }
+}
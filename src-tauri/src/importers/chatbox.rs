@@ -0,0 +1,216 @@
+//! Chatbox backup (`chatbox-exported-data-*.json`)
+//!
+//! Sessions live either in a `chat-sessions` array (older versions) or as
+//! `session:<id>` entries listed in `chat-sessions-list`. Provider credentials are
+//! in `settings`, flat for the built-in providers and under `providers` /
+//! `customProviders` in newer versions. Copilots become assistants.
+
+use super::{
+    ImportBundle, ImportedAssistant, ImportedConversation, ImportedMessage, ImportedProvider,
+    map_provider_type, parse_role, str_field, timestamp,
+};
+use serde_json::Value;
+
+/// Flat settings keys of the built-in providers: (provider, key, host, model)
+const LEGACY_PROVIDER_KEYS: &[(&str, &str, &str, &str)] = &[
+    ("openai", "openaiKey", "apiHost", "model"),
+    ("claude", "claudeApiKey", "claudeApiHost", "claudeModel"),
+    ("gemini", "geminiAPIKey", "geminiAPIHost", "geminiModel"),
+    ("ollama", "", "ollamaHost", "ollamaModel"),
+    ("groq", "groqAPIKey", "", "groqModel"),
+    ("deepseek", "deepseekAPIKey", "", "deepseekModel"),
+];
+
+pub(super) fn matches(json: &Value) -> bool {
+    json.get("chat-sessions").is_some()
+        || json.get("chat-sessions-list").is_some()
+        || json
+            .as_object()
+            .is_some_and(|o| o.keys().any(|k| k.starts_with("session:")))
+}
+
+pub(super) fn parse(json: &Value) -> ImportBundle {
+    ImportBundle {
+        providers: parse_providers(&json["settings"]),
+        assistants: json["myCopilots"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(parse_copilot)
+            .collect(),
+        conversations: sessions(json)
+            .into_iter()
+            .filter_map(parse_session)
+            .collect(),
+    }
+}
+
+fn parse_providers(settings: &Value) -> Vec<ImportedProvider> {
+    let mut providers = Vec::new();
+
+    if let Some(configured) = settings["providers"].as_object() {
+        for (id, config) in configured {
+            let api_key = str_field(config, "apiKey");
+            let base_url = str_field(config, "apiHost");
+            if api_key.is_none() && base_url.is_none() {
+                continue;
+            }
+            let models = config["models"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|m| str_field(m, "modelId").or_else(|| m.as_str().map(String::from)))
+                .collect();
+            providers.push(ImportedProvider {
+                name: format!("Chatbox {}", id),
+                provider_type: map_provider_type(id).to_string(),
+                api_key,
+                base_url,
+                models,
+            });
+        }
+    } else {
+        for &(id, key, host, model) in LEGACY_PROVIDER_KEYS {
+            let api_key = str_field(settings, key);
+            let base_url = str_field(settings, host);
+            if api_key.is_none() && (id != "ollama" || base_url.is_none()) {
+                continue;
+            }
+            providers.push(ImportedProvider {
+                name: format!("Chatbox {}", id),
+                provider_type: map_provider_type(id).to_string(),
+                api_key,
+                base_url,
+                models: str_field(settings, model).into_iter().collect(),
+            });
+        }
+    }
+
+    for custom in settings["customProviders"].as_array().into_iter().flatten() {
+        let Some(base_url) = str_field(custom, "host").or_else(|| str_field(custom, "apiHost"))
+        else {
+            continue;
+        };
+        providers.push(ImportedProvider {
+            name: str_field(custom, "name").unwrap_or_else(|| "Chatbox custom".to_string()),
+            provider_type: "custom_openai".to_string(),
+            api_key: str_field(custom, "key").or_else(|| str_field(custom, "apiKey")),
+            base_url: Some(base_url),
+            models: str_field(custom, "model").into_iter().collect(),
+        });
+    }
+
+    providers
+}
+
+fn parse_copilot(copilot: &Value) -> Option<ImportedAssistant> {
+    Some(ImportedAssistant {
+        name: str_field(copilot, "name")?,
+        description: None,
+        system_prompt: str_field(copilot, "prompt")?,
+        model: None,
+        provider: None,
+    })
+}
+
+/// Sessions from either storage layout
+fn sessions(json: &Value) -> Vec<&Value> {
+    if let Some(list) = json["chat-sessions"].as_array() {
+        return list.iter().collect();
+    }
+    let Some(object) = json.as_object() else {
+        return Vec::new();
+    };
+    match json["chat-sessions-list"].as_array() {
+        Some(list) => list
+            .iter()
+            .filter_map(|meta| meta["id"].as_str())
+            .filter_map(|id| object.get(&format!("session:{}", id)))
+            .collect(),
+        None => object
+            .iter()
+            .filter(|(k, _)| k.starts_with("session:"))
+            .map(|(_, v)| v)
+            .collect(),
+    }
+}
+
+fn parse_session(session: &Value) -> Option<ImportedConversation> {
+    let messages: Vec<ImportedMessage> = session["messages"]
+        .as_array()?
+        .iter()
+        .filter_map(|m| {
+            let role = parse_role(m["role"].as_str()?)?;
+            let content = message_text(m)?;
+            Some(ImportedMessage {
+                role,
+                content,
+                created_at: timestamp(&m["timestamp"]),
+                model: str_field(m, "model"),
+            })
+        })
+        .collect();
+    if messages.is_empty() {
+        return None;
+    }
+
+    Some(ImportedConversation {
+        title: str_field(session, "name").unwrap_or_else(|| "Imported chat".to_string()),
+        created_at: messages.first().and_then(|m| m.created_at.clone()),
+        messages,
+    })
+}
+
+/// Text of a message: `contentParts` in newer versions, `content` in older ones
+fn message_text(message: &Value) -> Option<String> {
+    let text = match message["contentParts"].as_array() {
+        Some(parts) => parts
+            .iter()
+            .filter(|p| p["type"] == "text")
+            .filter_map(|p| p["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => message["content"].as_str()?.to_string(),
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::ImportedRole;
+
+    #[test]
+    fn test_parse_chatbox_backup() {
+        let json = serde_json::json!({
+            "settings": { "openaiKey": "sk-test", "apiHost": "https://api.openai.com", "model": "gpt-4o" },
+            "myCopilots": [{ "name": "Translator", "prompt": "Translate to French" }],
+            "chat-sessions-list": [{ "id": "s1" }],
+            "session:s1": {
+                "name": "Greetings",
+                "messages": [
+                    { "role": "system", "content": "You are helpful" },
+                    { "role": "user", "content": "Hi", "timestamp": 1704067200000i64 },
+                    { "role": "assistant", "contentParts": [{ "type": "text", "text": "Hello!" }], "model": "gpt-4o" }
+                ]
+            }
+        });
+        assert!(matches(&json));
+
+        let bundle = parse(&json);
+        assert_eq!(bundle.providers.len(), 1);
+        assert_eq!(bundle.providers[0].provider_type, "openai");
+        assert_eq!(bundle.providers[0].models, vec!["gpt-4o"]);
+        assert_eq!(bundle.assistants[0].system_prompt, "Translate to French");
+
+        let conversation = &bundle.conversations[0];
+        assert_eq!(conversation.title, "Greetings");
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[1].role, ImportedRole::Assistant);
+        assert_eq!(conversation.messages[1].content, "Hello!");
+        assert_eq!(
+            conversation.created_at.as_deref(),
+            Some("2024-01-01T00:00:00+00:00")
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Cherry Studio backup (`cherry-studio.*.zip`, or its `data.json`)
+//!
+//! Settings are a redux-persist dump in `localStorage["persist:cherry-studio"]`: a
+//! JSON string whose slices (`llm`, `assistants`) are JSON strings themselves.
+//! Messages are in `indexedDB.topics`; newer versions keep their text in
+//! `indexedDB.message_blocks`, older ones in the message's `content`.
+
+use super::{
+    ImportBundle, ImportedAssistant, ImportedConversation, ImportedMessage, ImportedProvider,
+    map_provider_type, parse_role, str_field, timestamp,
+};
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+const PERSIST_KEY: &str = "persist:cherry-studio";
+/// Largest `data.json` read from a backup zip
+const MAX_DATA_JSON_BYTES: u64 = 512 * 1024 * 1024;
+
+pub(super) fn matches(json: &Value) -> bool {
+    json["localStorage"].get(PERSIST_KEY).is_some() || json["indexedDB"]["topics"].is_array()
+}
+
+/// Read `data.json` out of a backup zip
+pub(super) fn read_backup_zip(path: &Path) -> Result<Value> {
+    let file = std::fs::File::open(path)?;
+    let mut zip = zip::ZipArchive::new(file)?;
+    let entry = zip
+        .by_name("data.json")
+        .map_err(|_| anyhow!("Not a Cherry Studio backup: data.json is missing"))?;
+    let mut content = String::new();
+    entry
+        .take(MAX_DATA_JSON_BYTES)
+        .read_to_string(&mut content)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+pub(super) fn parse(json: &Value) -> ImportBundle {
+    let persisted = persisted_state(json);
+    let llm = slice(&persisted, "llm");
+    let providers: Vec<&Value> = llm["providers"].as_array().into_iter().flatten().collect();
+    // Assistants reference providers by id; bundles link them by name
+    let provider_names: HashMap<&str, String> = providers
+        .iter()
+        .filter_map(|p| {
+            let id = p["id"].as_str()?;
+            Some((id, str_field(p, "name").unwrap_or_else(|| id.to_string())))
+        })
+        .collect();
+    let assistants_slice = slice(&persisted, "assistants");
+    let assistants: Vec<&Value> = assistants_slice["assistants"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    // Topic names live on the assistants, messages in IndexedDB
+    let topic_names: HashMap<&str, &Value> = assistants
+        .iter()
+        .flat_map(|a| a["topics"].as_array().into_iter().flatten())
+        .filter_map(|t| Some((t["id"].as_str()?, t)))
+        .collect();
+    let blocks: HashMap<&str, &Value> = json["indexedDB"]["message_blocks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|b| Some((b["id"].as_str()?, b)))
+        .collect();
+
+    ImportBundle {
+        providers: providers.iter().filter_map(|p| parse_provider(p)).collect(),
+        assistants: assistants
+            .iter()
+            .filter_map(|a| parse_assistant(a, &provider_names))
+            .collect(),
+        conversations: json["indexedDB"]["topics"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|topic| {
+                let meta = topic["id"].as_str().and_then(|id| topic_names.get(id));
+                parse_topic(topic, meta.copied(), &blocks)
+            })
+            .collect(),
+    }
+}
+
+/// Decode the persisted redux state (a JSON string)
+fn persisted_state(json: &Value) -> Value {
+    json["localStorage"][PERSIST_KEY]
+        .as_str()
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or(Value::Null)
+}
+
+/// A persisted slice, stored as a nested JSON string
+fn slice(persisted: &Value, key: &str) -> Value {
+    match &persisted[key] {
+        Value::String(s) => serde_json::from_str(s).unwrap_or(Value::Null),
+        other => other.clone(),
+    }
+}
+
+fn parse_provider(provider: &Value) -> Option<ImportedProvider> {
+    let id = provider["id"].as_str()?;
+    let api_key = str_field(provider, "apiKey");
+    let is_local = matches!(id, "ollama" | "lmstudio");
+    let enabled = provider["enabled"].as_bool().unwrap_or(false);
+    if api_key.is_none() && !(is_local && enabled) {
+        return None;
+    }
+
+    // Prefer the provider id (e.g. "deepseek"); fall back to its API type for custom ones
+    let mut provider_type = map_provider_type(id);
+    if provider_type == "custom_openai"
+        && let Some(kind) = provider["type"].as_str().filter(|t| *t != "openai")
+    {
+        provider_type = map_provider_type(kind);
+    }
+
+    Some(ImportedProvider {
+        name: str_field(provider, "name").unwrap_or_else(|| id.to_string()),
+        provider_type: provider_type.to_string(),
+        api_key,
+        base_url: str_field(provider, "apiHost"),
+        models: provider["models"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|m| str_field(m, "id"))
+            .collect(),
+    })
+}
+
+fn parse_assistant(
+    assistant: &Value,
+    provider_names: &HashMap<&str, String>,
+) -> Option<ImportedAssistant> {
+    Some(ImportedAssistant {
+        name: str_field(assistant, "name")?,
+        description: str_field(assistant, "description"),
+        system_prompt: str_field(assistant, "prompt").unwrap_or_default(),
+        model: str_field(&assistant["model"], "id"),
+        provider: assistant["model"]["provider"]
+            .as_str()
+            .and_then(|id| provider_names.get(id).cloned()),
+    })
+}
+
+fn parse_topic(
+    topic: &Value,
+    meta: Option<&Value>,
+    blocks: &HashMap<&str, &Value>,
+) -> Option<ImportedConversation> {
+    let messages: Vec<ImportedMessage> = topic["messages"]
+        .as_array()?
+        .iter()
+        .filter_map(|m| {
+            let role = parse_role(m["role"].as_str()?)?;
+            let content = message_text(m, blocks)?;
+            Some(ImportedMessage {
+                role,
+                content,
+                created_at: timestamp(&m["createdAt"]),
+                model: str_field(&m["model"], "id").or_else(|| str_field(m, "modelId")),
+            })
+        })
+        .collect();
+    if messages.is_empty() {
+        return None;
+    }
+
+    Some(ImportedConversation {
+        title: meta
+            .and_then(|t| str_field(t, "name"))
+            .unwrap_or_else(|| "Imported chat".to_string()),
+        created_at: meta
+            .and_then(|t| timestamp(&t["createdAt"]))
+            .or_else(|| messages.first().and_then(|m| m.created_at.clone())),
+        messages,
+    })
+}
+
+/// Main text blocks of a message, or its legacy `content`
+fn message_text(message: &Value, blocks: &HashMap<&str, &Value>) -> Option<String> {
+    let from_blocks: Vec<&str> = message["blocks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| blocks.get(id.as_str()?))
+        .filter(|b| b["type"] == "main_text")
+        .filter_map(|b| b["content"].as_str())
+        .collect();
+    let text = if from_blocks.is_empty() {
+        message["content"].as_str()?.to_string()
+    } else {
+        from_blocks.join("\n\n")
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cherry_studio_backup() {
+        let llm = serde_json::json!({
+            "providers": [
+                { "id": "deepseek", "type": "openai", "name": "DeepSeek", "apiKey": "sk-1", "apiHost": "https://api.deepseek.com", "models": [{ "id": "deepseek-chat" }] },
+                { "id": "silicon", "type": "openai", "name": "Silicon", "apiKey": "", "models": [] }
+            ]
+        });
+        let assistants = serde_json::json!({
+            "assistants": [{
+                "id": "a1", "name": "Default", "prompt": "Be concise",
+                "model": { "id": "deepseek-chat", "provider": "deepseek" },
+                "topics": [{ "id": "t1", "name": "Rust question", "createdAt": "2024-05-01T10:00:00.000Z" }]
+            }]
+        });
+        let persisted = serde_json::json!({
+            "llm": llm.to_string(),
+            "assistants": assistants.to_string(),
+        });
+        let json = serde_json::json!({
+            "localStorage": { PERSIST_KEY: persisted.to_string() },
+            "indexedDB": {
+                "topics": [{ "id": "t1", "messages": [
+                    { "id": "m1", "role": "user", "blocks": ["b1"], "createdAt": "2024-05-01T10:00:00.000Z" },
+                    { "id": "m2", "role": "assistant", "blocks": ["b2", "b3"], "model": { "id": "deepseek-chat" } }
+                ]}],
+                "message_blocks": [
+                    { "id": "b1", "type": "main_text", "content": "What is a lifetime?" },
+                    { "id": "b2", "type": "thinking", "content": "Let me think" },
+                    { "id": "b3", "type": "main_text", "content": "A lifetime is..." }
+                ]
+            }
+        });
+        assert!(matches(&json));
+
+        let bundle = parse(&json);
+        assert_eq!(bundle.providers.len(), 1);
+        assert_eq!(bundle.providers[0].provider_type, "deepseek");
+        assert_eq!(bundle.assistants[0].provider.as_deref(), Some("DeepSeek"));
+
+        let conversation = &bundle.conversations[0];
+        assert_eq!(conversation.title, "Rust question");
+        assert_eq!(conversation.messages[0].content, "What is a lifetime?");
+        assert_eq!(conversation.messages[1].content, "A lifetime is...");
+        assert_eq!(
+            conversation.messages[1].model.as_deref(),
+            Some("deepseek-chat")
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Jan data folder
+//!
+//! Each thread is `threads/<id>/thread.json` plus one message per line in
+//! `threads/<id>/messages.jsonl`; assistants are `assistants/<id>/assistant.json`.
+//! Remote engine credentials come from the inference extensions' settings
+//! (`settings/@janhq/inference-<engine>-extension/settings.json`).
+
+use super::{
+    ImportBundle, ImportedAssistant, ImportedConversation, ImportedMessage, ImportedProvider,
+    map_provider_type, parse_role, str_field, timestamp,
+};
+use anyhow::Result;
+use serde_json::Value;
+use std::path::Path;
+
+pub(super) fn parse(dir: &Path) -> Result<ImportBundle> {
+    let mut threads: Vec<(Option<String>, ImportedConversation)> = Vec::new();
+    for thread_dir in subdirectories(&dir.join("threads")) {
+        let Some(thread) = read_json(&thread_dir.join("thread.json")) else {
+            continue;
+        };
+        let messages =
+            std::fs::read_to_string(thread_dir.join("messages.jsonl")).unwrap_or_default();
+        if let Some(conversation) = parse_thread(&thread, &messages) {
+            threads.push((conversation.created_at.clone(), conversation));
+        }
+    }
+    // Oldest first, so imported conversations keep their relative order
+    threads.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(ImportBundle {
+        providers: parse_providers(&dir.join("settings").join("@janhq")),
+        assistants: subdirectories(&dir.join("assistants"))
+            .iter()
+            .filter_map(|d| read_json(&d.join("assistant.json")))
+            .filter_map(|a| parse_assistant(&a))
+            .collect(),
+        conversations: threads.into_iter().map(|(_, c)| c).collect(),
+    })
+}
+
+fn subdirectories(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut dirs: Vec<_> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn parse_providers(extensions_dir: &Path) -> Vec<ImportedProvider> {
+    subdirectories(extensions_dir)
+        .iter()
+        .filter_map(|dir| {
+            let folder = dir.file_name()?.to_str()?;
+            let engine = folder
+                .strip_prefix("inference-")?
+                .strip_suffix("-extension")?;
+            let settings = read_json(&dir.join("settings.json"))?;
+            parse_engine_settings(engine, &settings)
+        })
+        .collect()
+}
+
+/// Settings are a list of `{ key, controllerProps: { value } }`
+fn parse_engine_settings(engine: &str, settings: &Value) -> Option<ImportedProvider> {
+    let value = |suffix: &str| {
+        settings
+            .as_array()?
+            .iter()
+            .find(|s| s["key"].as_str().is_some_and(|k| k.ends_with(suffix)))
+            .and_then(|s| str_field(&s["controllerProps"], "value"))
+    };
+    let api_key = value("api-key")?;
+    let base_url = value("chat-completions-endpoint").map(|endpoint| {
+        endpoint
+            .trim_end_matches('/')
+            .trim_end_matches("/chat/completions")
+            .to_string()
+    });
+
+    Some(ImportedProvider {
+        name: format!("Jan {}", engine),
+        provider_type: map_provider_type(engine).to_string(),
+        api_key: Some(api_key),
+        base_url,
+        models: Vec::new(),
+    })
+}
+
+fn parse_assistant(assistant: &Value) -> Option<ImportedAssistant> {
+    // Jan ships a stock assistant with no instructions; it adds nothing
+    let instructions = str_field(assistant, "instructions")?;
+    Some(ImportedAssistant {
+        name: str_field(assistant, "name")?,
+        description: str_field(assistant, "description"),
+        system_prompt: instructions,
+        model: str_field(&assistant["model"], "id"),
+        provider: None,
+    })
+}
+
+fn parse_thread(thread: &Value, messages_jsonl: &str) -> Option<ImportedConversation> {
+    let default_model = thread["assistants"]
+        .as_array()
+        .and_then(|a| a.first())
+        .and_then(|a| str_field(&a["model"], "id"));
+
+    let messages: Vec<ImportedMessage> = messages_jsonl
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|m| {
+            let role = parse_role(m["role"].as_str()?)?;
+            let content = message_text(&m)?;
+            Some(ImportedMessage {
+                role,
+                content,
+                created_at: timestamp(&m["created_at"]).or_else(|| timestamp(&m["created"])),
+                model: default_model.clone(),
+            })
+        })
+        .collect();
+    if messages.is_empty() {
+        return None;
+    }
+
+    Some(ImportedConversation {
+        title: str_field(thread, "title").unwrap_or_else(|| "Imported chat".to_string()),
+        created_at: timestamp(&thread["created"])
+            .or_else(|| messages.first().and_then(|m| m.created_at.clone())),
+        messages,
+    })
+}
+
+/// Text parts are `{ type: "text", text: { value } }`
+fn message_text(message: &Value) -> Option<String> {
+    let text = match &message["content"] {
+        Value::String(s) => s.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|p| p["type"] == "text")
+            .filter_map(|p| p["text"]["value"].as_str().or_else(|| p["text"].as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::ImportedRole;
+
+    #[test]
+    fn test_parse_thread() {
+        let thread = serde_json::json!({
+            "id": "jan_1",
+            "title": "Trip planning",
+            "created": 1704067200000i64,
+            "assistants": [{ "model": { "id": "llama3.2-3b-instruct" } }]
+        });
+        let messages = [
+            r#"{"role":"user","content":[{"type":"text","text":{"value":"Plan a trip","annotations":[]}}],"created_at":1704067200}"#,
+            r#"{"role":"assistant","content":[{"type":"text","text":{"value":"Sure!","annotations":[]}}],"created_at":1704067205}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let conversation = parse_thread(&thread, &messages).unwrap();
+        assert_eq!(conversation.title, "Trip planning");
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[1].role, ImportedRole::Assistant);
+        assert_eq!(
+            conversation.messages[1].model.as_deref(),
+            Some("llama3.2-3b-instruct")
+        );
+    }
+
+    #[test]
+    fn test_parse_engine_settings() {
+        let settings = serde_json::json!([
+            { "key": "openai-api-key", "controllerProps": { "value": "sk-jan" } },
+            { "key": "chat-completions-endpoint", "controllerProps": { "value": "https://api.openai.com/v1/chat/completions" } }
+        ]);
+        let provider = parse_engine_settings("openai", &settings).unwrap();
+        assert_eq!(provider.provider_type, "openai");
+        assert_eq!(
+            provider.base_url.as_deref(),
+            Some("https://api.openai.com/v1")
+        );
+        assert!(parse_engine_settings("groq", &serde_json::json!([])).is_none());
+    }
+}
@@ -0,0 +1,245 @@
+//! Importers for other desktop chat apps' data
+//!
+//! Each adapter turns an export into an [`ImportBundle`]: providers (with their
+//! models), assistants and conversations in a neutral shape that
+//! `commands::import_chat_data` then writes into ChatShell's schema.
+//!
+//! Supported sources:
+//! - Chatbox: the JSON file from Settings → Backup
+//! - Cherry Studio: the backup `.zip` (or the `data.json` inside it)
+//! - Jan: the data folder (containing `threads/`)
+
+mod chatbox;
+mod cherry_studio;
+mod jan;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    Chatbox,
+    CherryStudio,
+    Jan,
+}
+
+impl ImportSource {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "chatbox" => Some(ImportSource::Chatbox),
+            "cherry_studio" => Some(ImportSource::CherryStudio),
+            "jan" => Some(ImportSource::Jan),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedProvider {
+    pub name: String,
+    /// ChatShell provider type (see [`map_provider_type`])
+    pub provider_type: String,
+    pub api_key: Option<String>,
+    pub base_url: Option<String>,
+    pub models: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedAssistant {
+    pub name: String,
+    pub description: Option<String>,
+    pub system_prompt: String,
+    /// Model id as the provider knows it, e.g. "gpt-4o"
+    pub model: Option<String>,
+    /// Name of the provider in the same bundle
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportedRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedMessage {
+    pub role: ImportedRole,
+    pub content: String,
+    /// RFC 3339
+    pub created_at: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedConversation {
+    pub title: String,
+    /// RFC 3339
+    pub created_at: Option<String>,
+    pub messages: Vec<ImportedMessage>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportBundle {
+    pub providers: Vec<ImportedProvider>,
+    pub assistants: Vec<ImportedAssistant>,
+    pub conversations: Vec<ImportedConversation>,
+}
+
+/// Guess the source app from the shape of the export
+pub fn detect_source(path: &Path) -> Result<ImportSource> {
+    if path.is_dir() {
+        if path.join("threads").is_dir() {
+            return Ok(ImportSource::Jan);
+        }
+        return Err(anyhow!(
+            "Unrecognized data folder: expected a Jan data folder"
+        ));
+    }
+    if has_extension(path, "zip") {
+        return Ok(ImportSource::CherryStudio);
+    }
+
+    let json = read_json(path)?;
+    if cherry_studio::matches(&json) {
+        Ok(ImportSource::CherryStudio)
+    } else if chatbox::matches(&json) {
+        Ok(ImportSource::Chatbox)
+    } else {
+        Err(anyhow!("Unrecognized export format"))
+    }
+}
+
+/// Parse an export into a bundle
+pub fn load(path: &Path, source: ImportSource) -> Result<ImportBundle> {
+    let bundle = match source {
+        ImportSource::Chatbox => chatbox::parse(&read_json(path)?),
+        ImportSource::CherryStudio => {
+            let json = if has_extension(path, "zip") {
+                cherry_studio::read_backup_zip(path)?
+            } else {
+                read_json(path)?
+            };
+            cherry_studio::parse(&json)
+        }
+        ImportSource::Jan => jan::parse(path)?,
+    };
+
+    tracing::info!(
+        "📥 [import] Parsed {:?} export: {} provider(s), {} assistant(s), {} conversation(s)",
+        source,
+        bundle.providers.len(),
+        bundle.assistants.len(),
+        bundle.conversations.len()
+    );
+    Ok(bundle)
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Map another app's provider id/type onto a ChatShell provider type. Unknown
+/// providers with a base URL are imported as OpenAI-compatible.
+pub fn map_provider_type(id: &str) -> &'static str {
+    match id.to_lowercase().replace(['-', ' '], "_").as_str() {
+        "openai" | "openai_response" => "openai",
+        "anthropic" | "claude" => "anthropic",
+        "gemini" | "google" => "gemini",
+        "azure" | "azure_openai" => "azure",
+        "deepseek" => "deepseek",
+        "groq" => "groq",
+        "mistral" | "mistralai" => "mistral",
+        "moonshot" | "kimi" => "moonshot",
+        "ollama" => "ollama",
+        "openrouter" => "openrouter",
+        "perplexity" => "perplexity",
+        "together" | "togetherai" => "together",
+        "xai" | "grok" => "xai",
+        "cohere" => "cohere",
+        "hyperbolic" => "hyperbolic",
+        "minimax" => "minimax",
+        _ => "custom_openai",
+    }
+}
+
+/// Normalize a timestamp (epoch seconds or milliseconds, or a date string) to RFC 3339
+pub(crate) fn timestamp(value: &Value) -> Option<String> {
+    let millis = match value {
+        Value::Number(n) => {
+            let n = n.as_f64()?;
+            // Anything past ~2001 in milliseconds is far beyond any date in seconds
+            if n > 1e12 {
+                n as i64
+            } else {
+                (n * 1000.0) as i64
+            }
+        }
+        Value::String(s) => {
+            if let Ok(n) = s.parse::<f64>() {
+                return timestamp(&serde_json::json!(n));
+            }
+            return chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|d| d.with_timezone(&chrono::Utc).to_rfc3339());
+        }
+        _ => return None,
+    };
+    chrono::DateTime::from_timestamp_millis(millis).map(|d| d.to_rfc3339())
+}
+
+/// Non-empty string field
+pub(crate) fn str_field(value: &Value, key: &str) -> Option<String> {
+    value[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Parse a role string; system and tool messages are not imported
+pub(crate) fn parse_role(role: &str) -> Option<ImportedRole> {
+    match role {
+        "user" => Some(ImportedRole::User),
+        "assistant" => Some(ImportedRole::Assistant),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_normalization() {
+        let expected = "2024-01-01T00:00:00+00:00";
+        assert_eq!(
+            timestamp(&serde_json::json!(1704067200)).as_deref(),
+            Some(expected)
+        );
+        assert_eq!(
+            timestamp(&serde_json::json!(1704067200000i64)).as_deref(),
+            Some(expected)
+        );
+        assert_eq!(
+            timestamp(&serde_json::json!("2024-01-01T00:00:00.000Z")).as_deref(),
+            Some(expected)
+        );
+        assert_eq!(timestamp(&serde_json::json!(null)), None);
+    }
+
+    #[test]
+    fn test_map_provider_type() {
+        assert_eq!(map_provider_type("OpenAI"), "openai");
+        assert_eq!(map_provider_type("claude"), "anthropic");
+        assert_eq!(map_provider_type("silicon"), "custom_openai");
+    }
+}
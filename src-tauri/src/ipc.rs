@@ -0,0 +1,314 @@
+//! Local IPC server for CLI and editor integrations
+//!
+//! When enabled in settings, the running app listens on a unix socket
+//! (`{app_data_dir}/chatshell.sock`, mode 0600) or, on Windows, the named pipe
+//! `\\.\pipe\chatshell`. The protocol is newline-delimited JSON:
+//!
+//! ```text
+//! -> {"id": 1, "method": "send_message", "params": {"conversation_id": "...", "content": "Hi", "stream": true}}
+//! <- {"id": 1, "result": { ...user message... }}
+//! <- {"id": 1, "event": "chunk", "data": {"content": "Hel"}}
+//! <- {"id": 1, "event": "complete", "data": { ...assistant message... }}
+//! ```
+//!
+//! Methods: `new_conversation`, `list_conversations`, `get_messages`, `send_message`,
+//! `stop_generation`, `search_history`.
+
+use crate::commands::{self, AppState};
+use crate::models::CreateConversationRequest;
+use serde::Deserialize;
+use serde_json::Value;
+use tauri::{Listener, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+/// Settings key: "true" starts the IPC server at launch
+pub const IPC_SERVER_ENABLED_KEY: &str = "ipc_server_enabled";
+
+#[cfg(windows)]
+pub const PIPE_NAME: &str = r"\\.\pipe\chatshell";
+
+/// Events forwarded to clients that asked for a streamed response
+const STREAM_EVENTS: &[(&str, &str)] = &[
+    ("chat-stream", "chunk"),
+    ("chat-stream-reasoning", "reasoning"),
+    ("tool-call-started", "tool_call_started"),
+    ("tool-call-completed", "tool_call_completed"),
+    ("chat-complete", "complete"),
+    ("chat-error", "error"),
+    ("generation-stopped", "stopped"),
+];
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendMessageParams {
+    conversation_id: String,
+    content: String,
+    #[serde(default)]
+    provider: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    search_enabled: Option<bool>,
+    #[serde(default)]
+    stream: bool,
+}
+
+/// Path of the unix socket
+#[cfg(unix)]
+pub fn socket_path(app: &tauri::AppHandle) -> anyhow::Result<std::path::PathBuf> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?;
+    Ok(dir.join("chatshell.sock"))
+}
+
+/// Start the server when enabled in settings
+pub async fn start_if_enabled(app: tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let enabled = matches!(
+        state
+            .db
+            .get_setting(IPC_SERVER_ENABLED_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    );
+    if !enabled {
+        return;
+    }
+
+    if let Err(e) = serve(app).await {
+        tracing::error!("❌ [ipc] Server stopped: {}", e);
+    }
+}
+
+#[cfg(unix)]
+async fn serve(app: tauri::AppHandle) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = socket_path(&app)?;
+    // A previous run may have left the socket file behind
+    let _ = std::fs::remove_file(&path);
+    let listener = tokio::net::UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    tracing::info!("🔌 [ipc] Listening on {:?}", path);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(handle_connection(app.clone(), stream));
+    }
+}
+
+#[cfg(windows)]
+async fn serve(app: tauri::AppHandle) -> anyhow::Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .reject_remote_clients(true)
+        .create(PIPE_NAME)?;
+    tracing::info!("🔌 [ipc] Listening on {}", PIPE_NAME);
+
+    loop {
+        server.connect().await?;
+        let connected = server;
+        server = ServerOptions::new()
+            .reject_remote_clients(true)
+            .create(PIPE_NAME)?;
+        tokio::spawn(handle_connection(app.clone(), connected));
+    }
+}
+
+async fn handle_connection<S>(app: tauri::AppHandle, stream: S)
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+
+    // Single writer so responses and streamed events never interleave mid-line
+    let writer_task = tokio::spawn(async move {
+        while let Some(message) = rx.recv().await {
+            let mut line = message.to_string();
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Request = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(e) => {
+                let _ = tx.send(serde_json::json!({
+                    "id": Value::Null,
+                    "error": format!("Invalid request: {}", e),
+                }));
+                continue;
+            }
+        };
+
+        let app = app.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let id = request.id.clone();
+            let response = match dispatch(&app, request, &tx).await {
+                Ok(result) => serde_json::json!({ "id": id, "result": result }),
+                Err(error) => serde_json::json!({ "id": id, "error": error }),
+            };
+            let _ = tx.send(response);
+        });
+    }
+
+    drop(tx);
+    let _ = writer_task.await;
+}
+
+async fn dispatch(
+    app: &tauri::AppHandle,
+    request: Request,
+    tx: &mpsc::UnboundedSender<Value>,
+) -> Result<Value, String> {
+    let state: tauri::State<'_, AppState> = app.state();
+    tracing::info!("🔌 [ipc] {}", request.method);
+
+    match request.method.as_str() {
+        "new_conversation" => {
+            let title = request.params["title"]
+                .as_str()
+                .unwrap_or("New Conversation")
+                .to_string();
+            let conversation =
+                commands::create_conversation(state, CreateConversationRequest { title }).await?;
+            to_value(conversation)
+        }
+        "list_conversations" => to_value(commands::list_conversations(state).await?),
+        "get_messages" => {
+            let conversation_id = string_param(&request.params, "conversation_id")?;
+            let messages = state
+                .db
+                .list_messages_by_conversation(&conversation_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            to_value(messages)
+        }
+        "search_history" => {
+            let query = string_param(&request.params, "query")?;
+            let limit = request.params["limit"].as_i64();
+            to_value(commands::search_chat_history(state, query, limit, None).await?)
+        }
+        "stop_generation" => {
+            let conversation_id = string_param(&request.params, "conversation_id")?;
+            to_value(commands::chat::stop_generation(state, app.clone(), conversation_id).await?)
+        }
+        "send_message" => {
+            let params: SendMessageParams =
+                serde_json::from_value(request.params).map_err(|e| e.to_string())?;
+            if params.stream {
+                forward_stream_events(app, &params.conversation_id, request.id, tx.clone());
+            }
+            let message = commands::chat::send_message(
+                state,
+                app.clone(),
+                params.conversation_id,
+                params.content,
+                params.provider,
+                params.model,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                params.search_enabled,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?;
+            to_value(message)
+        }
+        other => Err(format!("Unknown method: {}", other)),
+    }
+}
+
+/// Forward this conversation's streaming events to the client until the response
+/// completes, fails or is stopped
+fn forward_stream_events(
+    app: &tauri::AppHandle,
+    conversation_id: &str,
+    request_id: Value,
+    tx: mpsc::UnboundedSender<Value>,
+) {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<(&'static str, Value)>();
+    let listener_ids: Vec<_> = STREAM_EVENTS
+        .iter()
+        .map(|&(event, name)| {
+            let event_tx = event_tx.clone();
+            let conversation_id = conversation_id.to_string();
+            app.listen_any(event, move |e| {
+                let Ok(payload) = serde_json::from_str::<Value>(e.payload()) else {
+                    return;
+                };
+                if payload["conversation_id"].as_str() == Some(conversation_id.as_str()) {
+                    let _ = event_tx.send((name, payload));
+                }
+            })
+        })
+        .collect();
+
+    let app = app.clone();
+    tokio::spawn(async move {
+        while let Some((name, payload)) = event_rx.recv().await {
+            let data = match name {
+                "complete" => payload["message"].clone(),
+                _ => payload,
+            };
+            let finished = matches!(name, "complete" | "error" | "stopped");
+            if tx
+                .send(serde_json::json!({ "id": request_id, "event": name, "data": data }))
+                .is_err()
+                || finished
+            {
+                break;
+            }
+        }
+        for id in listener_ids {
+            app.unlisten(id);
+        }
+    });
+}
+
+fn string_param(params: &Value, key: &str) -> Result<String, String> {
+    params[key]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("Missing parameter: {}", key))
+}
+
+fn to_value<T: serde::Serialize>(value: T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
@@ -0,0 +1,247 @@
+//! Persistent background job queue
+//!
+//! Work that runs after a response is saved (summary refreshes, webhook deliveries,
+//! answer verification) or when an automation is triggered is stored in the `jobs` table and picked up by a small worker
+//! pool, instead of living in a detached task. Jobs run highest priority first, failed attempts are retried with
+//! exponential backoff, and jobs interrupted by a quit are requeued on the next launch.
+//! `list_jobs` exposes the queue to the frontend.
+
+use crate::commands::AppState;
+use crate::commands::chat::{automation, summary, verification};
+use crate::db::Database;
+use crate::models::{AutomationEvent, CreateJobRequest, Job, JobKind, WebhookEvent};
+use crate::webhooks;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::Manager;
+use tokio::sync::Notify;
+
+const WORKER_COUNT: usize = 2;
+
+/// How often idle workers look for jobs whose retry delay has passed
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(10);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(600);
+
+/// Finished jobs are kept this long so they stay visible in `list_jobs`
+const FINISHED_JOB_RETENTION_DAYS: i64 = 7;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SummaryRefreshPayload {
+    pub conversation_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AnswerVerificationPayload {
+    pub conversation_id: String,
+    pub message_id: String,
+    /// The pages the reply was given, in prompt order
+    pub fetch_result_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AutomationRunPayload {
+    pub automation_id: String,
+    /// What triggered the run
+    #[serde(default)]
+    pub event: AutomationEvent,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct WebhookDeliveryPayload {
+    pub webhook_id: String,
+    pub event: WebhookEvent,
+    pub body: String,
+}
+
+/// Enqueues jobs and wakes idle workers
+pub struct JobQueue {
+    db: Database,
+    wake: Notify,
+}
+
+impl JobQueue {
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            wake: Notify::new(),
+        }
+    }
+
+    pub(crate) async fn enqueue<P: Serialize>(
+        &self,
+        kind: JobKind,
+        payload: &P,
+        priority: i64,
+    ) -> anyhow::Result<Job> {
+        let job = self
+            .db
+            .create_job(CreateJobRequest {
+                kind,
+                payload: serde_json::to_value(payload)?,
+                priority,
+                max_attempts: max_attempts(kind),
+            })
+            .await?;
+        tracing::debug!("📋 [jobs] Enqueued {} job {}", kind.id(), job.id);
+        self.wake.notify_one();
+        Ok(job)
+    }
+}
+
+fn max_attempts(kind: JobKind) -> i64 {
+    match kind {
+        JobKind::SummaryRefresh => 2,
+        JobKind::WebhookDelivery => 5,
+        JobKind::AnswerVerification => 2,
+        JobKind::AutomationRun => 3,
+    }
+}
+
+/// Delay before the next attempt after `attempts` failed ones
+fn retry_delay(attempts: i64) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.pow(exponent))
+        .min(RETRY_MAX_DELAY)
+}
+
+/// Requeue interrupted jobs, prune old ones and start the worker pool
+pub async fn start(app: tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+
+    match state.db.requeue_interrupted_jobs().await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("📋 [jobs] Requeued {} interrupted job(s)", count),
+        Err(e) => tracing::warn!("⚠️ [jobs] Failed to requeue interrupted jobs: {}", e),
+    }
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(FINISHED_JOB_RETENTION_DAYS);
+    if let Err(e) = state.db.prune_finished_jobs(cutoff).await {
+        tracing::warn!("⚠️ [jobs] Failed to prune finished jobs: {}", e);
+    }
+
+    for _ in 0..WORKER_COUNT {
+        tokio::spawn(worker(app.clone()));
+    }
+}
+
+async fn worker(app: tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    loop {
+        match state.db.claim_next_job().await {
+            Ok(Some(job)) => run_job(&app, &state, job).await,
+            Ok(None) => {
+                tokio::select! {
+                    _ = state.job_queue.wake.notified() => {}
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                }
+            }
+            Err(e) => {
+                tracing::error!("❌ [jobs] Failed to claim job: {}", e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+async fn run_job(app: &tauri::AppHandle, state: &AppState, job: Job) {
+    tracing::info!(
+        "📋 [jobs] Running {} job {} (attempt {}/{})",
+        job.kind.id(),
+        job.id,
+        job.attempts,
+        job.max_attempts
+    );
+
+    let result = match job.kind {
+        JobKind::SummaryRefresh => run_summary_refresh(app, state, &job).await,
+        JobKind::WebhookDelivery => run_webhook_delivery(state, &job).await,
+        JobKind::AnswerVerification => run_answer_verification(app, state, &job).await,
+        JobKind::AutomationRun => run_automation(app, state, &job).await,
+    };
+
+    let recorded = match result {
+        Ok(()) => state.db.complete_job(&job.id).await,
+        Err(e) if job.attempts < job.max_attempts => {
+            let delay = retry_delay(job.attempts);
+            tracing::warn!(
+                "⚠️ [jobs] {} job {} failed, retrying in {:?}: {}",
+                job.kind.id(),
+                job.id,
+                delay,
+                e
+            );
+            let retry_at = chrono::Utc::now()
+                + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::zero());
+            state.db.fail_job(&job.id, &e, Some(retry_at)).await
+        }
+        Err(e) => {
+            tracing::error!("❌ [jobs] {} job {} failed: {}", job.kind.id(), job.id, e);
+            state.db.fail_job(&job.id, &e, None).await
+        }
+    };
+    if let Err(e) = recorded {
+        tracing::error!("❌ [jobs] Failed to record result of job {}: {}", job.id, e);
+    }
+}
+
+async fn run_summary_refresh(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    job: &Job,
+) -> Result<(), String> {
+    let payload: SummaryRefreshPayload =
+        serde_json::from_value(job.payload.clone()).map_err(|e| e.to_string())?;
+    summary::refresh_summary_if_due(state, app, &payload.conversation_id).await
+}
+
+async fn run_answer_verification(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    job: &Job,
+) -> Result<(), String> {
+    let payload: AnswerVerificationPayload =
+        serde_json::from_value(job.payload.clone()).map_err(|e| e.to_string())?;
+    verification::verify_answer(
+        state,
+        app,
+        &payload.conversation_id,
+        &payload.message_id,
+        &payload.fetch_result_ids,
+    )
+    .await
+}
+
+async fn run_automation(app: &tauri::AppHandle, state: &AppState, job: &Job) -> Result<(), String> {
+    let payload: AutomationRunPayload =
+        serde_json::from_value(job.payload.clone()).map_err(|e| e.to_string())?;
+    automation::run_automation(state, app, &payload.automation_id, &payload.event).await
+}
+
+async fn run_webhook_delivery(state: &AppState, job: &Job) -> Result<(), String> {
+    let payload: WebhookDeliveryPayload =
+        serde_json::from_value(job.payload.clone()).map_err(|e| e.to_string())?;
+    let webhooks = state.db.list_webhooks().await.map_err(|e| e.to_string())?;
+    // Deleted or disabled since the event fired: nothing left to deliver
+    let Some(webhook) = webhooks
+        .into_iter()
+        .find(|w| w.id == payload.webhook_id && w.is_enabled)
+    else {
+        return Ok(());
+    };
+    webhooks::deliver(&webhook, payload.event, &payload.body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay(1), Duration::from_secs(10));
+        assert_eq!(retry_delay(2), Duration::from_secs(20));
+        assert_eq!(retry_delay(4), Duration::from_secs(80));
+        assert_eq!(retry_delay(30), RETRY_MAX_DELAY);
+    }
+}
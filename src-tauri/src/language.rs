@@ -0,0 +1,227 @@
+//! Lightweight detection of the language a message is written in
+//!
+//! Non-Latin scripts are recognized by their Unicode ranges (Han, kana, Hangul,
+//! Cyrillic, ...). Latin-script text is told apart by counting common function words
+//! of a few major languages; anything less certain is left undetected.
+
+use crate::models::REPLY_LANGUAGE_AUTO;
+
+/// Share of letters a non-Latin script needs before it decides the language, so a
+/// Chinese question quoting an English term is still Chinese
+const MIN_SCRIPT_SHARE: f64 = 0.2;
+
+/// Function-word hits a Latin-script language needs to be picked
+const MIN_WORD_HITS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Han,
+    Kana,
+    Hangul,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+}
+
+const SCRIPT_COUNT: usize = 10;
+
+fn script_of(c: char) -> Option<Script> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Script::Latin),
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' => {
+            Some(Script::Han)
+        }
+        '\u{3040}'..='\u{30FF}' => Some(Script::Kana),
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => Some(Script::Hangul),
+        '\u{0400}'..='\u{04FF}' => Some(Script::Cyrillic),
+        '\u{0370}'..='\u{03FF}' => Some(Script::Greek),
+        '\u{0600}'..='\u{06FF}' => Some(Script::Arabic),
+        '\u{0590}'..='\u{05FF}' => Some(Script::Hebrew),
+        '\u{0900}'..='\u{097F}' => Some(Script::Devanagari),
+        '\u{0E00}'..='\u{0E7F}' => Some(Script::Thai),
+        _ => None,
+    }
+}
+
+/// Common short words that rarely appear in the other listed languages
+const LATIN_LANGUAGES: &[(&str, &[&str])] = &[
+    (
+        "English",
+        &[
+            "the", "is", "are", "and", "what", "how", "why", "with", "this", "that", "you", "can",
+            "does",
+        ],
+    ),
+    (
+        "Spanish",
+        &[
+            "el", "los", "las", "qué", "cómo", "por", "para", "una", "está", "es", "y", "pero",
+        ],
+    ),
+    (
+        "French",
+        &[
+            "le", "les", "est", "et", "une", "des", "pour", "comment", "pourquoi", "avec", "je",
+            "vous",
+        ],
+    ),
+    (
+        "German",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "wie", "warum", "mit", "ein", "eine",
+        ],
+    ),
+    (
+        "Portuguese",
+        &[
+            "o", "os", "não", "uma", "como", "por", "para", "você", "é", "com", "isso", "também",
+        ],
+    ),
+    (
+        "Italian",
+        &[
+            "il", "gli", "è", "come", "perché", "non", "una", "sono", "della", "che", "questo",
+        ],
+    ),
+];
+
+/// English name of the language `text` is written in, or `None` when it can't be
+/// told with reasonable confidence
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let mut counts = [0usize; SCRIPT_COUNT];
+    let mut letters = 0usize;
+    for script in text.chars().filter_map(script_of) {
+        letters += 1;
+        counts[script as usize] += 1;
+    }
+    if letters == 0 {
+        return None;
+    }
+    let count = |script: Script| counts[script as usize];
+    let share = |n: usize| n as f64 / letters as f64;
+
+    // Kana only appears in Japanese, which mixes it with Han characters
+    if count(Script::Kana) > 0
+        && share(count(Script::Kana) + count(Script::Han)) >= MIN_SCRIPT_SHARE
+    {
+        return Some("Japanese");
+    }
+    let non_latin = [
+        (Script::Han, "Chinese"),
+        (Script::Hangul, "Korean"),
+        (Script::Cyrillic, "Russian"),
+        (Script::Greek, "Greek"),
+        (Script::Arabic, "Arabic"),
+        (Script::Hebrew, "Hebrew"),
+        (Script::Devanagari, "Hindi"),
+        (Script::Thai, "Thai"),
+    ];
+    if let Some((_, language)) = non_latin
+        .iter()
+        .filter(|(script, _)| share(count(*script)) >= MIN_SCRIPT_SHARE)
+        .max_by_key(|(script, _)| count(*script))
+    {
+        return Some(language);
+    }
+
+    detect_latin_language(text)
+}
+
+/// Language to reply in for a conversation's `reply_language` setting: the setting
+/// itself, or the detected language of `message` in auto mode
+pub fn resolve_reply_language(setting: &str, message: &str) -> Option<String> {
+    let setting = setting.trim();
+    if setting.is_empty() {
+        None
+    } else if setting.eq_ignore_ascii_case(REPLY_LANGUAGE_AUTO) {
+        detect_language(message).map(str::to_string)
+    } else {
+        Some(setting.to_string())
+    }
+}
+
+fn detect_latin_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut tied = false;
+    for (language, function_words) in LATIN_LANGUAGES {
+        let hits = words
+            .iter()
+            .filter(|w| function_words.contains(&w.as_str()))
+            .count();
+        match best {
+            Some((_, best_hits)) if hits == best_hits => tied = true,
+            Some((_, best_hits)) if hits < best_hits => {}
+            _ => {
+                best = Some((language, hits));
+                tied = false;
+            }
+        }
+    }
+
+    best.filter(|(_, hits)| *hits >= MIN_WORD_HITS && !tied)
+        .map(|(language, _)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_non_latin_scripts() {
+        assert_eq!(detect_language("北京今天的天气怎么样？"), Some("Chinese"));
+        assert_eq!(
+            detect_language("用 Rust 写一个 HTTP server 需要哪些 crate？"),
+            Some("Chinese")
+        );
+        assert_eq!(detect_language("東京の天気はどうですか"), Some("Japanese"));
+        assert_eq!(detect_language("오늘 날씨 어때요?"), Some("Korean"));
+        assert_eq!(detect_language("Как дела?"), Some("Russian"));
+    }
+
+    #[test]
+    fn test_detect_latin_languages() {
+        assert_eq!(
+            detect_language("What is the capital of Australia and why?"),
+            Some("English")
+        );
+        assert_eq!(
+            detect_language("Wie ist das Wetter und warum ist es kalt?"),
+            Some("German")
+        );
+        assert_eq!(
+            detect_language("Pourquoi le ciel est bleu et comment le mesurer ?"),
+            Some("French")
+        );
+    }
+
+    #[test]
+    fn test_resolve_reply_language() {
+        assert_eq!(
+            resolve_reply_language("auto", "这个函数有什么问题？"),
+            Some("Chinese".to_string())
+        );
+        assert_eq!(
+            resolve_reply_language(" Deutsch ", "What is this?"),
+            Some("Deutsch".to_string())
+        );
+        assert_eq!(resolve_reply_language("auto", "OK"), None);
+        assert_eq!(resolve_reply_language("", "Как дела?"), None);
+    }
+
+    #[test]
+    fn test_detect_uncertain() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("42 + 17"), None);
+        assert_eq!(detect_language("Kubernetes"), None);
+    }
+}
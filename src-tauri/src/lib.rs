@@ -1,19 +1,43 @@
+mod automations;
+mod code_chunking;
 pub mod commands;
+mod crash_report;
 mod crypto;
 pub mod db;
+mod deep_link;
+pub mod error;
+mod events;
+mod exporters;
+mod image_metadata;
+mod importers;
+mod ipc;
+mod jobs;
 mod keychain;
+mod language;
 mod llm;
 mod logger;
 pub mod mcp;
 pub mod models;
+mod network;
+mod notifications;
 mod prompts;
+mod quick_ask;
+mod repo_context;
 mod search;
+mod shutdown;
 pub mod skills;
 pub mod storage;
 mod thinking_parser;
+mod thumbnails;
+mod time_context;
 mod tokenizer;
+mod transcription;
+mod tray;
+mod unified_diff;
+mod updater;
 mod web_fetch;
 mod web_search;
+mod webhooks;
 
 use commands::AppState;
 use db::Database;
@@ -41,9 +65,17 @@ pub fn run() {
     }
 
     tauri::Builder::default()
+        // Must be registered first: forwards deep links from a second launch to this instance
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            tray::show_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(quick_ask::plugin())
         .setup(|app| {
             // Initialize app data directory
             let app_data_dir = app
@@ -62,6 +94,24 @@ pub fn run() {
 
             tracing::info!("Application starting");
 
+            if let Err(e) = crash_report::install(app_data_dir.join("crashes")) {
+                tracing::warn!("Failed to install crash reporter: {}", e);
+            }
+
+            web_fetch::set_chromium_dir(app_data_dir.join(web_fetch::CHROMIUM_DIR_NAME));
+            // Web search fails without Chrome; say so up front rather than on first use
+            tauri::async_runtime::spawn_blocking(|| {
+                let status = web_fetch::chrome_status();
+                if let Some(path) = status.path {
+                    tracing::info!("🌐 Headless Chrome: {}", path);
+                } else {
+                    tracing::warn!(
+                        "⚠️ Headless Chrome unavailable: {}",
+                        status.error.unwrap_or_default()
+                    );
+                }
+            });
+
             // Initialize storage directories
             if let Err(e) = storage::init_storage_dirs(app.handle()) {
                 tracing::warn!("Failed to initialize storage directories: {}", e);
@@ -117,6 +167,9 @@ pub fn run() {
                 }
             });
 
+            // Load user agent / Accept-Language overrides for web fetching
+            rt.block_on(web_fetch::reload_fetch_identity(&db));
+
             // Load bundled model capabilities data
             let capabilities_cache = {
                 let resource_path = app
@@ -137,13 +190,24 @@ pub fn run() {
                 })
             };
 
+            let job_queue = Arc::new(jobs::JobQueue::new(db.clone()));
             let app_state = AppState {
                 db,
                 generation_tasks: Arc::new(RwLock::new(HashMap::new())),
+                auxiliary_tasks: Arc::new(RwLock::new(HashMap::new())),
                 mcp_manager: Arc::new(McpConnectionManager::new()),
                 pending_oauth: Arc::new(RwLock::new(HashMap::new())),
                 bash_session_manager: Arc::new(BashSessionManager::new()),
                 capabilities_cache,
+                job_queue,
+                outbox: Arc::new(commands::chat::outbox::Outbox::default()),
+                request_log: Arc::new(commands::chat::request_debug::RequestLog::default()),
+                generation_pool: Arc::new(
+                    commands::chat::generation_pool::GenerationPool::default(),
+                ),
+                private_conversations: Arc::new(
+                    commands::chat::private::PrivateConversations::default(),
+                ),
             };
             // Grab handle before app_state is moved into managed state
             let manager_for_sweep = app_state.bash_session_manager.clone();
@@ -160,14 +224,38 @@ pub fn run() {
                 }
             });
 
+            tauri::async_runtime::spawn(jobs::start(app.handle().clone()));
+            tauri::async_runtime::spawn(automations::start(app.handle().clone()));
+            tauri::async_runtime::spawn(network::start(app.handle().clone()));
+            webhooks::register_listeners(app.handle());
+            notifications::init(app.handle());
+            updater::init(app.handle());
+            deep_link::init(app.handle());
+            if let Err(e) = tray::init(app.handle()) {
+                tracing::warn!("Failed to create tray icon: {}", e);
+            }
+            tauri::async_runtime::spawn(quick_ask::register_saved_shortcut(app.handle().clone()));
+
+            // Local socket for the CLI and editor plugins (opt-in via settings)
+            tauri::async_runtime::spawn(ipc::start_if_enabled(app.handle().clone()));
+
+            // Load local role models ahead of the first message (opt-in via settings)
+            tauri::async_runtime::spawn(commands::warm_up_on_startup(app.handle().clone()));
+
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if window.label() == "main" && matches!(event, tauri::WindowEvent::Focused(true)) {
+                notifications::on_main_window_focused(window.app_handle());
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // Provider commands
             commands::create_provider,
             commands::get_provider,
             commands::list_providers,
             commands::update_provider,
+            commands::set_provider_openrouter_routing,
             commands::delete_provider,
             // Model commands
             commands::create_model,
@@ -177,6 +265,13 @@ pub fn run() {
             commands::update_model,
             commands::delete_model,
             commands::soft_delete_model,
+            commands::list_deleted_models,
+            commands::restore_model,
+            commands::purge_deleted_models,
+            commands::set_model_hidden,
+            commands::set_model_openrouter_routing,
+            commands::set_model_default_preset,
+            commands::reorder_models,
             // Model Parameter Preset commands
             commands::list_model_parameter_presets,
             commands::get_model_parameter_preset,
@@ -184,6 +279,9 @@ pub fn run() {
             commands::create_model_parameter_preset,
             commands::update_model_parameter_preset,
             commands::delete_model_parameter_preset,
+            commands::duplicate_preset,
+            commands::export_model_parameter_presets,
+            commands::import_model_parameter_presets,
             // Assistant commands
             commands::create_assistant,
             commands::get_assistant,
@@ -209,15 +307,29 @@ pub fn run() {
             commands::list_conversations,
             commands::update_conversation,
             commands::delete_conversation,
+            commands::archive_conversation,
+            commands::unarchive_conversation,
             commands::fork_conversation,
+            commands::send_to_new_conversation,
             commands::chat::title::generate_conversation_title_manually,
+            commands::chat::summary::generate_conversation_summary,
+            commands::chat::translation::translate_message,
+            commands::chat::step_explanation::explain_step,
+            commands::chat::url_summary::summarize_url,
+            commands::chat::handoff::switch_assistant,
+            commands::chat::translation::delete_translation,
+            commands::chat::image_generation::generate_image,
             commands::add_conversation_participant,
             commands::list_conversation_participants,
             commands::get_conversation_participant_summary,
+            commands::update_conversation_participant,
+            commands::deactivate_participant,
             commands::remove_conversation_participant,
             // Conversation Settings commands
             commands::get_conversation_settings,
             commands::update_conversation_settings,
+            commands::set_conversation_parameter_overrides,
+            commands::clear_conversation_parameter_overrides,
             commands::reset_conversation_tools_to_global,
             commands::delete_conversation_settings,
             // Message commands
@@ -225,24 +337,41 @@ pub fn run() {
             commands::list_messages_by_conversation,
             commands::clear_messages_by_conversation,
             commands::delete_messages_from,
+            commands::edit_message,
+            commands::list_message_revisions,
+            commands::restore_revision,
+            commands::set_message_flag,
+            commands::clear_message_flag,
+            commands::get_message_flags,
+            commands::list_bookmarked_messages,
             commands::search_chat_history,
+            commands::search_in_conversation,
             // User Attachments (files)
             commands::get_message_attachments,
             commands::get_file_attachment,
+            commands::get_attachment_thumbnail,
+            commands::export_attachment,
+            commands::attach_repo_context,
             // Context Enrichments (search results, fetch results)
             commands::get_message_contexts,
             commands::get_search_result,
+            commands::get_search_result_items,
             commands::get_fetch_result,
             commands::get_fetch_results_by_source,
             commands::get_fetch_results_by_message,
+            commands::get_message_citations,
             // Process Steps (thinking, decisions, tool calls)
             commands::get_message_steps,
             commands::get_thinking_step,
             commands::get_search_decision,
+            commands::apply_patch,
             // Combined resources
             commands::get_message_resources,
             // Content reading
+            commands::unlink_message_context,
             commands::read_fetch_content,
+            commands::get_fetch_content_metadata,
+            commands::read_fetch_content_range,
             commands::read_file_content,
             commands::read_image_base64,
             commands::get_attachment_url,
@@ -254,7 +383,11 @@ pub fn run() {
             commands::get_setting,
             commands::set_setting,
             commands::get_all_settings,
+            commands::get_default_system_prompt,
+            commands::set_default_system_prompt,
+            commands::reset_default_system_prompt,
             commands::set_log_level,
+            commands::export_logs,
             // Crypto commands
             commands::generate_keypair,
             commands::export_keypair,
@@ -266,13 +399,31 @@ pub fn run() {
             commands::fetch_ollama_models,
             commands::fetch_provider_models,
             commands::check_provider_api,
+            commands::ping_model,
+            // Ollama model management
+            commands::ollama_pull_model,
+            commands::ollama_cancel_pull,
+            commands::ollama_delete_model,
+            commands::ollama_show_model,
+            commands::ollama_load_model,
+            commands::ollama_warm_up_models,
             // Chat commands
             commands::send_message,
             commands::stop_generation,
+            commands::chat::outbox::list_outbox,
+            commands::chat::outbox::get_network_status,
+            commands::chat::request_debug::get_last_request_debug,
+            commands::chat::private::create_private_conversation,
+            commands::chat::private::list_private_conversations,
+            commands::chat::private::close_private_conversation,
             // Web search commands
             commands::chat::web_search::perform_web_search,
+            commands::chat::web_search::force_search_query,
+            commands::chat::web_search::rerun_search,
             commands::chat::web_search::extract_search_keywords,
             commands::chat::web_search::get_search_providers,
+            commands::chat::web_search::get_search_provider_health,
+            commands::chat::web_search::fetch_search_result_item,
             // MCP commands
             commands::create_mcp_server,
             commands::list_mcp_servers,
@@ -308,17 +459,90 @@ pub fn run() {
             // Model capabilities commands
             commands::get_model_capabilities,
             commands::refresh_capabilities_cache,
+            // Model role commands
+            commands::get_model_roles,
+            commands::set_model_role,
+            commands::set_model_alias,
+            commands::resolve_model_reference,
+            // Usage commands
+            commands::get_usage_stats,
+            commands::set_conversation_budget,
+            commands::list_provider_policies,
+            commands::create_provider_policy,
+            commands::update_provider_policy,
+            commands::delete_provider_policy,
+            commands::set_conversation_provider_policy,
+            commands::list_workspaces,
+            commands::create_workspace,
+            commands::update_workspace,
+            commands::delete_workspace,
+            commands::set_conversation_workspace,
+            commands::list_conversation_templates,
+            commands::create_conversation_template,
+            commands::update_conversation_template,
+            commands::delete_conversation_template,
+            commands::create_conversation_from_template,
+            commands::get_conversation_budget_status,
+            commands::get_spend_report,
+            commands::set_monthly_spend_cap,
+            // Import commands
+            commands::detect_import_source,
+            commands::import_chat_data,
+            // Export commands
+            commands::export_conversation,
+            commands::export_messages,
+            commands::publish_conversation,
+            // Notification commands
+            commands::set_active_conversation,
+            // Quick Ask commands
+            commands::get_quick_ask_shortcut,
+            commands::set_quick_ask_shortcut,
+            commands::quick_ask,
+            commands::hide_quick_ask_window,
+            // Webhook commands
+            commands::list_webhooks,
+            commands::create_webhook,
+            commands::update_webhook,
+            commands::delete_webhook,
+            commands::test_webhook,
+            // Background job commands
+            commands::list_jobs,
+            // Automation commands
+            commands::list_automations,
+            commands::create_automation,
+            commands::update_automation,
+            commands::delete_automation,
+            commands::run_automation_now,
+            commands::create_drop_folder,
+            commands::list_automation_runs,
+            commands::get_automation_webhook_url,
+            // Diagnostics commands
+            commands::run_diagnostics,
+            commands::get_chrome_status,
+            commands::download_chromium,
+            commands::get_last_crash_report,
+            commands::dismiss_crash_report,
+            // Update commands
+            commands::check_for_update,
+            commands::download_update,
+            commands::install_and_restart,
         ])
         .build(tauri::generate_context!())
         .unwrap_or_else(|e| {
             tracing::error!("FATAL: Error while building tauri application: {}", e);
             std::process::exit(1);
         })
-        .run(move |app_handle, event| {
-            if let tauri::RunEvent::Exit = event {
-                tracing::info!("Application exiting, cleaning up bash sessions");
+        .run(move |app_handle, event| match event {
+            tauri::RunEvent::ExitRequested { api, .. } => {
+                shutdown::on_exit_requested(app_handle, &api);
+            }
+            tauri::RunEvent::Exit => {
+                // Fallback for anything graceful shutdown didn't finish
+                tracing::info!("Application exiting, cancelling LLM calls and cleaning up bash sessions");
                 let state: tauri::State<'_, AppState> = app_handle.state();
+                commands::chat::auxiliary::cancel_all_sync(&state);
                 state.bash_session_manager.kill_all_sync();
             }
+            _ => {}
         });
 }
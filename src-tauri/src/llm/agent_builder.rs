@@ -36,7 +36,8 @@ use crate::llm::{
     openrouter as openrouter_provider, perplexity as perplexity_provider,
     together as together_provider, xai as xai_provider,
 };
-use crate::models::ModelParameters;
+use crate::models::{ModelParameters, OpenRouterRouting};
+use crate::thinking_parser::ThinkingFormat;
 
 /// Configuration for building an agent.
 /// Combines system prompt with model parameters and tool registry.
@@ -88,6 +89,8 @@ pub struct AgentConfig {
     pub skill_tool: Option<SkillTool>,
     /// Project root directory for path security enforcement
     pub project_root: Option<PathBuf>,
+    /// OpenRouter provider routing, sent as the request's `provider` object
+    pub openrouter_routing: Option<OpenRouterRouting>,
 }
 
 impl AgentConfig {
@@ -227,6 +230,12 @@ impl AgentConfig {
         self
     }
 
+    /// Set OpenRouter provider routing preferences
+    pub fn with_openrouter_routing(mut self, routing: OpenRouterRouting) -> Self {
+        self.openrouter_routing = Some(routing);
+        self
+    }
+
     /// Set a shared bash session handle for conversation-level persistence
     pub fn with_bash_session(mut self, session: SharedBashSession) -> Self {
         self.bash_session = Some(session);
@@ -415,6 +424,20 @@ pub fn create_openrouter_agent(
         openrouter_config.model_params.additional_params = Some(params);
     }
 
+    // Routing preferences, unless the parameters already carry a `provider` object
+    if let Some(routing) = config.openrouter_routing.as_ref().filter(|r| !r.is_empty()) {
+        let mut params = openrouter_config
+            .model_params
+            .additional_params
+            .unwrap_or(serde_json::json!({}));
+        if let Some(obj) = params.as_object_mut()
+            && !obj.contains_key("provider")
+        {
+            obj.insert("provider".to_string(), serde_json::to_value(routing)?);
+        }
+        openrouter_config.model_params.additional_params = Some(params);
+    }
+
     Ok(build_agent(client.agent(model_id), &openrouter_config))
 }
 
@@ -424,6 +447,11 @@ pub fn create_ollama_agent(
     model_id: &str,
     config: &AgentConfig,
 ) -> Result<Agent<OllamaCompletionModel>> {
+    // Ollama takes most parameters in its `options` object
+    let mut ollama_config = config.clone();
+    ollama_config.model_params.additional_params =
+        ollama_provider::request_params(&config.model_params)?;
+
     let http_client = create_http_client();
     let client = ollama::Client::<reqwest::Client>::builder()
         .api_key(Nothing)
@@ -431,7 +459,7 @@ pub fn create_ollama_agent(
         .http_client(http_client)
         .build()?;
 
-    Ok(build_agent(client.agent(model_id), config))
+    Ok(build_agent(client.agent(model_id), &ollama_config))
 }
 
 /// Default max_tokens for Anthropic (required by the API, unlike OpenAI)
@@ -1247,6 +1275,7 @@ pub async fn stream_chat_with_agent(
     cancel_token: CancellationToken,
     callback: impl FnMut(String, StreamChunkType) -> bool + Send,
     log_prefix: &str,
+    thinking_formats: &[ThinkingFormat],
 ) -> Result<ChatResponse> {
     macro_rules! stream {
         ($agent:expr) => {
@@ -1257,6 +1286,7 @@ pub async fn stream_chat_with_agent(
                 cancel_token,
                 callback,
                 log_prefix,
+                thinking_formats,
             )
             .await
         };
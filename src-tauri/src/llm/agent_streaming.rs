@@ -2,6 +2,10 @@
 //!
 //! This module provides the streaming implementation for all agent types,
 //! handling cancellation, error recovery, and thinking content parsing.
+//!
+//! When the connection drops after text has arrived, the request is sent again with
+//! the partial reply as an assistant message and a request to continue it. The
+//! continuation streams through the same callback, so the caller sees one reply.
 
 use anyhow::Result;
 use futures::StreamExt;
@@ -12,8 +16,16 @@ use rig::streaming::{StreamedAssistantContent, StreamedUserContent, StreamingCha
 use tokio_util::sync::CancellationToken;
 
 use crate::llm::ChatResponse;
+use crate::llm::agent_builder::{build_assistant_message, build_user_message};
 use crate::llm::common::{StreamChunkType, ToolCallInfo, ToolResultInfo};
-use crate::thinking_parser;
+use crate::prompts;
+use crate::thinking_parser::{self, ThinkingFormat};
+
+/// Times a dropped stream is resumed before the partial reply is kept as is
+const MAX_RESUME_ATTEMPTS: u32 = 2;
+
+/// Shortest repeated text (in bytes) removed from the start of a continuation
+const MIN_OVERLAP_LEN: usize = 8;
 
 /// Strip internal error prefixes (e.g. "CompletionError: ProviderError: ") to
 /// produce a cleaner user-facing message.
@@ -31,6 +43,41 @@ fn strip_internal_prefixes(error: &str) -> String {
     s.to_string()
 }
 
+/// Whether a stream error means the connection dropped, rather than the provider
+/// rejecting the request
+fn is_transient_stream_error(error: &str) -> bool {
+    error.contains("decoding response body")
+        || error.contains("error reading a body")
+        || error.contains("connection")
+        || error.contains("stream")
+}
+
+/// History and prompt asking the model to continue `partial`, the visible text it had
+/// sent before the stream dropped
+fn resume_request(
+    prompt: &Message,
+    chat_history: &[Message],
+    partial: &str,
+) -> (Message, Vec<Message>) {
+    let mut history = chat_history.to_vec();
+    history.push(prompt.clone());
+    history.push(build_assistant_message(partial, None));
+    (
+        build_user_message(prompts::STREAM_RESUME_PROMPT, &[], &[]),
+        history,
+    )
+}
+
+/// Drop text at the start of a continuation that repeats the end of the partial reply
+fn strip_overlap<'a>(partial: &str, continuation: &'a str) -> &'a str {
+    let max = partial.len().min(continuation.len());
+    (MIN_OVERLAP_LEN..=max)
+        .rev()
+        .filter(|&len| partial.is_char_boundary(partial.len() - len))
+        .find(|&len| continuation.starts_with(&partial[partial.len() - len..]))
+        .map_or(continuation, |len| &continuation[len..])
+}
+
 /// Generic implementation for streaming with any agent type
 pub async fn stream_agent<M>(
     agent: Agent<M>,
@@ -39,6 +86,7 @@ pub async fn stream_agent<M>(
     cancel_token: CancellationToken,
     mut callback: impl FnMut(String, StreamChunkType) -> bool + Send,
     log_prefix: &str,
+    thinking_formats: &[ThinkingFormat],
 ) -> Result<ChatResponse>
 where
     M: CompletionModel + 'static,
@@ -47,7 +95,7 @@ where
     tracing::info!("🤖 [{}] Agent created, starting stream chat", log_prefix);
 
     let mut stream = agent
-        .stream_chat(prompt, chat_history)
+        .stream_chat(prompt.clone(), chat_history.clone())
         .multi_turn(100)
         .await;
 
@@ -57,6 +105,12 @@ where
     let mut consecutive_errors = 0;
     let mut is_reasoning = false;
     let mut last_error: Option<String> = None;
+    let mut usage_tokens: Option<(i64, i64)> = None;
+    let mut resume_attempts = 0;
+    // Tool rounds can't be replayed from the text alone, so only plain replies resume
+    let mut saw_tool_call = false;
+    // Set after resuming until the continuation's first text arrives
+    let mut check_overlap = false;
     const MAX_CONSECUTIVE_ERRORS: u32 = 3;
 
     tracing::info!("📥 [{}] Processing stream...", log_prefix);
@@ -89,7 +143,15 @@ where
                     is_reasoning = false;
                     tracing::info!("💡 [{}] Reasoning ended", log_prefix);
                 }
-                let text_str = &text.text;
+                let mut text_str = text.text.as_str();
+                if check_overlap && !text_str.is_empty() {
+                    check_overlap = false;
+                    let partial = thinking_parser::parse_thinking_content_with(
+                        &full_content,
+                        thinking_formats,
+                    );
+                    text_str = strip_overlap(&partial.content, text_str);
+                }
                 if !text_str.is_empty() {
                     full_content.push_str(text_str);
 
@@ -132,6 +194,7 @@ where
                 ..
             })) => {
                 consecutive_errors = 0;
+                saw_tool_call = true;
                 let tool_input = serde_json::to_string(&tool_call.function.arguments)
                     .unwrap_or_else(|_| "{}".to_string());
 
@@ -213,6 +276,7 @@ where
                         usage.input_tokens,
                         usage.output_tokens
                     );
+                    usage_tokens = Some((usage.input_tokens as i64, usage.output_tokens as i64));
                 }
             }
             Ok(MultiTurnStreamItem::StreamAssistantItem(
@@ -241,10 +305,39 @@ where
                 let error_str = e.to_string();
                 last_error = Some(strip_internal_prefixes(&error_str));
 
-                let is_decode_error = error_str.contains("decoding response body")
-                    || error_str.contains("error reading a body")
-                    || error_str.contains("connection")
-                    || error_str.contains("stream");
+                let is_decode_error = is_transient_stream_error(&error_str);
+
+                if is_decode_error
+                    && !full_content.is_empty()
+                    && !saw_tool_call
+                    && resume_attempts < MAX_RESUME_ATTEMPTS
+                {
+                    resume_attempts += 1;
+                    tracing::warn!(
+                        "🔁 [{}] Stream dropped after {} chars, resuming ({}/{}): {}",
+                        log_prefix,
+                        full_content.len(),
+                        resume_attempts,
+                        MAX_RESUME_ATTEMPTS,
+                        error_str
+                    );
+                    // Resume from the visible text; reasoning isn't sent back
+                    let partial = thinking_parser::parse_thinking_content_with(
+                        &full_content,
+                        thinking_formats,
+                    );
+                    let (resume_prompt, resume_history) =
+                        resume_request(&prompt, &chat_history, &partial.content);
+                    stream = agent
+                        .stream_chat(resume_prompt, resume_history)
+                        .multi_turn(100)
+                        .await;
+                    consecutive_errors = 0;
+                    last_error = None;
+                    is_reasoning = false;
+                    check_overlap = true;
+                    continue;
+                }
 
                 if is_decode_error && !full_content.is_empty() {
                     tracing::error!(
@@ -309,8 +402,8 @@ where
         return Err(anyhow::anyhow!("{}", clean_err));
     }
 
-    // Parse thinking content from XML tags in the text
-    let parsed = thinking_parser::parse_thinking_content(&full_content);
+    // Parse thinking content from the model family's reasoning delimiters
+    let parsed = thinking_parser::parse_thinking_content_with(&full_content, thinking_formats);
 
     // Combine API-provided reasoning with XML-parsed thinking content
     let final_thinking = if !full_reasoning.is_empty() {
@@ -330,6 +423,40 @@ where
     Ok(ChatResponse {
         content: parsed.content,
         thinking_content: final_thinking,
-        tokens: None,
+        tokens: usage_tokens.map(|(input, output)| input + output),
+        input_tokens: usage_tokens.map(|(input, _)| input),
+        output_tokens: usage_tokens.map(|(_, output)| output),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_overlap() {
+        let partial = "The quick brown fox jumps over";
+        assert_eq!(
+            strip_overlap(partial, "fox jumps over the lazy dog"),
+            " the lazy dog"
+        );
+        assert_eq!(strip_overlap(partial, " the lazy dog"), " the lazy dog");
+        // Short coincidental matches are kept
+        assert_eq!(strip_overlap(partial, "over and out"), "over and out");
+        // Multi-byte text is only split on character boundaries
+        assert_eq!(
+            strip_overlap("長い日本語のテキストです", "日本語のテキストです。続き"),
+            "。続き"
+        );
+    }
+
+    #[test]
+    fn test_is_transient_stream_error() {
+        assert!(is_transient_stream_error(
+            "error decoding response body: connection reset by peer"
+        ));
+        assert!(!is_transient_stream_error(
+            "invalid_api_key: Incorrect API key provided"
+        ));
+    }
+}
@@ -12,6 +12,10 @@ pub struct ModelCapabilities {
     pub supports_reasoning: Option<bool>,
     pub max_context_length: Option<i64>,
     pub max_output_length: Option<i64>,
+    /// Input price in USD per 1M tokens
+    pub input_price: Option<f64>,
+    /// Output price in USD per 1M tokens
+    pub output_price: Option<f64>,
 }
 
 /// Cache key: (provider_key, model_id)
@@ -42,6 +46,8 @@ struct RawModel {
     modalities: Option<RawModalities>,
     #[serde(default)]
     limit: Option<RawLimit>,
+    #[serde(default)]
+    cost: Option<RawCost>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -52,6 +58,13 @@ struct RawModalities {
     output: Vec<String>,
 }
 
+/// Prices in USD per 1M tokens
+#[derive(Debug, Deserialize)]
+struct RawCost {
+    input: Option<f64>,
+    output: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct RawLimit {
     context: Option<i64>,
@@ -180,6 +193,8 @@ fn convert_raw_model(raw: &RawModel) -> ModelCapabilities {
         supports_reasoning: raw.reasoning,
         max_context_length: raw.limit.as_ref().and_then(|l| l.context),
         max_output_length: raw.limit.as_ref().and_then(|l| l.output),
+        input_price: raw.cost.as_ref().and_then(|c| c.input),
+        output_price: raw.cost.as_ref().and_then(|c| c.output),
     }
 }
 
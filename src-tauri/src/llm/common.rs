@@ -150,6 +150,13 @@ pub fn format_model_display_name(model_id: &str) -> String {
     }
 }
 
+/// Strip a `data:<mime>;base64,` prefix, leaving the base64 payload
+fn strip_data_url(data: &str) -> &str {
+    data.strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .map_or(data, |(_, payload)| payload)
+}
+
 /// Build UserContent from text, optional images, and optional files
 pub fn build_user_content(
     text: &str,
@@ -176,10 +183,11 @@ pub fn build_user_content(
         contents.push(UserContent::Document(document));
     }
 
-    // Add image contents
+    // Add image contents. Providers like Ollama take the raw base64 payload
+    // (its `images` field) and reject data URLs.
     for img in images {
         let image = Image {
-            data: DocumentSourceKind::Base64(img.base64.clone()),
+            data: DocumentSourceKind::Base64(strip_data_url(&img.base64).to_string()),
             media_type: mime_to_image_media_type(&img.media_type),
             detail: Some(ImageDetail::Auto),
             additional_params: None,
@@ -198,6 +206,24 @@ pub fn build_user_content(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_build_user_content_uses_raw_base64_images() {
+        let images = vec![ImageData {
+            base64: "data:image/png;base64,iVBORw0KGgo=".to_string(),
+            media_type: "image/png".to_string(),
+        }];
+        let content = build_user_content("What is this?", &images, &[]);
+        let image = content
+            .iter()
+            .find_map(|c| match c {
+                UserContent::Image(image) => Some(image),
+                _ => None,
+            })
+            .expect("image content");
+        assert!(matches!(&image.data, DocumentSourceKind::Base64(b) if b == "iVBORw0KGgo="));
+        assert_eq!(strip_data_url("iVBORw0KGgo="), "iVBORw0KGgo=");
+    }
+
     #[test]
     fn test_mime_to_image_media_type() {
         assert_eq!(
@@ -0,0 +1,278 @@
+//! Image generation
+//!
+//! - OpenAI-compatible providers use `/images/generations` (DALL-E and gpt-image models).
+//! - OpenRouter image models are called through `/chat/completions` with image output
+//!   modality; images come back as data URLs on the assistant message.
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::llm::common::create_http_client;
+
+/// Upper bound on images per request
+pub const MAX_IMAGES_PER_REQUEST: u32 = 4;
+
+/// Options for an image generation request (all optional, provider defaults otherwise)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ImageGenerationOptions {
+    /// e.g. "1024x1024", "1536x1024", "auto"
+    #[serde(default)]
+    pub size: Option<String>,
+    /// e.g. "standard" / "hd" (DALL-E 3), "low" / "medium" / "high" (gpt-image)
+    #[serde(default)]
+    pub quality: Option<String>,
+    /// "vivid" / "natural" (DALL-E 3 only)
+    #[serde(default)]
+    pub style: Option<String>,
+    /// "transparent" / "opaque" / "auto" (gpt-image only)
+    #[serde(default)]
+    pub background: Option<String>,
+    /// Number of images (clamped to MAX_IMAGES_PER_REQUEST)
+    #[serde(default)]
+    pub n: Option<u32>,
+}
+
+/// A decoded generated image
+#[derive(Debug, Clone)]
+pub struct GeneratedImage {
+    pub bytes: Vec<u8>,
+    pub media_type: String,
+    /// Prompt as rewritten by the provider (DALL-E 3)
+    pub revised_prompt: Option<String>,
+}
+
+/// Generate images via an OpenAI-compatible `/images/generations` endpoint
+pub async fn generate_openai_images(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    prompt: &str,
+    options: &ImageGenerationOptions,
+) -> Result<Vec<GeneratedImage>> {
+    let mut body = serde_json::json!({
+        "model": model,
+        "prompt": prompt,
+        "n": options.n.unwrap_or(1).clamp(1, MAX_IMAGES_PER_REQUEST),
+    });
+    // gpt-image models always return base64 and reject `response_format`
+    if !model.starts_with("gpt-image") {
+        body["response_format"] = Value::from("b64_json");
+    }
+    for (key, value) in [
+        ("size", &options.size),
+        ("quality", &options.quality),
+        ("style", &options.style),
+        ("background", &options.background),
+    ] {
+        if let Some(v) = value {
+            body[key] = Value::from(v.as_str());
+        }
+    }
+
+    let response = post_json(
+        &format!("{}/images/generations", base_url.trim_end_matches('/')),
+        api_key,
+        &body,
+    )
+    .await?;
+
+    let mut images = Vec::new();
+    for item in response["data"].as_array().cloned().unwrap_or_default() {
+        let revised_prompt = item["revised_prompt"].as_str().map(|s| s.to_string());
+        let decoded = if let Some(b64) = item["b64_json"].as_str() {
+            decode_base64_image(b64, "image/png")
+        } else if let Some(url) = item["url"].as_str() {
+            download_image(url).await
+        } else {
+            continue;
+        };
+        match decoded {
+            Ok((bytes, media_type)) => images.push(GeneratedImage {
+                bytes,
+                media_type,
+                revised_prompt,
+            }),
+            Err(e) => tracing::warn!("⚠️ [image_generation] Skipping image: {}", e),
+        }
+    }
+
+    if images.is_empty() {
+        return Err(anyhow::anyhow!("Provider returned no images"));
+    }
+    Ok(images)
+}
+
+/// Generate images with an OpenRouter image model via chat completions
+pub async fn generate_openrouter_images(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    prompt: &str,
+    options: &ImageGenerationOptions,
+) -> Result<Vec<GeneratedImage>> {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": [{ "role": "user", "content": prompt }],
+        "modalities": ["image", "text"],
+    });
+    if let Some(ref size) = options.size
+        && let Some(ratio) = aspect_ratio_for_size(size)
+    {
+        body["image_config"] = serde_json::json!({ "aspect_ratio": ratio });
+    }
+
+    let response = post_json(
+        &format!("{}/chat/completions", base_url.trim_end_matches('/')),
+        api_key,
+        &body,
+    )
+    .await?;
+
+    let images: Vec<GeneratedImage> = extract_openrouter_image_urls(&response)
+        .iter()
+        .filter_map(|url| match parse_data_url(url) {
+            Some((media_type, b64)) => decode_base64_image(b64, media_type).ok(),
+            None => None,
+        })
+        .map(|(bytes, media_type)| GeneratedImage {
+            bytes,
+            media_type,
+            revised_prompt: None,
+        })
+        .collect();
+
+    if images.is_empty() {
+        return Err(anyhow::anyhow!("Model returned no images"));
+    }
+    Ok(images)
+}
+
+async fn post_json(url: &str, api_key: Option<&str>, body: &Value) -> Result<Value> {
+    let mut request = create_http_client().post(url).json(body);
+    if let Some(key) = api_key.filter(|k| !k.is_empty()) {
+        request = request.bearer_auth(key);
+    }
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Image generation failed: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+async fn download_image(url: &str) -> Result<(Vec<u8>, String)> {
+    let response = create_http_client().get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Failed to download generated image",
+            response.status().as_u16()
+        ));
+    }
+    let media_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("image/png")
+        .to_string();
+    Ok((response.bytes().await?.to_vec(), media_type))
+}
+
+fn decode_base64_image(b64: &str, media_type: &str) -> Result<(Vec<u8>, String)> {
+    let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, b64)?;
+    Ok((bytes, media_type.to_string()))
+}
+
+/// Split "data:image/png;base64,<data>" into (media type, base64 data)
+fn parse_data_url(url: &str) -> Option<(&str, &str)> {
+    let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+    let media_type = header.split(';').next().filter(|m| !m.is_empty())?;
+    Some((media_type, data))
+}
+
+/// Collect image URLs from `choices[].message.images[].image_url.url`
+fn extract_openrouter_image_urls(response: &Value) -> Vec<String> {
+    response["choices"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|choice| {
+            choice["message"]["images"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        })
+        .filter_map(|image| image["image_url"]["url"].as_str().map(|s| s.to_string()))
+        .collect()
+}
+
+/// Map an OpenAI-style size to the closest OpenRouter aspect ratio
+fn aspect_ratio_for_size(size: &str) -> Option<&'static str> {
+    let (w, h) = size.split_once('x')?;
+    let (w, h): (f64, f64) = (w.trim().parse().ok()?, h.trim().parse().ok()?);
+    if w <= 0.0 || h <= 0.0 {
+        return None;
+    }
+    let ratio = w / h;
+    let candidates = [
+        ("1:1", 1.0),
+        ("3:2", 1.5),
+        ("2:3", 2.0 / 3.0),
+        ("16:9", 16.0 / 9.0),
+        ("9:16", 9.0 / 16.0),
+        ("4:3", 4.0 / 3.0),
+        ("3:4", 0.75),
+    ];
+    candidates
+        .iter()
+        .min_by(|a, b| (a.1 - ratio).abs().total_cmp(&(b.1 - ratio).abs()))
+        .map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_data_url() {
+        assert_eq!(
+            parse_data_url("data:image/webp;base64,AAAA"),
+            Some(("image/webp", "AAAA"))
+        );
+        assert_eq!(parse_data_url("https://example.com/a.png"), None);
+    }
+
+    #[test]
+    fn test_extract_openrouter_image_urls() {
+        let response = serde_json::json!({
+            "choices": [{
+                "message": {
+                    "content": "Here you go",
+                    "images": [
+                        { "type": "image_url", "image_url": { "url": "data:image/png;base64,AAAA" } }
+                    ]
+                }
+            }]
+        });
+        assert_eq!(
+            extract_openrouter_image_urls(&response),
+            vec!["data:image/png;base64,AAAA"]
+        );
+        assert!(extract_openrouter_image_urls(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_aspect_ratio_for_size() {
+        assert_eq!(aspect_ratio_for_size("1024x1024"), Some("1:1"));
+        assert_eq!(aspect_ratio_for_size("1536x1024"), Some("3:2"));
+        assert_eq!(aspect_ratio_for_size("1792x1024"), Some("16:9"));
+        assert_eq!(aspect_ratio_for_size("auto"), None);
+    }
+}
@@ -10,6 +10,7 @@ pub mod galadriel;
 pub mod gemini;
 pub mod groq;
 pub mod hyperbolic;
+pub mod image_generation;
 pub mod minimax;
 pub mod minimax_cn;
 pub mod mira;
@@ -101,11 +102,18 @@ pub struct ChatResponse {
     pub content: String,
     pub thinking_content: Option<String>,
     pub tokens: Option<i64>,
+    /// Prompt tokens reported by the provider (aggregated across tool-call turns)
+    #[serde(default)]
+    pub input_tokens: Option<i64>,
+    /// Completion tokens reported by the provider (aggregated across tool-call turns)
+    #[serde(default)]
+    pub output_tokens: Option<i64>,
 }
 
 /// Unified function to call any LLM provider (non-streaming)
 /// Uses the agent-based approach for consistency across the codebase.
 /// This eliminates code duplication across different features (title generation, search decision, etc.)
+/// Returns an error once `cancel_token` is cancelled, since partial output is unusable here.
 pub async fn call_provider(
     provider: &str,
     model: String,
@@ -113,6 +121,7 @@ pub async fn call_provider(
     api_key: Option<String>,
     base_url: Option<String>,
     api_style: Option<String>,
+    cancel_token: CancellationToken,
 ) -> Result<ChatResponse> {
     // Extract system prompt if present
     let system_prompt = messages
@@ -181,18 +190,23 @@ pub async fn call_provider(
     let prompt =
         current_prompt.ok_or_else(|| anyhow::anyhow!("No user message found in request"))?;
 
-    // Create a no-op cancel token (not really cancellable for non-streaming)
-    let cancel_token = CancellationToken::new();
-
     // Use stream_chat_with_agent with a no-op callback
     // This collects the full response
-    stream_chat_with_agent(
+    let response = stream_chat_with_agent(
         agent,
         prompt,
         chat_history,
-        cancel_token,
+        cancel_token.clone(),
         |_, _| true,
         provider,
+        crate::thinking_parser::formats_for_model(&model),
     )
-    .await
+    .await?;
+
+    // A partial response is useless to callers that parse the full content
+    if cancel_token.is_cancelled() {
+        return Err(anyhow::anyhow!("Request cancelled"));
+    }
+
+    Ok(response)
 }
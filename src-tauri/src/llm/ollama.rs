@@ -1,6 +1,533 @@
-//! Ollama provider constants
+//! Ollama provider constants and local model management
 //!
-//! The actual client creation and streaming is handled by agent_builder.rs
+//! The actual client creation and streaming is handled by agent_builder.rs.
+//! This module wraps Ollama's model management endpoints (pull/delete/show) and
+//! model loading, which keeps local models in memory between requests.
+
+use anyhow::Result;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::llm::common::create_http_client;
+use crate::models::ModelParameters;
 
 /// Default Ollama API base URL
 pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// Settings key: how long Ollama keeps a model loaded after a request ("30m", "-1", ...).
+/// Unset leaves it to the server (5 minutes by default).
+pub const KEEP_ALIVE_SETTING_KEY: &str = "ollama_keep_alive";
+
+/// Settings key: load the chat and fast role models at startup when "true"
+pub const WARM_UP_ON_STARTUP_SETTING_KEY: &str = "ollama_warm_up_on_startup";
+
+/// One progress line from `/api/pull` (streamed as NDJSON)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PullProgress {
+    pub status: String,
+    #[serde(default)]
+    pub digest: Option<String>,
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub completed: Option<u64>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Model details returned by `/api/show`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OllamaModelDetails {
+    #[serde(default)]
+    pub modelfile: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<String>,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub details: Option<serde_json::Value>,
+    #[serde(default)]
+    pub model_info: Option<serde_json::Value>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub modified_at: Option<String>,
+}
+
+impl OllamaModelDetails {
+    /// Whether the model accepts images, from the `capabilities` list.
+    /// `None` on Ollama versions that don't report capabilities.
+    pub fn supports_vision(&self) -> Option<bool> {
+        if self.capabilities.is_empty() {
+            None
+        } else {
+            Some(self.capabilities.iter().any(|c| c == "vision"))
+        }
+    }
+}
+
+/// Build an Ollama API URL from a base URL (defaults to the local server)
+pub fn api_url(base_url: Option<&str>, path: &str) -> String {
+    let base = base_url
+        .filter(|b| !b.trim().is_empty())
+        .unwrap_or(DEFAULT_BASE_URL)
+        .trim_end_matches('/');
+    // Users sometimes configure the OpenAI-compatible endpoint (".../v1")
+    let base = base.strip_suffix("/v1").unwrap_or(base);
+    format!("{}/api/{}", base, path.trim_start_matches('/'))
+}
+
+/// Pull (download) a model, reporting each progress line to `on_progress`.
+/// Returning `false` from the callback aborts the pull.
+pub async fn pull_model(
+    base_url: Option<&str>,
+    model: &str,
+    mut on_progress: impl FnMut(&PullProgress) -> bool,
+) -> Result<()> {
+    let client = create_http_client();
+    let response = client
+        .post(api_url(base_url, "pull"))
+        .json(&serde_json::json!({ "model": model, "stream": true }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Failed to pull Ollama model: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    let mut stream = response.bytes_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        buffer.extend_from_slice(&chunk?);
+
+        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let progress: PullProgress = match serde_json::from_str(line) {
+                Ok(p) => p,
+                Err(e) => {
+                    tracing::warn!("⚠️ [ollama] Unparseable pull progress line: {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(ref err) = progress.error {
+                return Err(anyhow::anyhow!("Ollama pull failed: {}", err));
+            }
+            if !on_progress(&progress) {
+                return Err(anyhow::anyhow!("Pull cancelled"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Delete a local model
+pub async fn delete_model(base_url: Option<&str>, model: &str) -> Result<()> {
+    let client = create_http_client();
+    let response = client
+        .delete(api_url(base_url, "delete"))
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Failed to delete Ollama model: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    Ok(())
+}
+
+/// Show details (modelfile, parameters, template, capabilities) for a local model
+pub async fn show_model(base_url: Option<&str>, model: &str) -> Result<OllamaModelDetails> {
+    let client = create_http_client();
+    let response = client
+        .post(api_url(base_url, "show"))
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Failed to show Ollama model: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+/// Load a model into memory without generating anything, so the next request doesn't
+/// wait for it. Resolves once the model is loaded.
+pub async fn load_model(
+    base_url: Option<&str>,
+    model: &str,
+    keep_alive: Option<&str>,
+) -> Result<()> {
+    let mut body = serde_json::json!({ "model": model });
+    if let Some(keep_alive) = keep_alive {
+        body["keep_alive"] = serde_json::Value::String(keep_alive.to_string());
+    }
+
+    let client = create_http_client();
+    let response = client
+        .post(api_url(base_url, "generate"))
+        .json(&body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Failed to load Ollama model: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    Ok(())
+}
+
+/// Normalize a `keep_alive` setting into the duration string Ollama expects.
+/// Bare numbers are seconds ("300" -> "300s"); negative values keep the model loaded
+/// indefinitely. `None` for empty or malformed values.
+pub fn normalize_keep_alive(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if value.parse::<i64>().is_ok() {
+        return Some(format!("{}s", value));
+    }
+
+    let unit_start = value.find(|c: char| c.is_ascii_alphabetic())?;
+    let (amount, unit) = value.split_at(unit_start);
+    let valid_amount = amount.parse::<f64>().is_ok_and(f64::is_finite);
+    let valid_unit = matches!(unit, "ms" | "s" | "m" | "h");
+    (valid_amount && valid_unit).then(|| value.to_string())
+}
+
+/// Value type of a known Ollama model option
+#[derive(Debug, Clone, Copy)]
+enum OptionKind {
+    Int,
+    UnsignedInt,
+    Float,
+    Bool,
+    /// 0 (off), 1 (Mirostat) or 2 (Mirostat 2.0)
+    Mirostat,
+    StringList,
+}
+
+/// Model options Ollama accepts in the request's `options` object
+const KNOWN_OPTIONS: &[(&str, OptionKind)] = &[
+    ("num_ctx", OptionKind::UnsignedInt),
+    ("num_gpu", OptionKind::Int),
+    ("main_gpu", OptionKind::UnsignedInt),
+    ("num_thread", OptionKind::UnsignedInt),
+    ("num_batch", OptionKind::UnsignedInt),
+    ("num_keep", OptionKind::Int),
+    ("num_predict", OptionKind::Int),
+    ("use_mmap", OptionKind::Bool),
+    ("mirostat", OptionKind::Mirostat),
+    ("mirostat_eta", OptionKind::Float),
+    ("mirostat_tau", OptionKind::Float),
+    ("repeat_penalty", OptionKind::Float),
+    ("repeat_last_n", OptionKind::Int),
+    ("presence_penalty", OptionKind::Float),
+    ("frequency_penalty", OptionKind::Float),
+    ("top_k", OptionKind::UnsignedInt),
+    ("top_p", OptionKind::Float),
+    ("min_p", OptionKind::Float),
+    ("typical_p", OptionKind::Float),
+    ("seed", OptionKind::Int),
+    ("stop", OptionKind::StringList),
+];
+
+fn check_option(key: &str, value: &serde_json::Value) -> Result<()> {
+    let Some((_, kind)) = KNOWN_OPTIONS.iter().find(|(name, _)| *name == key) else {
+        tracing::warn!("⚠️ [ollama] Passing unknown option '{}' through", key);
+        return Ok(());
+    };
+    let (valid, expected) = match kind {
+        OptionKind::Int => (value.is_i64(), "an integer"),
+        OptionKind::UnsignedInt => (value.is_u64(), "a non-negative integer"),
+        OptionKind::Float => (value.is_number(), "a number"),
+        OptionKind::Bool => (value.is_boolean(), "a boolean"),
+        OptionKind::Mirostat => (value.as_u64().is_some_and(|v| v <= 2), "0, 1 or 2"),
+        OptionKind::StringList => (
+            value
+                .as_array()
+                .is_some_and(|items| items.iter().all(|v| v.is_string())),
+            "a list of strings",
+        ),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid Ollama option `{}`: expected {}, got {}",
+            key,
+            expected,
+            value
+        ))
+    }
+}
+
+/// Build the `additional_params` sent to Ollama from the resolved model parameters.
+///
+/// Ollama reads sampling and runtime settings from the request's `options` object, so
+/// `top_p`, the penalties and `max_tokens` (as `num_predict`) are mapped there, and
+/// `additional_params` may hold options either flat or under `options`. Known options
+/// are type-checked; `keep_alive` and `think` stay top-level request fields.
+pub fn request_params(params: &ModelParameters) -> Result<Option<serde_json::Value>> {
+    use serde_json::{Map, Value};
+
+    let mut out = Map::new();
+    if let Some(top_p) = params.top_p {
+        out.insert("top_p".to_string(), top_p.into());
+    }
+    if let Some(penalty) = params.frequency_penalty {
+        out.insert("frequency_penalty".to_string(), penalty.into());
+    }
+    if let Some(penalty) = params.presence_penalty {
+        out.insert("presence_penalty".to_string(), penalty.into());
+    }
+    if let Some(max_tokens) = params.max_tokens {
+        out.insert("num_predict".to_string(), max_tokens.into());
+    }
+
+    let additional = match &params.additional_params {
+        None | Some(Value::Null) => Map::new(),
+        Some(Value::Object(obj)) => obj.clone(),
+        Some(other) => {
+            return Err(anyhow::anyhow!(
+                "Ollama parameters must be a JSON object, got {}",
+                other
+            ));
+        }
+    };
+
+    for (key, value) in additional {
+        match key.as_str() {
+            "options" => {
+                let Value::Object(options) = value else {
+                    return Err(anyhow::anyhow!("Ollama `options` must be a JSON object"));
+                };
+                for (key, value) in options {
+                    check_option(&key, &value)?;
+                    out.insert(key, value);
+                }
+            }
+            "keep_alive" => {
+                let keep_alive = match &value {
+                    Value::String(s) => normalize_keep_alive(s),
+                    Value::Number(n) => n
+                        .as_i64()
+                        .and_then(|s| normalize_keep_alive(&s.to_string())),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid Ollama `keep_alive`: expected a duration like \"30m\", got {}",
+                        value
+                    )
+                })?;
+                out.insert(key, Value::String(keep_alive));
+            }
+            "think" => {
+                if !value.is_boolean() {
+                    return Err(anyhow::anyhow!("Ollama `think` must be a boolean"));
+                }
+                out.insert(key, value);
+            }
+            _ => {
+                check_option(&key, &value)?;
+                out.insert(key, value);
+            }
+        }
+    }
+
+    Ok((!out.is_empty()).then_some(Value::Object(out)))
+}
+
+/// Add `keep_alive` to request parameters unless they already set one
+pub fn apply_keep_alive(params: &mut ModelParameters, keep_alive: &str) {
+    let additional = params
+        .additional_params
+        .get_or_insert_with(|| serde_json::json!({}));
+    if let Some(obj) = additional.as_object_mut() {
+        obj.entry("keep_alive")
+            .or_insert_with(|| serde_json::Value::String(keep_alive.to_string()));
+    }
+}
+
+/// Ask the Ollama server whether a model accepts images.
+/// `None` when the server can't tell (unreachable, or too old to report capabilities).
+pub async fn model_supports_vision(base_url: Option<&str>, model: &str) -> Option<bool> {
+    match show_model(base_url, model).await {
+        Ok(details) => details.supports_vision(),
+        Err(e) => {
+            tracing::warn!(
+                "⚠️ [ollama] Failed to read capabilities of '{}': {}",
+                model,
+                e
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_url() {
+        assert_eq!(api_url(None, "pull"), "http://localhost:11434/api/pull");
+        assert_eq!(
+            api_url(Some("http://host:11434/"), "show"),
+            "http://host:11434/api/show"
+        );
+        assert_eq!(
+            api_url(Some("http://host:11434/v1"), "delete"),
+            "http://host:11434/api/delete"
+        );
+        assert_eq!(
+            api_url(Some("  "), "pull"),
+            "http://localhost:11434/api/pull"
+        );
+    }
+
+    #[test]
+    fn test_pull_progress_deserialization() {
+        let progress: PullProgress = serde_json::from_str(
+            r#"{"status":"downloading","digest":"sha256:abc","total":100,"completed":40}"#,
+        )
+        .unwrap();
+        assert_eq!(progress.status, "downloading");
+        assert_eq!(progress.total, Some(100));
+        assert_eq!(progress.completed, Some(40));
+
+        let done: PullProgress = serde_json::from_str(r#"{"status":"success"}"#).unwrap();
+        assert!(done.digest.is_none());
+    }
+
+    #[test]
+    fn test_normalize_keep_alive() {
+        assert_eq!(normalize_keep_alive("30m").as_deref(), Some("30m"));
+        assert_eq!(normalize_keep_alive(" 1.5h ").as_deref(), Some("1.5h"));
+        assert_eq!(normalize_keep_alive("300").as_deref(), Some("300s"));
+        assert_eq!(normalize_keep_alive("-1").as_deref(), Some("-1s"));
+        assert_eq!(normalize_keep_alive(""), None);
+        assert_eq!(normalize_keep_alive("forever"), None);
+        assert_eq!(normalize_keep_alive("10d"), None);
+    }
+
+    #[test]
+    fn test_apply_keep_alive_keeps_explicit_value() {
+        let mut params = ModelParameters::default();
+        apply_keep_alive(&mut params, "30m");
+        assert_eq!(
+            params.additional_params.as_ref().unwrap()["keep_alive"],
+            "30m"
+        );
+
+        let mut params = ModelParameters {
+            additional_params: Some(serde_json::json!({ "keep_alive": "-1m", "num_ctx": 8192 })),
+            ..Default::default()
+        };
+        apply_keep_alive(&mut params, "30m");
+        let additional = params.additional_params.unwrap();
+        assert_eq!(additional["keep_alive"], "-1m");
+        assert_eq!(additional["num_ctx"], 8192);
+    }
+
+    #[test]
+    fn test_request_params_maps_into_options() {
+        let params = ModelParameters {
+            top_p: Some(0.9),
+            max_tokens: Some(512),
+            additional_params: Some(serde_json::json!({
+                "num_ctx": 16384,
+                "options": { "mirostat": 2, "repeat_penalty": 1.1 },
+                "keep_alive": 600,
+            })),
+            ..Default::default()
+        };
+        let out = request_params(&params).unwrap().unwrap();
+        assert_eq!(out["top_p"], 0.9);
+        assert_eq!(out["num_predict"], 512);
+        assert_eq!(out["num_ctx"], 16384);
+        assert_eq!(out["mirostat"], 2);
+        assert_eq!(out["repeat_penalty"], 1.1);
+        assert_eq!(out["keep_alive"], "600s");
+        assert!(out.get("options").is_none());
+
+        assert!(
+            request_params(&ModelParameters::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_request_params_rejects_invalid_options() {
+        for additional in [
+            serde_json::json!({ "num_ctx": "big" }),
+            serde_json::json!({ "mirostat": 3 }),
+            serde_json::json!({ "options": { "num_gpu": 1.5 } }),
+            serde_json::json!({ "keep_alive": "soon" }),
+            serde_json::json!({ "stop": "###" }),
+            serde_json::json!([1, 2]),
+        ] {
+            let params = ModelParameters {
+                additional_params: Some(additional.clone()),
+                ..Default::default()
+            };
+            assert!(
+                request_params(&params).is_err(),
+                "{} was accepted",
+                additional
+            );
+        }
+    }
+
+    #[test]
+    fn test_supports_vision() {
+        let details: OllamaModelDetails =
+            serde_json::from_str(r#"{"capabilities":["completion","vision"]}"#).unwrap();
+        assert_eq!(details.supports_vision(), Some(true));
+
+        let details: OllamaModelDetails =
+            serde_json::from_str(r#"{"capabilities":["completion","tools"]}"#).unwrap();
+        assert_eq!(details.supports_vision(), Some(false));
+
+        assert_eq!(OllamaModelDetails::default().supports_vision(), None);
+    }
+}
@@ -116,7 +116,6 @@ impl BashSessionManager {
     }
 
     /// Kill all sessions (async). Waits for each process to exit.
-    #[allow(dead_code)]
     pub(crate) async fn kill_all(&self) {
         let all: Vec<(String, SharedBashSession)> = {
             let mut map = self.sessions.lock().unwrap();
@@ -4,7 +4,7 @@
 //! `old_string` to find and a `new_string` to replace it with. Supports
 //! `replace_all` for renaming variables across a file.
 
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
@@ -68,7 +68,7 @@ impl Tool for EditTool {
                 "properties": {
                     "path": {
                         "type": "string",
-                        "description": "Absolute path to the file to edit"
+                        "description": "Absolute path to the file to edit (relative paths resolve against the working directory, when set)"
                     },
                     "old_string": {
                         "type": "string",
@@ -105,7 +105,8 @@ impl Tool for EditTool {
             ));
         }
 
-        let path = Path::new(&args.path);
+        let resolved = path_policy::resolve(&args.path, self.project_root.as_deref());
+        let path = resolved.as_path();
 
         path_policy::check_write(path, self.project_root.as_deref()).map_err(|e| EditError(e))?;
 
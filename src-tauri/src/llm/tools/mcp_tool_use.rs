@@ -182,16 +182,13 @@ impl Tool for McpToolUseTool {
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
         let key = format!("{}/{}", args.server, args.tool);
 
-        // Security: scan arguments for STDIO servers
+        // STDIO servers get their arguments scanned
         let transport = self
             .transport_types
             .get(&key)
             .copied()
             .unwrap_or(McpTransportType::Http);
 
-        scan_mcp_arguments(&args.tool, &args.arguments, transport)
-            .map_err(McpToolUseError::Blocked)?;
-
         let clients = self.clients.read().await;
         let (_server_name, client) = clients
             .get(&key)
@@ -203,78 +200,91 @@ impl Tool for McpToolUseTool {
             args.server
         );
 
-        let arguments = args.arguments.as_object().cloned().unwrap_or_default();
-
-        let result = match client
-            .call_tool(CallToolRequestParams {
-                name: args.tool.clone().into(),
-                arguments: Some(arguments),
-                meta: None,
-                task: None,
-            })
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                return Err(McpToolUseError::CallFailed(format!(
-                    "Tool returned an error: {e}"
-                )));
-            }
-        };
+        call_mcp_tool(client, &args.tool, &args.arguments, transport).await
+    }
+}
 
-        if let Some(true) = result.is_error {
-            let error_msg: String = result
-                .content
-                .into_iter()
-                .filter_map(|c| match c.raw {
-                    RawContent::Text(raw) => Some(raw.text.to_string()),
-                    _ => None,
-                })
-                .collect::<Vec<_>>()
-                .join("\n");
-
-            let msg = if error_msg.is_empty() {
-                "No error message returned".to_string()
-            } else {
-                error_msg
-            };
-            return Err(McpToolUseError::CallFailed(msg));
+/// Call a tool on a connected MCP server and flatten its result to text. Arguments
+/// for STDIO servers are scanned first.
+pub(crate) async fn call_mcp_tool(
+    client: &Peer<RoleClient>,
+    tool: &str,
+    arguments: &serde_json::Value,
+    transport: McpTransportType,
+) -> Result<String, McpToolUseError> {
+    scan_mcp_arguments(tool, arguments, transport).map_err(McpToolUseError::Blocked)?;
+
+    let arguments = arguments.as_object().cloned().unwrap_or_default();
+
+    let result = match client
+        .call_tool(CallToolRequestParams {
+            name: tool.to_string().into(),
+            arguments: Some(arguments),
+            meta: None,
+            task: None,
+        })
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            return Err(McpToolUseError::CallFailed(format!(
+                "Tool returned an error: {e}"
+            )));
         }
+    };
 
-        Ok(result
+    if let Some(true) = result.is_error {
+        let error_msg: String = result
             .content
             .into_iter()
-            .map(|c| match c.raw {
-                RawContent::Text(raw) => raw.text.to_string(),
-                RawContent::Image(raw) => {
-                    format!("data:{};base64,{}", raw.mime_type, raw.data)
-                }
-                RawContent::Resource(raw) => match raw.resource {
-                    rmcp::model::ResourceContents::TextResourceContents {
-                        uri,
-                        mime_type,
-                        text,
-                        ..
-                    } => {
-                        format!(
-                            "{mime_type}{uri}:{text}",
-                            mime_type = mime_type.map(|m| format!("data:{m};")).unwrap_or_default(),
-                        )
-                    }
-                    rmcp::model::ResourceContents::BlobResourceContents {
-                        uri,
-                        mime_type,
-                        blob,
-                        ..
-                    } => format!(
-                        "{mime_type}{uri}:{blob}",
-                        mime_type = mime_type.map(|m| format!("data:{m};")).unwrap_or_default(),
-                    ),
-                },
-                _ => String::new(),
+            .filter_map(|c| match c.raw {
+                RawContent::Text(raw) => Some(raw.text.to_string()),
+                _ => None,
             })
-            .collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let msg = if error_msg.is_empty() {
+            "No error message returned".to_string()
+        } else {
+            error_msg
+        };
+        return Err(McpToolUseError::CallFailed(msg));
     }
+
+    Ok(result
+        .content
+        .into_iter()
+        .map(|c| match c.raw {
+            RawContent::Text(raw) => raw.text.to_string(),
+            RawContent::Image(raw) => {
+                format!("data:{};base64,{}", raw.mime_type, raw.data)
+            }
+            RawContent::Resource(raw) => match raw.resource {
+                rmcp::model::ResourceContents::TextResourceContents {
+                    uri,
+                    mime_type,
+                    text,
+                    ..
+                } => {
+                    format!(
+                        "{mime_type}{uri}:{text}",
+                        mime_type = mime_type.map(|m| format!("data:{m};")).unwrap_or_default(),
+                    )
+                }
+                rmcp::model::ResourceContents::BlobResourceContents {
+                    uri,
+                    mime_type,
+                    blob,
+                    ..
+                } => format!(
+                    "{mime_type}{uri}:{blob}",
+                    mime_type = mime_type.map(|m| format!("data:{m};")).unwrap_or_default(),
+                ),
+            },
+            _ => String::new(),
+        })
+        .collect::<String>())
 }
 
 #[cfg(test)]
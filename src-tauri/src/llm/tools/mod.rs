@@ -52,6 +52,7 @@ pub use grep::GrepTool;
 pub use kill_shell::KillShellTool;
 pub use mcp_schema::{McpSchemaTool, McpServerCatalog};
 pub use mcp_tool_use::McpToolUseTool;
+pub(crate) use mcp_tool_use::call_mcp_tool;
 pub use read::ReadTool;
 pub use skill::{SkillCatalogEntry, SkillTool};
 pub use web_fetch::WebFetchTool;
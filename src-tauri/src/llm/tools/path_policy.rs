@@ -185,6 +185,16 @@ pub fn check_write(path: &Path, project_root: Option<&Path>) -> Result<(), Strin
     Ok(())
 }
 
+/// Resolve a path given to a tool. `~` expands to the home directory, and relative
+/// paths resolve against the conversation's working directory when it has one.
+pub fn resolve(path: &str, working_directory: Option<&Path>) -> PathBuf {
+    let expanded = expand_tilde(path.trim());
+    match working_directory {
+        Some(dir) if expanded.is_relative() => dir.join(expanded),
+        _ => expanded,
+    }
+}
+
 /// Heuristic: does the string look like a filesystem path?
 ///
 /// Used by MCP argument scanning to decide whether to run path checks on a
@@ -470,6 +480,29 @@ mod tests {
         );
     }
 
+    // ---- resolve ------------------------------------------------------------
+
+    #[test]
+    fn resolve_relative_against_working_directory() {
+        let root = Path::new("/home/user/project");
+        assert_eq!(
+            resolve("src/main.rs", Some(root)),
+            PathBuf::from("/home/user/project/src/main.rs")
+        );
+        assert_eq!(
+            resolve("/tmp/out.txt", Some(root)),
+            PathBuf::from("/tmp/out.txt")
+        );
+        assert_eq!(resolve("src/main.rs", None), PathBuf::from("src/main.rs"));
+    }
+
+    #[test]
+    fn resolved_escape_is_still_outside_project() {
+        let root = Path::new("/home/user/project");
+        let escaped = resolve("../other/file.txt", Some(root));
+        assert!(check_write(&escaped, Some(root)).is_err());
+    }
+
     // ---- macOS-specific ---------------------------------------------------
 
     #[test]
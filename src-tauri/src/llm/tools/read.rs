@@ -210,7 +210,7 @@ impl Tool for ReadTool {
                 "properties": {
                     "path": {
                         "type": "string",
-                        "description": "Absolute path to the file to read"
+                        "description": "Absolute path to the file to read (relative paths resolve against the working directory, when set)"
                     },
                     "offset": {
                         "type": "number",
@@ -234,7 +234,8 @@ impl Tool for ReadTool {
             args.limit
         );
 
-        let path = std::path::Path::new(&args.path);
+        let resolved = path_policy::resolve(&args.path, self.project_root.as_deref());
+        let path = resolved.as_path();
 
         path_policy::check_read(path, self.project_root.as_deref()).map_err(|e| ReadError(e))?;
         if !path.exists() {
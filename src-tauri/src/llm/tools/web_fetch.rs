@@ -73,6 +73,7 @@ impl WebFetchTool {
                 mode: FetchMode::Local,
                 local_method: LocalMethod::Auto,
                 jina_api_key: None,
+                byte_budget: None,
             },
         }
     }
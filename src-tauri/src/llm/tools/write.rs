@@ -3,7 +3,7 @@
 //! Creates new files or overwrites existing ones with provided content.
 //! Automatically creates parent directories as needed.
 
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use rig::{completion::ToolDefinition, tool::Tool};
 use serde::{Deserialize, Serialize};
@@ -62,7 +62,7 @@ impl Tool for WriteTool {
                 "properties": {
                     "path": {
                         "type": "string",
-                        "description": "Absolute path to the file to write"
+                        "description": "Absolute path to the file to write (relative paths resolve against the working directory, when set)"
                     },
                     "content": {
                         "type": "string",
@@ -81,7 +81,8 @@ impl Tool for WriteTool {
             args.content.len()
         );
 
-        let path = Path::new(&args.path);
+        let resolved = path_policy::resolve(&args.path, self.project_root.as_deref());
+        let path = resolved.as_path();
 
         path_policy::check_write(path, self.project_root.as_deref()).map_err(|e| WriteError(e))?;
 
@@ -1,5 +1,8 @@
 use anyhow::Result;
-use std::path::PathBuf;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
@@ -15,11 +18,45 @@ type ReloadHandle = reload::Handle<EnvFilter, Registry>;
 
 static LOG_HANDLE: once_cell::sync::OnceCell<Arc<ReloadHandle>> = once_cell::sync::OnceCell::new();
 
+static LOG_DIR: once_cell::sync::OnceCell<PathBuf> = once_cell::sync::OnceCell::new();
+
+const LOG_FILE_PREFIX: &str = "chatshell-backend";
+
+/// Written by the frontend logger (`chatshell-frontend-<date>.log`)
+const FRONTEND_LOG_FILE_PREFIX: &str = "chatshell-frontend";
+
+/// Daily log files kept on disk; older ones are deleted on rotation
+const MAX_LOG_FILES: usize = 14;
+
+/// Daily log files included in an export
+const DEFAULT_EXPORT_DAYS: usize = 3;
+
+lazy_static! {
+    /// Secrets that may end up in logs (request dumps, provider errors)
+    static ref SECRET_PATTERNS: Vec<(Regex, &'static str)> = vec![
+        // Authorization headers
+        (Regex::new(r"(?i)(bearer\s+)[A-Za-z0-9._~+/=-]{8,}").unwrap(), "${1}[REDACTED]"),
+        // key=value / "key": "value" pairs
+        (
+            Regex::new(r#"(?i)((?:api[_-]?key|access[_-]?token|refresh[_-]?token|secret|password|x-api-key)["']?\s*[:=]\s*["']?)[^\s"',}&]+"#).unwrap(),
+            "${1}[REDACTED]",
+        ),
+        // Provider key formats (OpenAI/Anthropic, Groq, xAI, Google, OpenRouter)
+        (Regex::new(r"\b(?:sk|gsk|xai)-[A-Za-z0-9_-]{16,}").unwrap(), "[REDACTED]"),
+        (Regex::new(r"\bgsk_[A-Za-z0-9]{16,}").unwrap(), "[REDACTED]"),
+        (Regex::new(r"\bAIza[0-9A-Za-z_-]{30,}").unwrap(), "[REDACTED]"),
+    ];
+}
+
 pub fn init_logger(log_dir: PathBuf) -> Result<()> {
     std::fs::create_dir_all(&log_dir)?;
 
-    let file_appender =
-        RollingFileAppender::new(Rotation::DAILY, log_dir.clone(), "chatshell-backend");
+    let file_appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)?;
+    let _ = LOG_DIR.set(log_dir.clone());
 
     let console_layer = fmt::layer()
         .with_target(true)
@@ -51,6 +88,7 @@ pub fn init_logger(log_dir: PathBuf) -> Result<()> {
         .with(filter)
         .with(console_layer)
         .with(file_layer)
+        .with(crate::crash_report::BreadcrumbLayer)
         .init();
 
     tracing::info!("Logger initialized successfully");
@@ -87,3 +125,90 @@ pub async fn load_log_level_from_db(db: &crate::db::Database) -> Result<String>
         .unwrap_or_else(|| "info".to_string());
     Ok(level)
 }
+
+/// Mask API keys, tokens and passwords in a log line
+pub fn redact_secrets(text: &str) -> String {
+    SECRET_PATTERNS
+        .iter()
+        .fold(text.to_string(), |text, (pattern, replacement)| {
+            pattern.replace_all(&text, *replacement).into_owned()
+        })
+}
+
+/// Zip the most recent `days` backend and frontend log files, with secrets redacted,
+/// into `destination`. A `.zip` extension is added when missing. Returns the path written.
+pub fn export_logs(destination: &Path, days: Option<usize>) -> Result<PathBuf> {
+    let log_dir = LOG_DIR
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("Logger not initialized"))?;
+    let keep = days.unwrap_or(DEFAULT_EXPORT_DAYS).max(1);
+
+    let names: Vec<String> = std::fs::read_dir(log_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    let mut recent = Vec::new();
+    for prefix in [LOG_FILE_PREFIX, FRONTEND_LOG_FILE_PREFIX] {
+        let mut matching: Vec<&String> = names.iter().filter(|n| n.starts_with(prefix)).collect();
+        // Date-suffixed names sort chronologically
+        matching.sort();
+        recent.extend(matching.into_iter().rev().take(keep));
+    }
+
+    let destination = if destination.extension().is_some() {
+        destination.to_path_buf()
+    } else {
+        destination.with_extension("zip")
+    };
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&destination)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for name in &recent {
+        let content = std::fs::read(log_dir.join(name))?;
+        let entry_name = if name.ends_with(".log") {
+            name.to_string()
+        } else {
+            format!("{}.log", name)
+        };
+        zip.start_file(entry_name, options)?;
+        zip.write_all(redact_secrets(&String::from_utf8_lossy(&content)).as_bytes())?;
+    }
+    zip.finish()?;
+
+    tracing::info!(
+        "📤 [logger] Exported {} log file(s) to {:?}",
+        recent.len(),
+        destination
+    );
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        let line =
+            r#"request headers: {"Authorization": "Bearer abcdef123456789", "api_key": "hunter2"}"#;
+        let redacted = redact_secrets(line);
+        assert!(!redacted.contains("abcdef123456789"));
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("Bearer [REDACTED]"));
+
+        let redacted = redact_secrets("key sk-proj-abcdefghijklmnopqrstuvwx rejected");
+        assert_eq!(redacted, "key [REDACTED] rejected");
+
+        let redacted =
+            redact_secrets("url https://x.test/?key=AIzaSyA1234567890abcdefghijklmnopqrst");
+        assert!(!redacted.contains("AIzaSy"));
+    }
+
+    #[test]
+    fn test_redact_keeps_plain_text() {
+        let line = "INFO chatshell: Database initialized successfully";
+        assert_eq!(redact_secrets(line), line);
+    }
+}
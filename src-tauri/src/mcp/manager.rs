@@ -12,6 +12,7 @@ use rmcp::transport::streamable_http_client::{
 use rmcp::{RoleClient, ServiceExt};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
 use crate::models::{McpAuthType, McpConfig, McpTransportType, Tool};
@@ -426,6 +427,27 @@ impl McpConnectionManager {
         tracing::info!("🔌 Disconnected from {} MCP server(s)", count);
     }
 
+    /// Close every connection on app exit, giving stdio servers a moment to exit
+    pub async fn shutdown(&self, timeout: Duration) {
+        let connections: Vec<McpServerConnection> = {
+            let mut connections = self.connections.write().await;
+            connections.drain().map(|(_, conn)| conn).collect()
+        };
+        let count = connections.len();
+        for conn in connections {
+            match Arc::try_unwrap(conn._running_service) {
+                Ok(mut service) => {
+                    if let Err(e) = service.close_with_timeout(timeout).await {
+                        tracing::warn!("⚠️ Failed to close MCP server {}: {}", conn.tool.name, e);
+                    }
+                }
+                // Still used by a generation that is finishing; stop its transport task
+                Err(service) => service.cancellation_token().cancel(),
+            }
+        }
+        tracing::info!("🔌 Closed {} MCP server connection(s)", count);
+    }
+
     /// Get all active connections
     pub async fn get_active_connections(&self) -> Vec<McpServerConnection> {
         let connections = self.connections.read().await;
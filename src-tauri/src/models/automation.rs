@@ -0,0 +1,324 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+use crate::exporters::ExportFormat;
+
+/// Where an automation gathers material before each run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationSourceKind {
+    /// A page fetched like a URL in a message
+    Url,
+    /// An RSS or Atom feed; its new entries are listed in the prompt
+    Rss,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutomationSource {
+    pub kind: AutomationSourceKind,
+    pub url: String,
+}
+
+/// What starts an automation (stored as JSON)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationTrigger {
+    /// Every day at a local time, "HH:MM"
+    Schedule { run_at: String },
+    /// A file appears in a folder (subfolders are not watched)
+    FolderWatch { path: String },
+    /// A request to the local webhook server at `/hooks/<token>`. The token is always
+    /// generated by the app; one given in the input is ignored.
+    Webhook {
+        #[serde(default)]
+        token: String,
+    },
+    /// Any conversation is archived
+    ConversationArchived,
+}
+
+/// What an automation does when triggered (stored as JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationAction {
+    /// Send a prompt into a conversation; the reply is notified when it finishes
+    RunPrompt {
+        prompt: String,
+        #[serde(default)]
+        sources: Vec<AutomationSource>,
+        /// Assistant that replies; `None` uses the conversation's model
+        #[serde(default)]
+        assistant_id: Option<String>,
+        /// Conversation the replies go to; created on the first run when unset
+        #[serde(default)]
+        conversation_id: Option<String>,
+    },
+    /// Write a conversation to a file in `directory`. Without a conversation, the
+    /// one that was archived is exported.
+    ExportConversation {
+        #[serde(default)]
+        conversation_id: Option<String>,
+        format: ExportFormat,
+        directory: String,
+    },
+    /// Call a tool of an MCP server. `{{input}}` in string arguments is replaced
+    /// with the trigger's input (see [`AutomationEvent::input`]).
+    CallMcpTool {
+        server_id: String,
+        tool_name: String,
+        #[serde(default)]
+        arguments: serde_json::Value,
+    },
+}
+
+/// A trigger rule and the action it runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Automation {
+    pub id: String,
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub action: AutomationAction,
+    pub is_enabled: bool,
+    /// Last successful run
+    pub last_run_at: Option<String>,
+    /// Scheduled triggers only; unset while disabled
+    pub next_run_at: Option<String>,
+    /// Why the latest run failed; cleared by the next successful one
+    pub last_error: Option<String>,
+    /// Trigger bookkeeping, e.g. the files a folder watch has seen
+    #[serde(skip)]
+    pub trigger_state: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutomationInput {
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub action: AutomationAction,
+    #[serde(default = "default_enabled")]
+    pub is_enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AutomationInput {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Automation name is required".to_string());
+        }
+        match &self.trigger {
+            AutomationTrigger::Schedule { run_at } => {
+                if parse_run_time(run_at).is_none() {
+                    return Err(format!("Invalid time \"{}\", expected HH:MM", run_at));
+                }
+            }
+            AutomationTrigger::FolderWatch { path } => {
+                if !std::path::Path::new(path.trim()).is_absolute() {
+                    return Err("Choose the folder to watch".to_string());
+                }
+            }
+            AutomationTrigger::Webhook { .. } | AutomationTrigger::ConversationArchived => {}
+        }
+        match &self.action {
+            AutomationAction::RunPrompt {
+                prompt, sources, ..
+            } => {
+                if prompt.trim().is_empty() {
+                    return Err("Automation prompt is required".to_string());
+                }
+                for source in sources {
+                    match url::Url::parse(source.url.trim()) {
+                        Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                        _ => return Err(format!("Invalid source URL: {}", source.url)),
+                    }
+                }
+            }
+            AutomationAction::ExportConversation {
+                conversation_id,
+                directory,
+                ..
+            } => {
+                if !std::path::Path::new(directory.trim()).is_absolute() {
+                    return Err("Choose the folder to export to".to_string());
+                }
+                let has_conversation = conversation_id.as_deref().is_some_and(|id| !id.is_empty());
+                if !has_conversation && self.trigger != AutomationTrigger::ConversationArchived {
+                    return Err("Choose the conversation to export".to_string());
+                }
+            }
+            AutomationAction::CallMcpTool {
+                server_id,
+                tool_name,
+                arguments,
+            } => {
+                if server_id.trim().is_empty() || tool_name.trim().is_empty() {
+                    return Err("Choose the MCP server and tool to call".to_string());
+                }
+                if !(arguments.is_object() || arguments.is_null()) {
+                    return Err("Tool arguments must be a JSON object".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a time of day in "HH:MM" form
+pub fn parse_run_time(run_at: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(run_at.trim(), "%H:%M").ok()
+}
+
+/// What started a run, passed along to the action
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationEvent {
+    #[default]
+    Schedule,
+    /// Started by hand, whatever the trigger
+    Manual,
+    FileAdded {
+        path: String,
+    },
+    Webhook {
+        body: String,
+    },
+    ConversationArchived {
+        conversation_id: String,
+    },
+}
+
+impl AutomationEvent {
+    /// Text an MCP tool action receives as `{{input}}`: the file path, the webhook
+    /// body or the archived conversation's id
+    pub fn input(&self) -> &str {
+        match self {
+            AutomationEvent::Schedule | AutomationEvent::Manual => "",
+            AutomationEvent::FileAdded { path } => path,
+            AutomationEvent::Webhook { body } => body,
+            AutomationEvent::ConversationArchived { conversation_id } => conversation_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl AutomationRunStatus {
+    pub fn id(&self) -> &'static str {
+        match self {
+            AutomationRunStatus::Running => "running",
+            AutomationRunStatus::Succeeded => "succeeded",
+            AutomationRunStatus::Failed => "failed",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "running" => Some(AutomationRunStatus::Running),
+            "succeeded" => Some(AutomationRunStatus::Succeeded),
+            "failed" => Some(AutomationRunStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One entry of an automation's run history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationRun {
+    pub id: String,
+    pub automation_id: String,
+    pub event: AutomationEvent,
+    pub status: AutomationRunStatus,
+    /// What the action produced: the conversation posted to, the file written or the
+    /// tool's result
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_automation_input_validate() {
+        let mut input = AutomationInput {
+            name: "Morning digest".to_string(),
+            trigger: AutomationTrigger::Schedule {
+                run_at: "07:30".to_string(),
+            },
+            action: AutomationAction::RunPrompt {
+                prompt: "Summarize the news".to_string(),
+                sources: vec![AutomationSource {
+                    kind: AutomationSourceKind::Rss,
+                    url: "https://example.com/feed.xml".to_string(),
+                }],
+                assistant_id: None,
+                conversation_id: None,
+            },
+            is_enabled: true,
+        };
+        assert!(input.validate().is_ok());
+        input.trigger = AutomationTrigger::Schedule {
+            run_at: "7.30am".to_string(),
+        };
+        assert!(input.validate().is_err());
+        input.trigger = AutomationTrigger::Schedule {
+            run_at: "23:59".to_string(),
+        };
+        if let AutomationAction::RunPrompt { sources, .. } = &mut input.action {
+            sources[0].url = "file:///etc/hosts".to_string();
+        }
+        assert!(input.validate().is_err());
+
+        // Exports need a conversation unless one is being archived
+        input.action = AutomationAction::ExportConversation {
+            conversation_id: None,
+            format: ExportFormat::Markdown,
+            directory: if cfg!(windows) {
+                "C:\\Exports"
+            } else {
+                "/exports"
+            }
+            .to_string(),
+        };
+        assert!(input.validate().is_err());
+        input.trigger = AutomationTrigger::ConversationArchived;
+        assert!(input.validate().is_ok());
+    }
+
+    #[test]
+    fn test_automation_json_shape() {
+        let trigger: AutomationTrigger = serde_json::from_str(r#"{"type":"webhook"}"#).unwrap();
+        assert_eq!(
+            trigger,
+            AutomationTrigger::Webhook {
+                token: String::new()
+            }
+        );
+        let action: AutomationAction = serde_json::from_str(
+            r#"{"type":"call_mcp_tool","server_id":"s1","tool_name":"notify"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            action,
+            AutomationAction::CallMcpTool {
+                arguments: serde_json::Value::Null,
+                ..
+            }
+        ));
+        // Jobs queued before events existed
+        let event: AutomationEvent = serde_json::from_str(r#"{"type":"schedule"}"#).unwrap();
+        assert_eq!(event, AutomationEvent::Schedule);
+    }
+}
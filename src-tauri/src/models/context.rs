@@ -29,6 +29,37 @@ pub struct CreateSearchResultRequest {
     pub searched_at: String,
 }
 
+/// One entry of a search's result list, in engine order (rank 1 first)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResultItem {
+    pub id: String,
+    pub search_result_id: String,
+    pub rank: i32,
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+    /// Whether the page was fetched for this search
+    pub fetched: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSearchResultItemRequest {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// A `[number]` marker in an assistant message and the fetched page it cites
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageCitation {
+    pub message_id: String,
+    pub number: i32,
+    pub fetch_result_id: String,
+    pub url: String,
+    pub title: Option<String>,
+}
+
 /// Fetch result - stores metadata about a fetched web resource
 /// Content is stored in filesystem at storage_path
 /// source_type distinguishes between search-initiated and user-provided URL fetches
@@ -51,6 +82,7 @@ pub struct FetchResult {
     pub original_size: Option<i64>,
     pub processed_size: Option<i64>,
     pub favicon_url: Option<String>,
+    pub favicon_path: Option<String>, // Cached favicon, relative to attachments dir: "favicons/{hash}.ico"
     pub content_hash: Option<String>, // Blake3 hash of stored content for deduplication
     pub created_at: String,
     pub updated_at: String,
@@ -73,6 +105,7 @@ pub struct CreateFetchResultRequest {
     pub original_size: Option<i64>,
     pub processed_size: Option<i64>,
     pub favicon_url: Option<String>,
+    pub favicon_path: Option<String>,
     pub content_hash: Option<String>,
 }
 
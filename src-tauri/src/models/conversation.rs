@@ -5,10 +5,55 @@ use sqlx::FromRow;
 pub struct Conversation {
     pub id: String,
     pub title: String,
+    /// Short LLM-generated summary shown in the sidebar
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Message count when the summary was last generated (drives auto-refresh)
+    #[serde(default)]
+    pub summary_message_count: i64,
     pub created_at: String,
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_message: Option<String>,
+    /// Kept in memory only (see `commands::chat::private`); never true for stored rows
+    #[serde(default)]
+    #[sqlx(default)]
+    pub is_private: bool,
+    #[serde(default)]
+    #[sqlx(skip)]
+    pub metadata: Option<ConversationMetadata>,
+    /// Workspace whose defaults the conversation inherits
+    #[serde(default)]
+    #[sqlx(default)]
+    pub workspace_id: Option<String>,
+    /// Set while archived; archived conversations are still listed
+    #[serde(default)]
+    #[sqlx(default)]
+    pub archived_at: Option<String>,
+}
+
+/// Where a conversation came from (stored as JSON)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConversationMetadata {
+    /// Conversation the opening message was taken from
+    pub origin_conversation_id: Option<String>,
+    pub origin_message_id: Option<String>,
+}
+
+/// Longest title derived from message text, in characters
+const MAX_DERIVED_TITLE_CHARS: usize = 60;
+
+/// Title for a conversation started from a message: its first non-empty line,
+/// shortened with an ellipsis
+pub fn title_from_content(content: &str) -> Option<String> {
+    let line = content.lines().map(str::trim).find(|l| !l.is_empty())?;
+    if line.chars().count() <= MAX_DERIVED_TITLE_CHARS {
+        Some(line.to_string())
+    } else {
+        let title: String = line.chars().take(MAX_DERIVED_TITLE_CHARS - 1).collect();
+        Some(format!("{}…", title.trim_end()))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,7 +73,59 @@ pub struct ConversationParticipant {
     pub joined_at: String,
     pub left_at: Option<String>,
     pub last_read_at: Option<String>,
-    pub metadata: Option<String>,
+    pub metadata: Option<ParticipantMetadata>,
+}
+
+/// Per-conversation display settings for a participant (stored as JSON)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParticipantMetadata {
+    /// Label color as a hex string (`#rgb` or `#rrggbb`)
+    pub color: Option<String>,
+    /// Short name shown on the participant's messages
+    pub nickname: Option<String>,
+}
+
+impl ParticipantMetadata {
+    /// Whether `color` is a `#rgb` or `#rrggbb` hex color
+    pub fn has_valid_color(&self) -> bool {
+        self.color.as_deref().is_none_or(|c| {
+            c.strip_prefix('#').is_some_and(|hex| {
+                matches!(hex.len(), 3 | 6) && hex.chars().all(|ch| ch.is_ascii_hexdigit())
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantRole {
+    Owner,
+    Admin,
+    Member,
+    Observer,
+}
+
+impl From<ParticipantRole> for String {
+    fn from(role: ParticipantRole) -> Self {
+        match role {
+            ParticipantRole::Owner => "owner",
+            ParticipantRole::Admin => "admin",
+            ParticipantRole::Member => "member",
+            ParticipantRole::Observer => "observer",
+        }
+        .to_string()
+    }
+}
+
+/// Fields left as `None` keep their current value; an empty display name clears it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateConversationParticipantRequest {
+    pub display_name: Option<String>,
+    pub role: Option<ParticipantRole>,
+    /// Replaces the stored metadata as a whole
+    pub metadata: Option<ParticipantMetadata>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,4 +147,41 @@ pub struct ParticipantSummary {
     pub avatar_text: Option<String>,
     pub avatar_image_path: Option<String>,
     pub avatar_image_url: Option<String>,
+    /// Label color from the participant's metadata
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub nickname: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_participant_metadata_color_validation() {
+        let with_color = |c: &str| ParticipantMetadata {
+            color: Some(c.to_string()),
+            nickname: None,
+        };
+        assert!(ParticipantMetadata::default().has_valid_color());
+        assert!(with_color("#3b82f6").has_valid_color());
+        assert!(with_color("#F0A").has_valid_color());
+        assert!(!with_color("3b82f6").has_valid_color());
+        assert!(!with_color("#12345").has_valid_color());
+        assert!(!with_color("#zzzzzz").has_valid_color());
+    }
+
+    #[test]
+    fn test_title_from_content() {
+        assert_eq!(
+            title_from_content("\n  Compare the two plans\nin detail"),
+            Some("Compare the two plans".to_string())
+        );
+        let long = "word ".repeat(30);
+        let title = title_from_content(&long).unwrap();
+        assert_eq!(title.chars().count(), MAX_DERIVED_TITLE_CHARS);
+        assert!(title.ends_with('…'));
+        assert_eq!(title_from_content("  \n "), None);
+    }
 }
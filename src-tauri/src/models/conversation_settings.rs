@@ -33,6 +33,51 @@ impl From<PromptMode> for String {
     }
 }
 
+/// How much conversation history is sent with each request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryMode {
+    /// Every earlier message
+    #[default]
+    All,
+    /// The most recent messages that fit in `context_budget_percent` of the model's
+    /// context window
+    TokenBudget,
+    /// The conversation summary in place of the messages it covers, then the newer messages
+    Summarized,
+    /// Only the current message
+    None,
+}
+
+impl From<&str> for HistoryMode {
+    fn from(s: &str) -> Self {
+        match s {
+            // `last_n` was the message-count limit this mode replaced
+            "token_budget" | "last_n" => Self::TokenBudget,
+            "summarized" => Self::Summarized,
+            "none" => Self::None,
+            _ => Self::All,
+        }
+    }
+}
+
+impl From<HistoryMode> for String {
+    fn from(mode: HistoryMode) -> Self {
+        match mode {
+            HistoryMode::All => "all".to_string(),
+            HistoryMode::TokenBudget => "token_budget".to_string(),
+            HistoryMode::Summarized => "summarized".to_string(),
+            HistoryMode::None => "none".to_string(),
+        }
+    }
+}
+
+/// Share of the context window used in `token_budget` mode when none is set
+pub const DEFAULT_CONTEXT_BUDGET_PERCENT: i32 = 50;
+
+/// `reply_language` value that follows the language of each user message
+pub const REPLY_LANGUAGE_AUTO: &str = "auto";
+
 /// Custom deserializer for `Option<Option<T>>` fields in update requests.
 ///
 /// By default, serde treats JSON `null` as `None` for the outer Option (= "field not provided"),
@@ -64,6 +109,26 @@ pub struct ModelParameterOverrides {
     pub presence_penalty: Option<f64>,
 }
 
+impl ModelParameterOverrides {
+    /// Check each set parameter against the range providers accept
+    pub fn validate(&self) -> Result<(), String> {
+        let in_range = |name: &str, value: Option<f64>, min: f64, max: f64| match value {
+            Some(v) if !(min..=max).contains(&v) => {
+                Err(format!("{} must be between {} and {}", name, min, max))
+            }
+            _ => Ok(()),
+        };
+        in_range("temperature", self.temperature, 0.0, 2.0)?;
+        in_range("top_p", self.top_p, 0.0, 1.0)?;
+        in_range("frequency_penalty", self.frequency_penalty, -2.0, 2.0)?;
+        in_range("presence_penalty", self.presence_penalty, -2.0, 2.0)?;
+        if self.max_tokens.is_some_and(|t| t < 1) {
+            return Err("max_tokens must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
 /// Conversation-level settings that override assistant defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationSettings {
@@ -78,8 +143,31 @@ pub struct ConversationSettings {
     /// Custom parameter overrides
     pub parameter_overrides: ModelParameterOverrides,
 
-    /// Max number of context messages to include (null = unlimited)
-    pub context_message_count: Option<i32>,
+    /// History strategy for requests in this conversation
+    #[serde(default)]
+    pub history_mode: HistoryMode,
+
+    /// Percentage (1-100) of the model's context window the request may fill in
+    /// `token_budget` history mode (null = [`DEFAULT_CONTEXT_BUDGET_PERCENT`])
+    #[serde(default)]
+    pub context_budget_percent: Option<i32>,
+
+    /// Cumulative token limit (input + output) for the conversation (null = unlimited)
+    #[serde(default)]
+    pub token_budget: Option<i64>,
+
+    /// Cumulative cost limit in USD for the conversation (null = unlimited)
+    #[serde(default)]
+    pub cost_budget: Option<f64>,
+
+    /// Provider policy restricting which providers this conversation may use
+    #[serde(default)]
+    pub provider_policy_id: Option<String>,
+
+    /// Language replies should be written in: a language name, or
+    /// [`REPLY_LANGUAGE_AUTO`] to match the language of each user message
+    #[serde(default)]
+    pub reply_language: Option<String>,
 
     /// Selected preset ID for UI display
     pub selected_preset_id: Option<String>,
@@ -113,6 +201,18 @@ pub struct ConversationSettings {
     /// Working directory for bash tool (overrides default home directory)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_directory: Option<String>,
+
+    /// Model last used in this conversation (fallback when send_message omits provider/model)
+    #[serde(default)]
+    pub selected_model_id: Option<String>,
+
+    /// Assistant last used in this conversation (takes precedence over selected_model_id)
+    #[serde(default)]
+    pub selected_assistant_id: Option<String>,
+
+    /// Fields whose value comes from the conversation's workspace (read-only)
+    #[serde(default)]
+    pub inherited: Vec<String>,
 }
 
 impl ConversationSettings {
@@ -123,7 +223,12 @@ impl ConversationSettings {
             use_provider_defaults: true,
             use_custom_parameters: false,
             parameter_overrides: ModelParameterOverrides::default(),
-            context_message_count: None,
+            history_mode: HistoryMode::All,
+            context_budget_percent: None,
+            token_budget: None,
+            cost_budget: None,
+            provider_policy_id: None,
+            reply_language: None,
             selected_preset_id: None,
             system_prompt_mode: PromptMode::None,
             selected_system_prompt_id: None,
@@ -134,6 +239,26 @@ impl ConversationSettings {
             enabled_mcp_server_ids: Vec::new(),
             enabled_skill_ids: Vec::new(),
             working_directory: None,
+            selected_model_id: None,
+            selected_assistant_id: None,
+            inherited: Vec::new(),
+        }
+    }
+
+    pub fn is_inherited(&self, field: &str) -> bool {
+        self.inherited.iter().any(|f| f == field)
+    }
+
+    /// Drop the values resolved from the workspace so saving doesn't pin them on the
+    /// conversation. The history mode can't be unset, so it stays marked instead.
+    pub fn clear_inherited(&mut self) {
+        for field in std::mem::take(&mut self.inherited) {
+            match field.as_str() {
+                "context_budget_percent" => self.context_budget_percent = None,
+                "selected_model_id" => self.selected_model_id = None,
+                "selected_assistant_id" => self.selected_assistant_id = None,
+                _ => self.inherited.push(field),
+            }
         }
     }
 }
@@ -148,7 +273,37 @@ pub struct UpdateConversationSettingsRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameter_overrides: Option<ModelParameterOverrides>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub context_message_count: Option<Option<i32>>,
+    pub history_mode: Option<HistoryMode>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub context_budget_percent: Option<Option<i32>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub token_budget: Option<Option<i64>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub cost_budget: Option<Option<f64>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub provider_policy_id: Option<Option<String>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub reply_language: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selected_preset_id: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -173,6 +328,18 @@ pub struct UpdateConversationSettingsRequest {
         deserialize_with = "deserialize_double_option"
     )]
     pub working_directory: Option<Option<String>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub selected_model_id: Option<Option<String>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_double_option"
+    )]
+    pub selected_assistant_id: Option<Option<String>>,
 }
 
 #[cfg(test)]
@@ -200,6 +367,20 @@ mod tests {
         assert_eq!(String::from(PromptMode::Custom), "custom");
     }
 
+    #[test]
+    fn test_history_mode_round_trip() {
+        for mode in [
+            HistoryMode::All,
+            HistoryMode::TokenBudget,
+            HistoryMode::Summarized,
+            HistoryMode::None,
+        ] {
+            assert_eq!(HistoryMode::from(String::from(mode).as_str()), mode);
+        }
+        assert_eq!(HistoryMode::from("last_n"), HistoryMode::TokenBudget);
+        assert_eq!(HistoryMode::from("unknown"), HistoryMode::All);
+    }
+
     #[test]
     fn test_model_parameter_overrides_default() {
         let overrides = ModelParameterOverrides::default();
@@ -217,7 +398,8 @@ mod tests {
         assert_eq!(settings.conversation_id, "conv-123");
         assert!(settings.use_provider_defaults);
         assert!(!settings.use_custom_parameters);
-        assert!(settings.context_message_count.is_none());
+        assert_eq!(settings.history_mode, HistoryMode::All);
+        assert!(settings.context_budget_percent.is_none());
         assert!(settings.selected_preset_id.is_none());
         assert_eq!(settings.system_prompt_mode, PromptMode::None);
         assert!(settings.selected_system_prompt_id.is_none());
@@ -228,6 +410,8 @@ mod tests {
         assert!(settings.enabled_mcp_server_ids.is_empty());
         assert!(settings.enabled_skill_ids.is_empty());
         assert!(settings.working_directory.is_none());
+        assert!(settings.selected_model_id.is_none());
+        assert!(settings.selected_assistant_id.is_none());
     }
 
     #[test]
@@ -264,6 +448,31 @@ mod tests {
         assert_eq!(req.working_directory, Some(Some("/tmp/test".to_string())));
     }
 
+    #[test]
+    fn test_selected_binding_deserialization() {
+        let json = r#"{"selected_model_id": "model-1", "selected_assistant_id": null}"#;
+        let req: UpdateConversationSettingsRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.selected_model_id, Some(Some("model-1".to_string())));
+        assert_eq!(req.selected_assistant_id, Some(None));
+    }
+
+    #[test]
+    fn test_model_parameter_overrides_validate() {
+        let mut overrides = ModelParameterOverrides {
+            temperature: Some(0.7),
+            max_tokens: Some(1024),
+            ..Default::default()
+        };
+        assert!(overrides.validate().is_ok());
+
+        overrides.temperature = Some(3.0);
+        assert!(overrides.validate().is_err());
+
+        overrides.temperature = None;
+        overrides.max_tokens = Some(0);
+        assert!(overrides.validate().is_err());
+    }
+
     #[test]
     fn test_model_parameter_overrides_serialization() {
         let overrides = ModelParameterOverrides {
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::UpdateConversationSettingsRequest;
+
+/// A starting point for a recurring kind of conversation (a standup, a code review,
+/// a journal entry): its title, assistant, settings and first message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationTemplate {
+    pub id: String,
+    pub name: String,
+    /// Title of the new conversation; see [`render_title`] for the placeholders
+    pub title_pattern: String,
+    pub assistant_id: Option<String>,
+    /// Applied to the new conversation's settings; fields left out keep their defaults
+    pub settings: UpdateConversationSettingsRequest,
+    pub opening_message: Option<String>,
+    /// Send the opening message right away instead of leaving it as a draft
+    pub send_opening_message: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConversationTemplateInput {
+    pub name: String,
+    pub title_pattern: String,
+    #[serde(default)]
+    pub assistant_id: Option<String>,
+    #[serde(default)]
+    pub settings: UpdateConversationSettingsRequest,
+    #[serde(default)]
+    pub opening_message: Option<String>,
+    #[serde(default)]
+    pub send_opening_message: bool,
+}
+
+impl ConversationTemplateInput {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Template name is required".to_string());
+        }
+        if self.title_pattern.trim().is_empty() {
+            return Err("Title pattern is required".to_string());
+        }
+        if self.send_opening_message
+            && self
+                .opening_message
+                .as_deref()
+                .is_none_or(|m| m.trim().is_empty())
+        {
+            return Err("An opening message is required to send one".to_string());
+        }
+        if let Some(overrides) = &self.settings.parameter_overrides {
+            overrides.validate()?;
+        }
+        if let Some(Some(percent)) = self.settings.context_budget_percent
+            && !(1..=100).contains(&percent)
+        {
+            return Err("Context budget must be between 1 and 100 percent".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Fill in a title pattern: `{{date}}` (2026-03-09), `{{time}}` (14:05),
+/// `{{weekday}}` (Monday) and `{{month}}` (March 2026), in local time
+pub fn render_title(pattern: &str, now: DateTime<Local>) -> String {
+    pattern
+        .replace("{{date}}", &now.format("%Y-%m-%d").to_string())
+        .replace("{{time}}", &now.format("%H:%M").to_string())
+        .replace("{{weekday}}", &now.format("%A").to_string())
+        .replace("{{month}}", &now.format("%B %Y").to_string())
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_title() {
+        let now = Local.with_ymd_and_hms(2026, 3, 9, 14, 5, 0).unwrap();
+        assert_eq!(
+            render_title("Standup {{date}} ({{weekday}})", now),
+            "Standup 2026-03-09 (Monday)"
+        );
+        assert_eq!(
+            render_title("Journal: {{month}}, {{time}} ", now),
+            "Journal: March 2026, 14:05"
+        );
+        assert_eq!(render_title("Code review", now), "Code review");
+    }
+
+    #[test]
+    fn test_validate_requires_message_to_send() {
+        let input = ConversationTemplateInput {
+            name: "Standup".to_string(),
+            title_pattern: "Standup {{date}}".to_string(),
+            assistant_id: None,
+            settings: UpdateConversationSettingsRequest::default(),
+            opening_message: None,
+            send_opening_message: true,
+        };
+        assert!(input.validate().is_err());
+
+        let input = ConversationTemplateInput {
+            opening_message: Some("What did I do yesterday?".to_string()),
+            ..input
+        };
+        assert!(input.validate().is_ok());
+    }
+}
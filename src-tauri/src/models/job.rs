@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of work a background job performs; selects the handler in `jobs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Regenerate a conversation summary once enough new messages arrived
+    SummaryRefresh,
+    /// POST an event payload to a webhook
+    WebhookDelivery,
+    /// Check a reply against the pages it was written from
+    AnswerVerification,
+    /// Run a triggered automation's action
+    AutomationRun,
+}
+
+impl JobKind {
+    pub fn id(&self) -> &'static str {
+        match self {
+            JobKind::SummaryRefresh => "summary_refresh",
+            JobKind::WebhookDelivery => "webhook_delivery",
+            JobKind::AnswerVerification => "answer_verification",
+            JobKind::AutomationRun => "automation_run",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "summary_refresh" => Some(JobKind::SummaryRefresh),
+            "webhook_delivery" => Some(JobKind::WebhookDelivery),
+            "answer_verification" => Some(JobKind::AnswerVerification),
+            "automation_run" => Some(JobKind::AutomationRun),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    /// Gave up after `max_attempts`
+    Failed,
+}
+
+impl JobStatus {
+    pub fn id(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "queued" => Some(JobStatus::Queued),
+            "running" => Some(JobStatus::Running),
+            "completed" => Some(JobStatus::Completed),
+            "failed" => Some(JobStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Higher priorities are picked first
+pub const JOB_PRIORITY_LOW: i64 = -10;
+pub const JOB_PRIORITY_NORMAL: i64 = 0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    pub priority: i64,
+    pub attempts: i64,
+    pub max_attempts: i64,
+    pub last_error: Option<String>,
+    /// Not picked up before this time (RFC 3339); pushed back on retry
+    pub run_after: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateJobRequest {
+    pub kind: JobKind,
+    pub payload: serde_json::Value,
+    pub priority: i64,
+    pub max_attempts: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_match_serde() {
+        for kind in [
+            JobKind::SummaryRefresh,
+            JobKind::WebhookDelivery,
+            JobKind::AnswerVerification,
+            JobKind::AutomationRun,
+        ] {
+            let json = serde_json::to_string(&kind).unwrap();
+            assert_eq!(json, format!("\"{}\"", kind.id()));
+            assert_eq!(JobKind::from_id(kind.id()), Some(kind));
+        }
+        for status in [
+            JobStatus::Queued,
+            JobStatus::Running,
+            JobStatus::Completed,
+            JobStatus::Failed,
+        ] {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, format!("\"{}\"", status.id()));
+            assert_eq!(JobStatus::from_id(status.id()), Some(status));
+        }
+    }
+}
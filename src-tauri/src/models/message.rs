@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use super::model::ModelParameters;
+
 /// Message model (thinking_content moved to thinking_steps table)
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Message {
@@ -10,6 +12,38 @@ pub struct Message {
     pub sender_id: Option<String>,
     pub content: String,
     pub tokens: Option<i64>,
+    /// Prompt tokens reported by the provider
+    #[serde(default)]
+    pub input_tokens: Option<i64>,
+    /// Completion tokens reported by the provider
+    #[serde(default)]
+    pub output_tokens: Option<i64>,
+    /// Cost in USD, computed from token usage and the model's pricing
+    #[serde(default)]
+    pub cost: Option<f64>,
+    /// Suggested follow-up prompts generated after the response (quick-reply chips)
+    #[serde(default)]
+    pub follow_up_suggestions: Option<Vec<String>>,
+    /// Provider type that generated this reply (e.g. "openai", "ollama")
+    #[serde(default)]
+    pub provider_type: Option<String>,
+    /// Provider-side model identifier that generated this reply
+    #[serde(default)]
+    pub model_id: Option<String>,
+    /// Effective generation parameters sent with the request
+    #[serde(default)]
+    pub model_params: Option<ModelParameters>,
+    pub created_at: String,
+}
+
+/// Earlier content of a message, kept when the message is edited or a revision is
+/// restored
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MessageRevision {
+    pub id: String,
+    pub message_id: String,
+    pub content: String,
+    /// When this content was replaced
     pub created_at: String,
 }
 
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use super::message::Message;
+
+/// Longest emoji reaction accepted, in characters (ZWJ sequences and skin tones
+/// take several)
+const MAX_EMOJI_CHARS: usize = 16;
+
+/// Kind of mark a user can put on a message
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageFlagKind {
+    Bookmark,
+    ThumbsUp,
+    ThumbsDown,
+    /// Custom emoji reaction; a message can have several
+    Emoji,
+}
+
+impl MessageFlagKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MessageFlagKind::Bookmark => "bookmark",
+            MessageFlagKind::ThumbsUp => "thumbs_up",
+            MessageFlagKind::ThumbsDown => "thumbs_down",
+            MessageFlagKind::Emoji => "emoji",
+        }
+    }
+
+    /// The rating a thumbs flag replaces, since a message is either liked or disliked
+    pub fn opposite(self) -> Option<Self> {
+        match self {
+            MessageFlagKind::ThumbsUp => Some(MessageFlagKind::ThumbsDown),
+            MessageFlagKind::ThumbsDown => Some(MessageFlagKind::ThumbsUp),
+            _ => None,
+        }
+    }
+
+    /// Stored value for a flag: the emoji for reactions, empty for the other kinds
+    pub fn value_for(self, emoji: Option<&str>) -> Result<String, String> {
+        match self {
+            MessageFlagKind::Emoji => {
+                let emoji = emoji.map(str::trim).unwrap_or_default();
+                if emoji.is_empty() {
+                    return Err("An emoji is required for emoji reactions".to_string());
+                }
+                if emoji.chars().count() > MAX_EMOJI_CHARS
+                    || emoji
+                        .chars()
+                        .any(|c| c.is_whitespace() || c.is_ascii_alphanumeric())
+                {
+                    return Err(format!("Not a valid emoji reaction: {}", emoji));
+                }
+                Ok(emoji.to_string())
+            }
+            _ => Ok(String::new()),
+        }
+    }
+}
+
+impl std::fmt::Display for MessageFlagKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for MessageFlagKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bookmark" => Ok(MessageFlagKind::Bookmark),
+            "thumbs_up" => Ok(MessageFlagKind::ThumbsUp),
+            "thumbs_down" => Ok(MessageFlagKind::ThumbsDown),
+            "emoji" => Ok(MessageFlagKind::Emoji),
+            _ => Err(format!("Unknown message flag: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageFlag {
+    pub message_id: String,
+    pub kind: MessageFlagKind,
+    /// The emoji for `Emoji` flags
+    pub emoji: Option<String>,
+    pub created_at: String,
+}
+
+/// A bookmarked message with where it was said
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarkedMessage {
+    pub message: Message,
+    pub conversation_title: Option<String>,
+    pub bookmarked_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_kind_round_trip() {
+        for kind in [
+            MessageFlagKind::Bookmark,
+            MessageFlagKind::ThumbsUp,
+            MessageFlagKind::ThumbsDown,
+            MessageFlagKind::Emoji,
+        ] {
+            assert_eq!(kind.as_str().parse::<MessageFlagKind>(), Ok(kind));
+        }
+        assert!("heart".parse::<MessageFlagKind>().is_err());
+    }
+
+    #[test]
+    fn test_value_for() {
+        assert_eq!(
+            MessageFlagKind::Bookmark.value_for(Some("🎉")),
+            Ok(String::new())
+        );
+        assert_eq!(
+            MessageFlagKind::Emoji.value_for(Some(" 👍🏽 ")),
+            Ok("👍🏽".to_string())
+        );
+        assert!(MessageFlagKind::Emoji.value_for(None).is_err());
+        assert!(MessageFlagKind::Emoji.value_for(Some("lol")).is_err());
+        assert_eq!(
+            MessageFlagKind::ThumbsUp.opposite(),
+            Some(MessageFlagKind::ThumbsDown)
+        );
+    }
+}
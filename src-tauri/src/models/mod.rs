@@ -1,31 +1,63 @@
 mod assistant;
 mod attachment;
+mod automation;
 mod context;
 mod conversation;
 mod conversation_settings;
+mod conversation_template;
+mod job;
 mod knowledge_base;
 mod message;
+mod message_flag;
 mod message_resources;
 mod model;
 mod model_parameter_preset;
+mod model_role;
 mod process_step;
 mod prompt;
 mod provider;
+mod provider_policy;
 mod search;
 mod setting;
 mod skill;
 mod tool;
+mod usage;
 mod user;
+mod webhook;
+mod workspace;
 
 // Provider
-pub use provider::{CreateProviderRequest, Provider};
+pub use provider::{
+    CreateProviderRequest, DataCollectionPolicy, OpenRouterMaxPrice, OpenRouterRouting, Provider,
+};
+pub use provider_policy::{
+    LOCAL_ONLY_POLICY_ID, PROVIDER_POLICIES_KEY, ProviderPolicy, ProviderPolicyInput,
+};
 
 // Model and parameters
 pub use model::{CreateModelRequest, Model, ModelParameters};
 
+// Model roles and aliases
+pub use model_role::{
+    LEGACY_SUMMARY_MODEL_KEY, MODEL_ALIASES_KEY, ModelRole, ModelRoleAssignments,
+};
+
+// Background jobs
+pub use job::{CreateJobRequest, JOB_PRIORITY_LOW, JOB_PRIORITY_NORMAL, Job, JobKind, JobStatus};
+
+// Automations
+pub use automation::{
+    Automation, AutomationAction, AutomationEvent, AutomationInput, AutomationRun,
+    AutomationRunStatus, AutomationSource, AutomationSourceKind, AutomationTrigger, parse_run_time,
+};
+
+// Webhooks
+pub use webhook::{WEBHOOKS_KEY, Webhook, WebhookEvent, WebhookInput};
+
 // Model Parameter Preset
 pub use model_parameter_preset::{
-    CreateModelParameterPresetRequest, ModelParameterPreset, UpdateModelParameterPresetRequest,
+    CreateModelParameterPresetRequest, ExportedPreset, ModelParameterPreset, PresetExport,
+    UpdateModelParameterPresetRequest, unique_preset_name,
 };
 
 // Assistant
@@ -42,32 +74,44 @@ pub use user::{CreateUserRelationshipRequest, CreateUserRequest, User, UserRelat
 
 // Conversation
 pub use conversation::{
-    Conversation, ConversationParticipant, CreateConversationParticipantRequest,
-    CreateConversationRequest, ParticipantSummary,
+    Conversation, ConversationMetadata, ConversationParticipant,
+    CreateConversationParticipantRequest, CreateConversationRequest, ParticipantMetadata,
+    ParticipantRole, ParticipantSummary, UpdateConversationParticipantRequest, title_from_content,
 };
 
+// Workspaces
+pub use workspace::{CreateWorkspaceRequest, UpdateWorkspaceRequest, Workspace, WorkspaceSettings};
+
+// Conversation templates
+pub use conversation_template::{ConversationTemplate, ConversationTemplateInput, render_title};
+
 // Conversation Settings
 pub use conversation_settings::{
-    ConversationSettings, ModelParameterOverrides, PromptMode, UpdateConversationSettingsRequest,
+    ConversationSettings, DEFAULT_CONTEXT_BUDGET_PERCENT, HistoryMode, ModelParameterOverrides,
+    PromptMode, REPLY_LANGUAGE_AUTO, UpdateConversationSettingsRequest,
 };
 
 // Message
-pub use message::{CreateMessageRequest, Message};
+pub use message::{CreateMessageRequest, Message, MessageRevision};
+pub use message_flag::{BookmarkedMessage, MessageFlag, MessageFlagKind};
 
 // Attachments (user-provided files)
 pub use attachment::{CreateFileAttachmentRequest, FileAttachment, UserAttachment};
 
 // Context enrichments (system-fetched content)
 pub use context::{
-    ContextEnrichment, ContextType, CreateFetchResultRequest, CreateSearchResultRequest,
-    FetchResult, SearchResult,
+    ContextEnrichment, ContextType, CreateFetchResultRequest, CreateSearchResultItemRequest,
+    CreateSearchResultRequest, FetchResult, MessageCitation, SearchResult, SearchResultItem,
 };
 
 // Process steps (AI workflow artifacts)
 pub use process_step::{
-    CodeExecution, ContentBlock, CreateCodeExecutionRequest, CreateContentBlockRequest,
-    CreateSearchDecisionRequest, CreateThinkingStepRequest, CreateToolCallRequest, ProcessStep,
-    SearchDecision, StepType, ThinkingStep, ToolCall,
+    AssistantSwitch, CodeExecution, ContentBlock, CreateAssistantSwitchRequest,
+    CreateCodeExecutionRequest, CreateContentBlockRequest, CreateSearchDecisionRequest,
+    CreateStepExplanationRequest, CreateThinkingStepRequest, CreateToolCallRequest,
+    CreateTranscriptionRequest, CreateTranslationRequest, CreateVerificationRequest, ProcessStep,
+    SearchDecision, StepExplanation, StepType, ThinkingStep, ToolCall, Transcription, Translation,
+    UnsupportedClaim, Verification,
 };
 
 // Message resources
@@ -83,4 +127,12 @@ pub use skill::{CreateSkillRequest, Skill};
 pub use setting::Setting;
 
 // Search
-pub use search::{ConversationSearchResult, MessageSearchResult, SearchResults};
+pub use search::{
+    ConversationMessageMatch, ConversationSearchResult, MessageSearchResult, SearchResults,
+};
+
+// Usage
+pub use usage::{
+    BudgetStatus, MONTHLY_SPEND_CAP_KEY, ModelSpend, NewUsageEntry, ProviderSpend, SenderUsage,
+    SpendReport, SpendRow, UsageStats, month_bounds,
+};
@@ -10,10 +10,47 @@ pub struct Model {
     pub description: Option<String>,
     pub is_starred: bool, // Whether model is starred for quick access
     pub is_deleted: bool, // Soft delete flag
+    /// Hidden from the model picker (still usable by existing conversations)
+    #[serde(default)]
+    pub is_hidden: bool,
+    /// Manual ordering position (ascending); ties fall back to creation order
+    #[serde(default)]
+    pub sort_order: i64,
+    /// Input price in USD per 1M tokens
+    pub input_price: Option<f64>,
+    /// Output price in USD per 1M tokens
+    pub output_price: Option<f64>,
+    /// OpenRouter routing overrides on top of the provider's defaults
+    #[serde(default)]
+    #[sqlx(skip)]
+    pub openrouter_routing: Option<super::OpenRouterRouting>,
+    /// Parameter preset used when no assistant preset applies
+    #[serde(default)]
+    pub default_preset_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+impl Model {
+    /// Compute the USD cost of a request from its token usage.
+    /// Returns None when the model has no pricing or no usage was reported.
+    pub fn cost_for_usage(
+        &self,
+        input_tokens: Option<i64>,
+        output_tokens: Option<i64>,
+    ) -> Option<f64> {
+        if input_tokens.is_none() && output_tokens.is_none() {
+            return None;
+        }
+        if self.input_price.is_none() && self.output_price.is_none() {
+            return None;
+        }
+        let input = input_tokens.unwrap_or(0) as f64 * self.input_price.unwrap_or(0.0);
+        let output = output_tokens.unwrap_or(0) as f64 * self.output_price.unwrap_or(0.0);
+        Some((input + output) / 1_000_000.0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateModelRequest {
     pub name: String,
@@ -21,6 +58,12 @@ pub struct CreateModelRequest {
     pub model_id: String,
     pub description: Option<String>,
     pub is_starred: Option<bool>,
+    /// Input price in USD per 1M tokens (auto-filled from model metadata when omitted)
+    #[serde(default)]
+    pub input_price: Option<f64>,
+    /// Output price in USD per 1M tokens (auto-filled from model metadata when omitted)
+    #[serde(default)]
+    pub output_price: Option<f64>,
 }
 
 // ==========================================================================
@@ -97,3 +140,47 @@ impl ModelParameters {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn priced_model(input_price: Option<f64>, output_price: Option<f64>) -> Model {
+        Model {
+            id: "m".to_string(),
+            name: "Model".to_string(),
+            provider_id: "p".to_string(),
+            model_id: "model".to_string(),
+            description: None,
+            is_starred: false,
+            is_deleted: false,
+            is_hidden: false,
+            sort_order: 0,
+            input_price,
+            output_price,
+            openrouter_routing: None,
+            default_preset_id: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_cost_for_usage() {
+        let model = priced_model(Some(3.0), Some(15.0));
+        let cost = model.cost_for_usage(Some(1_000), Some(2_000)).unwrap();
+        assert!((cost - 0.033).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_for_usage_without_pricing_or_usage() {
+        assert_eq!(
+            priced_model(None, None).cost_for_usage(Some(10), Some(10)),
+            None
+        );
+        assert_eq!(
+            priced_model(Some(1.0), Some(1.0)).cost_for_usage(None, None),
+            None
+        );
+    }
+}
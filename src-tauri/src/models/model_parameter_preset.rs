@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::ModelParameterOverrides;
+
 /// Model Parameter Preset - Reusable configuration for LLM generation parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelParameterPreset {
@@ -51,3 +53,165 @@ pub struct UpdateModelParameterPresetRequest {
     pub additional_params: Option<serde_json::Value>,
     pub is_default: Option<bool>,
 }
+
+/// Version of the preset export format written by [`PresetExport::new`]
+pub const PRESET_EXPORT_VERSION: u32 = 1;
+
+/// A preset in the export format: parameters only, without ids, timestamps or flags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedPreset {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_params: Option<serde_json::Value>,
+}
+
+/// Shareable JSON file of parameter presets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetExport {
+    pub version: u32,
+    pub presets: Vec<ExportedPreset>,
+}
+
+impl PresetExport {
+    pub fn new(presets: &[ModelParameterPreset]) -> Self {
+        Self {
+            version: PRESET_EXPORT_VERSION,
+            presets: presets.iter().map(ExportedPreset::from).collect(),
+        }
+    }
+
+    /// Parse an export file, rejecting newer format versions and out-of-range values
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let export: PresetExport =
+            serde_json::from_str(json).map_err(|e| format!("Invalid preset file: {}", e))?;
+        if export.version > PRESET_EXPORT_VERSION {
+            return Err(format!(
+                "Preset file version {} is newer than this app supports ({})",
+                export.version, PRESET_EXPORT_VERSION
+            ));
+        }
+        for preset in &export.presets {
+            if preset.name.trim().is_empty() {
+                return Err("Preset name cannot be empty".to_string());
+            }
+            ModelParameterOverrides {
+                temperature: preset.temperature,
+                max_tokens: preset.max_tokens,
+                top_p: preset.top_p,
+                frequency_penalty: preset.frequency_penalty,
+                presence_penalty: preset.presence_penalty,
+            }
+            .validate()
+            .map_err(|e| format!("Preset '{}': {}", preset.name, e))?;
+        }
+        Ok(export)
+    }
+}
+
+impl From<&ModelParameterPreset> for ExportedPreset {
+    fn from(p: &ModelParameterPreset) -> Self {
+        Self {
+            name: p.name.clone(),
+            description: p.description.clone(),
+            temperature: p.temperature,
+            max_tokens: p.max_tokens,
+            top_p: p.top_p,
+            frequency_penalty: p.frequency_penalty,
+            presence_penalty: p.presence_penalty,
+            additional_params: p.additional_params.clone(),
+        }
+    }
+}
+
+impl From<ExportedPreset> for CreateModelParameterPresetRequest {
+    fn from(preset: ExportedPreset) -> Self {
+        Self {
+            name: preset.name.trim().to_string(),
+            description: preset.description,
+            temperature: preset.temperature,
+            max_tokens: preset.max_tokens,
+            top_p: preset.top_p,
+            frequency_penalty: preset.frequency_penalty,
+            presence_penalty: preset.presence_penalty,
+            additional_params: preset.additional_params,
+            is_default: None,
+        }
+    }
+}
+
+/// `name`, or `name (2)`, `name (3)`, ... if it is already taken
+pub fn unique_preset_name(name: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|t| t == name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded range always yields a free name")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_export_roundtrip() {
+        let preset = ModelParameterPreset {
+            id: "p1".to_string(),
+            name: "Precise".to_string(),
+            description: None,
+            temperature: Some(0.2),
+            max_tokens: None,
+            top_p: Some(0.9),
+            frequency_penalty: None,
+            presence_penalty: None,
+            additional_params: Some(serde_json::json!({ "top_k": 20 })),
+            is_system: true,
+            is_default: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let json = serde_json::to_string(&PresetExport::new(&[preset])).unwrap();
+        assert!(!json.contains("is_system") && !json.contains("p1"));
+
+        let parsed = PresetExport::parse(&json).unwrap();
+        assert_eq!(parsed.presets.len(), 1);
+        assert_eq!(parsed.presets[0].temperature, Some(0.2));
+        assert_eq!(
+            parsed.presets[0].additional_params.as_ref().unwrap()["top_k"],
+            20
+        );
+    }
+
+    #[test]
+    fn test_preset_export_parse_rejects_bad_input() {
+        assert!(PresetExport::parse("not json").is_err());
+        assert!(PresetExport::parse(r#"{"version": 99, "presets": []}"#).is_err());
+        assert!(
+            PresetExport::parse(
+                r#"{"version": 1, "presets": [{"name": "Hot", "temperature": 5}]}"#
+            )
+            .is_err()
+        );
+        assert!(PresetExport::parse(r#"{"version": 1, "presets": [{"name": " "}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_unique_preset_name() {
+        let taken = vec!["Creative".to_string(), "Creative (2)".to_string()];
+        assert_eq!(unique_preset_name("Precise", &taken), "Precise");
+        assert_eq!(unique_preset_name("Creative", &taken), "Creative (3)");
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Settings key holding the alias -> model DB id map (JSON object)
+pub const MODEL_ALIASES_KEY: &str = "model_aliases";
+
+/// Legacy settings key for the title/summary model, read as a fallback for `Fast`
+pub const LEGACY_SUMMARY_MODEL_KEY: &str = "conversation_summary_model_id";
+
+/// A role a model can be assigned to, so features reference roles
+/// instead of hardcoded model ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelRole {
+    /// Default model for new conversations
+    Chat,
+    /// Model used when a message carries images
+    Vision,
+    /// Cheap/quick model for auxiliary calls (titles, search decisions)
+    Fast,
+}
+
+impl ModelRole {
+    pub const ALL: [ModelRole; 3] = [ModelRole::Chat, ModelRole::Vision, ModelRole::Fast];
+
+    /// Settings key storing the model DB id assigned to this role
+    pub fn setting_key(&self) -> &'static str {
+        match self {
+            ModelRole::Chat => "default_chat_model_id",
+            ModelRole::Vision => "default_vision_model_id",
+            ModelRole::Fast => "fast_model_id",
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            ModelRole::Chat => "chat",
+            ModelRole::Vision => "vision",
+            ModelRole::Fast => "fast",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.id() == id)
+    }
+}
+
+/// Current role assignments and user-defined aliases (values are model DB ids)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelRoleAssignments {
+    pub chat: Option<String>,
+    pub vision: Option<String>,
+    pub fast: Option<String>,
+    pub aliases: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_role_ids_roundtrip() {
+        for role in ModelRole::ALL {
+            assert_eq!(ModelRole::from_id(role.id()), Some(role));
+        }
+        assert_eq!(ModelRole::from_id("unknown"), None);
+    }
+
+    #[test]
+    fn test_model_role_serde_matches_id() {
+        for role in ModelRole::ALL {
+            let json = serde_json::to_string(&role).unwrap();
+            assert_eq!(json, format!("\"{}\"", role.id()));
+        }
+    }
+}
@@ -25,13 +25,18 @@ pub struct CreateThinkingStepRequest {
 }
 
 /// Search decision - stores AI's reasoning about whether web search is needed
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchDecision {
     pub id: String,
     pub message_id: String,
     pub reasoning: String,
     pub search_needed: bool,
     pub search_query: Option<String>,
+    /// Other queries the model considered (JSON array in the database)
+    #[serde(default)]
+    pub alternative_queries: Vec<String>,
+    /// Model's confidence in the decision, 0.0-1.0
+    pub confidence: Option<f64>,
     pub search_result_id: Option<String>, // Link to resulting search if approved
     pub display_order: i32,
     pub created_at: String,
@@ -43,6 +48,9 @@ pub struct CreateSearchDecisionRequest {
     pub reasoning: String,
     pub search_needed: bool,
     pub search_query: Option<String>,
+    #[serde(default)]
+    pub alternative_queries: Vec<String>,
+    pub confidence: Option<f64>,
     pub search_result_id: Option<String>,
     pub display_order: Option<i32>,
 }
@@ -127,6 +135,137 @@ pub struct CreateContentBlockRequest {
     pub display_order: i32,
 }
 
+/// Transcription - stores the transcript of an audio attachment on a user message
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Transcription {
+    pub id: String,
+    pub message_id: String,
+    pub file_id: Option<String>,
+    pub content: String,
+    pub engine: String, // "whisper_cpp" | "provider"
+    pub status: String, // "success" | "error"
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub display_order: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTranscriptionRequest {
+    pub message_id: String,
+    pub file_id: Option<String>,
+    pub content: String,
+    pub engine: String,
+    pub status: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub display_order: Option<i32>,
+}
+
+/// Translation - a message translated on request, shown in place of the original
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Translation {
+    pub id: String,
+    pub message_id: String,
+    pub target_language: String,
+    pub content: String,
+    /// Model that produced the translation, e.g. "openai/gpt-4o-mini"
+    pub model: Option<String>,
+    pub display_order: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTranslationRequest {
+    pub message_id: String,
+    pub target_language: String,
+    pub content: String,
+    pub model: Option<String>,
+    pub display_order: Option<i32>,
+}
+
+/// Verification - a check of an assistant message against the pages it was given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verification {
+    pub id: String,
+    pub message_id: String,
+    /// "supported" | "partially_supported" | "unsupported"
+    pub verdict: String,
+    pub summary: String,
+    /// Statements the sources don't back (JSON array in the database)
+    #[serde(default)]
+    pub unsupported_claims: Vec<UnsupportedClaim>,
+    /// Model that ran the check, e.g. "openai/gpt-4o-mini"
+    pub model: Option<String>,
+    pub display_order: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnsupportedClaim {
+    pub claim: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateVerificationRequest {
+    pub message_id: String,
+    pub verdict: String,
+    pub summary: String,
+    #[serde(default)]
+    pub unsupported_claims: Vec<UnsupportedClaim>,
+    pub model: Option<String>,
+    pub display_order: Option<i32>,
+}
+
+/// Assistant switch - the conversation was handed to another assistant after this
+/// message (switch_assistant)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssistantSwitch {
+    pub id: String,
+    pub message_id: String,
+    pub from_assistant_id: Option<String>,
+    pub to_assistant_id: String,
+    /// Summary of the conversation so far, given to the new assistant
+    pub summary: Option<String>,
+    pub display_order: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAssistantSwitchRequest {
+    pub message_id: String,
+    pub from_assistant_id: Option<String>,
+    pub to_assistant_id: String,
+    pub summary: Option<String>,
+    pub display_order: Option<i32>,
+}
+
+/// Plain-language explanation of a thinking step or tool call (explain_step). Not a
+/// step of its own; cached so the explanation is only generated once.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StepExplanation {
+    pub id: String,
+    pub step_id: String,
+    /// "thinking" | "tool_call"
+    pub step_type: String,
+    pub message_id: String,
+    pub content: String,
+    /// Model that wrote the explanation, e.g. "openai/gpt-4o-mini"
+    pub model: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateStepExplanationRequest {
+    pub step_id: String,
+    pub step_type: StepType,
+    pub message_id: String,
+    pub content: String,
+    pub model: Option<String>,
+}
+
 /// Process step type enum
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -136,6 +275,10 @@ pub enum StepType {
     ToolCall,
     CodeExecution,
     ContentBlock,
+    Transcription,
+    Translation,
+    Verification,
+    AssistantSwitch,
 }
 
 impl std::fmt::Display for StepType {
@@ -146,6 +289,10 @@ impl std::fmt::Display for StepType {
             StepType::ToolCall => write!(f, "tool_call"),
             StepType::CodeExecution => write!(f, "code_execution"),
             StepType::ContentBlock => write!(f, "content_block"),
+            StepType::Transcription => write!(f, "transcription"),
+            StepType::Translation => write!(f, "translation"),
+            StepType::Verification => write!(f, "verification"),
+            StepType::AssistantSwitch => write!(f, "assistant_switch"),
         }
     }
 }
@@ -160,6 +307,10 @@ impl std::str::FromStr for StepType {
             "tool_call" => Ok(StepType::ToolCall),
             "code_execution" => Ok(StepType::CodeExecution),
             "content_block" => Ok(StepType::ContentBlock),
+            "transcription" => Ok(StepType::Transcription),
+            "translation" => Ok(StepType::Translation),
+            "verification" => Ok(StepType::Verification),
+            "assistant_switch" => Ok(StepType::AssistantSwitch),
             _ => Err(format!("Invalid step type: {}", s)),
         }
     }
@@ -174,6 +325,10 @@ pub enum ProcessStep {
     ToolCall(ToolCall),
     CodeExecution(CodeExecution),
     ContentBlock(ContentBlock),
+    Transcription(Transcription),
+    Translation(Translation),
+    Verification(Verification),
+    AssistantSwitch(AssistantSwitch),
 }
 
 impl ProcessStep {
@@ -184,6 +339,10 @@ impl ProcessStep {
             ProcessStep::ToolCall(t) => &t.id,
             ProcessStep::CodeExecution(c) => &c.id,
             ProcessStep::ContentBlock(b) => &b.id,
+            ProcessStep::Transcription(t) => &t.id,
+            ProcessStep::Translation(t) => &t.id,
+            ProcessStep::Verification(v) => &v.id,
+            ProcessStep::AssistantSwitch(s) => &s.id,
         }
     }
 
@@ -194,6 +353,10 @@ impl ProcessStep {
             ProcessStep::ToolCall(_) => StepType::ToolCall,
             ProcessStep::CodeExecution(_) => StepType::CodeExecution,
             ProcessStep::ContentBlock(_) => StepType::ContentBlock,
+            ProcessStep::Transcription(_) => StepType::Transcription,
+            ProcessStep::Translation(_) => StepType::Translation,
+            ProcessStep::Verification(_) => StepType::Verification,
+            ProcessStep::AssistantSwitch(_) => StepType::AssistantSwitch,
         }
     }
 
@@ -205,6 +368,10 @@ impl ProcessStep {
             ProcessStep::ToolCall(t) => t.display_order,
             ProcessStep::CodeExecution(c) => c.display_order,
             ProcessStep::ContentBlock(b) => b.display_order,
+            ProcessStep::Transcription(t) => t.display_order,
+            ProcessStep::Translation(t) => t.display_order,
+            ProcessStep::Verification(v) => v.display_order,
+            ProcessStep::AssistantSwitch(s) => s.display_order,
         }
     }
 }
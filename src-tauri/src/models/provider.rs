@@ -11,6 +11,10 @@ pub struct Provider {
     pub api_style: Option<String>, // "responses" | "chat_completions" (only for custom_openai)
     pub description: Option<String>,
     pub is_enabled: bool,
+    /// OpenRouter routing defaults for this provider's models
+    #[serde(default)]
+    #[sqlx(skip)]
+    pub openrouter_routing: Option<OpenRouterRouting>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -25,3 +29,135 @@ pub struct CreateProviderRequest {
     pub description: Option<String>,
     pub is_enabled: Option<bool>,
 }
+
+/// Whether OpenRouter may route to providers that store or train on prompts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataCollectionPolicy {
+    Allow,
+    Deny,
+}
+
+/// Highest price OpenRouter may route to, in USD per 1M tokens
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterMaxPrice {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completion: Option<f64>,
+}
+
+/// OpenRouter provider routing preferences, sent as the request's `provider` object.
+/// Set on a provider as defaults and on a model to override them field by field.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterRouting {
+    /// Upstream provider slugs to try first, in order (e.g. "anthropic", "together")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+    /// Whether to fall back to other providers when the listed ones fail
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_fallbacks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_collection: Option<DataCollectionPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<OpenRouterMaxPrice>,
+}
+
+impl OpenRouterRouting {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Check slugs and price caps before saving
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(slug) = self
+            .order
+            .iter()
+            .find(|s| s.trim().is_empty() || s.contains(char::is_whitespace))
+        {
+            return Err(format!(
+                "Invalid provider slug in routing order: '{}'",
+                slug
+            ));
+        }
+        if let Some(price) = &self.max_price {
+            for (name, value) in [("prompt", price.prompt), ("completion", price.completion)] {
+                if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                    return Err(format!("Max {} price must be a non-negative number", name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Combine provider defaults with a model's overrides; fields the model sets win
+    pub fn merged_with(&self, overrides: &OpenRouterRouting) -> OpenRouterRouting {
+        OpenRouterRouting {
+            order: if overrides.order.is_empty() {
+                self.order.clone()
+            } else {
+                overrides.order.clone()
+            },
+            allow_fallbacks: overrides.allow_fallbacks.or(self.allow_fallbacks),
+            data_collection: overrides.data_collection.or(self.data_collection),
+            max_price: overrides
+                .max_price
+                .clone()
+                .or_else(|| self.max_price.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_merge_prefers_model_fields() {
+        let provider = OpenRouterRouting {
+            order: vec!["anthropic".to_string()],
+            allow_fallbacks: Some(false),
+            data_collection: Some(DataCollectionPolicy::Deny),
+            max_price: None,
+        };
+        let model = OpenRouterRouting {
+            order: vec!["together".to_string(), "fireworks".to_string()],
+            max_price: Some(OpenRouterMaxPrice {
+                prompt: Some(1.0),
+                completion: None,
+            }),
+            ..Default::default()
+        };
+
+        let merged = provider.merged_with(&model);
+        assert_eq!(merged.order, vec!["together", "fireworks"]);
+        assert_eq!(merged.allow_fallbacks, Some(false));
+        assert_eq!(merged.data_collection, Some(DataCollectionPolicy::Deny));
+        assert_eq!(
+            serde_json::to_value(&merged).unwrap()["max_price"],
+            serde_json::json!({ "prompt": 1.0 })
+        );
+    }
+
+    #[test]
+    fn test_routing_validate() {
+        let mut routing = OpenRouterRouting {
+            order: vec!["deep infra".to_string()],
+            ..Default::default()
+        };
+        assert!(routing.validate().is_err());
+
+        routing.order = vec!["deepinfra".to_string()];
+        routing.max_price = Some(OpenRouterMaxPrice {
+            prompt: Some(-1.0),
+            completion: None,
+        });
+        assert!(routing.validate().is_err());
+
+        routing.max_price = None;
+        assert!(routing.validate().is_ok());
+        assert!(OpenRouterRouting::default().is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the user-defined provider policies (JSON array)
+pub const PROVIDER_POLICIES_KEY: &str = "provider_policies";
+
+/// Built-in policy allowing only providers on this machine or the local network
+pub const LOCAL_ONLY_POLICY_ID: &str = "local_only";
+
+/// Which providers a conversation may send messages to. A provider is allowed when
+/// any rule matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderPolicy {
+    pub id: String,
+    pub name: String,
+    /// Allow providers whose endpoint is on localhost or the local network
+    #[serde(default)]
+    pub allow_local: bool,
+    /// Provider records allowed wherever they run
+    #[serde(default)]
+    pub allowed_provider_ids: Vec<String>,
+    /// Provider types allowed wherever they run (e.g. "ollama", "azure")
+    #[serde(default)]
+    pub allowed_provider_types: Vec<String>,
+    /// Built-in policies can't be edited or deleted
+    #[serde(default)]
+    pub is_builtin: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderPolicyInput {
+    pub name: String,
+    #[serde(default)]
+    pub allow_local: bool,
+    #[serde(default)]
+    pub allowed_provider_ids: Vec<String>,
+    #[serde(default)]
+    pub allowed_provider_types: Vec<String>,
+}
+
+impl ProviderPolicyInput {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Policy name is required".to_string());
+        }
+        if !self.allow_local
+            && self.allowed_provider_ids.is_empty()
+            && self.allowed_provider_types.is_empty()
+        {
+            return Err("A policy must allow at least one provider".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl ProviderPolicy {
+    pub fn local_only() -> Self {
+        Self {
+            id: LOCAL_ONLY_POLICY_ID.to_string(),
+            name: "Local only".to_string(),
+            allow_local: true,
+            allowed_provider_ids: Vec::new(),
+            allowed_provider_types: Vec::new(),
+            is_builtin: true,
+        }
+    }
+
+    /// Whether requests to a provider are allowed. `is_local` is whether its endpoint
+    /// stays on this machine or network (see `network::is_local_endpoint`).
+    pub fn allows(&self, provider_id: Option<&str>, provider_type: &str, is_local: bool) -> bool {
+        (self.allow_local && is_local)
+            || provider_id.is_some_and(|id| self.allowed_provider_ids.iter().any(|a| a == id))
+            || self
+                .allowed_provider_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(provider_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_only_policy() {
+        let policy = ProviderPolicy::local_only();
+        assert!(policy.allows(None, "ollama", true));
+        assert!(policy.allows(Some("p1"), "openai", true));
+        assert!(!policy.allows(Some("p1"), "openai", false));
+    }
+
+    #[test]
+    fn test_policy_allow_lists() {
+        let policy = ProviderPolicy {
+            id: "eu".to_string(),
+            name: "EU only".to_string(),
+            allow_local: false,
+            allowed_provider_ids: vec!["azure-eu".to_string()],
+            allowed_provider_types: vec!["mistral".to_string()],
+            is_builtin: false,
+        };
+        assert!(policy.allows(Some("azure-eu"), "azure", false));
+        assert!(policy.allows(Some("p2"), "Mistral", false));
+        assert!(!policy.allows(Some("p3"), "openai", false));
+        // Local endpoints aren't implied unless allow_local is set
+        assert!(!policy.allows(None, "ollama", true));
+    }
+
+    #[test]
+    fn test_policy_input_validate() {
+        let mut input = ProviderPolicyInput {
+            name: "Nothing".to_string(),
+            allow_local: false,
+            allowed_provider_ids: Vec::new(),
+            allowed_provider_types: Vec::new(),
+        };
+        assert!(input.validate().is_err());
+        input.allow_local = true;
+        assert!(input.validate().is_ok());
+        input.name = " ".to_string();
+        assert!(input.validate().is_err());
+    }
+}
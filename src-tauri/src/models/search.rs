@@ -10,6 +10,19 @@ pub struct MessageSearchResult {
     pub created_at: String,
 }
 
+/// A message matching a find-in-conversation query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationMessageMatch {
+    pub message_id: String,
+    /// 0-based position of the message in the conversation, oldest first
+    pub ordinal: i64,
+    pub sender_type: String,
+    pub content_snippet: String,
+    /// Occurrences of the query terms in the message
+    pub match_count: usize,
+    pub created_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationSearchResult {
     pub id: String,
@@ -0,0 +1,360 @@
+use serde::{Deserialize, Serialize};
+
+/// Aggregated token usage and cost for a single sender (model or assistant)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SenderUsage {
+    pub sender_type: String,
+    pub sender_id: Option<String>,
+    pub message_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    /// Total cost in USD (only messages with known pricing contribute)
+    pub cost: f64,
+}
+
+/// Usage totals across messages, optionally scoped to a conversation or time range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub message_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: f64,
+    /// Messages that reported usage but have no pricing, so their cost is unknown
+    pub unpriced_message_count: i64,
+    pub by_sender: Vec<SenderUsage>,
+}
+
+/// Share of a budget at which sends start raising a warning
+pub const BUDGET_WARNING_RATIO: f64 = 0.8;
+
+/// A conversation's usage measured against its token and cost budgets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    pub token_budget: Option<i64>,
+    pub cost_budget: Option<f64>,
+    pub used_tokens: i64,
+    /// Cost in USD; replies without pricing don't count toward the cost budget
+    pub used_cost: f64,
+    pub unpriced_message_count: i64,
+    /// Expected usage of the next reply, the average of the replies so far
+    pub projected_tokens: i64,
+    pub projected_cost: f64,
+    /// Usage after the next reply is expected to reach [`BUDGET_WARNING_RATIO`] of a budget
+    pub warning: bool,
+    /// The next reply is expected to go over a budget
+    pub exceeded: bool,
+}
+
+impl BudgetStatus {
+    /// Returns `None` when neither budget is set
+    pub fn evaluate(
+        token_budget: Option<i64>,
+        cost_budget: Option<f64>,
+        stats: &UsageStats,
+    ) -> Option<Self> {
+        if token_budget.is_none() && cost_budget.is_none() {
+            return None;
+        }
+
+        let used_tokens = stats.input_tokens + stats.output_tokens;
+        let priced_count = stats.message_count - stats.unpriced_message_count;
+        let projected_tokens = if stats.message_count > 0 {
+            used_tokens / stats.message_count
+        } else {
+            0
+        };
+        let projected_cost = if priced_count > 0 {
+            stats.cost / priced_count as f64
+        } else {
+            0.0
+        };
+
+        // Share of each set budget the conversation is expected to use after the next reply
+        let ratios = [
+            token_budget.map(|b| (used_tokens + projected_tokens) as f64 / b as f64),
+            cost_budget.map(|b| (stats.cost + projected_cost) / b),
+        ];
+        let highest = ratios.into_iter().flatten().fold(0.0, f64::max);
+
+        Some(Self {
+            token_budget,
+            cost_budget,
+            used_tokens,
+            used_cost: stats.cost,
+            unpriced_message_count: stats.unpriced_message_count,
+            projected_tokens,
+            projected_cost,
+            warning: highest >= BUDGET_WARNING_RATIO,
+            exceeded: highest > 1.0,
+        })
+    }
+
+    /// Message for a send blocked by the budget
+    pub fn exceeded_message(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(budget) = self.token_budget {
+            parts.push(format!("{} of {} tokens used", self.used_tokens, budget));
+        }
+        if let Some(budget) = self.cost_budget {
+            parts.push(format!("${:.4} of ${:.2} spent", self.used_cost, budget));
+        }
+        format!(
+            "This message would exceed the conversation budget ({}). Raise or clear the budget to continue.",
+            parts.join(", ")
+        )
+    }
+}
+
+/// Settings key holding the monthly spend soft cap in USD (empty = no cap)
+pub const MONTHLY_SPEND_CAP_KEY: &str = "monthly_spend_cap";
+
+/// A reply's usage, as written to the usage ledger
+#[derive(Debug, Clone, Default)]
+pub struct NewUsageEntry {
+    pub message_id: Option<String>,
+    pub conversation_id: Option<String>,
+    pub provider_id: Option<String>,
+    pub provider_name: Option<String>,
+    pub provider_type: Option<String>,
+    pub model_db_id: Option<String>,
+    pub model_id: Option<String>,
+    pub model_name: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: Option<f64>,
+}
+
+/// Spend on a single model within a month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelSpend {
+    pub model_db_id: Option<String>,
+    pub model_id: Option<String>,
+    pub model_name: Option<String>,
+    pub message_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    /// Estimated cost in USD from the configured pricing
+    pub cost: f64,
+    pub unpriced_message_count: i64,
+}
+
+/// Spend on a provider within a month, broken down by model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderSpend {
+    pub provider_id: Option<String>,
+    pub provider_name: Option<String>,
+    pub provider_type: Option<String>,
+    pub message_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: f64,
+    pub unpriced_message_count: i64,
+    pub models: Vec<ModelSpend>,
+}
+
+/// Ledger totals for one model, tagged with its provider
+#[derive(Debug, Clone)]
+pub struct SpendRow {
+    pub provider_id: Option<String>,
+    pub provider_name: Option<String>,
+    pub provider_type: Option<String>,
+    pub model: ModelSpend,
+}
+
+/// Estimated spend across all conversations for one calendar month (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpendReport {
+    /// `YYYY-MM`
+    pub month: String,
+    pub message_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost: f64,
+    /// Replies whose model has no pricing, so their cost is unknown
+    pub unpriced_message_count: i64,
+    /// Monthly soft cap in USD, if one is set
+    pub cap: Option<f64>,
+    pub by_provider: Vec<ProviderSpend>,
+}
+
+impl SpendReport {
+    /// Group per-model rows under their provider, most expensive first
+    pub fn new(month: String, cap: Option<f64>, rows: Vec<SpendRow>) -> Self {
+        let mut report = Self {
+            month,
+            message_count: 0,
+            input_tokens: 0,
+            output_tokens: 0,
+            cost: 0.0,
+            unpriced_message_count: 0,
+            cap,
+            by_provider: Vec::new(),
+        };
+
+        for SpendRow {
+            provider_id,
+            provider_name,
+            provider_type,
+            model,
+        } in rows
+        {
+            report.message_count += model.message_count;
+            report.input_tokens += model.input_tokens;
+            report.output_tokens += model.output_tokens;
+            report.cost += model.cost;
+            report.unpriced_message_count += model.unpriced_message_count;
+
+            let index = match report.by_provider.iter().position(|p| {
+                p.provider_id == provider_id
+                    && (provider_id.is_some() || p.provider_type == provider_type)
+            }) {
+                Some(index) => index,
+                None => {
+                    report.by_provider.push(ProviderSpend {
+                        provider_id,
+                        provider_name,
+                        provider_type,
+                        message_count: 0,
+                        input_tokens: 0,
+                        output_tokens: 0,
+                        cost: 0.0,
+                        unpriced_message_count: 0,
+                        models: Vec::new(),
+                    });
+                    report.by_provider.len() - 1
+                }
+            };
+            let provider = &mut report.by_provider[index];
+            provider.message_count += model.message_count;
+            provider.input_tokens += model.input_tokens;
+            provider.output_tokens += model.output_tokens;
+            provider.cost += model.cost;
+            provider.unpriced_message_count += model.unpriced_message_count;
+            provider.models.push(model);
+        }
+
+        report.by_provider.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+        for provider in &mut report.by_provider {
+            provider.models.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+        }
+        report
+    }
+}
+
+/// Start and end (exclusive) dates of a `YYYY-MM` month, for comparing against
+/// RFC 3339 timestamps
+pub fn month_bounds(month: &str) -> Result<(String, String), String> {
+    let start = chrono::NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid month '{}', expected YYYY-MM", month))?;
+    let end = start
+        .checked_add_months(chrono::Months::new(1))
+        .ok_or_else(|| format!("Invalid month '{}'", month))?;
+    Ok((
+        start.format("%Y-%m-%d").to_string(),
+        end.format("%Y-%m-%d").to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(message_count: i64, tokens: i64, cost: f64) -> UsageStats {
+        UsageStats {
+            message_count,
+            input_tokens: tokens / 2,
+            output_tokens: tokens - tokens / 2,
+            cost,
+            unpriced_message_count: 0,
+            by_sender: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_budget_status_without_budget() {
+        assert_eq!(
+            BudgetStatus::evaluate(None, None, &stats(2, 1000, 0.1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_budget_status_projects_next_reply() {
+        // 4 replies averaging 1000 tokens: the next one reaches 5000
+        let status = BudgetStatus::evaluate(Some(10_000), None, &stats(4, 4000, 0.0)).unwrap();
+        assert_eq!(status.projected_tokens, 1000);
+        assert!(!status.warning);
+        assert!(!status.exceeded);
+
+        let status = BudgetStatus::evaluate(Some(6000), None, &stats(4, 4000, 0.0)).unwrap();
+        assert!(status.warning);
+        assert!(!status.exceeded);
+
+        let status = BudgetStatus::evaluate(Some(4500), None, &stats(4, 4000, 0.0)).unwrap();
+        assert!(status.exceeded);
+    }
+
+    #[test]
+    fn test_budget_status_cost_ignores_unpriced_replies() {
+        let mut usage = stats(3, 3000, 0.6);
+        usage.unpriced_message_count = 1;
+        let status = BudgetStatus::evaluate(None, Some(1.0), &usage).unwrap();
+        assert_eq!(status.projected_cost, 0.3);
+        assert!(status.warning);
+        assert!(!status.exceeded);
+
+        // A fresh conversation is always allowed
+        let status = BudgetStatus::evaluate(Some(100), Some(0.01), &stats(0, 0, 0.0)).unwrap();
+        assert!(!status.warning);
+    }
+
+    fn model_spend(model_id: &str, cost: f64) -> ModelSpend {
+        ModelSpend {
+            model_db_id: None,
+            model_id: Some(model_id.to_string()),
+            model_name: None,
+            message_count: 1,
+            input_tokens: 100,
+            output_tokens: 50,
+            cost,
+            unpriced_message_count: 0,
+        }
+    }
+
+    fn row(provider_id: &str, model_id: &str, cost: f64) -> SpendRow {
+        SpendRow {
+            provider_id: Some(provider_id.to_string()),
+            provider_name: None,
+            provider_type: None,
+            model: model_spend(model_id, cost),
+        }
+    }
+
+    #[test]
+    fn test_spend_report_groups_by_provider() {
+        let rows = vec![
+            row("openai", "gpt-4o-mini", 0.1),
+            row("anthropic", "claude", 0.5),
+            row("openai", "gpt-4o", 0.6),
+        ];
+        let report = SpendReport::new("2026-10".to_string(), Some(10.0), rows);
+
+        assert_eq!(report.message_count, 3);
+        assert!((report.cost - 1.2).abs() < 1e-9);
+        assert_eq!(report.by_provider.len(), 2);
+        let openai = &report.by_provider[0];
+        assert_eq!(openai.provider_id.as_deref(), Some("openai"));
+        assert!((openai.cost - 0.7).abs() < 1e-9);
+        assert_eq!(openai.models[0].model_id.as_deref(), Some("gpt-4o"));
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        assert_eq!(
+            month_bounds("2026-12").unwrap(),
+            ("2026-12-01".to_string(), "2027-01-01".to_string())
+        );
+        assert!(month_bounds("2026-13").is_err());
+        assert!(month_bounds("October").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the configured webhooks (JSON array, secrets encrypted)
+pub const WEBHOOKS_KEY: &str = "webhooks";
+
+/// Chat lifecycle events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A response finished and was saved
+    ChatComplete,
+    /// A tool call returned
+    ToolCallCompleted,
+    /// A conversation title was generated
+    TitleUpdated,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 3] = [
+        WebhookEvent::ChatComplete,
+        WebhookEvent::ToolCallCompleted,
+        WebhookEvent::TitleUpdated,
+    ];
+
+    /// Name used in the delivered payload
+    pub fn id(&self) -> &'static str {
+        match self {
+            WebhookEvent::ChatComplete => "chat_complete",
+            WebhookEvent::ToolCallCompleted => "tool_call_completed",
+            WebhookEvent::TitleUpdated => "title_updated",
+        }
+    }
+
+    /// App event the webhook is triggered by
+    pub fn app_event(&self) -> &'static str {
+        match self {
+            WebhookEvent::ChatComplete => "chat-complete",
+            WebhookEvent::ToolCallCompleted => "tool-call-completed",
+            WebhookEvent::TitleUpdated => "conversation-updated",
+        }
+    }
+}
+
+/// A configured webhook. The secret is never sent to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    #[serde(skip_serializing, default)]
+    pub secret: Option<String>,
+    /// Whether a signing secret is set
+    #[serde(default)]
+    pub has_secret: bool,
+    pub events: Vec<WebhookEvent>,
+    pub is_enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookInput {
+    pub name: String,
+    pub url: String,
+    /// `None` keeps the current secret on update; an empty string removes it
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEvent>,
+    #[serde(default = "default_enabled")]
+    pub is_enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_event_serde_matches_id() {
+        for event in WebhookEvent::ALL {
+            let json = serde_json::to_string(&event).unwrap();
+            assert_eq!(json, format!("\"{}\"", event.id()));
+        }
+    }
+
+    #[test]
+    fn test_webhook_secret_not_serialized() {
+        let webhook = Webhook {
+            id: "1".to_string(),
+            name: "n8n".to_string(),
+            url: "http://localhost:5678/webhook/x".to_string(),
+            secret: Some("s3cret".to_string()),
+            has_secret: true,
+            events: vec![WebhookEvent::ChatComplete],
+            is_enabled: true,
+            created_at: String::new(),
+        };
+        let json = serde_json::to_string(&webhook).unwrap();
+        assert!(!json.contains("s3cret"));
+        assert!(json.contains("\"has_secret\":true"));
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ConversationSettings, HistoryMode};
+
+/// A folder of related conversations sharing default settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    pub settings: WorkspaceSettings,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Defaults for the conversations in a workspace (stored as JSON).
+///
+/// Settings resolve global → workspace → conversation: a conversation's own value wins,
+/// and fields left unset here fall back to the global settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceSettings {
+    /// Web search provider id (instead of the global `search_provider` setting)
+    pub search_provider: Option<String>,
+    /// Model used until a conversation picks one (instead of the default chat model)
+    pub model_id: Option<String>,
+    /// Assistant used until a conversation picks one; takes precedence over `model_id`
+    pub assistant_id: Option<String>,
+    pub history_mode: Option<HistoryMode>,
+    pub context_budget_percent: Option<i32>,
+}
+
+impl WorkspaceSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(percent) = self.context_budget_percent
+            && !(1..=100).contains(&percent)
+        {
+            return Err("Context budget must be between 1 and 100 percent".to_string());
+        }
+        Ok(())
+    }
+
+    /// Fill in what a conversation leaves unset, recording each filled field in
+    /// `settings.inherited`. The history mode column always holds a value, so
+    /// `history_mode_set` tells whether the conversation picked one itself.
+    pub fn apply_to(&self, settings: &mut ConversationSettings, history_mode_set: bool) {
+        if !history_mode_set && let Some(mode) = self.history_mode {
+            settings.history_mode = mode;
+            settings.inherited.push("history_mode".to_string());
+        }
+        if settings.context_budget_percent.is_none() && self.context_budget_percent.is_some() {
+            settings.context_budget_percent = self.context_budget_percent;
+            settings
+                .inherited
+                .push("context_budget_percent".to_string());
+        }
+        if settings.selected_model_id.is_none()
+            && settings.selected_assistant_id.is_none()
+            && (self.model_id.is_some() || self.assistant_id.is_some())
+        {
+            settings.selected_model_id = self.model_id.clone();
+            settings.selected_assistant_id = self.assistant_id.clone();
+            settings.inherited.push("selected_model_id".to_string());
+            settings.inherited.push("selected_assistant_id".to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateWorkspaceRequest {
+    pub name: String,
+    #[serde(default)]
+    pub settings: WorkspaceSettings,
+}
+
+/// Fields left as `None` keep their current value; `settings` replaces the stored
+/// defaults as a whole
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct UpdateWorkspaceRequest {
+    pub name: Option<String>,
+    pub settings: Option<WorkspaceSettings>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_to_keeps_conversation_values() {
+        let workspace = WorkspaceSettings {
+            model_id: Some("workspace-model".to_string()),
+            history_mode: Some(HistoryMode::Summarized),
+            context_budget_percent: Some(40),
+            ..Default::default()
+        };
+
+        let mut settings = ConversationSettings::default_for_conversation("c".to_string());
+        workspace.apply_to(&mut settings, false);
+        assert_eq!(settings.history_mode, HistoryMode::Summarized);
+        assert_eq!(settings.context_budget_percent, Some(40));
+        assert_eq!(
+            settings.selected_model_id.as_deref(),
+            Some("workspace-model")
+        );
+        assert!(settings.is_inherited("history_mode"));
+
+        let mut settings = ConversationSettings::default_for_conversation("c".to_string());
+        settings.history_mode = HistoryMode::None;
+        settings.selected_assistant_id = Some("assistant".to_string());
+        workspace.apply_to(&mut settings, true);
+        assert_eq!(settings.history_mode, HistoryMode::None);
+        assert_eq!(settings.selected_model_id, None);
+        assert_eq!(
+            settings.inherited,
+            vec!["context_budget_percent".to_string()]
+        );
+
+        settings.clear_inherited();
+        assert_eq!(settings.context_budget_percent, None);
+        assert!(settings.inherited.is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+//! Network connectivity monitor
+//!
+//! Periodically opens TCP connections to a few well-known public resolvers; the network
+//! counts as online when any of them answers. Transitions emit `connectivity-changed`,
+//! and coming back online dispatches messages waiting in the chat outbox.
+
+use crate::commands::chat::outbox;
+use crate::events::{self, ConnectivityChanged};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// Cloudflare, Google and AliDNS, so one blocked resolver doesn't read as offline
+const PROBE_TARGETS: &[&str] = &["1.1.1.1:443", "8.8.8.8:443", "223.5.5.5:443"];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+const ONLINE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Checked more often while offline so queued messages go out soon after reconnecting
+const OFFLINE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+static ONLINE: AtomicBool = AtomicBool::new(true);
+
+pub fn is_online() -> bool {
+    ONLINE.load(Ordering::Relaxed)
+}
+
+/// Whether requests to this provider stay on this machine and work without a network
+pub fn is_local_endpoint(provider_type: &str, base_url: Option<&str>) -> bool {
+    let Some(base_url) = base_url.filter(|u| !u.trim().is_empty()) else {
+        // Ollama defaults to the local server
+        return provider_type == "ollama";
+    };
+    let Ok(url) = url::Url::parse(base_url) else {
+        return false;
+    };
+    match url.host() {
+        Some(url::Host::Domain(domain)) => {
+            domain.eq_ignore_ascii_case("localhost") || domain.ends_with(".local")
+        }
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+async fn probe() -> bool {
+    let attempts = PROBE_TARGETS.iter().map(|target| {
+        Box::pin(async move {
+            match tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(target)).await {
+                Ok(Ok(_)) => Ok(()),
+                _ => Err(()),
+            }
+        })
+    });
+    futures::future::select_ok(attempts).await.is_ok()
+}
+
+/// Start the monitor loop
+pub async fn start(app: tauri::AppHandle) {
+    loop {
+        let online = probe().await;
+        let was_online = ONLINE.swap(online, Ordering::Relaxed);
+        if online != was_online {
+            if online {
+                tracing::info!("🌐 [network] Connectivity restored");
+            } else {
+                tracing::warn!("🌐 [network] Network unreachable, queueing remote sends");
+            }
+            events::emit(&app, ConnectivityChanged { online });
+            if online {
+                outbox::flush(&app).await;
+            }
+        }
+
+        let interval = if online {
+            ONLINE_CHECK_INTERVAL
+        } else {
+            OFFLINE_CHECK_INTERVAL
+        };
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_endpoint() {
+        assert!(is_local_endpoint("ollama", None));
+        assert!(is_local_endpoint("ollama", Some("")));
+        assert!(is_local_endpoint(
+            "openai",
+            Some("http://localhost:1234/v1")
+        ));
+        assert!(is_local_endpoint("openai", Some("http://127.0.0.1:8080")));
+        assert!(is_local_endpoint(
+            "ollama",
+            Some("http://192.168.1.20:11434")
+        ));
+        assert!(is_local_endpoint("openai", Some("http://[::1]:8000")));
+        assert!(!is_local_endpoint("openai", None));
+        assert!(!is_local_endpoint(
+            "ollama",
+            Some("https://ollama.example.com")
+        ));
+        assert!(!is_local_endpoint(
+            "openai",
+            Some("https://api.openai.com/v1")
+        ));
+    }
+}
@@ -0,0 +1,162 @@
+//! Desktop notifications for responses that finish in the background
+//!
+//! A notification is shown when a response completes while the main window is
+//! unfocused or another conversation is open. Desktop notification backends don't
+//! report clicks, but clicking one focuses the app, so the first focus of the main
+//! window shortly after a notification navigates to its conversation.
+
+use crate::commands::AppState;
+use crate::events::{self, NavigateConversation};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{Listener, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Settings key: "false" disables completion notifications
+pub const NOTIFICATIONS_ENABLED_KEY: &str = "notifications_enabled";
+
+/// How long after a notification a window focus counts as "clicked"
+const CLICK_THROUGH_WINDOW: Duration = Duration::from_secs(120);
+const MAX_BODY_CHARS: usize = 120;
+
+/// Which conversation the UI shows, and the last notified conversation
+#[derive(Default)]
+pub struct NotificationState {
+    active_conversation: Mutex<Option<String>>,
+    pending: Mutex<Option<(String, Instant)>>,
+}
+
+impl NotificationState {
+    pub fn set_active_conversation(&self, conversation_id: Option<String>) {
+        if let Ok(mut active) = self.active_conversation.lock() {
+            *active = conversation_id;
+        }
+    }
+}
+
+/// Manage notification state and listen for completed responses
+pub fn init(app: &tauri::AppHandle) {
+    app.manage(NotificationState::default());
+
+    let app_for_listener = app.clone();
+    app.listen_any("chat-complete", move |e| {
+        let Ok(payload) = serde_json::from_str::<serde_json::Value>(e.payload()) else {
+            return;
+        };
+        let app = app_for_listener.clone();
+        tauri::async_runtime::spawn(async move {
+            notify_completion(&app, &payload).await;
+        });
+    });
+}
+
+/// Call when the main window gains focus
+pub fn on_main_window_focused(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<NotificationState>() else {
+        return;
+    };
+    let pending = state.pending.lock().ok().and_then(|mut p| p.take());
+    if let Some((conversation_id, sent_at)) = pending
+        && sent_at.elapsed() <= CLICK_THROUGH_WINDOW
+    {
+        events::emit(app, NavigateConversation { conversation_id });
+    }
+}
+
+async fn notify_completion(app: &tauri::AppHandle, payload: &serde_json::Value) {
+    let Some(conversation_id) = payload["conversation_id"].as_str() else {
+        return;
+    };
+    let content = payload["message"]["content"].as_str().unwrap_or_default();
+
+    let state: tauri::State<'_, AppState> = app.state();
+    // Notification centers keep a history, so private replies aren't shown there
+    if state.private_conversations.contains(conversation_id) {
+        return;
+    }
+
+    let notification_state = app.state::<NotificationState>();
+    let window_focused = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_focused().ok())
+        .unwrap_or(false);
+    let conversation_open = notification_state
+        .active_conversation
+        .lock()
+        .map(|a| a.as_deref() == Some(conversation_id))
+        .unwrap_or(false);
+    if window_focused && conversation_open {
+        return;
+    }
+
+    let enabled = !matches!(
+        state
+            .db
+            .get_setting(NOTIFICATIONS_ENABLED_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("false")
+    );
+    if !enabled {
+        return;
+    }
+
+    let title = state
+        .db
+        .get_conversation(conversation_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|c| c.title)
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| "ChatShell".to_string());
+
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(title)
+        .body(notification_body(content))
+        .show()
+    {
+        tracing::warn!("⚠️ [notifications] Failed to show notification: {}", e);
+        return;
+    }
+
+    if let Ok(mut pending) = notification_state.pending.lock() {
+        *pending = Some((conversation_id.to_string(), Instant::now()));
+    }
+}
+
+/// First non-empty line of the response, shortened
+fn notification_body(content: &str) -> String {
+    let line = content
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or("Response ready");
+    if line.chars().count() > MAX_BODY_CHARS {
+        format!(
+            "{}…",
+            line.chars().take(MAX_BODY_CHARS - 1).collect::<String>()
+        )
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_body_uses_first_line() {
+        assert_eq!(notification_body("\n## Summary\nDetails follow"), "Summary");
+        assert_eq!(notification_body("   "), "Response ready");
+        assert_eq!(
+            notification_body(&"a".repeat(200)).chars().count(),
+            MAX_BODY_CHARS
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Quick Ask: a system-wide hotkey that opens a small prompt window
+//!
+//! Queries from the window go through `send_message` into one dedicated
+//! "Quick Ask" conversation, so they show up in the main window's history too.
+
+use crate::commands::AppState;
+use crate::models::CreateConversationRequest;
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// Settings key: accelerator for the hotkey, e.g. "CommandOrControl+Shift+Space"
+/// (an empty value disables it)
+pub const QUICK_ASK_SHORTCUT_KEY: &str = "quick_ask_shortcut";
+/// Settings key: id of the dedicated Quick Ask conversation
+pub const QUICK_ASK_CONVERSATION_ID_KEY: &str = "quick_ask_conversation_id";
+
+pub const DEFAULT_QUICK_ASK_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+pub const QUICK_ASK_WINDOW_LABEL: &str = "quick-ask";
+const QUICK_ASK_TITLE: &str = "Quick Ask";
+
+/// Global shortcut plugin; any registered shortcut toggles the Quick Ask window
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                toggle_window(app);
+            }
+        })
+        .build()
+}
+
+/// The configured shortcut, falling back to the default
+pub async fn configured_shortcut(state: &AppState) -> String {
+    state
+        .db
+        .get_setting(QUICK_ASK_SHORTCUT_KEY)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| DEFAULT_QUICK_ASK_SHORTCUT.to_string())
+}
+
+/// Replace the registered hotkey. An empty shortcut only unregisters.
+pub fn register_shortcut(app: &tauri::AppHandle, shortcut: &str) -> Result<(), String> {
+    let global_shortcut = app.global_shortcut();
+    global_shortcut
+        .unregister_all()
+        .map_err(|e| e.to_string())?;
+    if shortcut.trim().is_empty() {
+        return Ok(());
+    }
+    global_shortcut
+        .register(shortcut.trim())
+        .map_err(|e| format!("Failed to register shortcut {}: {}", shortcut, e))?;
+    tracing::info!("⌨️ [quick_ask] Registered hotkey {}", shortcut);
+    Ok(())
+}
+
+/// Register the saved hotkey at startup
+pub async fn register_saved_shortcut(app: tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let shortcut = configured_shortcut(&state).await;
+    if let Err(e) = register_shortcut(&app, &shortcut) {
+        tracing::warn!("⚠️ [quick_ask] {}", e);
+    }
+}
+
+/// Show (creating on first use) or hide the Quick Ask window
+pub fn toggle_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ASK_WINDOW_LABEL) {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        return;
+    }
+
+    let result = WebviewWindowBuilder::new(
+        app,
+        QUICK_ASK_WINDOW_LABEL,
+        WebviewUrl::App("index.html#/quick-ask".into()),
+    )
+    .title(QUICK_ASK_TITLE)
+    .inner_size(640.0, 420.0)
+    .center()
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .focused(true)
+    .build();
+    if let Err(e) = result {
+        tracing::error!("❌ [quick_ask] Failed to open window: {}", e);
+    }
+}
+
+/// Id of the Quick Ask conversation, creating it if missing or deleted
+pub async fn ensure_conversation(state: &AppState) -> Result<String, String> {
+    if let Some(id) = state
+        .db
+        .get_setting(QUICK_ASK_CONVERSATION_ID_KEY)
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|id| !id.is_empty())
+        && state
+            .db
+            .get_conversation(&id)
+            .await
+            .map_err(|e| e.to_string())?
+            .is_some()
+    {
+        return Ok(id);
+    }
+
+    let conversation = state
+        .db
+        .create_conversation(CreateConversationRequest {
+            title: QUICK_ASK_TITLE.to_string(),
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    state
+        .db
+        .set_setting(QUICK_ASK_CONVERSATION_ID_KEY, &conversation.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!(
+        "⌨️ [quick_ask] Created Quick Ask conversation {}",
+        conversation.id
+    );
+    Ok(conversation.id)
+}
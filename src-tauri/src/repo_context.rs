@@ -0,0 +1,279 @@
+//! Git repository context
+//!
+//! Collects what a model needs to know about a local repository before answering
+//! questions about it: the branch, recent commits, the working tree status and its
+//! uncommitted diff, and the tracked file list. Everything comes from the `git` CLI
+//! on PATH, and every part is capped so a large repository can't flood the prompt.
+//! The result is rendered as a Markdown document the frontend attaches to a message.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+const MAX_COMMITS: usize = 10;
+const MAX_STATUS_LINES: usize = 200;
+const MAX_DIFF_CHARS: usize = 30_000;
+const MAX_FILES: usize = 1_000;
+const MAX_FILE_LIST_CHARS: usize = 20_000;
+
+/// Upper bound for a single git invocation
+const GIT_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoCommit {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoContext {
+    /// Top-level directory of the repository
+    pub root: String,
+    /// `None` on a detached HEAD
+    pub branch: Option<String>,
+    /// `None` before the first commit
+    pub head: Option<String>,
+    pub commits: Vec<RepoCommit>,
+    /// `git status --porcelain` lines
+    pub status: Vec<String>,
+    /// Uncommitted changes (staged and unstaged) against HEAD
+    pub diff: String,
+    pub diff_truncated: bool,
+    pub files: Vec<String>,
+    /// Number of tracked files, including the ones left out of `files`
+    pub file_count: usize,
+    /// File name for the attachment
+    pub name: String,
+    /// Markdown rendering of everything above
+    pub content: String,
+}
+
+/// Collect the context of the repository containing `path`
+pub async fn collect(path: &Path) -> Result<RepoContext> {
+    let root = git(path, &["rev-parse", "--show-toplevel"])
+        .await
+        .map_err(|_| anyhow::anyhow!("Not inside a git repository: {}", path.display()))?
+        .trim()
+        .to_string();
+    let root_path = Path::new(&root);
+
+    let head = git(
+        root_path,
+        &["rev-parse", "--verify", "-q", "--short", "HEAD"],
+    )
+    .await
+    .ok()
+    .map(|hash| hash.trim().to_string())
+    .filter(|hash| !hash.is_empty());
+    let branch = git(root_path, &["symbolic-ref", "-q", "--short", "HEAD"])
+        .await
+        .ok()
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty());
+
+    let commits = if head.is_some() {
+        parse_log(
+            &git(
+                root_path,
+                &[
+                    "log",
+                    &format!("-n{}", MAX_COMMITS),
+                    "--date=short",
+                    "--format=%h%x1f%an%x1f%ad%x1f%s",
+                ],
+            )
+            .await?,
+        )
+    } else {
+        Vec::new()
+    };
+
+    let status: Vec<String> = git(root_path, &["status", "--porcelain"])
+        .await?
+        .lines()
+        .take(MAX_STATUS_LINES)
+        .map(str::to_string)
+        .collect();
+
+    // Before the first commit there is nothing to diff against but the index
+    let diff_args: &[&str] = if head.is_some() {
+        &["diff", "HEAD", "--no-color", "--no-ext-diff"]
+    } else {
+        &["diff", "--cached", "--no-color", "--no-ext-diff"]
+    };
+    let (diff, diff_truncated) = truncate(&git(root_path, diff_args).await?, MAX_DIFF_CHARS);
+
+    let tracked = git(root_path, &["ls-files"]).await?;
+    let file_count = tracked.lines().count();
+    let files = cap_file_list(tracked.lines());
+
+    let name = root_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repository".to_string());
+    let mut context = RepoContext {
+        root,
+        branch,
+        head,
+        commits,
+        status,
+        diff,
+        diff_truncated,
+        files,
+        file_count,
+        name: format!("{}-context.md", name),
+        content: String::new(),
+    };
+    context.content = render(&context, &name);
+    Ok(context)
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let mut cmd = tokio::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(dir)
+        // Keep non-ASCII paths readable instead of octal-escaped
+        .args(["-c", "core.quotepath=off"])
+        .args(args)
+        // Reading status must not contend with the user's own git commands
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(GIT_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| anyhow::anyhow!("git {} timed out", args.join(" ")))?
+        .map_err(|e| anyhow::anyhow!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Parse `git log --format=%h%x1f%an%x1f%ad%x1f%s` output
+fn parse_log(output: &str) -> Vec<RepoCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\u{1f}');
+            Some(RepoCommit {
+                hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Tracked files up to the count and size caps
+fn cap_file_list<'a>(files: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut total = 0;
+    files
+        .take(MAX_FILES)
+        .take_while(|file| {
+            total += file.len() + 1;
+            total <= MAX_FILE_LIST_CHARS
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> (String, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => (text[..end].to_string(), true),
+        None => (text.to_string(), false),
+    }
+}
+
+fn render(context: &RepoContext, name: &str) -> String {
+    let mut out = format!("# Repository: {}\n\n- Path: {}\n", name, context.root);
+    match (&context.branch, &context.head) {
+        (Some(branch), Some(head)) => out.push_str(&format!("- Branch: {} ({})\n", branch, head)),
+        (Some(branch), None) => out.push_str(&format!("- Branch: {} (no commits yet)\n", branch)),
+        (None, Some(head)) => out.push_str(&format!("- Detached HEAD at {}\n", head)),
+        (None, None) => {}
+    }
+
+    if !context.commits.is_empty() {
+        out.push_str("\n## Recent commits\n\n");
+        for commit in &context.commits {
+            out.push_str(&format!(
+                "- {} {} {} ({})\n",
+                commit.hash, commit.date, commit.subject, commit.author
+            ));
+        }
+    }
+
+    out.push_str("\n## Working tree\n\n");
+    if context.status.is_empty() {
+        out.push_str("Clean, no uncommitted changes.\n");
+    } else {
+        out.push_str(&format!("```\n{}\n```\n", context.status.join("\n")));
+    }
+    if !context.diff.trim().is_empty() {
+        out.push_str(&format!(
+            "\n## Uncommitted diff\n\n```diff\n{}\n```\n",
+            context.diff.trim_end()
+        ));
+        if context.diff_truncated {
+            out.push_str(&format!(
+                "\n(The diff was cut at {} characters.)\n",
+                MAX_DIFF_CHARS
+            ));
+        }
+    }
+
+    out.push_str(&format!("\n## Tracked files ({})\n\n", context.file_count));
+    if !context.files.is_empty() {
+        out.push_str(&format!("```\n{}\n```\n", context.files.join("\n")));
+    }
+    if context.files.len() < context.file_count {
+        out.push_str(&format!(
+            "\n({} more files not listed.)\n",
+            context.file_count - context.files.len()
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let output = "a1b2c3d\u{1f}Ada\u{1f}2026-10-01\u{1f}Fix parser: handle \u{1f} in subjects\n\
+                      e4f5a6b\u{1f}Linus\u{1f}2026-09-30\u{1f}Initial commit\n\
+                      malformed line\n";
+        let commits = parse_log(output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, "a1b2c3d");
+        assert_eq!(commits[0].subject, "Fix parser: handle \u{1f} in subjects");
+        assert_eq!(commits[1].author, "Linus");
+        assert_eq!(commits[1].date, "2026-09-30");
+    }
+
+    #[test]
+    fn test_caps() {
+        let many: Vec<String> = (0..MAX_FILES + 50).map(|i| format!("f{}", i)).collect();
+        assert_eq!(
+            cap_file_list(many.iter().map(String::as_str)).len(),
+            MAX_FILES
+        );
+
+        let long = "x".repeat(MAX_FILE_LIST_CHARS / 2);
+        let files = [long.as_str(), long.as_str(), long.as_str()];
+        assert_eq!(cap_file_list(files.into_iter()).len(), 1);
+
+        assert_eq!(truncate("héllo", 2), ("hé".to_string(), true));
+        assert_eq!(truncate("héllo", 5), ("héllo".to_string(), false));
+    }
+}
@@ -264,3 +264,26 @@ fn replace_ignore_case(haystack: &str, needle: &str, replacement: &str) -> Strin
     }
     result
 }
+
+/// Case-insensitive occurrences of any query term in `content`
+pub fn count_matches(content: &str, query_terms: &[String]) -> usize {
+    let content = content.to_lowercase();
+    query_terms
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .map(|t| content.matches(t.as_str()).count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_matches_ignores_case_and_blank_terms() {
+        let terms = vec!["rust".to_string(), " ".to_string(), "Cargo".to_string()];
+        assert_eq!(count_matches("Rust uses cargo; rust is fast", &terms), 3);
+        assert_eq!(count_matches("nothing here", &terms), 0);
+    }
+}
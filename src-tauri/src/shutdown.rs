@@ -0,0 +1,104 @@
+//! Graceful shutdown
+//!
+//! The first exit request is deferred so in-flight work can wind down: generations are
+//! stopped (their cancel path saves accumulated content as a cancelled message), then
+//! MCP servers, headless browsers and bash sessions are closed before the app exits.
+//! A second request (including our own `exit`) goes through immediately.
+
+use crate::commands::{self, AppState};
+use crate::web_fetch;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{ExitRequestApi, Manager};
+
+/// How long to wait for generations to save their partial content
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(5);
+const MCP_CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+const BROWSER_CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Handle `RunEvent::ExitRequested`
+pub fn on_exit_requested(app: &tauri::AppHandle, api: &ExitRequestApi) {
+    if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    api.prevent_exit();
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        finalize(&app).await;
+        app.exit(0);
+    });
+}
+
+async fn finalize(app: &tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+
+    let conversation_ids: Vec<String> = state
+        .generation_tasks
+        .read()
+        .await
+        .keys()
+        .cloned()
+        .collect();
+    if !conversation_ids.is_empty() {
+        tracing::info!(
+            "🛑 [shutdown] Stopping {} generation(s)",
+            conversation_ids.len()
+        );
+    }
+    for conversation_id in conversation_ids {
+        let _ = commands::chat::stop_generation(app.state(), app.clone(), conversation_id).await;
+    }
+    commands::chat::auxiliary::cancel_all_sync(&state);
+
+    // Fetches stuck in a headless browser would otherwise hold up their generation
+    if web_fetch::live_browser_count() > 0 {
+        let _ = tokio::task::spawn_blocking(web_fetch::close_all_browsers).await;
+    }
+
+    let generations = state.generation_tasks.clone();
+    let finished = wait_until(FINALIZE_TIMEOUT, || async {
+        generations.read().await.is_empty()
+    })
+    .await;
+    if !finished {
+        tracing::warn!(
+            "⚠️ [shutdown] Generations still running after {:?}, exiting anyway",
+            FINALIZE_TIMEOUT
+        );
+    }
+
+    state.mcp_manager.shutdown(MCP_CLOSE_TIMEOUT).await;
+    state.bash_session_manager.kill_all().await;
+
+    let browsers_closed = wait_until(BROWSER_CLOSE_TIMEOUT, || async {
+        web_fetch::live_browser_count() == 0
+    })
+    .await;
+    if !browsers_closed {
+        tracing::warn!("⚠️ [shutdown] Headless browser(s) still running on exit");
+    }
+
+    tracing::info!("👋 [shutdown] Cleanup finished");
+}
+
+/// Poll `done` until it returns true or `timeout` elapses. Returns whether it finished.
+async fn wait_until<F, Fut>(timeout: Duration, done: F) -> bool
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        if done().await {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
+use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use tauri::Manager;
 
 // ========== Content Hashing (Blake3) ==========
@@ -65,6 +67,14 @@ pub fn get_extension_for_content_type(content_type: &str) -> &'static str {
         "image/jpeg" => "jpg",
         "image/gif" => "gif",
         "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "audio/mpeg" => "mp3",
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/ogg" => "ogg",
+        "audio/webm" => "webm",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "m4a",
+        "audio/flac" => "flac",
         _ => "bin",
     }
 }
@@ -76,6 +86,12 @@ pub fn generate_fetch_storage_path(content_hash: &str, content_type: &str) -> St
     format!("fetch/{}.{}", content_hash, ext)
 }
 
+/// Generate storage path for a cached favicon; identical icons share one file
+pub fn generate_favicon_storage_path(content_hash: &str, content_type: &str) -> String {
+    let ext = get_extension_for_content_type(content_type);
+    format!("favicons/{}.{}", content_hash, ext)
+}
+
 /// Generate storage path for a file attachment using content hash for deduplication
 /// Uses hash as filename to enable content-based deduplication
 pub fn generate_file_storage_path(content_hash: &str, original_ext: &str) -> String {
@@ -146,6 +162,68 @@ pub fn read_content(app_handle: &tauri::AppHandle, storage_path: &str) -> Result
     Ok(content)
 }
 
+/// A window of a stored text file, cut on UTF-8 character boundaries
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentRange {
+    pub content: String,
+    /// Byte offset where `content` starts
+    pub offset: u64,
+    /// Byte offset of the next window; equals `total_size` after the last one
+    pub next_offset: u64,
+    pub total_size: u64,
+}
+
+/// Read about `length` bytes of text starting at byte `offset`, without loading the
+/// whole file. Pass the returned `next_offset` to continue.
+pub fn read_content_range(
+    app_handle: &tauri::AppHandle,
+    storage_path: &str,
+    offset: u64,
+    length: u64,
+) -> Result<ContentRange> {
+    let full_path = get_full_path(app_handle, storage_path)?;
+    read_text_range(&full_path, offset, length)
+}
+
+fn read_text_range(path: &Path, offset: u64, length: u64) -> Result<ContentRange> {
+    // Longest UTF-8 sequence minus one: enough extra bytes to finish a split character
+    const SPILL: u64 = 3;
+    let is_continuation = |b: u8| b & 0xC0 == 0x80;
+
+    let mut file = fs::File::open(path)?;
+    let total_size = file.metadata()?.len();
+    let offset = offset.min(total_size);
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    file.take(length + SPILL).read_to_end(&mut buf)?;
+
+    // Skip the tail of a character that started before `offset`
+    let start = buf
+        .iter()
+        .take(SPILL as usize)
+        .take_while(|b| is_continuation(**b))
+        .count();
+    // Back up to the start of a character split by the window, or move forward when
+    // the window is too small to hold even one
+    let mut end = (start + length as usize).min(buf.len());
+    while end > start && end < buf.len() && is_continuation(buf[end]) {
+        end -= 1;
+    }
+    if end == start && start < buf.len() {
+        end = start + 1;
+        while end < buf.len() && is_continuation(buf[end]) {
+            end += 1;
+        }
+    }
+
+    Ok(ContentRange {
+        content: String::from_utf8_lossy(&buf[start..end]).into_owned(),
+        offset: offset + start as u64,
+        next_offset: offset + end as u64,
+        total_size,
+    })
+}
+
 /// Read binary content from a storage path
 pub fn read_binary(app_handle: &tauri::AppHandle, storage_path: &str) -> Result<Vec<u8>> {
     let full_path = get_full_path(app_handle, storage_path)?;
@@ -207,6 +285,7 @@ mod tests {
         assert_eq!(get_extension_for_content_type("text/plain"), "txt");
         assert_eq!(get_extension_for_content_type("application/json"), "json");
         assert_eq!(get_extension_for_content_type("image/png"), "png");
+        assert_eq!(get_extension_for_content_type("image/x-icon"), "ico");
         assert_eq!(get_extension_for_content_type("unknown/type"), "bin");
     }
 
@@ -217,6 +296,33 @@ mod tests {
         assert_eq!(path, "fetch/a1b2c3d4e5f6.md");
     }
 
+    #[test]
+    fn test_read_text_range_keeps_characters_whole() {
+        let path = std::env::temp_dir().join(format!("chatshell-range-{}.md", std::process::id()));
+        // "é" and "中" are multi-byte; windows must never split them
+        fs::write(&path, "aé中b").unwrap();
+
+        let first = read_text_range(&path, 0, 2).unwrap();
+        assert_eq!(first.content, "a");
+        assert_eq!(first.next_offset, 1);
+        assert_eq!(first.total_size, 7);
+
+        let second = read_text_range(&path, first.next_offset, 4).unwrap();
+        assert_eq!(second.content, "é");
+
+        // A window smaller than the next character still makes progress
+        let third = read_text_range(&path, second.next_offset, 1).unwrap();
+        assert_eq!(third.content, "中");
+
+        let rest = read_text_range(&path, third.next_offset, 100).unwrap();
+        assert_eq!(rest.content, "b");
+        assert_eq!(rest.next_offset, rest.total_size);
+
+        // Starting mid-character skips to the next whole one
+        assert_eq!(read_text_range(&path, 2, 100).unwrap().content, "中b");
+        fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_generate_file_storage_path() {
         let hash = "x1y2z3";
@@ -1,6 +1,8 @@
 use lazy_static::lazy_static;
 /// Utility for parsing thinking content from LLM responses
-/// Supports various thinking markers used by reasoning models like DeepSeek-R1
+/// Supports the reasoning delimiters used by local and hosted reasoning models
+/// (DeepSeek-R1, QwQ, Magistral, gpt-oss, ...). Which delimiters apply is picked
+/// per model family with [`formats_for_model`].
 use regex::Regex;
 
 lazy_static! {
@@ -10,6 +12,81 @@ lazy_static! {
     static ref THINKING_TAG_REGEX: Regex = Regex::new(r"(?is)<thinking>(.*?)</thinking>").unwrap();
     // Match <reasoning>...</reasoning> tags (case insensitive)
     static ref REASONING_TAG_REGEX: Regex = Regex::new(r"(?is)<reasoning>(.*?)</reasoning>").unwrap();
+    // Match [THINK]...[/THINK] blocks (Mistral Magistral)
+    static ref BRACKET_THINK_REGEX: Regex = Regex::new(r"(?s)\[THINK\](.*?)\[/THINK\]").unwrap();
+    // Match the analysis channel of the harmony format (gpt-oss), which ends at <|end|>,
+    // the next message header, or the end of the text
+    static ref HARMONY_ANALYSIS_REGEX: Regex = Regex::new(
+        r"(?s)(?:<\|start\|>\w*)?<\|channel\|>\s*analysis\s*<\|message\|>(.*?)(?:<\|end\|>|<\|start\|>\w*|\z)"
+    )
+    .unwrap();
+    // Remaining harmony framing around the final answer
+    static ref HARMONY_FRAMING_REGEX: Regex = Regex::new(
+        r"<\|start\|>\w*|<\|channel\|>\s*\w+\s*(?:<\|constrain\|>\s*\w+\s*)?<\|message\|>|<\|(?:end|return|call)\|>"
+    )
+    .unwrap();
+}
+
+/// A reasoning delimiter style
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThinkingFormat {
+    /// `<think>...</think>` (DeepSeek-R1, QwQ, Qwen3)
+    ThinkTag,
+    /// `<thinking>...</thinking>`
+    ThinkingTag,
+    /// `<reasoning>...</reasoning>`
+    ReasoningTag,
+    /// `[THINK]...[/THINK]` (Magistral)
+    BracketThink,
+    /// `<|channel|>analysis<|message|>...<|end|>` channels (gpt-oss harmony format)
+    HarmonyChannel,
+}
+
+impl ThinkingFormat {
+    fn regex(self) -> &'static Regex {
+        match self {
+            ThinkingFormat::ThinkTag => &THINK_TAG_REGEX,
+            ThinkingFormat::ThinkingTag => &THINKING_TAG_REGEX,
+            ThinkingFormat::ReasoningTag => &REASONING_TAG_REGEX,
+            ThinkingFormat::BracketThink => &BRACKET_THINK_REGEX,
+            ThinkingFormat::HarmonyChannel => &HARMONY_ANALYSIS_REGEX,
+        }
+    }
+}
+
+/// XML-style tags, recognized for every model
+pub const DEFAULT_FORMATS: &[ThinkingFormat] = &[
+    ThinkingFormat::ThinkTag,
+    ThinkingFormat::ThinkingTag,
+    ThinkingFormat::ReasoningTag,
+];
+
+const MISTRAL_FORMATS: &[ThinkingFormat] = &[
+    ThinkingFormat::BracketThink,
+    ThinkingFormat::ThinkTag,
+    ThinkingFormat::ThinkingTag,
+    ThinkingFormat::ReasoningTag,
+];
+
+const HARMONY_FORMATS: &[ThinkingFormat] = &[
+    ThinkingFormat::HarmonyChannel,
+    ThinkingFormat::ThinkTag,
+    ThinkingFormat::ThinkingTag,
+    ThinkingFormat::ReasoningTag,
+];
+
+/// Reasoning delimiters to look for in a model's output, by model family.
+/// Matches on the model id, so local tags like `magistral:24b` or
+/// `openai/gpt-oss-20b` are detected too.
+pub fn formats_for_model(model_id: &str) -> &'static [ThinkingFormat] {
+    let id = model_id.to_ascii_lowercase();
+    if id.contains("gpt-oss") || id.contains("gpt_oss") {
+        HARMONY_FORMATS
+    } else if id.contains("magistral") || id.contains("mistral") || id.contains("devstral") {
+        MISTRAL_FORMATS
+    } else {
+        DEFAULT_FORMATS
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -18,41 +95,30 @@ pub struct ParsedContent {
     pub thinking_content: Option<String>,
 }
 
-/// Parse thinking content from a response string
-/// Extracts content within thinking tags and removes them from the main content
-pub fn parse_thinking_content(text: &str) -> ParsedContent {
+/// Parse thinking content delimited by any of `formats` from a response string
+/// Extracts content within thinking delimiters and removes them from the main content
+pub fn parse_thinking_content_with(text: &str, formats: &[ThinkingFormat]) -> ParsedContent {
     let mut thinking_parts = Vec::new();
     let mut cleaned_content = text.to_string();
 
-    // Extract <think>...</think> content
-    for cap in THINK_TAG_REGEX.captures_iter(text) {
-        if let Some(thinking) = cap.get(1) {
-            thinking_parts.push(thinking.as_str().trim().to_string());
+    for format in formats {
+        let regex = format.regex();
+        for cap in regex.captures_iter(&cleaned_content) {
+            if let Some(thinking) = cap.get(1) {
+                let thinking = thinking.as_str().trim();
+                if !thinking.is_empty() {
+                    thinking_parts.push(thinking.to_string());
+                }
+            }
         }
+        cleaned_content = regex.replace_all(&cleaned_content, "").to_string();
     }
-    cleaned_content = THINK_TAG_REGEX
-        .replace_all(&cleaned_content, "")
-        .to_string();
-
-    // Extract <thinking>...</thinking> content
-    for cap in THINKING_TAG_REGEX.captures_iter(text) {
-        if let Some(thinking) = cap.get(1) {
-            thinking_parts.push(thinking.as_str().trim().to_string());
-        }
-    }
-    cleaned_content = THINKING_TAG_REGEX
-        .replace_all(&cleaned_content, "")
-        .to_string();
-
-    // Extract <reasoning>...</reasoning> content
-    for cap in REASONING_TAG_REGEX.captures_iter(text) {
-        if let Some(reasoning) = cap.get(1) {
-            thinking_parts.push(reasoning.as_str().trim().to_string());
-        }
+
+    if formats.contains(&ThinkingFormat::HarmonyChannel) {
+        cleaned_content = HARMONY_FRAMING_REGEX
+            .replace_all(&cleaned_content, "")
+            .to_string();
     }
-    cleaned_content = REASONING_TAG_REGEX
-        .replace_all(&cleaned_content, "")
-        .to_string();
 
     // Clean up the main content (remove extra whitespace)
     cleaned_content = cleaned_content.trim().to_string();
@@ -74,6 +140,10 @@ pub fn parse_thinking_content(text: &str) -> ParsedContent {
 mod tests {
     use super::*;
 
+    fn parse_thinking_content(text: &str) -> ParsedContent {
+        parse_thinking_content_with(text, DEFAULT_FORMATS)
+    }
+
     #[test]
     fn test_parse_think_tags() {
         let text = "<think>Let me consider this carefully...</think>The answer is 42.";
@@ -114,4 +184,38 @@ mod tests {
         assert_eq!(parsed.content, text);
         assert_eq!(parsed.thinking_content, None);
     }
+
+    #[test]
+    fn test_parse_bracket_think() {
+        let text = "[THINK]Check the units first.[/THINK]It is 3 meters.";
+        let parsed = parse_thinking_content_with(text, formats_for_model("magistral:24b"));
+        assert_eq!(parsed.content, "It is 3 meters.");
+        assert_eq!(
+            parsed.thinking_content,
+            Some("Check the units first.".to_string())
+        );
+
+        // Other families leave brackets alone
+        let parsed = parse_thinking_content_with(text, formats_for_model("llama3.1:8b"));
+        assert_eq!(parsed.content, text);
+    }
+
+    #[test]
+    fn test_parse_harmony_channels() {
+        let text = "<|channel|>analysis<|message|>User wants a greeting.<|end|>\
+<|start|>assistant<|channel|>final<|message|>Hello!<|return|>";
+        let parsed = parse_thinking_content_with(text, formats_for_model("openai/gpt-oss-20b"));
+        assert_eq!(parsed.content, "Hello!");
+        assert_eq!(
+            parsed.thinking_content,
+            Some("User wants a greeting.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formats_for_model() {
+        assert_eq!(formats_for_model("deepseek-r1:14b"), DEFAULT_FORMATS);
+        assert_eq!(formats_for_model("gpt-oss:120b"), HARMONY_FORMATS);
+        assert_eq!(formats_for_model("Magistral-Small-2509"), MISTRAL_FORMATS);
+    }
 }
@@ -0,0 +1,106 @@
+//! Attachment thumbnails
+//!
+//! Small JPEG previews of image attachments, stored next to the originals as
+//! `thumbnails/{content_hash}.jpg` so every attachment sharing the same content
+//! shares one thumbnail.
+
+use anyhow::Result;
+
+/// Longest side of a thumbnail in pixels
+pub const THUMBNAIL_MAX_DIMENSION: u32 = 256;
+
+const THUMBNAIL_JPEG_QUALITY: u8 = 75;
+
+/// Storage path of the thumbnail for a given content hash
+pub fn thumbnail_storage_path(content_hash: &str) -> String {
+    format!("thumbnails/{}.jpg", content_hash)
+}
+
+/// Whether thumbnails are generated for this MIME type
+pub fn supports_thumbnail(mime_type: &str) -> bool {
+    matches!(
+        mime_type,
+        "image/png" | "image/jpeg" | "image/gif" | "image/webp"
+    )
+}
+
+/// Render a JPEG thumbnail; transparent areas are flattened onto white
+pub fn render_thumbnail(bytes: &[u8]) -> Result<Vec<u8>> {
+    let img = image::load_from_memory(bytes)?;
+    let img = if img.width() > THUMBNAIL_MAX_DIMENSION || img.height() > THUMBNAIL_MAX_DIMENSION {
+        img.thumbnail(THUMBNAIL_MAX_DIMENSION, THUMBNAIL_MAX_DIMENSION)
+    } else {
+        img
+    };
+
+    let mut rgba = img.to_rgba8();
+    for pixel in rgba.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        for channel in 0..3 {
+            pixel[channel] = ((pixel[channel] as u32 * alpha + 255 * (255 - alpha)) / 255) as u8;
+        }
+    }
+    let rgb = image::DynamicImage::ImageRgba8(rgba).to_rgb8();
+
+    let mut output = std::io::Cursor::new(Vec::new());
+    let encoder =
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut output, THUMBNAIL_JPEG_QUALITY);
+    rgb.write_with_encoder(encoder)?;
+    Ok(output.into_inner())
+}
+
+/// Create the thumbnail for `bytes` unless one already exists. Returns its storage path.
+pub fn ensure_thumbnail(
+    app_handle: &tauri::AppHandle,
+    content_hash: &str,
+    bytes: &[u8],
+) -> Result<String> {
+    let storage_path = thumbnail_storage_path(content_hash);
+    if !crate::storage::file_exists(app_handle, &storage_path)? {
+        let thumbnail = render_thumbnail(bytes)?;
+        crate::storage::write_binary(app_handle, &storage_path, &thumbnail)?;
+    }
+    Ok(storage_path)
+}
+
+/// Generate a thumbnail in the background; failures are only logged since the
+/// thumbnail is recreated on demand when missing
+pub fn spawn_thumbnail(app_handle: &tauri::AppHandle, content_hash: String, bytes: Vec<u8>) {
+    let app_handle = app_handle.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = ensure_thumbnail(&app_handle, &content_hash, &bytes) {
+            tracing::warn!("⚠️ [thumbnails] Failed to create thumbnail: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_storage_path() {
+        assert_eq!(thumbnail_storage_path("abc123"), "thumbnails/abc123.jpg");
+    }
+
+    #[test]
+    fn test_render_thumbnail_downscales() {
+        let img = image::RgbaImage::from_pixel(1024, 512, image::Rgba([10, 20, 30, 0]));
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+
+        let thumbnail = render_thumbnail(&png.into_inner()).unwrap();
+        let decoded = image::load_from_memory(&thumbnail).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (256, 128));
+        // Fully transparent pixels become white
+        assert!(decoded.to_rgb8().get_pixel(0, 0)[0] > 240);
+    }
+
+    #[test]
+    fn test_supports_thumbnail() {
+        assert!(supports_thumbnail("image/webp"));
+        assert!(!supports_thumbnail("application/pdf"));
+    }
+}
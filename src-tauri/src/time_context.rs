@@ -0,0 +1,133 @@
+//! Local time, time zone and locale for the system prompt
+//!
+//! Models otherwise answer time-sensitive questions "as of" their training data. The
+//! date is always part of the environment block; with [`TIME_CONTEXT_ENABLED_KEY`] set,
+//! the time of day, time zone and locale are added as well.
+
+use crate::db::Database;
+use chrono::{DateTime, FixedOffset, Local};
+
+/// Settings key: "true" adds local time, time zone and locale to the system prompt
+pub const TIME_CONTEXT_ENABLED_KEY: &str = "time_context_enabled";
+
+/// Settings key of the UI language, used when the system locale is unknown
+const UI_LANGUAGE_KEY: &str = "language";
+
+/// Environment block lines describing the current date, plus the time, time zone and
+/// locale when enabled in settings
+pub(crate) async fn environment_lines(db: &Database) -> String {
+    let now = Local::now().fixed_offset();
+    if !time_context_enabled(db).await {
+        return format_lines(now, None, None, false);
+    }
+
+    let locale = match system_locale() {
+        Some(locale) => Some(locale),
+        None => db.get_setting(UI_LANGUAGE_KEY).await.ok().flatten(),
+    };
+    format_lines(now, system_time_zone().as_deref(), locale.as_deref(), true)
+}
+
+async fn time_context_enabled(db: &Database) -> bool {
+    matches!(
+        db.get_setting(TIME_CONTEXT_ENABLED_KEY)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("true")
+    )
+}
+
+fn format_lines(
+    now: DateTime<FixedOffset>,
+    time_zone: Option<&str>,
+    locale: Option<&str>,
+    detailed: bool,
+) -> String {
+    let today = now.format("%a %b %d %Y");
+    if !detailed {
+        return format!("- Today's date: {today}");
+    }
+
+    let offset = now.format("UTC%:z");
+    let mut lines = format!(
+        "- Today's date: {today}\n- Current local time: {}\n- Time zone: ",
+        now.format("%H:%M")
+    );
+    match time_zone {
+        Some(zone) => lines.push_str(&format!("{zone} ({offset})")),
+        None => lines.push_str(&offset.to_string()),
+    }
+    if let Some(locale) = locale {
+        lines.push_str(&format!("\n- Locale: {locale}"));
+    }
+    lines
+}
+
+/// IANA name of the system time zone, from `TZ` or the `/etc/localtime` link
+fn system_time_zone() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        let tz = tz.trim_start_matches(':');
+        if !tz.is_empty() {
+            return Some(tz.to_string());
+        }
+    }
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    zone_from_zoneinfo_path(&target.to_string_lossy())
+}
+
+/// `/usr/share/zoneinfo/Europe/Berlin` → `Europe/Berlin`
+fn zone_from_zoneinfo_path(path: &str) -> Option<String> {
+    let (_, zone) = path.rsplit_once("zoneinfo/")?;
+    (!zone.is_empty()).then(|| zone.to_string())
+}
+
+/// Locale from the POSIX environment variables, as a BCP 47 tag
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find_map(|value| normalize_locale(&value))
+}
+
+/// `de_DE.UTF-8` → `de-DE`; the `C` and `POSIX` locales say nothing about the user
+fn normalize_locale(value: &str) -> Option<String> {
+    let tag = value.split(['.', '@']).next()?.trim();
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        return None;
+    }
+    Some(tag.replace('_', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T14:03:00+02:00").unwrap();
+        assert_eq!(
+            format_lines(now, None, None, false),
+            "- Today's date: Fri Oct 16 2026"
+        );
+        assert_eq!(
+            format_lines(now, Some("Europe/Berlin"), Some("de-DE"), true),
+            "- Today's date: Fri Oct 16 2026\n- Current local time: 14:03\n\
+- Time zone: Europe/Berlin (UTC+02:00)\n- Locale: de-DE"
+        );
+        assert!(format_lines(now, None, None, true).ends_with("- Time zone: UTC+02:00"));
+    }
+
+    #[test]
+    fn test_locale_and_zone_parsing() {
+        assert_eq!(normalize_locale("de_DE.UTF-8"), Some("de-DE".to_string()));
+        assert_eq!(normalize_locale("sr_RS@latin"), Some("sr-RS".to_string()));
+        assert_eq!(normalize_locale("C.UTF-8"), None);
+        assert_eq!(
+            zone_from_zoneinfo_path("/var/db/timezone/zoneinfo/America/New_York"),
+            Some("America/New_York".to_string())
+        );
+        assert_eq!(zone_from_zoneinfo_path("/etc/localtime"), None);
+    }
+}
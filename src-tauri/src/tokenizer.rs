@@ -1,8 +1,14 @@
 use jieba_rs::Jieba;
 use lazy_static::lazy_static;
+use tiktoken_rs::CoreBPE;
 
 lazy_static! {
     static ref JIEBA: Jieba = Jieba::new();
+    // o200k_base (GPT-4o family). Other vocabularies land within a few percent,
+    // which is close enough for budgeting context.
+    static ref BPE: Option<CoreBPE> = tiktoken_rs::o200k_base()
+        .inspect_err(|e| tracing::warn!("⚠️ [tokenizer] Failed to load BPE vocabulary: {}", e))
+        .ok();
 }
 
 /// Tokenize text for indexing in FTS. Uses search mode for overlapping segments
@@ -25,3 +31,12 @@ pub fn tokenize_query(query: &str) -> String {
         .collect::<Vec<&str>>()
         .join(" ")
 }
+
+/// Number of model tokens in `text`. Falls back to ~4 characters per token if the
+/// vocabulary couldn't be loaded.
+pub fn count_tokens(text: &str) -> usize {
+    match BPE.as_ref() {
+        Some(bpe) => bpe.encode_ordinary(text).len(),
+        None => text.chars().count().div_ceil(4),
+    }
+}
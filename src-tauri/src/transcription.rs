@@ -0,0 +1,232 @@
+//! Audio transcription
+//!
+//! Two engines are supported:
+//! - `whisper_cpp`: a local whisper.cpp CLI (`whisper-cli`) with a user-supplied ggml model.
+//!   Stock builds only decode WAV; builds with ffmpeg support also accept mp3/ogg/m4a.
+//! - `provider`: an OpenAI-compatible `/audio/transcriptions` endpoint (OpenAI, Groq, ...)
+//!   on one of the configured providers.
+
+use anyhow::Result;
+use std::path::Path;
+use std::time::Duration;
+
+/// Settings key: "whisper_cpp" or "provider" (defaults to whisper_cpp)
+pub const TRANSCRIPTION_ENGINE_KEY: &str = "transcription_engine";
+/// Settings key: path to the whisper.cpp CLI binary (defaults to `whisper-cli` on PATH)
+pub const WHISPER_CPP_BINARY_KEY: &str = "whisper_cpp_binary";
+/// Settings key: path to the ggml model file used by whisper.cpp
+pub const WHISPER_CPP_MODEL_KEY: &str = "whisper_cpp_model";
+/// Settings key: provider id whose STT endpoint is used by the `provider` engine
+pub const TRANSCRIPTION_PROVIDER_ID_KEY: &str = "transcription_provider_id";
+/// Settings key: model name for the `provider` engine (defaults to `whisper-1`)
+pub const TRANSCRIPTION_MODEL_KEY: &str = "transcription_model";
+/// Settings key: optional ISO-639-1 language hint (auto-detected when unset)
+pub const TRANSCRIPTION_LANGUAGE_KEY: &str = "transcription_language";
+
+pub const DEFAULT_WHISPER_CPP_BINARY: &str = "whisper-cli";
+pub const DEFAULT_TRANSCRIPTION_MODEL: &str = "whisper-1";
+
+/// Upper bound for a single transcription (long memos on CPU can be slow)
+const TRANSCRIPTION_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TranscriptionEngine {
+    #[default]
+    WhisperCpp,
+    Provider,
+}
+
+impl TranscriptionEngine {
+    pub fn id(&self) -> &'static str {
+        match self {
+            TranscriptionEngine::WhisperCpp => "whisper_cpp",
+            TranscriptionEngine::Provider => "provider",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "whisper_cpp" => Some(TranscriptionEngine::WhisperCpp),
+            "provider" => Some(TranscriptionEngine::Provider),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a MIME type is treated as an audio attachment
+pub fn is_audio_mime_type(mime_type: &str) -> bool {
+    mime_type.starts_with("audio/")
+}
+
+/// Transcribe an audio file with the local whisper.cpp CLI
+pub async fn transcribe_with_whisper_cpp(
+    binary: &str,
+    model_path: &str,
+    audio_path: &Path,
+    language: Option<&str>,
+) -> Result<String> {
+    if !Path::new(model_path).exists() {
+        return Err(anyhow::anyhow!(
+            "whisper.cpp model not found: {}",
+            model_path
+        ));
+    }
+
+    let mut cmd = tokio::process::Command::new(binary);
+    // -nt: no timestamps, -np: no progress/log output (transcript only on stdout)
+    cmd.arg("-m")
+        .arg(model_path)
+        .arg("-f")
+        .arg(audio_path)
+        .arg("-nt")
+        .arg("-np")
+        .arg("-l")
+        .arg(language.unwrap_or("auto"))
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(TRANSCRIPTION_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| anyhow::anyhow!("whisper.cpp timed out"))?
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", binary, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!(
+            "whisper.cpp exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+
+    Ok(clean_transcript(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Transcribe audio bytes with an OpenAI-compatible `/audio/transcriptions` endpoint
+pub async fn transcribe_with_provider(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    file_name: &str,
+    mime_type: &str,
+    bytes: &[u8],
+    language: Option<&str>,
+) -> Result<String> {
+    let url = format!("{}/audio/transcriptions", base_url.trim_end_matches('/'));
+    let boundary = format!("chatshell-{}", uuid::Uuid::now_v7().simple());
+
+    let mut fields = vec![("model", model), ("response_format", "text")];
+    if let Some(lang) = language {
+        fields.push(("language", lang));
+    }
+    let body = build_multipart_body(&boundary, &fields, file_name, mime_type, bytes);
+
+    let mut request = reqwest::Client::new()
+        .post(&url)
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .timeout(TRANSCRIPTION_TIMEOUT)
+        .body(body);
+    if let Some(key) = api_key.filter(|k| !k.is_empty()) {
+        request = request.bearer_auth(key);
+    }
+
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "[HTTP {}] Transcription request failed: {}",
+            status.as_u16(),
+            body
+        ));
+    }
+
+    Ok(clean_transcript(&response.text().await?))
+}
+
+/// Encode text fields and a single file as a multipart/form-data body
+fn build_multipart_body(
+    boundary: &str,
+    fields: &[(&str, &str)],
+    file_name: &str,
+    mime_type: &str,
+    bytes: &[u8],
+) -> Vec<u8> {
+    let mut body = Vec::with_capacity(bytes.len() + 512);
+
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                boundary, name, value
+            )
+            .as_bytes(),
+        );
+    }
+
+    let safe_name = file_name.replace(['"', '\r', '\n'], "_");
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            boundary, safe_name, mime_type
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    body
+}
+
+/// Collapse whisper's line-per-segment output into plain text
+fn clean_transcript(raw: &str) -> String {
+    raw.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_ids_round_trip() {
+        for engine in [
+            TranscriptionEngine::WhisperCpp,
+            TranscriptionEngine::Provider,
+        ] {
+            assert_eq!(TranscriptionEngine::from_id(engine.id()), Some(engine));
+        }
+        assert_eq!(TranscriptionEngine::from_id("unknown"), None);
+    }
+
+    #[test]
+    fn test_build_multipart_body() {
+        let body = build_multipart_body(
+            "b",
+            &[("model", "whisper-1")],
+            "memo.m4a",
+            "audio/mp4",
+            b"DATA",
+        );
+        let text = String::from_utf8(body).unwrap();
+
+        assert!(text.starts_with(
+            "--b\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\nwhisper-1\r\n"
+        ));
+        assert!(text.contains("name=\"file\"; filename=\"memo.m4a\"\r\nContent-Type: audio/mp4"));
+        assert!(text.ends_with("\r\n\r\nDATA\r\n--b--\r\n"));
+    }
+
+    #[test]
+    fn test_clean_transcript() {
+        assert_eq!(
+            clean_transcript("\n Hello there. \n\n How are you?\n"),
+            "Hello there. How are you?"
+        );
+    }
+}
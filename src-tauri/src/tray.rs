@@ -0,0 +1,219 @@
+//! System tray icon
+//!
+//! Shows how many generations are running, the most recent conversations, and quick
+//! actions (new chat, stop all generations). The menu is rebuilt whenever a
+//! generation starts, finishes, fails or is stopped, and when conversations change.
+
+use crate::commands::{self, AppState};
+use crate::events::{self, NavigateConversation, TrayNewChat};
+use std::time::Duration;
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{Listener, Manager, Wry};
+
+const TRAY_ID: &str = "main";
+const RECENT_CONVERSATIONS: usize = 5;
+
+/// Events after which the tray is rebuilt
+const REFRESH_EVENTS: &[&str] = &[
+    "generation-started",
+    "generation-stopped",
+    "chat-complete",
+    "chat-error",
+    "conversation-updated",
+];
+
+/// Tasks are removed from `generation_tasks` right after their final event is
+/// emitted, so refreshes wait briefly before counting
+const REFRESH_DELAY: Duration = Duration::from_millis(300);
+
+const MENU_SHOW: &str = "show";
+const MENU_NEW_CHAT: &str = "new_chat";
+const MENU_STOP_ALL: &str = "stop_all";
+const MENU_QUIT: &str = "quit";
+const CONVERSATION_PREFIX: &str = "conversation:";
+
+/// Create the tray icon and keep it in sync with generation events
+pub fn init(app: &tauri::AppHandle) -> tauri::Result<()> {
+    let menu = Menu::with_items(
+        app,
+        &[&MenuItem::with_id(
+            app,
+            MENU_SHOW,
+            "Open ChatShell",
+            true,
+            None::<&str>,
+        )?],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("ChatShell")
+        .menu(&menu)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    for event in REFRESH_EVENTS {
+        let app_for_listener = app.clone();
+        app.listen_any(*event, move |_| {
+            let app = app_for_listener.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(REFRESH_DELAY).await;
+                refresh(&app).await;
+            });
+        });
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { refresh(&app).await });
+    Ok(())
+}
+
+/// Rebuild the tray menu and tooltip from the current state
+pub async fn refresh(app: &tauri::AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let state: tauri::State<'_, AppState> = app.state();
+    let active = state.generation_tasks.read().await.len();
+    let recent = state
+        .db
+        .list_conversations()
+        .await
+        .map(|c| c.into_iter().take(RECENT_CONVERSATIONS).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    match build_menu(app, active, &recent) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+            let _ = tray.set_tooltip(Some(format!("ChatShell — {}", status_label(active))));
+        }
+        Err(e) => tracing::warn!("⚠️ [tray] Failed to rebuild menu: {}", e),
+    }
+}
+
+fn build_menu(
+    app: &tauri::AppHandle,
+    active: usize,
+    recent: &[crate::models::Conversation],
+) -> tauri::Result<Menu<Wry>> {
+    let status = MenuItem::with_id(app, "status", status_label(active), false, None::<&str>)?;
+    let show = MenuItem::with_id(app, MENU_SHOW, "Open ChatShell", true, None::<&str>)?;
+    let new_chat = MenuItem::with_id(app, MENU_NEW_CHAT, "New Chat", true, None::<&str>)?;
+    let stop_all = MenuItem::with_id(
+        app,
+        MENU_STOP_ALL,
+        "Stop All Generations",
+        active > 0,
+        None::<&str>,
+    )?;
+    let quit = MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?;
+
+    let conversation_items = recent
+        .iter()
+        .map(|c| {
+            MenuItem::with_id(
+                app,
+                format!("{}{}", CONVERSATION_PREFIX, c.id),
+                truncate_title(&c.title),
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let conversation_refs: Vec<&dyn IsMenuItem<Wry>> = conversation_items
+        .iter()
+        .map(|i| i as &dyn IsMenuItem<Wry>)
+        .collect();
+    let recent_menu = Submenu::with_items(
+        app,
+        "Recent Conversations",
+        !conversation_refs.is_empty(),
+        &conversation_refs,
+    )?;
+
+    Menu::with_items(
+        app,
+        &[
+            &status,
+            &PredefinedMenuItem::separator(app)?,
+            &show,
+            &new_chat,
+            &recent_menu,
+            &stop_all,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )
+}
+
+fn handle_menu_event(app: &tauri::AppHandle, id: &str) {
+    match id {
+        MENU_SHOW => show_main_window(app),
+        MENU_NEW_CHAT => {
+            show_main_window(app);
+            events::emit(app, TrayNewChat {});
+        }
+        MENU_STOP_ALL => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move { stop_all(&app).await });
+        }
+        MENU_QUIT => app.exit(0),
+        other => {
+            if let Some(conversation_id) = other.strip_prefix(CONVERSATION_PREFIX) {
+                show_main_window(app);
+                events::emit(
+                    app,
+                    NavigateConversation {
+                        conversation_id: conversation_id.to_string(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+async fn stop_all(app: &tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let conversation_ids: Vec<String> = state
+        .generation_tasks
+        .read()
+        .await
+        .keys()
+        .cloned()
+        .collect();
+    tracing::info!(
+        "🛑 [tray] Stopping {} generation(s)",
+        conversation_ids.len()
+    );
+    for conversation_id in conversation_ids {
+        let _ = commands::chat::stop_generation(app.state(), app.clone(), conversation_id).await;
+    }
+}
+
+pub(crate) fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn status_label(active: usize) -> String {
+    match active {
+        0 => "No active generations".to_string(),
+        1 => "1 generation running".to_string(),
+        n => format!("{} generations running", n),
+    }
+}
+
+fn truncate_title(title: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    if title.chars().count() <= MAX_CHARS {
+        title.to_string()
+    } else {
+        format!("{}…", title.chars().take(MAX_CHARS - 1).collect::<String>())
+    }
+}
@@ -0,0 +1,337 @@
+//! Unified diffs suggested by models
+//!
+//! Parses a single-file unified diff (optionally inside a ```diff fence) and applies it
+//! to a file's content. Models rarely get hunk line counts right, so the counts are
+//! ignored: each hunk is located by its context and removed lines, preferring the
+//! position its header names, and matched exactly before falling back to ignoring
+//! trailing whitespace. Line endings and the final newline of the file are preserved.
+
+/// A parsed single-file diff
+#[derive(Debug, PartialEq)]
+pub struct Patch {
+    hunks: Vec<Hunk>,
+    /// `--- /dev/null`: the diff creates the file
+    creates_file: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Hunk {
+    /// 1-based line the hunk starts at in the original file (0 for an empty file)
+    old_start: usize,
+    lines: Vec<HunkLine>,
+}
+
+#[derive(Debug, PartialEq)]
+enum HunkLine {
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Result of applying a patch
+#[derive(Debug, PartialEq)]
+pub struct Applied {
+    pub content: String,
+    pub hunks: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+impl Patch {
+    pub fn creates_file(&self) -> bool {
+        self.creates_file
+    }
+
+    /// Apply to the current content of the file (`None` when it doesn't exist)
+    pub fn apply(&self, original: Option<&str>) -> Result<Applied, String> {
+        let original = match (original, self.creates_file) {
+            (Some(_), true) => return Err("The diff creates a file that already exists".into()),
+            (None, false) => return Err("The file to patch doesn't exist".into()),
+            (original, _) => original.unwrap_or_default(),
+        };
+        let line_ending = if original.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let ends_with_newline = original.is_empty() || original.ends_with('\n');
+        let mut lines: Vec<String> = if original.is_empty() {
+            Vec::new()
+        } else {
+            original
+                .trim_end_matches('\n')
+                .split('\n')
+                .map(|line| line.trim_end_matches('\r').to_string())
+                .collect()
+        };
+
+        let (mut additions, mut deletions) = (0, 0);
+        // Hunks apply in order; `offset` tracks how earlier hunks shifted the lines
+        let mut offset: isize = 0;
+        let mut min_start = 0;
+        for (index, hunk) in self.hunks.iter().enumerate() {
+            let old: Vec<&str> = hunk
+                .lines
+                .iter()
+                .filter_map(|line| match line {
+                    HunkLine::Context(text) | HunkLine::Removed(text) => Some(text.as_str()),
+                    HunkLine::Added(_) => None,
+                })
+                .collect();
+            let new: Vec<String> = hunk
+                .lines
+                .iter()
+                .filter_map(|line| match line {
+                    HunkLine::Context(text) | HunkLine::Added(text) => Some(text.clone()),
+                    HunkLine::Removed(_) => None,
+                })
+                .collect();
+
+            let expected = (hunk.old_start.saturating_sub(1) as isize + offset).max(0) as usize;
+            let start = find_block(&lines, &old, expected, min_start).ok_or_else(|| {
+                format!(
+                    "Hunk {} doesn't match the file (expected near line {})",
+                    index + 1,
+                    hunk.old_start
+                )
+            })?;
+            lines.splice(start..start + old.len(), new.iter().cloned());
+            offset += new.len() as isize - old.len() as isize;
+            min_start = start + new.len();
+
+            additions += hunk
+                .lines
+                .iter()
+                .filter(|line| matches!(line, HunkLine::Added(_)))
+                .count();
+            deletions += hunk
+                .lines
+                .iter()
+                .filter(|line| matches!(line, HunkLine::Removed(_)))
+                .count();
+        }
+
+        let mut content = lines.join(line_ending);
+        if ends_with_newline && !content.is_empty() {
+            content.push_str(line_ending);
+        }
+        Ok(Applied {
+            content,
+            hunks: self.hunks.len(),
+            additions,
+            deletions,
+        })
+    }
+}
+
+/// Parse a unified diff for one file
+pub fn parse(diff: &str) -> Result<Patch, String> {
+    let diff = strip_fence(diff);
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut creates_file = false;
+    let mut files = 0;
+
+    let lines: Vec<&str> = diff
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        // A file header is a `---` line directly followed by a `+++` line; anything
+        // else starting with `---` inside a hunk is a removed line
+        if let Some(old) = line.strip_prefix("--- ")
+            && let Some(new) = lines.get(index).and_then(|next| next.strip_prefix("+++ "))
+        {
+            index += 1;
+            files += 1;
+            if new.trim() == "/dev/null" {
+                return Err("Deleting files isn't supported".to_string());
+            }
+            creates_file = old.trim() == "/dev/null";
+            continue;
+        }
+        if let Some(header) = line.strip_prefix("@@") {
+            hunks.push(Hunk {
+                old_start: parse_hunk_start(header)
+                    .ok_or_else(|| format!("Malformed hunk header: {}", line))?,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        // Text before the first hunk (`diff --git`, `index`, prose) is skipped
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        if let Some(text) = line.strip_prefix('+') {
+            hunk.lines.push(HunkLine::Added(text.to_string()));
+        } else if let Some(text) = line.strip_prefix('-') {
+            hunk.lines.push(HunkLine::Removed(text.to_string()));
+        } else if let Some(text) = line.strip_prefix(' ') {
+            hunk.lines.push(HunkLine::Context(text.to_string()));
+        } else if line.is_empty() {
+            // Editors and models often drop the space of an empty context line
+            hunk.lines.push(HunkLine::Context(String::new()));
+        }
+        // Anything else is "\ No newline at end of file" or git metadata
+    }
+
+    if files > 1 {
+        return Err("The diff changes more than one file".to_string());
+    }
+    // Trailing blank lines are separators, not context
+    for hunk in &mut hunks {
+        while hunk.lines.last() == Some(&HunkLine::Context(String::new())) {
+            hunk.lines.pop();
+        }
+    }
+    hunks.retain(|hunk| !hunk.lines.is_empty());
+    if hunks.is_empty() {
+        return Err("No changes found in the diff".to_string());
+    }
+    if hunks.iter().all(|hunk| {
+        hunk.lines
+            .iter()
+            .all(|line| matches!(line, HunkLine::Context(_)))
+    }) {
+        return Err("The diff doesn't change anything".to_string());
+    }
+    Ok(Patch {
+        hunks,
+        creates_file,
+    })
+}
+
+/// The content of the first ```diff or ```patch block, or the text as is
+fn strip_fence(text: &str) -> &str {
+    let Some(start) = text
+        .find("```diff")
+        .or_else(|| text.find("```patch"))
+        .and_then(|fence| text[fence..].find('\n').map(|end| fence + end + 1))
+    else {
+        return text;
+    };
+    match text[start..].find("\n```") {
+        Some(end) => &text[start..start + end + 1],
+        None => &text[start..],
+    }
+}
+
+/// Old start line from the rest of a `@@ -12,5 +12,7 @@` header
+fn parse_hunk_start(header: &str) -> Option<usize> {
+    let old = header.split_whitespace().next()?.strip_prefix('-')?;
+    old.split(',').next()?.parse().ok()
+}
+
+/// Start of `block` in `lines` at or after `min_start`, closest to `expected`
+fn find_block(
+    lines: &[String],
+    block: &[&str],
+    expected: usize,
+    min_start: usize,
+) -> Option<usize> {
+    if block.is_empty() {
+        return Some(expected.clamp(min_start, lines.len()));
+    }
+    if block.len() > lines.len() {
+        return None;
+    }
+    let last_start = lines.len() - block.len();
+    if min_start > last_start {
+        return None;
+    }
+    let matches_at = |start: usize, loose: bool| {
+        block.iter().enumerate().all(|(i, text)| {
+            let line = lines[start + i].as_str();
+            if loose {
+                line.trim_end() == text.trim_end()
+            } else {
+                line == *text
+            }
+        })
+    };
+    for loose in [false, true] {
+        let found = (min_start..=last_start)
+            .filter(|&start| matches_at(start, loose))
+            .min_by_key(|&start| start.abs_diff(expected));
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str =
+        "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n\nfn other() {}\n";
+
+    #[test]
+    fn test_apply_fenced_diff() {
+        let diff = "Here's the fix:\n\n```diff\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1,4 +1,4 @@\n fn main() {\n-    let x = 1;\n+    let x = 2;\n     println!(\"{}\", x);\n }\n```\n";
+        let patch = parse(diff).unwrap();
+        let applied = patch.apply(Some(ORIGINAL)).unwrap();
+        assert_eq!(
+            applied.content,
+            "fn main() {\n    let x = 2;\n    println!(\"{}\", x);\n}\n\nfn other() {}\n"
+        );
+        assert_eq!(
+            (applied.hunks, applied.additions, applied.deletions),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_apply_ignores_wrong_line_numbers() {
+        // The header points at the wrong line and has the wrong counts
+        let diff = "@@ -1,2 +1,3 @@\n fn other() {}\n+fn another() {}\n";
+        let applied = parse(diff).unwrap().apply(Some(ORIGINAL)).unwrap();
+        assert!(
+            applied
+                .content
+                .ends_with("fn other() {}\nfn another() {}\n")
+        );
+
+        let crlf = ORIGINAL.replace('\n', "\r\n");
+        let applied = parse(diff).unwrap().apply(Some(&crlf)).unwrap();
+        assert!(
+            applied
+                .content
+                .ends_with("fn other() {}\r\nfn another() {}\r\n")
+        );
+    }
+
+    #[test]
+    fn test_apply_rejects_mismatch() {
+        let diff = "@@ -2,1 +2,1 @@\n-    let y = 1;\n+    let y = 2;\n";
+        let error = parse(diff).unwrap().apply(Some(ORIGINAL)).unwrap_err();
+        assert!(error.starts_with("Hunk 1 doesn't match"));
+    }
+
+    #[test]
+    fn test_new_file() {
+        let diff = "--- /dev/null\n+++ b/notes.md\n@@ -0,0 +1,2 @@\n+# Notes\n+- first\n";
+        let patch = parse(diff).unwrap();
+        assert!(patch.creates_file());
+        assert_eq!(patch.apply(None).unwrap().content, "# Notes\n- first\n");
+        assert!(patch.apply(Some("exists")).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("no diff here").is_err());
+        assert!(parse("@@ -1 +1 @@\n same\n").is_err());
+        let two_files = "--- a/one\n+++ b/one\n@@ -1 +1 @@\n-a\n+b\n--- a/two\n+++ b/two\n@@ -1 +1 @@\n-c\n+d\n";
+        assert_eq!(
+            parse(two_files).unwrap_err(),
+            "The diff changes more than one file"
+        );
+        assert_eq!(
+            parse("--- a/x\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n").unwrap_err(),
+            "Deleting files isn't supported"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! In-app updates
+//!
+//! Wraps the Tauri updater so settings can check, download and install updates in
+//! separate steps. Each release channel has its own update manifest; the channel is a
+//! setting. A found update is kept in [`UpdaterState`] between the steps, and the
+//! downloaded bundle is kept until `install_and_restart`.
+
+use crate::commands::AppState;
+use crate::events::{self, UpdateDownloadProgress};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tokio::sync::Mutex;
+
+/// Settings key: "stable" (default) or "beta"
+pub const UPDATE_CHANNEL_KEY: &str = "update_channel";
+
+const STABLE_MANIFEST_URL: &str = "https://dl.chatshell.app/updates/stable/latest.json";
+const BETA_MANIFEST_URL: &str = "https://dl.chatshell.app/updates/beta/latest.json";
+
+/// Progress interval when the server doesn't send a content length
+const PROGRESS_STEP_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("beta") => Self::Beta,
+            _ => Self::Stable,
+        }
+    }
+
+    fn manifest_url(self) -> &'static str {
+        match self {
+            Self::Stable => STABLE_MANIFEST_URL,
+            Self::Beta => BETA_MANIFEST_URL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub channel: UpdateChannel,
+    /// Release notes from the manifest
+    pub notes: Option<String>,
+    pub date: Option<String>,
+}
+
+/// The update found by the last check, and its bundle once downloaded
+#[derive(Default)]
+pub struct UpdaterState {
+    available: Mutex<Option<Update>>,
+    downloaded: Mutex<Option<Vec<u8>>>,
+}
+
+pub fn init(app: &tauri::AppHandle) {
+    app.manage(UpdaterState::default());
+}
+
+pub async fn load_channel(app: &tauri::AppHandle) -> UpdateChannel {
+    let state: tauri::State<'_, AppState> = app.state();
+    let value = state
+        .db
+        .get_setting(UPDATE_CHANNEL_KEY)
+        .await
+        .ok()
+        .flatten();
+    UpdateChannel::from_setting(value.as_deref())
+}
+
+/// Release builds carry the signing public key; dev builds don't and can't verify updates
+fn is_configured(app: &tauri::AppHandle) -> bool {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|config| config["pubkey"].as_str())
+        .is_some_and(|pubkey| !pubkey.trim().is_empty())
+}
+
+/// Check the channel's manifest; a newer version is remembered for `download`
+pub async fn check(app: &tauri::AppHandle) -> anyhow::Result<Option<UpdateInfo>> {
+    if !is_configured(app) {
+        anyhow::bail!("Updates are not available in this build");
+    }
+    let channel = load_channel(app).await;
+    tracing::info!("⬆️ [updater] Checking {:?} channel for updates", channel);
+
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![url::Url::parse(channel.manifest_url())?])?
+        .build()?;
+    let update = updater.check().await?;
+
+    let state = app.state::<UpdaterState>();
+    *state.downloaded.lock().await = None;
+    let info = update.as_ref().map(|update| UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        channel,
+        notes: update.body.clone(),
+        date: update.date.map(|d| d.to_string()),
+    });
+    match &info {
+        Some(info) => tracing::info!("⬆️ [updater] Update available: {}", info.version),
+        None => tracing::info!("⬆️ [updater] Already up to date"),
+    }
+    *state.available.lock().await = update;
+    Ok(info)
+}
+
+/// Download the update found by the last check, emitting progress events
+pub async fn download(app: &tauri::AppHandle) -> anyhow::Result<()> {
+    let state = app.state::<UpdaterState>();
+    let Some(update) = state.available.lock().await.clone() else {
+        anyhow::bail!("No update available; check for updates first");
+    };
+    tracing::info!("⬆️ [updater] Downloading {}", update.version);
+
+    let version = update.version.clone();
+    let mut downloaded: u64 = 0;
+    let mut last_reported: Option<(u64, Option<u64>)> = None;
+    let bytes = update
+        .download(
+            |chunk_length, content_length| {
+                downloaded += chunk_length as u64;
+                let percent = content_length
+                    .filter(|total| *total > 0)
+                    .map(|total| (downloaded * 100 / total).min(100));
+                // One event per percent, or per MiB when the size is unknown
+                let due = match (last_reported, percent) {
+                    (None, _) => true,
+                    (Some((_, last_percent)), Some(_)) => percent != last_percent,
+                    (Some((last_bytes, _)), None) => downloaded - last_bytes >= PROGRESS_STEP_BYTES,
+                };
+                if !due {
+                    return;
+                }
+                last_reported = Some((downloaded, percent));
+                events::emit(
+                    app,
+                    UpdateDownloadProgress {
+                        version: version.clone(),
+                        downloaded,
+                        total: content_length,
+                        percent,
+                        finished: false,
+                    },
+                );
+            },
+            || {},
+        )
+        .await?;
+
+    let total = bytes.len() as u64;
+    *state.downloaded.lock().await = Some(bytes);
+    events::emit(
+        app,
+        UpdateDownloadProgress {
+            version,
+            downloaded: total,
+            total: Some(total),
+            percent: Some(100),
+            finished: true,
+        },
+    );
+    tracing::info!("⬆️ [updater] Download complete ({} bytes)", total);
+    Ok(())
+}
+
+/// Install the downloaded update and restart. The restart goes through the regular
+/// exit path, so in-flight generations are finalized first.
+pub async fn install_and_restart(app: &tauri::AppHandle) -> anyhow::Result<()> {
+    let state = app.state::<UpdaterState>();
+    let Some(update) = state.available.lock().await.clone() else {
+        anyhow::bail!("No update available; check for updates first");
+    };
+    let Some(bytes) = state.downloaded.lock().await.take() else {
+        anyhow::bail!("The update hasn't been downloaded yet");
+    };
+
+    tracing::info!("⬆️ [updater] Installing {}", update.version);
+    update.install(bytes)?;
+
+    tracing::info!("⬆️ [updater] Restarting into {}", update.version);
+    app.request_restart();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_from_setting_defaults_to_stable() {
+        assert_eq!(UpdateChannel::from_setting(None), UpdateChannel::Stable);
+        assert_eq!(
+            UpdateChannel::from_setting(Some("nightly")),
+            UpdateChannel::Stable
+        );
+        assert_eq!(
+            UpdateChannel::from_setting(Some("beta")),
+            UpdateChannel::Beta
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Download budget shared by the fetches of one message
+//!
+//! A message's links and search results are fetched in parallel, and any of them can
+//! turn out to be a video or a data dump. Bodies are read in chunks against a shared
+//! [`ByteBudget`]: a download that would overrun it is aborted, and once it is used up
+//! the remaining URLs are skipped. Either way the URL ends up as a failed fetch, which
+//! the prompt lists with the reason.
+
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::Response;
+use reqwest::header::CONTENT_TYPE;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Settings key: megabytes a message's fetches may download in total ("0" = no limit)
+pub const FETCH_BUDGET_MB_KEY: &str = "web_fetch_budget_mb";
+
+pub const DEFAULT_FETCH_BUDGET_MB: u64 = 50;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Debug)]
+pub struct ByteBudget {
+    limit: u64,
+    used: AtomicU64,
+}
+
+impl ByteBudget {
+    pub fn from_megabytes(megabytes: u64) -> Self {
+        Self {
+            limit: megabytes.saturating_mul(BYTES_PER_MB),
+            used: AtomicU64::new(0),
+        }
+    }
+
+    /// Count downloaded bytes; false once the total is over the limit
+    pub fn consume(&self, bytes: u64) -> bool {
+        self.used.fetch_add(bytes, Ordering::Relaxed) + bytes <= self.limit
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used.load(Ordering::Relaxed))
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Reason given for URLs skipped because the budget is used up
+    pub fn exhausted_reason(&self) -> String {
+        format!(
+            "Download budget of {} MB for this message used up, not fetched",
+            self.limit / BYTES_PER_MB
+        )
+    }
+
+    fn overrun_reason(&self, bytes: u64) -> String {
+        format!(
+            "Download budget of {} MB for this message would be exceeded ({:.1} MB), download aborted",
+            self.limit / BYTES_PER_MB,
+            bytes as f64 / BYTES_PER_MB as f64
+        )
+    }
+}
+
+/// Read a response body as text, counting it against the budget. Decoded like
+/// `Response::text`: by the charset in Content-Type, UTF-8 by default.
+pub(super) async fn read_body(
+    mut response: Response,
+    budget: Option<&ByteBudget>,
+) -> Result<String, String> {
+    let Some(budget) = budget else {
+        return response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response body: {}", e));
+    };
+
+    // Announced sizes are rejected before downloading anything
+    if let Some(length) = response.content_length()
+        && length > budget.remaining()
+    {
+        return Err(budget.overrun_reason(length));
+    }
+
+    let encoding = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|content_type| {
+            content_type
+                .split(';')
+                .find_map(|param| param.trim().strip_prefix("charset="))
+                .and_then(|label| Encoding::for_label(label.trim_matches('"').as_bytes()))
+        })
+        .unwrap_or(UTF_8);
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response body: {}", e))?
+    {
+        body.extend_from_slice(&chunk);
+        if !budget.consume(chunk.len() as u64) {
+            return Err(budget.overrun_reason(body.len() as u64));
+        }
+    }
+
+    Ok(encoding.decode(&body).0.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_runs_out() {
+        let budget = ByteBudget::from_megabytes(1);
+        assert!(budget.consume(BYTES_PER_MB / 2));
+        assert!(!budget.is_exhausted());
+        assert_eq!(budget.remaining(), BYTES_PER_MB / 2);
+        assert!(!budget.consume(BYTES_PER_MB));
+        assert!(budget.is_exhausted());
+        assert!(
+            budget
+                .exhausted_reason()
+                .starts_with("Download budget of 1 MB")
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Locating the Chrome used by the headless modules
+//!
+//! Web search and the fetch fallback drive a headless Chrome. An installed Chrome or
+//! Chromium (or the `CHROME` environment variable) is preferred; otherwise the Chromium
+//! that [`download_chromium`] put into the app data directory is used. When neither
+//! exists, launching fails with [`ChromeUnavailable`] rather than a bare launch error.
+
+use crate::error::{AppError, ErrorKind};
+use headless_chrome::browser::default_executable;
+use headless_chrome::{Browser, FetcherOptions, LaunchOptions, Revision};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Chromium snapshot revision fetched by [`download_chromium`]
+pub const CHROMIUM_REVISION: &str = "1095492";
+
+/// Directory under app data the Chromium download goes to
+pub const CHROMIUM_DIR_NAME: &str = "chromium";
+
+static CHROMIUM_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Set where downloaded Chromium lives; called once at startup
+pub fn set_chromium_dir(dir: PathBuf) {
+    let _ = CHROMIUM_DIR.set(dir);
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "Web search and fetching protected pages need Chrome or Chromium, which wasn't found ({reason}). Install Chrome, or download Chromium from the diagnostics settings."
+)]
+pub struct ChromeUnavailable {
+    pub reason: String,
+}
+
+/// Map a headless browser failure to an [`AppError`], keeping a missing browser
+/// distinguishable from other failures
+pub fn browser_app_error(error: anyhow::Error) -> AppError {
+    match error.downcast_ref::<ChromeUnavailable>() {
+        Some(unavailable) => AppError::new(ErrorKind::BrowserUnavailable, unavailable.to_string()),
+        None => AppError::from(error),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChromeStatus {
+    pub available: bool,
+    pub path: Option<String>,
+    /// Whether `path` is the Chromium downloaded into app data
+    pub downloaded: bool,
+    pub revision: String,
+    /// Why no browser was found
+    pub error: Option<String>,
+}
+
+/// The Chrome executable headless browsers are launched with, and whether it is the
+/// downloaded Chromium. Blocking; call from a blocking thread.
+pub fn find_chrome() -> Result<(PathBuf, bool), ChromeUnavailable> {
+    let installed_error = match default_executable() {
+        Ok(path) => return Ok((path, false)),
+        Err(e) => e,
+    };
+    if let Some(path) = CHROMIUM_DIR
+        .get()
+        .map(|dir| downloaded_executable(dir))
+        .filter(|path| path.is_file())
+    {
+        return Ok((path, true));
+    }
+    Err(ChromeUnavailable {
+        reason: installed_error,
+    })
+}
+
+/// Blocking; call from a blocking thread
+pub fn chrome_status() -> ChromeStatus {
+    let found = find_chrome();
+    ChromeStatus {
+        available: found.is_ok(),
+        path: found
+            .as_ref()
+            .ok()
+            .map(|(path, _)| path.display().to_string()),
+        downloaded: found.as_ref().is_ok_and(|(_, downloaded)| *downloaded),
+        revision: CHROMIUM_REVISION.to_string(),
+        error: found.err().map(|e| e.to_string()),
+    }
+}
+
+/// Download the pinned Chromium revision into the app data directory, then launch it
+/// once to make sure it runs. Blocking; call from a blocking thread.
+pub fn download_chromium() -> anyhow::Result<PathBuf> {
+    let dir = CHROMIUM_DIR
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("Chromium directory is not set"))?;
+    tracing::info!(
+        "⬇️ [headless] Downloading Chromium r{} into {:?}",
+        CHROMIUM_REVISION,
+        dir
+    );
+
+    // Without a path, the launch fetches the revision given in the fetcher options
+    let launch_options = LaunchOptions::default_builder()
+        .headless(true)
+        .fetcher_options(
+            FetcherOptions::default()
+                .with_revision(Revision::Specific(CHROMIUM_REVISION.to_string()))
+                .with_install_dir(Some(dir))
+                .with_allow_standard_dirs(false)
+                .with_allow_download(true),
+        )
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build launch options: {}", e))?;
+    let browser = Browser::new(launch_options)
+        .map_err(|e| anyhow::anyhow!("Failed to download or launch Chromium: {}", e))?;
+    drop(browser);
+
+    let path = downloaded_executable(dir);
+    tracing::info!("✅ [headless] Chromium ready at {:?}", path);
+    Ok(path)
+}
+
+/// Where the fetcher unpacks the pinned revision: `<dir>/<platform>-<rev>/<archive>/...`
+fn downloaded_executable(dir: &Path) -> PathBuf {
+    #[cfg(target_os = "linux")]
+    let (platform, executable) = ("linux", "chrome-linux/chrome");
+    #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+    let (platform, executable) = ("mac_arm", "chrome-mac/Chromium.app/Contents/MacOS/Chromium");
+    #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+    let (platform, executable) = ("mac", "chrome-mac/Chromium.app/Contents/MacOS/Chromium");
+    #[cfg(windows)]
+    let (platform, executable) = ("win", "chrome-win/chrome.exe");
+
+    dir.join(format!("{}-{}", platform, CHROMIUM_REVISION))
+        .join(executable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_browser_maps_to_structured_error() {
+        let error = anyhow::Error::new(ChromeUnavailable {
+            reason: "Could not auto detect a chrome executable".to_string(),
+        });
+        let app_error = browser_app_error(error);
+        assert_eq!(app_error.kind, ErrorKind::BrowserUnavailable);
+        assert!(app_error.message.contains("Could not auto detect"));
+
+        let other = browser_app_error(anyhow::anyhow!("Navigation timeout"));
+        assert_eq!(other.kind, ErrorKind::Timeout);
+    }
+}
@@ -0,0 +1,113 @@
+//! Favicon downloads
+//!
+//! Fetch results store their page's favicon in attachment storage so the UI doesn't
+//! request it from the site on every render. Icons are small; anything large, slow or
+//! not an image is ignored and the UI falls back to its generic icon.
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+use super::identity;
+use super::types::HTTP_CLIENT;
+
+const MAX_FAVICON_BYTES: usize = 256 * 1024;
+const FAVICON_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct Favicon {
+    pub bytes: Vec<u8>,
+    pub mime_type: String,
+}
+
+/// Download a favicon, or decode it when the page inlined it as a `data:` URL
+pub async fn download_favicon(url: &str) -> Option<Favicon> {
+    if let Some(data_url) = url.strip_prefix("data:") {
+        return decode_data_url(data_url);
+    }
+
+    let request = HTTP_CLIENT.get(url).timeout(FAVICON_TIMEOUT);
+    let mut response = match identity::apply_to_request(request).send().await {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            tracing::info!("⚠️ [favicon] {} returned {}", url, response.status());
+            return None;
+        }
+        Err(e) => {
+            tracing::info!("⚠️ [favicon] Failed to download {}: {}", url, e);
+            return None;
+        }
+    };
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_FAVICON_BYTES as u64)
+    {
+        return None;
+    }
+
+    let declared = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_lowercase())
+        .unwrap_or_default();
+    // Icons are often served as application/octet-stream
+    let mime_type = if declared.starts_with("image/") {
+        declared
+    } else {
+        mime_from_extension(url)?.to_string()
+    };
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.ok()? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_FAVICON_BYTES {
+            return None;
+        }
+    }
+    (!bytes.is_empty()).then_some(Favicon { bytes, mime_type })
+}
+
+fn mime_from_extension(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    let extension = path.rsplit_once('.')?.1;
+    match extension {
+        "ico" => Some("image/x-icon"),
+        "png" => Some("image/png"),
+        "svg" => Some("image/svg+xml"),
+        "gif" => Some("image/gif"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// `image/png;base64,iVBOR...` (the part after `data:`)
+fn decode_data_url(data_url: &str) -> Option<Favicon> {
+    let (header, data) = data_url.split_once(',')?;
+    let mime_type = header.strip_suffix(";base64")?.to_lowercase();
+    if !mime_type.starts_with("image/") {
+        return None;
+    }
+    let bytes = STANDARD.decode(data.trim()).ok()?;
+    (!bytes.is_empty() && bytes.len() <= MAX_FAVICON_BYTES).then_some(Favicon { bytes, mime_type })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_favicon_type_detection() {
+        assert_eq!(
+            mime_from_extension("https://example.com/favicon.ICO?v=2"),
+            Some("image/x-icon")
+        );
+        assert_eq!(mime_from_extension("https://example.com/icon"), None);
+
+        let favicon = decode_data_url("image/png;base64,iVBORw0KGgo=").unwrap();
+        assert_eq!(favicon.mime_type, "image/png");
+        assert_eq!(&favicon.bytes[..4], b"\x89PNG");
+        assert!(decode_data_url("text/html;base64,PGI+").is_none());
+    }
+}
@@ -0,0 +1,266 @@
+//! RSS and Atom feeds
+//!
+//! Automations can list feeds as sources; their recent entries are handed to the
+//! model as titles, links and short summaries rather than fetching every article.
+
+use chrono::{DateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use super::budget::{ByteBudget, read_body};
+use super::identity;
+use super::types::HTTP_CLIENT;
+
+/// Feeds larger than this are not worth reading for a digest
+const MAX_FEED_MB: u64 = 5;
+const MAX_FEED_ITEMS: usize = 50;
+const MAX_SUMMARY_CHARS: usize = 400;
+
+#[derive(Debug, Clone, Default)]
+pub struct Feed {
+    pub title: Option<String>,
+    /// In document order, usually newest first
+    pub items: Vec<FeedItem>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FeedItem {
+    pub title: String,
+    pub link: Option<String>,
+    /// Plain text, shortened
+    pub summary: Option<String>,
+    pub published: Option<DateTime<Utc>>,
+}
+
+/// Download and parse an RSS or Atom feed
+pub async fn fetch_feed(url: &str) -> Result<Feed, String> {
+    tracing::info!("📰 [feed] Fetching {}", url);
+    let response = identity::apply_to_request(HTTP_CLIENT.get(url))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch feed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Feed returned status: {}", response.status()));
+    }
+    let body = read_body(response, Some(&ByteBudget::from_megabytes(MAX_FEED_MB))).await?;
+    let feed = parse_feed(&body)?;
+    tracing::info!("📰 [feed] {} entries in {}", feed.items.len(), url);
+    Ok(feed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Title,
+    Link,
+    Summary,
+    Published,
+}
+
+fn field_for(local_name: &[u8]) -> Option<Field> {
+    match local_name {
+        b"title" => Some(Field::Title),
+        b"link" => Some(Field::Link),
+        // content:encoded has the local name "encoded"
+        b"description" | b"summary" | b"content" | b"encoded" => Some(Field::Summary),
+        b"pubDate" | b"published" | b"updated" | b"date" => Some(Field::Published),
+        _ => None,
+    }
+}
+
+/// Parse RSS 2.0, RSS 1.0 (RDF) or Atom
+fn parse_feed(xml: &str) -> Result<Feed, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut feed = Feed::default();
+    let mut item: Option<FeedItem> = None;
+    // Field being read, the element it started with, and its text so far
+    let mut field: Option<(Field, Vec<u8>)> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = match reader.read_event() {
+            Ok(event) => event,
+            // Keep what was read before the broken markup
+            Err(e) if !feed.items.is_empty() => {
+                tracing::debug!("[feed] Stopped at malformed XML: {}", e);
+                break;
+            }
+            Err(e) => return Err(format!("Invalid feed XML: {}", e)),
+        };
+        match event {
+            Event::Start(e) => {
+                let name = e.local_name().as_ref().to_vec();
+                if matches!(name.as_slice(), b"item" | b"entry") {
+                    item = Some(FeedItem::default());
+                } else if field.is_none()
+                    && let Some(kind) = field_for(&name)
+                {
+                    if kind == Field::Link
+                        && let Some(item) = item.as_mut()
+                        && take_atom_link(item, &e)
+                    {
+                        continue;
+                    }
+                    field = Some((kind, name));
+                    text.clear();
+                }
+            }
+            Event::Empty(e) => {
+                if e.local_name().as_ref() == b"link"
+                    && let Some(item) = item.as_mut()
+                {
+                    take_atom_link(item, &e);
+                }
+            }
+            Event::Text(e) if field.is_some() => {
+                text.push_str(&e.decode().unwrap_or_default());
+            }
+            Event::CData(e) if field.is_some() => {
+                text.push_str(&e.decode().unwrap_or_default());
+            }
+            Event::GeneralRef(e) if field.is_some() => {
+                let entity = format!("&{};", e.decode().unwrap_or_default());
+                text.push_str(&quick_xml::escape::unescape(&entity).unwrap_or_default());
+            }
+            Event::End(e) => {
+                let name = e.local_name();
+                if let Some((kind, start)) = &field
+                    && start.as_slice() == name.as_ref()
+                {
+                    match item.as_mut() {
+                        Some(item) => set_field(item, *kind, &text),
+                        None if *kind == Field::Title && feed.title.is_none() => {
+                            feed.title =
+                                Some(collapse_whitespace(&text)).filter(|title| !title.is_empty());
+                        }
+                        None => {}
+                    }
+                    field = None;
+                } else if matches!(name.as_ref(), b"item" | b"entry")
+                    && let Some(done) = item.take()
+                {
+                    if !done.title.is_empty() || done.link.is_some() {
+                        feed.items.push(done);
+                    }
+                    if feed.items.len() >= MAX_FEED_ITEMS {
+                        break;
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if feed.items.is_empty() {
+        return Err("No feed entries found".to_string());
+    }
+    Ok(feed)
+}
+
+/// Atom links are attributes; only the first alternate link is the article
+fn take_atom_link(item: &mut FeedItem, element: &BytesStart) -> bool {
+    let attribute = |key: &[u8]| {
+        element
+            .try_get_attribute(key)
+            .ok()
+            .flatten()
+            .and_then(|a| a.unescape_value().ok().map(|v| v.trim().to_string()))
+    };
+    let Some(href) = attribute(b"href").filter(|href| !href.is_empty()) else {
+        return false;
+    };
+    let rel = attribute(b"rel");
+    if item.link.is_none() && matches!(rel.as_deref(), None | Some("alternate")) {
+        item.link = Some(href);
+    }
+    true
+}
+
+fn set_field(item: &mut FeedItem, kind: Field, raw: &str) {
+    match kind {
+        Field::Title if item.title.is_empty() => item.title = collapse_whitespace(raw),
+        Field::Link if item.link.is_none() => {
+            item.link = Some(raw.trim().to_string()).filter(|link| !link.is_empty());
+        }
+        Field::Summary if item.summary.is_none() => {
+            // Descriptions are usually HTML
+            let fragment = scraper::Html::parse_fragment(raw);
+            let plain = collapse_whitespace(&fragment.root_element().text().collect::<String>());
+            item.summary = (!plain.is_empty()).then(|| shorten(&plain));
+        }
+        Field::Published if item.published.is_none() => {
+            let raw = raw.trim();
+            item.published = DateTime::parse_from_rfc2822(raw)
+                .or_else(|_| DateTime::parse_from_rfc3339(raw))
+                .ok()
+                .map(|date| date.with_timezone(&Utc));
+        }
+        _ => {}
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn shorten(text: &str) -> String {
+    if text.chars().count() <= MAX_SUMMARY_CHARS {
+        return text.to_string();
+    }
+    let short: String = text.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+    format!("{}…", short.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+            <rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/"><channel>
+              <title>Example News</title>
+              <item>
+                <title>Rust &amp; WebAssembly</title>
+                <link> https://example.com/a </link>
+                <description><![CDATA[<p>First <b>post</b></p>]]></description>
+                <pubDate>Tue, 10 Jun 2025 04:00:00 GMT</pubDate>
+              </item>
+              <item><title>Second</title><link>https://example.com/b</link></item>
+            </channel></rss>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example News"));
+        assert_eq!(feed.items.len(), 2);
+        let first = &feed.items[0];
+        assert_eq!(first.title, "Rust & WebAssembly");
+        assert_eq!(first.link.as_deref(), Some("https://example.com/a"));
+        assert_eq!(first.summary.as_deref(), Some("First post"));
+        assert_eq!(
+            first.published.unwrap().to_rfc3339(),
+            "2025-06-10T04:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Blog</title>
+              <entry>
+                <title type="html">Hello</title>
+                <link rel="self" href="https://example.com/feed/1"/>
+                <link href="https://example.com/hello"/>
+                <updated>2025-06-01T12:00:00Z</updated>
+                <summary>Intro</summary>
+              </entry>
+            </feed>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Blog"));
+        assert_eq!(
+            feed.items[0].link.as_deref(),
+            Some("https://example.com/hello")
+        );
+        assert_eq!(feed.items[0].summary.as_deref(), Some("Intro"));
+        assert!(feed.items[0].published.is_some());
+        assert!(parse_feed("<html><body>Not a feed</body></html>").is_err());
+    }
+}
@@ -1,9 +1,13 @@
 use futures::stream::{FuturesUnordered, StreamExt};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use url::Url;
 
+use super::budget::{ByteBudget, read_body};
 use super::extractors::extract_favicon_url;
 use super::headless::{fetch_with_headless_browser, fetch_with_headless_fallback};
+use super::host_guard;
+use super::identity;
 use super::jina::fetch_with_jina;
 use super::processors::{
     process_html_with_readability, process_json_content, process_text_content, process_xml_content,
@@ -33,12 +37,18 @@ pub struct FetchConfig {
     pub mode: FetchMode,
     pub local_method: LocalMethod,
     pub jina_api_key: Option<String>,
+    /// Shared by every fetch made with this config; `None` = unlimited
+    pub byte_budget: Option<Arc<ByteBudget>>,
 }
 
 /// Fetch and parse a web resource using Mozilla's Readability algorithm for HTML.
 /// max_chars: None = no truncation, Some(n) = truncate to n characters
 /// Falls back to headless browser if direct HTTP fetch fails with non-200 status.
-pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedWebResource {
+pub async fn fetch_web_resource(
+    url: &str,
+    max_chars: Option<usize>,
+    budget: Option<&ByteBudget>,
+) -> FetchedWebResource {
     tracing::info!("📡 [fetcher] Starting fetch for: {}", url);
 
     // Validate URL first
@@ -53,13 +63,11 @@ pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedW
 
     tracing::info!("📨 [fetcher] Sending HTTP request...");
 
-    let response = match HTTP_CLIENT
+    let request = HTTP_CLIENT
         .get(url)
         .header("Accept", "text/markdown, text/html, */*")
-        .header("Accept-Encoding", "gzip, deflate, br, zstd")
-        .send()
-        .await
-    {
+        .header("Accept-Encoding", "gzip, deflate, br, zstd");
+    let response = match identity::apply_to_request(request).send().await {
         Ok(r) => r,
         Err(e) => {
             // Network error - try headless browser fallback
@@ -67,7 +75,7 @@ pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedW
                 "⚠️ [fetcher] HTTP request failed: {}, trying headless browser...",
                 e
             );
-            return fetch_with_headless_fallback(url, max_chars).await;
+            return fetch_with_headless_fallback(url, max_chars, budget).await;
         }
     };
 
@@ -79,7 +87,7 @@ pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedW
             "⚠️ [fetcher] HTTP error {}, trying headless browser fallback...",
             response.status()
         );
-        return fetch_with_headless_fallback(url, max_chars).await;
+        return fetch_with_headless_fallback(url, max_chars, budget).await;
     }
 
     let content_type = response
@@ -96,16 +104,14 @@ pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedW
         .trim()
         .to_string();
 
-    let body = match response.text().await {
+    // Binary content is rejected before downloading it
+    if is_binary_mime(&mime_type) {
+        return unsupported_binary(url, mime_type);
+    }
+
+    let body = match read_body(response, budget).await {
         Ok(c) => c,
-        Err(e) => {
-            return FetchedWebResource::error(
-                url,
-                mime_type,
-                format!("Failed to read response body: {}", e),
-                None,
-            );
-        }
+        Err(e) => return FetchedWebResource::error(url, mime_type, e, None),
     };
 
     // Handle different content types
@@ -131,24 +137,6 @@ pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedW
         // XML - treat as text with code block (no favicon for non-HTML)
         "application/xml" | "text/xml" => process_xml_content(url, &body, mime_type, max_chars),
 
-        // Unsupported binary types (no favicon for binary content)
-        mime if mime.starts_with("image/")
-            || mime.starts_with("audio/")
-            || mime.starts_with("video/")
-            || mime == "application/pdf"
-            || mime == "application/octet-stream" =>
-        {
-            FetchedWebResource::error(
-                url,
-                mime_type,
-                format!(
-                    "Unsupported content type: {}. Binary content cannot be processed.",
-                    mime
-                ),
-                None,
-            )
-        }
-
         // Unknown types - try to parse as HTML (many sites don't set content-type correctly)
         _ => {
             // Check if it looks like HTML
@@ -174,24 +162,66 @@ pub async fn fetch_web_resource(url: &str, max_chars: Option<usize>) -> FetchedW
     }
 }
 
+fn is_binary_mime(mime: &str) -> bool {
+    mime.starts_with("image/")
+        || mime.starts_with("audio/")
+        || mime.starts_with("video/")
+        || mime == "application/pdf"
+        || mime == "application/octet-stream"
+}
+
+fn unsupported_binary(url: &str, mime_type: String) -> FetchedWebResource {
+    let error = format!(
+        "Unsupported content type: {}. Binary content cannot be processed.",
+        mime_type
+    );
+    FetchedWebResource::error(url, mime_type, error, None)
+}
+
 /// Fetch web resource with configuration
 pub async fn fetch_web_resource_with_config(
     url: &str,
     max_chars: Option<usize>,
     config: &FetchConfig,
 ) -> FetchedWebResource {
+    let budget = config.byte_budget.as_deref();
+    if let Some(budget) = budget
+        && budget.is_exhausted()
+    {
+        tracing::info!("💾 [fetcher] Skipping {}: download budget used up", url);
+        return FetchedWebResource::error(url, String::new(), budget.exhausted_reason(), None);
+    }
+
     match config.mode {
-        FetchMode::Api => fetch_with_jina(url, config.jina_api_key.as_deref()).await,
-        FetchMode::Local => match config.local_method {
-            LocalMethod::Auto => fetch_web_resource(url, max_chars).await,
-            LocalMethod::FetchOnly => fetch_with_http_only(url, max_chars).await,
-            LocalMethod::HeadlessOnly => fetch_with_headless_only(url, max_chars).await,
-        },
+        FetchMode::Api => fetch_with_jina(url, config.jina_api_key.as_deref(), budget).await,
+        FetchMode::Local => {
+            let host = host_guard::host_of(url);
+            if let Some(host) = &host
+                && let Err(reason) = host_guard::acquire(host).await
+            {
+                tracing::info!("🚧 [fetcher] {}", reason);
+                return FetchedWebResource::error(url, String::new(), reason, None);
+            }
+
+            let result = match config.local_method {
+                LocalMethod::Auto => fetch_web_resource(url, max_chars, budget).await,
+                LocalMethod::FetchOnly => fetch_with_http_only(url, max_chars, budget).await,
+                LocalMethod::HeadlessOnly => fetch_with_headless_only(url, max_chars, budget).await,
+            };
+            if let Some(host) = &host {
+                host_guard::record(host, result.extraction_error.as_deref());
+            }
+            result
+        }
     }
 }
 
 /// Fetch using HTTP only (no headless fallback)
-async fn fetch_with_http_only(url: &str, max_chars: Option<usize>) -> FetchedWebResource {
+async fn fetch_with_http_only(
+    url: &str,
+    max_chars: Option<usize>,
+    budget: Option<&ByteBudget>,
+) -> FetchedWebResource {
     tracing::info!("📡 [fetcher] Starting HTTP-only fetch for: {}", url);
 
     // Validate URL first
@@ -204,13 +234,11 @@ async fn fetch_with_http_only(url: &str, max_chars: Option<usize>) -> FetchedWeb
         );
     }
 
-    let response = match HTTP_CLIENT
+    let request = HTTP_CLIENT
         .get(url)
         .header("Accept", "text/markdown, text/html, */*")
-        .header("Accept-Encoding", "gzip, deflate, br, zstd")
-        .send()
-        .await
-    {
+        .header("Accept-Encoding", "gzip, deflate, br, zstd");
+    let response = match identity::apply_to_request(request).send().await {
         Ok(r) => r,
         Err(e) => {
             return FetchedWebResource::error(
@@ -245,16 +273,14 @@ async fn fetch_with_http_only(url: &str, max_chars: Option<usize>) -> FetchedWeb
         .trim()
         .to_string();
 
-    let body = match response.text().await {
+    // Binary content is rejected before downloading it
+    if is_binary_mime(&mime_type) {
+        return unsupported_binary(url, mime_type);
+    }
+
+    let body = match read_body(response, budget).await {
         Ok(c) => c,
-        Err(e) => {
-            return FetchedWebResource::error(
-                url,
-                mime_type,
-                format!("Failed to read response body: {}", e),
-                None,
-            );
-        }
+        Err(e) => return FetchedWebResource::error(url, mime_type, e, None),
     };
 
     // Handle different content types (same as fetch_web_resource)
@@ -269,22 +295,6 @@ async fn fetch_with_http_only(url: &str, max_chars: Option<usize>) -> FetchedWeb
         "text/plain" => process_text_content(url, &body, mime_type, max_chars, None),
         "application/json" => process_json_content(url, &body, max_chars, None),
         "application/xml" | "text/xml" => process_xml_content(url, &body, mime_type, max_chars),
-        mime if mime.starts_with("image/")
-            || mime.starts_with("audio/")
-            || mime.starts_with("video/")
-            || mime == "application/pdf"
-            || mime == "application/octet-stream" =>
-        {
-            FetchedWebResource::error(
-                url,
-                mime_type,
-                format!(
-                    "Unsupported content type: {}. Binary content cannot be processed.",
-                    mime
-                ),
-                None,
-            )
-        }
         _ => {
             let trimmed = body.trim();
             if trimmed.starts_with("<!DOCTYPE")
@@ -308,7 +318,11 @@ async fn fetch_with_http_only(url: &str, max_chars: Option<usize>) -> FetchedWeb
 }
 
 /// Fetch using headless Chrome only
-async fn fetch_with_headless_only(url: &str, max_chars: Option<usize>) -> FetchedWebResource {
+async fn fetch_with_headless_only(
+    url: &str,
+    max_chars: Option<usize>,
+    budget: Option<&ByteBudget>,
+) -> FetchedWebResource {
     tracing::info!("📡 [fetcher] Starting headless Chrome fetch for: {}", url);
 
     // Validate URL first
@@ -328,6 +342,10 @@ async fn fetch_with_headless_only(url: &str, max_chars: Option<usize>) -> Fetche
 
     match html_result {
         Ok(Ok(html)) => {
+            // Only the page itself is counted, not the assets Chrome loaded for it
+            if let Some(budget) = budget {
+                budget.consume(html.len() as u64);
+            }
             let favicon_url = extract_favicon_url(url, Some(&html));
             process_html_with_readability(
                 url,
@@ -438,7 +456,7 @@ pub async fn fetch_urls_with_channel(
                 let url = url.clone();
                 async move {
                     tracing::info!("🔗 [fetcher] Fetching: {}", url);
-                    let result = fetch_web_resource(&url, max_chars).await;
+                    let result = fetch_web_resource(&url, max_chars, None).await;
                     tracing::info!(
                         "✅ [fetcher] Completed: {} (error: {:?})",
                         url,
@@ -461,7 +479,10 @@ pub async fn fetch_urls_with_channel(
     (rx, handle)
 }
 
-/// Build LLM content with fetched web resources as attachments
+/// Build LLM content with fetched web resources as attachments.
+///
+/// Pages that were fetched successfully are numbered from 1 in order, and the model is
+/// asked to cite them by number.
 pub fn build_llm_content_with_attachments(
     original_content: &str,
     fetched_resources: &[FetchedWebResource],
@@ -471,7 +492,15 @@ pub fn build_llm_content_with_attachments(
     }
 
     let mut content = original_content.to_string();
+    if fetched_resources
+        .iter()
+        .any(|r| r.extraction_error.is_none())
+    {
+        content.push_str("\n\n---\n");
+        content.push_str(crate::prompts::SOURCE_CITATION_INSTRUCTION);
+    }
 
+    let mut number = 0;
     for resource in fetched_resources {
         if resource.extraction_error.is_some() {
             content.push_str(&format!(
@@ -483,8 +512,10 @@ pub fn build_llm_content_with_attachments(
                     .unwrap_or("Unknown error")
             ));
         } else {
+            number += 1;
             content.push_str(&format!(
-                "\n\n---\n**Content from {}:**\n\n{}",
+                "\n\n---\n**[{}] Content from {}:**\n\n{}",
+                number,
                 resource.url,
                 resource.content.trim()
             ));
@@ -1,16 +1,94 @@
 use anyhow::Result;
-use headless_chrome::{Browser, LaunchOptions};
-use std::time::Duration;
+use headless_chrome::{Browser, LaunchOptions, Tab};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
+use super::budget::ByteBudget;
+use super::chrome::find_chrome;
+use super::identity::{DEFAULT_ACCEPT_LANGUAGE, apply_fetch_identity};
 use super::processors::process_html_with_readability;
 use super::types::{FetchedWebResource, STEALTH_JS};
 use crate::web_fetch::extractors::extract_favicon_url;
 
+lazy_static! {
+    /// Browsers currently running, so they can be closed on app exit
+    static ref LIVE_BROWSERS: Mutex<HashMap<u64, Browser>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_BROWSER_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Longest a fetch waits for the page to settle, bot checks included
+const PAGE_SETTLE_TIMEOUT: Duration = Duration::from_secs(20);
+/// How long the HTML must stay unchanged for the page to count as loaded
+const SETTLE_DURATION: Duration = Duration::from_millis(600);
+const SETTLE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Text shown on Cloudflare-style bot checks
+const CHALLENGE_INDICATORS: &[&str] = &[
+    "Just a moment",
+    "Verifying",
+    "checking your browser",
+    "Please wait",
+    "Checking if the site",
+];
+
+/// A headless browser registered for shutdown.
+/// Chrome is killed once the last handle to the browser is dropped.
+pub struct ManagedBrowser {
+    id: u64,
+    browser: Browser,
+}
+
+impl Deref for ManagedBrowser {
+    type Target = Browser;
+
+    fn deref(&self) -> &Browser {
+        &self.browser
+    }
+}
+
+impl Drop for ManagedBrowser {
+    fn drop(&mut self) {
+        if let Ok(mut browsers) = LIVE_BROWSERS.lock() {
+            browsers.remove(&self.id);
+        }
+    }
+}
+
+/// Number of headless browsers still running
+pub fn live_browser_count() -> usize {
+    LIVE_BROWSERS.lock().map(|b| b.len()).unwrap_or(0)
+}
+
+/// Close the tabs of every running browser so in-flight fetches fail fast
+/// and release their browser. Blocking; call from a blocking thread.
+pub fn close_all_browsers() {
+    let browsers: Vec<Browser> = match LIVE_BROWSERS.lock() {
+        Ok(browsers) => browsers.values().cloned().collect(),
+        Err(_) => return,
+    };
+    for browser in browsers {
+        let tabs: Vec<_> = match browser.get_tabs().lock() {
+            Ok(tabs) => tabs.clone(),
+            Err(_) => continue,
+        };
+        for tab in tabs {
+            let _ = tab.close(false);
+        }
+    }
+}
+
 /// Create a new headless browser instance
-pub fn create_new_browser() -> Result<Browser> {
+pub fn create_new_browser() -> Result<ManagedBrowser> {
     tracing::info!("🌐 [headless] Creating new browser instance...");
 
+    let (chrome_path, _) = find_chrome()?;
     let launch_options = LaunchOptions::default_builder()
+        .path(Some(chrome_path))
         .headless(true)
         .window_size(Some((1920, 1080)))
         .idle_browser_timeout(Duration::from_secs(300))
@@ -20,8 +98,13 @@ pub fn create_new_browser() -> Result<Browser> {
     let browser = Browser::new(launch_options)
         .map_err(|e| anyhow::anyhow!("Failed to launch browser: {}", e))?;
 
+    let id = NEXT_BROWSER_ID.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut browsers) = LIVE_BROWSERS.lock() {
+        browsers.insert(id, browser.clone());
+    }
+
     tracing::info!("✅ [headless] Browser instance created");
-    Ok(browser)
+    Ok(ManagedBrowser { id, browser })
 }
 
 /// Fetch webpage content using headless Chrome browser
@@ -35,12 +118,8 @@ pub fn fetch_with_headless_browser(url: &str) -> Result<String> {
         .new_tab()
         .map_err(|e| anyhow::anyhow!("Failed to create tab: {}", e))?;
 
-    // Set realistic User-Agent before navigation
-    tab.set_user_agent(
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        Some("en-US,en;q=0.9"),
-        Some("macOS"),
-    ).map_err(|e| anyhow::anyhow!("Failed to set user agent: {}", e))?;
+    // Set realistic User-Agent before navigation (or the one configured in settings)
+    apply_fetch_identity(&tab, DEFAULT_ACCEPT_LANGUAGE)?;
 
     // Navigate to a blank page first to inject stealth JS
     tab.navigate_to("about:blank")
@@ -62,53 +141,90 @@ pub fn fetch_with_headless_browser(url: &str) -> Result<String> {
     tab.wait_until_navigated()
         .map_err(|e| anyhow::anyhow!("Navigation timeout: {}", e))?;
 
-    // Wait for Cloudflare challenge to complete (usually takes 5-10 seconds)
-    tracing::info!("⏳ [headless] Waiting for page to load (Cloudflare check)...");
-    std::thread::sleep(Duration::from_secs(8));
-
-    // Check if we're still on the challenge page
-    let mut html = tab
-        .get_content()
-        .map_err(|e| anyhow::anyhow!("Failed to get page content: {}", e))?;
-
-    // If still showing challenge, wait more and retry
-    let challenge_indicators = [
-        "Just a moment",
-        "Verifying",
-        "checking your browser",
-        "Please wait",
-        "Checking if the site",
-    ];
-    let mut retries = 0;
-    while retries < 3 && challenge_indicators.iter().any(|ind| html.contains(ind)) {
-        tracing::info!(
-            "⏳ [headless] Still on challenge page, waiting more... (retry {})",
-            retries + 1
-        );
-        std::thread::sleep(Duration::from_secs(5));
-        html = tab
-            .get_content()
-            .map_err(|e| anyhow::anyhow!("Failed to get page content: {}", e))?;
-        retries += 1;
-    }
-
-    if challenge_indicators.iter().any(|ind| html.contains(ind)) {
+    // Most pages settle well within a second; a Cloudflare check takes 5-10 seconds
+    tracing::info!("⏳ [headless] Waiting for page to settle...");
+    let started = Instant::now();
+    let html = wait_until_settled(&tab, PAGE_SETTLE_TIMEOUT)?;
+    if is_challenge_page(&html) {
         return Err(anyhow::anyhow!(
-            "Cloudflare challenge could not be bypassed after {} retries",
-            retries
+            "Cloudflare challenge could not be bypassed within {}s",
+            PAGE_SETTLE_TIMEOUT.as_secs()
         ));
     }
+    tracing::info!(
+        "⏳ [headless] Page settled after {}ms",
+        started.elapsed().as_millis()
+    );
 
     tracing::info!("✅ [headless] Successfully fetched {} bytes", html.len());
 
     Ok(html)
 }
 
+/// Poll until the document has loaded, isn't a bot check, and its HTML stayed the same
+/// for [`SETTLE_DURATION`] (the page stopped loading content). Returns the HTML at that
+/// point, or whatever the page shows once `timeout` runs out.
+fn wait_until_settled(tab: &Tab, timeout: Duration) -> Result<String> {
+    let deadline = Instant::now() + timeout;
+    let mut last_html = String::new();
+    let mut unchanged_since = Instant::now();
+
+    loop {
+        let html = tab
+            .get_content()
+            .map_err(|e| anyhow::anyhow!("Failed to get page content: {}", e))?;
+        if html != last_html {
+            last_html = html;
+            unchanged_since = Instant::now();
+        } else if unchanged_since.elapsed() >= SETTLE_DURATION
+            && !is_challenge_page(&last_html)
+            && document_complete(tab)
+        {
+            return Ok(last_html);
+        }
+
+        if Instant::now() >= deadline {
+            return Ok(last_html);
+        }
+        std::thread::sleep(SETTLE_POLL_INTERVAL);
+    }
+}
+
+fn document_complete(tab: &Tab) -> bool {
+    tab.evaluate("document.readyState", false)
+        .ok()
+        .and_then(|result| result.value)
+        .is_some_and(|state| state == "complete")
+}
+
+fn is_challenge_page(html: &str) -> bool {
+    CHALLENGE_INDICATORS
+        .iter()
+        .any(|indicator| html.contains(indicator))
+}
+
+/// Wait until `selector` matches an element, e.g. the first search result. Returns
+/// false on timeout; the caller can still parse whatever loaded.
+pub fn wait_for_selector(tab: &Tab, selector: &str, timeout: Duration) -> bool {
+    let started = Instant::now();
+    let found = tab
+        .wait_for_element_with_custom_timeout(selector, timeout)
+        .is_ok();
+    tracing::info!(
+        "⏳ [headless] '{}' {} after {}ms",
+        selector,
+        if found { "appeared" } else { "not found" },
+        started.elapsed().as_millis()
+    );
+    found
+}
+
 /// Async wrapper for headless browser fallback
 /// Runs the blocking headless browser operation in a separate thread
 pub async fn fetch_with_headless_fallback(
     url: &str,
     max_chars: Option<usize>,
+    budget: Option<&ByteBudget>,
 ) -> FetchedWebResource {
     let url_owned = url.to_string();
 
@@ -118,7 +234,10 @@ pub async fn fetch_with_headless_fallback(
 
     match html_result {
         Ok(Ok(html)) => {
-            // Successfully got HTML from headless browser
+            // Successfully got HTML from headless browser; its assets aren't counted
+            if let Some(budget) = budget {
+                budget.consume(html.len() as u64);
+            }
             let favicon_url = extract_favicon_url(url, Some(&html));
             process_html_with_readability(
                 url,
@@ -0,0 +1,144 @@
+//! Per-host politeness and circuit breaking for local fetches
+//!
+//! Search results often contain several pages from the same site. Requests to one host
+//! are spaced [`MIN_HOST_INTERVAL`] apart, and a host that failed
+//! [`FAILURE_THRESHOLD`] times in a row (errors, bot checks the headless fallback
+//! couldn't pass) is skipped for [`COOLDOWN`] instead of costing another 20-second
+//! headless attempt. After the cooldown one request is let through; a success closes
+//! the breaker, a failure opens it again.
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use url::Url;
+
+const MIN_HOST_INTERVAL: Duration = Duration::from_millis(500);
+const FAILURE_THRESHOLD: u32 = 3;
+const COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+lazy_static! {
+    static ref HOSTS: HostGuard = HostGuard::default();
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    next_slot: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct HostGuard {
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+impl HostGuard {
+    /// Time to wait before requesting `host`, or the reason to skip it
+    fn reserve(&self, host: &str, now: Instant) -> Result<Duration, String> {
+        let mut hosts = self.hosts.lock().map_err(|e| e.to_string())?;
+        let state = hosts.entry(host.to_string()).or_default();
+
+        if let Some(open_until) = state.open_until {
+            if now < open_until {
+                return Err(format!(
+                    "Skipped: {} failed {} times in a row, retrying in {}s",
+                    host,
+                    state.consecutive_failures,
+                    (open_until - now).as_secs()
+                ));
+            }
+            // Half-open: this request decides whether the breaker closes
+            state.open_until = None;
+        }
+
+        let slot = state.next_slot.map_or(now, |next| next.max(now));
+        state.next_slot = Some(slot + MIN_HOST_INTERVAL);
+        Ok(slot - now)
+    }
+
+    fn record(&self, host: &str, failed: bool, now: Instant) {
+        let Ok(mut hosts) = self.hosts.lock() else {
+            return;
+        };
+        let state = hosts.entry(host.to_string()).or_default();
+        if !failed {
+            state.consecutive_failures = 0;
+            return;
+        }
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= FAILURE_THRESHOLD {
+            tracing::warn!(
+                "🚧 [fetcher] {} failed {} times in a row, pausing it for {}s",
+                host,
+                state.consecutive_failures,
+                COOLDOWN.as_secs()
+            );
+            state.open_until = Some(now + COOLDOWN);
+        }
+    }
+}
+
+pub(super) fn host_of(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(|host| host.to_lowercase())
+}
+
+/// Wait for the host's next request slot. Returns the reason when the host is skipped.
+pub(super) async fn acquire(host: &str) -> Result<(), String> {
+    let wait = HOSTS.reserve(host, Instant::now())?;
+    if !wait.is_zero() {
+        tracing::info!(
+            "⏳ [fetcher] Spacing requests to {}: {}ms",
+            host,
+            wait.as_millis()
+        );
+        tokio::time::sleep(wait).await;
+    }
+    Ok(())
+}
+
+/// Record a fetch outcome. Problems with the URL, content type or download budget
+/// aren't the host's fault.
+pub(super) fn record(host: &str, error: Option<&str>) {
+    let failed = error.is_some_and(|e| {
+        !e.starts_with("Invalid URL")
+            && !e.starts_with("Unsupported content type")
+            && !e.starts_with("Download budget")
+    });
+    HOSTS.record(host, failed, Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spacing_and_breaker() {
+        let guard = HostGuard::default();
+        let now = Instant::now();
+
+        assert_eq!(guard.reserve("example.com", now), Ok(Duration::ZERO));
+        assert_eq!(guard.reserve("example.com", now), Ok(MIN_HOST_INTERVAL));
+        assert_eq!(guard.reserve("other.org", now), Ok(Duration::ZERO));
+
+        for _ in 0..FAILURE_THRESHOLD {
+            guard.record("example.com", true, now);
+        }
+        assert!(guard.reserve("example.com", now).is_err());
+
+        // Half-open after the cooldown; one more failure opens it again
+        let later = now + COOLDOWN;
+        assert!(guard.reserve("example.com", later).is_ok());
+        guard.record("example.com", true, later);
+        assert!(guard.reserve("example.com", later).is_err());
+
+        let much_later = later + COOLDOWN;
+        assert!(guard.reserve("example.com", much_later).is_ok());
+        guard.record("example.com", false, much_later);
+        guard.record("example.com", true, much_later);
+        assert!(guard.reserve("example.com", much_later).is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+//! Identity that fetches and searches present to sites
+//!
+//! Plain HTTP fetches identify as ChatShell, and headless Chrome as a desktop Chrome that
+//! prefers English. Region-locked or localizing sites then serve the wrong language or
+//! block the request, so both can be overridden in settings ([`FETCH_USER_AGENT_KEY`],
+//! [`FETCH_ACCEPT_LANGUAGE_KEY`]). The overrides are cached here because the fetch and
+//! search code has no database access; [`reload_fetch_identity`] refreshes the cache at
+//! startup and whenever either setting is saved.
+
+use crate::db::Database;
+use headless_chrome::Tab;
+use lazy_static::lazy_static;
+use reqwest::RequestBuilder;
+use reqwest::header::{ACCEPT_LANGUAGE, USER_AGENT};
+use std::sync::RwLock;
+
+/// Settings key: user agent sent by HTTP fetches and headless Chrome (unset = defaults)
+pub const FETCH_USER_AGENT_KEY: &str = "web_fetch_user_agent";
+
+/// Settings key: Accept-Language value, e.g. "de-DE,de;q=0.9,en;q=0.5"
+pub const FETCH_ACCEPT_LANGUAGE_KEY: &str = "web_fetch_accept_language";
+
+const DEFAULT_BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// Platform reported along with the default browser user agent
+const DEFAULT_BROWSER_PLATFORM: &str = "macOS";
+
+/// Accept-Language headless tabs default to; Baidu search passes a Chinese one instead
+pub const DEFAULT_ACCEPT_LANGUAGE: &str = "en-US,en;q=0.9";
+
+#[derive(Debug, Clone, Default)]
+struct FetchIdentity {
+    user_agent: Option<String>,
+    accept_language: Option<String>,
+}
+
+lazy_static! {
+    static ref IDENTITY: RwLock<FetchIdentity> = RwLock::new(FetchIdentity::default());
+}
+
+fn current() -> FetchIdentity {
+    IDENTITY
+        .read()
+        .map(|identity| identity.clone())
+        .unwrap_or_default()
+}
+
+/// Load the identity overrides from settings
+pub async fn reload_fetch_identity(db: &Database) {
+    let identity = FetchIdentity {
+        user_agent: non_empty_setting(db, FETCH_USER_AGENT_KEY).await,
+        accept_language: non_empty_setting(db, FETCH_ACCEPT_LANGUAGE_KEY).await,
+    };
+    tracing::info!(
+        "🪪 [fetcher] User agent: {}, Accept-Language: {}",
+        identity.user_agent.as_deref().unwrap_or("default"),
+        identity.accept_language.as_deref().unwrap_or("default")
+    );
+    if let Ok(mut current) = IDENTITY.write() {
+        *current = identity;
+    }
+}
+
+async fn non_empty_setting(db: &Database, key: &str) -> Option<String> {
+    db.get_setting(key)
+        .await
+        .ok()
+        .flatten()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Add the configured headers to an HTTP request; without overrides the client's
+/// defaults apply
+pub(super) fn apply_to_request(request: RequestBuilder) -> RequestBuilder {
+    let identity = current();
+    let request = match identity.user_agent {
+        Some(user_agent) => request.header(USER_AGENT, user_agent),
+        None => request,
+    };
+    match identity.accept_language {
+        Some(accept_language) => request.header(ACCEPT_LANGUAGE, accept_language),
+        None => request,
+    }
+}
+
+/// Set the user agent and Accept-Language of a headless tab; call before navigating.
+/// `default_accept_language` applies unless the setting overrides it.
+pub fn apply_fetch_identity(tab: &Tab, default_accept_language: &str) -> anyhow::Result<()> {
+    let identity = current();
+    // A custom user agent may claim any platform, so none is reported with it
+    let (user_agent, platform) = match identity.user_agent.as_deref() {
+        Some(user_agent) => (user_agent, None),
+        None => (DEFAULT_BROWSER_USER_AGENT, Some(DEFAULT_BROWSER_PLATFORM)),
+    };
+    let accept_language = identity
+        .accept_language
+        .as_deref()
+        .unwrap_or(default_accept_language);
+    tab.set_user_agent(user_agent, Some(accept_language), platform)
+        .map_err(|e| anyhow::anyhow!("Failed to set user agent: {}", e))?;
+    Ok(())
+}
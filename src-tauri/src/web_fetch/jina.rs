@@ -1,12 +1,17 @@
 use chrono::Utc;
 
+use super::budget::{ByteBudget, read_body};
 use super::extractors::extract_favicon_url;
 use super::types::{FetchedWebResource, HTTP_CLIENT, WebFetchMetadata};
 
 const JINA_READER_BASE_URL: &str = "https://r.jina.ai/";
 
 /// Fetch webpage content using Jina Reader API
-pub async fn fetch_with_jina(url: &str, api_key: Option<&str>) -> FetchedWebResource {
+pub async fn fetch_with_jina(
+    url: &str,
+    api_key: Option<&str>,
+    budget: Option<&ByteBudget>,
+) -> FetchedWebResource {
     tracing::info!("📡 [jina] Fetching via Jina Reader: {}", url);
 
     let jina_url = format!("{}{}", JINA_READER_BASE_URL, url);
@@ -35,7 +40,7 @@ pub async fn fetch_with_jina(url: &str, api_key: Option<&str>) -> FetchedWebReso
                 );
             }
 
-            match response.text().await {
+            match read_body(response, budget).await {
                 Ok(content) => {
                     tracing::info!(
                         "✅ [jina] Successfully fetched {} bytes from Jina",
@@ -65,12 +70,7 @@ pub async fn fetch_with_jina(url: &str, api_key: Option<&str>) -> FetchedWebReso
                 }
                 Err(e) => {
                     tracing::info!("❌ [jina] Failed to read Jina response: {}", e);
-                    FetchedWebResource::error(
-                        url,
-                        String::new(),
-                        format!("Failed to read Jina response: {}", e),
-                        None,
-                    )
+                    FetchedWebResource::error(url, String::new(), e, None)
                 }
             }
         }
@@ -0,0 +1,372 @@
+//! Technical markup that Readability and htmd would otherwise degrade
+//!
+//! Readability strips `class` attributes, which is where highlighters put the code
+//! language, and drops the `<script type="math/tex">` sources MathJax renders from.
+//! htmd loses `<th>` row headers in table bodies, leaves a `<pre>` without `<code>`
+//! unfenced, and turns MathML into a run of symbols. So before extraction, code blocks,
+//! data tables and formulas are converted to markdown here and replaced by plain-text
+//! placeholders, which [`PreparedHtml::restore`] swaps back after conversion. Whatever
+//! Readability discards takes its placeholder with it.
+
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node, Selector};
+
+const PLACEHOLDER_PREFIX: &str = "CHATSHELLBLOCK";
+
+/// Renderings that duplicate the source of a formula next to it
+const MATH_RENDERINGS: &str = ".katex-html, .MathJax_Preview, .MathJax, .MathJax_Display, \
+    .MathJax_SVG, .MathJax_CHTML, img.mwe-math-fallback-image-inline, \
+    img.mwe-math-fallback-image-display";
+
+/// Cell content this long means a layout table rather than data
+const MAX_DATA_CELL_CHARS: usize = 500;
+
+pub(super) struct PreparedHtml {
+    pub html: String,
+    /// Markdown for each placeholder, indexed by its number
+    blocks: Vec<String>,
+}
+
+impl PreparedHtml {
+    /// Put the converted blocks back into the markdown
+    pub fn restore(&self, markdown: &str) -> String {
+        // A block may contain placeholders of earlier ones (formulas in a table cell)
+        self.blocks
+            .iter()
+            .enumerate()
+            .rev()
+            .fold(markdown.to_string(), |markdown, (index, block)| {
+                markdown.replace(&placeholder(index), block)
+            })
+    }
+}
+
+fn placeholder(index: usize) -> String {
+    format!("{}{}X", PLACEHOLDER_PREFIX, index)
+}
+
+/// Replace formulas, data tables and code blocks with placeholders
+pub(super) fn prepare_html(html: &str) -> PreparedHtml {
+    let mut document = Html::parse_document(html);
+    let mut blocks = Vec::new();
+
+    remove_all(&mut document, MATH_RENDERINGS);
+    replace_all(&mut document, "math", &mut blocks, mathml_block);
+    replace_all(
+        &mut document,
+        r#"script[type^="math/tex"]"#,
+        &mut blocks,
+        tex_script_block,
+    );
+    // Tables holding code blocks are left to htmd, their code blocks are converted below
+    replace_all(&mut document, "table", &mut blocks, table_block);
+    replace_all(&mut document, "pre", &mut blocks, code_block);
+
+    PreparedHtml {
+        html: document.html(),
+        blocks,
+    }
+}
+
+fn remove_all(document: &mut Html, selector: &str) {
+    let Ok(selector) = Selector::parse(selector) else {
+        return;
+    };
+    let ids: Vec<_> = document.select(&selector).map(|el| el.id()).collect();
+    for id in ids {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+}
+
+/// Replace each matching element `convert` returns markdown for. Code blocks keep their
+/// `<pre>` with the placeholder inside, so they still count as content for Readability.
+fn replace_all(
+    document: &mut Html,
+    selector: &str,
+    blocks: &mut Vec<String>,
+    convert: fn(ElementRef) -> Option<String>,
+) {
+    let Ok(selector) = Selector::parse(selector) else {
+        return;
+    };
+    let replacements: Vec<_> = document
+        .select(&selector)
+        .filter_map(|el| {
+            convert(el).map(|markdown| (el.id(), el.value().name() == "pre", markdown))
+        })
+        .collect();
+
+    for (id, keep_element, markdown) in replacements {
+        let Some(mut node) = document.tree.get_mut(id) else {
+            continue;
+        };
+        let text = Node::Text(Text {
+            text: placeholder(blocks.len()).into(),
+        });
+        if keep_element {
+            while let Some(mut child) = node.first_child() {
+                child.detach();
+            }
+            node.append(text);
+        } else {
+            node.insert_before(text);
+            node.detach();
+        }
+        blocks.push(markdown);
+    }
+}
+
+fn code_block(pre: ElementRef) -> Option<String> {
+    let code = pre.text().collect::<String>();
+    let code = code.trim_end_matches('\n');
+    if code.trim().is_empty() {
+        return None;
+    }
+
+    // Longer than any backtick run in the code
+    let mut longest_run = 0;
+    let mut run = 0;
+    for c in code.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest_run = longest_run.max(run);
+    }
+    let fence = "`".repeat((longest_run + 1).max(3));
+
+    let language = code_language(pre).unwrap_or_default();
+    Some(format!(
+        "\n\n{}{}\n{}\n{}\n\n",
+        fence, language, code, fence
+    ))
+}
+
+/// Language hint from the `<pre>`, its `<code>` or a highlighter wrapper around it
+fn code_language(pre: ElementRef) -> Option<String> {
+    let code = pre
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|el| el.value().name() == "code");
+    let parent = pre.parent().and_then(ElementRef::wrap);
+
+    [code, Some(pre), parent]
+        .into_iter()
+        .flatten()
+        .find_map(|el| {
+            let element = el.value();
+            let from_class = element.attr("class").and_then(|class| {
+                class.split_whitespace().find_map(|token| {
+                    ["language-", "lang-", "highlight-source-"]
+                        .iter()
+                        .find_map(|prefix| token.strip_prefix(prefix))
+                })
+            });
+            // SyntaxHighlighter: class="brush: js"
+            let from_brush = element.attr("class").and_then(|class| {
+                class
+                    .split_once("brush:")
+                    .and_then(|(_, rest)| rest.split([' ', ';']).find(|s| !s.is_empty()))
+            });
+            from_class
+                .or(from_brush)
+                .or_else(|| element.attr("data-lang"))
+                .or_else(|| element.attr("data-language"))
+                .or_else(|| {
+                    (el.id() == pre.id())
+                        .then(|| element.attr("lang"))
+                        .flatten()
+                })
+                .map(|language| language.trim().to_lowercase())
+                .filter(|language| {
+                    !language.is_empty()
+                        && language.len() <= 20
+                        && !["none", "nohighlight", "plaintext"].contains(&language.as_str())
+                        && language
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || "+#-_.".contains(c))
+                })
+        })
+}
+
+fn table_block(table: ElementRef) -> Option<String> {
+    let element = table.value();
+    if element.attr("role") == Some("presentation") {
+        return None;
+    }
+    let Ok(nested) = Selector::parse("table, pre, blockquote, h1, h2, h3, h4, h5, h6") else {
+        return None;
+    };
+    if table.select(&nested).next().is_some() {
+        return None;
+    }
+
+    let rows: Vec<Vec<String>> = table_rows(table)
+        .into_iter()
+        .map(|row| {
+            row.children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+                .flat_map(|cell| {
+                    let span = cell
+                        .value()
+                        .attr("colspan")
+                        .and_then(|s| s.trim().parse::<usize>().ok())
+                        .unwrap_or(1)
+                        .clamp(1, 20);
+                    std::iter::once(cell_markdown(cell))
+                        .chain(std::iter::repeat_n(String::new(), span - 1))
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|row| !row.is_empty())
+        .collect();
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if rows.len() < 2
+        || columns == 0
+        || rows
+            .iter()
+            .flatten()
+            .any(|cell| cell.chars().count() > MAX_DATA_CELL_CHARS)
+    {
+        return None;
+    }
+
+    let caption = Selector::parse("caption")
+        .ok()
+        .and_then(|sel| table.select(&sel).next())
+        .map(|caption| caption.text().collect::<String>().trim().to_string())
+        .filter(|caption| !caption.is_empty());
+
+    let mut markdown = String::from("\n\n");
+    if let Some(caption) = caption {
+        markdown.push_str(&format!("{}\n\n", caption));
+    }
+    for (index, row) in rows.iter().enumerate() {
+        let cells: Vec<&str> = (0..columns)
+            .map(|i| row.get(i).map_or("", String::as_str))
+            .collect();
+        markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+        // The first row is the header: GFM tables need one
+        if index == 0 {
+            markdown.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+        }
+    }
+    markdown.push('\n');
+    Some(markdown)
+}
+
+/// Rows of the table itself, whether or not they sit in a thead/tbody/tfoot
+fn table_rows(table: ElementRef) -> Vec<ElementRef> {
+    let mut rows = Vec::new();
+    for child in table.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "tr" => rows.push(child),
+            "thead" | "tbody" | "tfoot" => rows.extend(
+                child
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|row| row.value().name() == "tr"),
+            ),
+            _ => {}
+        }
+    }
+    rows
+}
+
+fn cell_markdown(cell: ElementRef) -> String {
+    let markdown = htmd::convert(&cell.inner_html()).unwrap_or_default();
+    markdown
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+fn mathml_block(math: ElementRef) -> Option<String> {
+    let tex = Selector::parse(r#"annotation[encoding="application/x-tex"]"#)
+        .ok()
+        .and_then(|sel| math.select(&sel).next())
+        .map(|annotation| annotation.text().collect::<String>())
+        .or_else(|| math.value().attr("alttext").map(str::to_string))
+        .unwrap_or_else(|| math.text().collect());
+
+    let display = math.value().attr("display") == Some("block")
+        || math.ancestors().filter_map(ElementRef::wrap).any(|el| {
+            el.value().attr("class").is_some_and(|class| {
+                class.contains("katex-display") || class.contains("math-display")
+            })
+        });
+    format_math(&tex, display)
+}
+
+/// MathJax 2 keeps the source in `<script type="math/tex">`, or `math/tex; mode=display`
+fn tex_script_block(script: ElementRef) -> Option<String> {
+    let display = script
+        .value()
+        .attr("type")
+        .is_some_and(|kind| kind.contains("mode=display"));
+    format_math(&script.text().collect::<String>(), display)
+}
+
+fn format_math(tex: &str, display: bool) -> Option<String> {
+    let tex = tex.split_whitespace().collect::<Vec<_>>().join(" ");
+    // Wikipedia wraps every formula in {\displaystyle ...}
+    let tex = ["{\\displaystyle", "{\\textstyle"]
+        .iter()
+        .find_map(|wrapper| tex.strip_prefix(wrapper))
+        .and_then(|inner| inner.strip_suffix('}'))
+        .map_or(tex.as_str(), str::trim);
+    if tex.is_empty() {
+        return None;
+    }
+    Some(if display {
+        format!("\n\n$$\n{}\n$$\n\n", tex)
+    } else {
+        format!("${}$", tex)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_markdown(html: &str) -> String {
+        let prepared = prepare_html(html);
+        prepared.restore(&htmd::convert(&prepared.html).unwrap())
+    }
+
+    #[test]
+    fn test_code_block_keeps_language() {
+        let markdown = to_markdown(
+            r#"<div class="highlight highlight-source-rust"><pre><span class="k">fn</span> main() {}
+</pre></div><pre><code class="language-py">print("`")</code></pre>"#,
+        );
+        assert!(markdown.contains("```rust\nfn main() {}\n```"));
+        assert!(markdown.contains("```py\nprint(\"`\")\n```"));
+    }
+
+    #[test]
+    fn test_table_with_row_headers() {
+        let markdown = to_markdown(
+            "<table><caption>Limits</caption><tr><th>Plan</th><th>Requests</th></tr>\
+             <tr><th>Free</th><td>100 | day</td></tr>\
+             <tr><th>Pro</th><td><p>10k</p></td></tr></table>",
+        );
+        assert!(markdown.contains("Limits\n\n| Plan | Requests |\n| --- | --- |\n"));
+        assert!(markdown.contains("| Free | 100 \\| day |\n| Pro | 10k |"));
+    }
+
+    #[test]
+    fn test_math_uses_tex_source() {
+        let markdown = to_markdown(
+            r#"<p>Energy <span class="katex"><span class="katex-mathml"><math><semantics><mrow><mi>E</mi></mrow><annotation encoding="application/x-tex">E = mc^2</annotation></semantics></math></span><span class="katex-html" aria-hidden="true">E=mc2</span></span> and</p>
+            <script type="math/tex; mode=display">\sum_i x_i</script>
+            <math alttext="{\displaystyle a+b}"><mi>a</mi><mo>+</mo><mi>b</mi></math>"#,
+        );
+        assert!(markdown.contains("Energy $E = mc^2$ and"));
+        assert!(!markdown.contains("E=mc2"));
+        assert!(markdown.contains("$$\n\\sum_i x_i\n$$"));
+        assert!(markdown.contains("$a+b$"));
+    }
+}
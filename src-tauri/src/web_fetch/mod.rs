@@ -1,7 +1,14 @@
+mod budget;
+mod chrome;
 mod extractors;
+mod favicon;
+mod feed;
 mod fetcher;
 mod headless;
+mod host_guard;
+mod identity;
 mod jina;
+mod markup;
 mod processors;
 mod types;
 
@@ -9,8 +16,19 @@ mod types;
 pub use types::{FetchedWebResource, STEALTH_JS};
 
 // Re-export public functions
+pub use budget::{ByteBudget, DEFAULT_FETCH_BUDGET_MB, FETCH_BUDGET_MB_KEY};
+pub use chrome::{
+    CHROMIUM_DIR_NAME, ChromeStatus, ChromeUnavailable, browser_app_error, chrome_status,
+    download_chromium, set_chromium_dir,
+};
+pub use favicon::{Favicon, download_favicon};
+pub use feed::{Feed, FeedItem, fetch_feed};
 pub use fetcher::{
     FetchConfig, FetchMode, LocalMethod, build_llm_content_with_attachments,
     fetch_urls_with_config, fetch_web_resource_with_config,
 };
-pub use headless::create_new_browser;
+pub use headless::{close_all_browsers, create_new_browser, live_browser_count, wait_for_selector};
+pub use identity::{
+    DEFAULT_ACCEPT_LANGUAGE, FETCH_ACCEPT_LANGUAGE_KEY, FETCH_USER_AGENT_KEY, apply_fetch_identity,
+    reload_fetch_identity,
+};
@@ -8,6 +8,7 @@ use super::extractors::{
     extract_headings, extract_meta_description, extract_meta_keywords, normalize_html_images,
     truncate_by_chars,
 };
+use super::markup::prepare_html;
 use super::types::{FetchedWebResource, WebFetchMetadata};
 
 /// Process HTML content using Mozilla's Readability algorithm and convert to markdown
@@ -37,8 +38,11 @@ pub fn process_html_with_readability(
         }
     };
 
+    // Code blocks, tables and formulas are converted up front and restored after htmd
+    let prepared = prepare_html(html_content);
+
     // Create a cursor from the HTML content for readability
-    let mut cursor = Cursor::new(html_content.as_bytes());
+    let mut cursor = Cursor::new(prepared.html.as_bytes());
 
     let (title, content_html) = match extract(&mut cursor, &parsed_url) {
         Ok(product) => {
@@ -60,9 +64,13 @@ pub fn process_html_with_readability(
 
             let body_html = Selector::parse("body")
                 .ok()
-                .and_then(|sel| document.select(&sel).next())
-                .map(|el| el.html())
-                .unwrap_or_else(|| html_content.to_string());
+                .and_then(|sel| {
+                    Html::parse_document(&prepared.html)
+                        .select(&sel)
+                        .next()
+                        .map(|el| el.html())
+                })
+                .unwrap_or_else(|| prepared.html.clone());
 
             (title, body_html)
         }
@@ -72,7 +80,7 @@ pub fn process_html_with_readability(
     let normalized_html = normalize_html_images(&content_html);
 
     // Convert extracted HTML to markdown
-    let markdown = htmd::convert(&normalized_html).unwrap_or_default();
+    let markdown = prepared.restore(&htmd::convert(&normalized_html).unwrap_or_default());
     let original_length = markdown.chars().count();
 
     let (extracted_content, truncated) = match max_chars {
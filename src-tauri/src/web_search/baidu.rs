@@ -6,12 +6,14 @@
 use anyhow::Result;
 use chrono::Utc;
 use scraper::{Html, Selector};
-use std::time::Duration;
 use url::form_urlencoded;
 
-use crate::web_fetch::{STEALTH_JS, create_new_browser};
+use crate::web_fetch::{STEALTH_JS, apply_fetch_identity, create_new_browser, wait_for_selector};
 
 use super::types::{SearchProvider, SearchResultItem, WebSearchResponse};
+use super::utils::RESULTS_WAIT_TIMEOUT;
+
+const RESULT_SELECTOR: &str = "div.result, div.c-container";
 
 /// Perform Baidu search using headless Chrome
 ///
@@ -50,12 +52,8 @@ fn search_baidu_sync(query: &str, max_results: usize) -> Result<Vec<SearchResult
         .new_tab()
         .map_err(|e| anyhow::anyhow!("Failed to create tab: {}", e))?;
 
-    // Set realistic User-Agent before navigation
-    tab.set_user_agent(
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        Some("zh-CN,zh;q=0.9,en;q=0.8"),
-        Some("macOS"),
-    ).map_err(|e| anyhow::anyhow!("Failed to set user agent: {}", e))?;
+    // Set realistic User-Agent before navigation (or the one configured in settings)
+    apply_fetch_identity(&tab, "zh-CN,zh;q=0.9,en;q=0.8")?;
 
     // Navigate to blank page first to inject stealth JS
     tab.navigate_to("about:blank")
@@ -83,9 +81,9 @@ fn search_baidu_sync(query: &str, max_results: usize) -> Result<Vec<SearchResult
     tab.wait_until_navigated()
         .map_err(|e| anyhow::anyhow!("Navigation timeout: {}", e))?;
 
-    // Wait for results to load (Baidu may need a bit more time)
+    // Wait for results to load
     tracing::info!("⏳ [web_search] Waiting for search results to load...");
-    std::thread::sleep(Duration::from_secs(4));
+    wait_for_selector(&tab, RESULT_SELECTOR, RESULTS_WAIT_TIMEOUT);
 
     let html = tab
         .get_content()
@@ -110,7 +108,7 @@ fn parse_baidu_results(html: &str, max_results: usize) -> Vec<SearchResultItem>
     // - h3.t > a for the title and URL
     // - div.c-abstract or span.content-right_8Zs40 for the snippet
 
-    let result_selector = Selector::parse("div.result, div.c-container").unwrap();
+    let result_selector = Selector::parse(RESULT_SELECTOR).unwrap();
     let title_selector = Selector::parse("h3 a, h3.c-title a").unwrap();
     let snippet_selector =
         Selector::parse("div.c-abstract, span.content-right_8Zs40, div.c-span-last").unwrap();
@@ -5,6 +5,7 @@
 use anyhow::Result;
 use chrono::Local;
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
 use crate::llm::{self, ChatMessage};
 use crate::prompts::SEARCH_DECISION_SYSTEM_PROMPT;
@@ -20,6 +21,7 @@ pub async fn decide_search_needed(
     api_key: Option<&str>,
     base_url: Option<&str>,
     api_style: Option<&str>,
+    cancel_token: CancellationToken,
 ) -> Result<SearchDecisionResult> {
     tracing::info!(
         "🤔 [search_decision] Asking AI if search is needed for: {}",
@@ -59,6 +61,7 @@ pub async fn decide_search_needed(
         api_key.map(|s| s.to_string()),
         base_url.map(|s| s.to_string()),
         api_style.map(|s| s.to_string()),
+        cancel_token,
     )
     .await?;
 
@@ -69,21 +72,46 @@ pub async fn decide_search_needed(
     let parsed: Value = serde_json::from_str(&json_str)
         .map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}", e))?;
 
-    let result = SearchDecisionResult {
-        reasoning: parsed["reasoning"].as_str().unwrap_or("").to_string(),
-        search_needed: parsed["search_needed"].as_bool().unwrap_or(false),
-        search_query: parsed["search_query"].as_str().map(|s| s.to_string()),
-    };
+    let result = parse_decision(&parsed);
 
     tracing::info!(
-        "✅ [search_decision] Decision: search_needed={}, query={:?}",
+        "✅ [search_decision] Decision: search_needed={}, query={:?}, confidence={:?}, alternatives={:?}",
         result.search_needed,
-        result.search_query
+        result.search_query,
+        result.confidence,
+        result.alternative_queries
     );
 
     Ok(result)
 }
 
+/// Most alternative queries kept per decision
+const MAX_ALTERNATIVE_QUERIES: usize = 3;
+
+fn parse_decision(parsed: &Value) -> SearchDecisionResult {
+    let search_query = parsed["search_query"].as_str().map(|s| s.to_string());
+    let alternative_queries = parsed["alternative_queries"]
+        .as_array()
+        .map(|queries| {
+            queries
+                .iter()
+                .filter_map(|q| q.as_str().map(str::trim))
+                .filter(|q| !q.is_empty() && Some(*q) != search_query.as_deref())
+                .take(MAX_ALTERNATIVE_QUERIES)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    SearchDecisionResult {
+        reasoning: parsed["reasoning"].as_str().unwrap_or("").to_string(),
+        search_needed: parsed["search_needed"].as_bool().unwrap_or(false),
+        search_query,
+        alternative_queries,
+        confidence: parsed["confidence"].as_f64().map(|c| c.clamp(0.0, 1.0)),
+    }
+}
+
 /// Extract JSON from AI response (handles markdown code blocks)
 fn extract_json_from_response(response: &str) -> Result<String> {
     let trimmed = response.trim();
@@ -119,3 +147,35 @@ fn extract_json_from_response(response: &str) -> Result<String> {
 
     Err(anyhow::anyhow!("No JSON found in response"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decision_with_alternatives() {
+        let parsed: Value = serde_json::from_str(
+            r#"{
+                "reasoning": "Needs current data",
+                "search_needed": true,
+                "search_query": "rust 2024 edition",
+                "alternative_queries": ["rust 2024 edition", " ", "rust edition guide 2024"],
+                "confidence": 1.4
+            }"#,
+        )
+        .unwrap();
+        let result = parse_decision(&parsed);
+        assert!(result.search_needed);
+        assert_eq!(result.alternative_queries, vec!["rust edition guide 2024"]);
+        assert_eq!(result.confidence, Some(1.0));
+    }
+
+    #[test]
+    fn test_parse_decision_without_new_fields() {
+        let parsed: Value =
+            serde_json::from_str(r#"{"reasoning": "Known", "search_needed": false}"#).unwrap();
+        let result = parse_decision(&parsed);
+        assert!(result.alternative_queries.is_empty());
+        assert_eq!(result.confidence, None);
+    }
+}
@@ -6,12 +6,18 @@
 use anyhow::Result;
 use chrono::Utc;
 use scraper::{Html, Selector};
-use std::time::Duration;
 use url::form_urlencoded;
 
-use crate::web_fetch::{STEALTH_JS, create_new_browser};
+use crate::web_fetch::{
+    DEFAULT_ACCEPT_LANGUAGE, STEALTH_JS, apply_fetch_identity, create_new_browser,
+    wait_for_selector,
+};
 
 use super::types::{DuckDuckGoSearchResponse, SearchResultItem};
+use super::utils::RESULTS_WAIT_TIMEOUT;
+
+/// Each result of the HTML version
+const RESULT_SELECTOR: &str = ".result";
 
 /// Perform DuckDuckGo search using headless Chrome
 ///
@@ -53,12 +59,8 @@ fn search_duckduckgo_sync(query: &str, max_results: usize) -> Result<Vec<SearchR
         .new_tab()
         .map_err(|e| anyhow::anyhow!("Failed to create tab: {}", e))?;
 
-    // Set realistic User-Agent before navigation
-    tab.set_user_agent(
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        Some("en-US,en;q=0.9"),
-        Some("macOS"),
-    ).map_err(|e| anyhow::anyhow!("Failed to set user agent: {}", e))?;
+    // Set realistic User-Agent before navigation (or the one configured in settings)
+    apply_fetch_identity(&tab, DEFAULT_ACCEPT_LANGUAGE)?;
 
     // Navigate to blank page first to inject stealth JS
     tab.navigate_to("about:blank")
@@ -88,7 +90,7 @@ fn search_duckduckgo_sync(query: &str, max_results: usize) -> Result<Vec<SearchR
 
     // Wait for results to load
     tracing::info!("⏳ [web_search] Waiting for search results to load...");
-    std::thread::sleep(Duration::from_secs(3));
+    wait_for_selector(&tab, RESULT_SELECTOR, RESULTS_WAIT_TIMEOUT);
 
     let html = tab
         .get_content()
@@ -115,7 +117,7 @@ fn parse_duckduckgo_results(html: &str, max_results: usize) -> Vec<SearchResultI
     //   <a class="result__snippet">Snippet text...</a>
     // </div>
 
-    let result_selector = Selector::parse(".result").unwrap();
+    let result_selector = Selector::parse(RESULT_SELECTOR).unwrap();
     let title_selector = Selector::parse(".result__a").unwrap();
     let snippet_selector = Selector::parse(".result__snippet").unwrap();
 
@@ -0,0 +1,254 @@
+//! Search provider health and fallback ordering
+//!
+//! The scraped engines break without notice (markup changes, bot checks), so each
+//! search records its outcome per provider. [`search_with_fallback`] tries the chosen
+//! provider first unless it has been failing, then the others by recent success rate.
+//! A search that returns no results counts as a failure: a broken result parser looks
+//! exactly like that.
+
+use anyhow::Result;
+use chrono::Utc;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::search;
+use super::types::{SearchProvider, WebSearchResponse};
+use crate::web_fetch::ChromeUnavailable;
+
+/// Outcomes kept per provider
+const WINDOW: usize = 20;
+/// Outcomes needed before a provider's success rate is trusted
+const MIN_SAMPLES: usize = 3;
+/// Below this success rate the chosen provider is demoted behind healthier ones
+const DEMOTE_BELOW: f64 = 0.5;
+
+lazy_static! {
+    static ref HEALTH: Mutex<HashMap<SearchProvider, ProviderStats>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Default)]
+struct ProviderStats {
+    /// Most recent last
+    outcomes: VecDeque<bool>,
+    last_success_at: Option<String>,
+    last_error: Option<String>,
+    last_error_at: Option<String>,
+    last_latency_ms: Option<u64>,
+}
+
+impl ProviderStats {
+    fn push(&mut self, success: bool) {
+        if self.outcomes.len() == WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(success);
+    }
+
+    fn success_rate(&self) -> Option<f64> {
+        (self.outcomes.len() >= MIN_SAMPLES).then(|| {
+            self.outcomes.iter().filter(|ok| **ok).count() as f64 / self.outcomes.len() as f64
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchProviderHealth {
+    pub id: String,
+    pub name: String,
+    /// Share of the last searches that returned results; `None` until there are enough
+    pub success_rate: Option<f64>,
+    pub samples: usize,
+    pub last_success_at: Option<String>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+    pub last_latency_ms: Option<u64>,
+}
+
+/// Health of every provider, in the order a search would try them
+pub fn provider_health(preferred: SearchProvider) -> Vec<SearchProviderHealth> {
+    let Ok(health) = HEALTH.lock() else {
+        return Vec::new();
+    };
+    fallback_order(preferred, &health)
+        .into_iter()
+        .map(|provider| {
+            let stats = health.get(&provider);
+            SearchProviderHealth {
+                id: provider.id().to_string(),
+                name: provider.display_name().to_string(),
+                success_rate: stats.and_then(ProviderStats::success_rate),
+                samples: stats.map_or(0, |s| s.outcomes.len()),
+                last_success_at: stats.and_then(|s| s.last_success_at.clone()),
+                last_error: stats.and_then(|s| s.last_error.clone()),
+                last_error_at: stats.and_then(|s| s.last_error_at.clone()),
+                last_latency_ms: stats.and_then(|s| s.last_latency_ms),
+            }
+        })
+        .collect()
+}
+
+/// Search with `preferred`, falling back to the other providers when it fails or finds
+/// nothing. The response's `provider` tells which one served the results.
+pub async fn search_with_fallback(
+    preferred: SearchProvider,
+    query: &str,
+    max_results: usize,
+) -> Result<WebSearchResponse> {
+    let order = match HEALTH.lock() {
+        Ok(health) => fallback_order(preferred, &health),
+        Err(_) => vec![preferred],
+    };
+
+    let mut empty_response = None;
+    let mut first_error = None;
+    for provider in order {
+        let started = Instant::now();
+        let result = search(provider, query, max_results).await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(response) if !response.results.is_empty() => {
+                record(provider, None, latency_ms);
+                if provider != preferred {
+                    tracing::info!(
+                        "🔁 [web_search] {} served the results instead of {}",
+                        provider.display_name(),
+                        preferred.display_name()
+                    );
+                }
+                return Ok(response);
+            }
+            Ok(response) => {
+                record(provider, Some("No results".to_string()), latency_ms);
+                empty_response.get_or_insert(response);
+            }
+            Err(e) => {
+                record(provider, Some(e.to_string()), latency_ms);
+                // Every provider needs the browser, so trying the next one is pointless
+                if e.downcast_ref::<ChromeUnavailable>().is_some() {
+                    return Err(e);
+                }
+                tracing::warn!("⚠️ [web_search] {} failed: {}", provider.display_name(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match (empty_response, first_error) {
+        (Some(response), _) => Ok(response),
+        (None, Some(e)) => Err(e),
+        (None, None) => Err(anyhow::anyhow!("No search provider available")),
+    }
+}
+
+fn record(provider: SearchProvider, error: Option<String>, latency_ms: u64) {
+    let Ok(mut health) = HEALTH.lock() else {
+        return;
+    };
+    let stats = health.entry(provider).or_default();
+    let now = Utc::now().to_rfc3339();
+    stats.push(error.is_none());
+    stats.last_latency_ms = Some(latency_ms);
+    match error {
+        None => stats.last_success_at = Some(now),
+        Some(error) => {
+            stats.last_error = Some(error);
+            stats.last_error_at = Some(now);
+        }
+    }
+}
+
+/// The preferred provider first unless it is failing, then the rest by success rate.
+/// Providers without enough history rank as healthy.
+fn fallback_order(
+    preferred: SearchProvider,
+    health: &HashMap<SearchProvider, ProviderStats>,
+) -> Vec<SearchProvider> {
+    let rate = |provider: &SearchProvider| {
+        health
+            .get(provider)
+            .and_then(ProviderStats::success_rate)
+            .unwrap_or(1.0)
+    };
+
+    let mut others: Vec<SearchProvider> = SearchProvider::all()
+        .into_iter()
+        .filter(|p| *p != preferred)
+        .collect();
+    // Stable, so equally healthy providers keep their default order
+    others.sort_by(|a, b| rate(b).total_cmp(&rate(a)));
+
+    let preferred_rate = rate(&preferred);
+    let insert_at = if preferred_rate < DEMOTE_BELOW {
+        others
+            .iter()
+            .position(|p| rate(p) <= preferred_rate)
+            .unwrap_or(others.len())
+    } else {
+        0
+    };
+    others.insert(insert_at, preferred);
+    others
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(outcomes: &[bool]) -> ProviderStats {
+        let mut stats = ProviderStats::default();
+        for outcome in outcomes {
+            stats.push(*outcome);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_fallback_order() {
+        let mut health = HashMap::new();
+        assert_eq!(
+            fallback_order(SearchProvider::Yahoo, &health),
+            vec![
+                SearchProvider::Yahoo,
+                SearchProvider::DuckDuckGo,
+                SearchProvider::Baidu
+            ]
+        );
+
+        // A failing preferred provider moves behind the healthy ones
+        health.insert(
+            SearchProvider::DuckDuckGo,
+            stats(&[false, false, true, false]),
+        );
+        health.insert(SearchProvider::Yahoo, stats(&[true, false, true]));
+        assert_eq!(
+            fallback_order(SearchProvider::DuckDuckGo, &health),
+            vec![
+                SearchProvider::Baidu,
+                SearchProvider::Yahoo,
+                SearchProvider::DuckDuckGo
+            ]
+        );
+
+        // Too little history to judge
+        health.insert(SearchProvider::DuckDuckGo, stats(&[false, false]));
+        assert_eq!(
+            fallback_order(SearchProvider::DuckDuckGo, &health)[0],
+            SearchProvider::DuckDuckGo
+        );
+    }
+
+    #[test]
+    fn test_window_keeps_recent_outcomes() {
+        let mut stats = stats(&[false; WINDOW]);
+        assert_eq!(stats.success_rate(), Some(0.0));
+        for _ in 0..WINDOW / 2 {
+            stats.push(true);
+        }
+        assert_eq!(stats.outcomes.len(), WINDOW);
+        assert_eq!(stats.success_rate(), Some(0.5));
+    }
+}
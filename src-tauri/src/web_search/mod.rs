@@ -7,10 +7,12 @@
 //! - Baidu
 //!
 //! All providers use headless Chrome with stealth mode to bypass bot detection.
+//! [`search_with_fallback`] moves on to another provider when one fails.
 
 mod baidu;
 mod decision;
 mod duckduckgo;
+mod health;
 mod types;
 mod utils;
 mod yahoo;
@@ -20,6 +22,8 @@ use anyhow::Result;
 // Re-export types
 pub use types::{SearchDecisionResult, SearchProvider, WebSearchResponse};
 
+pub use health::{SearchProviderHealth, provider_health, search_with_fallback};
+
 // Re-export decision and utils functions
 pub use decision::decide_search_needed;
 pub use utils::extract_search_keywords;
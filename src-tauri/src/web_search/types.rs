@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Supported search engine providers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum SearchProvider {
     #[default]
@@ -103,4 +103,9 @@ pub struct SearchDecisionResult {
     pub reasoning: String,
     pub search_needed: bool,
     pub search_query: Option<String>,
+    /// Other queries the model considered
+    #[serde(default)]
+    pub alternative_queries: Vec<String>,
+    /// 0.0-1.0, when the model reported one
+    pub confidence: Option<f64>,
 }
@@ -1,5 +1,10 @@
 //! Utility functions for web search
 
+use std::time::Duration;
+
+/// Longest a search waits for its first result to render
+pub(super) const RESULTS_WAIT_TIMEOUT: Duration = Duration::from_secs(8);
+
 /// Extract search keywords from user input
 ///
 /// This is a simple implementation that extracts the first few lines
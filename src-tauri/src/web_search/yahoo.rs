@@ -6,12 +6,26 @@
 use anyhow::Result;
 use chrono::Utc;
 use scraper::{Html, Selector};
-use std::time::Duration;
 use url::form_urlencoded;
 
-use crate::web_fetch::{STEALTH_JS, create_new_browser};
+use crate::web_fetch::{
+    DEFAULT_ACCEPT_LANGUAGE, STEALTH_JS, apply_fetch_identity, create_new_browser,
+    wait_for_selector,
+};
 
 use super::types::{SearchProvider, SearchResultItem, WebSearchResponse};
+use super::utils::RESULTS_WAIT_TIMEOUT;
+
+/// Yahoo uses different result structures; tried in order
+const RESULT_SELECTORS: [&str; 7] = [
+    "div.algo-sr",
+    "div.dd.algo",
+    "li.ov-a",
+    "div.Sr",
+    "div[data-uuid]",
+    "div.algo",
+    "li.reg.searchCenterMiddle",
+];
 
 /// Perform Yahoo search using headless Chrome
 ///
@@ -50,12 +64,8 @@ fn search_yahoo_sync(query: &str, max_results: usize) -> Result<Vec<SearchResult
         .new_tab()
         .map_err(|e| anyhow::anyhow!("Failed to create tab: {}", e))?;
 
-    // Set realistic User-Agent before navigation
-    tab.set_user_agent(
-        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-        Some("en-US,en;q=0.9"),
-        Some("macOS"),
-    ).map_err(|e| anyhow::anyhow!("Failed to set user agent: {}", e))?;
+    // Set realistic User-Agent before navigation (or the one configured in settings)
+    apply_fetch_identity(&tab, DEFAULT_ACCEPT_LANGUAGE)?;
 
     // Navigate to blank page first to inject stealth JS
     tab.navigate_to("about:blank")
@@ -85,7 +95,7 @@ fn search_yahoo_sync(query: &str, max_results: usize) -> Result<Vec<SearchResult
 
     // Wait for results to load
     tracing::info!("⏳ [web_search] Waiting for search results to load...");
-    std::thread::sleep(Duration::from_secs(3));
+    wait_for_selector(&tab, &RESULT_SELECTORS.join(", "), RESULTS_WAIT_TIMEOUT);
 
     let html = tab
         .get_content()
@@ -106,17 +116,7 @@ fn parse_yahoo_results(html: &str, max_results: usize) -> Vec<SearchResultItem>
     let mut results = Vec::new();
 
     // Yahoo uses different structures - try multiple selectors
-    let selectors_to_try = [
-        "div.algo-sr",
-        "div.dd.algo",
-        "li.ov-a",
-        "div.Sr",
-        "div[data-uuid]",
-        "div.algo",
-        "li.reg.searchCenterMiddle",
-    ];
-
-    for selector_str in selectors_to_try {
+    for selector_str in RESULT_SELECTORS {
         let result_selector = match Selector::parse(selector_str) {
             Ok(s) => s,
             Err(_) => continue,
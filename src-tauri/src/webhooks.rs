@@ -0,0 +1,158 @@
+//! Webhook delivery for chat lifecycle events
+//!
+//! Each configured webhook receives a JSON POST for the events it subscribes to:
+//!
+//! ```json
+//! {"event": "chat_complete", "timestamp": "2025-01-01T00:00:00Z", "data": { ... }}
+//! ```
+//!
+//! When a secret is set, the body is signed with HMAC-SHA256 and sent as
+//! `X-Chatshell-Signature: sha256=<hex>`, so receivers can verify the request.
+
+use crate::commands::AppState;
+use crate::jobs::WebhookDeliveryPayload;
+use crate::models::{JOB_PRIORITY_NORMAL, JobKind, Webhook, WebhookEvent};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+use tauri::{Listener, Manager};
+
+pub const SIGNATURE_HEADER: &str = "X-Chatshell-Signature";
+pub const EVENT_HEADER: &str = "X-Chatshell-Event";
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Forward subscribed app events to the configured webhooks
+pub fn register_listeners(app: &tauri::AppHandle) {
+    for event in WebhookEvent::ALL {
+        let app_for_listener = app.clone();
+        app.listen_any(event.app_event(), move |e| {
+            let Ok(data) = serde_json::from_str::<serde_json::Value>(e.payload()) else {
+                return;
+            };
+            // Only title changes are delivered for `conversation-updated`
+            if event == WebhookEvent::TitleUpdated && data.get("title").is_none() {
+                return;
+            }
+            let app = app_for_listener.clone();
+            tauri::async_runtime::spawn(async move {
+                dispatch(&app, event, data).await;
+            });
+        });
+    }
+}
+
+/// Deliver an event that wasn't emitted globally, such as streaming output sent over a
+/// window's channel
+pub fn forward<P: Serialize>(app: &tauri::AppHandle, event: WebhookEvent, payload: &P) {
+    let data = match serde_json::to_value(payload) {
+        Ok(data) => data,
+        Err(e) => {
+            tracing::warn!("⚠️ [webhooks] Failed to serialize {}: {}", event.id(), e);
+            return;
+        }
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        dispatch(&app, event, data).await;
+    });
+}
+
+async fn dispatch(app: &tauri::AppHandle, event: WebhookEvent, data: serde_json::Value) {
+    let state: tauri::State<'_, AppState> = app.state();
+    // Private conversations never leave the app
+    if data["conversation_id"]
+        .as_str()
+        .is_some_and(|id| state.private_conversations.contains(id))
+    {
+        return;
+    }
+    let webhooks = match state.db.list_webhooks().await {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("❌ [webhooks] Failed to load webhooks: {}", e);
+            return;
+        }
+    };
+
+    let targets: Vec<Webhook> = webhooks
+        .into_iter()
+        .filter(|w| w.is_enabled && w.events.contains(&event))
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+
+    let body = serde_json::json!({
+        "event": event.id(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "data": data,
+    })
+    .to_string();
+
+    // Delivered (and retried) by the job queue
+    for webhook in targets {
+        let payload = WebhookDeliveryPayload {
+            webhook_id: webhook.id,
+            event,
+            body: body.clone(),
+        };
+        if let Err(e) = state
+            .job_queue
+            .enqueue(JobKind::WebhookDelivery, &payload, JOB_PRIORITY_NORMAL)
+            .await
+        {
+            tracing::error!(
+                "❌ [webhooks] Failed to queue delivery to {}: {}",
+                webhook.name,
+                e
+            );
+        }
+    }
+}
+
+/// POST a payload to one webhook
+pub async fn deliver(webhook: &Webhook, event: WebhookEvent, body: &str) -> Result<(), String> {
+    let mut request = reqwest::Client::new()
+        .post(&webhook.url)
+        .timeout(DELIVERY_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event.id())
+        .body(body.to_string());
+    if let Some(secret) = webhook.secret.as_deref() {
+        request = request.header(SIGNATURE_HEADER, sign(secret, body.as_bytes()));
+    }
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status().as_u16()));
+    }
+
+    tracing::info!("🪝 [webhooks] Delivered {} to {}", event.id(), webhook.name);
+    Ok(())
+}
+
+/// `sha256=<hex HMAC-SHA256 of body>`
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_matches_known_hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -72,6 +72,7 @@ import { useSkillStore } from '@/stores/skillStore'
 import { useConversationStore } from '@/stores/conversation'
 import { getRandomPresetColor, getRandomNameAndEmoji } from '@/lib/assistant-utils'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface AssistantDialogProps {
   open: boolean
@@ -387,7 +388,7 @@ export function AssistantDialog({
       onOpenChange(false)
     } catch (err) {
       logger.error('Failed to save assistant:', err)
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setIsSaving(false)
     }
@@ -1,8 +1,8 @@
-import { useState, useMemo, useEffect } from 'react'
+import { useState, useMemo, useEffect, useCallback } from 'react'
 import { useTranslation } from 'react-i18next'
 import { Globe, ExternalLink, AlertTriangle } from 'lucide-react'
 import { openUrl } from '@tauri-apps/plugin-opener'
-import { invoke } from '@tauri-apps/api/core'
+import { invoke, convertFileSrc } from '@tauri-apps/api/core'
 import {
   Dialog,
   DialogContent,
@@ -12,36 +12,149 @@ import {
 } from '@/components/ui/dialog'
 import { Button } from '@/components/ui/button'
 import { MarkdownContent } from '@/components/markdown-content'
-import type { FetchResult } from '@/types'
-import { getDomain, getFaviconUrl } from './utils'
+import type { FetchContentMetadata, FetchContentRange, FetchResult } from '@/types'
+import { formatFileSize, getDomain, getFaviconUrl } from './utils'
 import { logger } from '@/lib/logger'
 
+// Favicons cached at fetch time are served from storage; older results use the remote URL
+function useFaviconUrl(fetchResult: FetchResult): string {
+  const [cachedUrl, setCachedUrl] = useState<string | null>(null)
+  const remoteUrl = useMemo(() => getFaviconUrl(fetchResult), [fetchResult])
+
+  useEffect(() => {
+    if (!fetchResult.favicon_path) return
+    let cancelled = false
+    invoke<string>('get_attachment_url', { storagePath: fetchResult.favicon_path })
+      .then((fullPath) => {
+        if (!cancelled) setCachedUrl(convertFileSrc(fullPath))
+      })
+      .catch((err) => logger.error('Failed to resolve cached favicon:', err))
+    return () => {
+      cancelled = true
+    }
+  }, [fetchResult.favicon_path])
+
+  if (fetchResult.favicon_path) return cachedUrl ?? ''
+  return remoteUrl
+}
+
+// Loads stored content when `enabled` turns on. Small files are read whole; large ones
+// page by page, so multi-megabyte documents don't have to be rendered at once.
+function useFetchContent(fetchResult: FetchResult, enabled: boolean) {
+  const [content, setContent] = useState<string | null>(null)
+  const [requested, setRequested] = useState(false)
+  const [loading, setLoading] = useState(false)
+  const [loadingMore, setLoadingMore] = useState(false)
+  const [nextOffset, setNextOffset] = useState(0)
+  const [totalSize, setTotalSize] = useState(0)
+  const [pageLength, setPageLength] = useState(0)
+
+  useEffect(() => {
+    if (!enabled || requested || fetchResult.status !== 'success') return
+    const storagePath = fetchResult.storage_path
+    setRequested(true)
+    setLoading(true)
+    invoke<FetchContentMetadata>('get_fetch_content_metadata', { storagePath })
+      .then(async (metadata) => {
+        setTotalSize(metadata.total_size)
+        setPageLength(metadata.max_range_length)
+        if (metadata.can_read_whole) {
+          setContent(await invoke<string>('read_fetch_content', { storagePath }))
+          setNextOffset(metadata.total_size)
+          return
+        }
+        const page = await invoke<FetchContentRange>('read_fetch_content_range', {
+          storagePath,
+          offset: 0,
+          length: metadata.max_range_length,
+        })
+        setContent(page.content)
+        setNextOffset(page.next_offset)
+      })
+      .catch((err) => logger.error('Failed to load fetch content:', err))
+      .finally(() => setLoading(false))
+  }, [enabled, requested, fetchResult])
+
+  const loadMore = useCallback(async () => {
+    setLoadingMore(true)
+    try {
+      const page = await invoke<FetchContentRange>('read_fetch_content_range', {
+        storagePath: fetchResult.storage_path,
+        offset: nextOffset,
+        length: pageLength,
+      })
+      setContent((prev) => (prev ?? '') + page.content)
+      setNextOffset(page.next_offset)
+      setTotalSize(page.total_size)
+    } catch (err) {
+      logger.error('Failed to load more fetch content:', err)
+    } finally {
+      setLoadingMore(false)
+    }
+  }, [fetchResult.storage_path, nextOffset, pageLength])
+
+  return {
+    content,
+    loading,
+    loadingMore,
+    loadedSize: nextOffset,
+    totalSize,
+    hasMore: content !== null && nextOffset < totalSize,
+    loadMore,
+  }
+}
+
+function FetchContentBody({
+  content,
+  loading,
+  loadingMore,
+  loadedSize,
+  totalSize,
+  hasMore,
+  loadMore,
+}: ReturnType<typeof useFetchContent>) {
+  const { t } = useTranslation('attachments')
+
+  return (
+    <div className="flex-1 overflow-y-auto border rounded-md p-4 min-h-[200px]">
+      {loading ? (
+        <p className="text-sm text-muted-foreground">{t('loadingContent')}</p>
+      ) : content ? (
+        <>
+          <MarkdownContent content={content} className="text-sm" />
+          {hasMore && (
+            <div className="flex items-center justify-between gap-2 mt-4 pt-3 border-t">
+              <span className="text-xs text-muted-foreground">
+                {t('contentShownOf', {
+                  shown: formatFileSize(loadedSize),
+                  total: formatFileSize(totalSize),
+                })}
+              </span>
+              <Button variant="outline" size="sm" onClick={loadMore} disabled={loadingMore}>
+                {loadingMore ? t('loadingContent') : t('loadMore')}
+              </Button>
+            </div>
+          )}
+        </>
+      ) : (
+        <p className="text-sm text-muted-foreground">{t('noContentAvailable')}</p>
+      )}
+    </div>
+  )
+}
+
 // FetchResult preview component
 export function FetchResultPreview({ fetchResult }: { fetchResult: FetchResult }) {
   const { t } = useTranslation(['common', 'attachments'])
   const [faviconError, setFaviconError] = useState(false)
   const [isDialogOpen, setIsDialogOpen] = useState(false)
-  const [content, setContent] = useState<string | null>(null)
-  const [loadingContent, setLoadingContent] = useState(false)
+  const fetchContent = useFetchContent(fetchResult, isDialogOpen)
 
-  const faviconUrl = useMemo(() => getFaviconUrl(fetchResult), [fetchResult])
+  const faviconUrl = useFaviconUrl(fetchResult)
   const domain = getDomain(fetchResult.url)
   const title = fetchResult.title || domain
   const isFailed = fetchResult.status === 'failed'
 
-  // Load content from filesystem when dialog opens
-  useEffect(() => {
-    if (isDialogOpen && !content && !loadingContent && fetchResult.status === 'success') {
-      setLoadingContent(true)
-      invoke<string>('read_fetch_content', { storagePath: fetchResult.storage_path })
-        .then(setContent)
-        .catch((err) => {
-          logger.error('Failed to load fetch content:', err)
-          setContent(null)
-        })
-        .finally(() => setLoadingContent(false))
-    }
-  }, [isDialogOpen, content, loadingContent, fetchResult])
 
   const handleOpenLink = () => {
     openUrl(fetchResult.url)
@@ -156,15 +269,7 @@ export function FetchResultPreview({ fetchResult }: { fetchResult: FetchResult }
             <p className="text-sm text-muted-foreground break-all font-mono">{fetchResult.url}</p>
           </div>
 
-          <div className="flex-1 overflow-y-auto border rounded-md p-4 min-h-[200px]">
-            {loadingContent ? (
-              <p className="text-sm text-muted-foreground">{t('attachments:loadingContent')}</p>
-            ) : content ? (
-              <MarkdownContent content={content} className="text-sm" />
-            ) : (
-              <p className="text-sm text-muted-foreground">{t('attachments:noContentAvailable')}</p>
-            )}
-          </div>
+          <FetchContentBody {...fetchContent} />
 
           <DialogFooter>
             <Button variant="outline" onClick={() => setIsDialogOpen(false)}>
@@ -186,27 +291,13 @@ export function SearchResultFetchItem({ fetchResult }: { fetchResult: FetchResul
   const { t } = useTranslation(['common', 'attachments'])
   const [faviconError, setFaviconError] = useState(false)
   const [isDialogOpen, setIsDialogOpen] = useState(false)
-  const [content, setContent] = useState<string | null>(null)
-  const [loadingContent, setLoadingContent] = useState(false)
+  const fetchContent = useFetchContent(fetchResult, isDialogOpen)
 
-  const faviconUrl = useMemo(() => getFaviconUrl(fetchResult), [fetchResult])
+  const faviconUrl = useFaviconUrl(fetchResult)
   const domain = getDomain(fetchResult.url)
   const title = fetchResult.title || domain
   const isFailed = fetchResult.status === 'failed'
 
-  // Load content from filesystem when dialog opens
-  useEffect(() => {
-    if (isDialogOpen && !content && !loadingContent && fetchResult.status === 'success') {
-      setLoadingContent(true)
-      invoke<string>('read_fetch_content', { storagePath: fetchResult.storage_path })
-        .then(setContent)
-        .catch((err) => {
-          logger.error('Failed to load fetch content:', err)
-          setContent(null)
-        })
-        .finally(() => setLoadingContent(false))
-    }
-  }, [isDialogOpen, content, loadingContent, fetchResult])
 
   const handleOpenLink = () => {
     openUrl(fetchResult.url)
@@ -275,19 +366,7 @@ export function SearchResultFetchItem({ fetchResult }: { fetchResult: FetchResul
             </div>
           )}
 
-          {!isFailed && (
-            <div className="flex-1 overflow-y-auto border rounded-md p-4 min-h-[200px]">
-              {loadingContent ? (
-                <p className="text-sm text-muted-foreground">{t('attachments:loadingContent')}</p>
-              ) : content ? (
-                <MarkdownContent content={content} className="text-sm" />
-              ) : (
-                <p className="text-sm text-muted-foreground">
-                  {t('attachments:noContentAvailable')}
-                </p>
-              )}
-            </div>
-          )}
+          {!isFailed && <FetchContentBody {...fetchContent} />}
 
           <DialogFooter>
             <Button variant="outline" onClick={() => setIsDialogOpen(false)}>
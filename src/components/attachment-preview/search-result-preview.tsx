@@ -7,11 +7,17 @@ import {
   XCircle,
   CircleQuestionMark,
   CheckCircle2,
+  RotateCw,
 } from 'lucide-react'
 import { invoke } from '@tauri-apps/api/core'
+import { toast } from 'sonner'
 import type { SearchResult, FetchResult, SearchDecision } from '@/types'
 import { SearchResultFetchItem, ProcessingUrlItem } from './fetch-result-preview'
 import { logger } from '@/lib/logger'
+import { useConversationStore } from '@/stores/conversation'
+import { useMessageStore } from '@/stores/message'
+import { Input } from '@/components/ui/input'
+import { Button } from '@/components/ui/button'
 
 // Map search engine IDs to display names
 const SEARCH_ENGINE_NAMES: Record<string, string> = {
@@ -223,6 +229,19 @@ export function SearchResultPreview({
 export function SearchDecisionPreview({ decision }: { decision: SearchDecision }) {
   const { t } = useTranslation(['attachments', 'common'])
   const [isExpanded, setIsExpanded] = useState(false)
+  const conversationId = useConversationStore((state) => state.currentConversation?.id)
+  const rerunSearch = useMessageStore((state) => state.rerunSearch)
+  const [editedQuery, setEditedQuery] = useState(decision.search_query ?? '')
+
+  const handleRerunSearch = async (query: string) => {
+    if (!conversationId || !query.trim()) return
+    try {
+      await rerunSearch(conversationId, decision.message_id, query.trim())
+    } catch (error) {
+      toast.error(t('rerunSearchFailed'))
+      logger.error('Failed to rerun search:', error)
+    }
+  }
 
   // Dynamic container styles based on state
   const containerClass = isExpanded
@@ -275,6 +294,54 @@ export function SearchDecisionPreview({ decision }: { decision: SearchDecision }
               <p className="text-xs text-foreground/70 leading-relaxed">{decision.search_query}</p>
             </div>
           )}
+
+          {decision.confidence != null && (
+            <div className="space-y-1">
+              <p className="text-xs text-muted-foreground/70 uppercase tracking-wider">
+                {t('confidence')}
+              </p>
+              <p className="text-xs text-foreground/70">{Math.round(decision.confidence * 100)}%</p>
+            </div>
+          )}
+
+          {/* Other queries the model considered; picking one re-runs the reply with it */}
+          {decision.alternative_queries.length > 0 && (
+            <div className="space-y-1">
+              <p className="text-xs text-muted-foreground/70 uppercase tracking-wider">
+                {t('alternativeQueries')}
+              </p>
+              {decision.alternative_queries.map((query) => (
+                <button
+                  key={query}
+                  onClick={() => handleRerunSearch(query)}
+                  title={t('searchWithQuery')}
+                  className="flex items-center gap-1.5 text-xs text-foreground/70 hover:text-foreground transition-colors cursor-pointer"
+                >
+                  <RotateCw className="h-3 w-3 flex-shrink-0" />
+                  <span className="text-left">{query}</span>
+                </button>
+              ))}
+            </div>
+          )}
+
+          {/* Correct the query by hand */}
+          <form
+            onSubmit={(e) => {
+              e.preventDefault()
+              handleRerunSearch(editedQuery)
+            }}
+            className="flex items-center gap-1.5"
+          >
+            <Input
+              value={editedQuery}
+              onChange={(e) => setEditedQuery(e.target.value)}
+              placeholder={t('editSearchQuery')}
+              className="h-7 text-xs"
+            />
+            <Button type="submit" size="sm" variant="outline" disabled={!editedQuery.trim()}>
+              {t('searchAgain')}
+            </Button>
+          </form>
         </div>
       )}
     </div>
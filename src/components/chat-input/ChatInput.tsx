@@ -33,10 +33,13 @@ import { useMcpStore } from '@/stores/mcpStore'
 import { useSkillStore } from '@/stores/skillStore'
 import { useModelStore } from '@/stores/modelStore'
 import { useModelCapabilities } from '@/hooks/useModelCapabilities'
-import { getContextCountOptions } from '@/types'
-import type { ModelParameterPreset, PromptMode } from '@/types'
+import { DEFAULT_CONTEXT_BUDGET_PERCENT } from '@/types'
+import type { HistoryMode, ModelParameterPreset, PromptMode } from '@/types'
 import { logger } from '@/lib/logger'
 
+// Minimum time between warm-up requests for the same model
+const WARM_UP_INTERVAL_MS = 60_000
+
 // interface ChatInputProps {}
 
 export function ChatInput(/* _props: ChatInputProps */) {
@@ -71,6 +74,21 @@ export function ChatInput(/* _props: ChatInputProps */) {
   const toolsDisabled = capabilities.supports_tool_use === false
   const visionDisabled = capabilities.supports_vision === false
 
+  // Load a local Ollama model when the user starts composing, so the first token
+  // doesn't wait for the model to load. Throttled per model.
+  const lastWarmUpRef = useRef<Record<string, number>>({})
+  const warmUpSelectedModel = () => {
+    if (!selectedModelEarly || selectedProviderEarly?.provider_type !== 'ollama') return
+    const key = selectedModelEarly.id
+    const now = Date.now()
+    if (now - (lastWarmUpRef.current[key] ?? 0) < WARM_UP_INTERVAL_MS) return
+    lastWarmUpRef.current[key] = now
+    invoke('ollama_load_model', {
+      baseUrl: selectedProviderEarly.base_url ?? null,
+      model: selectedModelEarly.model_id,
+    }).catch((error) => logger.warn('Failed to warm up Ollama model:', error))
+  }
+
   // Attachment handling hook
   const {
     attachments,
@@ -120,9 +138,7 @@ export function ChatInput(/* _props: ChatInputProps */) {
     (state) => state.setUseCustomParameters
   )
   const setSelectedPresetId = useConversationSettingsStore((state) => state.setSelectedPresetId)
-  const setContextMessageCount = useConversationSettingsStore(
-    (state) => state.setContextMessageCount
-  )
+  const setHistoryMode = useConversationSettingsStore((state) => state.setHistoryMode)
   const setSystemPrompt = useConversationSettingsStore((state) => state.setSystemPrompt)
   const setEnabledMcpServerIds = useConversationSettingsStore(
     (state) => state.setEnabledMcpServerIds
@@ -181,11 +197,13 @@ export function ChatInput(/* _props: ChatInputProps */) {
 
   const contextCountLabel = useMemo(() => {
     if (!conversationSettings) return t('settings:contextUnlimited')
-    const count = conversationSettings.contextMessageCount
-    if (count === null) return t('settings:contextUnlimited')
-    const options = getContextCountOptions(t)
-    const option = options.find((opt) => opt.value === count)
-    return option?.label || t('countMsgs', { count })
+    const { historyMode, contextBudgetPercent } = conversationSettings
+    if (historyMode === 'summarized') return t('historySummarized')
+    if (historyMode === 'none') return t('historyNone')
+    if (historyMode === 'all') return t('settings:contextUnlimited')
+    return t('settings:contextBudgetPercent', {
+      percent: contextBudgetPercent ?? DEFAULT_CONTEXT_BUDGET_PERCENT,
+    })
   }, [conversationSettings, t])
 
   // Compute system prompt label based on current settings
@@ -362,9 +380,9 @@ export function ChatInput(/* _props: ChatInputProps */) {
     }
   }
 
-  const handleSaveContextCount = (count: number | null) => {
+  const handleSaveContextCount = (mode: HistoryMode, percent: number | null) => {
     if (currentConversation) {
-      setContextMessageCount(currentConversation.id, count)
+      setHistoryMode(currentConversation.id, mode, percent)
     }
   }
 
@@ -511,6 +529,7 @@ export function ChatInput(/* _props: ChatInputProps */) {
             placeholder={t('typeMessage')}
             value={input}
             onChange={(e) => setInput(e.target.value)}
+            onFocus={warmUpSelectedModel}
             onKeyDown={handleKeyDown}
             onPaste={handlePaste}
             disabled={!selectedModel && !selectedAssistant}
@@ -612,7 +631,8 @@ export function ChatInput(/* _props: ChatInputProps */) {
       <ContextCountDialog
         isOpen={isContextCountDialogOpen}
         onOpenChange={setIsContextCountDialogOpen}
-        contextMessageCount={conversationSettings?.contextMessageCount ?? null}
+        historyMode={conversationSettings?.historyMode ?? 'all'}
+        contextBudgetPercent={conversationSettings?.contextBudgetPercent ?? null}
         onSave={handleSaveContextCount}
       />
 
@@ -11,49 +11,68 @@ import {
 import { Button } from '@/components/ui/button'
 import { Label } from '@/components/ui/label'
 import { Input } from '@/components/ui/input'
-import { getContextCountOptions } from '@/types'
+import { getContextBudgetOptions } from '@/types'
+import type { HistoryMode } from '@/types'
+import { DEFAULT_CONTEXT_BUDGET_PERCENT } from '@/types'
 import { cn } from '@/lib/utils'
 
+const isValidPercent = (value: number) => !isNaN(value) && value >= 1 && value <= 100
+
 interface ContextCountDialogProps {
   isOpen: boolean
   onOpenChange: (open: boolean) => void
-  contextMessageCount: number | null
-  onSave: (count: number | null) => void
+  historyMode: HistoryMode
+  contextBudgetPercent: number | null
+  onSave: (mode: HistoryMode, percent: number | null) => void
 }
 
 export function ContextCountDialog({
   isOpen,
   onOpenChange,
-  contextMessageCount,
+  historyMode,
+  contextBudgetPercent,
   onSave,
 }: ContextCountDialogProps) {
   const { t } = useTranslation(['chat', 'common'])
-  const contextCountOptions = getContextCountOptions(t)
-  const [selectedValue, setSelectedValue] = useState<number | null>(contextMessageCount)
+  const contextCountOptions = getContextBudgetOptions(t)
+  const [selectedMode, setSelectedMode] = useState<HistoryMode>(historyMode)
+  const [selectedValue, setSelectedValue] = useState<number | null>(contextBudgetPercent)
   const [customValue, setCustomValue] = useState<string>('')
   const [isCustom, setIsCustom] = useState(false)
 
   // Reset state when dialog opens
   useEffect(() => {
     if (isOpen) {
-      setSelectedValue(contextMessageCount)
-      const isPresetValue = contextCountOptions.some((opt) => opt.value === contextMessageCount)
-      setIsCustom(!isPresetValue && contextMessageCount !== null)
-      if (!isPresetValue && contextMessageCount !== null) {
-        setCustomValue(String(contextMessageCount))
+      const percent = contextBudgetPercent ?? DEFAULT_CONTEXT_BUDGET_PERCENT
+      const count = historyMode === 'token_budget' ? percent : null
+      setSelectedMode(historyMode)
+      setSelectedValue(count)
+      const isPresetValue = contextCountOptions.some((opt) => opt.value === count)
+      setIsCustom(!isPresetValue && count !== null)
+      if (!isPresetValue && count !== null) {
+        setCustomValue(String(count))
       } else {
         setCustomValue('')
       }
     }
-  }, [isOpen, contextMessageCount])
+  }, [isOpen, historyMode, contextBudgetPercent])
 
   const handleOptionSelect = (value: number | null) => {
+    setSelectedMode(value === null ? 'all' : 'token_budget')
     setSelectedValue(value)
     setIsCustom(false)
     setCustomValue('')
   }
 
+  const handleModeSelect = (mode: HistoryMode) => {
+    setSelectedMode(mode)
+    setSelectedValue(null)
+    setIsCustom(false)
+    setCustomValue('')
+  }
+
   const handleCustomToggle = () => {
+    setSelectedMode('token_budget')
     setIsCustom(true)
     setSelectedValue(null)
   }
@@ -61,26 +80,30 @@ export function ContextCountDialog({
   const handleApply = () => {
     if (isCustom) {
       const parsed = parseInt(customValue, 10)
-      if (!isNaN(parsed) && parsed > 0) {
-        onSave(parsed)
+      if (isValidPercent(parsed)) {
+        onSave('token_budget', parsed)
       }
     } else {
-      onSave(selectedValue)
+      onSave(selectedMode, selectedValue)
     }
     onOpenChange(false)
   }
 
+  const isCountOptionSelected = (value: number | null) =>
+    !isCustom &&
+    selectedValue === value &&
+    (value === null ? selectedMode === 'all' : selectedMode === 'token_budget')
+
   const isValidCustomValue = () => {
     if (!isCustom) return true
-    const parsed = parseInt(customValue, 10)
-    return !isNaN(parsed) && parsed > 0
+    return isValidPercent(parseInt(customValue, 10))
   }
 
   return (
     <Dialog open={isOpen} onOpenChange={onOpenChange}>
       <DialogContent className="sm:max-w-[400px]">
         <DialogHeader>
-          <DialogTitle>{t('contextMessageCount')}</DialogTitle>
+          <DialogTitle>{t('contextBudget')}</DialogTitle>
           <DialogDescription>{t('setContextMessages')}</DialogDescription>
         </DialogHeader>
 
@@ -91,9 +114,7 @@ export function ContextCountDialog({
               onClick={() => handleOptionSelect(option.value)}
               className={cn(
                 'flex items-center justify-between rounded-lg border p-3 text-left transition-colors hover:bg-accent',
-                selectedValue === option.value && !isCustom
-                  ? 'border-primary bg-accent'
-                  : 'border-border'
+                isCountOptionSelected(option.value) ? 'border-primary bg-accent' : 'border-border'
               )}
             >
               <span className="font-medium">{option.label}</span>
@@ -103,6 +124,21 @@ export function ContextCountDialog({
             </button>
           ))}
 
+          {(['summarized', 'none'] as const).map((mode) => (
+            <button
+              key={mode}
+              onClick={() => handleModeSelect(mode)}
+              className={cn(
+                'flex items-center justify-between rounded-lg border p-3 text-left transition-colors hover:bg-accent',
+                selectedMode === mode && !isCustom ? 'border-primary bg-accent' : 'border-border'
+              )}
+            >
+              <span className="font-medium">
+                {mode === 'summarized' ? t('historySummarized') : t('historyNone')}
+              </span>
+            </button>
+          ))}
+
           {/* Custom option */}
           <button
             onClick={handleCustomToggle}
@@ -117,12 +153,13 @@ export function ContextCountDialog({
           {isCustom && (
             <div className="flex items-center gap-2 px-3 py-2">
               <Label htmlFor="custom-count" className="shrink-0">
-                {t('messages')}
+                {t('budgetPercent')}
               </Label>
               <Input
                 id="custom-count"
                 type="number"
                 min={1}
+                max={100}
                 value={customValue}
                 onChange={(e) => setCustomValue(e.target.value)}
                 placeholder={t('enterNumber')}
@@ -8,6 +8,7 @@ import { usePromptStore } from '@/stores/promptStore'
 import type { Attachment } from './types'
 import type { Model } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface UseSubmitHandlerOptions {
   input: string
@@ -196,13 +197,11 @@ export function useSubmitHandler({
         settings?.useCustomParameters && settings.parameterOverrides
           ? settings.parameterOverrides
           : undefined
-      const contextMessageCount = settings?.contextMessageCount ?? undefined
 
       logger.info('Using conversation settings:', {
         useProviderDefaults: settings?.useProviderDefaults,
         useCustomParameters: settings?.useCustomParameters,
         hasParameterOverrides: !!parameterOverrides,
-        contextMessageCount,
       })
 
       const resolvedParams = {
@@ -223,7 +222,6 @@ export function useSubmitHandler({
         files: files.length > 0 ? files : undefined,
         searchEnabled: webSearchEnabled,
         parameterOverrides,
-        contextMessageCount,
         useProviderDefaults,
       }
 
@@ -256,7 +254,6 @@ export function useSubmitHandler({
           resolvedParams.files,
           resolvedParams.searchEnabled,
           resolvedParams.parameterOverrides,
-          resolvedParams.contextMessageCount,
           resolvedParams.useProviderDefaults
         )
         logger.info('Message sent successfully')
@@ -266,7 +263,7 @@ export function useSubmitHandler({
     } catch (error) {
       logger.error('Failed to send message:', error)
       toast.error(t('failedMessageToSend'), {
-        description: error instanceof Error ? error.message : String(error),
+        description: errorMessage(error),
       })
     }
   }
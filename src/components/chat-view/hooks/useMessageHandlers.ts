@@ -10,6 +10,7 @@ import { useModelStore } from '@/stores/modelStore'
 import { useConversationSettingsStore } from '@/stores/conversationSettingsStore'
 import { usePromptStore } from '@/stores/promptStore'
 import type { Conversation, MessageResources } from '@/types'
+import { errorMessage } from '@/types'
 
 interface UseMessageHandlersOptions {
   messagesEndRef: RefObject<HTMLDivElement | null>
@@ -128,7 +129,6 @@ export function useMessageHandlers({
           settings?.useCustomParameters && settings.parameterOverrides
             ? settings.parameterOverrides
             : undefined
-        const contextMessageCount = settings?.contextMessageCount ?? undefined
 
         await sendMessage(
           content,
@@ -148,13 +148,12 @@ export function useMessageHandlers({
           files.length > 0 ? files : undefined,
           false,
           parameterOverrides,
-          contextMessageCount,
           useProviderDefaults
         )
       } catch (error) {
         logger.error('Failed to revert message:', error)
         toast.error(t('failedToRevertMessage'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -193,7 +192,7 @@ export function useMessageHandlers({
       } catch (error) {
         logger.error('Failed to fork conversation:', error)
         toast.error(t('failedToForkConversation'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -13,6 +13,7 @@ import { ProviderSettingsDialog } from '@/components/provider-settings-dialog'
 import { generateRandomAvatarData } from '@/lib/assistant-utils'
 import type { CreateAssistantRequest } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 export function OnboardingDialog() {
   const { t } = useTranslation('onboarding')
@@ -158,7 +159,7 @@ export function OnboardingDialog() {
     } catch (err) {
       logger.error('Failed to create assistants:', err)
       toast.error(t('failedToCreateAssistants'), {
-        description: String(err),
+        description: errorMessage(err),
       })
     } finally {
       setIsCreatingAssistant(false)
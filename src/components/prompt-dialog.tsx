@@ -26,6 +26,7 @@ import { Loader2, ChevronsUpDown, Check } from 'lucide-react'
 import type { Prompt, CreatePromptRequest } from '@/types'
 import { usePromptStore } from '@/stores/promptStore'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface PromptDialogProps {
   open: boolean
@@ -130,7 +131,7 @@ export function PromptDialog({ open, onOpenChange, prompt, mode = 'create' }: Pr
       }
     } catch (err) {
       logger.error('Failed to save prompt:', err)
-      setError(String(err))
+      setError(errorMessage(err))
     } finally {
       setIsSaving(false)
     }
@@ -11,6 +11,7 @@ import { RadioGroup, RadioGroupItem } from '@/components/ui/radio-group'
 import type { LLMProvider, ModelItem } from './types'
 import { isCustomProvider } from './types'
 import { DEFAULT_PROVIDER_MODELS } from './constants'
+import { errorMessage } from '@/types'
 
 type CheckStatus = 'idle' | 'checking' | 'success' | 'failed'
 
@@ -138,7 +139,7 @@ export function ProviderForm({
       }
     } catch (e) {
       setCheckStatus('failed')
-      setCheckError(e instanceof Error ? e.message : String(e))
+      setCheckError(errorMessage(e))
     }
   }, [selectedProvider, isCustom, compatibilityType, apiKey, apiBaseUrl, apiStyle, models])
 
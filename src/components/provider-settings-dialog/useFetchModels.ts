@@ -3,6 +3,7 @@ import { invoke } from '@tauri-apps/api/core'
 import type { LLMProvider, ModelInfo } from './types'
 import { logger } from '@/lib/logger'
 import i18n from '@/lib/i18n'
+import { errorMessage } from '@/types'
 
 export interface UseFetchModelsReturn {
   availableModels: ModelInfo[]
@@ -125,7 +126,7 @@ export function useFetchModels({
       setAvailableModels(fetchedModels)
     } catch (error) {
       logger.error('Error fetching models:', error)
-      setFetchError(error instanceof Error ? error.message : String(error))
+      setFetchError(errorMessage(error))
     } finally {
       setIsLoading(false)
     }
@@ -6,6 +6,7 @@ import type { CreateProviderRequest, CreateModelRequest, Provider } from '@/type
 import type { LLMProvider, ModelItem } from './types'
 import { isCustomProvider } from './types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 export interface UseProviderSaveReturn {
   isSaving: boolean
@@ -154,7 +155,7 @@ export function useProviderSave({
     } catch (error) {
       logger.error('Failed to save provider:', error)
       toast.error(t('failedToSaveProvider'), {
-        description: error instanceof Error ? error.message : String(error),
+        description: errorMessage(error),
       })
     } finally {
       setIsSaving(false)
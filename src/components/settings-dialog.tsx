@@ -6,6 +6,7 @@ import {
   Bot,
   Check,
   ChevronDown,
+  Download,
   Eye,
   EyeOff,
   FileDown,
@@ -27,6 +28,7 @@ import {
 } from 'lucide-react'
 
 import { invoke } from '@tauri-apps/api/core'
+import { save } from '@tauri-apps/plugin-dialog'
 import {
   Breadcrumb,
   BreadcrumbItem,
@@ -63,6 +65,7 @@ import type {
   SearchProviderId,
   WebFetchMode,
   WebFetchLocalMethod,
+  TitleGenerationMode,
   LogLevel,
   Tool,
   Skill,
@@ -79,6 +82,7 @@ import {
   SKILL_SOURCE_ORDER,
 } from '@/types'
 import { LLMProviderSettings } from '@/components/settings-dialog/llm-provider-settings'
+import { UpdateSettings } from '@/components/settings-dialog/update-settings'
 import { invalidateCapabilitiesCache } from '@/hooks/useModelCapabilities'
 import { logger } from '@/lib/logger'
 import { changeLanguage, supportedLanguages, getCurrentLanguage } from '@/lib/i18n'
@@ -97,6 +101,7 @@ const data = {
     { name: 'webFetch', icon: FileDown },
     { name: 'webSearch', icon: Search },
     { name: 'language', icon: Globe },
+    { name: 'updates', icon: Download },
     { name: 'advanced', icon: Settings },
     // { name: 'Navigation', icon: Menu },
     // { name: 'Home', icon: Home },
@@ -112,6 +117,14 @@ const data = {
   ],
 }
 
+const TITLE_GENERATION_MODES: TitleGenerationMode[] = ['auto', 'local_only', 'manual']
+
+const titleGenerationModeLabels: Record<TitleGenerationMode, string> = {
+  auto: 'titleGenerationAuto',
+  local_only: 'titleGenerationLocalOnly',
+  manual: 'titleGenerationManual',
+}
+
 interface SettingsDialogProps {
   open: boolean
   onOpenChange: (open: boolean) => void
@@ -122,6 +135,7 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
   const { t: tc } = useTranslation('common')
   const [activeSection, setActiveSection] = React.useState('llmProvider')
   const [summaryModelId, setSummaryModelId] = React.useState('')
+  const [titleGenerationMode, setTitleGenerationMode] = React.useState<TitleGenerationMode>('auto')
   const [searchProviderId, setSearchProviderId] = React.useState<SearchProviderId>('duckduckgo')
 
   // Web Fetch state
@@ -146,6 +160,7 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
   const [expandedToolsId, setExpandedToolsId] = React.useState<string | null>(null)
   const [oauthAuthorizingId, setOauthAuthorizingId] = React.useState<string | null>(null)
   const [capabilitiesRefreshing, setCapabilitiesRefreshing] = React.useState(false)
+  const [logsExporting, setLogsExporting] = React.useState(false)
 
   const saveSetting = useSettingsStore((state) => state.saveSetting)
   const getSetting = useSettingsStore((state) => state.getSetting)
@@ -156,6 +171,10 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
   const loadModels = useModelStore((state) => state.loadModels)
   const getModelById = useModelStore((state) => state.getModelById)
 
+  // Conversation title store methods
+  const getTitleGenerationMode = useSettingsStore((state) => state.getTitleGenerationMode)
+  const saveTitleGenerationMode = useSettingsStore((state) => state.setTitleGenerationMode)
+
   // Web Fetch store methods
   const getWebFetchMode = useSettingsStore((state) => state.getWebFetchMode)
   const saveWebFetchMode = useSettingsStore((state) => state.setWebFetchMode)
@@ -207,6 +226,9 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
         const summaryModelValue = await getSetting('conversation_summary_model_id')
         if (summaryModelValue) setSummaryModelId(summaryModelValue)
 
+        const titleMode = await getTitleGenerationMode()
+        setTitleGenerationMode(titleMode)
+
         const searchProviderValue = await getSetting('search_provider')
         if (searchProviderValue) {
           setSearchProviderId(searchProviderValue as SearchProviderId)
@@ -238,6 +260,7 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
     ensureMcpLoaded,
     scanSkills,
     getSetting,
+    getTitleGenerationMode,
     getWebFetchMode,
     getWebFetchLocalMethod,
     getJinaApiKey,
@@ -264,6 +287,15 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
   }
 
   // Web Fetch handlers
+  const handleSaveTitleGenerationMode = async (mode: TitleGenerationMode) => {
+    setTitleGenerationMode(mode)
+    try {
+      await saveTitleGenerationMode(mode)
+    } catch (error) {
+      logger.error('Failed to save title generation mode:', error)
+    }
+  }
+
   const handleSaveWebFetchMode = async (mode: WebFetchMode) => {
     setWebFetchMode(mode)
     try {
@@ -908,6 +940,32 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
             </DropdownMenu>
             <p className="text-xs text-muted-foreground max-w-md">{t('chooseModelForTitles')}</p>
           </div>
+          <div className="grid gap-2">
+            <Label>{t('titleGeneration')}</Label>
+            <DropdownMenu>
+              <DropdownMenuTrigger asChild>
+                <Button variant="outline" className="w-full max-w-md justify-between">
+                  <span>{t(titleGenerationModeLabels[titleGenerationMode])}</span>
+                  <ChevronDown className="ml-2 h-4 w-4 shrink-0 opacity-50" />
+                </Button>
+              </DropdownMenuTrigger>
+              <DropdownMenuContent className="w-[400px]">
+                {TITLE_GENERATION_MODES.map((mode) => (
+                  <DropdownMenuItem key={mode} onClick={() => handleSaveTitleGenerationMode(mode)}>
+                    <div className="flex items-center gap-2">
+                      {titleGenerationMode === mode && <Check className="h-4 w-4 text-primary" />}
+                      <span className={titleGenerationMode === mode ? 'font-medium' : ''}>
+                        {t(titleGenerationModeLabels[mode])}
+                      </span>
+                    </div>
+                  </DropdownMenuItem>
+                ))}
+              </DropdownMenuContent>
+            </DropdownMenu>
+            <p className="text-xs text-muted-foreground max-w-md">
+              {t('titleGenerationDescription')}
+            </p>
+          </div>
         </div>
       )
     }
@@ -1141,6 +1199,10 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
       )
     }
 
+    if (activeSection === 'updates') {
+      return <UpdateSettings open={open} />
+    }
+
     if (activeSection === 'advanced') {
       const logLevels: LogLevel[] = ['trace', 'debug', 'info', 'warn', 'error']
 
@@ -1254,6 +1316,41 @@ export function SettingsDialog({ open, onOpenChange }: SettingsDialogProps) {
 
           <div className="grid gap-2">
             <p className="text-sm text-muted-foreground max-w-md">{t('logFilesLocation')}</p>
+            <div className="flex items-center gap-2">
+              <Button
+                variant="outline"
+                size="sm"
+                disabled={logsExporting}
+                onClick={async () => {
+                  const date = new Date().toISOString().slice(0, 10)
+                  const destinationPath = await save({
+                    defaultPath: `chatshell-logs-${date}.zip`,
+                    filters: [{ name: 'Zip', extensions: ['zip'] }],
+                  })
+                  if (!destinationPath) return
+                  setLogsExporting(true)
+                  try {
+                    await invoke<string>('export_logs', { destinationPath })
+                    const { toast } = await import('sonner')
+                    toast.success(t('exportLogsSuccess'))
+                  } catch (error) {
+                    logger.error('Failed to export logs:', error)
+                    const { toast } = await import('sonner')
+                    toast.error(t('exportLogsError'))
+                  } finally {
+                    setLogsExporting(false)
+                  }
+                }}
+              >
+                {logsExporting ? (
+                  <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                ) : (
+                  <FileDown className="mr-2 h-4 w-4" />
+                )}
+                {t('exportLogs')}
+              </Button>
+            </div>
+            <p className="text-xs text-muted-foreground max-w-md">{t('exportLogsDescription')}</p>
           </div>
         </div>
       )
@@ -1 +1,2 @@
 export { LLMProviderSettings } from './llm-provider-settings'
+export { UpdateSettings } from './update-settings'
@@ -0,0 +1,193 @@
+'use client'
+
+import * as React from 'react'
+import { useTranslation } from 'react-i18next'
+import { Check, ChevronDown, Download, Loader2, RefreshCw, RotateCw } from 'lucide-react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu'
+import { Label } from '@/components/ui/label'
+import { useSettingsStore } from '@/stores/settingsStore'
+import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
+import type { UpdateChannel, UpdateDownloadProgressEvent, UpdateInfo } from '@/types'
+
+const UPDATE_CHANNEL_KEY = 'update_channel'
+const CHANNELS: UpdateChannel[] = ['stable', 'beta']
+
+type UpdateStage = 'idle' | 'checking' | 'downloading' | 'downloaded' | 'installing'
+
+interface UpdateSettingsProps {
+  open: boolean
+}
+
+export function UpdateSettings({ open }: UpdateSettingsProps) {
+  const { t } = useTranslation('settings')
+  const getSetting = useSettingsStore((state) => state.getSetting)
+  const saveSetting = useSettingsStore((state) => state.saveSetting)
+
+  const [channel, setChannel] = React.useState<UpdateChannel>('stable')
+  const [stage, setStage] = React.useState<UpdateStage>('idle')
+  const [update, setUpdate] = React.useState<UpdateInfo | null>(null)
+  const [upToDate, setUpToDate] = React.useState(false)
+  const [progress, setProgress] = React.useState<UpdateDownloadProgressEvent | null>(null)
+
+  React.useEffect(() => {
+    if (!open) return
+    getSetting(UPDATE_CHANNEL_KEY).then((value) => setChannel(value === 'beta' ? 'beta' : 'stable'))
+  }, [open, getSetting])
+
+  React.useEffect(() => {
+    const unlisten = listen<UpdateDownloadProgressEvent>('update-download-progress', (event) => {
+      setProgress(event.payload)
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  const handleChannelChange = async (value: UpdateChannel) => {
+    setChannel(value)
+    setUpdate(null)
+    setUpToDate(false)
+    setStage('idle')
+    try {
+      await saveSetting(UPDATE_CHANNEL_KEY, value)
+    } catch (error) {
+      logger.error('Failed to save update channel:', error)
+    }
+  }
+
+  const handleCheck = async () => {
+    setStage('checking')
+    setUpToDate(false)
+    setProgress(null)
+    try {
+      const info = await invoke<UpdateInfo | null>('check_for_update')
+      setUpdate(info)
+      setUpToDate(!info)
+    } catch (error) {
+      logger.error('Failed to check for updates:', error)
+      toast.error(t('updateCheckError', { error: errorMessage(error) }))
+    } finally {
+      setStage('idle')
+    }
+  }
+
+  const handleDownload = async () => {
+    setStage('downloading')
+    try {
+      await invoke('download_update')
+      setStage('downloaded')
+    } catch (error) {
+      logger.error('Failed to download update:', error)
+      toast.error(t('updateDownloadError', { error: errorMessage(error) }))
+      setStage('idle')
+    }
+  }
+
+  const handleInstall = async () => {
+    setStage('installing')
+    try {
+      await invoke('install_and_restart')
+    } catch (error) {
+      logger.error('Failed to install update:', error)
+      toast.error(t('updateInstallError', { error: errorMessage(error) }))
+      setStage('downloaded')
+    }
+  }
+
+  return (
+    <div className="grid gap-6">
+      {/* Release channel */}
+      <div className="grid gap-2">
+        <Label>{t('updateChannel')}</Label>
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button variant="outline" className="w-full max-w-md justify-between">
+              <span>{t(`updateChannels.${channel}`)}</span>
+              <ChevronDown className="ml-2 h-4 w-4 shrink-0 opacity-50" />
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent className="w-[400px]">
+            {CHANNELS.map((value) => (
+              <DropdownMenuItem key={value} onClick={() => handleChannelChange(value)}>
+                <div className="flex items-center gap-2">
+                  {value === channel && <Check className="h-4 w-4 text-primary" />}
+                  <div>
+                    <span className={value === channel ? 'font-medium' : ''}>
+                      {t(`updateChannels.${value}`)}
+                    </span>
+                    <p className="text-xs text-muted-foreground">
+                      {t(`updateChannelDescriptions.${value}`)}
+                    </p>
+                  </div>
+                </div>
+              </DropdownMenuItem>
+            ))}
+          </DropdownMenuContent>
+        </DropdownMenu>
+      </div>
+
+      {/* Check, download, install */}
+      <div className="grid gap-2">
+        <div className="flex items-center gap-2">
+          <Button variant="outline" size="sm" disabled={stage !== 'idle'} onClick={handleCheck}>
+            <RefreshCw className={`mr-2 h-4 w-4 ${stage === 'checking' ? 'animate-spin' : ''}`} />
+            {t('checkForUpdates')}
+          </Button>
+          {update && (stage === 'idle' || stage === 'downloading') && (
+            <Button size="sm" disabled={stage === 'downloading'} onClick={handleDownload}>
+              {stage === 'downloading' ? (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              ) : (
+                <Download className="mr-2 h-4 w-4" />
+              )}
+              {t('downloadUpdate')}
+            </Button>
+          )}
+          {update && (stage === 'downloaded' || stage === 'installing') && (
+            <Button size="sm" disabled={stage === 'installing'} onClick={handleInstall}>
+              {stage === 'installing' ? (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              ) : (
+                <RotateCw className="mr-2 h-4 w-4" />
+              )}
+              {t('installAndRestart')}
+            </Button>
+          )}
+        </div>
+        {upToDate && <p className="text-sm text-muted-foreground">{t('upToDate')}</p>}
+        {update && (
+          <div className="grid gap-1 max-w-md">
+            <p className="text-sm">
+              {t('updateAvailable', {
+                version: update.version,
+                current: update.current_version,
+              })}
+            </p>
+            {stage === 'downloading' && progress && (
+              <p className="text-xs text-muted-foreground">
+                {progress.percent !== undefined && progress.percent !== null
+                  ? t('updateDownloadProgress', { percent: progress.percent })
+                  : t('updateDownloadedBytes', {
+                      size: (progress.downloaded / (1024 * 1024)).toFixed(1),
+                    })}
+              </p>
+            )}
+            {update.notes && (
+              <p className="text-xs text-muted-foreground whitespace-pre-wrap">{update.notes}</p>
+            )}
+          </div>
+        )}
+      </div>
+    </div>
+  )
+}
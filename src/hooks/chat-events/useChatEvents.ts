@@ -11,15 +11,15 @@ import type {
   AttachmentProcessingErrorEvent,
   AttachmentUpdateEvent,
   SearchDecisionCompleteEvent,
+  ReasoningStartedEvent,
+  ToolCallStartedEvent,
+  ToolCallCompletedEvent,
 } from '@/types'
 import type {
   ConversationUpdatedEvent,
   GenerationStoppedEvent,
   McpAuthRequiredEvent,
-  ReasoningStartedEvent,
   SearchDecisionStartedEvent,
-  ToolCallStartedEvent,
-  ToolCallCompletedEvent,
 } from './types'
 import { logger } from '@/lib/logger'
 import {
@@ -67,7 +67,8 @@ export function useChatEvents(conversationId: string | null) {
 
     logger.info('[useChatEvents] Setting up event listeners for conversation:', conversationId)
 
-    // Listen for streaming chunks
+    // Listen for streaming chunks. Generations started from this window stream over the
+    // channel passed to send_message; these cover the rest (Quick Ask, IPC clients).
     const unlistenStream = listen<ChatStreamEvent>('chat-stream', (event) => {
       logger.info('[useChatEvents] Received chat-stream event', event.payload)
       logger.info('[useChatEvents] Event conversation_id', {
@@ -1,4 +1,4 @@
-import { useEffect, useState } from 'react'
+import { useCallback, useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { useConversationStore } from '@/stores/conversation'
 import { useMessageStore } from '@/stores/message'
@@ -10,11 +10,14 @@ import { usePromptStore } from '@/stores/promptStore'
 import { useMcpStore } from '@/stores/mcpStore'
 import { useOnboardingStore } from '@/stores/onboardingStore'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
+import type { CrashReport } from '@/types'
 
 export function useAppInit() {
   const [isInitialized, setIsInitialized] = useState(false)
   const [error, setError] = useState<string | null>(null)
   const [keychainAvailable, setKeychainAvailable] = useState(true)
+  const [crashReport, setCrashReport] = useState<CrashReport | null>(null)
 
   // Use selector only for reactive state (conversations)
   const conversations = useConversationStore((state) => state.conversations)
@@ -72,6 +75,17 @@ export function useAppInit() {
           logger.warn('Failed to check keychain availability:', err)
         }
 
+        // Offer the report if the previous session crashed
+        try {
+          const report = await invoke<CrashReport | null>('get_last_crash_report')
+          if (report) {
+            logger.warn('Previous session crashed:', report.message)
+            setCrashReport(report)
+          }
+        } catch (err) {
+          logger.warn('Failed to check for crash reports:', err)
+        }
+
         // Check if onboarding is needed
         const onboardingComplete = await settingsStore.getSetting('onboarding_complete')
         const hasAssistants = assistantStore.assistants.length > 0
@@ -85,7 +99,7 @@ export function useAppInit() {
         logger.info('App initialization complete')
       } catch (err) {
         logger.error('Failed to initialize app:', err)
-        setError(String(err))
+        setError(errorMessage(err))
       } finally {
         setIsInitialized(true)
       }
@@ -165,5 +179,15 @@ export function useAppInit() {
     }
   }, [conversations])
 
-  return { isInitialized, error, keychainAvailable }
+  const dismissCrashReport = useCallback(async () => {
+    if (!crashReport) return
+    setCrashReport(null)
+    try {
+      await invoke('dismiss_crash_report', { id: crashReport.id })
+    } catch (err) {
+      logger.warn('Failed to dismiss crash report:', err)
+    }
+  }, [crashReport])
+
+  return { isInitialized, error, keychainAvailable, crashReport, dismissCrashReport }
 }
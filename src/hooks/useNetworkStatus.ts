@@ -0,0 +1,40 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { logger } from '@/lib/logger'
+import type { ConnectivityChangedEvent, OutboxEntry, OutboxUpdatedEvent } from '@/types'
+
+/**
+ * Tracks backend connectivity and messages waiting in the outbox
+ */
+export function useNetworkStatus() {
+  const [online, setOnline] = useState(true)
+  const [outbox, setOutbox] = useState<OutboxEntry[]>([])
+
+  useEffect(() => {
+    invoke<boolean>('get_network_status')
+      .then(setOnline)
+      .catch((err) => logger.warn('Failed to get network status:', err))
+    invoke<OutboxEntry[]>('list_outbox')
+      .then(setOutbox)
+      .catch((err) => logger.warn('Failed to list outbox:', err))
+
+    const unlistenConnectivity = listen<ConnectivityChangedEvent>(
+      'connectivity-changed',
+      (event) => {
+        logger.info('[useNetworkStatus] Connectivity changed:', event.payload.online)
+        setOnline(event.payload.online)
+      }
+    )
+    const unlistenOutbox = listen<OutboxUpdatedEvent>('outbox-updated', (event) => {
+      setOutbox(event.payload.entries)
+    })
+
+    return () => {
+      unlistenConnectivity.then((fn) => fn())
+      unlistenOutbox.then((fn) => fn())
+    }
+  }, [])
+
+  return { online, outbox }
+}
@@ -12,6 +12,7 @@ import type { Assistant as AssistantListItem } from '@/components/assistant-list
 import type { Prompt as PromptListItem } from '@/components/prompt-list'
 import type { Message } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 export function useSidebarHandlers() {
   const { t } = useTranslation('messages')
@@ -72,7 +73,7 @@ export function useSidebarHandlers() {
       } catch (error) {
         logger.error('Failed to handle model click:', error)
         toast.error(t('failedToSelectModel'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -120,7 +121,7 @@ export function useSidebarHandlers() {
       } catch (error) {
         logger.error('Failed to handle assistant click:', error)
         toast.error(t('failedToSelectAssistant'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -182,7 +183,7 @@ export function useSidebarHandlers() {
       } catch (error) {
         logger.error('Failed to delete model:', error)
         toast.error(t('failedToDeleteModel'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -202,7 +203,7 @@ export function useSidebarHandlers() {
       } catch (error) {
         logger.error('Failed to delete assistant:', error)
         toast.error(t('failedToDeleteAssistant'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -222,7 +223,7 @@ export function useSidebarHandlers() {
       } catch (error) {
         logger.error('Failed to delete prompt:', error)
         toast.error(t('failedToDeletePrompt'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -268,7 +269,7 @@ export function useSidebarHandlers() {
     } catch (error) {
       logger.error('Failed to create new conversation:', error)
       toast.error(t('failedToCreateConversation'), {
-        description: error instanceof Error ? error.message : String(error),
+        description: errorMessage(error),
       })
     }
   }, [conversations, createConversation, selectConversation])
@@ -283,7 +284,7 @@ export function useSidebarHandlers() {
       } catch (error) {
         logger.error('Failed to generate title:', error)
         toast.error(t('failedToGenerateTitle'), {
-          description: error instanceof Error ? error.message : String(error),
+          description: errorMessage(error),
         })
       }
     },
@@ -1,6 +1,8 @@
 import { useEffect, useState } from 'react'
 import { useTranslation } from 'react-i18next'
-import { AlertTriangle, X } from 'lucide-react'
+import { AlertTriangle, WifiOff, X } from 'lucide-react'
+import { save } from '@tauri-apps/plugin-dialog'
+import { writeTextFile } from '@tauri-apps/plugin-fs'
 import { AppSidebar } from '@/components/app-sidebar'
 import { ChatView } from '@/components/chat-view'
 import { SearchDialog } from '@/components/search/search-dialog'
@@ -18,13 +20,38 @@ import { SidebarInset, SidebarProvider, SidebarTrigger } from '@/components/ui/s
 import { Toaster } from '@/components/ui/sonner'
 import { useConversationStore } from '@/stores/conversation'
 import { useAppInit } from '@/hooks/useAppInit'
+import { useNetworkStatus } from '@/hooks/useNetworkStatus'
 import { OnboardingDialog } from '@/components/onboarding-dialog'
+import { logger } from '@/lib/logger'
 
 export function ChatPage() {
   const { t } = useTranslation()
   // Initialize app (load agents, conversations, settings)
-  const { isInitialized, error: initError, keychainAvailable } = useAppInit()
+  const {
+    isInitialized,
+    error: initError,
+    keychainAvailable,
+    crashReport,
+    dismissCrashReport,
+  } = useAppInit()
   const [showKeychainWarning, setShowKeychainWarning] = useState(true)
+  const { online, outbox } = useNetworkStatus()
+
+  const handleSaveCrashReport = async () => {
+    if (!crashReport) return
+    try {
+      const filePath = await save({
+        defaultPath: `chatshell-crash-${crashReport.id}.json`,
+        filters: [{ name: 'JSON', extensions: ['json'] }],
+      })
+      if (filePath) {
+        await writeTextFile(filePath, JSON.stringify(crashReport, null, 2))
+        await dismissCrashReport()
+      }
+    } catch (err) {
+      logger.error('Failed to save crash report:', err)
+    }
+  }
 
   // Prevent default browser drag-drop behavior (which opens files)
   // This allows only the chat-input component to handle file drops
@@ -124,6 +151,35 @@ export function ChatPage() {
               </button>
             </div>
           )}
+          {!online && (
+            <div className="flex items-center gap-2 bg-muted border-b px-4 py-2 text-sm text-muted-foreground">
+              <WifiOff className="h-4 w-4 shrink-0" />
+              <span>{t('common:offlineOutbox', { count: outbox.length })}</span>
+            </div>
+          )}
+          {crashReport && (
+            <div className="flex items-center justify-between gap-2 bg-red-500/10 border-b border-red-500/20 px-4 py-2 text-sm text-red-600 dark:text-red-400">
+              <div className="flex items-center gap-2">
+                <AlertTriangle className="h-4 w-4 shrink-0" />
+                <span>{t('common:crashReportAvailable')}</span>
+              </div>
+              <div className="flex items-center gap-1">
+                <button
+                  onClick={handleSaveCrashReport}
+                  className="px-2 py-1 hover:bg-red-500/20 rounded transition-colors"
+                >
+                  {t('common:saveCrashReport')}
+                </button>
+                <button
+                  onClick={dismissCrashReport}
+                  className="p-1 hover:bg-red-500/20 rounded transition-colors"
+                  aria-label="Dismiss crash report"
+                >
+                  <X className="h-4 w-4" />
+                </button>
+              </div>
+            </div>
+          )}
           <ChatView />
         </SidebarInset>
       </SidebarProvider>
@@ -3,6 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import type { Assistant, CreateAssistantRequest } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface AssistantStore {
   assistants: Assistant[]
@@ -41,7 +42,7 @@ export const useAssistantStore = create<AssistantStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         logger.error('Failed to load assistants:', error)
@@ -63,7 +64,7 @@ export const useAssistantStore = create<AssistantStore>()(
         return assistant
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -90,7 +91,7 @@ export const useAssistantStore = create<AssistantStore>()(
         return assistant
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -113,7 +114,7 @@ export const useAssistantStore = create<AssistantStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -4,11 +4,13 @@ import type {
   CreateConversationRequest,
   ConversationParticipant,
   CreateConversationParticipantRequest,
+  UpdateConversationParticipantRequest,
   Assistant,
   Model,
 } from '@/types'
 import type { ImmerSet, StoreGet, ConversationStoreActions } from './types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreActions => ({
   loadConversations: async () => {
@@ -25,7 +27,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
       })
     } catch (error) {
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
         draft.isLoading = false
       })
       logger.error('Failed to load conversations:', error)
@@ -50,7 +52,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
       return conversation
     } catch (error) {
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
         draft.isLoading = false
       })
       throw error
@@ -77,7 +79,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
       return conversation
     } catch (error) {
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
         draft.isLoading = false
       })
       throw error
@@ -109,7 +111,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
       })
     } catch (error) {
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
         draft.isLoading = false
       })
       throw error
@@ -225,7 +227,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
     } catch (error) {
       logger.error('Failed to select conversation:', error)
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
       })
     }
   },
@@ -251,7 +253,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
     } catch (error) {
       logger.error('Failed to load participants:', error)
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
       })
     }
   },
@@ -280,7 +282,52 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
     } catch (error) {
       logger.error('Failed to add participant:', error)
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
+      })
+      throw error
+    }
+  },
+
+  updateParticipant: async (participantId: string, req: UpdateConversationParticipantRequest) => {
+    try {
+      const participant = await invoke<ConversationParticipant>('update_conversation_participant', {
+        id: participantId,
+        req,
+      })
+      set((draft) => {
+        const index = draft.currentParticipants.findIndex(
+          (p: ConversationParticipant) => p.id === participantId
+        )
+        if (index !== -1) {
+          draft.currentParticipants[index] = participant
+        }
+      })
+    } catch (error) {
+      logger.error('Failed to update participant:', error)
+      set((draft) => {
+        draft.error = errorMessage(error)
+      })
+      throw error
+    }
+  },
+
+  deactivateParticipant: async (participantId: string) => {
+    try {
+      const participant = await invoke<ConversationParticipant>('deactivate_participant', {
+        id: participantId,
+      })
+      set((draft) => {
+        const index = draft.currentParticipants.findIndex(
+          (p: ConversationParticipant) => p.id === participantId
+        )
+        if (index !== -1) {
+          draft.currentParticipants[index] = participant
+        }
+      })
+    } catch (error) {
+      logger.error('Failed to deactivate participant:', error)
+      set((draft) => {
+        draft.error = errorMessage(error)
       })
       throw error
     }
@@ -297,7 +344,7 @@ export const createActions = (set: ImmerSet, get: StoreGet): ConversationStoreAc
     } catch (error) {
       logger.error('Failed to remove participant:', error)
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
       })
       throw error
     }
@@ -1,4 +1,10 @@
-import type { Conversation, ConversationParticipant, Model, Assistant } from '@/types'
+import type {
+  Conversation,
+  ConversationParticipant,
+  UpdateConversationParticipantRequest,
+  Model,
+  Assistant,
+} from '@/types'
 import type { Draft } from 'immer'
 
 // Conversation store state (without actions)
@@ -44,6 +50,12 @@ export interface ConversationStoreActions {
     participantId?: string,
     displayName?: string
   ) => Promise<void>
+  updateParticipant: (
+    participantId: string,
+    req: UpdateConversationParticipantRequest
+  ) => Promise<void>
+  // Marks the participant as left so it isn't re-added on the next message
+  deactivateParticipant: (participantId: string) => Promise<void>
   removeParticipant: (participantId: string) => Promise<void>
 
   // Selection actions
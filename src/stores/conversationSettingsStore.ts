@@ -5,10 +5,16 @@ import type {
   ConversationSettings,
   ConversationSettingsResponse,
   PromptMode,
+  HistoryMode,
   ModelParameterOverrides,
   UpdateConversationSettingsRequest,
 } from '@/types'
-import { fromBackendSettings, toBackendRequest, createDefaultConversationSettings } from '@/types'
+import {
+  fromBackendSettings,
+  toBackendRequest,
+  createDefaultConversationSettings,
+  errorMessage,
+} from '@/types'
 import { useMcpStore } from './mcpStore'
 import { useSkillStore } from './skillStore'
 import { logger } from '@/lib/logger'
@@ -50,8 +56,12 @@ interface ConversationSettingsActions {
   // Set selected preset ID
   setSelectedPresetId: (conversationId: string, presetId: string | null) => Promise<void>
 
-  // Set context message count
-  setContextMessageCount: (conversationId: string, count: number | null) => Promise<void>
+  // Set history strategy (percent applies to 'token_budget')
+  setHistoryMode: (
+    conversationId: string,
+    mode: HistoryMode,
+    percent: number | null
+  ) => Promise<void>
 
   // System prompt settings
   setSystemPromptMode: (conversationId: string, mode: PromptMode) => Promise<void>
@@ -175,7 +185,7 @@ export const useConversationSettingsStore = create<ConversationSettingsStore>()(
         const defaultSettings = createDefaultSettings(conversationId)
         set((draft) => {
           draft.settings[conversationId] = defaultSettings
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.loading[conversationId] = false
         })
         return defaultSettings
@@ -270,16 +280,17 @@ export const useConversationSettingsStore = create<ConversationSettingsStore>()(
       }
     },
 
-    setContextMessageCount: async (conversationId: string, count: number | null) => {
+    setHistoryMode: async (conversationId: string, mode: HistoryMode, percent: number | null) => {
       try {
         const response = await updateSettingsInBackend(conversationId, {
-          contextMessageCount: count,
+          historyMode: mode,
+          contextBudgetPercent: percent,
         })
         set((draft) => {
           draft.settings[conversationId] = fromBackendSettings(response)
         })
       } catch (error) {
-        logger.error('[conversationSettingsStore] Failed to update contextMessageCount:', error)
+        logger.error('[conversationSettingsStore] Failed to update historyMode:', error)
       }
     },
 
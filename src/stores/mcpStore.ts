@@ -3,7 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import { openUrl } from '@tauri-apps/plugin-opener'
 import type { Tool, McpServerConfig, ProbeResult } from '@/types'
-import { isMcpTool, getTransportType } from '@/types'
+import { isMcpTool, getTransportType, errorMessage } from '@/types'
 import { logger } from '@/lib/logger'
 
 export interface OAuthStatusResult {
@@ -111,7 +111,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] Failed to load MCP servers:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -150,7 +150,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] Failed to create MCP server:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -190,7 +190,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] Failed to update MCP server:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -212,7 +212,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] Failed to delete MCP server:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -241,7 +241,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] Failed to toggle MCP server:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -279,7 +279,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] HTTP test connection failed:', error)
         set((draft) => {
-          draft.testError = String(error)
+          draft.testError = errorMessage(error)
           draft.testingEndpoint = null
         })
         throw error
@@ -303,7 +303,7 @@ export const useMcpStore = create<McpState>()(
       } catch (error) {
         logger.error('[mcpStore] STDIO test connection failed:', error)
         set((draft) => {
-          draft.testError = String(error)
+          draft.testError = errorMessage(error)
           draft.testingEndpoint = null
         })
         throw error
@@ -351,7 +351,7 @@ export const useMcpStore = create<McpState>()(
         logger.error('[mcpStore] Failed to connect to server:', { id, error })
         set((draft) => {
           draft.connectionStatus[id] = 'error'
-          draft.connectionErrors[id] = String(error)
+          draft.connectionErrors[id] = errorMessage(error)
           delete draft.serverTools[id]
         })
 
@@ -382,7 +382,7 @@ export const useMcpStore = create<McpState>()(
         logger.error('[mcpStore] Probe failed for server:', { id, error })
         const errorResult: ProbeResult = {
           status: 'error',
-          error: String(error),
+          error: errorMessage(error),
         }
         set((draft) => {
           draft.probeResults[id] = errorResult
@@ -9,7 +9,9 @@ import type {
 } from './types'
 import { MAX_MESSAGES_IN_MEMORY } from './types'
 import { cleanupThrottleState } from './throttle'
+import { createStreamChannel } from './streamChannel'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCrudActions => ({
   loadMessages: async (conversationId: string) => {
@@ -44,7 +46,7 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
         if (convState) {
           convState.isLoading = false
         }
-        draft.error = String(error)
+        draft.error = errorMessage(error)
       })
       logger.error('Failed to load messages:', error)
     }
@@ -68,7 +70,6 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
     files?: { name: string; content: string; mimeType: string }[],
     searchEnabled?: boolean,
     parameterOverrides?: SendMessageParameterOverrides,
-    contextMessageCount?: number | null,
     useProviderDefaults?: boolean
   ) => {
     set((draft) => {
@@ -124,7 +125,6 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
         hasFiles: !!files?.length,
         searchEnabled,
         hasParameterOverrides: !!parameterOverrides,
-        contextMessageCount,
         useProviderDefaults,
       })
 
@@ -147,8 +147,8 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
         files,
         searchEnabled,
         parameterOverrides,
-        contextMessageCount,
         useProviderDefaults,
+        streamChannel: createStreamChannel(get),
       })
 
       logger.info('[messageStore] Received user message:', userMessage)
@@ -174,12 +174,12 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
       logger.error('[messageStore] Error type:', typeof error)
       logger.error('[messageStore] Error details:', {
         error,
-        errorString: String(error),
+        errorString: errorMessage(error),
         errorKeys: error ? Object.keys(error) : 'null',
       })
 
       set((draft) => {
-        draft.error = String(error)
+        draft.error = errorMessage(error)
         draft.isSending = false
       })
       throw error
@@ -229,7 +229,7 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
         if (convState) {
           convState.isLoading = false
         }
-        draft.error = String(error)
+        draft.error = errorMessage(error)
       })
       throw error
     }
@@ -412,7 +412,6 @@ export const createCrudActions = (set: ImmerSet, get: StoreGet): MessageStoreCru
       next.files,
       next.searchEnabled,
       next.parameterOverrides,
-      next.contextMessageCount,
       next.useProviderDefaults
     )
   },
@@ -1,4 +1,8 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { Message } from '@/types'
 import type { ImmerSet, StoreGet, MessageStoreSearchActions } from './types'
+import { createStreamChannel } from './streamChannel'
+import { logger } from '@/lib/logger'
 
 export const createSearchActions = (set: ImmerSet, get: StoreGet): MessageStoreSearchActions => ({
   setPendingSearchDecision: (conversationId: string, messageId: string, pending: boolean) => {
@@ -24,4 +28,37 @@ export const createSearchActions = (set: ImmerSet, get: StoreGet): MessageStoreS
       }
     })
   },
+
+  // Search again for a user message with `query` and regenerate the reply
+  rerunSearch: async (conversationId: string, userMessageId: string, query: string) => {
+    get().getConversationState(conversationId) // Ensure state exists
+
+    try {
+      await invoke('rerun_search', {
+        messageId: userMessageId,
+        query,
+        streamChannel: createStreamChannel(get),
+      })
+
+      set((draft) => {
+        const convState = draft.conversationStates[conversationId]
+        if (convState) {
+          // The backend removed everything after the user message
+          const idx = convState.messages.findIndex((m: Message) => m.id === userMessageId)
+          if (idx >= 0) {
+            convState.messages = convState.messages.slice(0, idx + 1)
+          }
+          convState.isStreaming = true
+          convState.isWaitingForAI = true
+          convState.streamingContent = ''
+          convState.streamingReasoningContent = ''
+          convState.streamingToolCalls = {}
+          convState.apiError = null
+        }
+      })
+    } catch (error) {
+      logger.error('[messageStore] Failed to rerun search:', error)
+      throw error
+    }
+  },
 })
@@ -0,0 +1,41 @@
+import { Channel } from '@tauri-apps/api/core'
+import type { StreamChannelEvent } from '@/types'
+import type { StoreGet } from './types'
+
+/**
+ * Channel passed to send_message. Streaming output of that generation arrives here
+ * instead of as global events, so other windows don't receive it.
+ */
+export function createStreamChannel(get: StoreGet): Channel<StreamChannelEvent> {
+  const channel = new Channel<StreamChannelEvent>()
+  channel.onmessage = (message) => {
+    const store = get()
+    const convId = message.conversation_id
+    switch (message.event) {
+      case 'chat-stream':
+        store.appendStreamingChunk(convId, message.content)
+        break
+      case 'chat-stream-reasoning':
+        store.appendStreamingReasoningChunk(convId, message.content)
+        break
+      case 'reasoning-started':
+        store.setIsReasoningActive(convId, true)
+        break
+      case 'chat-stream-image':
+        store.appendStreamingImage(convId, message.image_url)
+        break
+      case 'tool-call-started':
+        store.addStreamingToolCall(
+          convId,
+          message.tool_call_id,
+          message.tool_name,
+          message.tool_input
+        )
+        break
+      case 'tool-call-completed':
+        store.updateStreamingToolCall(convId, message.tool_call_id, message.tool_output)
+        break
+    }
+  }
+  return channel
+}
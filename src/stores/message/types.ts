@@ -125,7 +125,6 @@ export interface PendingMessage {
   files?: { name: string; content: string; mimeType: string }[]
   searchEnabled?: boolean
   parameterOverrides?: SendMessageParameterOverrides
-  contextMessageCount?: number | null
   useProviderDefaults?: boolean
 }
 
@@ -159,7 +158,6 @@ export interface MessageStoreCrudActions {
     files?: { name: string; content: string; mimeType: string }[],
     searchEnabled?: boolean,
     parameterOverrides?: SendMessageParameterOverrides,
-    contextMessageCount?: number | null,
     useProviderDefaults?: boolean
   ) => Promise<void>
   stopGeneration: (conversationId: string) => Promise<void>
@@ -192,6 +190,7 @@ export interface MessageStoreUrlActions {
 export interface MessageStoreSearchActions {
   setPendingSearchDecision: (conversationId: string, messageId: string, pending: boolean) => void
   clearPendingSearchDecisions: (conversationId: string) => void
+  rerunSearch: (conversationId: string, userMessageId: string, query: string) => Promise<void>
 }
 
 // Combined actions type (for backwards compatibility)
@@ -3,6 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import type { Model, Provider, CreateModelRequest } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface ModelState {
   models: Model[]
@@ -42,7 +43,7 @@ export const useModelStore = create<ModelState>()(
       } catch (error) {
         logger.error('[modelStore] Failed to load models:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -63,7 +64,7 @@ export const useModelStore = create<ModelState>()(
       } catch (error) {
         logger.error('[modelStore] Failed to load providers:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -90,7 +91,7 @@ export const useModelStore = create<ModelState>()(
       } catch (error) {
         logger.error('[modelStore] Failed to load models and providers:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -113,7 +114,7 @@ export const useModelStore = create<ModelState>()(
         return model
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -136,7 +137,7 @@ export const useModelStore = create<ModelState>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -3,6 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import type { Prompt, CreatePromptRequest } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface PromptState {
   prompts: Prompt[]
@@ -40,7 +41,7 @@ export const usePromptStore = create<PromptState>()(
       } catch (error) {
         logger.error('[promptStore] Failed to load prompts:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -72,7 +73,7 @@ export const usePromptStore = create<PromptState>()(
       } catch (error) {
         logger.error('[promptStore] Failed to load prompts by category:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -92,7 +93,7 @@ export const usePromptStore = create<PromptState>()(
         return prompt
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -116,7 +117,7 @@ export const usePromptStore = create<PromptState>()(
         return prompt
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -136,7 +137,7 @@ export const usePromptStore = create<PromptState>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -160,7 +161,7 @@ export const usePromptStore = create<PromptState>()(
         return prompt
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
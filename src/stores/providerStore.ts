@@ -3,6 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import type { Provider, CreateProviderRequest } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface ProviderStore {
   providers: Provider[]
@@ -35,7 +36,7 @@ export const useProviderStore = create<ProviderStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         logger.error('Failed to load providers:', error)
@@ -56,7 +57,7 @@ export const useProviderStore = create<ProviderStore>()(
         return provider
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -80,7 +81,7 @@ export const useProviderStore = create<ProviderStore>()(
         return provider
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -100,7 +101,7 @@ export const useProviderStore = create<ProviderStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -10,8 +10,10 @@ import type {
   WebFetchMode,
   WebFetchLocalMethod,
   WebFetchApiProvider,
+  TitleGenerationMode,
   LogLevel,
 } from '@/types'
+import { errorMessage } from '@/types'
 
 interface SettingsStore {
   settings: Record<string, string>
@@ -38,6 +40,10 @@ interface SettingsStore {
   getJinaApiKey: () => Promise<string | null>
   setJinaApiKey: (key: string) => Promise<void>
 
+  // Conversation title settings
+  getTitleGenerationMode: () => Promise<TitleGenerationMode>
+  setTitleGenerationMode: (mode: TitleGenerationMode) => Promise<void>
+
   // Logging settings
   getLogLevelRust: () => Promise<LogLevel>
   setLogLevelRust: (level: LogLevel) => Promise<void>
@@ -70,7 +76,7 @@ export const useSettingsStore = create<SettingsStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         logger.error('Failed to load settings:', error)
@@ -100,7 +106,7 @@ export const useSettingsStore = create<SettingsStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         throw error
@@ -136,7 +142,7 @@ export const useSettingsStore = create<SettingsStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         logger.error('Failed to fetch models:', error)
@@ -200,6 +206,16 @@ export const useSettingsStore = create<SettingsStore>()(
       await get().saveSetting('jina_api_key', key)
     },
 
+    // Conversation title settings
+    getTitleGenerationMode: async () => {
+      const value = await get().getSetting('title_generation_mode')
+      return (value as TitleGenerationMode) || 'auto'
+    },
+
+    setTitleGenerationMode: async (mode: TitleGenerationMode) => {
+      await get().saveSetting('title_generation_mode', mode)
+    },
+
     // Logging settings
     getLogLevelRust: async () => {
       const value = await get().getSetting('log_level_rust')
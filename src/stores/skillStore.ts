@@ -3,6 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import type { Skill, SkillSourceInfo } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface SkillState {
   skills: Skill[]
@@ -46,7 +47,7 @@ export const useSkillStore = create<SkillState>()(
       } catch (error) {
         logger.error('[skillStore] Failed to load skills:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
@@ -69,7 +70,7 @@ export const useSkillStore = create<SkillState>()(
       } catch (error) {
         logger.error('[skillStore] Failed to scan skills:', error)
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
       }
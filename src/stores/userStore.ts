@@ -3,6 +3,7 @@ import { immer } from 'zustand/middleware/immer'
 import { invoke } from '@tauri-apps/api/core'
 import type { User } from '@/types'
 import { logger } from '@/lib/logger'
+import { errorMessage } from '@/types'
 
 interface UserStore {
   selfUser: User | null
@@ -37,7 +38,7 @@ export const useUserStore = create<UserStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         logger.error('Failed to load self user:', error)
@@ -58,7 +59,7 @@ export const useUserStore = create<UserStore>()(
         })
       } catch (error) {
         set((draft) => {
-          draft.error = String(error)
+          draft.error = errorMessage(error)
           draft.isLoading = false
         })
         logger.error('Failed to load users:', error)
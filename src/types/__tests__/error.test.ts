@@ -0,0 +1,16 @@
+import { describe, it, expect } from 'vitest'
+import { errorMessage, isAppError } from '../error'
+
+describe('errorMessage', () => {
+  it('should use the message of a structured command error', () => {
+    const error = { kind: 'auth', message: 'Invalid API key', retryable: false }
+    expect(isAppError(error)).toBe(true)
+    expect(errorMessage(error)).toBe('Invalid API key')
+  })
+
+  it('should handle Error instances and plain strings', () => {
+    expect(errorMessage(new Error('boom'))).toBe('boom')
+    expect(errorMessage('failed')).toBe('failed')
+    expect(isAppError('failed')).toBe(false)
+  })
+})
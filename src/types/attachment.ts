@@ -19,6 +19,28 @@ export interface CreateFileAttachmentRequest {
   storage_path: string
 }
 
+// Git repository context (attach_repo_context); `content` is attached as a Markdown file
+export interface RepoCommit {
+  hash: string
+  author: string
+  date: string
+  subject: string
+}
+
+export interface RepoContext {
+  root: string
+  branch?: string // Absent on a detached HEAD
+  head?: string // Absent before the first commit
+  commits: RepoCommit[]
+  status: string[] // `git status --porcelain` lines
+  diff: string
+  diff_truncated: boolean
+  files: string[]
+  file_count: number // Tracked files, including those left out of `files`
+  name: string
+  content: string
+}
+
 // User attachment type enum (currently only files)
 // User-provided URLs are stored as fetch_results with source_type="user_link"
 export type UserAttachmentType = 'file'
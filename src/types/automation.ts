@@ -0,0 +1,91 @@
+import type { ExportFormat } from './export'
+
+export type AutomationSourceKind = 'url' | 'rss'
+
+export interface AutomationSource {
+  kind: AutomationSourceKind
+  url: string
+}
+
+// What starts an automation
+export type AutomationTrigger =
+  // Every day at a local time, "HH:MM"
+  | { type: 'schedule'; run_at: string }
+  // A file appears in the folder (subfolders are not watched)
+  | { type: 'folder_watch'; path: string }
+  // POST to get_automation_webhook_url; the token is always generated by the app
+  | { type: 'webhook'; token?: string }
+  | { type: 'conversation_archived' }
+
+// What an automation does when triggered
+export type AutomationAction =
+  | {
+      type: 'run_prompt'
+      prompt: string
+      sources?: AutomationSource[]
+      // Replies with the conversation's model when unset
+      assistant_id?: string | null
+      // Created on the first run when unset
+      conversation_id?: string | null
+    }
+  | {
+      type: 'export_conversation'
+      // The archived conversation when unset
+      conversation_id?: string | null
+      format: ExportFormat
+      directory: string
+    }
+  | {
+      type: 'call_mcp_tool'
+      server_id: string
+      tool_name: string
+      // "{{input}}" in strings is replaced with the file path, webhook body or
+      // archived conversation id
+      arguments?: Record<string, unknown> | null
+    }
+
+// Trigger rule and the action it runs
+export interface Automation {
+  id: string
+  name: string
+  trigger: AutomationTrigger
+  action: AutomationAction
+  is_enabled: boolean
+  last_run_at?: string
+  // Scheduled triggers only
+  next_run_at?: string
+  last_error?: string
+  created_at: string
+  updated_at: string
+}
+
+// Input for create_automation / update_automation
+export interface AutomationInput {
+  name: string
+  trigger: AutomationTrigger
+  action: AutomationAction
+  is_enabled?: boolean
+}
+
+// What started a run
+export type AutomationEvent =
+  | { type: 'schedule' }
+  | { type: 'manual' }
+  | { type: 'file_added'; path: string }
+  | { type: 'webhook'; body: string }
+  | { type: 'conversation_archived'; conversation_id: string }
+
+export type AutomationRunStatus = 'running' | 'succeeded' | 'failed'
+
+// Entry of an automation's history (list_automation_runs)
+export interface AutomationRun {
+  id: string
+  automation_id: string
+  event: AutomationEvent
+  status: AutomationRunStatus
+  // Conversation posted to, file written or tool result
+  output?: string
+  error?: string
+  started_at: string
+  finished_at?: string
+}
@@ -19,6 +19,28 @@ export interface CreateSearchResultRequest {
   searched_at: string
 }
 
+// One entry of a search's result list (rank 1 first); fetched is false for results
+// whose page wasn't fetched into the context
+export interface SearchResultItem {
+  id: string
+  search_result_id: string
+  rank: number
+  title: string
+  url: string
+  snippet: string
+  fetched: boolean
+  created_at: string
+}
+
+// A [number] marker in an assistant message and the fetched page it cites
+export interface MessageCitation {
+  message_id: string
+  number: number
+  fetch_result_id: string
+  url: string
+  title?: string
+}
+
 // Fetch result - stores fetched web resource metadata (content in filesystem)
 // source_type="user_link" indicates a user-provided URL (no separate user_links table)
 export interface FetchResult {
@@ -38,10 +60,26 @@ export interface FetchResult {
   original_size?: number
   processed_size?: number
   favicon_url?: string
+  favicon_path?: string // Cached favicon, relative to attachments dir; load via get_attachment_url
   created_at: string
   updated_at: string
 }
 
+// Result of `get_fetch_content_metadata`
+export interface FetchContentMetadata {
+  total_size: number
+  can_read_whole: boolean // false when the content must be read with `read_fetch_content_range`
+  max_range_length: number
+}
+
+// A page of fetched content; byte offsets, cut on character boundaries
+export interface FetchContentRange {
+  content: string
+  offset: number
+  next_offset: number // equals total_size after the last page
+  total_size: number
+}
+
 export interface CreateFetchResultRequest {
   source_type?: string
   source_id?: string
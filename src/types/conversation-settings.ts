@@ -12,6 +12,13 @@ export interface ModelParameterOverrides {
 // Prompt mode options
 export type PromptMode = 'none' | 'existing' | 'custom'
 
+// History strategy options
+// 'all' = every earlier message
+// 'token_budget' = the newest messages that fit in contextBudgetPercent of the context window
+// 'summarized' = conversation summary plus the messages after it
+// 'none' = only the current message
+export type HistoryMode = 'all' | 'token_budget' | 'summarized' | 'none'
+
 export interface ConversationSettings {
   // Conversation ID this settings belongs to
   conversationId: string
@@ -24,9 +31,22 @@ export interface ConversationSettings {
   parameterOverrides: ModelParameterOverrides
 
   // Context settings
-  // null = unlimited (send all history)
-  // number = max number of messages to include
-  contextMessageCount: number | null
+  historyMode: HistoryMode
+  // Share of the model's context window (1-100) in 'token_budget' mode
+  // (null = DEFAULT_CONTEXT_BUDGET_PERCENT)
+  contextBudgetPercent: number | null
+
+  // Cumulative limits for the conversation; sends that would exceed them are refused
+  // (null = unlimited). Cost is in USD.
+  tokenBudget: number | null
+  costBudget: number | null
+
+  // Provider policy restricting which providers may receive messages (null = any)
+  providerPolicyId: string | null
+
+  // Language replies are written in: a language name, REPLY_LANGUAGE_AUTO to match
+  // each user message, or null to leave it to the model
+  replyLanguage: string | null
 
   // Which preset is currently selected (for UI display)
   // null when using default or custom parameters
@@ -56,6 +76,9 @@ export interface ConversationSettings {
 
   // Working directory for bash tool (overrides default home directory)
   workingDirectory: string | null
+
+  // Backend field names whose value comes from the conversation's workspace
+  inherited: string[]
 }
 
 // Request to update conversation settings (all fields optional for partial updates)
@@ -63,7 +86,12 @@ export interface UpdateConversationSettingsRequest {
   useProviderDefaults?: boolean
   useCustomParameters?: boolean
   parameterOverrides?: ModelParameterOverrides
-  contextMessageCount?: number | null
+  historyMode?: HistoryMode
+  contextBudgetPercent?: number | null
+  tokenBudget?: number | null
+  costBudget?: number | null
+  providerPolicyId?: string | null
+  replyLanguage?: string | null
   selectedPresetId?: string | null
   systemPromptMode?: PromptMode
   selectedSystemPromptId?: string | null
@@ -82,7 +110,12 @@ export interface ConversationSettingsResponse {
   use_provider_defaults: boolean
   use_custom_parameters: boolean
   parameter_overrides: ModelParameterOverrides
-  context_message_count: number | null
+  history_mode: HistoryMode
+  context_budget_percent: number | null
+  token_budget: number | null
+  cost_budget: number | null
+  provider_policy_id: string | null
+  reply_language: string | null
   selected_preset_id: string | null
   system_prompt_mode: PromptMode
   selected_system_prompt_id: string | null
@@ -93,6 +126,7 @@ export interface ConversationSettingsResponse {
   enabled_mcp_server_ids: string[]
   enabled_skill_ids: string[]
   working_directory: string | null
+  inherited?: string[]
 }
 
 // Convert backend response to frontend format
@@ -102,7 +136,12 @@ export function fromBackendSettings(response: ConversationSettingsResponse): Con
     useProviderDefaults: response.use_provider_defaults,
     useCustomParameters: response.use_custom_parameters,
     parameterOverrides: response.parameter_overrides,
-    contextMessageCount: response.context_message_count,
+    historyMode: response.history_mode ?? 'all',
+    contextBudgetPercent: response.context_budget_percent ?? null,
+    tokenBudget: response.token_budget ?? null,
+    costBudget: response.cost_budget ?? null,
+    providerPolicyId: response.provider_policy_id ?? null,
+    replyLanguage: response.reply_language ?? null,
     selectedPresetId: response.selected_preset_id,
     systemPromptMode: response.system_prompt_mode,
     selectedSystemPromptId: response.selected_system_prompt_id,
@@ -113,6 +152,7 @@ export function fromBackendSettings(response: ConversationSettingsResponse): Con
     enabledMcpServerIds: response.enabled_mcp_server_ids ?? [],
     enabledSkillIds: response.enabled_skill_ids ?? [],
     workingDirectory: response.working_directory ?? null,
+    inherited: response.inherited ?? [],
   }
 }
 
@@ -123,7 +163,13 @@ export function toBackendRequest(req: UpdateConversationSettingsRequest): Record
   if (req.useProviderDefaults !== undefined) result.use_provider_defaults = req.useProviderDefaults
   if (req.useCustomParameters !== undefined) result.use_custom_parameters = req.useCustomParameters
   if (req.parameterOverrides !== undefined) result.parameter_overrides = req.parameterOverrides
-  if (req.contextMessageCount !== undefined) result.context_message_count = req.contextMessageCount
+  if (req.historyMode !== undefined) result.history_mode = req.historyMode
+  if (req.contextBudgetPercent !== undefined)
+    result.context_budget_percent = req.contextBudgetPercent
+  if (req.tokenBudget !== undefined) result.token_budget = req.tokenBudget
+  if (req.costBudget !== undefined) result.cost_budget = req.costBudget
+  if (req.providerPolicyId !== undefined) result.provider_policy_id = req.providerPolicyId
+  if (req.replyLanguage !== undefined) result.reply_language = req.replyLanguage
   if (req.selectedPresetId !== undefined) result.selected_preset_id = req.selectedPresetId
   if (req.systemPromptMode !== undefined) result.system_prompt_mode = req.systemPromptMode
   if (req.selectedSystemPromptId !== undefined)
@@ -150,7 +196,12 @@ export const createDefaultConversationSettings = (
   useProviderDefaults: true, // Default: use provider's default settings
   useCustomParameters: false,
   parameterOverrides: {},
-  contextMessageCount: null,
+  historyMode: 'all',
+  contextBudgetPercent: null,
+  tokenBudget: null,
+  costBudget: null,
+  providerPolicyId: null,
+  replyLanguage: null,
   selectedPresetId: null,
   // Prompt defaults - 'none' means use assistant's prompts
   systemPromptMode: 'none',
@@ -162,6 +213,7 @@ export const createDefaultConversationSettings = (
   enabledMcpServerIds,
   enabledSkillIds,
   workingDirectory: null,
+  inherited: [],
 })
 
 // Parameter limits for validation
@@ -175,15 +227,35 @@ export const PARAMETER_LIMITS = {
 
 import type { TFunction } from 'i18next'
 
-export function getContextCountOptions(
+// Mirrors DEFAULT_CONTEXT_BUDGET_PERCENT in the backend
+export const DEFAULT_CONTEXT_BUDGET_PERCENT = 50
+
+// replyLanguage value that follows the language of each user message
+export const REPLY_LANGUAGE_AUTO = 'auto'
+
+// Budget presets; null = unlimited ('all' mode)
+export function getContextBudgetOptions(
   t: TFunction
 ): Array<{ value: number | null; label: string }> {
   return [
     { value: null, label: t('settings:contextUnlimited') },
-    { value: 5, label: t('settings:context5Messages') },
-    { value: 10, label: t('settings:context10Messages') },
-    { value: 20, label: t('settings:context20Messages') },
-    { value: 50, label: t('settings:context50Messages') },
-    { value: 100, label: t('settings:context100Messages') },
+    ...[25, 50, 75, 100].map((percent) => ({
+      value: percent,
+      label: t('settings:contextBudgetPercent', { percent }),
+    })),
   ]
 }
+
+// Usage measured against a conversation's budgets (get_conversation_budget_status)
+export interface BudgetStatus {
+  token_budget: number | null
+  cost_budget: number | null
+  used_tokens: number
+  used_cost: number
+  unpriced_message_count: number
+  // Expected usage of the next reply (average of the replies so far)
+  projected_tokens: number
+  projected_cost: number
+  warning: boolean
+  exceeded: boolean
+}
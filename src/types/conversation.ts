@@ -1,3 +1,5 @@
+import type { ConversationSettingsResponse, HistoryMode } from './conversation-settings'
+
 // Conversation types
 export interface Conversation {
   id: string
@@ -5,12 +7,87 @@ export interface Conversation {
   created_at: string
   updated_at: string
   last_message?: string
+  summary?: string
+  summary_message_count?: number
+  // Held in memory only (create_private_conversation); gone when closed or on quit
+  is_private?: boolean
+  metadata?: ConversationMetadata | null
+  // Workspace whose defaults the conversation inherits
+  workspace_id?: string | null
+  // Set while archived (archive_conversation)
+  archived_at?: string | null
+}
+
+// Where a conversation came from (send_to_new_conversation)
+export interface ConversationMetadata {
+  origin_conversation_id?: string | null
+  origin_message_id?: string | null
+}
+
+// A folder of related conversations sharing default settings
+export interface Workspace {
+  id: string
+  name: string
+  settings: WorkspaceSettings
+  created_at: string
+  updated_at: string
+}
+
+// Defaults for the conversations in a workspace. Settings resolve
+// global -> workspace -> conversation; unset fields fall back to the global settings.
+export interface WorkspaceSettings {
+  search_provider?: string | null
+  model_id?: string | null
+  // Takes precedence over model_id
+  assistant_id?: string | null
+  history_mode?: HistoryMode | null
+  context_budget_percent?: number | null
+}
+
+export interface CreateWorkspaceRequest {
+  name: string
+  settings?: WorkspaceSettings
+}
+
+// Omitted fields keep their value; settings replaces the stored defaults as a whole
+export interface UpdateWorkspaceRequest {
+  name?: string
+  settings?: WorkspaceSettings
 }
 
 export interface CreateConversationRequest {
   title: string
 }
 
+// Settings a template applies to new conversations; omitted fields keep their defaults
+export type ConversationTemplateSettings = Partial<
+  Omit<ConversationSettingsResponse, 'conversation_id' | 'inherited'>
+>
+
+// Starting point for a recurring kind of conversation (create_conversation_from_template).
+// title_pattern may contain {{date}}, {{time}}, {{weekday}} and {{month}}.
+export interface ConversationTemplate {
+  id: string
+  name: string
+  title_pattern: string
+  assistant_id: string | null
+  settings: ConversationTemplateSettings
+  opening_message: string | null
+  // Send opening_message right away; otherwise it is offered as a draft
+  send_opening_message: boolean
+  created_at: string
+  updated_at: string
+}
+
+export interface ConversationTemplateInput {
+  name: string
+  title_pattern: string
+  assistant_id?: string | null
+  settings?: ConversationTemplateSettings
+  opening_message?: string | null
+  send_opening_message?: boolean
+}
+
 // Conversation participant types
 export interface ConversationParticipant {
   id: string
@@ -23,7 +100,22 @@ export interface ConversationParticipant {
   joined_at: string
   left_at?: string
   last_read_at?: string
-  metadata?: string
+  metadata?: ParticipantMetadata
+}
+
+// Per-conversation display settings for a participant
+export interface ParticipantMetadata {
+  color?: string // "#rgb" or "#rrggbb"
+  nickname?: string
+}
+
+export type ParticipantRole = 'owner' | 'admin' | 'member' | 'observer'
+
+// Omitted fields keep their current value; an empty display_name clears it
+export interface UpdateConversationParticipantRequest {
+  display_name?: string
+  role?: ParticipantRole
+  metadata?: ParticipantMetadata
 }
 
 export interface CreateConversationParticipantRequest {
@@ -43,4 +135,6 @@ export interface ParticipantSummary {
   avatar_text?: string
   avatar_image_path?: string
   avatar_image_url?: string
+  color?: string
+  nickname?: string
 }
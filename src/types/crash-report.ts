@@ -0,0 +1,16 @@
+// Written by the backend panic hook, returned by `get_last_crash_report` on the next launch
+export interface CrashReport {
+  id: string
+  timestamp: string
+  app_version: string
+  db_schema_version: number
+  os: string
+  arch: string
+  thread: string
+  message: string
+  // file:line:column of the panic
+  location?: string
+  backtrace: string
+  // Most recent log lines before the crash, secrets redacted
+  recent_events: string[]
+}
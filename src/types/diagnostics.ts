@@ -0,0 +1,28 @@
+export type CheckStatus = 'ok' | 'warning' | 'error' | 'skipped'
+
+export interface DiagnosticCheck {
+  // e.g. "database", "ollama", "provider:OpenAI"
+  name: string
+  status: CheckStatus
+  message: string
+  latency_ms?: number
+}
+
+// Result of `run_diagnostics`, meant to be attached to bug reports
+export interface DiagnosticsReport {
+  app_version: string
+  os: string
+  arch: string
+  generated_at: string
+  checks: DiagnosticCheck[]
+}
+
+// Result of `get_chrome_status` and `download_chromium`
+export interface ChromeStatus {
+  available: boolean
+  path?: string | null
+  // path is the Chromium downloaded into app data
+  downloaded: boolean
+  revision: string
+  error?: string | null
+}
@@ -0,0 +1,42 @@
+// Structured error returned by Tauri commands (mirrors AppError in src-tauri/src/error.rs)
+export type ErrorKind =
+  | 'auth'
+  | 'network'
+  | 'timeout'
+  | 'rate_limited'
+  | 'not_found'
+  | 'validation'
+  | 'provider'
+  | 'cancelled'
+  | 'budget_exceeded'
+  | 'provider_not_allowed'
+  | 'browser_unavailable'
+  | 'internal'
+
+export interface ProviderErrorDetails {
+  status?: number | null
+  provider_type?: string | null
+}
+
+export interface AppError {
+  kind: ErrorKind
+  message: string
+  retryable: boolean
+  provider?: ProviderErrorDetails
+}
+
+export function isAppError(value: unknown): value is AppError {
+  return (
+    typeof value === 'object' &&
+    value !== null &&
+    typeof (value as AppError).kind === 'string' &&
+    typeof (value as AppError).message === 'string'
+  )
+}
+
+// Human-readable message for anything thrown by invoke() or application code
+export function errorMessage(error: unknown): string {
+  if (isAppError(error)) return error.message
+  if (error instanceof Error) return error.message
+  return String(error)
+}
@@ -1,49 +1,92 @@
+import type { BudgetStatus, HistoryMode } from './conversation-settings'
+import type { ErrorKind, ProviderErrorDetails } from './error'
 import type { Message } from './message'
+import type { OutboxEntry } from './outbox'
 
-// Event payloads
-export interface ChatStreamEvent {
+// Event payloads (mirror src-tauri/src/events.rs). Every payload carries the
+// backend's event schema version.
+export const EVENT_SCHEMA_VERSION = 1
+
+export interface EventEnvelope {
+  schema_version: number
+}
+
+export interface ChatStreamEvent extends EventEnvelope {
   conversation_id: string
   content: string
 }
 
 // Event for streaming reasoning/thinking content (from models like GPT-5, Gemini with thinking)
-export interface ChatStreamReasoningEvent {
+export interface ChatStreamReasoningEvent extends EventEnvelope {
   conversation_id: string
   content: string
 }
 
-export interface ChatCompleteEvent {
+export interface ReasoningStartedEvent extends EventEnvelope {
+  conversation_id: string
+}
+
+// Tool call events (built-in tools and MCP)
+export interface ToolCallStartedEvent extends EventEnvelope {
+  conversation_id: string
+  tool_call_id: string
+  tool_name: string
+  tool_input: string
+}
+
+export interface ToolCallCompletedEvent extends EventEnvelope {
+  conversation_id: string
+  tool_call_id: string
+  tool_name: string
+  tool_input: string
+  tool_output: string
+}
+
+export interface ChatCompleteEvent extends EventEnvelope {
   conversation_id: string
   message: Message | null
+  user_message?: Message
+  cancelled: boolean
 }
 
-export interface ChatErrorEvent {
+// Follow-up suggestions arrive after chat-complete; they're also saved on the message
+export interface FollowUpSuggestionsEvent extends EventEnvelope {
+  conversation_id: string
+  message_id: string
+  suggestions: string[]
+}
+
+export interface ChatErrorEvent extends EventEnvelope {
   conversation_id: string
   error: string
+  kind: ErrorKind
+  retryable: boolean
+  provider: ProviderErrorDetails | null
 }
 
-export interface AttachmentProcessingStartedEvent {
+export interface AttachmentProcessingStartedEvent extends EventEnvelope {
   message_id: string
   conversation_id: string
   urls: string[]
 }
 
-export interface AttachmentProcessingCompleteEvent {
+export interface AttachmentProcessingCompleteEvent extends EventEnvelope {
   message_id: string
   conversation_id: string
   attachment_ids: string[]
 }
 
-export interface AttachmentProcessingErrorEvent {
+export interface AttachmentProcessingErrorEvent extends EventEnvelope {
   message_id: string
   conversation_id: string
   attachment_id?: string
   error: string
 }
 
-export interface AttachmentUpdateEvent {
+export interface AttachmentUpdateEvent extends EventEnvelope {
   message_id: string
   conversation_id: string
+  attachment_id: string
   completed_url?: string
   attachment?: {
     type: string
@@ -52,12 +95,77 @@ export interface AttachmentUpdateEvent {
   }
 }
 
-export interface ChatStreamImageEvent {
+export interface ChatStreamImageEvent extends EventEnvelope {
   conversation_id: string
   image_url: string
 }
 
-export interface SearchDecisionCompleteEvent {
+export interface SearchDecisionCompleteEvent extends EventEnvelope {
+  message_id: string
+  conversation_id: string
+}
+
+// A reply was checked against its sources (opt-in); the result is a verification step
+export interface AnswerVerifiedEvent extends EventEnvelope {
+  conversation_id: string
   message_id: string
+  verification_id: string
+  verdict: string
+}
+
+// History sent with a request (debugging aid)
+export interface ContextBuiltEvent extends EventEnvelope {
+  conversation_id: string
+  history_mode: HistoryMode
+  budget_tokens?: number
+  total_tokens: number
+  included_message_ids: string[]
+  omitted_message_count: number
+}
+
+// A send brought the conversation close to its token or cost budget
+export interface ConversationBudgetWarningEvent extends EventEnvelope {
   conversation_id: string
+  status: BudgetStatus
 }
+
+// This month's estimated spend went over the soft cap
+export interface SpendCapReachedEvent extends EventEnvelope {
+  month: string
+  spent: number
+  cap: number
+}
+
+// Waiting for a free generation slot (see max_concurrent_generations)
+export interface GenerationQueuedEvent extends EventEnvelope {
+  conversation_id: string
+  provider: string
+}
+
+export interface ConnectivityChangedEvent extends EventEnvelope {
+  online: boolean
+}
+
+// Carries every message still waiting for the network
+export interface OutboxUpdatedEvent extends EventEnvelope {
+  entries: OutboxEntry[]
+}
+
+// percent is missing when the server doesn't report the download size
+export interface UpdateDownloadProgressEvent extends EventEnvelope {
+  version: string
+  downloaded: number
+  total?: number
+  percent?: number
+  finished: boolean
+}
+
+// Streaming output sent over the channel passed to send_message, tagged with the
+// name of the equivalent global event
+export type StreamChannelEvent =
+  | ({ event: 'chat-stream' } & ChatStreamEvent)
+  | ({ event: 'chat-stream-reasoning' } & ChatStreamReasoningEvent)
+  | ({ event: 'reasoning-started' } & ReasoningStartedEvent)
+  | ({ event: 'chat-stream-image' } & ChatStreamImageEvent)
+  | ({ event: 'tool-call-started' } & ToolCallStartedEvent)
+  | ({ event: 'tool-call-completed' } & ToolCallCompletedEvent)
@@ -0,0 +1,11 @@
+export type ExportFormat = 'markdown' | 'html' | 'json'
+
+// Extra content to include besides message text; omitted fields default to true
+export interface ExportOptions {
+  include_thinking?: boolean
+  // Tool calls and code executions
+  include_tool_calls?: boolean
+  include_search_decisions?: boolean
+  // Fetched web pages and web search queries
+  include_sources?: boolean
+}
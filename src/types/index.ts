@@ -1,5 +1,11 @@
 // Provider types
-export type { Provider, CreateProviderRequest } from './provider'
+export type {
+  Provider,
+  CreateProviderRequest,
+  OpenRouterRouting,
+  ProviderPolicy,
+  ProviderPolicyInput,
+} from './provider'
 
 // Model types
 export type {
@@ -63,14 +69,32 @@ export type {
 // Conversation types
 export type {
   Conversation,
+  ConversationMetadata,
   CreateConversationRequest,
   ConversationParticipant,
   CreateConversationParticipantRequest,
+  ParticipantMetadata,
+  ParticipantRole,
   ParticipantSummary,
+  UpdateConversationParticipantRequest,
+  Workspace,
+  WorkspaceSettings,
+  CreateWorkspaceRequest,
+  UpdateWorkspaceRequest,
+  ConversationTemplate,
+  ConversationTemplateInput,
+  ConversationTemplateSettings,
 } from './conversation'
 
 // Message types
-export type { Message, CreateMessageRequest } from './message'
+export type {
+  Message,
+  MessageRevision,
+  MessageFlagKind,
+  MessageFlag,
+  BookmarkedMessage,
+  CreateMessageRequest,
+} from './message'
 
 // Attachment types (user attachments - files only)
 export type {
@@ -78,6 +102,8 @@ export type {
   CreateFileAttachmentRequest,
   UserAttachmentType,
   UserAttachment,
+  RepoCommit,
+  RepoContext,
 } from './attachment'
 export { isFileAttachment } from './attachment'
 
@@ -85,8 +111,12 @@ export { isFileAttachment } from './attachment'
 export type {
   SearchResult,
   CreateSearchResultRequest,
+  SearchResultItem,
+  MessageCitation,
   FetchResult,
   CreateFetchResultRequest,
+  FetchContentMetadata,
+  FetchContentRange,
   ContextType,
   ContextEnrichment,
 } from './context'
@@ -100,12 +130,26 @@ export type {
   CreateSearchDecisionRequest,
   ToolCall,
   CreateToolCallRequest,
+  PatchResult,
   CodeExecution,
   CreateCodeExecutionRequest,
+  Translation,
+  Verification,
+  UnsupportedClaim,
+  AssistantSwitch,
+  StepExplanation,
   StepType,
   ProcessStep,
 } from './process-step'
-export { isThinkingStep, isSearchDecision, isToolCall, isCodeExecution } from './process-step'
+export {
+  isThinkingStep,
+  isSearchDecision,
+  isToolCall,
+  isCodeExecution,
+  isTranslation,
+  isVerification,
+  isAssistantSwitch,
+} from './process-step'
 
 // Message resources
 export type { MessageResources, Attachment } from './message-resources'
@@ -114,31 +158,95 @@ export type { MessageResources, Attachment } from './message-resources'
 export type { Prompt, CreatePromptRequest } from './prompt'
 
 // Search types
-export type { MessageSearchResult, ConversationSearchResult, SearchResults } from './search'
+export type {
+  MessageSearchResult,
+  ConversationMessageMatch,
+  ConversationSearchResult,
+  SearchResults,
+} from './search'
+
+// Export types
+export type { ExportFormat, ExportOptions } from './export'
 
 // Setting types
 export type {
   Setting,
   SearchProvider,
   SearchProviderId,
+  SearchProviderHealth,
   WebFetchMode,
   WebFetchLocalMethod,
   WebFetchApiProvider,
+  TitleGenerationMode,
   LogLevel,
+  DefaultSystemPrompt,
 } from './setting'
 
+// Background job types
+export type { Job, JobKind, JobStatus } from './job'
+
+// Automation types
+export type {
+  Automation,
+  AutomationAction,
+  AutomationEvent,
+  AutomationInput,
+  AutomationRun,
+  AutomationRunStatus,
+  AutomationSource,
+  AutomationSourceKind,
+  AutomationTrigger,
+} from './automation'
+
+// Usage and spend types
+export type { ModelSpend, ProviderSpend, SpendReport } from './usage'
+
+// Diagnostics types
+export type { CheckStatus, ChromeStatus, DiagnosticCheck, DiagnosticsReport } from './diagnostics'
+
+// Crash report types
+export type { CrashReport } from './crash-report'
+
+// Outbox types
+export type { OutboxEntry } from './outbox'
+
+// Request debug types
+export type { RequestDebug } from './request-debug'
+
+// Update types
+export type { UpdateChannel, UpdateInfo } from './update'
+
+// Error types
+export type { AppError, ErrorKind, ProviderErrorDetails } from './error'
+export { isAppError, errorMessage } from './error'
+
 // Event types
+export { EVENT_SCHEMA_VERSION } from './event'
 export type {
+  EventEnvelope,
   ChatStreamEvent,
   ChatStreamReasoningEvent,
   ChatStreamImageEvent,
+  ReasoningStartedEvent,
+  ToolCallStartedEvent,
+  ToolCallCompletedEvent,
+  StreamChannelEvent,
   ChatCompleteEvent,
   ChatErrorEvent,
+  FollowUpSuggestionsEvent,
   AttachmentProcessingStartedEvent,
   AttachmentProcessingCompleteEvent,
   AttachmentProcessingErrorEvent,
   AttachmentUpdateEvent,
   SearchDecisionCompleteEvent,
+  AnswerVerifiedEvent,
+  ContextBuiltEvent,
+  ConversationBudgetWarningEvent,
+  SpendCapReachedEvent,
+  GenerationQueuedEvent,
+  ConnectivityChangedEvent,
+  OutboxUpdatedEvent,
+  UpdateDownloadProgressEvent,
 } from './event'
 
 // URL status type (used in message store for tracking fetch status)
@@ -149,13 +257,17 @@ export type {
   ModelParameterOverrides,
   ConversationSettings,
   PromptMode,
+  HistoryMode,
   UpdateConversationSettingsRequest,
   ConversationSettingsResponse,
+  BudgetStatus,
 } from './conversation-settings'
 export {
   createDefaultConversationSettings,
   fromBackendSettings,
   toBackendRequest,
   PARAMETER_LIMITS,
-  getContextCountOptions,
+  getContextBudgetOptions,
+  DEFAULT_CONTEXT_BUDGET_PERCENT,
+  REPLY_LANGUAGE_AUTO,
 } from './conversation-settings'
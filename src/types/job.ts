@@ -0,0 +1,25 @@
+export type JobKind =
+  | 'summary_refresh'
+  | 'webhook_delivery'
+  | 'answer_verification'
+  | 'automation_run'
+
+export type JobStatus = 'queued' | 'running' | 'completed' | 'failed'
+
+// Background job from the persistent queue (see `list_jobs`)
+export interface Job {
+  id: string
+  kind: JobKind
+  payload: Record<string, unknown>
+  status: JobStatus
+  // Higher runs first
+  priority: number
+  attempts: number
+  max_attempts: number
+  last_error?: string
+  // Not picked up before this time; pushed back on retry
+  run_after: string
+  created_at: string
+  updated_at: string
+  completed_at?: string
+}
@@ -1,3 +1,5 @@
+import type { ModelParameters } from './model'
+
 // Message types (thinking_content moved to ThinkingStep)
 export interface Message {
   id: string
@@ -6,9 +8,41 @@ export interface Message {
   sender_id?: string
   content: string
   tokens?: number
+  input_tokens?: number
+  output_tokens?: number
+  cost?: number // USD
+  follow_up_suggestions?: string[]
+  // Provider, model and parameters that generated this reply
+  provider_type?: string
+  model_id?: string
+  model_params?: ModelParameters
   created_at: string
 }
 
+// Earlier content of an edited message (list_message_revisions)
+export interface MessageRevision {
+  id: string
+  message_id: string
+  content: string
+  created_at: string // When this content was replaced
+}
+
+// Bookmark, rating or emoji reaction on a message
+export type MessageFlagKind = 'bookmark' | 'thumbs_up' | 'thumbs_down' | 'emoji'
+
+export interface MessageFlag {
+  message_id: string
+  kind: MessageFlagKind
+  emoji?: string | null // Set for 'emoji' flags
+  created_at: string
+}
+
+export interface BookmarkedMessage {
+  message: Message
+  conversation_title?: string | null
+  bookmarked_at: string
+}
+
 export interface CreateMessageRequest {
   conversation_id?: string
   sender_type: string
@@ -1,3 +1,5 @@
+import type { OpenRouterRouting } from './provider'
+
 // Model (LLM) types
 export interface Model {
   id: string
@@ -7,6 +9,12 @@ export interface Model {
   description?: string
   is_starred: boolean // For quick access in chat interface
   is_deleted?: boolean // Soft delete flag
+  is_hidden?: boolean // Hidden from the model picker
+  sort_order?: number // Manual ordering position (ascending)
+  input_price?: number // USD per 1M input tokens
+  output_price?: number // USD per 1M output tokens
+  openrouter_routing?: OpenRouterRouting // Overrides the provider's routing defaults
+  default_preset_id?: string // Parameter preset used when no assistant preset applies
   created_at: string
   updated_at: string
 }
@@ -17,6 +25,8 @@ export interface CreateModelRequest {
   model_id: string
   description?: string
   is_starred?: boolean
+  input_price?: number // USD per 1M input tokens; auto-filled when omitted
+  output_price?: number // USD per 1M output tokens; auto-filled when omitted
 }
 
 // ==========================================================================
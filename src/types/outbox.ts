@@ -0,0 +1,9 @@
+// A message saved while offline, waiting for the network to start its reply
+export interface OutboxEntry {
+  message_id: string
+  conversation_id: string
+  content: string
+  provider: string
+  model: string
+  queued_at: string
+}
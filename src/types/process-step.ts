@@ -20,9 +20,14 @@ export interface CreateThinkingStepRequest {
 // Search decision - stores AI's reasoning about whether web search is needed
 export interface SearchDecision {
   id: string
+  message_id: string
   reasoning: string
   search_needed: boolean
   search_query?: string
+  // Other queries the model considered
+  alternative_queries: string[]
+  // Model's confidence in the decision, 0-1
+  confidence?: number
   search_result_id?: string // Link to resulting search if approved
   display_order: number
   created_at: string
@@ -32,6 +37,8 @@ export interface CreateSearchDecisionRequest {
   reasoning: string
   search_needed: boolean
   search_query?: string
+  alternative_queries?: string[]
+  confidence?: number
   search_result_id?: string
   display_order?: number
 }
@@ -61,6 +68,17 @@ export interface CreateToolCallRequest {
   completed_at?: string
 }
 
+// Result of applying a suggested diff (recorded as an "apply_patch" tool call)
+export interface PatchResult {
+  path: string
+  dry_run: boolean
+  hunks: number
+  additions: number
+  deletions: number
+  created: boolean
+  tool_call_id?: string
+}
+
 // Code execution - stores code interpreter results
 export interface CodeExecution {
   id: string
@@ -101,6 +119,71 @@ export interface CreateContentBlockRequest {
   display_order: number
 }
 
+// Transcription - transcript of an audio attachment on a user message
+export interface Transcription {
+  id: string
+  message_id: string
+  file_id?: string
+  content: string
+  engine: string // "whisper_cpp" | "provider"
+  status: string // "success" | "error"
+  error?: string
+  duration_ms?: number
+  display_order: number
+  created_at: string
+}
+
+// Translation - message translated on request (translate_message)
+export interface Translation {
+  id: string
+  message_id: string
+  target_language: string
+  content: string
+  model?: string // "provider/model" that produced it
+  display_order: number
+  created_at: string
+}
+
+// Verification - check of a reply against the pages it was written from
+export interface UnsupportedClaim {
+  claim: string
+  reason: string
+}
+
+export interface Verification {
+  id: string
+  message_id: string
+  verdict: 'supported' | 'partially_supported' | 'unsupported'
+  summary: string
+  unsupported_claims: UnsupportedClaim[]
+  model?: string // "provider/model" that ran the check
+  display_order: number
+  created_at: string
+}
+
+// Assistant switch - conversation handed to another assistant after this message
+// (switch_assistant)
+export interface AssistantSwitch {
+  id: string
+  message_id: string
+  from_assistant_id?: string | null
+  to_assistant_id: string
+  summary?: string | null // Briefing given to the new assistant
+  display_order: number
+  created_at: string
+}
+
+// Plain-language explanation of a thinking step or tool call (explain_step), cached per step
+export interface StepExplanation {
+  id: string
+  step_id: string
+  step_type: 'thinking' | 'tool_call'
+  message_id: string
+  content: string
+  model?: string // "provider/model" that wrote it
+  created_at: string
+}
+
 // Process step type enum
 export type StepType =
   | 'thinking'
@@ -108,6 +191,10 @@ export type StepType =
   | 'tool_call'
   | 'code_execution'
   | 'content_block'
+  | 'transcription'
+  | 'translation'
+  | 'verification'
+  | 'assistant_switch'
 
 // Unified process step type
 export type ProcessStep =
@@ -116,6 +203,10 @@ export type ProcessStep =
   | ({ type: 'tool_call' } & ToolCall)
   | ({ type: 'code_execution' } & CodeExecution)
   | ({ type: 'content_block' } & ContentBlock)
+  | ({ type: 'transcription' } & Transcription)
+  | ({ type: 'translation' } & Translation)
+  | ({ type: 'verification' } & Verification)
+  | ({ type: 'assistant_switch' } & AssistantSwitch)
 
 // Helper type guards for process steps
 export function isThinkingStep(step: ProcessStep): step is { type: 'thinking' } & ThinkingStep {
@@ -144,6 +235,22 @@ export function isContentBlock(
   return step.type === 'content_block'
 }
 
+export function isTranslation(step: ProcessStep): step is { type: 'translation' } & Translation {
+  return step.type === 'translation'
+}
+
+export function isVerification(
+  step: ProcessStep
+): step is { type: 'verification' } & Verification {
+  return step.type === 'verification'
+}
+
+export function isAssistantSwitch(
+  step: ProcessStep
+): step is { type: 'assistant_switch' } & AssistantSwitch {
+  return step.type === 'assistant_switch'
+}
+
 // Helper to get display_order from any ProcessStep
 export function getDisplayOrder(step: ProcessStep): number {
   return step.display_order
@@ -8,10 +8,23 @@ export interface Provider {
   api_style?: string // "responses" | "chat_completions" (only for custom_openai)
   description?: string
   is_enabled: boolean
+  openrouter_routing?: OpenRouterRouting // Routing defaults (OpenRouter only)
   created_at: string
   updated_at: string
 }
 
+// OpenRouter provider routing, sent as the request's `provider` object.
+// Set on a provider as defaults; a model's routing overrides them field by field.
+export interface OpenRouterRouting {
+  order?: string[] // Upstream provider slugs to try first, e.g. "anthropic"
+  allow_fallbacks?: boolean
+  data_collection?: 'allow' | 'deny'
+  max_price?: {
+    prompt?: number // USD per 1M tokens
+    completion?: number // USD per 1M tokens
+  }
+}
+
 export interface CreateProviderRequest {
   name: string
   provider_type: string
@@ -21,3 +34,21 @@ export interface CreateProviderRequest {
   description?: string
   is_enabled?: boolean
 }
+
+// Which providers a conversation may send messages to; a provider is allowed when any
+// rule matches (mirrors ProviderPolicy in the backend)
+export interface ProviderPolicy {
+  id: string // 'local_only' for the built-in policy
+  name: string
+  allow_local: boolean // Providers on localhost or the local network
+  allowed_provider_ids: string[]
+  allowed_provider_types: string[]
+  is_builtin: boolean
+}
+
+export interface ProviderPolicyInput {
+  name: string
+  allow_local: boolean
+  allowed_provider_ids: string[]
+  allowed_provider_types: string[]
+}
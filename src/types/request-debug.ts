@@ -0,0 +1,19 @@
+import type { ModelParameters } from './model'
+
+// Result of `get_last_request_debug`: what the provider received for the latest
+// generation in a conversation. API keys are never included and inline image data
+// is replaced with a placeholder.
+export interface RequestDebug {
+  conversation_id: string
+  provider_type: string
+  model_id: string
+  base_url?: string
+  api_style?: string
+  // Includes appended tool, skill and environment instructions
+  system_prompt?: string
+  model_params: ModelParameters
+  tools: string[]
+  // Provider-neutral messages, prompt last
+  messages: Record<string, unknown>[]
+  created_at: string
+}
@@ -7,6 +7,17 @@ export interface MessageSearchResult {
   created_at: string
 }
 
+// Result of `search_in_conversation`, in conversation order
+export interface ConversationMessageMatch {
+  message_id: string
+  // 0-based position of the message in the conversation
+  ordinal: number
+  sender_type: string
+  content_snippet: string
+  match_count: number
+  created_at: string
+}
+
 export interface ConversationSearchResult {
   id: string
   title: string
@@ -14,10 +14,34 @@ export interface SearchProvider {
 // Known search provider IDs
 export type SearchProviderId = 'duckduckgo' | 'yahoo' | 'baidu'
 
+// Result of `get_search_provider_health`, in the order searches try the providers
+export interface SearchProviderHealth {
+  id: SearchProviderId
+  name: string
+  // Share of recent searches that returned results; null until there are enough
+  success_rate: number | null
+  samples: number
+  last_success_at: string | null
+  last_error: string | null
+  last_error_at: string | null
+  last_latency_ms: number | null
+}
+
 // Web Fetch types
 export type WebFetchMode = 'local' | 'api'
 export type WebFetchLocalMethod = 'auto' | 'fetch' | 'headless'
 export type WebFetchApiProvider = 'jina'
 
+// Conversation title types
+export type TitleGenerationMode = 'auto' | 'local_only' | 'manual'
+
 // Logging types
 export type LogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error'
+
+// System prompt for conversations without an assistant or custom prompt
+// (get_default_system_prompt)
+export interface DefaultSystemPrompt {
+  prompt: string
+  is_custom: boolean // false when the built-in prompt is in effect
+  builtin: string
+}
@@ -0,0 +1,10 @@
+export type UpdateChannel = 'stable' | 'beta'
+
+// Result of `check_for_update` when a newer version exists
+export interface UpdateInfo {
+  version: string
+  current_version: string
+  channel: UpdateChannel
+  notes?: string
+  date?: string
+}
@@ -0,0 +1,38 @@
+// Estimated spend on one model within a month (costs in USD)
+export interface ModelSpend {
+  model_db_id: string | null
+  model_id: string | null
+  model_name: string | null
+  message_count: number
+  input_tokens: number
+  output_tokens: number
+  cost: number
+  unpriced_message_count: number
+}
+
+export interface ProviderSpend {
+  provider_id: string | null
+  provider_name: string | null
+  provider_type: string | null
+  message_count: number
+  input_tokens: number
+  output_tokens: number
+  cost: number
+  unpriced_message_count: number
+  models: ModelSpend[]
+}
+
+// Application-wide spend for a calendar month (see `get_spend_report`)
+export interface SpendReport {
+  // YYYY-MM
+  month: string
+  message_count: number
+  input_tokens: number
+  output_tokens: number
+  cost: number
+  // Replies whose model has no pricing, so their cost is unknown
+  unpriced_message_count: number
+  // Monthly soft cap, null when none is set
+  cap: number | null
+  by_provider: ProviderSpend[]
+}